@@ -83,6 +83,8 @@ pub enum DbProvider {
     Mysql,
     Mssql,
     Sqlite,
+    CockroachDb,
+    MariaDb,
 }
 
 impl From<Syntax> for DbProvider {
@@ -92,6 +94,8 @@ impl From<Syntax> for DbProvider {
             Syntax::Postgres => DbProvider::Postgres,
             Syntax::Mssql => DbProvider::Mssql,
             Syntax::Sqlite => DbProvider::Sqlite,
+            Syntax::CockroachDb => DbProvider::CockroachDb,
+            Syntax::MariaDb => DbProvider::MariaDb,
         }
     }
 }
@@ -103,6 +107,8 @@ impl From<DbProvider> for Syntax {
             DbProvider::Postgres => Syntax::Postgres,
             DbProvider::Mssql => Syntax::Mssql,
             DbProvider::Sqlite => Syntax::Sqlite,
+            DbProvider::CockroachDb => Syntax::CockroachDb,
+            DbProvider::MariaDb => Syntax::MariaDb,
         }
     }
 }
@@ -0,0 +1,71 @@
+use crate::Syntax;
+
+/// What a database backend can do, for code that has to work with any of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Statements can return the rows they changed (`RETURNING`, or `OUTPUT` on MSSQL)
+    pub supports_returning: bool,
+    /// Transactions can be partially rolled back (`SAVEPOINT`, or `SAVE TRANSACTION` on MSSQL)
+    pub supports_savepoints: bool,
+    /// The most params that can be sent with a single statement
+    pub max_bind_params: usize,
+}
+
+impl Capabilities {
+    /// The capabilities of the backends that speak this syntax
+    pub fn for_syntax(syntax: Syntax) -> Self {
+        let (supports_returning, max_bind_params) = match syntax {
+            Syntax::Postgres | Syntax::CockroachDb => (true, 65_535),
+            Syntax::Mysql => (false, 65_535),
+            // MariaDB 10.5+
+            Syntax::MariaDb => (true, 65_535),
+            // Sqlite 3.35+ for RETURNING, 3.32+ for the higher param limit
+            Syntax::Sqlite => (true, 32_766),
+            // 2100, less the two that sp_executesql takes for the statement itself
+            Syntax::Mssql => (true, 2_098),
+        };
+        Self {
+            supports_returning,
+            supports_savepoints: true,
+            max_bind_params,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_only_return_rows_where_the_backend_can() {
+        assert!(Capabilities::for_syntax(Syntax::Postgres).supports_returning);
+        assert!(Capabilities::for_syntax(Syntax::MariaDb).supports_returning);
+        assert!(!Capabilities::for_syntax(Syntax::Mysql).supports_returning);
+    }
+
+    #[test]
+    fn should_leave_mssql_room_for_the_statement_params() {
+        assert_eq!(
+            Capabilities::for_syntax(Syntax::Mssql).max_bind_params,
+            2_098
+        );
+        assert_eq!(
+            Capabilities::for_syntax(Syntax::Sqlite).max_bind_params,
+            32_766
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn should_downcast_a_client_to_its_backend() {
+        let client = crate::sqlite::connect("sqlite::memory:").await.unwrap();
+        let client: &dyn crate::Client = &client;
+        assert!(client.as_sqlite().is_some());
+        #[cfg(feature = "postgres")]
+        assert!(client.as_postgres().is_none());
+        assert_eq!(
+            client.capabilities(),
+            Capabilities::for_syntax(Syntax::Sqlite)
+        );
+    }
+}
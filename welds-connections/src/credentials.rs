@@ -0,0 +1,119 @@
+/// A username and password used when opening new connections.
+///
+/// Pass these to a client's `update_credentials` when a secret is rotated.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl Credentials {
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+}
+
+// Don't leak the password into logs
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &"********")
+            .finish()
+    }
+}
+
+/// Closes all the idle connections in a sqlx pool.
+/// The pool will open new connections (with its current options) as they are needed
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
+pub(crate) async fn close_idle<DB: sqlx::Database>(pool: &sqlx::Pool<DB>) {
+    for _ in 0..pool.num_idle() {
+        match pool.try_acquire() {
+            Some(conn) => {
+                if let Err(err) = conn.close().await {
+                    log::debug!("error closing idle connection: {}", err);
+                }
+            }
+            None => break,
+        }
+    }
+}
+
+/// When the credentials of a sqlx pool were last rotated. Connections opened before then are
+/// closed when they are returned to the pool, instead of living on until the pool's max lifetime
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Rotated(std::sync::Arc<std::sync::Mutex<Option<std::time::Instant>>>);
+
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
+impl Rotated {
+    pub(crate) fn now(&self) {
+        *self.0.lock().unwrap() = Some(std::time::Instant::now());
+    }
+
+    // a connection that has been open for `age` was opened after the last rotation
+    pub(crate) fn keeps(&self, age: std::time::Duration) -> bool {
+        match *self.0.lock().unwrap() {
+            Some(at) => age < at.elapsed(),
+            None => true,
+        }
+    }
+
+    /// Closes the connections opened before the last rotation as they are released
+    pub(crate) fn retire_on_release<DB: sqlx::Database>(
+        &self,
+        options: sqlx::pool::PoolOptions<DB>,
+    ) -> sqlx::pool::PoolOptions<DB> {
+        let rotated = self.clone();
+        options.after_release(move |_, meta| {
+            let keep = rotated.keeps(meta.age);
+            Box::pin(async move { Ok(keep) })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_not_log_the_password() {
+        let credentials = Credentials::new("app", "hunter2");
+        let logged = format!("{:?}", credentials);
+        assert!(logged.contains("app"));
+        assert!(!logged.contains("hunter2"));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn should_retire_connections_opened_before_a_rotation_on_release() {
+        use std::time::Duration;
+        let rotated = Rotated::default();
+        let options = rotated.retire_on_release(sqlx::sqlite::SqlitePoolOptions::new());
+        let pool = options.connect("sqlite::memory:").await.unwrap();
+
+        let before = pool.acquire().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        rotated.now();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let after = pool.acquire().await.unwrap();
+        assert_eq!(pool.size(), 2);
+
+        drop(before);
+        drop(after);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(pool.size(), 1);
+        assert_eq!(pool.num_idle(), 1);
+    }
+
+    #[test]
+    fn should_keep_every_connection_until_the_first_rotation() {
+        let rotated = Rotated::default();
+        assert!(rotated.keeps(std::time::Duration::from_secs(3600)));
+        rotated.now();
+        assert!(!rotated.keeps(std::time::Duration::from_secs(3600)));
+    }
+}
@@ -0,0 +1,611 @@
+use crate::errors::{Error, Result};
+use crate::instrument::Hooks;
+use crate::statement_cache::persistent;
+use crate::{AfterCommit, Client, ExecuteResult, Fetch, Namespace, Param, Row, Syntax};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "mysql")]
+use crate::mysql::MysqlParam;
+#[cfg(feature = "postgres")]
+use crate::postgres::PostgresParam;
+
+/// A unit of work spanning several clients (sharded databases, ...) that commits everywhere
+/// or nowhere, using two-phase commit.
+///
+/// Postgres uses `PREPARE TRANSACTION` / `COMMIT PREPARED` (the server needs
+/// `max_prepared_transactions` > 0). MySql uses XA transactions.
+///
+/// ```rust,ignore
+/// let dtx = DistributedTransaction::begin(&[&shard_a, &shard_b]).await?;
+/// dtx.client(0).execute("UPDATE accounts SET balance = balance - 10 WHERE id = $1", &[&from]).await?;
+/// dtx.client(1).execute("UPDATE accounts SET balance = balance + 10 WHERE id = $1", &[&to]).await?;
+/// dtx.commit().await?;
+/// ```
+///
+/// Dropping it without a commit or rollback closes its connections, which rolls back
+/// every branch that wasn't prepared. Branches that were prepared are left on the server.
+pub struct DistributedTransaction {
+    branches: Vec<Branch>,
+}
+
+impl DistributedTransaction {
+    /// Starts a branch of the transaction on each client.
+    /// Only Postgres and MySql clients (not their transactions) can take part, and a
+    /// `MockClient` with their syntax
+    pub async fn begin(clients: &[&dyn Client]) -> Result<DistributedTransaction> {
+        let id = next_id();
+        let mut dtx = DistributedTransaction {
+            branches: Vec::default(),
+        };
+        for (i, client) in clients.iter().enumerate() {
+            let xid = format!("{}_{}", id, i);
+            match Branch::begin(*client, xid).await {
+                Ok(branch) => dtx.branches.push(branch),
+                Err(err) => {
+                    dtx.rollback_all().await;
+                    return Err(err);
+                }
+            }
+        }
+        Ok(dtx)
+    }
+
+    /// The branch of the transaction on the client at this index (in the order given to `begin`).
+    ///
+    /// Panics if the index is out of range
+    pub fn client(&self, index: usize) -> &Branch {
+        &self.branches[index]
+    }
+
+    /// Prepares every branch, then commits them all.
+    ///
+    /// If a branch fails to prepare, every branch is rolled back and the error is returned.
+    /// If a branch fails to commit after all of them were prepared, the branches left are
+    /// reported with `Error::InDoubt` and need to be committed by hand.
+    pub async fn commit(mut self) -> Result<()> {
+        for i in 0..self.branches.len() {
+            if let Err(err) = self.branches[i].prepare().await {
+                self.rollback_all().await;
+                return Err(err);
+            }
+        }
+
+        let mut in_doubt = Vec::default();
+        for branch in &self.branches {
+            if branch.finish(true).await.is_err() {
+                in_doubt.push(branch.xid.clone());
+            }
+        }
+        match in_doubt.is_empty() {
+            true => Ok(()),
+            false => Err(Error::InDoubt(in_doubt)),
+        }
+    }
+
+    /// Rolls back every branch
+    pub async fn rollback(mut self) -> Result<()> {
+        match self.rollback_all().await {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    // rolls back what it can, returning the first error
+    async fn rollback_all(&mut self) -> Option<Error> {
+        let mut first_err = None;
+        for branch in &self.branches {
+            if let Err(err) = branch.finish(false).await {
+                first_err.get_or_insert(err);
+            }
+        }
+        first_err
+    }
+}
+
+// unique enough to not collide with other processes preparing transactions on the same server
+fn next_id() -> String {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let count = COUNTER.fetch_add(1, Ordering::SeqCst);
+    format!("welds_{}_{}_{}", std::process::id(), nanos, count)
+}
+
+pub(crate) enum BranchConn {
+    #[cfg(feature = "postgres")]
+    Postgres(sqlx::pool::PoolConnection<sqlx::Postgres>),
+    #[cfg(feature = "mysql")]
+    Mysql(sqlx::pool::PoolConnection<sqlx::MySql>),
+    #[cfg(feature = "mock")]
+    Mock(crate::mock::MockClient),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Active,
+    // MySql: the branch has ended (XA END) but isn't prepared.
+    // Postgres: PREPARE was sent, but the transaction might have been rolled back instead
+    Ended,
+    Prepared,
+    Finished,
+}
+
+/// One client's part of a `DistributedTransaction`. Run statements on it like any other client
+pub struct Branch {
+    xid: String,
+    syntax: Syntax,
+    hooks: Hooks,
+    statement_cache: bool,
+    conn: Mutex<Option<BranchConn>>,
+    state: Mutex<State>,
+    after_commit: Mutex<Vec<AfterCommit>>,
+}
+
+impl Branch {
+    async fn begin(client: &dyn Client, xid: String) -> Result<Branch> {
+        #[cfg(feature = "postgres")]
+        if let Some(client) = client
+            .as_postgres()
+            .filter(|c| c.syntax() == Syntax::Postgres)
+        {
+            let (conn, hooks, statement_cache) = client.branch_conn().await?;
+            let branch = Branch::new(xid, client.syntax(), hooks, statement_cache, conn);
+            branch.run_raw("BEGIN").await?;
+            return Ok(branch);
+        }
+        #[cfg(feature = "mysql")]
+        if let Some(client) = client.as_mysql() {
+            let (conn, hooks, statement_cache) = client.branch_conn().await?;
+            let branch = Branch::new(xid, client.syntax(), hooks, statement_cache, conn);
+            branch
+                .run_raw(&format!("XA START '{}'", branch.xid))
+                .await?;
+            return Ok(branch);
+        }
+        #[cfg(feature = "mock")]
+        if let Some(client) = client.as_mock() {
+            let start = match client.syntax() {
+                Syntax::Postgres => "BEGIN".to_owned(),
+                Syntax::Mysql | Syntax::MariaDb => format!("XA START '{}'", xid),
+                syntax => return Err(Error::TwoPhaseCommitUnsupported(syntax)),
+            };
+            let (conn, hooks, statement_cache) = client.branch_conn().await?;
+            let branch = Branch::new(xid, client.syntax(), hooks, statement_cache, conn);
+            branch.run_raw(&start).await?;
+            return Ok(branch);
+        }
+        Err(Error::TwoPhaseCommitUnsupported(client.syntax()))
+    }
+
+    fn new(
+        xid: String,
+        syntax: Syntax,
+        hooks: Hooks,
+        statement_cache: bool,
+        conn: BranchConn,
+    ) -> Self {
+        Self {
+            xid,
+            syntax,
+            hooks,
+            statement_cache,
+            conn: Mutex::new(Some(conn)),
+            state: Mutex::new(State::Active),
+            after_commit: Mutex::default(),
+        }
+    }
+
+    /// The id the branch is prepared under. Needed to resolve it by hand if it is left in doubt
+    pub fn xid(&self) -> &str {
+        &self.xid
+    }
+
+    async fn prepare(&self) -> Result<()> {
+        match self.syntax {
+            Syntax::Mysql | Syntax::MariaDb => {
+                self.run_raw(&format!("XA END '{}'", self.xid)).await?;
+                *self.state.lock().unwrap() = State::Ended;
+                self.run_raw(&format!("XA PREPARE '{}'", self.xid)).await?;
+            }
+            _ => {
+                self.run_raw(&format!("PREPARE TRANSACTION '{}'", self.xid))
+                    .await?;
+                // In a transaction that has failed, PREPARE rolls back instead of erroring
+                *self.state.lock().unwrap() = State::Ended;
+                let sql = "SELECT CAST(COUNT(*) AS BIGINT) FROM pg_prepared_xacts WHERE gid = $1";
+                let rows = self.fetch_rows(sql, &[&self.xid]).await?;
+                let found: i64 = match rows.first() {
+                    Some(row) => row.get_by_position(0)?,
+                    None => 0,
+                };
+                if found == 0 {
+                    *self.state.lock().unwrap() = State::Finished;
+                    return Err(Error::TransactionRolledBack(self.xid.clone()));
+                }
+            }
+        }
+        *self.state.lock().unwrap() = State::Prepared;
+        Ok(())
+    }
+
+    // commits or rolls back the branch, in whatever state it is in
+    async fn finish(&self, commit: bool) -> Result<()> {
+        let state = *self.state.lock().unwrap();
+        let mysql = matches!(self.syntax, Syntax::Mysql | Syntax::MariaDb);
+        let statements = match (state, mysql, commit) {
+            (State::Finished, _, _) => vec![],
+            (State::Active, false, _) => vec!["ROLLBACK".to_string()],
+            (State::Active, true, _) => vec![
+                format!("XA END '{}'", self.xid),
+                format!("XA ROLLBACK '{}'", self.xid),
+            ],
+            (State::Ended, true, _) => vec![format!("XA ROLLBACK '{}'", self.xid)],
+            (State::Ended, false, _) => vec![format!("ROLLBACK PREPARED '{}'", self.xid)],
+            (State::Prepared, false, true) => vec![format!("COMMIT PREPARED '{}'", self.xid)],
+            (State::Prepared, false, false) => vec![format!("ROLLBACK PREPARED '{}'", self.xid)],
+            (State::Prepared, true, true) => vec![format!("XA COMMIT '{}'", self.xid)],
+            (State::Prepared, true, false) => vec![format!("XA ROLLBACK '{}'", self.xid)],
+        };
+        for sql in statements {
+            self.run_raw(&sql).await?;
+        }
+        *self.state.lock().unwrap() = State::Finished;
+        let held = std::mem::take(&mut *self.after_commit.lock().unwrap());
+        if commit && state == State::Prepared {
+            for work in held {
+                work().await;
+            }
+        }
+        Ok(())
+    }
+
+    // transaction control statements, without params
+    async fn run_raw(&self, sql: &str) -> Result<()> {
+        self.hooks
+            .run(self.syntax, sql, &[], async {
+                let mut conn = self.take_conn();
+                let result = execute_raw(&mut conn, sql).await;
+                self.return_conn(conn);
+                result
+            })
+            .await?;
+        Ok(())
+    }
+
+    // Same as Transaction: the connection is taken out while a statement runs on it.
+    // It will panic if the branch is used by more than one statement at a time
+    fn take_conn(&self) -> BranchConn {
+        let mut conn = self.conn.lock().unwrap();
+        conn.take().expect("Branch connection was already taken")
+    }
+
+    fn return_conn(&self, conn: BranchConn) {
+        let mut slot = self.conn.lock().unwrap();
+        assert!(slot.is_none(), "Overriding existing branch connection");
+        *slot = Some(conn);
+    }
+}
+
+impl Drop for Branch {
+    fn drop(&mut self) {
+        // never hand a connection that is still in a transaction back to the pool
+        if *self.state.lock().unwrap() != State::Finished {
+            if let Some(conn) = self.conn.lock().unwrap().take() {
+                match conn {
+                    #[cfg(feature = "postgres")]
+                    BranchConn::Postgres(c) => drop(c.detach()),
+                    #[cfg(feature = "mysql")]
+                    BranchConn::Mysql(c) => drop(c.detach()),
+                    #[cfg(feature = "mock")]
+                    BranchConn::Mock(_) => {}
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Client for Branch {
+    async fn execute(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<ExecuteResult> {
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let persistent = persistent(self.statement_cache);
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                let mut conn = self.take_conn();
+                let result = execute_on(&mut conn, sql, params, persistent).await;
+                self.return_conn(conn);
+                result
+            })
+            .await
+    }
+
+    async fn fetch_rows(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<Vec<Row>> {
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let persistent = persistent(self.statement_cache);
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                let mut conn = self.take_conn();
+                let result = fetch_rows_on(&mut conn, sql, params, persistent).await;
+                self.return_conn(conn);
+                result
+            })
+            .await
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        fetches: &[Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        let mut datasets = Vec::default();
+        for (index, fetch) in fetches.iter().enumerate() {
+            let rows = self.fetch_rows(fetch.sql, fetch.params).await;
+            datasets.push(rows.map_err(|err| fetch.failed(index, err))?);
+        }
+        Ok(datasets)
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+
+    fn namespace(&self) -> Option<&Namespace> {
+        self.hooks.namespace()
+    }
+
+    fn in_transaction(&self) -> bool {
+        true
+    }
+
+    fn after_commit(&self, work: AfterCommit) -> Option<AfterCommit> {
+        self.after_commit.lock().unwrap().push(work);
+        None
+    }
+}
+
+// Sent as plain text: XA statements can't be prepared
+async fn execute_raw(conn: &mut BranchConn, sql: &str) -> Result<ExecuteResult> {
+    use sqlx::Executor;
+    let rows_affected = match conn {
+        #[cfg(feature = "postgres")]
+        BranchConn::Postgres(c) => c.execute(sql).await?.rows_affected(),
+        #[cfg(feature = "mysql")]
+        BranchConn::Mysql(c) => c.execute(sql).await?.rows_affected(),
+        #[cfg(feature = "mock")]
+        BranchConn::Mock(c) => c.execute_unhooked(sql, &[]).rows_affected,
+    };
+    Ok(ExecuteResult { rows_affected })
+}
+
+async fn execute_on(
+    conn: &mut BranchConn,
+    sql: &str,
+    params: &[&(dyn Param + Sync + Send)],
+    persistent: bool,
+) -> Result<ExecuteResult> {
+    let rows_affected = match conn {
+        #[cfg(feature = "postgres")]
+        BranchConn::Postgres(c) => {
+            let mut query = sqlx::query::<sqlx::Postgres>(sql).persistent(persistent);
+            for param in params {
+                query = PostgresParam::add_param(*param, query);
+            }
+            query.execute(&mut **c).await?.rows_affected()
+        }
+        #[cfg(feature = "mysql")]
+        BranchConn::Mysql(c) => {
+            let mut query = sqlx::query::<sqlx::MySql>(sql).persistent(persistent);
+            for param in params {
+                query = MysqlParam::add_param(*param, query);
+            }
+            query.execute(&mut **c).await?.rows_affected()
+        }
+        #[cfg(feature = "mock")]
+        BranchConn::Mock(c) => c.execute_unhooked(sql, params).rows_affected,
+    };
+    Ok(ExecuteResult { rows_affected })
+}
+
+async fn fetch_rows_on(
+    conn: &mut BranchConn,
+    sql: &str,
+    params: &[&(dyn Param + Sync + Send)],
+    persistent: bool,
+) -> Result<Vec<Row>> {
+    let rows = match conn {
+        #[cfg(feature = "postgres")]
+        BranchConn::Postgres(c) => {
+            let mut query = sqlx::query::<sqlx::Postgres>(sql).persistent(persistent);
+            for param in params {
+                query = PostgresParam::add_param(*param, query);
+            }
+            query
+                .fetch_all(&mut **c)
+                .await?
+                .drain(..)
+                .map(Row::from)
+                .collect()
+        }
+        #[cfg(feature = "mysql")]
+        BranchConn::Mysql(c) => {
+            let mut query = sqlx::query::<sqlx::MySql>(sql).persistent(persistent);
+            for param in params {
+                query = MysqlParam::add_param(*param, query);
+            }
+            query
+                .fetch_all(&mut **c)
+                .await?
+                .drain(..)
+                .map(Row::from)
+                .collect()
+        }
+        #[cfg(feature = "mock")]
+        BranchConn::Mock(c) => c.fetch_rows_unhooked(sql, params),
+    };
+    Ok(rows)
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::mock::{MockClient, MockRow};
+    use std::sync::Arc;
+
+    // a branch on a mock client, in the given state
+    async fn branch(syntax: Syntax, state: State) -> (MockClient, Branch) {
+        let client = MockClient::new(syntax);
+        let (conn, hooks, statement_cache) = client.branch_conn().await.unwrap();
+        let branch = Branch::new("x1".to_owned(), syntax, hooks, statement_cache, conn);
+        *branch.state.lock().unwrap() = state;
+        (client, branch)
+    }
+
+    fn sql(client: &MockClient) -> Vec<String> {
+        client.queries().into_iter().map(|q| q.sql).collect()
+    }
+
+    #[tokio::test]
+    async fn should_finish_a_branch_from_each_state() {
+        use State::*;
+        let cases: [(State, Syntax, bool, &[&str]); 14] = [
+            (Active, Syntax::Postgres, true, &["ROLLBACK"]),
+            (Active, Syntax::Postgres, false, &["ROLLBACK"]),
+            (
+                Active,
+                Syntax::Mysql,
+                true,
+                &["XA END 'x1'", "XA ROLLBACK 'x1'"],
+            ),
+            (
+                Active,
+                Syntax::Mysql,
+                false,
+                &["XA END 'x1'", "XA ROLLBACK 'x1'"],
+            ),
+            (Ended, Syntax::Postgres, true, &["ROLLBACK PREPARED 'x1'"]),
+            (Ended, Syntax::Postgres, false, &["ROLLBACK PREPARED 'x1'"]),
+            (Ended, Syntax::Mysql, true, &["XA ROLLBACK 'x1'"]),
+            (Ended, Syntax::MariaDb, false, &["XA ROLLBACK 'x1'"]),
+            (Prepared, Syntax::Postgres, true, &["COMMIT PREPARED 'x1'"]),
+            (
+                Prepared,
+                Syntax::Postgres,
+                false,
+                &["ROLLBACK PREPARED 'x1'"],
+            ),
+            (Prepared, Syntax::Mysql, true, &["XA COMMIT 'x1'"]),
+            (Prepared, Syntax::Mysql, false, &["XA ROLLBACK 'x1'"]),
+            (Finished, Syntax::Postgres, true, &[]),
+            (Finished, Syntax::Mysql, false, &[]),
+        ];
+        for (state, syntax, commit, expected) in cases {
+            let (client, branch) = branch(syntax, state).await;
+            branch.finish(commit).await.unwrap();
+            let case = format!("{:?} {:?} commit: {}", state, syntax, commit);
+            assert_eq!(sql(&client), expected, "{case}");
+            assert_eq!(*branch.state.lock().unwrap(), Finished, "{case}");
+
+            // a finished branch is left alone
+            branch.finish(commit).await.unwrap();
+            assert_eq!(sql(&client).len(), expected.len(), "{case}");
+        }
+    }
+
+    #[tokio::test]
+    async fn should_only_run_after_commit_work_of_a_committed_prepared_branch() {
+        use std::sync::atomic::AtomicUsize;
+        for (state, commit, runs) in [
+            (State::Prepared, true, 1),
+            (State::Prepared, false, 0),
+            (State::Ended, true, 0),
+            (State::Active, true, 0),
+        ] {
+            let (_client, branch) = branch(Syntax::Postgres, state).await;
+            let ran = Arc::new(AtomicUsize::new(0));
+            let counter = ran.clone();
+            let work: AfterCommit = Box::new(move || {
+                Box::pin(async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                })
+            });
+            assert!(branch.after_commit(work).is_none());
+            branch.finish(commit).await.unwrap();
+            assert_eq!(ran.load(Ordering::SeqCst), runs, "{:?} {}", state, commit);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_prepare_then_commit_every_branch() {
+        let pg = MockClient::new(Syntax::Postgres);
+        pg.enqueue_rows("pg_prepared_xacts", vec![MockRow::new().with("count", 1)]);
+        let my = MockClient::new(Syntax::Mysql);
+        let dtx = DistributedTransaction::begin(&[&pg, &my]).await.unwrap();
+        let (pg_xid, my_xid) = (
+            dtx.client(0).xid().to_owned(),
+            dtx.client(1).xid().to_owned(),
+        );
+        dtx.commit().await.unwrap();
+
+        assert_eq!(
+            sql(&pg),
+            [
+                "BEGIN".to_owned(),
+                format!("PREPARE TRANSACTION '{pg_xid}'"),
+                "SELECT CAST(COUNT(*) AS BIGINT) FROM pg_prepared_xacts WHERE gid = $1".to_owned(),
+                format!("COMMIT PREPARED '{pg_xid}'"),
+            ]
+        );
+        assert_eq!(
+            sql(&my),
+            [
+                format!("XA START '{my_xid}'"),
+                format!("XA END '{my_xid}'"),
+                format!("XA PREPARE '{my_xid}'"),
+                format!("XA COMMIT '{my_xid}'"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_roll_back_every_branch_when_postgres_rolls_back_a_prepare() {
+        let my = MockClient::new(Syntax::Mysql);
+        // no prepared transaction is found, postgres rolled it back
+        let pg = MockClient::new(Syntax::Postgres);
+        let dtx = DistributedTransaction::begin(&[&my, &pg]).await.unwrap();
+        let my_xid = dtx.client(0).xid().to_owned();
+        let err = dtx.commit().await.unwrap_err();
+        assert!(matches!(err, Error::TransactionRolledBack(_)), "{err}");
+
+        assert_eq!(sql(&my).last().unwrap(), &format!("XA ROLLBACK '{my_xid}'"));
+        // the rolled back branch has nothing left to finish
+        assert!(!sql(&pg).iter().any(|sql| sql.contains("ROLLBACK")));
+    }
+
+    #[tokio::test]
+    async fn should_refuse_clients_without_two_phase_commit() {
+        let client = MockClient::new(Syntax::Sqlite);
+        let err = DistributedTransaction::begin(&[&client])
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            Error::TwoPhaseCommitUnsupported(Syntax::Sqlite)
+        ));
+        assert!(client.queries().is_empty());
+    }
+}
@@ -10,15 +10,87 @@ pub enum Error {
     TiberiusConnPool(bb8_tiberius::Error),
     #[cfg(feature = "mssql")]
     Tiberius(tiberius::error::Error),
+    #[cfg(feature = "odbc")]
+    Odbc(odbc_api::Error),
+    /// A param of a type the ODBC client can't send
+    #[cfg(feature = "odbc")]
+    UnsupportedParam {
+        /// The position of the param, starting at 1
+        position: usize,
+        rust_type: &'static str,
+    },
+    /// The blocking thread running an ODBC call panicked or was cancelled
+    #[cfg(feature = "odbc")]
+    OdbcWorker(String),
+    ColumnDecode(String, String),
+    #[cfg(feature = "replay")]
+    Replay(String),
     Bb8(&'static str),
     InvalidDatabaseUrl,
+    CredentialsNotRotatable,
+    ClientClosed,
+    CloseTimeout(usize),
+    TwoPhaseCommitUnsupported(crate::Syntax),
+    InDoubt(Vec<String>),
+    TransactionRolledBack(String),
+    ProceduresUnsupported(crate::Syntax),
+    /// A schema or table prefix that isn't a plain identifier
+    InvalidNamespace(String),
+    /// Every tenant pool was in use, there was no room to open one for this tenant
+    TenantBudgetExceeded(String),
+    /// A param was bound with a type the database couldn't use for it.
+    ///
+    /// Only Postgres and MSSQL report it, they can describe the types a statement wants for
+    /// its params. MySQL and SQLite can't, and convert a param to the type of its column
+    /// (SQLite keeps it as it is), a failure there is returned as the database's own error
+    ParamType {
+        /// The position of the param, starting at 1
+        position: usize,
+        rust_type: &'static str,
+        /// The SQL type the param was sent as
+        sql_type: String,
+        /// The type the database wanted, from describing the statement
+        column_type: String,
+        /// The error from the database
+        message: String,
+    },
+    /// A column was read into a rust type that can't hold it
+    ColumnType {
+        column: String,
+        rust_type: &'static str,
+        column_type: String,
+    },
+    /// A statement of a `fetch_many` failed, the statements after it were not run
+    FetchMany {
+        /// The position of the statement in the batch
+        index: usize,
+        label: Option<String>,
+        source: Box<Error>,
+    },
     RowNowFound,
     ColumnNotFound(String),
     UnexpectedNoneInColumn(String),
     JsonParseError(String, String),
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::FetchMany { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    /// The error itself, or the error of the statement that failed for a `FetchMany`
+    pub fn root(&self) -> &Error {
+        match self {
+            Error::FetchMany { source, .. } => source.root(),
+            _ => self,
+        }
+    }
+}
 
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -29,8 +101,73 @@ impl Display for Error {
             Error::TiberiusConnPool(err) => err.to_string(),
             #[cfg(feature = "mssql")]
             Error::Tiberius(err) => err.to_string(),
+            #[cfg(feature = "odbc")]
+            Error::Odbc(err) => err.to_string(),
+            #[cfg(feature = "odbc")]
+            Error::UnsupportedParam {
+                position,
+                rust_type,
+            } => format!(
+                "param {position} is a `{rust_type}`, ODBC can only send numbers, bools, text and bytes"
+            ),
+            #[cfg(feature = "odbc")]
+            Error::OdbcWorker(err) => format!("ODBC worker thread failed: {err}"),
+            Error::ColumnDecode(col, msg) => format!("unable to decode column: {col}. {msg}"),
+            #[cfg(feature = "replay")]
+            Error::Replay(msg) => msg.clone(),
             Error::Bb8(err) => err.to_string(),
             Error::InvalidDatabaseUrl => "Invalid database URL".to_string(),
+            Error::CredentialsNotRotatable => {
+                "Credentials can only be updated on clients made from a connection string"
+                    .to_string()
+            }
+            Error::ClientClosed => "The client has been closed".to_string(),
+            Error::CloseTimeout(count) => {
+                format!("Gave up waiting for {count} queries to finish while closing the client")
+            }
+            Error::TwoPhaseCommitUnsupported(syntax) => {
+                format!("Two-phase commit is not supported for {syntax:?} clients")
+            }
+            Error::InDoubt(xids) => format!(
+                "Prepared transactions were left uncommitted, resolve them by hand: {}",
+                xids.join(", ")
+            ),
+            Error::TransactionRolledBack(xid) => {
+                format!("Transaction {xid} was rolled back instead of being prepared")
+            }
+            Error::ParamType {
+                position,
+                rust_type,
+                sql_type,
+                column_type,
+                message,
+            } => format!(
+                "param {position} is a `{rust_type}` (sent as {sql_type}), but the database expects {column_type}. {message}"
+            ),
+            Error::ColumnType {
+                column,
+                rust_type,
+                column_type,
+            } => format!("column {column} is {column_type}, it can't be read into a `{rust_type}`"),
+            Error::FetchMany {
+                index,
+                label: Some(label),
+                source,
+            } => format!("fetch_many statement {index} ({label}) failed: {source}"),
+            Error::FetchMany {
+                index,
+                label: None,
+                source,
+            } => format!("fetch_many statement {index} failed: {source}"),
+            Error::ProceduresUnsupported(syntax) => {
+                format!("Stored procedures are not supported for {syntax:?} clients")
+            }
+            Error::InvalidNamespace(name) => format!(
+                "{name:?} can't be used as a schema or table prefix, only letters, digits and underscores can"
+            ),
+            Error::TenantBudgetExceeded(tenant) => format!(
+                "No room to open a pool for tenant {tenant}, every tenant pool is in use"
+            ),
             Error::RowNowFound => "Row not found".to_string(),
             Error::ColumnNotFound(name) => format!("Column not found: {name}"),
             Error::UnexpectedNoneInColumn(name) => format!("Unexpected None in column: {name}"),
@@ -75,3 +212,13 @@ impl From<tiberius::error::Error> for Error {
         Error::Tiberius(inner)
     }
 }
+
+#[cfg(feature = "odbc")]
+impl From<odbc_api::Error> for Error {
+    fn from(inner: odbc_api::Error) -> Self {
+        Error::Odbc(inner)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn should_name_the_param_and_both_types_of_a_param_type_error() {
+    let err = Error::ParamType {
+        position: 2,
+        rust_type: "alloc::string::String",
+        sql_type: "TEXT".to_owned(),
+        column_type: "INT4".to_owned(),
+        message: "operator does not exist: integer = text".to_owned(),
+    };
+    assert_eq!(err.to_string(), "param 2 is a `alloc::string::String` (sent as TEXT), but the database expects INT4. operator does not exist: integer = text");
+}
+
+#[test]
+fn should_name_the_column_and_both_types_of_a_column_type_error() {
+    let err = Error::ColumnType {
+        column: "id".to_owned(),
+        rust_type: "i16",
+        column_type: "INT8".to_owned(),
+    };
+    assert_eq!(
+        err.to_string(),
+        "column id is INT8, it can't be read into a `i16`"
+    );
+}
@@ -0,0 +1,108 @@
+use crate::errors::Result;
+use crate::leaks::{CheckedOut, LeakDetector};
+use crate::priority::{Permit, PriorityQueue};
+use crate::shutdown::{InFlight, Shutdown};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// When a client lets a statement or transaction run: not once it is closed, in priority
+/// order when it has a queue, and watched for leaks when that is turned on
+#[derive(Clone, Default)]
+pub(crate) struct Admission {
+    shutdown: Arc<Shutdown>,
+    // set on a transaction. The whole transaction counts as in flight
+    transaction: Option<Arc<InFlight>>,
+    queue: Option<Arc<PriorityQueue>>,
+    // the slot in the queue a transaction holds until it is done
+    permit: Option<Arc<Permit>>,
+    leaks: Option<Arc<LeakDetector>>,
+    // the connection a transaction has checked out, while leak detection is on
+    checked_out: Option<Arc<CheckedOut>>,
+}
+
+impl Admission {
+    pub(crate) fn priority_queue(&mut self, slots: usize, starvation_limit: Duration) {
+        self.queue = Some(Arc::new(PriorityQueue::new(slots, starvation_limit)));
+    }
+
+    /// Backtraces are costly to capture, so this is only turned on in debug builds
+    pub(crate) fn detect_leaks(&mut self, threshold: Duration) {
+        if cfg!(debug_assertions) {
+            self.leaks = Some(Arc::new(LeakDetector::new(threshold)));
+        }
+    }
+
+    /// The admission of a new transaction, which holds its slot until it is done.
+    /// Fails if the client has been closed
+    pub(crate) async fn for_transaction(&self) -> Result<Admission> {
+        let in_flight = self.shutdown.enter()?;
+        let permit = self.queue_permit().await;
+        let mut admission = self.clone();
+        admission.transaction = Some(Arc::new(in_flight));
+        admission.permit = permit.map(Arc::new);
+        admission.checked_out = self
+            .leaks
+            .as_ref()
+            .map(|leaks| Arc::new(leaks.check_out("transaction")));
+        Ok(admission)
+    }
+
+    /// Waits for this statement's turn in the priority queue, and watches the connection it
+    /// checks out for leaks. Statements in a transaction don't wait and aren't watched,
+    /// the transaction already has its slot and its connection
+    pub(crate) async fn admit(&self) -> Admitted {
+        let permit = self.queue_permit().await;
+        let checked_out = match (&self.leaks, &self.transaction) {
+            (Some(leaks), None) => Some(leaks.check_out("statement")),
+            _ => None,
+        };
+        Admitted {
+            _permit: permit,
+            _checked_out: checked_out,
+        }
+    }
+
+    async fn queue_permit(&self) -> Option<Permit> {
+        match (&self.queue, &self.transaction) {
+            (Some(queue), None) => Some(queue.admit().await),
+            _ => None,
+        }
+    }
+
+    /// Counts a statement as in flight until the returned value is dropped.
+    /// Fails if the client has been closed
+    pub(crate) fn track(&self) -> Result<Option<InFlight>> {
+        match self.transaction {
+            Some(_) => Ok(None),
+            None => Ok(Some(self.shutdown.enter()?)),
+        }
+    }
+
+    /// Refuses new statements and waits (up to the deadline) for the ones running to finish
+    pub(crate) async fn close(&self, deadline: Instant) -> Result<()> {
+        self.shutdown.close(deadline).await
+    }
+}
+
+/// A statement let through by its client. Hold on to it until the connection is given back
+pub(crate) struct Admitted {
+    _permit: Option<Permit>,
+    _checked_out: Option<CheckedOut>,
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_watch_the_connection_of_a_statement_for_leaks() {
+        let mut admission = Admission::default();
+        admission.detect_leaks(Duration::from_secs(60));
+        assert!(admission.admit().await._checked_out.is_some());
+
+        // the transaction's own connection is watched, not each of its statements
+        let transaction = admission.for_transaction().await.unwrap();
+        assert!(transaction.checked_out.is_some());
+        assert!(transaction.admit().await._checked_out.is_none());
+    }
+}
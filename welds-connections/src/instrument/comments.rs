@@ -0,0 +1,131 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+type Tags = BTreeMap<String, String>;
+
+thread_local! {
+    // The context of the task that is currently being polled
+    static CURRENT: RefCell<Option<Tags>> = const { RefCell::new(None) };
+}
+
+/// Runs the future with key/values that are added to the SQL comment of every statement it runs.
+/// Only used by clients that have SQL comments turned on (`with_sql_comments`).
+///
+/// The context belongs to the future (task), not the thread. It is inherited by nested
+/// contexts, but not by tasks that are spawned from inside it.
+///
+/// ```rust,ignore
+/// let users = with_query_context([("route", "/users")], async {
+///     set_query_context("controller", "users");
+///     User::all().run(&client).await
+/// })
+/// .await?;
+/// // SELECT ... /*application='api',controller='users',route='%2Fusers'*/
+/// ```
+pub fn with_query_context<F, K, V>(
+    tags: impl IntoIterator<Item = (K, V)>,
+    future: F,
+) -> WithQueryContext<F>
+where
+    F: Future,
+    K: Into<String>,
+    V: Into<String>,
+{
+    let mut context = CURRENT.with(|c| c.borrow().clone()).unwrap_or_default();
+    context.extend(tags.into_iter().map(|(k, v)| (k.into(), v.into())));
+    WithQueryContext {
+        inner: Box::pin(future),
+        context,
+    }
+}
+
+/// Sets a key/value on the context of the current `with_query_context`.
+/// Does nothing when called outside of one.
+pub fn set_query_context(key: impl Into<String>, value: impl Into<String>) {
+    CURRENT.with(|c| {
+        if let Some(context) = c.borrow_mut().as_mut() {
+            context.insert(key.into(), value.into());
+        }
+    });
+}
+
+/// A future that is run with a query context. See `with_query_context`
+pub struct WithQueryContext<F> {
+    inner: Pin<Box<F>>,
+    context: Tags,
+}
+
+impl<F: Future> Future for WithQueryContext<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let context = std::mem::take(&mut this.context);
+        let guard = Restore {
+            outer: CURRENT.with(|c| c.replace(Some(context))),
+            context: &mut this.context,
+        };
+        let poll = this.inner.as_mut().poll(cx);
+        drop(guard);
+        poll
+    }
+}
+
+// Puts the outer context back (even on panic) and saves any changes made while polling
+struct Restore<'a> {
+    outer: Option<Tags>,
+    context: &'a mut Tags,
+}
+
+impl Drop for Restore<'_> {
+    fn drop(&mut self) {
+        let outer = self.outer.take();
+        *self.context = CURRENT.with(|c| c.replace(outer)).unwrap_or_default();
+    }
+}
+
+/// Appends a sqlcommenter comment with the client's tags and the current context.
+/// The current context wins if both have the same key.
+pub(crate) fn annotate<'s>(sql: &'s str, tags: &Tags) -> Cow<'s, str> {
+    let mut all = tags.clone();
+    CURRENT.with(|c| {
+        if let Some(context) = c.borrow().as_ref() {
+            all.extend(context.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+    });
+    // leave statements that already have a comment alone
+    if all.is_empty() || sql.trim_end().ends_with("*/") {
+        return Cow::Borrowed(sql);
+    }
+
+    let comment: Vec<String> = all
+        .iter()
+        .map(|(k, v)| format!("{}='{}'", encode(k), encode(v).replace('\'', "\\'")))
+        .collect();
+    let comment = format!("/*{}*/", comment.join(","));
+
+    // the comment goes before a trailing semicolon
+    let body = sql.trim_end();
+    match body.strip_suffix(';') {
+        Some(body) => Cow::Owned(format!("{} {};", body, comment)),
+        None => Cow::Owned(format!("{} {}", body, comment)),
+    }
+}
+
+// url (percent) encoding, as the sqlcommenter spec asks for
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
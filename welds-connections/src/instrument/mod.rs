@@ -0,0 +1,498 @@
+use crate::errors::{Error, Result};
+use crate::value::{self, Value};
+use crate::{ExecuteResult, Namespace, Param, Row, Syntax};
+use std::borrow::Cow;
+use std::future::Future;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+mod admission;
+mod comments;
+mod rewrite;
+use admission::{Admission, Admitted};
+pub use comments::{set_query_context, with_query_context, WithQueryContext};
+use rewrite::Rewrite;
+
+#[cfg(feature = "tracing")]
+mod spans;
+
+/// Callbacks that are run around every statement a client sends to the database.
+///
+/// Add a hook to every client with `add_query_hook`,
+/// or to a single client with its `with_query_hook`.
+/// Hooks added to a client are also run for the transactions it starts.
+///
+/// ```rust,ignore
+/// struct Audit;
+///
+/// impl QueryHook for Audit {
+///     fn after_query(&self, sql: &str, duration: Duration, outcome: QueryOutcome<'_>) {
+///         log::info!("{} ({:?}) {:?}", sql, duration, outcome);
+///     }
+/// }
+///
+/// welds_connections::instrument::add_query_hook(Arc::new(Audit));
+/// ```
+pub trait QueryHook: Send + Sync {
+    /// Called right before the statement is sent to the database
+    fn before_query(&self, _sql: &str, _params: &[&(dyn Param + Sync + Send)]) {}
+
+    /// Called once the database has finished with the statement
+    fn after_query(&self, _sql: &str, _duration: Duration, _outcome: QueryOutcome<'_>) {}
+
+    /// Called instead of `before_query` for a statement of a `fetch_many` that was given a label
+    fn before_labeled_query(&self, _label: &str, sql: &str, params: &[&(dyn Param + Sync + Send)]) {
+        self.before_query(sql, params)
+    }
+
+    /// Called instead of `after_query` for a statement of a `fetch_many` that was given a label
+    fn after_labeled_query(
+        &self,
+        _label: &str,
+        sql: &str,
+        duration: Duration,
+        outcome: QueryOutcome<'_>,
+    ) {
+        self.after_query(sql, duration, outcome)
+    }
+}
+
+/// What happened when a statement was run
+#[derive(Debug, Clone, Copy)]
+pub enum QueryOutcome<'a> {
+    /// The statement returned this many rows
+    Rows(usize),
+    /// The statement changed this many rows
+    RowsAffected(u64),
+    Error(&'a Error),
+}
+
+static GLOBAL_HOOKS: RwLock<Vec<Arc<dyn QueryHook>>> = RwLock::new(Vec::new());
+
+/// Adds a hook that is run for every client and transaction in the process
+pub fn add_query_hook(hook: Arc<dyn QueryHook>) {
+    GLOBAL_HOOKS.write().unwrap().push(hook);
+}
+
+/// Removes all the hooks added with `add_query_hook`
+pub fn clear_query_hooks() {
+    GLOBAL_HOOKS.write().unwrap().clear();
+}
+
+/// The `log` target every statement is written to (at DEBUG)
+pub const LOG_TARGET: &str = "welds::query";
+
+/// How parameter values are written to the query log.
+///
+/// Params often hold personal data, so they are left out unless you opt in.
+/// Params of a type that isn't a number, bool, text or bytes (a date, a uuid, ...) are logged
+/// by the name of their type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParamLogging {
+    /// Only the number of params is logged
+    #[default]
+    Omit,
+    /// Each param is logged as a keyed 64 bit hash of its value (SipHash, with a random key
+    /// for each process). Lets you see when two statements used the same value without
+    /// seeing the value.
+    ///
+    /// This is pseudonymization, not anonymization: the hashes only match within one run of
+    /// the process, and a value that is easy to guess (an id, a yes/no) can still be found
+    /// by anyone who can hash with the same key.
+    Hash,
+    /// Params are logged as they are. Only use this where the logs can hold PII
+    Values,
+}
+
+static PARAM_LOGGING: AtomicU8 = AtomicU8::new(0);
+
+/// Sets how params are written to the query log for every client in the process
+pub fn set_param_logging(policy: ParamLogging) {
+    let value = match policy {
+        ParamLogging::Omit => 0,
+        ParamLogging::Hash => 1,
+        ParamLogging::Values => 2,
+    };
+    PARAM_LOGGING.store(value, Ordering::Relaxed);
+}
+
+/// The current policy for writing params to the query log
+pub fn param_logging() -> ParamLogging {
+    match PARAM_LOGGING.load(Ordering::Relaxed) {
+        1 => ParamLogging::Hash,
+        2 => ParamLogging::Values,
+        _ => ParamLogging::Omit,
+    }
+}
+
+/// The builder methods for the hooks of a client, the same on every client. Written inside
+/// the `impl` of a client that keeps its `Hooks` in a `hooks` field. `pooled` adds the ones
+/// for clients that hand out connections from a pool
+macro_rules! client_builders {
+    () => {
+        /// Adds a hook that is run around every statement this client (and its transactions) runs
+        pub fn with_query_hook(
+            mut self,
+            hook: std::sync::Arc<dyn $crate::instrument::QueryHook>,
+        ) -> Self {
+            self.hooks.push(hook);
+            self
+        }
+
+        /// Logs (at WARN) every statement this client (and its transactions) runs that takes longer
+        /// than the threshold
+        pub fn log_slow_queries(mut self, threshold: std::time::Duration) -> Self {
+            self.hooks.log_slow(threshold);
+            self
+        }
+
+        /// Appends a sqlcommenter comment (`/*application='api'*/`) to every statement this
+        /// client (and its transactions) runs, with these tags and any set with `instrument::with_query_context`
+        pub fn with_sql_comments(mut self, tags: &[(&str, &str)]) -> Self {
+            self.hooks.add_comments(tags);
+            self
+        }
+
+        /// Moves the tables of the models this client (and its transactions) reads and writes
+        /// into the schema (`FROM tenant_42.orders`). See `Namespace`.
+        /// Fails with `InvalidNamespace` unless the schema is a plain identifier
+        pub fn with_schema(mut self, schema: &str) -> $crate::errors::Result<Self> {
+            self.hooks
+                .set_namespace($crate::Namespace::Schema(schema.to_owned()))?;
+            Ok(self)
+        }
+
+        /// Prefixes the tables of the models this client (and its transactions) reads and writes
+        /// (`FROM tenant_42_orders`). See `Namespace`.
+        /// Fails with `InvalidNamespace` unless the prefix is a plain identifier
+        pub fn with_table_prefix(mut self, prefix: &str) -> $crate::errors::Result<Self> {
+            self.hooks
+                .set_namespace($crate::Namespace::TablePrefix(prefix.to_owned()))?;
+            Ok(self)
+        }
+    };
+    (pooled) => {
+        $crate::instrument::client_builders!();
+
+        /// Queues statements and transactions for a connection by their priority once `slots` of
+        /// them are running, so interactive work goes ahead of background work (`priority::with_priority`).
+        /// Set `slots` to the size of the pool. Work that has waited longer than `starvation_limit`
+        /// goes to the front of the queue, whatever its priority.
+        pub fn with_priority_queue(
+            mut self,
+            slots: usize,
+            starvation_limit: std::time::Duration,
+        ) -> Self {
+            self.hooks.priority_queue(slots, starvation_limit);
+            self
+        }
+
+        /// Logs a warning, with a backtrace of where it was started, for every transaction or
+        /// statement that holds its connection longer than `threshold`. Only turned on in debug builds
+        pub fn with_leak_detection(mut self, threshold: std::time::Duration) -> Self {
+            self.hooks.detect_leaks(threshold);
+            self
+        }
+    };
+}
+pub(crate) use client_builders;
+
+/// Everything a client does around the statements it runs, in three parts:
+/// the hooks that observe them, how their SQL is rewritten, and when they are let through.
+/// Along with the namespace the tables of its statements are written in
+#[derive(Clone, Default)]
+pub(crate) struct Hooks {
+    observers: Observers,
+    rewrite: Rewrite,
+    admission: Admission,
+    namespace: Option<Namespace>,
+}
+
+impl Hooks {
+    pub(crate) fn push(&mut self, hook: Arc<dyn QueryHook>) {
+        self.observers.local.push(hook);
+    }
+
+    pub(crate) fn log_slow(&mut self, threshold: Duration) {
+        self.observers.slow_threshold = Some(threshold);
+    }
+
+    pub(crate) fn add_comments(&mut self, tags: &[(&str, &str)]) {
+        self.rewrite.add_comments(tags);
+    }
+
+    pub(crate) fn set_namespace(&mut self, namespace: Namespace) -> Result<()> {
+        namespace.validate()?;
+        self.namespace = Some(namespace);
+        Ok(())
+    }
+
+    pub(crate) fn namespace(&self) -> Option<&Namespace> {
+        self.namespace.as_ref()
+    }
+
+    /// The SQL that should be sent to the database: with its sqlcommenter comment if turned on
+    pub(crate) fn annotate<'s>(&self, sql: &'s str) -> Cow<'s, str> {
+        self.rewrite.annotate(sql)
+    }
+
+    pub(crate) fn priority_queue(&mut self, slots: usize, starvation_limit: Duration) {
+        self.admission.priority_queue(slots, starvation_limit);
+    }
+
+    /// Warns about transactions and statements that hold their connection longer than the threshold.
+    /// Only turned on in debug builds
+    pub(crate) fn detect_leaks(&mut self, threshold: Duration) {
+        self.admission.detect_leaks(threshold);
+    }
+
+    /// The hooks for a new transaction. Fails if the client has been closed.
+    /// Waits for a slot if the client has a priority queue, the transaction holds it until it is done
+    pub(crate) async fn for_transaction(&self) -> Result<Hooks> {
+        Ok(Hooks {
+            observers: self.observers.clone(),
+            rewrite: self.rewrite.clone(),
+            admission: self.admission.for_transaction().await?,
+            namespace: self.namespace.clone(),
+        })
+    }
+
+    /// Waits for this statement's turn in the client's priority queue, and watches its connection
+    /// for leaks when that is turned on. Hold on to it until the connection is given back to the pool.
+    /// Statements in a transaction don't wait, the transaction already has its slot
+    pub(crate) async fn admit(&self) -> Admitted {
+        self.admission.admit().await
+    }
+
+    /// Counts a statement as in flight until the returned value is dropped.
+    /// Fails if the client has been closed
+    #[cfg(feature = "odbc")]
+    pub(crate) fn track(&self) -> Result<Option<crate::shutdown::InFlight>> {
+        self.admission.track()
+    }
+
+    /// Refuses new statements and waits (up to the deadline) for the ones running to finish
+    pub(crate) async fn close(&self, deadline: Instant) -> Result<()> {
+        self.admission.close(deadline).await
+    }
+
+    /// True if there are any hooks to run, or the SQL is changed before it is sent
+    #[cfg(feature = "odbc")]
+    pub(crate) fn is_active(&self) -> bool {
+        self.observers.is_active() || self.rewrite.is_active()
+    }
+
+    /// Runs the work for a statement, calling the hooks before and after it
+    pub(crate) async fn run<T, F>(
+        &self,
+        syntax: Syntax,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+        work: F,
+    ) -> Result<T>
+    where
+        T: Observe,
+        F: Future<Output = Result<T>>,
+    {
+        self.run_labeled(None, syntax, sql, params, work).await
+    }
+
+    /// `run`, for a statement that may have been given a label (a `Fetch` of a `fetch_many`).
+    /// Fails if the client has been closed
+    pub(crate) async fn run_labeled<T, F>(
+        &self,
+        label: Option<&str>,
+        syntax: Syntax,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+        work: F,
+    ) -> Result<T>
+    where
+        T: Observe,
+        F: Future<Output = Result<T>>,
+    {
+        let _in_flight = self.admission.track()?;
+        self.observers
+            .observe(label, syntax, sql, params, work)
+            .await
+    }
+}
+
+impl std::fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hooks")
+            .field("local", &self.observers.local.len())
+            .field("slow_threshold", &self.observers.slow_threshold)
+            .field("rewrite", &self.rewrite)
+            .field("namespace", &self.namespace)
+            .finish()
+    }
+}
+
+/// The hooks of a single client, along with the global hooks, the query log, and the slow
+/// query log
+#[derive(Clone, Default)]
+struct Observers {
+    local: Vec<Arc<dyn QueryHook>>,
+    slow_threshold: Option<Duration>,
+}
+
+impl Observers {
+    #[cfg(feature = "odbc")]
+    fn is_active(&self) -> bool {
+        !self.local.is_empty()
+            || self.slow_threshold.is_some()
+            || log::log_enabled!(target: LOG_TARGET, log::Level::Debug)
+            || !GLOBAL_HOOKS.read().unwrap().is_empty()
+    }
+
+    // runs the work, calling the hooks and writing the logs around it
+    async fn observe<T, F>(
+        &self,
+        label: Option<&str>,
+        syntax: Syntax,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+        work: F,
+    ) -> Result<T>
+    where
+        T: Observe,
+        F: Future<Output = Result<T>>,
+    {
+        let mut hooks = GLOBAL_HOOKS.read().unwrap().clone();
+        hooks.extend(self.local.iter().cloned());
+        let log_query = log::log_enabled!(target: LOG_TARGET, log::Level::Debug);
+
+        #[cfg(feature = "tracing")]
+        let span = spans::query_span(syntax, sql, label);
+        #[cfg(feature = "tracing")]
+        let work = tracing::Instrument::instrument(work, span.clone());
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = syntax;
+            if hooks.is_empty() && self.slow_threshold.is_none() && !log_query {
+                return work.await;
+            }
+        }
+
+        for hook in &hooks {
+            match label {
+                Some(label) => hook.before_labeled_query(label, sql, params),
+                None => hook.before_query(sql, params),
+            }
+        }
+        let start = Instant::now();
+        let result = work.await;
+        let duration = start.elapsed();
+        let outcome = match &result {
+            Ok(value) => value.outcome(),
+            Err(err) => QueryOutcome::Error(err),
+        };
+        #[cfg(feature = "tracing")]
+        spans::record(&span, duration, outcome);
+        for hook in &hooks {
+            match label {
+                Some(label) => hook.after_labeled_query(label, sql, duration, outcome),
+                None => hook.after_query(sql, duration, outcome),
+            }
+        }
+        // labeled statements are logged as `[label] SQL`
+        let tag = label.map(|l| format!("[{}] ", l)).unwrap_or_default();
+        if log_query {
+            log::debug!(
+                target: LOG_TARGET,
+                "{}{} params: {} ({:?}, {})",
+                tag,
+                sql,
+                format_params(params, param_logging()),
+                duration,
+                outcome
+            );
+        }
+        if self.is_slow(duration) {
+            log::warn!(
+                "Slow query ({:?}, {} params): {}{}",
+                duration,
+                params.len(),
+                tag,
+                sql
+            );
+        }
+        result
+    }
+
+    // took longer than the slow query threshold, if there is one
+    fn is_slow(&self, duration: Duration) -> bool {
+        self.slow_threshold
+            .map(|threshold| duration > threshold)
+            .unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for QueryOutcome<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryOutcome::Rows(count) => write!(f, "{} rows", count),
+            QueryOutcome::RowsAffected(count) => write!(f, "{} rows affected", count),
+            QueryOutcome::Error(err) => write!(f, "error: {}", err),
+        }
+    }
+}
+
+// The params as they should appear in the query log
+fn format_params(params: &[&(dyn Param + Sync + Send)], policy: ParamLogging) -> String {
+    let rendered: Vec<String> = match policy {
+        ParamLogging::Omit => return format!("[{} omitted]", params.len()),
+        ParamLogging::Hash => params
+            .iter()
+            .map(|p| hashed(&value::of_param(*p)))
+            .collect(),
+        ParamLogging::Values => params
+            .iter()
+            .map(|p| value::of_param(*p).to_string())
+            .collect(),
+    };
+    format!("[{}]", rendered.join(", "))
+}
+
+// A keyed hash of the value. The key is random for each process, the hashes can't be
+// matched against ones made anywhere else
+fn hashed(value: &Value) -> String {
+    static KEY: OnceLock<RandomState> = OnceLock::new();
+    match value {
+        // only the type is known, there is no value to hide
+        Value::Opaque(_) => value.to_string(),
+        _ => {
+            let key = KEY.get_or_init(RandomState::new);
+            format!("#{:016x}", key.hash_one(format!("{:?}", value)))
+        }
+    }
+}
+
+/// The results of a statement that can be reported to a hook
+pub(crate) trait Observe {
+    fn outcome(&self) -> QueryOutcome<'_>;
+}
+
+impl Observe for ExecuteResult {
+    fn outcome(&self) -> QueryOutcome<'_> {
+        QueryOutcome::RowsAffected(self.rows_affected)
+    }
+}
+
+impl Observe for Vec<Row> {
+    fn outcome(&self) -> QueryOutcome<'_> {
+        QueryOutcome::Rows(self.len())
+    }
+}
+
+impl Observe for Vec<Vec<Row>> {
+    fn outcome(&self) -> QueryOutcome<'_> {
+        QueryOutcome::Rows(self.iter().map(|set| set.len()).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests;
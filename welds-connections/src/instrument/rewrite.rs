@@ -0,0 +1,34 @@
+use super::comments;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// How a client changes the SQL before it is sent: its sqlcommenter comment
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Rewrite {
+    comment_tags: Option<Arc<BTreeMap<String, String>>>,
+}
+
+impl Rewrite {
+    pub(crate) fn add_comments(&mut self, tags: &[(&str, &str)]) {
+        let tags = tags
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        self.comment_tags = Some(Arc::new(tags));
+    }
+
+    /// The SQL that should be sent to the database
+    pub(crate) fn annotate<'s>(&self, sql: &'s str) -> Cow<'s, str> {
+        match &self.comment_tags {
+            Some(tags) => comments::annotate(sql, tags),
+            None => Cow::Borrowed(sql),
+        }
+    }
+
+    /// True if the SQL is changed before it is sent
+    #[cfg(feature = "odbc")]
+    pub(crate) fn is_active(&self) -> bool {
+        self.comment_tags.is_some()
+    }
+}
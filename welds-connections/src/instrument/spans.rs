@@ -0,0 +1,80 @@
+use super::QueryOutcome;
+use crate::Syntax;
+use std::time::Duration;
+use tracing::field::Empty;
+use tracing::Span;
+
+/// A span for a single statement.
+/// Field names follow the OpenTelemetry semantic conventions for database clients
+pub(super) fn query_span(syntax: Syntax, sql: &str, label: Option<&str>) -> Span {
+    tracing::info_span!(
+        "welds.query",
+        otel.name = operation(sql),
+        otel.kind = "client",
+        otel.status_code = Empty,
+        db.system = db_system(syntax),
+        db.statement = sql,
+        db.query.summary = label,
+        db.response.returned_rows = Empty,
+        db.rows_affected = Empty,
+        db.duration_ms = Empty,
+        error.message = Empty,
+    )
+}
+
+/// Fills in the fields that are only known once the statement is finished
+pub(super) fn record(span: &Span, duration: Duration, outcome: QueryOutcome<'_>) {
+    span.record("db.duration_ms", duration.as_secs_f64() * 1000.0);
+    match outcome {
+        QueryOutcome::Rows(count) => {
+            span.record("db.response.returned_rows", count as u64);
+        }
+        QueryOutcome::RowsAffected(count) => {
+            span.record("db.rows_affected", count);
+        }
+        QueryOutcome::Error(err) => {
+            span.record("otel.status_code", "ERROR");
+            span.record("error.message", tracing::field::display(err));
+        }
+    }
+}
+
+// The well-known `db.system` names
+fn db_system(syntax: Syntax) -> &'static str {
+    match syntax {
+        Syntax::Mysql => "mysql",
+        Syntax::MariaDb => "mariadb",
+        Syntax::Postgres => "postgresql",
+        Syntax::CockroachDb => "cockroachdb",
+        Syntax::Sqlite => "sqlite",
+        Syntax::Mssql => "mssql",
+    }
+}
+
+// The first keyword of the statement (SELECT, INSERT, ...)
+fn operation(sql: &str) -> &str {
+    sql.split_whitespace().next().unwrap_or("query")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_name_the_span_by_the_first_keyword() {
+        assert_eq!(operation("SELECT * FROM users"), "SELECT");
+        assert_eq!(
+            operation("\n  insert into users (id) VALUES ($1)"),
+            "insert"
+        );
+        assert_eq!(operation(""), "query");
+    }
+
+    #[test]
+    fn should_use_the_opentelemetry_db_system_names() {
+        assert_eq!(db_system(Syntax::Postgres), "postgresql");
+        assert_eq!(db_system(Syntax::CockroachDb), "cockroachdb");
+        assert_eq!(db_system(Syntax::MariaDb), "mariadb");
+        assert_eq!(db_system(Syntax::Mssql), "mssql");
+    }
+}
@@ -0,0 +1,245 @@
+use super::*;
+
+#[test]
+fn should_only_count_omitted_params() {
+    let params: [&(dyn Param + Sync + Send); 2] = [&1_i32, &"secret".to_owned()];
+    assert_eq!(format_params(&params, ParamLogging::Omit), "[2 omitted]");
+}
+
+#[test]
+fn should_log_param_values() {
+    let nothing: Option<i64> = None;
+    let params: [&(dyn Param + Sync + Send); 4] =
+        [&1_i32, &"ann".to_owned(), &nothing, &vec![1_u8, 2, 3]];
+    assert_eq!(
+        format_params(&params, ParamLogging::Values),
+        r#"[1, "ann", NULL, <3 bytes>]"#
+    );
+}
+
+#[test]
+fn should_log_the_type_of_params_without_a_value() {
+    let opaque = Value::Opaque("uuid::Uuid".to_owned());
+    assert_eq!(opaque.to_string(), "<uuid::Uuid>");
+    assert_eq!(hashed(&opaque), "<uuid::Uuid>");
+}
+
+#[test]
+fn should_log_a_keyed_hash_of_param_values() {
+    let params: [&(dyn Param + Sync + Send); 3] =
+        [&"ann".to_owned(), &"ann".to_owned(), &"bob".to_owned()];
+    let logged = format_params(&params, ParamLogging::Hash);
+    let hashes: Vec<&str> = logged.trim_matches(['[', ']']).split(", ").collect();
+    assert_eq!(hashes[0], hashes[1]);
+    assert_ne!(hashes[0], hashes[2]);
+    for hash in &hashes {
+        assert_eq!(hash.len(), 17, "{hash}");
+        assert!(hash.starts_with('#'));
+    }
+    assert!(!logged.contains("ann"));
+    // the same within the process
+    assert_eq!(format_params(&params, ParamLogging::Hash), logged);
+}
+
+// Writes down what it was called with
+#[derive(Default)]
+struct Recorder {
+    calls: std::sync::Mutex<Vec<String>>,
+}
+
+impl Recorder {
+    fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+impl QueryHook for Recorder {
+    fn before_query(&self, sql: &str, params: &[&(dyn Param + Sync + Send)]) {
+        let call = format!("before {} ({} params)", sql, params.len());
+        self.calls.lock().unwrap().push(call);
+    }
+
+    fn after_query(&self, sql: &str, _duration: Duration, outcome: QueryOutcome<'_>) {
+        let call = format!("after {} ({})", sql, outcome);
+        self.calls.lock().unwrap().push(call);
+    }
+
+    fn before_labeled_query(&self, label: &str, sql: &str, _: &[&(dyn Param + Sync + Send)]) {
+        let call = format!("before [{}] {}", label, sql);
+        self.calls.lock().unwrap().push(call);
+    }
+}
+
+#[tokio::test]
+async fn should_call_the_hooks_around_a_statement() {
+    let recorder = Arc::new(Recorder::default());
+    let mut hooks = Hooks::default();
+    hooks.push(recorder.clone());
+
+    let id = 1_i32;
+    let work = async { Ok(ExecuteResult { rows_affected: 3 }) };
+    let sql = "DELETE FROM users WHERE id = $1";
+    hooks
+        .run(Syntax::Postgres, sql, &[&id], work)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        recorder.calls(),
+        [
+            "before DELETE FROM users WHERE id = $1 (1 params)",
+            "after DELETE FROM users WHERE id = $1 (3 rows affected)",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn should_pass_the_error_of_a_failed_statement_to_the_hooks() {
+    let recorder = Arc::new(Recorder::default());
+    let mut hooks = Hooks::default();
+    hooks.push(recorder.clone());
+
+    let work = async { Err::<Vec<Row>, _>(Error::RowNowFound) };
+    let result = hooks.run(Syntax::Sqlite, "SELECT 1", &[], work).await;
+
+    assert!(matches!(result, Err(Error::RowNowFound)));
+    assert_eq!(
+        recorder.calls()[1],
+        format!("after SELECT 1 (error: {})", Error::RowNowFound)
+    );
+}
+
+#[tokio::test]
+async fn should_call_the_labeled_hooks_for_a_labeled_statement() {
+    let recorder = Arc::new(Recorder::default());
+    let mut hooks = Hooks::default();
+    hooks.push(recorder.clone());
+
+    let work = async { Ok(vec![Vec::<Row>::default(), Vec::default()]) };
+    let sql = "SELECT * FROM users";
+    hooks
+        .run_labeled(Some("users"), Syntax::Mysql, sql, &[], work)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        recorder.calls(),
+        [
+            "before [users] SELECT * FROM users",
+            "after SELECT * FROM users (0 rows)"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn should_run_the_hooks_of_a_client_for_its_transactions() {
+    let recorder = Arc::new(Recorder::default());
+    let mut hooks = Hooks::default();
+    hooks.push(recorder.clone());
+    let transaction = hooks.for_transaction().await.unwrap();
+
+    let work = async { Ok(ExecuteResult { rows_affected: 0 }) };
+    transaction
+        .run(Syntax::Postgres, "COMMIT", &[], work)
+        .await
+        .unwrap();
+    assert_eq!(recorder.calls().len(), 2);
+}
+
+#[tokio::test]
+async fn should_refuse_statements_once_closed() {
+    let recorder = Arc::new(Recorder::default());
+    let mut hooks = Hooks::default();
+    hooks.push(recorder.clone());
+    hooks.close(Instant::now()).await.unwrap();
+
+    let work = async { Ok(ExecuteResult { rows_affected: 0 }) };
+    let result = hooks.run(Syntax::Postgres, "SELECT 1", &[], work).await;
+    assert!(matches!(result, Err(Error::ClientClosed)));
+    assert!(recorder.calls().is_empty());
+    assert!(matches!(
+        hooks.for_transaction().await,
+        Err(Error::ClientClosed)
+    ));
+}
+
+#[test]
+fn should_only_log_statements_slower_than_the_threshold() {
+    let mut observers = Observers::default();
+    assert!(!observers.is_slow(Duration::from_secs(60)));
+
+    observers.slow_threshold = Some(Duration::from_millis(100));
+    assert!(!observers.is_slow(Duration::from_millis(100)));
+    assert!(observers.is_slow(Duration::from_millis(101)));
+}
+
+fn tags(pairs: &[(&str, &str)]) -> std::collections::BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn should_append_an_encoded_sql_comment() {
+    let tags = tags(&[("application", "api"), ("route", "/users?id=1")]);
+    assert_eq!(
+        comments::annotate("SELECT 1", &tags),
+        "SELECT 1 /*application='api',route='%2Fusers%3Fid%3D1'*/"
+    );
+    // before the trailing semicolon
+    assert_eq!(
+        comments::annotate("SELECT 1; ", &tags),
+        "SELECT 1 /*application='api',route='%2Fusers%3Fid%3D1'*/;"
+    );
+}
+
+#[test]
+fn should_leave_statements_without_tags_or_with_a_comment_alone() {
+    assert!(matches!(
+        comments::annotate("SELECT 1", &tags(&[])),
+        Cow::Borrowed(_)
+    ));
+    let commented = "SELECT 1 /*action='index'*/";
+    assert_eq!(
+        comments::annotate(commented, &tags(&[("application", "api")])),
+        commented
+    );
+}
+
+#[tokio::test]
+async fn should_add_the_query_context_to_the_comment() {
+    let tags = tags(&[("application", "api"), ("route", "none")]);
+    let annotated = with_query_context([("route", "/users")], async {
+        set_query_context("controller", "users");
+        let inner = with_query_context([("action", "list")], async {
+            comments::annotate("SELECT 1", &tags)
+        })
+        .await;
+        (inner, comments::annotate("SELECT 1", &tags))
+    })
+    .await;
+
+    assert_eq!(
+        annotated.0,
+        "SELECT 1 /*action='list',application='api',controller='users',route='%2Fusers'*/"
+    );
+    assert_eq!(
+        annotated.1,
+        "SELECT 1 /*application='api',controller='users',route='%2Fusers'*/"
+    );
+    // outside of the context
+    assert_eq!(
+        comments::annotate("SELECT 1", &tags),
+        "SELECT 1 /*application='api',route='none'*/"
+    );
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_report_the_rows_of_every_result_set() {
+    use crate::mock::MockRow;
+    let row = || Row::from(MockRow::new().with("id", 1));
+    let sets = vec![vec![row()], Vec::default(), vec![row(), row()]];
+    assert!(matches!(sets.outcome(), QueryOutcome::Rows(3)));
+}
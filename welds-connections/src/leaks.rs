@@ -0,0 +1,160 @@
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Warns about connections that are checked out for longer than a threshold.
+/// Shared by a client, its clones, and its transactions
+pub(crate) struct LeakDetector {
+    threshold: Duration,
+    next_id: AtomicU64,
+    held: Arc<Mutex<HashMap<u64, Held>>>,
+    watching: Mutex<bool>,
+}
+
+// A connection that is checked out, and where from
+struct Held {
+    kind: &'static str,
+    since: Instant,
+    backtrace: Backtrace,
+    warned: bool,
+}
+
+impl LeakDetector {
+    pub(crate) fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            next_id: AtomicU64::new(0),
+            held: Arc::default(),
+            watching: Mutex::new(false),
+        }
+    }
+
+    /// Starts tracking a connection. It is given back when the returned value is dropped
+    pub(crate) fn check_out(&self, kind: &'static str) -> CheckedOut {
+        self.watch();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let held = Held {
+            kind,
+            since: Instant::now(),
+            backtrace: Backtrace::force_capture(),
+            warned: false,
+        };
+        self.held.lock().unwrap().insert(id, held);
+        CheckedOut {
+            id,
+            threshold: self.threshold,
+            held: Arc::downgrade(&self.held),
+        }
+    }
+
+    // A connection that is never given back is never dropped, so a thread looks over the
+    // checked out connections instead of waiting for them. It stops once the client is gone
+    fn watch(&self) {
+        let mut watching = self.watching.lock().unwrap();
+        if *watching {
+            return;
+        }
+        *watching = true;
+        let held = Arc::downgrade(&self.held);
+        let threshold = self.threshold;
+        let every = (threshold / 4).clamp(Duration::from_millis(10), Duration::from_secs(1));
+        let spawned = thread::Builder::new()
+            .name("welds-leak-detector".to_owned())
+            .spawn(move || watch(held, threshold, every));
+        if let Err(err) = spawned {
+            log::warn!("Could not start the connection leak detector: {}", err);
+        }
+    }
+}
+
+fn watch(held: Weak<Mutex<HashMap<u64, Held>>>, threshold: Duration, every: Duration) {
+    loop {
+        thread::sleep(every);
+        let held = match held.upgrade() {
+            Some(held) => held,
+            None => return,
+        };
+        let mut held = held.lock().unwrap();
+        for h in held.values_mut() {
+            let elapsed = h.since.elapsed();
+            if !h.warned && elapsed > threshold {
+                h.warned = true;
+                log::warn!(
+                    "A {} has held a connection for {:?}, it may have leaked. Checked out at:\n{}",
+                    h.kind,
+                    elapsed,
+                    h.backtrace
+                );
+            }
+        }
+    }
+}
+
+/// A connection tracked by a `LeakDetector`
+pub(crate) struct CheckedOut {
+    id: u64,
+    threshold: Duration,
+    held: Weak<Mutex<HashMap<u64, Held>>>,
+}
+
+impl Drop for CheckedOut {
+    fn drop(&mut self) {
+        let held = match self.held.upgrade() {
+            Some(held) => held,
+            None => return,
+        };
+        let h = match held.lock().unwrap().remove(&self.id) {
+            Some(h) => h,
+            None => return,
+        };
+        let elapsed = h.since.elapsed();
+        if h.warned {
+            log::warn!(
+                "The {} reported above gave its connection back after {:?}",
+                h.kind,
+                elapsed
+            );
+        } else if elapsed > self.threshold {
+            // the watcher didn't get to it before it was given back
+            log::warn!(
+                "A {} held a connection for {:?}. Checked out at:\n{}",
+                h.kind,
+                elapsed,
+                h.backtrace
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_forget_a_connection_once_it_is_given_back() {
+        let detector = LeakDetector::new(Duration::from_secs(60));
+        let first = detector.check_out("transaction");
+        let second = detector.check_out("transaction");
+        assert_eq!(detector.held.lock().unwrap().len(), 2);
+
+        drop(first);
+        let held = detector.held.lock().unwrap();
+        assert_eq!(held.keys().collect::<Vec<_>>(), [&second.id]);
+    }
+
+    #[test]
+    fn should_flag_a_connection_held_past_the_threshold() {
+        let detector = LeakDetector::new(Duration::from_millis(10));
+        let held = detector.check_out("transaction");
+        let flagged = || detector.held.lock().unwrap()[&held.id].warned;
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !flagged() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert!(flagged());
+    }
+}
@@ -1,27 +1,85 @@
 pub use crate::errors::Error;
 use crate::errors::Result;
 use async_trait::async_trait;
+pub use capabilities::Capabilities;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "mssql"))]
+pub use credentials::Credentials;
+pub use namespace::Namespace;
 pub use row::{Row, RowInner};
 use std::time::Duration;
-pub use transaction::Transaction;
+pub use transaction::{AfterCommit, Transaction};
+pub mod capabilities;
+#[cfg(any(
+    feature = "mysql",
+    feature = "postgres",
+    feature = "mssql",
+    feature = "sqlite"
+))]
+pub mod credentials;
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+pub mod distributed;
 pub mod errors;
+pub mod instrument;
+pub(crate) mod leaks;
+pub mod namespace;
+pub mod priority;
+pub mod procedure;
+pub mod retry;
 pub mod row;
+pub(crate) mod shutdown;
+pub mod statement_cache;
+pub mod tenants;
 pub mod transaction;
+pub mod value;
 
+#[cfg(feature = "mock")]
+pub mod mock;
 #[cfg(feature = "mssql")]
 pub mod mssql;
 #[cfg(feature = "mysql")]
 pub mod mysql;
 #[cfg(feature = "noop")]
 pub mod noop;
+#[cfg(feature = "odbc")]
+pub mod odbc;
 #[cfg(feature = "postgres")]
 pub mod postgres;
+#[cfg(feature = "replay")]
+pub mod replay;
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
 
+/// One statement of a `fetch_many`. Made with `Fetch::new`, and `with_label` to name it
+#[non_exhaustive]
 pub struct Fetch<'s, 'args, 't> {
     pub sql: &'s str,
     pub params: &'args [&'t (dyn Param + Sync + Send)],
+    /// Names the statement in hooks, logs, and the error returned if it fails
+    pub label: Option<&'s str>,
+}
+
+impl<'s, 'args, 't> Fetch<'s, 'args, 't> {
+    pub fn new(sql: &'s str, params: &'args [&'t (dyn Param + Sync + Send)]) -> Self {
+        Self {
+            sql,
+            params,
+            label: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: &'s str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// The error for this statement, the `index`th of a `fetch_many`, failing
+    pub(crate) fn failed(&self, index: usize, err: Error) -> Error {
+        Error::FetchMany {
+            index,
+            label: self.label.map(|l| l.to_owned()),
+            source: Box::new(err),
+        }
+    }
 }
 
 #[async_trait]
@@ -49,6 +107,113 @@ pub trait Client: Sync + Send {
 
     // Returns what syntax (dialect) of SQL the backend is expecting
     fn syntax(&self) -> Syntax;
+
+    /// Runs SQL that returns several result sets (a stored procedure, a batch of SELECTs),
+    /// returning the rows of each set on its own.
+    ///
+    /// Only MSSQL keeps the sets apart, on the other backends all the rows are one set.
+    async fn fetch_result_sets(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<Vec<Vec<Row>>> {
+        Ok(vec![self.fetch_rows(sql, params).await?])
+    }
+
+    /// Calls a stored procedure, `CALL` or `EXEC` depending on the backend.
+    ///
+    /// Returns the rows the procedure selected, and the values of its OUT params.
+    /// Postgres, MySql, MariaDB and MSSQL have procedures, other backends return `ProceduresUnsupported`
+    ///
+    /// ```rust,ignore
+    /// let params = [ProcParam::input(&customer_id), ProcParam::output("total", "INT")];
+    /// let result = client.call_procedure("order_total", &params).await?;
+    /// let total: i32 = result.outputs.unwrap().get("total")?;
+    /// ```
+    async fn call_procedure(
+        &self,
+        name: &str,
+        params: &[procedure::ProcParam<'_>],
+    ) -> Result<procedure::ProcedureResult> {
+        procedure::call(self, name, params).await
+    }
+
+    /// Runs a cheap statement (`SELECT 1`) on the database and returns how long it took
+    async fn ping(&self) -> Result<std::time::Duration> {
+        let start = std::time::Instant::now();
+        self.fetch_rows("SELECT 1", &[]).await?;
+        Ok(start.elapsed())
+    }
+
+    /// True if the database answered a `ping`. Handy for readiness probes
+    async fn is_healthy(&self) -> bool {
+        self.ping().await.is_ok()
+    }
+
+    /// What the backend behind this client can do
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::for_syntax(self.syntax())
+    }
+
+    /// The schema (or table prefix) this client moves the tables of its statements into, if any
+    fn namespace(&self) -> Option<&Namespace> {
+        None
+    }
+
+    /// True for a transaction (or a branch of a distributed one), whose writes other clients
+    /// don't see until it commits
+    fn in_transaction(&self) -> bool {
+        false
+    }
+
+    /// Holds the work until the transaction commits, it is dropped if the transaction rolls back.
+    /// A client that isn't a transaction hands the work back to be run now
+    fn after_commit(&self, work: AfterCommit) -> Option<AfterCommit> {
+        Some(work)
+    }
+
+    /// The client as one a transaction can be started on.
+    /// None for a transaction, and for a client that can't start one
+    fn as_transact_start(&self) -> Option<&(dyn TransactStart + Sync)> {
+        None
+    }
+
+    /// The concrete client, if this is a `PostgresClient`.
+    /// Useful with clients from `connect` to get to the backend specific APIs
+    #[cfg(feature = "postgres")]
+    fn as_postgres(&self) -> Option<&postgres::PostgresClient> {
+        None
+    }
+
+    /// The concrete client, if this is a `MysqlClient`
+    #[cfg(feature = "mysql")]
+    fn as_mysql(&self) -> Option<&mysql::MysqlClient> {
+        None
+    }
+
+    /// The concrete client, if this is a `SqliteClient`
+    #[cfg(feature = "sqlite")]
+    fn as_sqlite(&self) -> Option<&sqlite::SqliteClient> {
+        None
+    }
+
+    /// The concrete client, if this is a `MssqlClient`
+    #[cfg(feature = "mssql")]
+    fn as_mssql(&self) -> Option<&mssql::MssqlClient> {
+        None
+    }
+
+    /// The concrete client, if this is an `OdbcClient`
+    #[cfg(feature = "odbc")]
+    fn as_odbc(&self) -> Option<&odbc::OdbcClient> {
+        None
+    }
+
+    /// The concrete client, if this is a `MockClient`
+    #[cfg(feature = "mock")]
+    fn as_mock(&self) -> Option<&mock::MockClient> {
+        None
+    }
 }
 
 /// Used the ENV DATABASE_URL
@@ -61,7 +226,10 @@ pub async fn connect_from_env() -> Result<Box<dyn Client>> {
 /// Returns a connection pool (Client) for the given connection string.
 /// connection string formats:
 /// SQLX Connection String (postgres, mysql, sqlite)
+/// SQLX Postgres Connection String using `cockroachdb://` (cockroachdb)
+/// SQLX MySql Connection String using `mariadb://` (mariadb)
 /// ADO Connection String (mssql)
+/// ODBC Connection String prefixed with `odbc:` (odbc)
 pub async fn connect(
     cs: impl Into<String>,
     timeout: Option<Duration>,
@@ -81,18 +249,38 @@ pub async fn connect(
         let client = postgres::connect(&cs, timeout, max_connections).await?;
         return Ok(Box::new(client));
     }
+    #[cfg(feature = "postgres")]
+    if let Some(rest) = cs.strip_prefix("cockroachdb:") {
+        log::debug!("Welds connecting to CockroachDB");
+        let url = format!("postgresql:{}", rest);
+        let client = postgres::connect_cockroachdb(&url, timeout, max_connections).await?;
+        return Ok(Box::new(client));
+    }
     #[cfg(feature = "mysql")]
     if cs.starts_with("mysql:") {
         log::debug!("Welds connecting to MySql");
         let client = mysql::connect(&cs, timeout, max_connections).await?;
         return Ok(Box::new(client));
     }
+    #[cfg(feature = "mysql")]
+    if let Some(rest) = cs.strip_prefix("mariadb:") {
+        log::debug!("Welds connecting to MariaDB");
+        let url = format!("mysql:{}", rest);
+        let client = mysql::connect_mariadb(&url, timeout, max_connections).await?;
+        return Ok(Box::new(client));
+    }
     #[cfg(feature = "sqlite")]
     if cs.starts_with("sqlite:") {
         log::debug!("Welds connecting to Sqlite");
         let client = sqlite::connect(&cs).await?;
         return Ok(Box::new(client));
     }
+    #[cfg(feature = "odbc")]
+    if let Some(odbc_cs) = cs.strip_prefix("odbc:") {
+        log::debug!("Welds connecting to ODBC");
+        let client = odbc::connect(odbc_cs, timeout, max_connections).await?;
+        return Ok(Box::new(client));
+    }
     #[cfg(feature = "mssql")]
     if !cs.is_empty() {
         log::debug!("Welds connecting to MSSQL");
@@ -110,7 +298,10 @@ pub async fn connect(
 ///
 /// connection string formats:
 /// SQLX Connection String (postgres, mysql, sqlite)
+/// SQLX Postgres Connection String using `cockroachdb://` (cockroachdb)
+/// SQLX MySql Connection String using `mariadb://` (mariadb)
 /// ADO Connection String (mssql)
+/// ODBC Connection String prefixed with `odbc:` (odbc)
 pub async fn connect_transaction_start(
     cs: impl Into<String>,
     timeout: Option<Duration>,
@@ -130,18 +321,38 @@ pub async fn connect_transaction_start(
         let client = postgres::connect(&cs, timeout, max_connections).await?;
         return Ok(Box::new(client));
     }
+    #[cfg(feature = "postgres")]
+    if let Some(rest) = cs.strip_prefix("cockroachdb:") {
+        log::debug!("Welds connecting to CockroachDB");
+        let url = format!("postgresql:{}", rest);
+        let client = postgres::connect_cockroachdb(&url, timeout, max_connections).await?;
+        return Ok(Box::new(client));
+    }
     #[cfg(feature = "mysql")]
     if cs.starts_with("mysql:") {
         log::debug!("Welds connecting to MySql");
         let client = mysql::connect(&cs, timeout, max_connections).await?;
         return Ok(Box::new(client));
     }
+    #[cfg(feature = "mysql")]
+    if let Some(rest) = cs.strip_prefix("mariadb:") {
+        log::debug!("Welds connecting to MariaDB");
+        let url = format!("mysql:{}", rest);
+        let client = mysql::connect_mariadb(&url, timeout, max_connections).await?;
+        return Ok(Box::new(client));
+    }
     #[cfg(feature = "sqlite")]
     if cs.starts_with("sqlite:") {
         log::debug!("Welds connecting to Sqlite");
         let client = sqlite::connect(&cs).await?;
         return Ok(Box::new(client));
     }
+    #[cfg(feature = "odbc")]
+    if let Some(odbc_cs) = cs.strip_prefix("odbc:") {
+        log::debug!("Welds connecting to ODBC");
+        let client = odbc::connect(odbc_cs, timeout, max_connections).await?;
+        return Ok(Box::new(client));
+    }
     #[cfg(feature = "mssql")]
     if !cs.is_empty() {
         log::debug!("Welds connecting to MSSQL");
@@ -187,4 +398,8 @@ pub enum Syntax {
     Postgres,
     Sqlite,
     Mssql,
+    /// CockroachDB speaks the postgres wire protocol, but has its own types and quirks
+    CockroachDb,
+    /// MariaDB speaks the mysql protocol, but supports things mysql doesn't (RETURNING)
+    MariaDb,
 }
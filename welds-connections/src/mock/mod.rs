@@ -0,0 +1,320 @@
+use super::Row;
+use super::{Client, Param};
+use crate::distributed::BranchConn;
+use crate::errors::Result;
+use crate::instrument::{client_builders, Hooks};
+use crate::row::{RowInner, ValueRow};
+use crate::transaction::TransT;
+use crate::value::{self, ParamValue, ToValue, Value};
+use crate::{ExecuteResult, Fetch, Namespace, Syntax, TransactStart, Transaction};
+use async_trait::async_trait;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A client for unit tests. No database needed.
+///
+/// Queue up the rows a query should return, run your code, then check the SQL it ran.
+///
+/// ```rust,ignore
+/// let client = MockClient::new(Syntax::Postgres);
+/// client.enqueue_rows("FROM products", vec![MockRow::new().with("id", 1).with("name", "plush")]);
+///
+/// let products = Product::all().run(&client).await?;
+///
+/// assert_eq!(products.len(), 1);
+/// assert!(client.last_sql().unwrap().starts_with("SELECT"));
+/// ```
+///
+/// Canned results are matched against the SQL being run by pattern.
+/// A pattern matches if the SQL contains it, and `*` matches anything.
+/// Each canned result is used once, in the order they were queued.
+/// Queries without a canned result return no rows.
+#[derive(Clone)]
+pub struct MockClient {
+    syntax: Syntax,
+    state: Arc<Mutex<MockState>>,
+    hooks: Hooks,
+}
+
+#[derive(Default)]
+struct MockState {
+    canned: Vec<Canned>,
+    queries: Vec<RecordedQuery>,
+}
+
+struct Canned {
+    pattern: String,
+    response: Response,
+}
+
+enum Response {
+    Rows(Vec<MockRow>),
+    RowsAffected(u64),
+}
+
+/// A SQL statement that was run on a `MockClient`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedQuery {
+    pub sql: String,
+    /// Params of a type a `Value` can't hold (dates, uuids, ...) are recorded as `Value::Opaque`
+    pub params: Vec<Value>,
+}
+
+/// Builds a row for the mock client to return
+#[derive(Debug, Clone, Default)]
+pub struct MockRow {
+    columns: Vec<String>,
+    values: Vec<Value>,
+}
+
+impl MockRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a column to the row
+    pub fn with(mut self, column: impl Into<String>, value: impl ToValue) -> Self {
+        self.columns.push(column.into());
+        self.values.push(value.to_value());
+        self
+    }
+}
+
+impl From<MockRow> for Row {
+    fn from(row: MockRow) -> Row {
+        Row {
+            inner: RowInner::Mock(ValueRow::new(Arc::new(row.columns), row.values)),
+        }
+    }
+}
+
+/// The params the mock client can record. That is every param, see `RecordedQuery::params`
+pub trait MockParam: ParamValue {}
+
+impl<T> MockParam for T where T: ParamValue {}
+
+impl MockClient {
+    pub fn new(syntax: Syntax) -> Self {
+        Self {
+            syntax,
+            state: Arc::new(Mutex::new(MockState::default())),
+            hooks: Hooks::default(),
+        }
+    }
+
+    client_builders!();
+
+    /// Closes the client like a real client would. New statements and transactions are
+    /// refused, the ones already running get until the timeout to finish
+    pub async fn close(&self, timeout: Duration) -> Result<()> {
+        self.hooks.close(Instant::now() + timeout).await
+    }
+
+    /// The client itself as a branch of a `DistributedTransaction`, its statements are recorded here
+    pub(crate) async fn branch_conn(&self) -> Result<(BranchConn, Hooks, bool)> {
+        let hooks = self.hooks.for_transaction().await?;
+        Ok((BranchConn::Mock(self.clone()), hooks, false))
+    }
+
+    /// The next query matching the pattern will return these rows
+    pub fn enqueue_rows(&self, pattern: impl Into<String>, rows: Vec<MockRow>) {
+        self.enqueue(pattern.into(), Response::Rows(rows));
+    }
+
+    /// The next execute matching the pattern will report this many rows affected
+    pub fn enqueue_rows_affected(&self, pattern: impl Into<String>, rows_affected: u64) {
+        self.enqueue(pattern.into(), Response::RowsAffected(rows_affected));
+    }
+
+    /// Every statement that has been run, in order.
+    /// Transactions show up as `BEGIN`, `COMMIT`, and `ROLLBACK`
+    pub fn queries(&self) -> Vec<RecordedQuery> {
+        self.state.lock().unwrap().queries.clone()
+    }
+
+    /// The SQL of the last statement that was run
+    pub fn last_sql(&self) -> Option<String> {
+        let state = self.state.lock().unwrap();
+        state.queries.last().map(|q| q.sql.clone())
+    }
+
+    /// Forgets all the recorded statements and any canned results that were not used
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.queries.clear();
+        state.canned.clear();
+    }
+
+    fn enqueue(&self, pattern: String, response: Response) {
+        let mut state = self.state.lock().unwrap();
+        state.canned.push(Canned { pattern, response });
+    }
+
+    fn record(&self, sql: &str, params: &[&(dyn Param + Sync + Send)]) {
+        let params = params.iter().map(|p| value::of_param(*p)).collect();
+        let mut state = self.state.lock().unwrap();
+        state.queries.push(RecordedQuery {
+            sql: sql.to_owned(),
+            params,
+        });
+    }
+
+    // Runs a statement without calling the hooks. Transactions call their own hooks
+    pub(crate) fn execute_unhooked(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> ExecuteResult {
+        self.record(sql, params);
+        let rows_affected = self.take_rows_affected(sql);
+        ExecuteResult { rows_affected }
+    }
+
+    pub(crate) fn fetch_rows_unhooked(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Vec<Row> {
+        self.record(sql, params);
+        self.take_rows(sql)
+    }
+
+    fn take_rows(&self, sql: &str) -> Vec<Row> {
+        let mut state = self.state.lock().unwrap();
+        let found = state.canned.iter().position(|c| {
+            matches!(c.response, Response::Rows(_)) && pattern_matches(&c.pattern, sql)
+        });
+        match found.map(|i| state.canned.remove(i).response) {
+            Some(Response::Rows(rows)) => rows.into_iter().map(Row::from).collect(),
+            _ => Vec::default(),
+        }
+    }
+
+    fn take_rows_affected(&self, sql: &str) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let found = state.canned.iter().position(|c| {
+            matches!(c.response, Response::RowsAffected(_)) && pattern_matches(&c.pattern, sql)
+        });
+        match found.map(|i| state.canned.remove(i).response) {
+            Some(Response::RowsAffected(count)) => count,
+            _ => 0,
+        }
+    }
+}
+
+// true if all the parts of the pattern between `*` are found in the sql, in order
+fn pattern_matches(pattern: &str, sql: &str) -> bool {
+    let mut rest = sql;
+    for part in pattern.split('*') {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[async_trait]
+impl Client for MockClient {
+    async fn execute(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<ExecuteResult> {
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                Ok(self.execute_unhooked(sql, params))
+            })
+            .await
+    }
+
+    async fn fetch_rows(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<Vec<Row>> {
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                Ok(self.fetch_rows_unhooked(sql, params))
+            })
+            .await
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        fetches: &[Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        let mut datasets = Vec::default();
+        for fetch in fetches {
+            let annotated = self.hooks.annotate(fetch.sql);
+            let sql = annotated.as_ref();
+            let rows = self
+                .hooks
+                .run_labeled(fetch.label, self.syntax, sql, fetch.params, async {
+                    Ok(self.fetch_rows_unhooked(sql, fetch.params))
+                })
+                .await?;
+            datasets.push(rows);
+        }
+        Ok(datasets)
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+
+    fn namespace(&self) -> Option<&Namespace> {
+        self.hooks.namespace()
+    }
+
+    fn as_transact_start(&self) -> Option<&(dyn TransactStart + Sync)> {
+        Some(self)
+    }
+
+    fn as_mock(&self) -> Option<&MockClient> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl TransactStart for MockClient {
+    async fn begin(&self) -> Result<Transaction> {
+        let hooks = self.hooks.for_transaction().await?;
+        self.record("BEGIN", &[]);
+        let t = MockTransaction {
+            client: self.clone(),
+            _t: PhantomData,
+        };
+        Ok(Transaction::new(TransT::Mock(t)).with_hooks(hooks))
+    }
+}
+
+/// A transaction on the mock client. Statements are recorded on the client that started it
+pub(crate) struct MockTransaction<'t> {
+    client: MockClient,
+    _t: PhantomData<&'t ()>,
+}
+
+impl<'t> MockTransaction<'t> {
+    pub(crate) fn client(&self) -> &MockClient {
+        &self.client
+    }
+
+    pub(crate) async fn commit(self) -> Result<()> {
+        self.client.record("COMMIT", &[]);
+        Ok(())
+    }
+
+    pub(crate) async fn rollback(self) -> Result<()> {
+        self.client.record("ROLLBACK", &[]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,185 @@
+use super::*;
+
+fn user(id: i32, name: &str) -> MockRow {
+    MockRow::new().with("id", id).with("name", name)
+}
+
+#[tokio::test]
+async fn should_return_the_enqueued_rows_once_in_order() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("FROM users", vec![user(1, "ann"), user(2, "bob")]);
+    client.enqueue_rows("FROM users", vec![user(3, "cat")]);
+
+    let rows = client.fetch_rows("SELECT * FROM users", &[]).await.unwrap();
+    let names: Vec<String> = rows.iter().map(|r| r.get("name").unwrap()).collect();
+    assert_eq!(names, ["ann", "bob"]);
+
+    let rows = client.fetch_rows("SELECT * FROM users", &[]).await.unwrap();
+    assert_eq!(rows[0].get::<i32>("id").unwrap(), 3);
+
+    // nothing left to return
+    let rows = client.fetch_rows("SELECT * FROM users", &[]).await.unwrap();
+    assert!(rows.is_empty());
+}
+
+#[tokio::test]
+async fn should_read_the_columns_of_a_canned_row() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let row = MockRow::new()
+        .with("id", 7_i64)
+        .with("note", Option::<String>::None)
+        .with("active", true);
+    client.enqueue_rows("*", vec![row]);
+
+    let rows = client.fetch_rows("SELECT 1", &[]).await.unwrap();
+    let row = &rows[0];
+    assert_eq!(row.get::<i64>("id").unwrap(), 7);
+    assert_eq!(row.get::<Option<String>>("note").unwrap(), None);
+    assert!(row.get::<bool>("active").unwrap());
+    assert!(row.get_by_position::<i32>(0).is_ok());
+    assert!(matches!(
+        row.get::<i64>("missing"),
+        Err(crate::Error::ColumnNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn should_record_the_statements_and_their_params() {
+    let client = MockClient::new(Syntax::Postgres);
+    let id = 5_i32;
+    let name = "ann".to_owned();
+    let nothing: Option<i64> = None;
+    client
+        .execute(
+            "UPDATE users SET name = $1 WHERE id = $2 AND org = $3",
+            &[&name, &id, &nothing],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        client.queries(),
+        [RecordedQuery {
+            sql: "UPDATE users SET name = $1 WHERE id = $2 AND org = $3".to_owned(),
+            params: vec![Value::Text("ann".to_owned()), Value::Int(5), Value::Null],
+        }]
+    );
+    client.reset();
+    assert!(client.queries().is_empty());
+}
+
+#[tokio::test]
+async fn should_match_a_pattern_with_wildcards() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("SELECT*FROM orders*status", vec![user(1, "ann")]);
+
+    // the parts have to be found in order
+    let rows = client
+        .fetch_rows("SELECT status FROM orders", &[])
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+
+    let rows = client
+        .fetch_rows("SELECT t1.id FROM orders t1 WHERE t1.status = $1", &[])
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[test]
+fn should_match_patterns_by_their_parts() {
+    assert!(pattern_matches("*", "anything at all"));
+    assert!(pattern_matches("FROM users", "SELECT * FROM users t1"));
+    assert!(pattern_matches(
+        "INSERT*RETURNING",
+        "INSERT INTO a VALUES (1) RETURNING *"
+    ));
+    assert!(!pattern_matches(
+        "RETURNING*INSERT",
+        "INSERT INTO a RETURNING *"
+    ));
+    assert!(!pattern_matches("FROM orders", "SELECT * FROM users"));
+}
+
+#[tokio::test]
+async fn should_keep_rows_affected_apart_from_rows() {
+    let client = MockClient::new(Syntax::Mysql);
+    client.enqueue_rows_affected("DELETE", 3);
+    client.enqueue_rows("DELETE", vec![user(1, "ann")]);
+
+    let result = client.execute("DELETE FROM users", &[]).await.unwrap();
+    assert_eq!(result.rows_affected(), 3);
+    let result = client.execute("DELETE FROM users", &[]).await.unwrap();
+    assert_eq!(result.rows_affected(), 0);
+    let rows = client.fetch_rows("DELETE FROM users", &[]).await.unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[tokio::test]
+async fn should_record_a_transaction_on_its_client() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("FROM users", vec![user(1, "ann")]);
+
+    let transaction = client.begin().await.unwrap();
+    assert_eq!(transaction.syntax(), Syntax::Postgres);
+    let rows = transaction
+        .fetch_rows("SELECT * FROM users", &[])
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    transaction
+        .execute("DELETE FROM users", &[&1_i32])
+        .await
+        .unwrap();
+    transaction.commit().await.unwrap();
+
+    let transaction = client.begin().await.unwrap();
+    transaction.rollback().await.unwrap();
+
+    let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+    assert_eq!(
+        sql,
+        [
+            "BEGIN",
+            "SELECT * FROM users",
+            "DELETE FROM users",
+            "COMMIT",
+            "BEGIN",
+            "ROLLBACK"
+        ]
+    );
+    assert_eq!(client.queries()[2].params, [Value::Int(1)]);
+}
+
+#[tokio::test]
+async fn should_refuse_statements_once_closed() {
+    let client = MockClient::new(Syntax::Sqlite);
+    client.close(Duration::from_millis(10)).await.unwrap();
+    let err = client.execute("DELETE FROM users", &[]).await.err();
+    assert!(matches!(err, Some(crate::Error::ClientClosed)));
+    assert!(client.begin().await.is_err());
+}
+
+#[tokio::test]
+async fn should_ping_with_a_cheap_statement() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.ping().await.unwrap();
+    assert_eq!(client.last_sql().as_deref(), Some("SELECT 1"));
+    assert!(client.is_healthy().await);
+
+    client.close(Duration::from_millis(10)).await.unwrap();
+    assert!(!client.is_healthy().await);
+}
+
+#[tokio::test]
+async fn should_return_a_single_result_set_where_they_are_not_kept_apart() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("*", vec![user(1, "ann"), user(2, "bob")]);
+    let sets = client
+        .fetch_result_sets("SELECT * FROM users", &[])
+        .await
+        .unwrap();
+    assert_eq!(sets.len(), 1);
+    assert_eq!(sets[0].len(), 2);
+}
@@ -0,0 +1,150 @@
+use super::{DbConn, MssqlParam};
+use crate::errors::Error;
+use crate::value::ParamValue;
+use crate::Param;
+use tiberius::ColumnData;
+
+// The errors MSSQL gives when a param was sent with a type it can't use
+// 206 operand type clash (int is incompatible with date),
+// 245 conversion failed (converting the nvarchar value 'a' to data type int),
+// 8114 error converting data type nvarchar to int
+const BIND_TYPE_ERRORS: &[u32] = &[206, 245, 8114];
+
+/// Turns a failed statement into a `ParamType` error when it failed because of the type of a param.
+///
+/// MSSQL is asked what types it wants for the params (`sp_describe_undeclared_parameters`),
+/// from the columns they are used with. The first param sent as a type of another kind
+/// (a number for a date, text for a number) is reported. Any other error is returned as is.
+pub(super) async fn diagnose_bind(
+    conn: &mut DbConn,
+    sql: &str,
+    params: &[&(dyn Param + Sync + Send)],
+    err: tiberius::error::Error,
+) -> Error {
+    let is_bind_error = err
+        .code()
+        .map(|code| BIND_TYPE_ERRORS.contains(&code))
+        .unwrap_or_default();
+    if !is_bind_error {
+        return err.into();
+    }
+
+    let expected = match describe(conn, sql).await {
+        Ok(expected) => expected,
+        Err(_) => return err.into(),
+    };
+
+    for (index, param) in params.iter().enumerate() {
+        let param = *param;
+        let position = index + 1;
+        let column_type = match expected.iter().find(|(ordinal, _)| *ordinal == position) {
+            Some((_, column_type)) => column_type,
+            None => continue,
+        };
+        let args = MssqlParam::add_param(param, Vec::default());
+        let sql_type = match args.first() {
+            Some(arg) => sent_as(&arg.to_sql()),
+            None => continue,
+        };
+        if kind(sql_type) == kind(column_type) {
+            continue;
+        }
+        return Error::ParamType {
+            position,
+            rust_type: ParamValue::param_type(param),
+            sql_type: sql_type.to_owned(),
+            column_type: column_type.to_owned(),
+            message: err.to_string(),
+        };
+    }
+    err.into()
+}
+
+// the position and type MSSQL wants for each param of the statement
+async fn describe(conn: &mut DbConn, sql: &str) -> tiberius::Result<Vec<(usize, String)>> {
+    let describe = "EXEC sp_describe_undeclared_parameters @tsql = @P1";
+    let rows = conn
+        .query(describe, &[&sql])
+        .await?
+        .into_first_result()
+        .await?;
+    let mut expected = Vec::default();
+    for row in rows {
+        let ordinal: Option<i32> = row.try_get("parameter_ordinal")?;
+        let column_type: Option<&str> = row.try_get("suggested_system_type_name")?;
+        if let (Some(ordinal), Some(column_type)) = (ordinal, column_type) {
+            expected.push((ordinal as usize, column_type.to_owned()));
+        }
+    }
+    Ok(expected)
+}
+
+// the SQL type tiberius sends a value as
+fn sent_as(data: &ColumnData<'_>) -> &'static str {
+    match data {
+        ColumnData::U8(_) => "tinyint",
+        ColumnData::I16(_) => "smallint",
+        ColumnData::I32(_) => "int",
+        ColumnData::I64(_) => "bigint",
+        ColumnData::F32(_) => "real",
+        ColumnData::F64(_) => "float",
+        ColumnData::Bit(_) => "bit",
+        ColumnData::String(_) => "nvarchar",
+        ColumnData::Guid(_) => "uniqueidentifier",
+        ColumnData::Binary(_) => "varbinary",
+        ColumnData::Numeric(_) => "numeric",
+        ColumnData::Xml(_) => "xml",
+        ColumnData::DateTime(_) => "datetime",
+        ColumnData::SmallDateTime(_) => "smalldatetime",
+        ColumnData::Time(_) => "time",
+        ColumnData::Date(_) => "date",
+        ColumnData::DateTime2(_) => "datetime2",
+        ColumnData::DateTimeOffset(_) => "datetimeoffset",
+    }
+}
+
+// Types of the same kind convert into each other without an error
+#[derive(Debug, PartialEq, Eq)]
+enum Kind<'t> {
+    Number,
+    Text,
+    Time,
+    Binary,
+    Other(&'t str),
+}
+
+fn kind(sql_type: &str) -> Kind<'_> {
+    let name = sql_type.split('(').next().unwrap_or_default().trim();
+    match name.to_ascii_lowercase().as_str() {
+        "tinyint" | "smallint" | "int" | "bigint" | "real" | "float" | "bit" | "numeric"
+        | "decimal" | "money" | "smallmoney" => Kind::Number,
+        "nvarchar" | "varchar" | "nchar" | "char" | "ntext" | "text" | "sysname" => Kind::Text,
+        "datetime" | "smalldatetime" | "time" | "date" | "datetime2" | "datetimeoffset" => {
+            Kind::Time
+        }
+        "varbinary" | "binary" | "image" | "rowversion" | "timestamp" => Kind::Binary,
+        _ => Kind::Other(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tiberius::ToSql;
+
+    #[test]
+    fn should_compare_types_by_their_kind() {
+        assert_eq!(kind("int"), kind("bigint"));
+        assert_eq!(kind("nvarchar(4000)"), kind("varchar(50)"));
+        assert_eq!(kind("DATETIME2(7)"), kind("date"));
+        assert_ne!(kind("nvarchar"), kind("int"));
+        assert_ne!(kind("uniqueidentifier"), kind("xml"));
+    }
+
+    #[test]
+    fn should_name_the_type_a_param_is_sent_as() {
+        assert_eq!(sent_as(&5_i32.to_sql()), "int");
+        assert_eq!(sent_as(&"a".to_owned().to_sql()), "nvarchar");
+        assert_eq!(sent_as(&Some(1.5_f64).to_sql()), "float");
+    }
+}
@@ -2,21 +2,36 @@ use super::transaction::{TransT, Transaction};
 use super::Row;
 use super::TransactStart;
 use super::{Client, Param};
-use crate::errors::Result;
-use crate::ExecuteResult;
+use crate::credentials::Credentials;
+use crate::errors::{Error, Result};
+use crate::instrument::{client_builders, Hooks};
+use crate::{ExecuteResult, Namespace, Syntax};
 use async_trait::async_trait;
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 use tokio::sync::oneshot;
 
 use bb8::Pool;
 use bb8_tiberius::ConnectionManager;
-use tiberius::ToSql;
+use tiberius::{AuthMethod, Config, ToSql};
 
+mod diagnose;
 pub(crate) mod transaction;
+use diagnose::diagnose_bind;
 
 pub struct MssqlClient {
-    pool: Pool<ConnectionManager>,
+    // None once the client is closed
+    pool: RwLock<Option<Pool<ConnectionManager>>>,
+    settings: Option<PoolSettings>,
+    hooks: Hooks,
+}
+
+// What is needed to rebuild the pool when the credentials change
+struct PoolSettings {
+    cs: String,
+    timeout: Option<Duration>,
+    retry: Option<bool>,
+    max_connections: Option<usize>,
 }
 
 pub(crate) type DbConn = tiberius::Client<tokio_util::compat::Compat<tokio::net::TcpStream>>;
@@ -24,9 +39,10 @@ pub(crate) type DbConn = tiberius::Client<tokio_util::compat::Compat<tokio::net:
 #[async_trait]
 impl TransactStart for MssqlClient {
     async fn begin(&self) -> Result<Transaction> {
+        let hooks = self.hooks.for_transaction().await?;
         // WARNING: we are taking the connection out of the pool. we must put it back when we are finished
 
-        let conn = self.pool.dedicated_connection().await?;
+        let conn = self.pool()?.dedicated_connection().await?;
         let conn = Arc::new(Mutex::new(Some(conn)));
         let (tx, rx) = oneshot::channel();
 
@@ -49,7 +65,7 @@ impl TransactStart for MssqlClient {
             }
         });
 
-        Ok(Transaction::new(inner))
+        Ok(Transaction::new(inner).with_hooks(hooks))
     }
 }
 
@@ -59,29 +75,99 @@ pub async fn connect(
     retry: Option<bool>,
     max_connections: Option<usize>,
 ) -> Result<MssqlClient> {
+    let settings = PoolSettings {
+        cs: cs.to_owned(),
+        timeout,
+        retry,
+        max_connections,
+    };
     let mgr = bb8_tiberius::ConnectionManager::build(cs)?;
-    let mut pool = bb8::Pool::builder().max_size(max_connections.unwrap_or(2) as _);
-    if let Some(timeout) = timeout {
+    let pool = build_pool(mgr, &settings).await?;
+    Ok(MssqlClient {
+        pool: RwLock::new(Some(pool)),
+        settings: Some(settings),
+        hooks: Hooks::default(),
+    })
+}
+
+async fn build_pool(
+    mgr: ConnectionManager,
+    settings: &PoolSettings,
+) -> Result<Pool<ConnectionManager>> {
+    let mut pool = bb8::Pool::builder().max_size(settings.max_connections.unwrap_or(2) as _);
+    if let Some(timeout) = settings.timeout {
         pool = pool.connection_timeout(timeout);
     }
-    if let Some(retry) = retry {
+    if let Some(retry) = settings.retry {
         pool = pool.retry_connection(retry);
     }
-    let pool = pool.build(mgr).await.unwrap();
-    Ok(MssqlClient { pool })
+    Ok(pool.build(mgr).await?)
 }
 
 impl From<Pool<ConnectionManager>> for MssqlClient {
     fn from(pool: Pool<ConnectionManager>) -> MssqlClient {
-        MssqlClient { pool }
+        MssqlClient {
+            pool: RwLock::new(Some(pool)),
+            settings: None,
+            hooks: Hooks::default(),
+        }
     }
 }
 
 impl MssqlClient {
     /// Returns a reference to the underlying tiberius connection
     /// useful when you want to access the database yourself without welds
+    ///
+    /// Panics if the client has been closed
     pub fn as_tiberius_pool(&mut self) -> &mut Pool<ConnectionManager> {
-        &mut self.pool
+        self.pool
+            .get_mut()
+            .unwrap()
+            .as_mut()
+            .expect("the client has been closed")
+    }
+
+    client_builders!(pooled);
+
+    fn pool(&self) -> Result<Pool<ConnectionManager>> {
+        self.pool.read().unwrap().clone().ok_or(Error::ClientClosed)
+    }
+
+    /// Swaps the credentials used when opening new connections.
+    ///
+    /// A new pool is built with the new credentials. Connections that are in use finish on
+    /// the old pool, which is closed once they are all returned.
+    /// Only available on clients made with `connect`.
+    pub async fn update_credentials(&self, credentials: &Credentials) -> Result<()> {
+        let settings = self
+            .settings
+            .as_ref()
+            .ok_or(Error::CredentialsNotRotatable)?;
+        let mut config = Config::from_ado_string(&settings.cs)?;
+        config.authentication(AuthMethod::sql_server(
+            &credentials.username,
+            &credentials.password,
+        ));
+        let pool = build_pool(ConnectionManager::new(config), settings).await?;
+        let mut current = self.pool.write().unwrap();
+        if current.is_none() {
+            return Err(Error::ClientClosed);
+        }
+        *current = Some(pool);
+        Ok(())
+    }
+
+    /// Closes the client gracefully.
+    ///
+    /// New statements and transactions are refused, the ones already running get until the
+    /// timeout to finish, then the pool and all its connections are closed.
+    /// Fails with `CloseTimeout` if statements were still running at the timeout;
+    /// their connections are closed as they finish.
+    pub async fn close(&self, timeout: Duration) -> Result<()> {
+        let drained = self.hooks.close(Instant::now() + timeout).await;
+        // dropping the pool closes the idle connections, and the rest when they are returned
+        self.pool.write().unwrap().take();
+        drained
     }
 }
 
@@ -92,16 +178,26 @@ impl Client for MssqlClient {
         sql: &str,
         params: &[&(dyn Param + Sync + Send)],
     ) -> Result<ExecuteResult> {
-        let mut conn = self.pool.get().await?;
+        let _admitted = self.hooks.admit().await;
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let pool = self.pool()?;
+        let mut conn = pool.get().await?;
         let mut args: Vec<&dyn ToSql> = Vec::new();
         for &p in params {
             args = MssqlParam::add_param(p, args);
         }
-        log::debug!("MSSQL_EXEC: {}", sql);
-        let r = conn.execute(sql, &args).await?;
-        Ok(ExecuteResult {
-            rows_affected: r.rows_affected().iter().sum(),
-        })
+        self.hooks
+            .run(Syntax::Mssql, sql, params, async {
+                let r = match conn.execute(sql, &args).await {
+                    Ok(r) => r,
+                    Err(err) => return Err(diagnose_bind(&mut conn, sql, params, err).await),
+                };
+                Ok(ExecuteResult {
+                    rows_affected: r.rows_affected().iter().sum(),
+                })
+            })
+            .await
     }
 
     async fn fetch_rows(
@@ -109,46 +205,86 @@ impl Client for MssqlClient {
         sql: &str,
         params: &[&(dyn Param + Sync + Send)],
     ) -> Result<Vec<Row>> {
-        let mut conn = self.pool.get().await?;
+        let _admitted = self.hooks.admit().await;
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let pool = self.pool()?;
+        let mut conn = pool.get().await?;
         let mut args: Vec<&dyn ToSql> = Vec::new();
         for &p in params {
             args = MssqlParam::add_param(p, args);
         }
-        log::debug!("MSSQL_QUERY: {}", sql);
-        let stream = conn.query(sql, &args).await?;
-
-        let mssql_rows = stream.into_results().await?;
-        let mut all = Vec::default();
-        for batch in mssql_rows {
-            for r in batch {
-                all.push(Row::from(r))
-            }
+        self.hooks
+            .run(Syntax::Mssql, sql, params, async {
+                let mssql_rows = query(&mut conn, sql, &args, params).await?;
+                let mut all = Vec::default();
+                for batch in mssql_rows {
+                    for r in batch {
+                        all.push(Row::from(r))
+                    }
+                }
+                Ok(all)
+            })
+            .await
+    }
+
+    async fn fetch_result_sets(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<Vec<Vec<Row>>> {
+        let _admitted = self.hooks.admit().await;
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let pool = self.pool()?;
+        let mut conn = pool.get().await?;
+        let mut args: Vec<&dyn ToSql> = Vec::new();
+        for &p in params {
+            args = MssqlParam::add_param(p, args);
         }
-        Ok(all)
+        self.hooks
+            .run(Syntax::Mssql, sql, params, async {
+                let mssql_rows = query(&mut conn, sql, &args, params).await?;
+                let sets = mssql_rows
+                    .into_iter()
+                    .map(|batch| batch.into_iter().map(Row::from).collect())
+                    .collect();
+                Ok(sets)
+            })
+            .await
     }
 
     async fn fetch_many<'s, 'args, 't>(
         &self,
         args: &[crate::Fetch<'s, 'args, 't>],
     ) -> Result<Vec<Vec<Row>>> {
+        let _admitted = self.hooks.admit().await;
         let mut resultset = Vec::default();
-        let mut conn = self.pool.get().await?;
-        for fetch in args {
+        let pool = self.pool()?;
+        let mut conn = pool.get().await?;
+        for (index, fetch) in args.iter().enumerate() {
             let sql = fetch.sql;
             let params = fetch.params;
+            let annotated = self.hooks.annotate(sql);
+            let sql = annotated.as_ref();
             let mut args: Vec<&dyn ToSql> = Vec::new();
             for &p in params {
                 args = MssqlParam::add_param(p, args);
             }
-            log::debug!("MSSQL_QUERY: {}", sql);
-            let stream = conn.query(sql, &args).await?;
-            let mssql_rows = stream.into_results().await?;
-            let mut all = Vec::default();
-            for batch in mssql_rows {
-                for r in batch {
-                    all.push(Row::from(r))
-                }
-            }
+            let all = self
+                .hooks
+                .run_labeled(fetch.label, Syntax::Mssql, sql, params, async {
+                    let mssql_rows = query(&mut conn, sql, &args, params).await?;
+                    let mut all = Vec::default();
+                    for batch in mssql_rows {
+                        for r in batch {
+                            all.push(Row::from(r))
+                        }
+                    }
+                    Ok(all)
+                })
+                .await
+                .map_err(|err| fetch.failed(index, err))?;
             resultset.push(all)
         }
         Ok(resultset)
@@ -157,6 +293,35 @@ impl Client for MssqlClient {
     fn syntax(&self) -> crate::Syntax {
         crate::Syntax::Mssql
     }
+
+    fn namespace(&self) -> Option<&Namespace> {
+        self.hooks.namespace()
+    }
+
+    fn as_transact_start(&self) -> Option<&(dyn TransactStart + Sync)> {
+        Some(self)
+    }
+
+    fn as_mssql(&self) -> Option<&MssqlClient> {
+        Some(self)
+    }
+}
+
+// every result set of the statement. A failure because of the type of a param is diagnosed
+async fn query(
+    conn: &mut DbConn,
+    sql: &str,
+    args: &[&dyn ToSql],
+    params: &[&(dyn Param + Sync + Send)],
+) -> Result<Vec<Vec<tiberius::Row>>> {
+    let results = match conn.query(sql, args).await {
+        Ok(stream) => stream.into_results().await,
+        Err(err) => Err(err),
+    };
+    match results {
+        Ok(results) => Ok(results),
+        Err(err) => Err(diagnose_bind(conn, sql, params, err).await),
+    }
 }
 
 //impl<T> Param for T where T: MssqlParam {}
@@ -119,7 +119,6 @@ impl<'t> Client for MssqlTransaction<'t> {
         for &p in params {
             args = MssqlParam::add_param(p, args);
         }
-        log::debug!("MSSQL_TRANS_EXEC: {}", sql);
         let r = conn.execute(sql, &args).await;
         self.return_conn(conn);
         let r = r?;
@@ -163,6 +162,18 @@ impl<'t> Client for MssqlTransaction<'t> {
         results.drain(..).collect()
     }
 
+    async fn fetch_result_sets(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<Vec<Vec<Row>>> {
+        assert_eq!(self.state, State::Open);
+        let mut conn = self.take_conn();
+        let results = fetch_result_sets_inner(&mut conn, sql, params).await;
+        self.return_conn(conn);
+        results
+    }
+
     fn syntax(&self) -> crate::Syntax {
         crate::Syntax::Mssql
     }
@@ -173,23 +184,29 @@ async fn fetch_rows_inner<'t>(
     sql: &str,
     params: &[&(dyn Param + Sync + Send)],
 ) -> Result<Vec<Row>> {
+    let sets = fetch_result_sets_inner(conn, sql, params).await?;
+    Ok(sets.into_iter().flatten().collect())
+}
+
+async fn fetch_result_sets_inner<'t>(
+    conn: &mut DbConn,
+    sql: &str,
+    params: &[&(dyn Param + Sync + Send)],
+) -> Result<Vec<Vec<Row>>> {
     let mut args: Vec<&dyn ToSql> = Vec::new();
     for &p in params {
         args = MssqlParam::add_param(p, args);
     }
-    log::debug!("MSSQL_TRANS_QUERY: {}", sql);
 
     let stream = conn.query(sql, &args).await;
     let stream = stream?;
 
     let mssql_rows = stream.into_results().await?;
-    let mut all = Vec::default();
-    for batch in mssql_rows {
-        for r in batch {
-            all.push(Row::from(r))
-        }
-    }
-    Ok(all)
+    let sets = mssql_rows
+        .into_iter()
+        .map(|batch| batch.into_iter().map(Row::from).collect())
+        .collect();
+    Ok(sets)
 }
 
 impl<'t> Drop for MssqlTransaction<'t> {
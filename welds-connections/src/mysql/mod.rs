@@ -2,25 +2,38 @@ use super::transaction::{TransT, Transaction};
 use super::Row;
 use super::TransactStart;
 use super::{Client, Param};
+use crate::credentials::{close_idle, Credentials, Rotated};
+use crate::distributed::BranchConn;
 use crate::errors::Result;
-use crate::ExecuteResult;
+use crate::instrument::{client_builders, Hooks};
+use crate::shutdown::with_deadline;
+use crate::statement_cache::persistent;
+use crate::{ExecuteResult, Namespace, Syntax};
 use async_trait::async_trait;
 use sqlx::mysql::{MySqlArguments, MySqlPoolOptions};
 use sqlx::query::Query;
 use sqlx::{MySql, MySqlPool};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 pub struct MysqlClient {
     pool: Arc<MySqlPool>,
+    syntax: Syntax,
+    hooks: Hooks,
+    statement_cache: bool,
+    rotated: Rotated,
 }
 
 #[async_trait]
 impl TransactStart for MysqlClient {
     async fn begin(&self) -> Result<Transaction> {
+        let hooks = self.hooks.for_transaction().await?;
         let t = self.pool.begin().await?;
         let t = TransT::Mysql(t);
-        Ok(Transaction::new(t))
+        Ok(Transaction::new(t)
+            .with_syntax(self.syntax)
+            .with_hooks(hooks)
+            .with_statement_cache(self.statement_cache))
     }
 }
 
@@ -29,7 +42,8 @@ pub async fn connect(
     timeout: Option<Duration>,
     max_connections: Option<usize>,
 ) -> Result<MysqlClient> {
-    let mut pool = MySqlPoolOptions::new();
+    let rotated = Rotated::default();
+    let mut pool = rotated.retire_on_release(MySqlPoolOptions::new());
     if let Some(timeout) = timeout {
         pool = pool.acquire_timeout(timeout);
     }
@@ -39,13 +53,32 @@ pub async fn connect(
     let pool = pool.connect(url).await?;
     Ok(MysqlClient {
         pool: Arc::new(pool),
+        syntax: Syntax::Mysql,
+        hooks: Hooks::default(),
+        statement_cache: true,
+        rotated,
     })
 }
 
+/// Connects to a MariaDB server.
+/// MariaDB speaks the mysql protocol, use a `mysql://` url
+pub async fn connect_mariadb(
+    url: &str,
+    timeout: Option<Duration>,
+    max_connections: Option<usize>,
+) -> Result<MysqlClient> {
+    let client = connect(url, timeout, max_connections).await?;
+    Ok(client.as_mariadb())
+}
+
 impl From<sqlx::MySqlPool> for MysqlClient {
     fn from(pool: sqlx::MySqlPool) -> MysqlClient {
         MysqlClient {
             pool: Arc::new(pool),
+            syntax: Syntax::Mysql,
+            hooks: Hooks::default(),
+            statement_cache: true,
+            rotated: Rotated::default(),
         }
     }
 }
@@ -54,6 +87,79 @@ impl MysqlClient {
     pub fn as_sqlx_pool(&self) -> &MySqlPool {
         &self.pool
     }
+
+    /// Marks this client as talking to MariaDB instead of MySql
+    pub fn as_mariadb(mut self) -> Self {
+        self.syntax = Syntax::MariaDb;
+        self
+    }
+
+    client_builders!(pooled);
+
+    /// Turns caching of prepared statements on or off for this client (and its transactions).
+    ///
+    /// On by default. Each cached statement holds a server-side handle, so turn it off if
+    /// the server is near `max_prepared_stmt_count`.
+    /// Use `statement_cache::cache_statements` to override it for a block of work.
+    pub fn with_statement_cache(mut self, enabled: bool) -> Self {
+        self.statement_cache = enabled;
+        self
+    }
+
+    /// Starts a read only transaction that sees the database as it was when it started,
+    /// with `START TRANSACTION WITH CONSISTENT SNAPSHOT`. Every read in it is from the same
+    /// snapshot, which is what reports and exports that span several queries need.
+    ///
+    /// The snapshot only holds under `REPEATABLE READ` (the InnoDB default).
+    pub async fn begin_consistent_snapshot(&self) -> Result<Transaction<'_>> {
+        let hooks = self.hooks.for_transaction().await?;
+        let t = self
+            .pool
+            .begin_with("START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY")
+            .await?;
+        let t = TransT::Mysql(t);
+        Ok(Transaction::new(t)
+            .with_syntax(self.syntax)
+            .with_hooks(hooks)
+            .with_statement_cache(self.statement_cache))
+    }
+
+    /// A connection of its own for a branch of a `DistributedTransaction`
+    pub(crate) async fn branch_conn(&self) -> Result<(BranchConn, Hooks, bool)> {
+        let hooks = self.hooks.for_transaction().await?;
+        let conn = self.pool.acquire().await?;
+        Ok((BranchConn::Mysql(conn), hooks, self.statement_cache))
+    }
+
+    /// Closes the client gracefully.
+    ///
+    /// New statements and transactions are refused, the ones already running get until the
+    /// timeout to finish, then all the pooled connections are closed.
+    /// Fails with `CloseTimeout` if statements were still running at the timeout;
+    /// their connections are closed as they finish.
+    pub async fn close(&self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        let drained = self.hooks.close(deadline).await;
+        with_deadline(deadline, self.pool.close()).await;
+        drained
+    }
+
+    /// Swaps the credentials used when opening new connections.
+    ///
+    /// Idle connections are closed so they are re-opened with the new credentials.
+    /// Connections that are in use keep the old credentials until they are returned to the
+    /// pool, then they are closed. On a client made `From` a pool they are only retired by
+    /// the pool's max lifetime.
+    pub async fn update_credentials(&self, credentials: &Credentials) -> Result<()> {
+        let options = (*self.pool.connect_options())
+            .clone()
+            .username(&credentials.username)
+            .password(&credentials.password);
+        self.pool.set_connect_options(options);
+        self.rotated.now();
+        close_idle(&self.pool).await;
+        Ok(())
+    }
 }
 
 use sqlx::encode::Encode;
@@ -66,14 +172,21 @@ impl Client for MysqlClient {
         sql: &str,
         params: &[&(dyn Param + Sync + Send)],
     ) -> Result<ExecuteResult> {
-        let mut query = sqlx::query::<MySql>(sql);
+        let _admitted = self.hooks.admit().await;
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let mut query = sqlx::query::<MySql>(sql).persistent(persistent(self.statement_cache));
         for param in params {
             query = MysqlParam::add_param(*param, query);
         }
-        let r = query.execute(&*self.pool).await?;
-        Ok(ExecuteResult {
-            rows_affected: r.rows_affected(),
-        })
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                let r = query.execute(&*self.pool).await?;
+                Ok(ExecuteResult {
+                    rows_affected: r.rows_affected(),
+                })
+            })
+            .await
     }
 
     async fn fetch_rows(
@@ -81,37 +194,66 @@ impl Client for MysqlClient {
         sql: &str,
         params: &[&(dyn Param + Sync + Send)],
     ) -> Result<Vec<Row>> {
-        let mut query = sqlx::query::<MySql>(sql);
+        let _admitted = self.hooks.admit().await;
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let mut query = sqlx::query::<MySql>(sql).persistent(persistent(self.statement_cache));
         for param in params {
             query = MysqlParam::add_param(*param, query);
         }
-        let mut raw_rows = query.fetch_all(&*self.pool).await?;
-        let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
-        Ok(rows)
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                let mut raw_rows = query.fetch_all(&*self.pool).await?;
+                let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+                Ok(rows)
+            })
+            .await
     }
 
     async fn fetch_many<'s, 'args, 't>(
         &self,
         fetches: &[crate::Fetch<'s, 'args, 't>],
     ) -> Result<Vec<Vec<Row>>> {
+        let _admitted = self.hooks.admit().await;
         let mut datasets = Vec::default();
         let mut conn = self.pool.acquire().await?;
-        for fetch in fetches {
+        for (index, fetch) in fetches.iter().enumerate() {
             let sql = fetch.sql;
             let params = fetch.params;
-            let mut query = sqlx::query::<MySql>(sql);
+            let annotated = self.hooks.annotate(sql);
+            let sql = annotated.as_ref();
+            let mut query = sqlx::query::<MySql>(sql).persistent(persistent(self.statement_cache));
             for param in params {
                 query = MysqlParam::add_param(*param, query);
             }
-            let mut raw_rows = query.fetch_all(&mut *conn).await?;
-            let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+            let rows = self
+                .hooks
+                .run_labeled(fetch.label, self.syntax, sql, params, async {
+                    let mut raw_rows = query.fetch_all(&mut *conn).await?;
+                    let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+                    Ok(rows)
+                })
+                .await
+                .map_err(|err| fetch.failed(index, err))?;
             datasets.push(rows);
         }
         Ok(datasets)
     }
 
     fn syntax(&self) -> crate::Syntax {
-        crate::Syntax::Mysql
+        self.syntax
+    }
+
+    fn namespace(&self) -> Option<&Namespace> {
+        self.hooks.namespace()
+    }
+
+    fn as_transact_start(&self) -> Option<&(dyn TransactStart + Sync)> {
+        Some(self)
+    }
+
+    fn as_mysql(&self) -> Option<&MysqlClient> {
+        Some(self)
     }
 }
 
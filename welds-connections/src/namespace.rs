@@ -0,0 +1,130 @@
+use crate::errors::{Error, Result};
+
+/// Where the tables of a client's statements live, for apps that keep each tenant in its own
+/// schema (or behind a table prefix) of a shared database.
+/// Set on a client with `with_schema` or `with_table_prefix`.
+///
+/// The tables of the models are written in the namespace when welds writes the SQL for the
+/// client (and its transactions), so one set of models serves every tenant:
+///
+/// ```rust,ignore
+/// let tenant = client.clone().with_schema("tenant_42")?;
+/// let orders = Order::all().run(&tenant).await?;
+/// // SELECT t1."id", t1."total" FROM tenant_42.orders t1
+/// ```
+///
+/// Only the tables welds writes are moved: the tables of models, and the join tables of their
+/// relationships. A model that names its schema (`schema = "public"`) stays in it, a prefix is
+/// still put on its table. SQL handed to the client as text is sent as it is, system catalogs,
+/// temp tables and CTEs in it included; use `table` to name a tenant's tables in it.
+///
+/// The tables are qualified in the SQL rather than with a Postgres `search_path` or MySQL `USE`,
+/// those would stay set on the pooled connection for the next client to use it.
+///
+/// The schema or prefix is written into the SQL as is, so it must be a plain identifier:
+/// letters, digits and underscores, not starting with a digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Namespace {
+    /// Tables are qualified with a schema: a Postgres or MSSQL schema, a MySQL database,
+    /// or an attached Sqlite database
+    Schema(String),
+    /// Table names are prefixed: `users` is `tenant_42_users`
+    TablePrefix(String),
+}
+
+impl Namespace {
+    /// Fails with `InvalidNamespace` unless the schema or prefix is a plain identifier
+    pub fn validate(&self) -> Result<()> {
+        let name = match self {
+            Namespace::Schema(name) | Namespace::TablePrefix(name) => name,
+        };
+        let mut chars = name.chars();
+        let starts_ok = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+        if starts_ok && chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Ok(());
+        }
+        Err(Error::InvalidNamespace(name.to_owned()))
+    }
+
+    /// The name a table is written with in this namespace. A table that names its schema
+    /// (`audit.events`) stays in it, a prefix is still put on its name (`audit.t42_events`)
+    pub fn table(&self, table: &str) -> String {
+        let (schema, name) = match table.rsplit_once('.') {
+            Some((schema, name)) => (Some(schema), name),
+            None => (None, table),
+        };
+        match (self, schema) {
+            (Namespace::Schema(_), Some(_)) => table.to_owned(),
+            (Namespace::Schema(schema), None) => format!("{}.{}", schema, name),
+            (Namespace::TablePrefix(prefix), Some(schema)) => {
+                format!("{}.{}", schema, prefixed(prefix, name))
+            }
+            (Namespace::TablePrefix(prefix), None) => prefixed(prefix, name),
+        }
+    }
+
+    /// How the columns of a table are written (`table.column`) in this namespace.
+    /// A schema doesn't change it, a prefix renames the table
+    pub fn table_alias(&self, table: &str) -> String {
+        match self {
+            Namespace::Schema(_) => table.to_owned(),
+            Namespace::TablePrefix(_) => self.table(table),
+        }
+    }
+}
+
+// the prefix goes inside the quotes of a quoted name
+fn prefixed(prefix: &str, table: &str) -> String {
+    match table.chars().next() {
+        Some(quote @ ('"' | '`' | '[')) => format!("{}{}{}", quote, prefix, &table[1..]),
+        _ => format!("{}{}", prefix, table),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> Namespace {
+        Namespace::Schema("tenant_42".to_owned())
+    }
+
+    #[test]
+    fn should_only_take_identifiers() {
+        assert!(schema().validate().is_ok());
+        assert!(Namespace::TablePrefix("_t42_".to_owned())
+            .validate()
+            .is_ok());
+        for name in [
+            "",
+            "42tenant",
+            "tenant.x",
+            "t; DROP TABLE users",
+            "t\"",
+            "tenant-42",
+        ] {
+            let err = Namespace::Schema(name.to_owned()).validate().err().unwrap();
+            assert!(matches!(err, Error::InvalidNamespace(_)), "{name}");
+        }
+    }
+
+    #[test]
+    fn should_write_the_tables_in_the_schema() {
+        assert_eq!(schema().table("users"), "tenant_42.users");
+        assert_eq!(schema().table("\"users\""), "tenant_42.\"users\"");
+        // a table that names its schema stays in it
+        assert_eq!(schema().table("audit.events"), "audit.events");
+        assert_eq!(schema().table_alias("users"), "users");
+    }
+
+    #[test]
+    fn should_prefix_the_tables_inside_their_quotes() {
+        let prefix = Namespace::TablePrefix("t42_".to_owned());
+        assert_eq!(prefix.table("users"), "t42_users");
+        assert_eq!(prefix.table("[roles]"), "[t42_roles]");
+        assert_eq!(prefix.table("audit.\"events\""), "audit.\"t42_events\"");
+        assert_eq!(prefix.table_alias("users"), "t42_users");
+    }
+}
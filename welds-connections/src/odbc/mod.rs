@@ -0,0 +1,502 @@
+use super::Row;
+use super::{Client, Param};
+use crate::errors::{Error, Result};
+use crate::instrument::{client_builders, Hooks};
+use crate::row::{RowInner, ValueRow};
+use crate::transaction::{TransT, Transaction};
+use crate::value::{ParamValue, Value};
+use crate::{ExecuteResult, Namespace, Syntax, TransactStart};
+use async_trait::async_trait;
+use odbc_api::parameter::InputParameter;
+use odbc_api::{
+    Bit, ConnectionOptions, Cursor, CursorRow, DataType, Environment, IntoParameter, Nullable,
+    ResultSetMetadata,
+};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinError;
+
+pub(crate) mod transaction;
+
+type Connection = odbc_api::Connection<'static>;
+
+// The connections a pool opens when `max_connections` isn't given
+const DEFAULT_MAX_CONNECTIONS: usize = 10;
+
+// The ODBC driver manager environment is shared by every connection in the process
+static ENVIRONMENT: OnceLock<Environment> = OnceLock::new();
+
+fn environment() -> Result<&'static Environment> {
+    if let Some(env) = ENVIRONMENT.get() {
+        return Ok(env);
+    }
+    let env = Environment::new()?;
+    Ok(ENVIRONMENT.get_or_init(|| env))
+}
+
+/// A client that talks to any database with an ODBC driver installed.
+///
+/// ODBC drivers are blocking, all work is done on tokio's blocking thread pool.
+/// The SQL dialect is picked from the name the driver reports for the DBMS.
+/// If the dialect is guessed wrong, it can be set with `with_syntax`,
+/// or given up front with `connect_with_syntax`
+pub struct OdbcClient {
+    pool: Arc<ConnectionPool>,
+    dbms_name: String,
+    syntax: Syntax,
+    hooks: Hooks,
+}
+
+struct ConnectionPool {
+    cs: String,
+    login_timeout_sec: Option<u32>,
+    idle: Mutex<Vec<Connection>>,
+    permits: Arc<Semaphore>,
+    closed: AtomicBool,
+}
+
+impl ConnectionPool {
+    fn open(&self) -> Result<Connection> {
+        let env = environment()?;
+        let options = ConnectionOptions {
+            login_timeout_sec: self.login_timeout_sec,
+            ..Default::default()
+        };
+        Ok(env.connect_with_connection_string(&self.cs, options)?)
+    }
+
+    fn take(&self) -> Option<Connection> {
+        self.idle.lock().unwrap().pop()
+    }
+
+    fn give_back(&self, conn: Connection) {
+        // connections returned after the pool is closed are dropped (disconnected)
+        if !self.closed.load(Ordering::SeqCst) {
+            self.idle.lock().unwrap().push(conn);
+        }
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.idle.lock().unwrap().clear();
+    }
+}
+
+/// Returns a connection pool (OdbcClient) for the given ODBC connection string.
+/// example: `Driver={PostgreSQL Unicode};Server=localhost;Database=app;Uid=user;Pwd=pass;`
+///
+/// The pool opens up to `max_connections` connections, 10 by default.
+/// Databases welds doesn't know the SQL syntax of are written to in a best-effort syntax
+/// (see `syntax_from_dbms`), use `connect_with_syntax` to pick one.
+pub async fn connect(
+    cs: &str,
+    timeout: Option<Duration>,
+    max_connections: Option<usize>,
+) -> Result<OdbcClient> {
+    open_pool(cs, None, timeout, max_connections).await
+}
+
+/// Returns a connection pool (OdbcClient) that writes SQL in the given syntax,
+/// whatever database the driver reports.
+pub async fn connect_with_syntax(
+    cs: &str,
+    syntax: Syntax,
+    timeout: Option<Duration>,
+    max_connections: Option<usize>,
+) -> Result<OdbcClient> {
+    open_pool(cs, Some(syntax), timeout, max_connections).await
+}
+
+async fn open_pool(
+    cs: &str,
+    syntax: Option<Syntax>,
+    timeout: Option<Duration>,
+    max_connections: Option<usize>,
+) -> Result<OdbcClient> {
+    let pool = Arc::new(ConnectionPool {
+        cs: cs.to_owned(),
+        login_timeout_sec: timeout.map(|t| t.as_secs() as u32),
+        idle: Mutex::new(Vec::default()),
+        permits: Arc::new(Semaphore::new(pool_size(max_connections))),
+        closed: AtomicBool::new(false),
+    });
+
+    // open the first connection up front to validate the connection string and look up the DBMS
+    let first = pool.clone();
+    let dbms_name = joined(
+        tokio::task::spawn_blocking(move || {
+            let conn = first.open()?;
+            let name = conn.database_management_system_name()?;
+            first.give_back(conn);
+            Result::Ok(name)
+        })
+        .await,
+    )?;
+
+    let syntax = match syntax {
+        Some(syntax) => syntax,
+        None => syntax_from_dbms(&dbms_name),
+    };
+    log::debug!("ODBC connected to {} using {:?} syntax", dbms_name, syntax);
+
+    Ok(OdbcClient {
+        pool,
+        dbms_name,
+        syntax,
+        hooks: Hooks::default(),
+    })
+}
+
+impl OdbcClient {
+    /// Overrides the SQL dialect that was detected when connecting
+    pub fn with_syntax(mut self, syntax: Syntax) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
+    client_builders!();
+
+    /// Closes the client gracefully.
+    ///
+    /// New statements are refused, the ones already running get until the timeout to
+    /// finish, then all the pooled connections are closed.
+    /// Fails with `CloseTimeout` if statements were still running at the timeout;
+    /// their connections are closed as they finish.
+    pub async fn close(&self, timeout: Duration) -> Result<()> {
+        let drained = self.hooks.close(Instant::now() + timeout).await;
+        self.pool.close();
+        drained
+    }
+
+    /// The name of the database as reported by the ODBC driver
+    pub fn dbms_name(&self) -> &str {
+        &self.dbms_name
+    }
+
+    // Runs the given work on a pooled connection in tokio's blocking thread pool.
+    // Connections that hit an error are dropped rather than returned to the pool
+    async fn run<F, R>(&self, work: F) -> Result<R>
+    where
+        F: FnOnce(&Connection) -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let _permit = self
+            .pool
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("ODBC pool semaphore is never closed");
+        let pool = self.pool.clone();
+        joined(
+            tokio::task::spawn_blocking(move || {
+                let conn = match pool.take() {
+                    Some(conn) => conn,
+                    None => pool.open()?,
+                };
+                let result = work(&conn);
+                if result.is_ok() {
+                    pool.give_back(conn);
+                }
+                result
+            })
+            .await,
+        )
+    }
+}
+
+// A pool of no connections would wait forever
+fn pool_size(max_connections: Option<usize>) -> usize {
+    max_connections.unwrap_or(DEFAULT_MAX_CONNECTIONS).max(1)
+}
+
+// The result of work done on the blocking thread pool, or an error if the thread didn't finish it
+fn joined<R>(result: std::result::Result<Result<R>, JoinError>) -> Result<R> {
+    result.map_err(|err| Error::OdbcWorker(err.to_string()))?
+}
+
+/// Picks the welds syntax for the DBMS an ODBC driver is connected to.
+///
+/// Databases with no matching syntax get the Postgres syntax, the closest welds has to
+/// standard SQL: double quoted identifiers and `?` placeholders (ODBC's own).
+/// It is a best effort, SQL welds writes that the database doesn't understand fails there
+fn syntax_from_dbms(dbms_name: &str) -> Syntax {
+    let name = dbms_name.to_lowercase();
+    if name.contains("sql server") {
+        return Syntax::Mssql;
+    }
+    if name.contains("cockroach") {
+        return Syntax::CockroachDb;
+    }
+    if name.contains("postgres") {
+        return Syntax::Postgres;
+    }
+    if name.contains("mariadb") {
+        return Syntax::MariaDb;
+    }
+    if name.contains("mysql") {
+        return Syntax::Mysql;
+    }
+    if name.contains("sqlite") {
+        return Syntax::Sqlite;
+    }
+    log::warn!(
+        "welds has no syntax for {}, writing SQL for it in the Postgres syntax",
+        dbms_name
+    );
+    Syntax::Postgres
+}
+
+/// ODBC only understands `?` placeholders.
+/// rewrites the numbered placeholders of postgres ($1) and mssql (@p1) into `?`
+fn positional_placeholders(sql: &str, syntax: Syntax) -> Cow<'_, str> {
+    let prefix = match syntax {
+        Syntax::Postgres => "$",
+        Syntax::Mssql => "@p",
+        _ => return Cow::Borrowed(sql),
+    };
+    let mut out = String::with_capacity(sql.len());
+    let mut in_string = false;
+    let mut rest = sql;
+    while let Some(c) = rest.chars().next() {
+        if c == '\'' {
+            in_string = !in_string;
+        }
+        if !in_string && rest.starts_with(prefix) {
+            let after = &rest[prefix.len()..];
+            let digits = after.chars().take_while(|c| c.is_ascii_digit()).count();
+            if digits > 0 {
+                out.push('?');
+                rest = &after[digits..];
+                continue;
+            }
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    Cow::Owned(out)
+}
+
+// Copies the params out, to be sent from the worker thread.
+// Fails for params of types that aren't numbers, bools, text or bytes
+fn to_values(params: &[&(dyn Param + Sync + Send)]) -> Result<Vec<Value>> {
+    params
+        .iter()
+        .enumerate()
+        .map(|(index, p)| {
+            // called on the `dyn Param`, a reference to it would be read as a param of its own
+            let param: &(dyn Param + Sync + Send) = *p;
+            sendable(index + 1, param.param_value(), param.param_type())
+        })
+        .collect()
+}
+
+fn sendable(position: usize, value: Option<Value>, rust_type: &'static str) -> Result<Value> {
+    match value {
+        Some(Value::Opaque(_)) | None => Err(Error::UnsupportedParam {
+            position,
+            rust_type,
+        }),
+        Some(value) => Ok(value),
+    }
+}
+
+fn bind(values: &[Value]) -> Vec<Box<dyn InputParameter>> {
+    values
+        .iter()
+        .map(|v| -> Box<dyn InputParameter> {
+            match v {
+                Value::Null => Box::new(Nullable::<i64>::null()),
+                Value::Bool(b) => Box::new(Bit::from_bool(*b)),
+                Value::Int(i) => Box::new(*i),
+                Value::Float(f) => Box::new(*f),
+                Value::Text(s) => Box::new(s.clone().into_parameter()),
+                Value::Bytes(b) => Box::new(b.clone().into_parameter()),
+                // sent as text, the database converts it to the type of the column
+                Value::Uuid(_) | Value::DateTime(_) => Box::new(v.to_string().into_parameter()),
+                Value::Opaque(_) => unreachable!("to_values refuses opaque params"),
+            }
+        })
+        .collect()
+}
+
+fn exec(conn: &Connection, sql: &str, values: &[Value]) -> Result<u64> {
+    let params = bind(values);
+    let mut stmt = conn.preallocate()?;
+    stmt.execute(sql, params.as_slice())?;
+    let affected = stmt.row_count()?.unwrap_or_default();
+    Ok(affected as u64)
+}
+
+fn query(conn: &Connection, sql: &str, values: &[Value]) -> Result<Vec<Row>> {
+    let params = bind(values);
+    let mut rows = Vec::default();
+    let mut cursor = match conn.execute(sql, params.as_slice(), None)? {
+        Some(cursor) => cursor,
+        None => return Ok(rows),
+    };
+
+    let count = cursor.num_result_cols()? as u16;
+    let mut names = Vec::with_capacity(count as usize);
+    let mut types = Vec::with_capacity(count as usize);
+    for col in 1..=count {
+        names.push(cursor.col_name(col)?);
+        types.push(cursor.col_data_type(col)?);
+    }
+    let names = Arc::new(names);
+
+    while let Some(mut row) = cursor.next_row()? {
+        let mut cells = Vec::with_capacity(count as usize);
+        for (i, data_type) in types.iter().enumerate() {
+            cells.push(read_cell(&mut row, i as u16 + 1, data_type)?);
+        }
+        rows.push(Row {
+            inner: RowInner::Odbc(ValueRow::new(names.clone(), cells)),
+        });
+    }
+    Ok(rows)
+}
+
+fn read_cell(row: &mut CursorRow, col: u16, data_type: &DataType) -> Result<Value> {
+    let value = match data_type {
+        DataType::TinyInt | DataType::SmallInt | DataType::Integer | DataType::BigInt => {
+            let mut v = Nullable::<i64>::null();
+            row.get_data(col, &mut v)?;
+            v.into_opt().map(Value::Int)
+        }
+        DataType::Real | DataType::Float { .. } | DataType::Double => {
+            let mut v = Nullable::<f64>::null();
+            row.get_data(col, &mut v)?;
+            v.into_opt().map(Value::Float)
+        }
+        DataType::Bit => {
+            let mut v = Nullable::<Bit>::null();
+            row.get_data(col, &mut v)?;
+            v.into_opt().map(|b| Value::Bool(b.as_bool()))
+        }
+        DataType::Binary { .. } | DataType::Varbinary { .. } | DataType::LongVarbinary { .. } => {
+            let mut buf = Vec::new();
+            let not_null = row.get_binary(col, &mut buf)?;
+            not_null.then_some(Value::Bytes(buf))
+        }
+        // Everything else (text, decimals, dates, ...) is read as text
+        _ => {
+            let mut buf = Vec::new();
+            let not_null = row.get_text(col, &mut buf)?;
+            not_null.then(|| Value::Text(String::from_utf8_lossy(&buf).into_owned()))
+        }
+    };
+    Ok(value.unwrap_or(Value::Null))
+}
+
+#[async_trait]
+impl Client for OdbcClient {
+    async fn execute(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<ExecuteResult> {
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let odbc_sql = positional_placeholders(sql, self.syntax).into_owned();
+        let values = to_values(params)?;
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                let rows_affected = self.run(move |conn| exec(conn, &odbc_sql, &values)).await?;
+                Ok(ExecuteResult { rows_affected })
+            })
+            .await
+    }
+
+    async fn fetch_rows(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<Vec<Row>> {
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let odbc_sql = positional_placeholders(sql, self.syntax).into_owned();
+        let values = to_values(params)?;
+        let work = self.run(move |conn| query(conn, &odbc_sql, &values));
+        self.hooks.run(self.syntax, sql, params, work).await
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        fetches: &[crate::Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        // The hooks can't be called from the worker thread.
+        // Each statement is run on its own so they are still timed one at a time
+        if self.hooks.is_active() {
+            let mut datasets = Vec::default();
+            for (index, fetch) in fetches.iter().enumerate() {
+                let rows = self.fetch_rows(fetch.sql, fetch.params).await;
+                datasets.push(rows.map_err(|err| fetch.failed(index, err))?);
+            }
+            return Ok(datasets);
+        }
+
+        let _in_flight = self.hooks.track()?;
+        let work = fetches
+            .iter()
+            .enumerate()
+            .map(|(index, fetch)| {
+                let sql = positional_placeholders(fetch.sql, self.syntax).into_owned();
+                let label = fetch.label.map(|l| l.to_owned());
+                let values = to_values(fetch.params).map_err(|err| fetch.failed(index, err))?;
+                Ok((sql, values, label))
+            })
+            .collect::<Result<Vec<(String, Vec<Value>, Option<String>)>>>()?;
+        self.run(move |conn| {
+            let mut datasets = Vec::default();
+            for (index, (sql, values, label)) in work.iter().enumerate() {
+                let rows = query(conn, sql, values).map_err(|err| Error::FetchMany {
+                    index,
+                    label: label.clone(),
+                    source: Box::new(err),
+                })?;
+                datasets.push(rows);
+            }
+            Ok(datasets)
+        })
+        .await
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+
+    fn namespace(&self) -> Option<&Namespace> {
+        self.hooks.namespace()
+    }
+
+    fn as_transact_start(&self) -> Option<&(dyn TransactStart + Sync)> {
+        Some(self)
+    }
+
+    fn as_odbc(&self) -> Option<&OdbcClient> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl TransactStart for OdbcClient {
+    async fn begin(&self) -> Result<Transaction> {
+        let hooks = self.hooks.for_transaction().await?;
+        let trans = transaction::OdbcTransaction::begin(self.pool.clone(), self.syntax).await?;
+        Ok(Transaction::new(TransT::Odbc(trans)).with_hooks(hooks))
+    }
+}
+
+/// The params the ODBC backend can send.
+/// Numbers, bools, text, bytes and an `Option` of any of them are sent, a param of any other
+/// type fails the statement with `UnsupportedParam`
+pub trait OdbcParam: ParamValue {}
+
+impl<T> OdbcParam for T where T: ParamValue {}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,92 @@
+use super::*;
+
+#[test]
+fn should_pick_the_syntax_from_the_dbms_name() {
+    assert_eq!(syntax_from_dbms("Microsoft SQL Server"), Syntax::Mssql);
+    assert_eq!(syntax_from_dbms("PostgreSQL"), Syntax::Postgres);
+    assert_eq!(syntax_from_dbms("CockroachDB"), Syntax::CockroachDb);
+    assert_eq!(syntax_from_dbms("MySQL"), Syntax::Mysql);
+    assert_eq!(syntax_from_dbms("MariaDB"), Syntax::MariaDb);
+    assert_eq!(syntax_from_dbms("SQLite"), Syntax::Sqlite);
+}
+
+#[test]
+fn should_fall_back_to_the_postgres_syntax_for_an_unknown_dbms() {
+    assert_eq!(syntax_from_dbms("Oracle"), Syntax::Postgres);
+}
+
+#[test]
+fn should_size_the_pool() {
+    assert_eq!(pool_size(None), DEFAULT_MAX_CONNECTIONS);
+    assert_eq!(pool_size(Some(25)), 25);
+    assert_eq!(pool_size(Some(0)), 1);
+}
+
+#[test]
+fn should_rewrite_postgres_placeholders() {
+    let sql = "SELECT * FROM t WHERE a = $1 AND b = $12";
+    assert_eq!(
+        positional_placeholders(sql, Syntax::Postgres),
+        "SELECT * FROM t WHERE a = ? AND b = ?"
+    );
+}
+
+#[test]
+fn should_rewrite_mssql_placeholders() {
+    let sql = "SELECT * FROM t WHERE a = @p1 AND b = @p2";
+    assert_eq!(
+        positional_placeholders(sql, Syntax::Mssql),
+        "SELECT * FROM t WHERE a = ? AND b = ?"
+    );
+}
+
+#[test]
+fn should_not_rewrite_placeholders_in_strings() {
+    let sql = "SELECT '$1 it''s $2' FROM t WHERE a = $1 AND b = '$'";
+    assert_eq!(
+        positional_placeholders(sql, Syntax::Postgres),
+        "SELECT '$1 it''s $2' FROM t WHERE a = ? AND b = '$'"
+    );
+}
+
+#[test]
+fn should_leave_question_mark_placeholders_alone() {
+    let sql = "SELECT * FROM t WHERE a = ? AND b = '$1'";
+    assert!(matches!(
+        positional_placeholders(sql, Syntax::Mysql),
+        Cow::Borrowed(_)
+    ));
+    assert!(matches!(
+        positional_placeholders(sql, Syntax::Sqlite),
+        Cow::Borrowed(_)
+    ));
+}
+
+#[test]
+fn should_copy_the_params_it_can_send() {
+    let id = 1_i64;
+    let name = Some("ann".to_owned());
+    let nothing: Option<i32> = None;
+    let values = to_values(&[&id, &name, &nothing]).unwrap();
+    assert_eq!(
+        values,
+        [Value::Int(1), Value::Text("ann".to_owned()), Value::Null]
+    );
+}
+
+#[test]
+fn should_refuse_params_it_cant_send() {
+    let when = std::time::SystemTime::UNIX_EPOCH;
+    let err = sendable(2, when.param_value(), when.param_type())
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err,
+        Error::UnsupportedParam {
+            position: 2,
+            rust_type: "std::time::SystemTime"
+        }
+    ));
+    let opaque = Some(Value::Opaque("uuid::Uuid".to_owned()));
+    assert!(sendable(1, opaque, "welds_connections::value::Value").is_err());
+}
@@ -0,0 +1,145 @@
+use super::{exec, joined, positional_placeholders, query, to_values, Connection, ConnectionPool};
+use crate::errors::Result;
+use crate::{ExecuteResult, Param, Row, Syntax};
+use std::sync::{Arc, Mutex};
+use tokio::sync::OwnedSemaphorePermit;
+
+/// A transaction on one of the connections of an `OdbcClient`.
+///
+/// Autocommit is turned off on the connection while the transaction is open, it goes back
+/// to the pool once the transaction is committed or rolled back.
+/// A transaction that is dropped before it finishes is rolled back
+pub(crate) struct OdbcTransaction {
+    pool: Arc<ConnectionPool>,
+    conn: Arc<Mutex<Option<Connection>>>,
+    permit: Option<OwnedSemaphorePermit>,
+    syntax: Syntax,
+}
+
+impl OdbcTransaction {
+    pub(crate) async fn begin(pool: Arc<ConnectionPool>, syntax: Syntax) -> Result<Self> {
+        let permit = pool
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("ODBC pool semaphore is never closed");
+        let opening = pool.clone();
+        let conn = joined(
+            tokio::task::spawn_blocking(move || {
+                let conn = match opening.take() {
+                    Some(conn) => conn,
+                    None => opening.open()?,
+                };
+                conn.set_autocommit(false)?;
+                Result::Ok(conn)
+            })
+            .await,
+        )?;
+        Ok(Self {
+            pool,
+            conn: Arc::new(Mutex::new(Some(conn))),
+            permit: Some(permit),
+            syntax,
+        })
+    }
+
+    pub(crate) fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+
+    pub(crate) async fn execute(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<ExecuteResult> {
+        let sql = positional_placeholders(sql, self.syntax).into_owned();
+        let values = to_values(params)?;
+        let rows_affected = self.run(move |conn| exec(conn, &sql, &values)).await?;
+        Ok(ExecuteResult { rows_affected })
+    }
+
+    pub(crate) async fn fetch_rows(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<Vec<Row>> {
+        let sql = positional_placeholders(sql, self.syntax).into_owned();
+        let values = to_values(params)?;
+        self.run(move |conn| query(conn, &sql, &values)).await
+    }
+
+    pub(crate) async fn commit(mut self) -> Result<()> {
+        self.finish(true).await
+    }
+
+    pub(crate) async fn rollback(mut self) -> Result<()> {
+        self.finish(false).await
+    }
+
+    // Runs the given work on the transaction's connection in tokio's blocking thread pool
+    async fn run<F, R>(&self, work: F) -> Result<R>
+    where
+        F: FnOnce(&Connection) -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let conn = self.conn.clone();
+        joined(
+            tokio::task::spawn_blocking(move || {
+                let conn = conn.lock().unwrap();
+                let conn = conn.as_ref().expect("held until the transaction finishes");
+                work(conn)
+            })
+            .await,
+        )
+    }
+
+    // Ends the transaction and gives the connection back to the pool.
+    // A connection that fails to end it is dropped, the driver rolls back what is left
+    async fn finish(&mut self, commit: bool) -> Result<()> {
+        let conn = self.conn.lock().unwrap().take();
+        let conn = conn.expect("held until the transaction finishes");
+        let pool = self.pool.clone();
+        let permit = self.permit.take();
+        joined(
+            tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                match commit {
+                    true => conn.commit()?,
+                    false => conn.rollback()?,
+                }
+                conn.set_autocommit(true)?;
+                pool.give_back(conn);
+                Result::Ok(())
+            })
+            .await,
+        )
+    }
+}
+
+impl Drop for OdbcTransaction {
+    fn drop(&mut self) {
+        // a statement still running holds the connection, it is dropped when that finishes
+        let conn = match self.conn.try_lock() {
+            Ok(mut conn) => conn.take(),
+            Err(_) => None,
+        };
+        let Some(conn) = conn else {
+            return;
+        };
+        let pool = self.pool.clone();
+        let permit = self.permit.take();
+        let rollback = move || {
+            let _permit = permit;
+            if conn.rollback().is_ok() && conn.set_autocommit(true).is_ok() {
+                pool.give_back(conn);
+            }
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(rollback);
+            }
+            Err(_) => rollback(),
+        }
+    }
+}
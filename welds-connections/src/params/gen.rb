@@ -19,6 +19,14 @@ use super::postgres::PostgresParam;
 
 #[cfg(feature = "mysql")]
 use super::mysql::MysqlParam;
+
+#[cfg(feature = "odbc")]
+use super::odbc::OdbcParam;
+
+#[cfg(feature = "mock")]
+use super::mock::MockParam;
+
+use super::value::ParamValue;
 |
 
 
@@ -27,12 +35,13 @@ def blocky(cfgs, structs, wheres)
   %Q|
 
 #{cfgs}
-pub trait Param: #{structs.join(" + ")} {}
+pub trait Param: #{structs.join(" + ")} + ParamValue {}
 
 #{cfgs}
 impl<T> Param for T
  where
      #{wheres},
+     T: ParamValue,
      for<'a> &'a T: Send,
  {}
   
@@ -46,11 +55,13 @@ p = [
   ["postgres",true , ["'a", "Send","Encode<'a, sqlx::Postgres>", "Type<sqlx::Postgres>"]],
   ["mysql"   ,true , ["'a", "Send","Encode<'a, sqlx::MySql>", "Type<sqlx::MySql>"]],
   ["mssql"   ,false, ["MssqlParam"]],
+  ["odbc"    ,false, ["OdbcParam"]],
+  ["mock"    ,false, ["MockParam"]],
 ]
 
-cc = p.combination(1) + p.combination(2) + p.combination(3) + p.combination(4)
+cc = p.combination(1) + p.combination(2) + p.combination(3) + p.combination(4) + p.combination(5) + p.combination(6)
 
-all = ["sqlite", "postgres", "mysql", "mssql"]
+all = ["sqlite", "postgres", "mysql", "mssql", "odbc", "mock"]
 
 out_code = head
 
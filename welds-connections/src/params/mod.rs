@@ -5,115 +5,1421 @@ use sqlx::encode::Encode;
 #[cfg(any(feature = "mysql", feature = "sqlite", feature = "postgres"))]
 use sqlx::types::Type;
 
+#[cfg(feature = "mssql")]
+use super::mssql::MssqlParam;
 #[cfg(feature = "sqlite")]
 use super::sqlite::SqliteParam;
 
-#[cfg(feature = "mssql")]
-use super::mssql::MssqlParam;
+#[cfg(feature = "postgres")]
+use super::postgres::PostgresParam;
+
+#[cfg(feature = "mysql")]
+use super::mysql::MysqlParam;
+
+#[cfg(feature = "odbc")]
+use super::odbc::OdbcParam;
+
+#[cfg(feature = "mock")]
+use super::mock::MockParam;
+
+use super::value::ParamValue;
+
+#[cfg(all(
+    feature = "sqlite",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Sqlite> + Type<sqlx::Sqlite>,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: PostgresParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Postgres> + Type<sqlx::Postgres>,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mysql",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: MysqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mysql",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::MySql> + Type<sqlx::MySql>,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: MssqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    T: MssqlParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+pub trait Param: OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    T: OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+pub trait Param: MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    T: MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + PostgresParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + MysqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mssql",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + MssqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mssql",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Sqlite> + Type<sqlx::Sqlite> + MssqlParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "odbc",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "odbc",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Sqlite> + Type<sqlx::Sqlite> + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+pub trait Param: SqliteParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Sqlite> + Type<sqlx::Sqlite> + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    not(feature = "sqlite"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: PostgresParam + MysqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    not(feature = "sqlite"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: PostgresParam + MssqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Postgres> + Type<sqlx::Postgres> + MssqlParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+pub trait Param: PostgresParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Postgres> + Type<sqlx::Postgres> + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+pub trait Param: PostgresParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Postgres> + Type<sqlx::Postgres> + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: MysqlParam + MssqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::MySql> + Type<sqlx::MySql> + MssqlParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+pub trait Param: MysqlParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::MySql> + Type<sqlx::MySql> + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+pub trait Param: MysqlParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::MySql> + Type<sqlx::MySql> + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mock")
+))]
+pub trait Param: MssqlParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    T: MssqlParam + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc")
+))]
+pub trait Param: MssqlParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    T: MssqlParam + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql")
+))]
+pub trait Param: OdbcParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql")
+))]
+impl<T> Param for T
+where
+    T: OdbcParam + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + PostgresParam + MysqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mssql",
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + PostgresParam + MssqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mssql",
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + MssqlParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "odbc",
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + PostgresParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "odbc",
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mock",
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+pub trait Param: SqliteParam + PostgresParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mock",
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "postgres"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + MysqlParam + MssqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "postgres"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MssqlParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + MysqlParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+pub trait Param: SqliteParam + MysqlParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + MssqlParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Sqlite> + Type<sqlx::Sqlite> + MssqlParam + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc")
+))]
+pub trait Param: SqliteParam + MssqlParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Sqlite> + Type<sqlx::Sqlite> + MssqlParam + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql")
+))]
+pub trait Param: SqliteParam + OdbcParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::Sqlite> + Type<sqlx::Sqlite> + OdbcParam + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: PostgresParam + MysqlParam + MssqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MssqlParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+pub trait Param: PostgresParam + MysqlParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+pub trait Param: PostgresParam + MysqlParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mock")
+))]
+pub trait Param: PostgresParam + MssqlParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T:
+        'a + Send + Encode<'a, sqlx::Postgres> + Type<sqlx::Postgres> + MssqlParam + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "odbc")
+))]
+pub trait Param: PostgresParam + MssqlParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T:
+        'a + Send + Encode<'a, sqlx::Postgres> + Type<sqlx::Postgres> + MssqlParam + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql")
+))]
+pub trait Param: PostgresParam + OdbcParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql")
+))]
+impl<T> Param for T
+where
+    for<'a> T:
+        'a + Send + Encode<'a, sqlx::Postgres> + Type<sqlx::Postgres> + OdbcParam + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mock")
+))]
+pub trait Param: MysqlParam + MssqlParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::MySql> + Type<sqlx::MySql> + MssqlParam + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "odbc")
+))]
+pub trait Param: MysqlParam + MssqlParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::MySql> + Type<sqlx::MySql> + MssqlParam + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql")
+))]
+pub trait Param: MysqlParam + OdbcParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a + Send + Encode<'a, sqlx::MySql> + Type<sqlx::MySql> + OdbcParam + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql")
+))]
+pub trait Param: MssqlParam + OdbcParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql")
+))]
+impl<T> Param for T
+where
+    T: MssqlParam + OdbcParam + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + PostgresParam + MysqlParam + MssqlParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MssqlParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+pub trait Param: SqliteParam + PostgresParam + MysqlParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
 
-#[cfg(feature = "postgres")]
-use super::postgres::PostgresParam;
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+pub trait Param: SqliteParam + PostgresParam + MysqlParam + MockParam + ParamValue {}
 
-#[cfg(feature = "mysql")]
-use super::mysql::MysqlParam;
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
 
 #[cfg(all(
     feature = "sqlite",
-    not(feature = "postgres"),
+    feature = "postgres",
+    feature = "mssql",
+    feature = "odbc",
     not(feature = "mysql"),
-    not(feature = "mssql")
+    not(feature = "mock")
 ))]
-pub trait Param: SqliteParam {}
+pub trait Param: SqliteParam + PostgresParam + MssqlParam + OdbcParam + ParamValue {}
 
 #[cfg(all(
     feature = "sqlite",
-    not(feature = "postgres"),
+    feature = "postgres",
+    feature = "mssql",
+    feature = "odbc",
     not(feature = "mysql"),
-    not(feature = "mssql")
+    not(feature = "mock")
 ))]
 impl<T> Param for T
 where
-    for<'a> T: 'a + Send + Encode<'a, sqlx::Sqlite> + Type<sqlx::Sqlite>,
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + MssqlParam
+        + OdbcParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
 
 #[cfg(all(
+    feature = "sqlite",
     feature = "postgres",
-    not(feature = "sqlite"),
+    feature = "mssql",
+    feature = "mock",
     not(feature = "mysql"),
-    not(feature = "mssql")
+    not(feature = "odbc")
 ))]
-pub trait Param: PostgresParam {}
+pub trait Param: SqliteParam + PostgresParam + MssqlParam + MockParam + ParamValue {}
 
 #[cfg(all(
+    feature = "sqlite",
     feature = "postgres",
-    not(feature = "sqlite"),
+    feature = "mssql",
+    feature = "mock",
     not(feature = "mysql"),
-    not(feature = "mssql")
+    not(feature = "odbc")
 ))]
 impl<T> Param for T
 where
-    for<'a> T: 'a + Send + Encode<'a, sqlx::Postgres> + Type<sqlx::Postgres>,
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + MssqlParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
 
 #[cfg(all(
-    feature = "mysql",
-    not(feature = "sqlite"),
-    not(feature = "postgres"),
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "mysql"),
     not(feature = "mssql")
 ))]
-pub trait Param: MysqlParam {}
+pub trait Param: SqliteParam + PostgresParam + OdbcParam + MockParam + ParamValue {}
 
 #[cfg(all(
-    feature = "mysql",
-    not(feature = "sqlite"),
-    not(feature = "postgres"),
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "mysql"),
     not(feature = "mssql")
 ))]
 impl<T> Param for T
 where
-    for<'a> T: 'a + Send + Encode<'a, sqlx::MySql> + Type<sqlx::MySql>,
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
 
 #[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
     feature = "mssql",
-    not(feature = "sqlite"),
+    feature = "odbc",
     not(feature = "postgres"),
-    not(feature = "mysql")
+    not(feature = "mock")
 ))]
-pub trait Param: MssqlParam {}
+pub trait Param: SqliteParam + MysqlParam + MssqlParam + OdbcParam + ParamValue {}
 
 #[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
     feature = "mssql",
-    not(feature = "sqlite"),
+    feature = "odbc",
     not(feature = "postgres"),
-    not(feature = "mysql")
+    not(feature = "mock")
 ))]
 impl<T> Param for T
 where
-    T: MssqlParam,
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MssqlParam
+        + OdbcParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
 
 #[cfg(all(
     feature = "sqlite",
-    feature = "postgres",
-    not(feature = "mysql"),
-    not(feature = "mssql")
+    feature = "mysql",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "odbc")
 ))]
-pub trait Param: SqliteParam + PostgresParam {}
+pub trait Param: SqliteParam + MysqlParam + MssqlParam + MockParam + ParamValue {}
 
 #[cfg(all(
     feature = "sqlite",
-    feature = "postgres",
-    not(feature = "mysql"),
-    not(feature = "mssql")
+    feature = "mysql",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "odbc")
 ))]
 impl<T> Param for T
 where
@@ -121,8 +1427,11 @@ where
         + Send
         + Encode<'a, sqlx::Sqlite>
         + Type<sqlx::Sqlite>
-        + Encode<'a, sqlx::Postgres>
-        + Type<sqlx::Postgres>,
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MssqlParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -130,14 +1439,18 @@ where
 #[cfg(all(
     feature = "sqlite",
     feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "postgres"),
     not(feature = "mssql")
 ))]
-pub trait Param: SqliteParam + MysqlParam {}
+pub trait Param: SqliteParam + MysqlParam + OdbcParam + MockParam + ParamValue {}
 
 #[cfg(all(
     feature = "sqlite",
     feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "postgres"),
     not(feature = "mssql")
 ))]
@@ -148,7 +1461,10 @@ where
         + Encode<'a, sqlx::Sqlite>
         + Type<sqlx::Sqlite>
         + Encode<'a, sqlx::MySql>
-        + Type<sqlx::MySql>,
+        + Type<sqlx::MySql>
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -156,20 +1472,97 @@ where
 #[cfg(all(
     feature = "sqlite",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "postgres"),
     not(feature = "mysql")
 ))]
-pub trait Param: SqliteParam + MssqlParam {}
+pub trait Param: SqliteParam + MssqlParam + OdbcParam + MockParam + ParamValue {}
 
 #[cfg(all(
     feature = "sqlite",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "postgres"),
     not(feature = "mysql")
 ))]
 impl<T> Param for T
 where
-    for<'a> T: 'a + Send + Encode<'a, sqlx::Sqlite> + Type<sqlx::Sqlite> + MssqlParam,
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + MssqlParam
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mock")
+))]
+pub trait Param: PostgresParam + MysqlParam + MssqlParam + OdbcParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MssqlParam
+        + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "odbc")
+))]
+pub trait Param: PostgresParam + MysqlParam + MssqlParam + MockParam + ParamValue {}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MssqlParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -177,14 +1570,18 @@ where
 #[cfg(all(
     feature = "postgres",
     feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite"),
     not(feature = "mssql")
 ))]
-pub trait Param: PostgresParam + MysqlParam {}
+pub trait Param: PostgresParam + MysqlParam + OdbcParam + MockParam + ParamValue {}
 
 #[cfg(all(
     feature = "postgres",
     feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite"),
     not(feature = "mssql")
 ))]
@@ -195,7 +1592,10 @@ where
         + Encode<'a, sqlx::Postgres>
         + Type<sqlx::Postgres>
         + Encode<'a, sqlx::MySql>
-        + Type<sqlx::MySql>,
+        + Type<sqlx::MySql>
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -203,20 +1603,31 @@ where
 #[cfg(all(
     feature = "postgres",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite"),
     not(feature = "mysql")
 ))]
-pub trait Param: PostgresParam + MssqlParam {}
+pub trait Param: PostgresParam + MssqlParam + OdbcParam + MockParam + ParamValue {}
 
 #[cfg(all(
     feature = "postgres",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite"),
     not(feature = "mysql")
 ))]
 impl<T> Param for T
 where
-    for<'a> T: 'a + Send + Encode<'a, sqlx::Postgres> + Type<sqlx::Postgres> + MssqlParam,
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + MssqlParam
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -224,20 +1635,107 @@ where
 #[cfg(all(
     feature = "mysql",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite"),
     not(feature = "postgres")
 ))]
-pub trait Param: MysqlParam + MssqlParam {}
+pub trait Param: MysqlParam + MssqlParam + OdbcParam + MockParam + ParamValue {}
 
 #[cfg(all(
     feature = "mysql",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite"),
     not(feature = "postgres")
 ))]
 impl<T> Param for T
 where
-    for<'a> T: 'a + Send + Encode<'a, sqlx::MySql> + Type<sqlx::MySql> + MssqlParam,
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MssqlParam
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "mock")
+))]
+pub trait Param:
+    SqliteParam + PostgresParam + MysqlParam + MssqlParam + OdbcParam + ParamValue
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "mock")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MssqlParam
+        + OdbcParam,
+    T: ParamValue,
+    for<'a> &'a T: Send,
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "odbc")
+))]
+pub trait Param:
+    SqliteParam + PostgresParam + MysqlParam + MssqlParam + MockParam + ParamValue
+{
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "odbc")
+))]
+impl<T> Param for T
+where
+    for<'a> T: 'a
+        + Send
+        + Encode<'a, sqlx::Sqlite>
+        + Type<sqlx::Sqlite>
+        + Encode<'a, sqlx::Postgres>
+        + Type<sqlx::Postgres>
+        + Encode<'a, sqlx::MySql>
+        + Type<sqlx::MySql>
+        + MssqlParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -246,14 +1744,21 @@ where
     feature = "sqlite",
     feature = "postgres",
     feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "mssql")
 ))]
-pub trait Param: SqliteParam + PostgresParam + MysqlParam {}
+pub trait Param:
+    SqliteParam + PostgresParam + MysqlParam + OdbcParam + MockParam + ParamValue
+{
+}
 
 #[cfg(all(
     feature = "sqlite",
     feature = "postgres",
     feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "mssql")
 ))]
 impl<T> Param for T
@@ -265,7 +1770,10 @@ where
         + Encode<'a, sqlx::Postgres>
         + Type<sqlx::Postgres>
         + Encode<'a, sqlx::MySql>
-        + Type<sqlx::MySql>,
+        + Type<sqlx::MySql>
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -274,14 +1782,21 @@ where
     feature = "sqlite",
     feature = "postgres",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "mysql")
 ))]
-pub trait Param: SqliteParam + PostgresParam + MssqlParam {}
+pub trait Param:
+    SqliteParam + PostgresParam + MssqlParam + OdbcParam + MockParam + ParamValue
+{
+}
 
 #[cfg(all(
     feature = "sqlite",
     feature = "postgres",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "mysql")
 ))]
 impl<T> Param for T
@@ -292,7 +1807,10 @@ where
         + Type<sqlx::Sqlite>
         + Encode<'a, sqlx::Postgres>
         + Type<sqlx::Postgres>
-        + MssqlParam,
+        + MssqlParam
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -301,14 +1819,21 @@ where
     feature = "sqlite",
     feature = "mysql",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "postgres")
 ))]
-pub trait Param: SqliteParam + MysqlParam + MssqlParam {}
+pub trait Param:
+    SqliteParam + MysqlParam + MssqlParam + OdbcParam + MockParam + ParamValue
+{
+}
 
 #[cfg(all(
     feature = "sqlite",
     feature = "mysql",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "postgres")
 ))]
 impl<T> Param for T
@@ -319,7 +1844,10 @@ where
         + Type<sqlx::Sqlite>
         + Encode<'a, sqlx::MySql>
         + Type<sqlx::MySql>
-        + MssqlParam,
+        + MssqlParam
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -328,14 +1856,21 @@ where
     feature = "postgres",
     feature = "mysql",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite")
 ))]
-pub trait Param: PostgresParam + MysqlParam + MssqlParam {}
+pub trait Param:
+    PostgresParam + MysqlParam + MssqlParam + OdbcParam + MockParam + ParamValue
+{
+}
 
 #[cfg(all(
     feature = "postgres",
     feature = "mysql",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite")
 ))]
 impl<T> Param for T
@@ -346,7 +1881,10 @@ where
         + Type<sqlx::Postgres>
         + Encode<'a, sqlx::MySql>
         + Type<sqlx::MySql>
-        + MssqlParam,
+        + MssqlParam
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -355,15 +1893,22 @@ where
     feature = "sqlite",
     feature = "postgres",
     feature = "mysql",
-    feature = "mssql"
+    feature = "mssql",
+    feature = "odbc",
+    feature = "mock"
 ))]
-pub trait Param: SqliteParam + PostgresParam + MysqlParam + MssqlParam {}
+pub trait Param:
+    SqliteParam + PostgresParam + MysqlParam + MssqlParam + OdbcParam + MockParam + ParamValue
+{
+}
 
 #[cfg(all(
     feature = "sqlite",
     feature = "postgres",
     feature = "mysql",
-    feature = "mssql"
+    feature = "mssql",
+    feature = "odbc",
+    feature = "mock"
 ))]
 impl<T> Param for T
 where
@@ -375,7 +1920,10 @@ where
         + Type<sqlx::Postgres>
         + Encode<'a, sqlx::MySql>
         + Type<sqlx::MySql>
-        + MssqlParam,
+        + MssqlParam
+        + OdbcParam
+        + MockParam,
+    T: ParamValue,
     for<'a> &'a T: Send,
 {
 }
@@ -0,0 +1,61 @@
+use super::PostgresParam;
+use crate::errors::Error;
+use crate::Param;
+use sqlx::postgres::PgTypeInfo;
+use sqlx::{Either, Executor, Postgres, TypeInfo};
+
+// The errors postgres gives when a param was bound with a type it can't use
+// undefined_function (operator does not exist: integer = text),
+// datatype_mismatch (column "id" is of type integer but expression is of type text),
+// invalid_binary_representation (incorrect binary data format in bind parameter 1)
+const BIND_TYPE_ERRORS: &[&str] = &["42883", "42804", "22P03"];
+
+/// Turns a failed statement into a `ParamType` error when it failed because of the type of a param.
+///
+/// The statement is described again without the param types, so postgres infers the types it
+/// wants from the columns the params are used with. The first param that can't be sent as that
+/// type is reported. Any other error is returned as is.
+pub(super) async fn diagnose_bind<'e, E>(
+    executor: E,
+    sql: &str,
+    params: &[&(dyn Param + Sync + Send)],
+    err: sqlx::Error,
+) -> Error
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let is_bind_error = match &err {
+        sqlx::Error::Database(db) => db
+            .code()
+            .map(|code| BIND_TYPE_ERRORS.contains(&code.as_ref()))
+            .unwrap_or_default(),
+        _ => false,
+    };
+    if !is_bind_error {
+        return err.into();
+    }
+
+    let described = match executor.describe(sql).await {
+        Ok(described) => described,
+        Err(_) => return err.into(),
+    };
+    let expected: &[PgTypeInfo] = match described.parameters() {
+        Some(Either::Left(expected)) => expected,
+        _ => return err.into(),
+    };
+
+    for (index, (param, expected)) in params.iter().zip(expected).enumerate() {
+        let param = *param;
+        if PostgresParam::compatible(param, expected) {
+            continue;
+        }
+        return Error::ParamType {
+            position: index + 1,
+            rust_type: PostgresParam::rust_type(param),
+            sql_type: PostgresParam::type_info(param).name().to_string(),
+            column_type: expected.name().to_string(),
+            message: err.to_string(),
+        };
+    }
+    err.into()
+}
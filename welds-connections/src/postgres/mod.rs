@@ -2,26 +2,42 @@ use super::transaction::{TransT, Transaction};
 use super::Row;
 use super::TransactStart;
 use super::{Client, Param};
+use crate::credentials::{close_idle, Credentials, Rotated};
+use crate::distributed::BranchConn;
 use crate::errors::Result;
-use crate::ExecuteResult;
+use crate::instrument::{client_builders, Hooks};
+use crate::shutdown::with_deadline;
+use crate::statement_cache::persistent;
+use crate::{ExecuteResult, Namespace, Syntax};
 use async_trait::async_trait;
-use sqlx::postgres::{PgArguments, PgPoolOptions};
+use sqlx::postgres::{PgArguments, PgPoolOptions, PgTypeInfo};
 use sqlx::query::Query;
 use sqlx::{PgPool, Postgres};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+mod diagnose;
+use diagnose::diagnose_bind;
 
 #[derive(Debug, Clone)]
 pub struct PostgresClient {
     pool: Arc<PgPool>,
+    syntax: Syntax,
+    hooks: Hooks,
+    statement_cache: bool,
+    rotated: Rotated,
 }
 
 #[async_trait]
 impl TransactStart for PostgresClient {
     async fn begin(&self) -> Result<Transaction> {
+        let hooks = self.hooks.for_transaction().await?;
         let t = self.pool.begin().await?;
         let t = TransT::Postgres(t);
-        Ok(Transaction::new(t))
+        Ok(Transaction::new(t)
+            .with_syntax(self.syntax)
+            .with_hooks(hooks)
+            .with_statement_cache(self.statement_cache))
     }
 }
 
@@ -30,7 +46,8 @@ pub async fn connect(
     timeout: Option<Duration>,
     max_connections: Option<usize>,
 ) -> Result<PostgresClient> {
-    let mut pool = PgPoolOptions::new();
+    let rotated = Rotated::default();
+    let mut pool = rotated.retire_on_release(PgPoolOptions::new());
     if let Some(timeout) = timeout {
         pool = pool.acquire_timeout(timeout);
     }
@@ -40,13 +57,32 @@ pub async fn connect(
     let pool = pool.connect(url).await?;
     Ok(PostgresClient {
         pool: Arc::new(pool),
+        syntax: Syntax::Postgres,
+        hooks: Hooks::default(),
+        statement_cache: true,
+        rotated,
     })
 }
 
+/// Connects to a CockroachDB cluster.
+/// CockroachDB speaks the postgres wire protocol, use a `postgresql://` url
+pub async fn connect_cockroachdb(
+    url: &str,
+    timeout: Option<Duration>,
+    max_connections: Option<usize>,
+) -> Result<PostgresClient> {
+    let client = connect(url, timeout, max_connections).await?;
+    Ok(client.as_cockroachdb())
+}
+
 impl From<sqlx::PgPool> for PostgresClient {
     fn from(pool: sqlx::PgPool) -> PostgresClient {
         PostgresClient {
             pool: Arc::new(pool),
+            syntax: Syntax::Postgres,
+            hooks: Hooks::default(),
+            statement_cache: true,
+            rotated: Rotated::default(),
         }
     }
 }
@@ -55,6 +91,62 @@ impl PostgresClient {
     pub fn as_sqlx_pool(&self) -> &PgPool {
         &self.pool
     }
+
+    /// Swaps the credentials used when opening new connections.
+    ///
+    /// Idle connections are closed so they are re-opened with the new credentials.
+    /// Connections that are in use keep the old credentials until they are returned to the
+    /// pool, then they are closed. On a client made `From` a pool they are only retired by
+    /// the pool's max lifetime.
+    pub async fn update_credentials(&self, credentials: &Credentials) -> Result<()> {
+        let options = (*self.pool.connect_options())
+            .clone()
+            .username(&credentials.username)
+            .password(&credentials.password);
+        self.pool.set_connect_options(options);
+        self.rotated.now();
+        close_idle(&self.pool).await;
+        Ok(())
+    }
+
+    /// Marks this client as talking to CockroachDB instead of Postgres
+    pub fn as_cockroachdb(mut self) -> Self {
+        self.syntax = Syntax::CockroachDb;
+        self
+    }
+
+    client_builders!(pooled);
+
+    /// Turns caching of prepared statements on or off for this client (and its transactions).
+    ///
+    /// On by default: statements are prepared once per connection and re-used, which saves
+    /// re-parsing the very repetitive SQL models generate. Turn it off when going through a
+    /// pooler that can't keep prepared statements (PgBouncer in transaction mode).
+    /// Use `statement_cache::cache_statements` to override it for a block of work.
+    pub fn with_statement_cache(mut self, enabled: bool) -> Self {
+        self.statement_cache = enabled;
+        self
+    }
+
+    /// A connection of its own for a branch of a `DistributedTransaction`
+    pub(crate) async fn branch_conn(&self) -> Result<(BranchConn, Hooks, bool)> {
+        let hooks = self.hooks.for_transaction().await?;
+        let conn = self.pool.acquire().await?;
+        Ok((BranchConn::Postgres(conn), hooks, self.statement_cache))
+    }
+
+    /// Closes the client gracefully.
+    ///
+    /// New statements and transactions are refused, the ones already running get until the
+    /// timeout to finish, then all the pooled connections are closed.
+    /// Fails with `CloseTimeout` if statements were still running at the timeout;
+    /// their connections are closed as they finish.
+    pub async fn close(&self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        let drained = self.hooks.close(deadline).await;
+        with_deadline(deadline, self.pool.close()).await;
+        drained
+    }
 }
 
 use sqlx::encode::Encode;
@@ -67,14 +159,24 @@ impl Client for PostgresClient {
         sql: &str,
         params: &[&(dyn Param + Sync + Send)],
     ) -> Result<ExecuteResult> {
-        let mut query = sqlx::query::<Postgres>(sql).persistent(false);
+        let _admitted = self.hooks.admit().await;
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let mut query = sqlx::query::<Postgres>(sql).persistent(persistent(self.statement_cache));
         for param in params {
             query = PostgresParam::add_param(*param, query);
         }
-        let r = query.execute(&*self.pool).await?;
-        Ok(ExecuteResult {
-            rows_affected: r.rows_affected(),
-        })
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                let r = match query.execute(&*self.pool).await {
+                    Ok(r) => r,
+                    Err(err) => return Err(diagnose_bind(&*self.pool, sql, params, err).await),
+                };
+                Ok(ExecuteResult {
+                    rows_affected: r.rows_affected(),
+                })
+            })
+            .await
     }
 
     async fn fetch_rows(
@@ -82,37 +184,73 @@ impl Client for PostgresClient {
         sql: &str,
         params: &[&(dyn Param + Sync + Send)],
     ) -> Result<Vec<Row>> {
-        let mut query = sqlx::query::<Postgres>(sql).persistent(false);
+        let _admitted = self.hooks.admit().await;
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let mut query = sqlx::query::<Postgres>(sql).persistent(persistent(self.statement_cache));
         for param in params {
             query = PostgresParam::add_param(*param, query);
         }
-        let mut raw_rows = query.fetch_all(&*self.pool).await?;
-        let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
-        Ok(rows)
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                let mut raw_rows = match query.fetch_all(&*self.pool).await {
+                    Ok(rows) => rows,
+                    Err(err) => return Err(diagnose_bind(&*self.pool, sql, params, err).await),
+                };
+                let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+                Ok(rows)
+            })
+            .await
     }
 
     async fn fetch_many<'s, 'args, 't>(
         &self,
         fetches: &[crate::Fetch<'s, 'args, 't>],
     ) -> Result<Vec<Vec<Row>>> {
+        let _admitted = self.hooks.admit().await;
         let mut datasets = Vec::default();
         let mut conn = self.pool.acquire().await?;
-        for fetch in fetches {
+        for (index, fetch) in fetches.iter().enumerate() {
             let sql = fetch.sql;
             let params = fetch.params;
-            let mut query = sqlx::query::<Postgres>(sql).persistent(false);
+            let annotated = self.hooks.annotate(sql);
+            let sql = annotated.as_ref();
+            let mut query =
+                sqlx::query::<Postgres>(sql).persistent(persistent(self.statement_cache));
             for param in params {
                 query = PostgresParam::add_param(*param, query);
             }
-            let mut raw_rows = query.fetch_all(&mut *conn).await?;
-            let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+            let rows = self
+                .hooks
+                .run_labeled(fetch.label, self.syntax, sql, params, async {
+                    let mut raw_rows = match query.fetch_all(&mut *conn).await {
+                        Ok(rows) => rows,
+                        Err(err) => return Err(diagnose_bind(&mut *conn, sql, params, err).await),
+                    };
+                    let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+                    Ok(rows)
+                })
+                .await
+                .map_err(|err| fetch.failed(index, err))?;
             datasets.push(rows);
         }
         Ok(datasets)
     }
 
     fn syntax(&self) -> crate::Syntax {
-        crate::Syntax::Postgres
+        self.syntax
+    }
+
+    fn namespace(&self) -> Option<&Namespace> {
+        self.hooks.namespace()
+    }
+
+    fn as_transact_start(&self) -> Option<&(dyn TransactStart + Sync)> {
+        Some(self)
+    }
+
+    fn as_postgres(&self) -> Option<&PostgresClient> {
+        Some(self)
     }
 }
 
@@ -121,6 +259,15 @@ pub trait PostgresParam {
         &'q self,
         query: Query<'q, Postgres, PgArguments>,
     ) -> Query<'q, Postgres, PgArguments>;
+
+    /// The name of the rust type of the param, for error messages
+    fn rust_type(&self) -> &'static str;
+
+    /// The postgres type the param is sent as
+    fn type_info(&self) -> PgTypeInfo;
+
+    /// True if the param can be sent to a column of this type
+    fn compatible(&self, ty: &PgTypeInfo) -> bool;
 }
 
 impl<T> PostgresParam for T
@@ -134,4 +281,31 @@ where
     ) -> Query<'q, Postgres, PgArguments> {
         query.bind(self)
     }
+
+    fn rust_type(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn type_info(&self) -> PgTypeInfo {
+        <T as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(&self, ty: &PgTypeInfo) -> bool {
+        <T as Type<Postgres>>::compatible(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_cache_statements_unless_turned_off() {
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://welds@localhost/welds")
+            .unwrap();
+        let client = PostgresClient::from(pool);
+        assert!(client.statement_cache);
+        assert!(!client.with_statement_cache(false).statement_cache);
+    }
 }
@@ -0,0 +1,337 @@
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// How urgently a statement needs a connection when the pool is busy.
+/// Only used by clients with a priority queue (`with_priority_queue`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Someone is waiting on it (a web request, a UI)
+    Interactive,
+    #[default]
+    Normal,
+    /// Batch jobs, reports, backfills. Only gets a connection when nothing more urgent is waiting
+    Background,
+}
+
+thread_local! {
+    // The priority of the task that is currently being polled
+    static CURRENT: Cell<Option<Priority>> = const { Cell::new(None) };
+}
+
+/// Runs the future with every statement (and transaction) it starts queued at this priority.
+///
+/// ```rust,ignore
+/// let client = client.with_priority_queue(10, Duration::from_secs(5));
+/// priority::with_priority(Priority::Background, async {
+///     nightly_rollup(&client).await
+/// })
+/// .await?;
+/// ```
+///
+/// Tasks spawned from inside the future do not inherit the priority.
+pub fn with_priority<F: Future>(priority: Priority, future: F) -> WithPriority<F> {
+    WithPriority {
+        inner: Box::pin(future),
+        priority,
+    }
+}
+
+/// A future whose statements are queued at a priority. See `with_priority`
+pub struct WithPriority<F> {
+    inner: Pin<Box<F>>,
+    priority: Priority,
+}
+
+impl<F: Future> Future for WithPriority<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let guard = Restore(CURRENT.with(|c| c.replace(Some(this.priority))));
+        let poll = this.inner.as_mut().poll(cx);
+        drop(guard);
+        poll
+    }
+}
+
+// Puts the outer priority back, even on panic
+struct Restore(Option<Priority>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|c| c.set(self.0));
+    }
+}
+
+/// The priority of the work being run now. `Normal` outside of `with_priority`
+pub fn current_priority() -> Priority {
+    CURRENT.with(|c| c.get()).unwrap_or_default()
+}
+
+/// Hands out a fixed number of slots (one per pooled connection), most urgent waiter first.
+///
+/// A waiter that has waited longer than the starvation limit is served as if it were
+/// `Interactive`, so a steady stream of urgent work can't lock out background work forever.
+/// Waiters of the same rank are served in the order they arrived.
+pub(crate) struct PriorityQueue {
+    slots: usize,
+    starvation_limit: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    in_use: usize,
+    next_ticket: u64,
+    waiting: Vec<Waiter>,
+}
+
+struct Waiter {
+    ticket: u64,
+    priority: Priority,
+    since: Instant,
+    granted: bool,
+    waker: Waker,
+}
+
+impl PriorityQueue {
+    pub(crate) fn new(slots: usize, starvation_limit: Duration) -> Self {
+        Self {
+            slots: slots.max(1),
+            starvation_limit,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Waits for a free slot at the priority of the current task
+    pub(crate) fn admit(self: &Arc<Self>) -> Admit {
+        Admit {
+            queue: self.clone(),
+            priority: current_priority(),
+            ticket: None,
+        }
+    }
+
+    // hands the free slots to the most urgent waiters. Called with the state locked
+    fn grant(&self, state: &mut State) {
+        while state.in_use < self.slots {
+            let next = state
+                .waiting
+                .iter_mut()
+                .filter(|w| !w.granted)
+                .min_by_key(|w| {
+                    let starved = w.since.elapsed() >= self.starvation_limit;
+                    let rank = if starved {
+                        Priority::Interactive
+                    } else {
+                        w.priority
+                    };
+                    (rank, w.ticket)
+                });
+            let waiter = match next {
+                Some(waiter) => waiter,
+                None => return,
+            };
+            waiter.granted = true;
+            waiter.waker.wake_by_ref();
+            state.in_use += 1;
+        }
+    }
+
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.in_use -= 1;
+        self.grant(&mut state);
+    }
+}
+
+/// Resolves to a `Permit` once this statement's turn has come
+pub(crate) struct Admit {
+    queue: Arc<PriorityQueue>,
+    priority: Priority,
+    ticket: Option<u64>,
+}
+
+impl Future for Admit {
+    type Output = Permit;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Permit> {
+        let this = self.get_mut();
+        let queue = &this.queue;
+        let mut state = queue.state.lock().unwrap();
+
+        let ticket = match this.ticket {
+            Some(ticket) => ticket,
+            None => {
+                // nobody to jump ahead of, take a slot right away
+                if state.in_use < queue.slots && state.waiting.is_empty() {
+                    state.in_use += 1;
+                    return Poll::Ready(Permit(queue.clone()));
+                }
+                let ticket = state.next_ticket;
+                state.next_ticket += 1;
+                state.waiting.push(Waiter {
+                    ticket,
+                    priority: this.priority,
+                    since: Instant::now(),
+                    granted: false,
+                    waker: cx.waker().clone(),
+                });
+                this.ticket = Some(ticket);
+                queue.grant(&mut state);
+                ticket
+            }
+        };
+
+        let index = state.waiting.iter().position(|w| w.ticket == ticket);
+        let index = index.expect("a waiting ticket is only removed by its own Admit");
+        if state.waiting[index].granted {
+            state.waiting.remove(index);
+            this.ticket = None;
+            return Poll::Ready(Permit(queue.clone()));
+        }
+        if !state.waiting[index].waker.will_wake(cx.waker()) {
+            state.waiting[index].waker = cx.waker().clone();
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Admit {
+    // gave up waiting. If the slot was already handed over, pass it on
+    fn drop(&mut self) {
+        let ticket = match self.ticket {
+            Some(ticket) => ticket,
+            None => return,
+        };
+        let mut state = self.queue.state.lock().unwrap();
+        if let Some(index) = state.waiting.iter().position(|w| w.ticket == ticket) {
+            let waiter = state.waiting.remove(index);
+            if waiter.granted {
+                state.in_use -= 1;
+                self.queue.grant(&mut state);
+            }
+        }
+    }
+}
+
+/// A slot in the queue, held while a statement (or a transaction) has its connection
+pub(crate) struct Permit(Arc<PriorityQueue>);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.0.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // waits for a slot at the priority, notes when it got one, and holds it for a moment
+    async fn take_a_slot(
+        queue: Arc<PriorityQueue>,
+        priority: Priority,
+        order: Arc<Mutex<Vec<Priority>>>,
+    ) {
+        with_priority(priority, async move {
+            let _permit = queue.admit().await;
+            order.lock().unwrap().push(priority);
+            tokio::task::yield_now().await;
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn should_serve_the_most_urgent_waiter_first() {
+        let queue = Arc::new(PriorityQueue::new(1, Duration::from_secs(60)));
+        let order = Arc::new(Mutex::new(Vec::default()));
+        let busy = queue.admit().await;
+
+        let background = tokio::spawn(take_a_slot(
+            queue.clone(),
+            Priority::Background,
+            order.clone(),
+        ));
+        let interactive = tokio::spawn(take_a_slot(
+            queue.clone(),
+            Priority::Interactive,
+            order.clone(),
+        ));
+        // let both of them start waiting
+        tokio::task::yield_now().await;
+        assert!(order.lock().unwrap().is_empty());
+
+        drop(busy);
+        background.await.unwrap();
+        interactive.await.unwrap();
+        assert_eq!(
+            *order.lock().unwrap(),
+            [Priority::Interactive, Priority::Background]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_serve_a_starved_waiter_as_interactive() {
+        let queue = Arc::new(PriorityQueue::new(1, Duration::ZERO));
+        let order = Arc::new(Mutex::new(Vec::default()));
+        let busy = queue.admit().await;
+
+        let background = tokio::spawn(take_a_slot(
+            queue.clone(),
+            Priority::Background,
+            order.clone(),
+        ));
+        let interactive = tokio::spawn(take_a_slot(
+            queue.clone(),
+            Priority::Interactive,
+            order.clone(),
+        ));
+        tokio::task::yield_now().await;
+
+        // both have waited past the limit, the first to arrive goes first
+        drop(busy);
+        background.await.unwrap();
+        interactive.await.unwrap();
+        assert_eq!(
+            *order.lock().unwrap(),
+            [Priority::Background, Priority::Interactive]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_pass_on_a_slot_when_a_waiter_gives_up() {
+        let queue = Arc::new(PriorityQueue::new(1, Duration::from_secs(60)));
+        let busy = queue.admit().await;
+        let mut gave_up = Box::pin(queue.admit());
+        assert!(poll_once(gave_up.as_mut()).is_none());
+
+        drop(busy);
+        drop(gave_up);
+        assert_eq!(queue.state.lock().unwrap().in_use, 0);
+        let _permit = queue.admit().await;
+    }
+
+    #[tokio::test]
+    async fn should_only_set_the_priority_inside_with_priority() {
+        assert_eq!(current_priority(), Priority::Normal);
+        let inside = with_priority(Priority::Background, async { current_priority() }).await;
+        assert_eq!(inside, Priority::Background);
+        assert_eq!(current_priority(), Priority::Normal);
+    }
+
+    // polls the future once
+    fn poll_once<F: Future + Unpin>(future: F) -> Option<F::Output> {
+        let mut future = future;
+        let waker = Waker::noop();
+        let mut cx = Context::from_waker(waker);
+        match Pin::new(&mut future).poll(&mut cx) {
+            Poll::Ready(output) => Some(output),
+            Poll::Pending => None,
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use crate::errors::{Error, Result};
+use crate::{Client, Fetch, Param, Row, Syntax};
+
+/// A param passed to a stored procedure. See `Client::call_procedure`
+pub enum ProcParam<'a> {
+    /// A value passed into the procedure
+    In(&'a (dyn Param + Sync + Send)),
+    /// A value the procedure sets. It is read back into the column `name` of the outputs.
+    /// `sql_type` is the type to declare it as, only needed by MSSQL (`INT`, `NVARCHAR(50)`, ...)
+    Out { name: &'a str, sql_type: &'a str },
+}
+
+impl<'a> ProcParam<'a> {
+    pub fn input(value: &'a (dyn Param + Sync + Send)) -> Self {
+        ProcParam::In(value)
+    }
+
+    pub fn output(name: &'a str, sql_type: &'a str) -> Self {
+        ProcParam::Out { name, sql_type }
+    }
+}
+
+/// What a stored procedure returned
+pub struct ProcedureResult {
+    /// The result sets selected by the procedure.
+    /// Only MSSQL keeps them apart, other backends return all the rows as one set
+    pub result_sets: Vec<Vec<Row>>,
+    /// The values of the OUT params, a row with a column for each of them.
+    /// On Postgres the columns are named by the procedure's own param names
+    pub outputs: Option<Row>,
+}
+
+pub(crate) async fn call<C>(
+    client: &C,
+    name: &str,
+    params: &[ProcParam<'_>],
+) -> Result<ProcedureResult>
+where
+    C: Client + ?Sized,
+{
+    let inputs: Vec<&(dyn Param + Sync + Send)> = params
+        .iter()
+        .filter_map(|p| match p {
+            ProcParam::In(value) => Some(*value),
+            ProcParam::Out { .. } => None,
+        })
+        .collect();
+
+    match client.syntax() {
+        Syntax::Postgres | Syntax::CockroachDb => {
+            call_postgres(client, name, params, &inputs).await
+        }
+        Syntax::Mysql | Syntax::MariaDb => call_mysql(client, name, params, &inputs).await,
+        Syntax::Mssql => call_mssql(client, name, params, &inputs).await,
+        syntax => Err(Error::ProceduresUnsupported(syntax)),
+    }
+}
+
+// OUT params are passed as NULL, postgres returns their values as a single row
+async fn call_postgres<C: Client + ?Sized>(
+    client: &C,
+    name: &str,
+    params: &[ProcParam<'_>],
+    inputs: &[&(dyn Param + Sync + Send)],
+) -> Result<ProcedureResult> {
+    let mut next = 0;
+    let args: Vec<String> = params
+        .iter()
+        .map(|p| match p {
+            ProcParam::In(_) => {
+                next += 1;
+                format!("${}", next)
+            }
+            ProcParam::Out { .. } => "NULL".to_owned(),
+        })
+        .collect();
+    let sql = format!("CALL {}({})", name, args.join(", "));
+    let mut rows = client.fetch_rows(&sql, inputs).await?;
+    let has_outputs = params.iter().any(|p| matches!(p, ProcParam::Out { .. }));
+    let outputs = match has_outputs {
+        true => rows.pop(),
+        false => None,
+    };
+    Ok(ProcedureResult {
+        result_sets: Vec::default(),
+        outputs,
+    })
+}
+
+// OUT params are written to session variables, which are selected on the same connection
+async fn call_mysql<C: Client + ?Sized>(
+    client: &C,
+    name: &str,
+    params: &[ProcParam<'_>],
+    inputs: &[&(dyn Param + Sync + Send)],
+) -> Result<ProcedureResult> {
+    let mut selects = Vec::default();
+    let args: Vec<String> = params
+        .iter()
+        .enumerate()
+        .map(|(i, p)| match p {
+            ProcParam::In(_) => "?".to_owned(),
+            ProcParam::Out { name, .. } => {
+                let var = format!("@welds_out_{}", i);
+                selects.push(format!("{} AS {}", var, name));
+                var
+            }
+        })
+        .collect();
+    let call = format!("CALL {}({})", name, args.join(", "));
+
+    if selects.is_empty() {
+        let rows = client.fetch_rows(&call, inputs).await?;
+        return Ok(ProcedureResult {
+            result_sets: vec![rows],
+            outputs: None,
+        });
+    }
+
+    let select = format!("SELECT {}", selects.join(", "));
+    let fetches = [
+        Fetch::new(&call, inputs).with_label("call"),
+        Fetch::new(&select, &[]).with_label("outputs"),
+    ];
+    let mut datasets = client.fetch_many(&fetches).await?;
+    let outputs = datasets.pop().and_then(|mut rows| rows.pop());
+    Ok(ProcedureResult {
+        result_sets: datasets,
+        outputs,
+    })
+}
+
+// OUT params are declared as variables in the batch and selected as its last result set
+async fn call_mssql<C: Client + ?Sized>(
+    client: &C,
+    name: &str,
+    params: &[ProcParam<'_>],
+    inputs: &[&(dyn Param + Sync + Send)],
+) -> Result<ProcedureResult> {
+    let mut declares = String::default();
+    let mut selects = Vec::default();
+    let mut next = 0;
+    let args: Vec<String> = params
+        .iter()
+        .enumerate()
+        .map(|(i, p)| match p {
+            ProcParam::In(_) => {
+                next += 1;
+                format!("@p{}", next)
+            }
+            ProcParam::Out { name, sql_type } => {
+                let var = format!("@welds_out_{}", i);
+                declares.push_str(&format!("DECLARE {} {}; ", var, sql_type));
+                selects.push(format!("{} AS [{}]", var, name));
+                format!("{} OUTPUT", var)
+            }
+        })
+        .collect();
+
+    let mut sql = format!("{}EXEC {} {};", declares, name, args.join(", "));
+    if !selects.is_empty() {
+        sql.push_str(&format!(" SELECT {};", selects.join(", ")));
+    }
+    let mut result_sets = client.fetch_result_sets(&sql, inputs).await?;
+    let outputs = match selects.is_empty() {
+        true => None,
+        false => result_sets.pop().and_then(|mut rows| rows.pop()),
+    };
+    Ok(ProcedureResult {
+        result_sets,
+        outputs,
+    })
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::mock::{MockClient, MockRow};
+
+    fn statements(client: &MockClient) -> Vec<String> {
+        client.queries().into_iter().map(|q| q.sql).collect()
+    }
+
+    #[tokio::test]
+    async fn should_read_back_out_params_on_postgres() {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("CALL", vec![MockRow::new().with("total", 3)]);
+
+        let (a, b) = (1_i32, 2_i32);
+        let params = [
+            ProcParam::input(&a),
+            ProcParam::output("total", "INT"),
+            ProcParam::input(&b),
+        ];
+        let result = client.call_procedure("add", &params).await.unwrap();
+
+        assert_eq!(statements(&client), ["CALL add($1, NULL, $2)"]);
+        assert_eq!(client.queries()[0].params.len(), 2);
+        let outputs = result.outputs.unwrap();
+        assert_eq!(outputs.get::<i32>("total").unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn should_select_out_params_from_session_variables_on_mysql() {
+        let client = MockClient::new(Syntax::Mysql);
+        client.enqueue_rows("CALL", vec![MockRow::new().with("id", 7)]);
+        client.enqueue_rows("SELECT @", vec![MockRow::new().with("total", 3)]);
+
+        let a = 1_i32;
+        let params = [ProcParam::input(&a), ProcParam::output("total", "INT")];
+        let result = client.call_procedure("add", &params).await.unwrap();
+
+        assert_eq!(
+            statements(&client),
+            ["CALL add(?, @welds_out_1)", "SELECT @welds_out_1 AS total"]
+        );
+        assert_eq!(result.result_sets.len(), 1);
+        assert_eq!(result.result_sets[0][0].get::<i32>("id").unwrap(), 7);
+        let outputs = result.outputs.unwrap();
+        assert_eq!(outputs.get::<i32>("total").unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn should_declare_out_params_in_the_batch_on_mssql() {
+        let client = MockClient::new(Syntax::Mssql);
+        client.enqueue_rows("EXEC", vec![MockRow::new().with("total", 3)]);
+
+        let a = 1_i32;
+        let params = [
+            ProcParam::output("total", "INT"),
+            ProcParam::input(&a),
+            ProcParam::output("name", "NVARCHAR(50)"),
+        ];
+        let result = client.call_procedure("dbo.add", &params).await.unwrap();
+
+        assert_eq!(
+            statements(&client),
+            [
+                "DECLARE @welds_out_0 INT; DECLARE @welds_out_2 NVARCHAR(50); \
+              EXEC dbo.add @welds_out_0 OUTPUT, @p1, @welds_out_2 OUTPUT; \
+              SELECT @welds_out_0 AS [total], @welds_out_2 AS [name];"
+            ]
+        );
+        // the last result set holds the out params
+        assert!(result.result_sets.is_empty());
+        assert_eq!(result.outputs.unwrap().get::<i32>("total").unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn should_refuse_backends_without_procedures() {
+        let client = MockClient::new(Syntax::Sqlite);
+        let err = client.call_procedure("add", &[]).await.err();
+        assert!(matches!(
+            err,
+            Some(Error::ProceduresUnsupported(Syntax::Sqlite))
+        ));
+        assert!(client.queries().is_empty());
+    }
+}
@@ -0,0 +1,176 @@
+//! Recordings are stored as JSON lines.
+//! The first line names the syntax of the database, every other line is a statement.
+
+use super::{Recorded, Recording};
+use crate::errors::{Error, Result};
+use crate::row::ValueRow;
+use crate::value::Value;
+use crate::Syntax;
+use serde_json::{json, Value as Json};
+use std::sync::Arc;
+
+pub(super) fn header(syntax: Syntax) -> String {
+    json!({ "syntax": syntax_name(syntax) }).to_string()
+}
+
+pub(super) fn read_header(line: &str) -> Result<Syntax> {
+    let json = parse(line)?;
+    let name = json["syntax"].as_str().unwrap_or_default();
+    syntax_from_name(name).ok_or_else(|| invalid(format!("unknown syntax: {}", name)))
+}
+
+pub(super) fn write_recording(recording: &Recording) -> String {
+    let params: Vec<Json> = recording.params.iter().map(write_value).collect();
+    let mut json = json!({ "sql": recording.sql, "params": params });
+    match &recording.result {
+        Recorded::RowsAffected(count) => json["rows_affected"] = json!(count),
+        Recorded::Rows(rows) => {
+            let columns = rows
+                .first()
+                .map(|r| r.columns().to_vec())
+                .unwrap_or_default();
+            let values: Vec<Json> = rows
+                .iter()
+                .map(|r| Json::Array(r.values().iter().map(write_value).collect()))
+                .collect();
+            json["columns"] = json!(columns);
+            json["rows"] = Json::Array(values);
+        }
+    }
+    json.to_string()
+}
+
+pub(super) fn read_recording(line: &str) -> Result<Recording> {
+    let json = parse(line)?;
+    let sql = json["sql"]
+        .as_str()
+        .ok_or_else(|| invalid("missing sql"))?
+        .to_owned();
+    let params = read_values(&json["params"])?;
+
+    let result = if let Some(count) = json["rows_affected"].as_u64() {
+        Recorded::RowsAffected(count)
+    } else {
+        let columns: Vec<String> = json["columns"]
+            .as_array()
+            .ok_or_else(|| invalid("missing columns"))?
+            .iter()
+            .map(|c| c.as_str().unwrap_or_default().to_owned())
+            .collect();
+        let columns = Arc::new(columns);
+        let rows = json["rows"]
+            .as_array()
+            .ok_or_else(|| invalid("missing rows"))?
+            .iter()
+            .map(|r| Ok(ValueRow::new(columns.clone(), read_values(r)?)))
+            .collect::<Result<Vec<_>>>()?;
+        Recorded::Rows(rows)
+    };
+
+    Ok(Recording {
+        sql,
+        params,
+        result,
+    })
+}
+
+fn write_value(value: &Value) -> Json {
+    match value {
+        Value::Null => Json::Null,
+        Value::Bool(v) => json!(v),
+        Value::Int(v) => json!(v),
+        Value::Float(v) => json!(v),
+        Value::Text(v) => json!(v),
+        Value::Bytes(v) => {
+            let hex: String = v.iter().map(|b| format!("{:02x}", b)).collect();
+            json!({ "bytes": hex })
+        }
+        Value::Uuid(_) => json!({ "uuid": value.to_string() }),
+        Value::DateTime(v) => json!({ "datetime": v }),
+        Value::Opaque(type_name) => json!({ "opaque": type_name }),
+    }
+}
+
+fn read_values(json: &Json) -> Result<Vec<Value>> {
+    json.as_array()
+        .ok_or_else(|| invalid("expected a list of values"))?
+        .iter()
+        .map(read_value)
+        .collect()
+}
+
+fn read_value(json: &Json) -> Result<Value> {
+    let value = match json {
+        Json::Null => Value::Null,
+        Json::Bool(v) => Value::Bool(*v),
+        Json::Number(n) => match n.as_i64() {
+            Some(v) => Value::Int(v),
+            None => Value::Float(n.as_f64().unwrap_or_default()),
+        },
+        Json::String(v) => Value::Text(v.clone()),
+        Json::Object(map) if map.contains_key("uuid") => {
+            let hex = map["uuid"].as_str().unwrap_or_default().replace('-', "");
+            Value::Uuid(
+                read_hex(&hex)?
+                    .try_into()
+                    .map_err(|_| invalid("invalid uuid"))?,
+            )
+        }
+        Json::Object(map) if map.contains_key("datetime") => {
+            let text = map["datetime"].as_str().unwrap_or_default();
+            Value::DateTime(text.to_owned())
+        }
+        Json::Object(map) if map.contains_key("opaque") => {
+            let type_name = map["opaque"].as_str().unwrap_or_default();
+            Value::Opaque(type_name.to_owned())
+        }
+        Json::Object(map) => {
+            let hex = map
+                .get("bytes")
+                .and_then(|b| b.as_str())
+                .ok_or_else(|| invalid("unknown value"))?;
+            Value::Bytes(read_hex(hex)?)
+        }
+        Json::Array(_) => return Err(invalid("unknown value")),
+    };
+    Ok(value)
+}
+
+fn read_hex(hex: &str) -> Result<Vec<u8>> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2).unwrap_or_default(), 16))
+        .collect::<std::result::Result<Vec<u8>, _>>()
+        .map_err(|_| invalid("invalid bytes"))
+}
+
+fn syntax_name(syntax: Syntax) -> &'static str {
+    match syntax {
+        Syntax::Mysql => "mysql",
+        Syntax::Postgres => "postgres",
+        Syntax::Sqlite => "sqlite",
+        Syntax::Mssql => "mssql",
+        Syntax::CockroachDb => "cockroachdb",
+        Syntax::MariaDb => "mariadb",
+    }
+}
+
+fn syntax_from_name(name: &str) -> Option<Syntax> {
+    let all = [
+        Syntax::Mysql,
+        Syntax::Postgres,
+        Syntax::Sqlite,
+        Syntax::Mssql,
+        Syntax::CockroachDb,
+        Syntax::MariaDb,
+    ];
+    all.into_iter().find(|s| syntax_name(*s) == name)
+}
+
+fn parse(line: &str) -> Result<Json> {
+    serde_json::from_str(line).map_err(|err| invalid(err.to_string()))
+}
+
+fn invalid(reason: impl Into<String>) -> Error {
+    Error::Replay(format!("invalid recording: {}", reason.into()))
+}
@@ -0,0 +1,236 @@
+use super::Row;
+use super::{Client, Param};
+use crate::errors::{Error, Result};
+use crate::row::{RowInner, ValueRow};
+use crate::value::{self, Value};
+use crate::{ExecuteResult, Fetch, Syntax};
+use async_trait::async_trait;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+mod format;
+
+/// A client for golden-file testing.
+///
+/// In record mode every statement run on the client is passed to a real database, and the SQL,
+/// params, and results are written to a file. In replay mode the results are read back
+/// out of the file, no database needed.
+///
+/// ```rust,ignore
+/// let client = if std::env::var("RECORD").is_ok() {
+///     let db = welds_connections::connect(&url, None, None, None).await?;
+///     replay::record(db, "tests/golden/orders.jsonl")?
+/// } else {
+///     replay::replay("tests/golden/orders.jsonl")?
+/// };
+/// ```
+///
+/// When replaying, a statement is answered by the first recording with the same SQL and params
+/// that hasn't been used yet. Running a statement that was never recorded is an error.
+/// Params of a type a `Value` can't hold (dates, uuids, ...) are recorded by their type only,
+/// so they match any value of that type.
+///
+/// Only columns of simple types (numbers, bools, text, and binary) can be recorded.
+/// Transactions are not supported.
+pub struct ReplayClient {
+    syntax: Syntax,
+    mode: Mode,
+}
+
+enum Mode {
+    Record {
+        inner: Box<dyn Client>,
+        file: Mutex<File>,
+    },
+    Replay {
+        recordings: Mutex<Vec<Option<Recording>>>,
+    },
+}
+
+pub(crate) struct Recording {
+    pub(crate) sql: String,
+    pub(crate) params: Vec<Value>,
+    pub(crate) result: Recorded,
+}
+
+pub(crate) enum Recorded {
+    Rows(Vec<ValueRow>),
+    RowsAffected(u64),
+}
+
+/// Wraps a client, recording everything run on it to the file at `path`.
+/// The file is replaced if it already exists.
+pub fn record(inner: Box<dyn Client>, path: impl AsRef<Path>) -> Result<ReplayClient> {
+    let syntax = inner.syntax();
+    let mut file = File::create(path).map_err(io_error)?;
+    writeln!(file, "{}", format::header(syntax)).map_err(io_error)?;
+    Ok(ReplayClient {
+        syntax,
+        mode: Mode::Record {
+            inner,
+            file: Mutex::new(file),
+        },
+    })
+}
+
+/// Returns a client that answers with the results recorded in the file at `path`
+pub fn replay(path: impl AsRef<Path>) -> Result<ReplayClient> {
+    let file = File::open(path).map_err(io_error)?;
+    let mut lines = BufReader::new(file).lines();
+    let header = lines
+        .next()
+        .ok_or_else(|| Error::Replay("the recording is empty".to_owned()))?
+        .map_err(io_error)?;
+    let syntax = format::read_header(&header)?;
+
+    let mut recordings = Vec::default();
+    for line in lines {
+        let line = line.map_err(io_error)?;
+        if !line.trim().is_empty() {
+            recordings.push(Some(format::read_recording(&line)?));
+        }
+    }
+
+    Ok(ReplayClient {
+        syntax,
+        mode: Mode::Replay {
+            recordings: Mutex::new(recordings),
+        },
+    })
+}
+
+fn io_error(err: std::io::Error) -> Error {
+    Error::Replay(err.to_string())
+}
+
+fn to_values(params: &[&(dyn Param + Sync + Send)]) -> Vec<Value> {
+    params.iter().map(|p| value::of_param(*p)).collect()
+}
+
+impl ReplayClient {
+    fn write(&self, file: &Mutex<File>, recording: &Recording) -> Result<()> {
+        let line = format::write_recording(recording);
+        let mut file = file.lock().unwrap();
+        writeln!(file, "{}", line).map_err(io_error)
+    }
+
+    fn find(
+        recordings: &Mutex<Vec<Option<Recording>>>,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<Recorded> {
+        let params = to_values(params);
+        let mut recordings = recordings.lock().unwrap();
+        let found = recordings.iter_mut().find(|r| match r {
+            Some(r) => r.sql == sql && r.params == params,
+            None => false,
+        });
+        match found.and_then(|r| r.take()) {
+            Some(recording) => Ok(recording.result),
+            None => Err(Error::Replay(format!(
+                "no recording for: {} {:?}",
+                sql, params
+            ))),
+        }
+    }
+
+    fn record_rows(
+        &self,
+        file: &Mutex<File>,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+        rows: &[Row],
+    ) -> Result<()> {
+        let copied: Result<Vec<ValueRow>> = rows.iter().map(|r| r.to_value_row()).collect();
+        let recording = Recording {
+            sql: sql.to_owned(),
+            params: to_values(params),
+            result: Recorded::Rows(copied?),
+        };
+        self.write(file, &recording)
+    }
+}
+
+fn into_rows(rows: Vec<ValueRow>) -> Vec<Row> {
+    rows.into_iter()
+        .map(|r| Row {
+            inner: RowInner::Mock(r),
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Client for ReplayClient {
+    async fn execute(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<ExecuteResult> {
+        match &self.mode {
+            Mode::Record { inner, file } => {
+                let result = inner.execute(sql, params).await?;
+                let recording = Recording {
+                    sql: sql.to_owned(),
+                    params: to_values(params),
+                    result: Recorded::RowsAffected(result.rows_affected()),
+                };
+                self.write(file, &recording)?;
+                Ok(result)
+            }
+            Mode::Replay { recordings } => match Self::find(recordings, sql, params)? {
+                Recorded::RowsAffected(rows_affected) => Ok(ExecuteResult { rows_affected }),
+                Recorded::Rows(_) => Ok(ExecuteResult { rows_affected: 0 }),
+            },
+        }
+    }
+
+    async fn fetch_rows(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<Vec<Row>> {
+        match &self.mode {
+            Mode::Record { inner, file } => {
+                let rows = inner.fetch_rows(sql, params).await?;
+                self.record_rows(file, sql, params, &rows)?;
+                Ok(rows)
+            }
+            Mode::Replay { recordings } => match Self::find(recordings, sql, params)? {
+                Recorded::Rows(rows) => Ok(into_rows(rows)),
+                Recorded::RowsAffected(_) => Ok(Vec::default()),
+            },
+        }
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        fetches: &[Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        match &self.mode {
+            Mode::Record { inner, file } => {
+                let datasets = inner.fetch_many(fetches).await?;
+                for (fetch, rows) in fetches.iter().zip(&datasets) {
+                    self.record_rows(file, fetch.sql, fetch.params, rows)?;
+                }
+                Ok(datasets)
+            }
+            Mode::Replay { .. } => {
+                let mut results = Vec::default();
+                for (index, fetch) in fetches.iter().enumerate() {
+                    let rows = self.fetch_rows(fetch.sql, fetch.params).await;
+                    results.push(rows.map_err(|err| fetch.failed(index, err))?);
+                }
+                Ok(results)
+            }
+        }
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,123 @@
+use super::*;
+use crate::mock::{MockClient, MockRow};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// a file of its own for each test, removed when dropped
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str) -> Self {
+        let file = format!("welds-replay-{}-{}.jsonl", name, std::process::id());
+        Self(std::env::temp_dir().join(file))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn should_read_back_a_written_recording() {
+    let columns = Arc::new(vec!["id".to_owned(), "name".to_owned(), "photo".to_owned()]);
+    let row = ValueRow::new(
+        columns,
+        vec![
+            Value::Int(1),
+            Value::Text("ann \"the\" first".to_owned()),
+            Value::Bytes(vec![0, 15, 255]),
+        ],
+    );
+    let recording = Recording {
+        sql: "SELECT * FROM users WHERE id = $1".to_owned(),
+        params: vec![
+            Value::Int(1),
+            Value::Float(2.5),
+            Value::Bool(true),
+            Value::Null,
+            Value::Opaque("uuid::Uuid".to_owned()),
+        ],
+        result: Recorded::Rows(vec![row]),
+    };
+
+    let line = format::write_recording(&recording);
+    let read = format::read_recording(&line).unwrap();
+    assert_eq!(read.sql, recording.sql);
+    assert_eq!(read.params, recording.params);
+    let rows = match read.result {
+        Recorded::Rows(rows) => rows,
+        Recorded::RowsAffected(_) => panic!("expected rows"),
+    };
+    assert_eq!(rows[0].columns(), ["id", "name", "photo"]);
+    assert_eq!(
+        rows[0].values(),
+        [
+            Value::Int(1),
+            Value::Text("ann \"the\" first".to_owned()),
+            Value::Bytes(vec![0, 15, 255]),
+        ]
+    );
+}
+
+#[test]
+fn should_read_back_rows_affected_and_the_header() {
+    let recording = Recording {
+        sql: "DELETE FROM users".to_owned(),
+        params: Vec::default(),
+        result: Recorded::RowsAffected(4),
+    };
+    let read = format::read_recording(&format::write_recording(&recording)).unwrap();
+    assert!(matches!(read.result, Recorded::RowsAffected(4)));
+
+    for syntax in [Syntax::Postgres, Syntax::CockroachDb, Syntax::Mssql] {
+        assert_eq!(
+            format::read_header(&format::header(syntax)).unwrap(),
+            syntax
+        );
+    }
+    assert!(format::read_recording("{\"params\": []}").is_err());
+    assert!(format::read_header("{\"syntax\": \"oracle\"}").is_err());
+}
+
+#[tokio::test]
+async fn should_replay_what_was_recorded() {
+    let file = TempFile::new("replay");
+    let db = MockClient::new(Syntax::Sqlite);
+    db.enqueue_rows(
+        "FROM users",
+        vec![MockRow::new().with("id", 1).with("name", "ann")],
+    );
+    db.enqueue_rows(
+        "FROM users",
+        vec![MockRow::new().with("id", 2).with("name", "bob")],
+    );
+    db.enqueue_rows_affected("DELETE", 1);
+
+    let recorder = record(Box::new(db), &file.0).unwrap();
+    let sql = "SELECT id, name FROM users WHERE id = ?";
+    recorder.fetch_rows(sql, &[&1_i32]).await.unwrap();
+    recorder.fetch_rows(sql, &[&2_i32]).await.unwrap();
+    recorder.execute("DELETE FROM users", &[]).await.unwrap();
+    drop(recorder);
+
+    let replayer = replay(&file.0).unwrap();
+    assert_eq!(replayer.syntax(), Syntax::Sqlite);
+    // matched by params, not by the order they were recorded in
+    let rows = replayer.fetch_rows(sql, &[&2_i32]).await.unwrap();
+    assert_eq!(rows[0].get::<String>("name").unwrap(), "bob");
+    let rows = replayer.fetch_rows(sql, &[&1_i32]).await.unwrap();
+    assert_eq!(rows[0].get::<String>("name").unwrap(), "ann");
+    let result = replayer.execute("DELETE FROM users", &[]).await.unwrap();
+    assert_eq!(result.rows_affected(), 1);
+
+    // each recording answers once
+    let err = replayer.fetch_rows(sql, &[&1_i32]).await.err().unwrap();
+    assert!(matches!(err, Error::Replay(_)));
+    let err = replayer.fetch_rows(sql, &[&3_i32]).await.err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "no recording for: SELECT id, name FROM users WHERE id = ? [Int(3)]"
+    );
+}
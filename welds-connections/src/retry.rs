@@ -0,0 +1,219 @@
+use crate::errors::Error;
+use crate::{Client, Syntax, TransactStart, Transaction};
+use std::future::Future;
+use std::pin::Pin;
+
+/// The savepoint CockroachDB watches for to know the client is handling retries
+const COCKROACH_SAVEPOINT: &str = "cockroach_restart";
+
+/// The boxed future returned from the work done inside `transaction_with_retries`
+pub type RetryFuture<'t, T, E> =
+    Pin<Box<dyn Future<Output = std::result::Result<T, E>> + Send + 't>>;
+
+/// Errors that can tell if the database is asking for the transaction to be run again.
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for Error {
+    /// True for serialization failures (SQLSTATE 40001)
+    fn is_retryable(&self) -> bool {
+        match self.root() {
+            #[cfg(any(feature = "mysql", feature = "sqlite", feature = "postgres"))]
+            Error::Sqlx(sqlx::Error::Database(err)) => err.code().as_deref() == Some("40001"),
+            _ => false,
+        }
+    }
+}
+
+/// Runs `work` in a transaction, running it again if the database reports a serialization failure.
+///
+/// On CockroachDB the retry protocol is used: the work is wrapped in
+/// `SAVEPOINT cockroach_restart` and retried with `ROLLBACK TO SAVEPOINT` so the transaction
+/// keeps its priority. Other databases start a new transaction for each attempt.
+///
+/// `max_retries` is the number of times the work will be re-run after the first attempt.
+///
+/// ```rust,ignore
+/// let count = transaction_with_retries(&client, 5, |trans| {
+///     Box::pin(async move {
+///         trans.execute("UPDATE accounts SET balance = balance - 1", &[]).await?;
+///         Ok(1)
+///     })
+/// })
+/// .await?;
+/// ```
+pub async fn transaction_with_retries<C, T, E, F>(
+    client: &C,
+    max_retries: usize,
+    mut work: F,
+) -> std::result::Result<T, E>
+where
+    C: TransactStart + ?Sized,
+    E: From<Error> + Retryable,
+    F: for<'t, 'c> FnMut(&'t Transaction<'c>) -> RetryFuture<'t, T, E>,
+{
+    let mut attempt = 0;
+    loop {
+        let trans = client.begin().await?;
+        let result = match trans.syntax() {
+            Syntax::CockroachDb => {
+                with_savepoint(&trans, max_retries, &mut attempt, &mut work).await
+            }
+            _ => work(&trans).await,
+        };
+
+        match result {
+            Ok(value) => match trans.commit().await {
+                Ok(_) => return Ok(value),
+                Err(err) if err.is_retryable() && attempt < max_retries => {}
+                Err(err) => return Err(err.into()),
+            },
+            Err(err) => {
+                // The rollback error is less interesting than the error that caused it
+                let _ = trans.rollback().await;
+                if !err.is_retryable() || attempt >= max_retries {
+                    return Err(err);
+                }
+            }
+        }
+        attempt += 1;
+        log::debug!("Retrying transaction. attempt: {}", attempt);
+    }
+}
+
+// CockroachDB's client-side retry protocol. The RELEASE is the real commit point
+async fn with_savepoint<T, E, F>(
+    trans: &Transaction<'_>,
+    max_retries: usize,
+    attempt: &mut usize,
+    work: &mut F,
+) -> std::result::Result<T, E>
+where
+    E: From<Error> + Retryable,
+    F: for<'t, 'c> FnMut(&'t Transaction<'c>) -> RetryFuture<'t, T, E>,
+{
+    let savepoint = format!("SAVEPOINT {}", COCKROACH_SAVEPOINT);
+    let release = format!("RELEASE SAVEPOINT {}", COCKROACH_SAVEPOINT);
+    let restart = format!("ROLLBACK TO SAVEPOINT {}", COCKROACH_SAVEPOINT);
+
+    trans.execute(&savepoint, &[]).await?;
+    loop {
+        let err = match work(trans).await {
+            Ok(value) => match trans.execute(&release, &[]).await {
+                Ok(_) => return Ok(value),
+                Err(err) => E::from(err),
+            },
+            Err(err) => err,
+        };
+        if !err.is_retryable() || *attempt >= max_retries {
+            return Err(err);
+        }
+        *attempt += 1;
+        log::debug!("Restarting CockroachDB transaction. attempt: {}", attempt);
+        trans.execute(&restart, &[]).await?;
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::mock::MockClient;
+
+    #[derive(Debug)]
+    enum TestError {
+        Conflict,
+        Fatal,
+        Db,
+    }
+
+    impl From<Error> for TestError {
+        fn from(_: Error) -> Self {
+            TestError::Db
+        }
+    }
+
+    impl Retryable for TestError {
+        fn is_retryable(&self) -> bool {
+            matches!(self, TestError::Conflict)
+        }
+    }
+
+    fn sqls(client: &MockClient) -> Vec<String> {
+        client.queries().into_iter().map(|q| q.sql).collect()
+    }
+
+    // runs the work, failing with `error` the first `failures` times
+    async fn run(
+        client: &MockClient,
+        max_retries: usize,
+        failures: usize,
+        error: fn() -> TestError,
+    ) -> std::result::Result<usize, TestError> {
+        let mut tries = 0;
+        transaction_with_retries(client, max_retries, |trans| {
+            tries += 1;
+            let attempt = tries;
+            Box::pin(async move {
+                trans.execute("UPDATE accounts", &[]).await?;
+                match attempt <= failures {
+                    true => Err(error()),
+                    false => Ok(attempt),
+                }
+            })
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn should_run_the_work_again_after_a_retryable_error() {
+        let client = MockClient::new(Syntax::Postgres);
+        let tries = run(&client, 5, 2, || TestError::Conflict).await.unwrap();
+        assert_eq!(tries, 3);
+        let attempt = ["BEGIN", "UPDATE accounts"];
+        let expected = [
+            &attempt[..],
+            &["ROLLBACK"],
+            &attempt,
+            &["ROLLBACK"],
+            &attempt,
+            &["COMMIT"],
+        ];
+        assert_eq!(sqls(&client), expected.concat());
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_other_errors() {
+        let client = MockClient::new(Syntax::Postgres);
+        let err = run(&client, 5, 1, || TestError::Fatal).await.err();
+        assert!(matches!(err, Some(TestError::Fatal)));
+        assert_eq!(sqls(&client), ["BEGIN", "UPDATE accounts", "ROLLBACK"]);
+    }
+
+    #[tokio::test]
+    async fn should_give_up_after_the_retry_limit() {
+        let client = MockClient::new(Syntax::Postgres);
+        let err = run(&client, 2, 10, || TestError::Conflict).await.err();
+        assert!(matches!(err, Some(TestError::Conflict)));
+        let begins = sqls(&client).iter().filter(|s| *s == "BEGIN").count();
+        assert_eq!(begins, 3);
+        assert!(!sqls(&client).contains(&"COMMIT".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn should_roll_back_to_the_savepoint_on_cockroachdb() {
+        let client = MockClient::new(Syntax::CockroachDb);
+        let tries = run(&client, 5, 1, || TestError::Conflict).await.unwrap();
+        assert_eq!(tries, 2);
+        let expected = [
+            "BEGIN",
+            "SAVEPOINT cockroach_restart",
+            "UPDATE accounts",
+            "ROLLBACK TO SAVEPOINT cockroach_restart",
+            "UPDATE accounts",
+            "RELEASE SAVEPOINT cockroach_restart",
+            "COMMIT",
+        ];
+        assert_eq!(sqls(&client), expected);
+    }
+}
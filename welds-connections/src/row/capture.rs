@@ -0,0 +1,173 @@
+use super::{Row, RowInner, ValueRow};
+use crate::errors::Error;
+use crate::errors::Result;
+use crate::value::Value;
+use std::sync::Arc;
+
+impl Row {
+    /// Copies the values out of this row, no matter what database it came from.
+    /// Only simple types (numbers, bools, text, binary, and uuids and dates with the
+    /// `uuid` and `chrono` features) can be copied.
+    pub(crate) fn to_value_row(&self) -> Result<ValueRow> {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_values(r),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_values(r),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_values(r),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => mssql_values(r),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => Ok(r.clone()),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => Ok(r.clone()),
+        }
+    }
+}
+
+fn unsupported(column: &str, type_name: &str) -> Error {
+    Error::ColumnDecode(
+        column.to_owned(),
+        format!("values of type {} can not be copied", type_name),
+    )
+}
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn sqlx_values<R>(row: &R) -> Result<ValueRow>
+where
+    R: sqlx::Row,
+    usize: sqlx::ColumnIndex<R>,
+    for<'r> i64: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    for<'r> i32: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    for<'r> i16: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    for<'r> f64: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    for<'r> f32: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    for<'r> bool: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    for<'r> String: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    for<'r> Vec<u8>: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    R: TypedValues,
+{
+    use sqlx::{Column, TypeInfo, ValueRef};
+
+    let columns: Vec<String> = row.columns().iter().map(|c| c.name().to_owned()).collect();
+    let mut values = Vec::with_capacity(columns.len());
+
+    for (i, column) in columns.iter().enumerate() {
+        let raw = row.try_get_raw(i)?;
+        if raw.is_null() {
+            values.push(Value::Null);
+            continue;
+        }
+        let type_name = raw.type_info().name().to_owned();
+        // try_get checks the type of the column before decoding
+        let value = if let Ok(v) = row.try_get::<i64, _>(i) {
+            Value::Int(v)
+        } else if let Ok(v) = row.try_get::<i32, _>(i) {
+            Value::Int(v as i64)
+        } else if let Ok(v) = row.try_get::<i16, _>(i) {
+            Value::Int(v as i64)
+        } else if let Ok(v) = row.try_get::<f64, _>(i) {
+            Value::Float(v)
+        } else if let Ok(v) = row.try_get::<f32, _>(i) {
+            Value::Float(v as f64)
+        } else if let Ok(v) = row.try_get::<bool, _>(i) {
+            Value::Bool(v)
+        } else if let Ok(v) = row.try_get::<String, _>(i) {
+            Value::Text(v)
+        } else if let Some(v) = row.typed_value(i) {
+            v
+        } else if let Ok(v) = row.try_get::<Vec<u8>, _>(i) {
+            Value::Bytes(v)
+        } else {
+            return Err(unsupported(column, &type_name));
+        };
+        values.push(value);
+    }
+
+    Ok(ValueRow::new(Arc::new(columns), values))
+}
+
+// Reads the uuids and dates of a row, with the types of the `uuid` and `chrono` features
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+trait TypedValues {
+    fn typed_value(&self, index: usize) -> Option<Value>;
+}
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+macro_rules! typed_values {
+    ($row:ty) => {
+        impl TypedValues for $row {
+            fn typed_value(&self, index: usize) -> Option<Value> {
+                #[allow(unused_imports)]
+                use crate::value::ToValue;
+                #[allow(unused_imports)]
+                use sqlx::Row as _;
+                #[cfg(feature = "uuid")]
+                if let Ok(v) = self.try_get::<uuid::Uuid, _>(index) {
+                    return Some(v.to_value());
+                }
+                #[cfg(feature = "chrono")]
+                {
+                    use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+                    if let Ok(v) = self.try_get::<DateTime<Utc>, _>(index) {
+                        return Some(v.to_value());
+                    }
+                    if let Ok(v) = self.try_get::<NaiveDateTime, _>(index) {
+                        return Some(v.to_value());
+                    }
+                    if let Ok(v) = self.try_get::<NaiveDate, _>(index) {
+                        return Some(v.to_value());
+                    }
+                    if let Ok(v) = self.try_get::<NaiveTime, _>(index) {
+                        return Some(v.to_value());
+                    }
+                }
+                let _ = index;
+                None
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+typed_values!(sqlx::sqlite::SqliteRow);
+#[cfg(feature = "postgres")]
+typed_values!(sqlx::postgres::PgRow);
+#[cfg(feature = "mysql")]
+typed_values!(sqlx::mysql::MySqlRow);
+
+#[cfg(feature = "mssql")]
+fn mssql_values(row: &super::MssqlRowWrapper) -> Result<ValueRow> {
+    use tiberius::ColumnData;
+
+    let mut columns = Vec::default();
+    let mut values = Vec::default();
+
+    for cell in row.cells() {
+        let name = cell.column().name();
+        let value = match cell.data() {
+            ColumnData::U8(v) => v.map(|v| Value::Int(v as i64)),
+            ColumnData::I16(v) => v.map(|v| Value::Int(v as i64)),
+            ColumnData::I32(v) => v.map(|v| Value::Int(v as i64)),
+            ColumnData::I64(v) => v.map(Value::Int),
+            ColumnData::F32(v) => v.map(|v| Value::Float(v as f64)),
+            ColumnData::F64(v) => v.map(Value::Float),
+            ColumnData::Bit(v) => v.map(Value::Bool),
+            ColumnData::String(v) => v.as_ref().map(|v| Value::Text(v.to_string())),
+            ColumnData::Guid(v) => v.map(|v| Value::Uuid(*v.as_bytes())),
+            ColumnData::Binary(v) => v.as_ref().map(|v| Value::Bytes(v.to_vec())),
+            ColumnData::Numeric(v) => v.map(|v| Value::Text(v.to_string())),
+            _ => {
+                return Err(unsupported(
+                    name,
+                    &format!("{:?}", cell.column().column_type()),
+                ))
+            }
+        };
+        columns.push(name.to_owned());
+        values.push(value.unwrap_or(Value::Null));
+    }
+
+    Ok(ValueRow::new(Arc::new(columns), values))
+}
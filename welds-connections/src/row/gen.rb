@@ -1,9 +1,6 @@
 
 head = %Q|
 use super::*;
-
-#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
-use sqlx::Row as SqlxRow;
 |
 
 def blocky(cfg, wheres)
@@ -16,13 +13,17 @@ impl Row {
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -31,13 +32,17 @@ impl Row {
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -52,11 +57,13 @@ p = [
   ["postgres", "for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres>"],
   ["mysql"   , "for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql>"],
   ["mssql"   , "TiberiusDecode"],
+  ["odbc"    , "'static"],
+  ["mock"    , "'static"],
 ]
 
-cc = p.combination(1) + p.combination(2) + p.combination(3) + p.combination(4)
+cc = p.combination(1) + p.combination(2) + p.combination(3) + p.combination(4) + p.combination(5) + p.combination(6)
 
-all = ["sqlite", "postgres", "mysql", "mssql"]
+all = ["sqlite", "postgres", "mysql", "mssql", "odbc", "mock"]
 
 full = head
 
@@ -71,7 +78,7 @@ cc.each do |c|
   cfgs = "#[cfg(all(#{rules.join(", ")}))]"
 
   wheres = c.map{|a| a[1]}
-  wheres = wheres.join(" + ")
+  wheres = wheres.uniq.join(" + ")
 
   full = full + "\n\n" + blocky(cfgs, wheres)
 end
@@ -24,6 +24,13 @@ mod mssql_row_wrapper;
 #[cfg(feature = "mssql")]
 pub use mssql_row_wrapper::MssqlRowWrapper;
 
+#[cfg(feature = "replay")]
+mod capture;
+#[cfg(any(feature = "odbc", feature = "mock"))]
+mod value_row;
+#[cfg(any(feature = "odbc", feature = "mock"))]
+pub use value_row::ValueRow;
+
 /// all kinds of rows
 pub enum RowInner {
     #[cfg(feature = "sqlite")]
@@ -34,6 +41,10 @@ pub enum RowInner {
     Postgres(PgRow),
     #[cfg(feature = "mysql")]
     Mysql(MySqlRow),
+    #[cfg(feature = "odbc")]
+    Odbc(ValueRow),
+    #[cfg(feature = "mock")]
+    Mock(ValueRow),
 }
 
 #[cfg(feature = "sqlite")]
@@ -112,8 +123,47 @@ impl Row {
     }
 }
 
+#[cfg(feature = "odbc")]
+impl Row {
+    pub fn as_odbc_row(self) -> Option<ValueRow> {
+        match self.inner {
+            RowInner::Odbc(r) => Some(r),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(feature = "mssql")]
 use mssql_row_wrapper::TiberiusDecode;
 
+// Reads a column from a sqlx row. A column of a type that doesn't fit T is reported
+// as a `ColumnType` error naming the column and both types
+#[cfg(any(feature = "mysql", feature = "sqlite", feature = "postgres"))]
+fn sqlx_get<'r, T, R, I>(row: &'r R, index: I) -> Result<T>
+where
+    R: sqlx::Row,
+    I: sqlx::ColumnIndex<R> + Copy,
+    T: Decode<'r, R::Database> + Type<R::Database>,
+{
+    use sqlx::{Column, TypeInfo, ValueRef};
+    let err = match row.try_get(index) {
+        Ok(value) => return Ok(value),
+        Err(err) => err,
+    };
+    // the type of the value, sqlite columns made from expressions don't have one
+    let (Ok(column), Ok(value)) = (row.try_column(index), row.try_get_raw(index)) else {
+        return Err(err.into());
+    };
+    let column_type = value.type_info();
+    if !value.is_null() && !column_type.is_null() && !T::compatible(&column_type) {
+        return Err(crate::Error::ColumnType {
+            column: column.name().to_string(),
+            rust_type: std::any::type_name::<T>(),
+            column_type: column_type.name().to_string(),
+        });
+    }
+    Err(err.into())
+}
+
 // This code is scripted out cuz writing it for all the features to be to much
 mod row_gen;
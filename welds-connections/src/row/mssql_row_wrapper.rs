@@ -186,7 +186,7 @@ impl TiberiusDecode for tiberius::numeric::BigDecimal {
     }
 }
 
-#[cfg(feature = "mssql-chrono")]
+#[cfg(any(feature = "mssql-chrono", feature = "chrono"))]
 /// All mapping for the chrono crate
 mod chrono {
     use super::*;
@@ -1,172 +1,2645 @@
 use super::*;
 
-#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
-use sqlx::Row as SqlxRow;
+#[cfg(all(
+    feature = "sqlite",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mysql",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mssql",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "odbc",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    not(feature = "sqlite"),
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    not(feature = "mssql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mssql",
+    not(feature = "mysql"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "odbc",
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mock",
+    not(feature = "mysql"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "postgres"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "postgres"),
+    not(feature = "mysql"),
+    not(feature = "mssql")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "mysql"),
+    not(feature = "mssql")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mssql")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    not(feature = "odbc"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "odbc",
+    not(feature = "mssql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mock",
+    not(feature = "mssql"),
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "mysql"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + TiberiusDecode
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + TiberiusDecode
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
 
 #[cfg(all(
     feature = "sqlite",
-    not(feature = "postgres"),
+    feature = "postgres",
+    feature = "mssql",
+    feature = "mock",
     not(feature = "mysql"),
-    not(feature = "mssql")
+    not(feature = "odbc")
 ))]
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite>,
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite>,
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
 
 #[cfg(all(
+    feature = "sqlite",
     feature = "postgres",
-    not(feature = "sqlite"),
+    feature = "odbc",
+    feature = "mock",
     not(feature = "mysql"),
     not(feature = "mssql")
 ))]
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres>,
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres>,
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
 
 #[cfg(all(
+    feature = "sqlite",
     feature = "mysql",
-    not(feature = "sqlite"),
+    feature = "mssql",
+    feature = "odbc",
     not(feature = "postgres"),
-    not(feature = "mssql")
+    not(feature = "mock")
 ))]
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql>,
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql>,
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
 
 #[cfg(all(
+    feature = "sqlite",
+    feature = "mysql",
     feature = "mssql",
-    not(feature = "sqlite"),
+    feature = "mock",
     not(feature = "postgres"),
-    not(feature = "mysql")
+    not(feature = "odbc")
 ))]
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
 
 #[cfg(all(
     feature = "sqlite",
-    feature = "postgres",
-    not(feature = "mysql"),
+    feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
+    not(feature = "postgres"),
     not(feature = "mssql")
 ))]
 impl Row {
@@ -174,18 +2647,23 @@ impl Row {
     where
         T: for<'r> Decode<'r, sqlx::Sqlite>
             + Type<sqlx::Sqlite>
-            + for<'r> Decode<'r, sqlx::Postgres>
-            + Type<sqlx::Postgres>,
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -193,104 +2671,193 @@ impl Row {
     where
         T: for<'r> Decode<'r, sqlx::Sqlite>
             + Type<sqlx::Sqlite>
-            + for<'r> Decode<'r, sqlx::Postgres>
-            + Type<sqlx::Postgres>,
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
 
 #[cfg(all(
     feature = "sqlite",
-    feature = "mysql",
+    feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "postgres"),
-    not(feature = "mssql")
+    not(feature = "mysql")
 ))]
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Sqlite>
-            + Type<sqlx::Sqlite>
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "sqlite"),
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Sqlite>
-            + Type<sqlx::Sqlite>
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
 
 #[cfg(all(
-    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
     feature = "mssql",
-    not(feature = "postgres"),
-    not(feature = "mysql")
+    feature = "mock",
+    not(feature = "sqlite"),
+    not(feature = "odbc")
 ))]
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -298,6 +2865,8 @@ impl Row {
 #[cfg(all(
     feature = "postgres",
     feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite"),
     not(feature = "mssql")
 ))]
@@ -307,17 +2876,22 @@ impl Row {
         T: for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -326,17 +2900,22 @@ impl Row {
         T: for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -344,39 +2923,49 @@ impl Row {
 #[cfg(all(
     feature = "postgres",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite"),
     not(feature = "mysql")
 ))]
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -384,39 +2973,177 @@ impl Row {
 #[cfg(all(
     feature = "mysql",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite"),
     not(feature = "postgres")
 ))]
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "odbc",
+    not(feature = "mock")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, index),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get_by_position(index),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, index),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql",
+    feature = "mock",
+    not(feature = "odbc")
+))]
+impl Row {
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
+    {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => sqlx_get(r, name),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.try_get(name),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => sqlx_get(r, name),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
+        }
+    }
+
+    pub fn get_by_position<T>(&self, index: usize) -> Result<T>
+    where
+        T: for<'r> Decode<'r, sqlx::Sqlite>
+            + Type<sqlx::Sqlite>
+            + for<'r> Decode<'r, sqlx::Postgres>
+            + Type<sqlx::Postgres>
+            + for<'r> Decode<'r, sqlx::MySql>
+            + Type<sqlx::MySql>
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -425,6 +3152,8 @@ impl Row {
     feature = "sqlite",
     feature = "postgres",
     feature = "mysql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "mssql")
 ))]
 impl Row {
@@ -435,17 +3164,22 @@ impl Row {
             + for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -456,17 +3190,22 @@ impl Row {
             + for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -475,6 +3214,8 @@ impl Row {
     feature = "sqlite",
     feature = "postgres",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "mysql")
 ))]
 impl Row {
@@ -484,17 +3225,22 @@ impl Row {
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -504,17 +3250,22 @@ impl Row {
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -523,6 +3274,8 @@ impl Row {
     feature = "sqlite",
     feature = "mysql",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "postgres")
 ))]
 impl Row {
@@ -532,17 +3285,22 @@ impl Row {
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -552,17 +3310,22 @@ impl Row {
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -571,6 +3334,8 @@ impl Row {
     feature = "postgres",
     feature = "mysql",
     feature = "mssql",
+    feature = "odbc",
+    feature = "mock",
     not(feature = "sqlite")
 ))]
 impl Row {
@@ -580,17 +3345,22 @@ impl Row {
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -600,17 +3370,22 @@ impl Row {
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -619,7 +3394,9 @@ impl Row {
     feature = "sqlite",
     feature = "postgres",
     feature = "mysql",
-    feature = "mssql"
+    feature = "mssql",
+    feature = "odbc",
+    feature = "mock"
 ))]
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
@@ -630,17 +3407,22 @@ impl Row {
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(name)?),
+            RowInner::Sqlite(r) => sqlx_get(r, name),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get(name),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(name)?),
+            RowInner::Postgres(r) => sqlx_get(r, name),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            RowInner::Mysql(r) => sqlx_get(r, name),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get(name),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -652,17 +3434,22 @@ impl Row {
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + 'static,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
-            RowInner::Sqlite(r) => Ok(r.try_get(index)?),
+            RowInner::Sqlite(r) => sqlx_get(r, index),
             #[cfg(feature = "mssql")]
             RowInner::Mssql(r) => r.try_get_by_position(index),
             #[cfg(feature = "postgres")]
-            RowInner::Postgres(r) => Ok(r.try_get(index)?),
+            RowInner::Postgres(r) => sqlx_get(r, index),
             #[cfg(feature = "mysql")]
-            RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            RowInner::Mysql(r) => sqlx_get(r, index),
+            #[cfg(feature = "odbc")]
+            RowInner::Odbc(r) => r.try_get_by_position(index),
+            #[cfg(feature = "mock")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -0,0 +1,56 @@
+use crate::errors::Error;
+use crate::errors::Result;
+use crate::value::{read_as, Value};
+use std::any::Any;
+use std::sync::Arc;
+
+/// A row made of owned values.
+/// Rows from ODBC are copied out of the driver's buffers when they are fetched.
+/// The mock client builds them from canned data.
+///
+/// Columns can be read as numbers, bools, `String`, `Vec<u8>`, `Value`, or an `Option` of one of them.
+#[derive(Debug, Clone)]
+pub struct ValueRow {
+    columns: Arc<Vec<String>>,
+    values: Vec<Value>,
+}
+
+impl ValueRow {
+    /// Makes a row. The values must be in the same order as the columns
+    pub fn new(columns: Arc<Vec<String>>, values: Vec<Value>) -> ValueRow {
+        Self { columns, values }
+    }
+
+    /// Returns the names of the columns in this row
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the values in this row, in the same order as the columns
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    pub fn try_get<T>(&self, name: &str) -> Result<T>
+    where
+        T: Any,
+    {
+        let idx = self
+            .columns
+            .iter()
+            .position(|c| c == name)
+            .ok_or_else(|| Error::ColumnNotFound(name.to_owned()))?;
+        read_as(name, &self.values[idx])
+    }
+
+    pub fn try_get_by_position<T>(&self, idx: usize) -> Result<T>
+    where
+        T: Any,
+    {
+        let value = self
+            .values
+            .get(idx)
+            .ok_or_else(|| Error::ColumnNotFound(format!("BY_INDEX: {}", idx)))?;
+        read_as(&self.columns[idx], value)
+    }
+}
@@ -0,0 +1,154 @@
+use crate::errors::{Error, Result};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Instant;
+
+/// Keeps track of the work a client has in flight so it can be closed gracefully.
+/// Shared by a client, its clones, and its transactions
+#[derive(Default)]
+pub(crate) struct Shutdown {
+    closed: AtomicBool,
+    in_flight: AtomicUsize,
+    waiters: Mutex<Vec<Waker>>,
+}
+
+impl Shutdown {
+    /// Marks the start of a statement (or transaction).
+    /// Fails once the client has been closed
+    pub(crate) fn enter(self: &Arc<Self>) -> Result<InFlight> {
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(Error::ClientClosed);
+        }
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        // close may have started between the check and the count
+        if self.closed.load(Ordering::SeqCst) {
+            drop(InFlight(self.clone()));
+            return Err(Error::ClientClosed);
+        }
+        Ok(InFlight(self.clone()))
+    }
+
+    /// Stops new work from starting, then waits (up to the deadline) for the work in flight to
+    /// finish. Fails with the number of statements still running if the deadline is hit
+    pub(crate) async fn close(self: &Arc<Self>, deadline: Instant) -> Result<()> {
+        self.closed.store(true, Ordering::SeqCst);
+        let drained = Drained {
+            shutdown: self.clone(),
+        };
+        match with_deadline(deadline, drained).await {
+            Some(()) => Ok(()),
+            None => Err(Error::CloseTimeout(self.in_flight.load(Ordering::SeqCst))),
+        }
+    }
+
+    fn wake_all(&self) {
+        for waker in self.waiters.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// Work that is running on a client. Counted until dropped
+pub(crate) struct InFlight(Arc<Shutdown>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.wake_all();
+        }
+    }
+}
+
+// Resolves once nothing is in flight
+struct Drained {
+    shutdown: Arc<Shutdown>,
+}
+
+impl Future for Drained {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // register first so a drop between the check and the register isn't missed
+        let mut waiters = self.shutdown.waiters.lock().unwrap();
+        if self.shutdown.in_flight.load(Ordering::SeqCst) == 0 {
+            return Poll::Ready(());
+        }
+        waiters.retain(|w| !w.will_wake(cx.waker()));
+        waiters.push(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Runs the future until it finishes or the deadline passes, whichever is first.
+/// The deadline is kept with a sleeping thread so this works on any async runtime
+pub(crate) async fn with_deadline<F: Future>(deadline: Instant, future: F) -> Option<F::Output> {
+    let mut future = std::pin::pin!(future);
+    let mut timer: Option<Arc<Mutex<Option<Waker>>>> = None;
+    std::future::poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Poll::Ready(None);
+        }
+        match &timer {
+            Some(waker) => *waker.lock().unwrap() = Some(cx.waker().clone()),
+            None => {
+                let waker = Arc::new(Mutex::new(Some(cx.waker().clone())));
+                let wake = waker.clone();
+                let wait = deadline - now;
+                std::thread::spawn(move || {
+                    std::thread::sleep(wait);
+                    if let Some(waker) = wake.lock().unwrap().take() {
+                        waker.wake();
+                    }
+                });
+                timer = Some(waker);
+            }
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn should_wait_for_the_work_in_flight_to_finish() {
+        let shutdown = Arc::new(Shutdown::default());
+        let in_flight = shutdown.enter().unwrap();
+        let finish = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            drop(in_flight);
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        shutdown.close(deadline).await.unwrap();
+        assert_eq!(shutdown.in_flight.load(Ordering::SeqCst), 0);
+        finish.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_give_up_at_the_deadline() {
+        let shutdown = Arc::new(Shutdown::default());
+        let _in_flight = shutdown.enter().unwrap();
+
+        let deadline = Instant::now() + Duration::from_millis(20);
+        let err = shutdown.close(deadline).await.err();
+        assert!(matches!(err, Some(Error::CloseTimeout(1))));
+    }
+
+    #[tokio::test]
+    async fn should_refuse_new_work_once_closed() {
+        let shutdown = Arc::new(Shutdown::default());
+        shutdown.close(Instant::now()).await.unwrap();
+        assert!(matches!(shutdown.enter(), Err(Error::ClientClosed)));
+    }
+}
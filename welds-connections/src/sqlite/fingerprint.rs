@@ -0,0 +1,112 @@
+use std::borrow::Cow;
+
+/// The SQL as it is sent to sqlite, with comments removed and runs of whitespace collapsed.
+///
+/// Prepared statements are cached per connection by their SQL. Statements that only differ by
+/// formatting or by their sqlcommenter comment are sent as the same SQL, so every connection in
+/// the pool re-uses a single prepared statement for them. Quoted text and identifiers are left as is.
+///
+/// Dropping the comments loses nothing: sqlite has no server log for them to show up in, and the
+/// hooks (tracing, slow query log, ..) are still given the SQL with its comment.
+/// Without this, a comment that changes per request (a route, a trace id) would prepare every
+/// statement again and push the hot ones out of the cache.
+pub(crate) fn fingerprint(sql: &str) -> Cow<'_, str> {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut space = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '-' if chars.peek() == Some(&'-') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+                space = true;
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+                space = true;
+            }
+            c if c.is_whitespace() => space = true,
+            c => {
+                if space && !out.is_empty() {
+                    out.push(' ');
+                }
+                space = false;
+                out.push(c);
+                // copy quoted text through to its closing quote.
+                // An escaped quote ('') closes and re-opens, which works out the same
+                let close = match c {
+                    '\'' | '"' | '`' => c,
+                    '[' => ']',
+                    _ => continue,
+                };
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if c == close {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    match out == sql {
+        true => Cow::Borrowed(sql),
+        false => Cow::Owned(out),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_collapse_whitespace_and_drop_comments() {
+        let sql = "SELECT *\n  FROM users -- all of them\n WHERE id = ? /*application='api'*/";
+        assert_eq!(fingerprint(sql), "SELECT * FROM users WHERE id = ?");
+    }
+
+    #[test]
+    fn should_leave_quoted_text_alone() {
+        let sql = r#"SELECT 'a  -- b', "x  y", [z  /* w */] FROM t WHERE n = 'it''s'"#;
+        assert_eq!(fingerprint(sql), sql);
+        assert!(matches!(fingerprint(sql), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn should_send_differently_formatted_statements_as_the_same_sql() {
+        let a = fingerprint("SELECT id FROM users WHERE id = ? /*route='%2Fa'*/");
+        let b = fingerprint("SELECT id\n\tFROM users\n\tWHERE id = ?");
+        assert_eq!(a, b);
+    }
+
+    #[tokio::test]
+    async fn should_prepare_a_statement_once_whatever_its_comment() {
+        use crate::Client;
+        use sqlx::Connection;
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let client = crate::sqlite::SqliteClient::from(pool);
+
+        for route in ["a", "b", "c"] {
+            let sql = format!("SELECT 1 /*route='%2F{}'*/", route);
+            client.fetch_rows(&sql, &[]).await.unwrap();
+        }
+
+        let conn = client.as_sqlx_pool().acquire().await.unwrap();
+        assert_eq!(conn.cached_statements_size(), 1);
+    }
+}
@@ -2,24 +2,37 @@ use super::transaction::{TransT, Transaction};
 use super::Row;
 use super::TransactStart;
 use super::{Client, Param};
+use crate::credentials::close_idle;
 use crate::errors::Result;
-use crate::ExecuteResult;
+use crate::instrument::{client_builders, Hooks};
+use crate::shutdown::with_deadline;
+use crate::statement_cache::persistent;
+use crate::{ExecuteResult, Namespace, Syntax};
 use async_trait::async_trait;
 use sqlx::query::Query;
 use sqlx::sqlite::SqliteArguments;
 use sqlx::{Acquire, Sqlite, SqlitePool};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+mod fingerprint;
+pub(crate) use fingerprint::fingerprint;
 
 pub struct SqliteClient {
     pool: Arc<SqlitePool>,
+    hooks: Hooks,
+    statement_cache: bool,
 }
 
 #[async_trait]
 impl TransactStart for SqliteClient {
     async fn begin(&self) -> Result<Transaction> {
+        let hooks = self.hooks.for_transaction().await?;
         let t = self.pool.begin().await?;
         let t = TransT::Sqlite(t);
-        Ok(Transaction::new(t))
+        Ok(Transaction::new(t)
+            .with_hooks(hooks)
+            .with_statement_cache(self.statement_cache))
     }
 }
 
@@ -27,6 +40,8 @@ pub async fn connect(url: &str) -> Result<SqliteClient> {
     let pool = SqlitePool::connect(url).await?;
     Ok(SqliteClient {
         pool: Arc::new(pool),
+        hooks: Hooks::default(),
+        statement_cache: true,
     })
 }
 
@@ -34,6 +49,8 @@ impl From<sqlx::SqlitePool> for SqliteClient {
     fn from(pool: sqlx::SqlitePool) -> SqliteClient {
         SqliteClient {
             pool: Arc::new(pool),
+            hooks: Hooks::default(),
+            statement_cache: true,
         }
     }
 }
@@ -42,6 +59,40 @@ impl SqliteClient {
     pub fn as_sqlx_pool(&self) -> &SqlitePool {
         &self.pool
     }
+
+    client_builders!(pooled);
+
+    /// Turns caching of prepared statements on or off for this client (and its transactions).
+    /// On by default. Use `statement_cache::cache_statements` to override it for a block of work.
+    pub fn with_statement_cache(mut self, enabled: bool) -> Self {
+        self.statement_cache = enabled;
+        self
+    }
+
+    /// Sets how many prepared statements each connection keeps (100 by default).
+    ///
+    /// Idle connections are closed so they are re-opened with the new capacity.
+    /// Raise it when a hot loop runs more distinct statements than fit, so they aren't re-prepared.
+    pub async fn set_statement_cache_capacity(&self, capacity: usize) {
+        let options = (*self.pool.connect_options())
+            .clone()
+            .statement_cache_capacity(capacity);
+        self.pool.set_connect_options(options);
+        close_idle(&self.pool).await;
+    }
+
+    /// Closes the client gracefully.
+    ///
+    /// New statements and transactions are refused, the ones already running get until the
+    /// timeout to finish, then all the pooled connections are closed.
+    /// Fails with `CloseTimeout` if statements were still running at the timeout;
+    /// their connections are closed as they finish.
+    pub async fn close(&self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        let drained = self.hooks.close(deadline).await;
+        with_deadline(deadline, self.pool.close()).await;
+        drained
+    }
 }
 
 use sqlx::encode::Encode;
@@ -54,14 +105,22 @@ impl Client for SqliteClient {
         sql: &str,
         params: &[&(dyn Param + Sync + Send)],
     ) -> Result<ExecuteResult> {
-        let mut query = sqlx::query::<Sqlite>(sql);
+        let _admitted = self.hooks.admit().await;
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let sent = fingerprint(sql);
+        let mut query = sqlx::query::<Sqlite>(&sent).persistent(persistent(self.statement_cache));
         for param in params {
             query = SqliteParam::add_param(*param, query);
         }
-        let r = query.execute(&*self.pool).await?;
-        Ok(ExecuteResult {
-            rows_affected: r.rows_affected(),
-        })
+        self.hooks
+            .run(Syntax::Sqlite, sql, params, async {
+                let r = query.execute(&*self.pool).await?;
+                Ok(ExecuteResult {
+                    rows_affected: r.rows_affected(),
+                })
+            })
+            .await
     }
 
     async fn fetch_rows(
@@ -69,30 +128,50 @@ impl Client for SqliteClient {
         sql: &str,
         params: &[&(dyn Param + Sync + Send)],
     ) -> Result<Vec<Row>> {
-        let mut query = sqlx::query::<Sqlite>(sql);
+        let _admitted = self.hooks.admit().await;
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let sent = fingerprint(sql);
+        let mut query = sqlx::query::<Sqlite>(&sent).persistent(persistent(self.statement_cache));
         for param in params {
             query = SqliteParam::add_param(*param, query);
         }
-        let mut raw_rows = query.fetch_all(&*self.pool).await?;
-        let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
-        Ok(rows)
+        self.hooks
+            .run(Syntax::Sqlite, sql, params, async {
+                let mut raw_rows = query.fetch_all(&*self.pool).await?;
+                let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+                Ok(rows)
+            })
+            .await
     }
 
     async fn fetch_many<'s, 'args, 't>(
         &self,
         fetches: &[crate::Fetch<'s, 'args, 't>],
     ) -> Result<Vec<Vec<Row>>> {
+        let _admitted = self.hooks.admit().await;
         let mut datasets = Vec::default();
         let mut conn = self.pool.acquire().await?;
-        for fetch in fetches {
+        for (index, fetch) in fetches.iter().enumerate() {
             let sql = fetch.sql;
             let params = fetch.params;
-            let mut query = sqlx::query::<Sqlite>(sql);
+            let annotated = self.hooks.annotate(sql);
+            let sql = annotated.as_ref();
+            let sent = fingerprint(sql);
+            let mut query =
+                sqlx::query::<Sqlite>(&sent).persistent(persistent(self.statement_cache));
             for param in params {
                 query = SqliteParam::add_param(*param, query);
             }
-            let mut raw_rows = query.fetch_all(&mut *conn).await?;
-            let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+            let rows = self
+                .hooks
+                .run_labeled(fetch.label, Syntax::Sqlite, sql, params, async {
+                    let mut raw_rows = query.fetch_all(&mut *conn).await?;
+                    let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+                    Ok(rows)
+                })
+                .await
+                .map_err(|err| fetch.failed(index, err))?;
             datasets.push(rows);
         }
         Ok(datasets)
@@ -101,6 +180,18 @@ impl Client for SqliteClient {
     fn syntax(&self) -> crate::Syntax {
         crate::Syntax::Sqlite
     }
+
+    fn namespace(&self) -> Option<&Namespace> {
+        self.hooks.namespace()
+    }
+
+    fn as_transact_start(&self) -> Option<&(dyn TransactStart + Sync)> {
+        Some(self)
+    }
+
+    fn as_sqlite(&self) -> Option<&SqliteClient> {
+        Some(self)
+    }
 }
 
 pub trait SqliteParam {
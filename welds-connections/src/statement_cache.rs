@@ -0,0 +1,86 @@
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+thread_local! {
+    // The override of the task that is currently being polled
+    static CURRENT: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Runs the future with statement caching turned on or off for every statement it runs,
+/// whatever the client is set to (`with_statement_cache`).
+///
+/// Useful for one-off SQL (reports, ad-hoc `from_raw_sql`) that would only push the
+/// repetitive statements out of the connection's cache.
+///
+/// ```rust,ignore
+/// let report = cache_statements(false, async {
+///     client.fetch_rows(&one_off_sql, &[]).await
+/// })
+/// .await?;
+/// ```
+pub fn cache_statements<F: Future>(enabled: bool, future: F) -> CacheStatements<F> {
+    CacheStatements {
+        inner: Box::pin(future),
+        enabled,
+    }
+}
+
+/// A future that is run with statement caching forced on or off. See `cache_statements`
+pub struct CacheStatements<F> {
+    inner: Pin<Box<F>>,
+    enabled: bool,
+}
+
+impl<F: Future> Future for CacheStatements<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let guard = Restore(CURRENT.with(|c| c.replace(Some(this.enabled))));
+        let poll = this.inner.as_mut().poll(cx);
+        drop(guard);
+        poll
+    }
+}
+
+// Puts the outer override back, even on panic
+struct Restore(Option<bool>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|c| c.set(self.0));
+    }
+}
+
+/// If the statement being built should be prepared and kept on the connection.
+/// The client's setting, unless the statement is run inside `cache_statements`
+pub(crate) fn persistent(client_default: bool) -> bool {
+    CURRENT.with(|c| c.get()).unwrap_or(client_default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_use_the_client_setting_outside_of_cache_statements() {
+        assert!(persistent(true));
+        assert!(!persistent(false));
+    }
+
+    #[tokio::test]
+    async fn should_override_the_client_setting_inside_cache_statements() {
+        let (outer, inner) = cache_statements(false, async {
+            let outer = persistent(true);
+            let inner = cache_statements(true, async { persistent(false) }).await;
+            (outer, inner)
+        })
+        .await;
+        assert!(!outer);
+        assert!(inner);
+        // put back once the block is done
+        assert!(persistent(true));
+    }
+}
@@ -0,0 +1,434 @@
+use crate::errors::{Error, Result};
+use crate::{connect, Client};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+/// How to connect to the database of a tenant. Returned by the resolver of a `TenantPoolManager`
+#[derive(Debug, Clone)]
+pub struct TenantOptions {
+    /// A connection string, in any of the formats `connect` accepts
+    pub url: String,
+    /// The size of the tenant's pool. Counted against the manager's connection budget
+    pub max_connections: usize,
+    pub timeout: Option<Duration>,
+}
+
+impl TenantOptions {
+    pub fn new(url: impl Into<String>, max_connections: usize) -> Self {
+        Self {
+            url: url.into(),
+            max_connections: max_connections.max(1),
+            timeout: None,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+type Resolver =
+    dyn Fn(String) -> Pin<Box<dyn Future<Output = Result<TenantOptions>> + Send>> + Send + Sync;
+
+/// Keeps a pool (client) for each tenant of a database-per-tenant app.
+///
+/// The pool of a tenant is created the first time it is asked for, from the options the
+/// resolver returns for it, and re-used after that. Callers asking for a tenant that is still
+/// connecting wait for that one connect. When there are too many tenants (`max_tenants`) or
+/// their pools would go over the connection budget (`max_connections`), the least recently
+/// used pools are dropped to make room, before the new pool connects.
+///
+/// A pool is only dropped once nothing else holds its client, so the budget is never over-committed.
+/// If every cached pool is in use, `get` fails with `Error::TenantBudgetExceeded`.
+///
+/// ```rust,ignore
+/// let tenants = TenantPoolManager::new(|tenant: String| async move {
+///     let url = format!("postgres://app@db/{}", tenant);
+///     Ok(TenantOptions::new(url, 5))
+/// })
+/// .max_tenants(200)
+/// .max_connections(800);
+///
+/// let client = tenants.get("acme").await?;
+/// let orders = Order::all().run(client.as_ref()).await?;
+/// ```
+pub struct TenantPoolManager {
+    resolver: Box<Resolver>,
+    max_tenants: usize,
+    max_connections: Option<usize>,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    tenants: HashMap<String, Cached>,
+    // pools that were evicted while their clients were in use, counted until they are dropped
+    retired: Vec<(Arc<dyn Client>, usize)>,
+    // bumped on every use, orders the tenants from least to most recently used
+    clock: u64,
+}
+
+struct Cached {
+    slot: Arc<Slot>,
+    // reserved once the tenant's options are resolved, before it connects
+    max_connections: usize,
+    last_used: u64,
+}
+
+// The pool of a tenant, once it has connected
+#[derive(Default)]
+struct Slot {
+    state: Mutex<SlotState>,
+}
+
+enum SlotState {
+    Connecting(Vec<Waker>),
+    Open(Arc<dyn Client>),
+    Failed,
+}
+
+impl Default for SlotState {
+    fn default() -> Self {
+        SlotState::Connecting(Vec::default())
+    }
+}
+
+impl Slot {
+    fn finish(&self, outcome: SlotState) {
+        let before = std::mem::replace(&mut *self.state.lock().unwrap(), outcome);
+        if let SlotState::Connecting(waiting) = before {
+            waiting.into_iter().for_each(Waker::wake);
+        }
+    }
+
+    // open, and no one but the manager holds the client
+    fn idle(&self) -> bool {
+        match &*self.state.lock().unwrap() {
+            SlotState::Open(client) => Arc::strong_count(client) == 1,
+            _ => false,
+        }
+    }
+
+    fn client(&self) -> Option<Arc<dyn Client>> {
+        match &*self.state.lock().unwrap() {
+            SlotState::Open(client) => Some(client.clone()),
+            _ => None,
+        }
+    }
+}
+
+// Waits for the tenant to connect. None if the connect failed
+struct Opened<'s>(&'s Slot);
+
+impl Future for Opened<'_> {
+    type Output = Option<Arc<dyn Client>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut *self.0.state.lock().unwrap() {
+            SlotState::Open(client) => Poll::Ready(Some(client.clone())),
+            SlotState::Failed => Poll::Ready(None),
+            SlotState::Connecting(waiting) => {
+                waiting.push(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+// Held while connecting a tenant. A connect that fails (or is dropped) gives up the tenant's
+// place, the callers waiting on it try again
+struct Connecting<'m> {
+    manager: &'m TenantPoolManager,
+    tenant: &'m str,
+    slot: Arc<Slot>,
+    done: bool,
+}
+
+impl Drop for Connecting<'_> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        let mut state = self.manager.state.lock().unwrap();
+        let ours = state
+            .tenants
+            .get(self.tenant)
+            .is_some_and(|c| Arc::ptr_eq(&c.slot, &self.slot));
+        if ours {
+            state.tenants.remove(self.tenant);
+        }
+        drop(state);
+        self.slot.finish(SlotState::Failed);
+    }
+}
+
+impl TenantPoolManager {
+    /// A manager that connects to tenants with the options from the resolver.
+    /// Keeps up to 100 tenants and no connection budget until they are set
+    pub fn new<F, Fut>(resolver: F) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<TenantOptions>> + Send + 'static,
+    {
+        Self {
+            resolver: Box::new(move |tenant| Box::pin(resolver(tenant))),
+            max_tenants: 100,
+            max_connections: None,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// The most tenants to keep a pool open for
+    pub fn max_tenants(mut self, max_tenants: usize) -> Self {
+        self.max_tenants = max_tenants.max(1);
+        self
+    }
+
+    /// The most connections all the tenant pools can have together
+    pub fn max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = Some(max_connections);
+        self
+    }
+
+    /// The client of the tenant, connecting to its database if it isn't open yet
+    pub async fn get(&self, tenant: &str) -> Result<Arc<dyn Client>> {
+        loop {
+            let (slot, connecting) = self.slot(tenant);
+            if !connecting {
+                match Opened(&slot).await {
+                    Some(client) => return Ok(client),
+                    None => continue,
+                }
+            }
+            let mut connecting = Connecting {
+                manager: self,
+                tenant,
+                slot,
+                done: false,
+            };
+            let client = self.connect(tenant, &connecting.slot).await?;
+            connecting.slot.finish(SlotState::Open(client.clone()));
+            connecting.done = true;
+            return Ok(client);
+        }
+    }
+
+    /// Drops the tenant's pool. Its connections close once the clients handed out are dropped,
+    /// until then they are still counted against the budget.
+    /// Returns false if the tenant wasn't open
+    pub fn evict(&self, tenant: &str) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let cached = match state.tenants.remove(tenant) {
+            Some(cached) => cached,
+            None => return false,
+        };
+        if !cached.slot.idle() {
+            if let Some(client) = cached.slot.client() {
+                state.retired.push((client, cached.max_connections));
+            }
+        }
+        true
+    }
+
+    /// The tenants that have a pool open
+    pub fn tenants(&self) -> Vec<String> {
+        self.state.lock().unwrap().tenants.keys().cloned().collect()
+    }
+
+    /// How many connections of the budget the pools use, evicted pools still in use included
+    pub fn connections(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        used(&mut state)
+    }
+
+    // The tenant's slot, and true if it was just added and is for the caller to connect
+    fn slot(&self, tenant: &str) -> (Arc<Slot>, bool) {
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let now = state.clock;
+        if let Some(cached) = state.tenants.get_mut(tenant) {
+            cached.last_used = now;
+            return (cached.slot.clone(), false);
+        }
+        let slot = Arc::new(Slot::default());
+        let cached = Cached {
+            slot: slot.clone(),
+            max_connections: 0,
+            last_used: now,
+        };
+        state.tenants.insert(tenant.to_owned(), cached);
+        (slot, true)
+    }
+
+    // resolves the tenant, reserves its connections, then connects
+    async fn connect(&self, tenant: &str, slot: &Arc<Slot>) -> Result<Arc<dyn Client>> {
+        let options = (self.resolver)(tenant.to_owned()).await?;
+        let max_connections = options.max_connections.max(1);
+        {
+            let mut state = self.state.lock().unwrap();
+            self.make_room(&mut state, tenant, max_connections)?;
+            if let Some(cached) = state.tenants.get_mut(tenant) {
+                cached.max_connections = max_connections;
+            }
+        }
+
+        let client = connect(options.url, options.timeout, None, Some(max_connections)).await?;
+        let client: Arc<dyn Client> = Arc::from(client);
+
+        // evicted while connecting, counted until the clients handed out are dropped
+        let mut state = self.state.lock().unwrap();
+        let cached = state.tenants.get(tenant);
+        if !cached.is_some_and(|c| Arc::ptr_eq(&c.slot, slot)) {
+            state.retired.push((client.clone(), max_connections));
+        }
+        Ok(client)
+    }
+
+    // drops the least recently used idle pools until the new one fits
+    fn make_room(&self, state: &mut State, tenant: &str, needed: usize) -> Result<()> {
+        let over = |state: &mut State| {
+            let over_budget = self
+                .max_connections
+                .map(|budget| used(state) + needed > budget)
+                .unwrap_or(false);
+            // the tenant making room already has its place
+            over_budget || state.tenants.len() > self.max_tenants
+        };
+
+        while over(state) {
+            // only pools no one else is holding on to are dropped
+            let idle = state
+                .tenants
+                .iter()
+                .filter(|(name, c)| name.as_str() != tenant && c.slot.idle())
+                .min_by_key(|(_, c)| c.last_used)
+                .map(|(name, _)| name.clone());
+            match idle {
+                Some(name) => {
+                    log::debug!("Dropping the pool of tenant {} to make room", name);
+                    state.tenants.remove(&name);
+                }
+                None => return Err(Error::TenantBudgetExceeded(tenant.to_owned())),
+            }
+        }
+        Ok(())
+    }
+}
+
+// the connections of the open pools, and of the evicted pools that are still in use
+fn used(state: &mut State) -> usize {
+    state
+        .retired
+        .retain(|(client, _)| Arc::strong_count(client) > 1);
+    let open: usize = state.tenants.values().map(|c| c.max_connections).sum();
+    let retired: usize = state.retired.iter().map(|(_, n)| n).sum();
+    open + retired
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn manager() -> TenantPoolManager {
+        TenantPoolManager::new(|tenant: String| async move {
+            match tenant.as_str() {
+                "missing" => Err(Error::InvalidDatabaseUrl),
+                "huge" => Ok(TenantOptions::new("nope://huge", 10)),
+                _ => Ok(TenantOptions::new("sqlite::memory:", 2)),
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn should_reuse_the_pool_of_a_tenant() {
+        let tenants = manager();
+        let first = tenants.get("acme").await.unwrap();
+        let second = tenants.get("acme").await.unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(tenants.tenants(), ["acme"]);
+        assert_eq!(tenants.connections(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_drop_the_least_recently_used_idle_pool_to_make_room() {
+        let tenants = manager().max_tenants(2);
+        tenants.get("a").await.unwrap();
+        tenants.get("b").await.unwrap();
+        tenants.get("a").await.unwrap();
+
+        tenants.get("c").await.unwrap();
+        let mut open = tenants.tenants();
+        open.sort();
+        assert_eq!(open, ["a", "c"]);
+    }
+
+    #[tokio::test]
+    async fn should_not_drop_a_pool_that_is_in_use() {
+        let tenants = manager().max_connections(4);
+        let _a = tenants.get("a").await.unwrap();
+        let _b = tenants.get("b").await.unwrap();
+
+        let err = tenants.get("c").await.err();
+        assert!(matches!(err, Some(Error::TenantBudgetExceeded(t)) if t == "c"));
+        drop(_b);
+        tenants.get("c").await.unwrap();
+        assert_eq!(tenants.connections(), 4);
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_the_tenant_cant_be_resolved() {
+        let tenants = manager();
+        let err = tenants.get("missing").await.err();
+        assert!(matches!(err, Some(Error::InvalidDatabaseUrl)));
+        assert!(tenants.tenants().is_empty());
+        assert!(!tenants.evict("missing"));
+    }
+
+    #[tokio::test]
+    async fn should_connect_once_for_callers_at_the_same_time() {
+        let resolved = Arc::new(AtomicUsize::new(0));
+        let counter = resolved.clone();
+        let tenants = TenantPoolManager::new(move |_tenant: String| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move { Ok(TenantOptions::new("sqlite::memory:", 2)) }
+        });
+
+        let (first, second) = tokio::join!(tenants.get("acme"), tenants.get("acme"));
+        assert!(Arc::ptr_eq(&first.unwrap(), &second.unwrap()));
+        assert_eq!(resolved.load(Ordering::SeqCst), 1);
+        assert_eq!(tenants.connections(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_reserve_the_budget_before_connecting() {
+        let tenants = manager().max_connections(4);
+        // over the budget, so the bad url is never connected to
+        let err = tenants.get("huge").await.err();
+        assert!(matches!(err, Some(Error::TenantBudgetExceeded(t)) if t == "huge"));
+        assert!(tenants.tenants().is_empty());
+        assert_eq!(tenants.connections(), 0);
+    }
+
+    #[tokio::test]
+    async fn should_count_an_evicted_pool_until_its_clients_are_dropped() {
+        let tenants = manager().max_connections(4);
+        let a = tenants.get("a").await.unwrap();
+        assert!(tenants.evict("a"));
+        assert!(tenants.tenants().is_empty());
+        assert_eq!(tenants.connections(), 2);
+
+        let _b = tenants.get("b").await.unwrap();
+        let err = tenants.get("c").await.err();
+        assert!(matches!(err, Some(Error::TenantBudgetExceeded(t)) if t == "c"));
+
+        drop(a);
+        tenants.get("c").await.unwrap();
+        assert_eq!(tenants.connections(), 4);
+    }
+}
@@ -1,16 +1,30 @@
 use super::Row;
 use super::{Client, Param};
 use crate::errors::Result;
-use crate::{ExecuteResult, Syntax};
+use crate::instrument::Hooks;
+use crate::statement_cache::persistent;
+use crate::{ExecuteResult, Namespace, Syntax};
 use async_trait::async_trait;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Mutex;
 
+#[cfg(feature = "mock")]
+use crate::mock::MockTransaction;
 #[cfg(feature = "mssql")]
 use crate::mssql::transaction::MssqlTransaction;
+#[cfg(feature = "odbc")]
+use crate::odbc::transaction::OdbcTransaction;
+
+/// Work a transaction holds on to until it commits. See `Client::after_commit`
+pub type AfterCommit = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>;
 
 pub struct Transaction<'t> {
     inner: Mutex<Option<TransT<'t>>>,
     syntax: crate::Syntax,
+    hooks: Hooks,
+    statement_cache: bool,
+    after_commit: Mutex<Vec<AfterCommit>>,
 }
 
 impl<'t> Transaction<'t> {
@@ -24,22 +38,54 @@ impl<'t> Transaction<'t> {
             TransT::Postgres(_) => Syntax::Postgres,
             #[cfg(feature = "mysql")]
             TransT::Mysql(_) => Syntax::Mysql,
+            #[cfg(feature = "odbc")]
+            TransT::Odbc(t) => t.syntax(),
+            #[cfg(feature = "mock")]
+            TransT::Mock(t) => t.client().syntax(),
         };
 
         Self {
             syntax,
             inner: Mutex::new(Some(inner)),
+            hooks: Hooks::default(),
+            statement_cache: true,
+            after_commit: Mutex::default(),
         }
     }
 
+    /// Runs the hooks of the client that started this transaction
+    pub(crate) fn with_hooks(mut self, hooks: Hooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Keeps the statement caching setting of the client that started this transaction
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    pub(crate) fn with_statement_cache(mut self, enabled: bool) -> Self {
+        self.statement_cache = enabled;
+        self
+    }
+
+    /// Overrides the syntax for backends that share a driver (CockroachDB over postgres)
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    pub(crate) fn with_syntax(mut self, syntax: Syntax) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
     pub async fn rollback(self) -> Result<()> {
         let inner = self.take_conn();
         inner.rollback().await?;
         Ok(())
     }
+    /// Commits the transaction, then runs the work held with `after_commit`
     pub async fn commit(self) -> Result<()> {
         let inner = self.take_conn();
         inner.commit().await?;
+        let held = std::mem::take(&mut *self.after_commit.lock().unwrap());
+        for work in held {
+            work().await;
+        }
         Ok(())
     }
 }
@@ -78,6 +124,10 @@ pub(crate) enum TransT<'t> {
     Mysql(sqlx::Transaction<'t, sqlx::MySql>),
     #[cfg(feature = "mssql")]
     Mssql(MssqlTransaction<'t>),
+    #[cfg(feature = "odbc")]
+    Odbc(OdbcTransaction),
+    #[cfg(feature = "mock")]
+    Mock(MockTransaction<'t>),
 }
 
 impl<'t> TransT<'t> {
@@ -91,6 +141,10 @@ impl<'t> TransT<'t> {
             TransT::Postgres(t) => t.rollback().await?,
             #[cfg(feature = "mysql")]
             TransT::Mysql(t) => t.rollback().await?,
+            #[cfg(feature = "odbc")]
+            TransT::Odbc(t) => t.rollback().await?,
+            #[cfg(feature = "mock")]
+            TransT::Mock(t) => t.rollback().await?,
         }
         Ok(())
     }
@@ -104,6 +158,10 @@ impl<'t> TransT<'t> {
             TransT::Postgres(t) => t.commit().await?,
             #[cfg(feature = "mysql")]
             TransT::Mysql(t) => t.commit().await?,
+            #[cfg(feature = "odbc")]
+            TransT::Odbc(t) => t.commit().await?,
+            #[cfg(feature = "mock")]
+            TransT::Mock(t) => t.commit().await?,
         }
         Ok(())
     }
@@ -122,15 +180,35 @@ impl<'t> Client for Transaction<'t> {
         self.syntax
     }
 
+    fn namespace(&self) -> Option<&Namespace> {
+        self.hooks.namespace()
+    }
+
+    fn in_transaction(&self) -> bool {
+        true
+    }
+
+    fn after_commit(&self, work: AfterCommit) -> Option<AfterCommit> {
+        self.after_commit.lock().unwrap().push(work);
+        None
+    }
+
     async fn execute(
         &self,
         sql: &str,
         params: &[&(dyn Param + Sync + Send)],
     ) -> Result<ExecuteResult> {
-        let mut inner = self.take_conn();
-        let results = execute_inner(&mut inner, sql, params).await;
-        self.return_conn(inner);
-        results
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let persistent = persistent(self.statement_cache);
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                let mut inner = self.take_conn();
+                let results = execute_inner(&mut inner, sql, params, persistent).await;
+                self.return_conn(inner);
+                results
+            })
+            .await
     }
 
     async fn fetch_rows(
@@ -138,10 +216,35 @@ impl<'t> Client for Transaction<'t> {
         sql: &str,
         params: &[&(dyn Param + Sync + Send)],
     ) -> Result<Vec<Row>> {
-        let mut inner = self.take_conn();
-        let results = fetch_rows_inner(&mut inner, sql, params).await;
-        self.return_conn(inner);
-        results
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let persistent = persistent(self.statement_cache);
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                let mut inner = self.take_conn();
+                let results = fetch_rows_inner(&mut inner, sql, params, persistent).await;
+                self.return_conn(inner);
+                results
+            })
+            .await
+    }
+
+    async fn fetch_result_sets(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync + Send)],
+    ) -> Result<Vec<Vec<Row>>> {
+        let annotated = self.hooks.annotate(sql);
+        let sql = annotated.as_ref();
+        let persistent = persistent(self.statement_cache);
+        self.hooks
+            .run(self.syntax, sql, params, async {
+                let mut inner = self.take_conn();
+                let results = fetch_result_sets_inner(&mut inner, sql, params, persistent).await;
+                self.return_conn(inner);
+                results
+            })
+            .await
     }
 
     async fn fetch_many<'s, 'args, 'i>(
@@ -151,11 +254,24 @@ impl<'t> Client for Transaction<'t> {
         // transactions are already locked to a single connection.
         // Just run the batch of fetches
         let mut datasets = Vec::default();
+        let persistent = persistent(self.statement_cache);
         let mut inner = self.take_conn();
-        for fetch in fetches {
+        for (index, fetch) in fetches.iter().enumerate() {
             let sql = fetch.sql;
             let params = fetch.params;
-            let r = fetch_rows_inner(&mut inner, sql, params).await;
+            let annotated = self.hooks.annotate(sql);
+            let sql = annotated.as_ref();
+            let r = self
+                .hooks
+                .run_labeled(
+                    fetch.label,
+                    self.syntax,
+                    sql,
+                    params,
+                    fetch_rows_inner(&mut inner, sql, params, persistent),
+                )
+                .await
+                .map_err(|err| fetch.failed(index, err));
             let is_err = r.is_err();
             datasets.push(r);
             if is_err {
@@ -167,16 +283,22 @@ impl<'t> Client for Transaction<'t> {
     }
 }
 
+#[cfg_attr(
+    not(any(feature = "postgres", feature = "mysql", feature = "sqlite")),
+    allow(unused_variables)
+)]
 async fn execute_inner<'t>(
     inner: &mut TransT<'t>,
     sql: &str,
     params: &[&(dyn Param + Sync + Send)],
+    persistent: bool,
 ) -> Result<ExecuteResult> {
     match inner {
         #[cfg(feature = "sqlite")]
         TransT::Sqlite(t) => {
             let x: &mut <sqlx::Sqlite as sqlx::Database>::Connection = t;
-            let mut query = sqlx::query::<sqlx::Sqlite>(sql);
+            let sent = crate::sqlite::fingerprint(sql);
+            let mut query = sqlx::query::<sqlx::Sqlite>(&sent).persistent(persistent);
             for param in params {
                 query = SqliteParam::add_param(*param, query)
             }
@@ -189,7 +311,7 @@ async fn execute_inner<'t>(
         #[cfg(feature = "postgres")]
         TransT::Postgres(t) => {
             let x: &mut <sqlx::Postgres as sqlx::Database>::Connection = t;
-            let mut query = sqlx::query::<sqlx::Postgres>(sql);
+            let mut query = sqlx::query::<sqlx::Postgres>(sql).persistent(persistent);
             for param in params {
                 query = PostgresParam::add_param(*param, query)
             }
@@ -202,7 +324,7 @@ async fn execute_inner<'t>(
         #[cfg(feature = "mysql")]
         TransT::Mysql(t) => {
             let x: &mut <sqlx::MySql as sqlx::Database>::Connection = t;
-            let mut query = sqlx::query::<sqlx::MySql>(sql);
+            let mut query = sqlx::query::<sqlx::MySql>(sql).persistent(persistent);
             for param in params {
                 query = MysqlParam::add_param(*param, query)
             }
@@ -220,19 +342,50 @@ async fn execute_inner<'t>(
             }
             result
         }
+
+        #[cfg(feature = "odbc")]
+        TransT::Odbc(t) => t.execute(sql, params).await,
+
+        #[cfg(feature = "mock")]
+        TransT::Mock(t) => Ok(t.client().execute_unhooked(sql, params)),
     }
 }
 
+// Only MSSQL keeps the result sets apart
+async fn fetch_result_sets_inner(
+    inner: &mut TransT<'_>,
+    sql: &str,
+    params: &[&(dyn Param + Sync + Send)],
+    persistent: bool,
+) -> Result<Vec<Vec<Row>>> {
+    #[cfg(feature = "mssql")]
+    if let TransT::Mssql(inner) = &mut *inner {
+        let result = inner.fetch_result_sets(sql, params).await;
+        if result.is_err() {
+            inner.rollback_internal().await?;
+        }
+        return result;
+    }
+    let rows = fetch_rows_inner(inner, sql, params, persistent).await?;
+    Ok(vec![rows])
+}
+
+#[cfg_attr(
+    not(any(feature = "postgres", feature = "mysql", feature = "sqlite")),
+    allow(unused_variables)
+)]
 async fn fetch_rows_inner<'t>(
     inner: &mut TransT<'t>,
     sql: &str,
     params: &[&(dyn Param + Sync + Send)],
+    persistent: bool,
 ) -> Result<Vec<Row>> {
     match inner {
         #[cfg(feature = "sqlite")]
         TransT::Sqlite(t) => {
             let x: &mut <sqlx::Sqlite as sqlx::Database>::Connection = t;
-            let mut query = sqlx::query::<sqlx::Sqlite>(sql);
+            let sent = crate::sqlite::fingerprint(sql);
+            let mut query = sqlx::query::<sqlx::Sqlite>(&sent).persistent(persistent);
             for param in params {
                 query = SqliteParam::add_param(*param, query)
             }
@@ -244,7 +397,7 @@ async fn fetch_rows_inner<'t>(
         #[cfg(feature = "postgres")]
         TransT::Postgres(t) => {
             let x: &mut <sqlx::Postgres as sqlx::Database>::Connection = t;
-            let mut query = sqlx::query::<sqlx::Postgres>(sql);
+            let mut query = sqlx::query::<sqlx::Postgres>(sql).persistent(persistent);
             for param in params {
                 query = PostgresParam::add_param(*param, query)
             }
@@ -256,7 +409,7 @@ async fn fetch_rows_inner<'t>(
         #[cfg(feature = "mysql")]
         TransT::Mysql(t) => {
             let x: &mut <sqlx::MySql as sqlx::Database>::Connection = t;
-            let mut query = sqlx::query::<sqlx::MySql>(sql);
+            let mut query = sqlx::query::<sqlx::MySql>(sql).persistent(persistent);
             for param in params {
                 query = MysqlParam::add_param(*param, query)
             }
@@ -273,5 +426,11 @@ async fn fetch_rows_inner<'t>(
             }
             result
         }
+
+        #[cfg(feature = "odbc")]
+        TransT::Odbc(t) => t.fetch_rows(sql, params).await,
+
+        #[cfg(feature = "mock")]
+        TransT::Mock(t) => Ok(t.client().fetch_rows_unhooked(sql, params)),
     }
 }
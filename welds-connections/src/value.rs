@@ -0,0 +1,570 @@
+use crate::errors::Error;
+use crate::errors::Result;
+use crate::Param;
+use std::any::Any;
+
+/// An owned database value.
+/// Used by backends that copy data out of the driver (odbc) and by the mock client.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+    /// A uuid, read from `uuid::Uuid` with the `uuid` feature
+    Uuid([u8; 16]),
+    /// A date, a time of day, or a timestamp as ISO 8601 text (`2024-05-01T10:30:00Z`).
+    /// Read from the `chrono` types with the `chrono` feature, timestamps with a timezone are
+    /// written in UTC so the same moment is always the same text
+    DateTime(String),
+    /// A param of a type a `Value` can't hold, known only by the name of its rust type.
+    /// Recorded by the mock client, never sent to a database
+    Opaque(String),
+}
+
+/// Reads a param back as a `Value`. Every param has this, the clients that copy their params
+/// (the mock, replay and odbc) use it instead of asking more of the param's type.
+///
+/// Only the types a `Value` can hold give a value back: numbers, bools, text, bytes,
+/// `Value` itself, uuids and dates with the `uuid` and `chrono` features,
+/// and an `Option` of any of them.
+pub trait ParamValue {
+    /// The value of the param, `None` if its type isn't one a `Value` can hold
+    fn param_value(&self) -> Option<Value>;
+
+    /// The name of the rust type of the param
+    fn param_type(&self) -> &'static str;
+}
+
+impl<T: Any> ParamValue for T {
+    fn param_value(&self) -> Option<Value> {
+        value_of(self)
+    }
+
+    fn param_type(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+}
+
+/// A param as a `Value`, or `Opaque` with the name of its type when a `Value` can't hold it.
+/// How the mock and replay clients record params, and the query log writes them
+pub fn of_param(param: &(dyn Param + Sync + Send)) -> Value {
+    // called on the `dyn Param`, a reference to it would be read as a param of its own
+    param
+        .param_value()
+        .unwrap_or_else(|| Value::Opaque(param.param_type().to_owned()))
+}
+
+/// Written the way the query log shows a param: `42`, `"text"`, `NULL`, `<3 bytes>`,
+/// and the type for an opaque value `<uuid::Uuid>`
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => write!(f, "NULL"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Text(s) => write!(f, "{:?}", s),
+            Value::Bytes(b) => write!(f, "<{} bytes>", b.len()),
+            Value::Uuid(bytes) => write!(f, "{}", uuid_text(bytes)),
+            Value::DateTime(text) => write!(f, "{}", text),
+            Value::Opaque(name) => write!(f, "<{}>", name),
+        }
+    }
+}
+
+// the hyphenated form of a uuid, `67e55044-10b1-426f-9247-bb680e5fe0c8`
+fn uuid_text(bytes: &[u8; 16]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn value_of(any: &dyn Any) -> Option<Value> {
+    macro_rules! known {
+        ($($t:ty),*) => {
+            $(
+                if let Some(v) = any.downcast_ref::<$t>() {
+                    return Some(v.to_value());
+                }
+                if let Some(v) = any.downcast_ref::<Option<$t>>() {
+                    return Some(v.to_value());
+                }
+            )*
+        };
+    }
+    known!(
+        Value,
+        bool,
+        String,
+        &'static str,
+        Vec<u8>,
+        f32,
+        f64,
+        i8,
+        i16,
+        i32,
+        i64,
+        u8,
+        u16,
+        u32
+    );
+    #[cfg(feature = "uuid")]
+    known!(uuid::Uuid);
+    #[cfg(feature = "chrono")]
+    known!(
+        chrono::NaiveDate,
+        chrono::NaiveTime,
+        chrono::NaiveDateTime,
+        chrono::DateTime<chrono::Utc>,
+        chrono::DateTime<chrono::FixedOffset>,
+        chrono::DateTime<chrono::Local>
+    );
+    // only the u64s that fit
+    if let Some(v) = any.downcast_ref::<u64>() {
+        return i64::try_from(*v).ok().map(Value::Int);
+    }
+    if let Some(v) = any.downcast_ref::<Option<u64>>() {
+        return match v {
+            Some(v) => i64::try_from(*v).ok().map(Value::Int),
+            None => Some(Value::Null),
+        };
+    }
+    None
+}
+
+/// Reads a value into a `T`, for any `T` that `FromValue` can read.
+/// Rows of copied values are read with this, so `Row::get` asks nothing more of its type than
+/// the real backends do. Other types are an error
+pub(crate) fn read_as<T: Any>(col: &str, value: &Value) -> Result<T> {
+    let mut out: Option<T> = None;
+    let slot: &mut dyn Any = &mut out;
+    let mut known = false;
+    macro_rules! known {
+        ($($t:ty),*) => {
+            $(
+                if let Some(slot) = slot.downcast_mut::<Option<$t>>() {
+                    *slot = Some(<$t as FromValue>::read(col, value)?);
+                    known = true;
+                } else if let Some(slot) = slot.downcast_mut::<Option<Option<$t>>>() {
+                    *slot = Some(<Option<$t> as FromValue>::read(col, value)?);
+                    known = true;
+                }
+            )*
+        };
+    }
+    known!(
+        Value,
+        bool,
+        String,
+        Vec<u8>,
+        f32,
+        f64,
+        i8,
+        i16,
+        i32,
+        i64,
+        u8,
+        u16,
+        u32,
+        u64
+    );
+    #[cfg(feature = "uuid")]
+    known!(uuid::Uuid);
+    #[cfg(feature = "chrono")]
+    known!(
+        chrono::NaiveDate,
+        chrono::NaiveTime,
+        chrono::NaiveDateTime,
+        chrono::DateTime<chrono::Utc>,
+        chrono::DateTime<chrono::FixedOffset>,
+        chrono::DateTime<chrono::Local>
+    );
+    if !known {
+        return Err(Error::ColumnDecode(
+            col.to_owned(),
+            format!(
+                "a {} can't be read from a copied value, only numbers, bools, text, bytes, uuids and dates can",
+                std::any::type_name::<T>()
+            ),
+        ));
+    }
+    Ok(out.expect("the value was read into the slot"))
+}
+
+/// Converts a rust value into a `Value` so it can be sent as a param
+pub trait ToValue {
+    fn to_value(&self) -> Value;
+}
+
+/// Reads a rust value out of a `Value` in a row
+pub trait FromValue
+where
+    Self: Sized,
+{
+    fn read(col: &str, value: &Value) -> Result<Self>;
+}
+
+impl ToValue for Value {
+    fn to_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl<T> ToValue for Option<T>
+where
+    T: ToValue,
+{
+    fn to_value(&self) -> Value {
+        match self {
+            Some(v) => v.to_value(),
+            None => Value::Null,
+        }
+    }
+}
+
+impl ToValue for bool {
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+impl ToValue for String {
+    fn to_value(&self) -> Value {
+        Value::Text(self.clone())
+    }
+}
+
+impl ToValue for &str {
+    fn to_value(&self) -> Value {
+        Value::Text(self.to_string())
+    }
+}
+
+impl ToValue for Vec<u8> {
+    fn to_value(&self) -> Value {
+        Value::Bytes(self.clone())
+    }
+}
+
+impl ToValue for f32 {
+    fn to_value(&self) -> Value {
+        Value::Float(*self as f64)
+    }
+}
+
+impl ToValue for f64 {
+    fn to_value(&self) -> Value {
+        Value::Float(*self)
+    }
+}
+
+macro_rules! int_to_value {
+    ($($t:ty),*) => {
+        $(
+            impl ToValue for $t {
+                fn to_value(&self) -> Value {
+                    Value::Int(*self as i64)
+                }
+            }
+        )*
+    };
+}
+
+int_to_value!(i8, i16, i32, i64, u8, u16, u32);
+
+fn mismatch(col: &str, value: &Value, expected: &str) -> Error {
+    Error::ColumnDecode(
+        col.to_owned(),
+        format!("expected {}, found {:?}", expected, value),
+    )
+}
+
+impl FromValue for Value {
+    fn read(_col: &str, value: &Value) -> Result<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl<T> FromValue for Option<T>
+where
+    T: FromValue,
+{
+    fn read(col: &str, value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Ok(None),
+            _ => Ok(Some(T::read(col, value)?)),
+        }
+    }
+}
+
+impl FromValue for String {
+    fn read(col: &str, value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Err(Error::UnexpectedNoneInColumn(col.to_owned())),
+            Value::Text(v) => Ok(v.clone()),
+            Value::Int(v) => Ok(v.to_string()),
+            Value::Float(v) => Ok(v.to_string()),
+            Value::Bool(v) => Ok(v.to_string()),
+            Value::Uuid(v) => Ok(uuid_text(v)),
+            Value::DateTime(v) => Ok(v.clone()),
+            Value::Bytes(_) | Value::Opaque(_) => Err(mismatch(col, value, "text")),
+        }
+    }
+}
+
+impl FromValue for Vec<u8> {
+    fn read(col: &str, value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Err(Error::UnexpectedNoneInColumn(col.to_owned())),
+            Value::Bytes(v) => Ok(v.clone()),
+            Value::Uuid(v) => Ok(v.to_vec()),
+            Value::Text(v) => Ok(v.as_bytes().to_vec()),
+            _ => Err(mismatch(col, value, "binary")),
+        }
+    }
+}
+
+impl FromValue for bool {
+    fn read(col: &str, value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Err(Error::UnexpectedNoneInColumn(col.to_owned())),
+            Value::Bool(v) => Ok(*v),
+            Value::Int(v) => Ok(*v != 0),
+            Value::Text(v) if v == "1" || v.eq_ignore_ascii_case("true") => Ok(true),
+            Value::Text(v) if v == "0" || v.eq_ignore_ascii_case("false") => Ok(false),
+            _ => Err(mismatch(col, value, "bool")),
+        }
+    }
+}
+
+impl FromValue for f64 {
+    fn read(col: &str, value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Err(Error::UnexpectedNoneInColumn(col.to_owned())),
+            Value::Float(v) => Ok(*v),
+            Value::Int(v) => Ok(*v as f64),
+            // decimals and numerics are read as text
+            Value::Text(v) => v.trim().parse().map_err(|_| mismatch(col, value, "f64")),
+            _ => Err(mismatch(col, value, "f64")),
+        }
+    }
+}
+
+impl FromValue for f32 {
+    fn read(col: &str, value: &Value) -> Result<Self> {
+        let v: f64 = FromValue::read(col, value)?;
+        Ok(v as f32)
+    }
+}
+
+macro_rules! int_from_value {
+    ($($t:ty),*) => {
+        $(
+            impl FromValue for $t {
+                fn read(col: &str, value: &Value) -> Result<Self> {
+                    let v: i64 = match value {
+                        Value::Null => return Err(Error::UnexpectedNoneInColumn(col.to_owned())),
+                        Value::Int(v) => *v,
+                        Value::Bool(v) => *v as i64,
+                        Value::Text(v) => v
+                            .trim()
+                            .parse()
+                            .map_err(|_| mismatch(col, value, stringify!($t)))?,
+                        _ => return Err(mismatch(col, value, stringify!($t))),
+                    };
+                    <$t>::try_from(v).map_err(|_| mismatch(col, value, stringify!($t)))
+                }
+            }
+        )*
+    };
+}
+
+int_from_value!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+#[cfg(feature = "uuid")]
+impl ToValue for uuid::Uuid {
+    fn to_value(&self) -> Value {
+        Value::Uuid(self.into_bytes())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl FromValue for uuid::Uuid {
+    fn read(col: &str, value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Err(Error::UnexpectedNoneInColumn(col.to_owned())),
+            Value::Uuid(v) => Ok(uuid::Uuid::from_bytes(*v)),
+            Value::Text(v) => v.parse().map_err(|_| mismatch(col, value, "uuid")),
+            Value::Bytes(v) => uuid::Uuid::from_slice(v).map_err(|_| mismatch(col, value, "uuid")),
+            _ => Err(mismatch(col, value, "uuid")),
+        }
+    }
+}
+
+// The dates and times are written as ISO 8601 text, and read back from it
+#[cfg(feature = "chrono")]
+mod dates {
+    use super::{mismatch, FromValue, ToValue, Value};
+    use crate::errors::{Error, Result};
+    use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+    use chrono::{SecondsFormat, TimeZone};
+
+    impl ToValue for NaiveDate {
+        fn to_value(&self) -> Value {
+            Value::DateTime(self.format("%Y-%m-%d").to_string())
+        }
+    }
+
+    impl ToValue for NaiveTime {
+        fn to_value(&self) -> Value {
+            Value::DateTime(self.format("%H:%M:%S%.f").to_string())
+        }
+    }
+
+    impl ToValue for NaiveDateTime {
+        fn to_value(&self) -> Value {
+            Value::DateTime(self.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+        }
+    }
+
+    impl<Tz: TimeZone> ToValue for DateTime<Tz> {
+        fn to_value(&self) -> Value {
+            let utc = self.with_timezone(&Utc);
+            Value::DateTime(utc.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+    }
+
+    fn text<'v>(col: &str, value: &'v Value, expected: &str) -> Result<&'v str> {
+        match value {
+            Value::Null => Err(Error::UnexpectedNoneInColumn(col.to_owned())),
+            Value::DateTime(v) | Value::Text(v) => Ok(v.as_str()),
+            _ => Err(mismatch(col, value, expected)),
+        }
+    }
+
+    impl FromValue for NaiveDate {
+        fn read(col: &str, value: &Value) -> Result<Self> {
+            let v = text(col, value, "date")?;
+            v.parse().map_err(|_| mismatch(col, value, "date"))
+        }
+    }
+
+    impl FromValue for NaiveTime {
+        fn read(col: &str, value: &Value) -> Result<Self> {
+            let v = text(col, value, "time")?;
+            v.parse().map_err(|_| mismatch(col, value, "time"))
+        }
+    }
+
+    impl FromValue for NaiveDateTime {
+        fn read(col: &str, value: &Value) -> Result<Self> {
+            let v = text(col, value, "timestamp")?;
+            v.parse().map_err(|_| mismatch(col, value, "timestamp"))
+        }
+    }
+
+    impl FromValue for DateTime<FixedOffset> {
+        fn read(col: &str, value: &Value) -> Result<Self> {
+            let v = text(col, value, "timestamp")?;
+            DateTime::parse_from_rfc3339(v).map_err(|_| mismatch(col, value, "timestamp"))
+        }
+    }
+
+    impl FromValue for DateTime<Utc> {
+        fn read(col: &str, value: &Value) -> Result<Self> {
+            let v: DateTime<FixedOffset> = FromValue::read(col, value)?;
+            Ok(v.with_timezone(&Utc))
+        }
+    }
+
+    impl FromValue for DateTime<Local> {
+        fn read(col: &str, value: &Value) -> Result<Self> {
+            let v: DateTime<FixedOffset> = FromValue::read(col, value)?;
+            Ok(v.with_timezone(&Local))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Unknown;
+
+    #[test]
+    fn should_read_back_the_params_a_value_can_hold() {
+        assert_eq!(5_i16.param_value(), Some(Value::Int(5)));
+        assert_eq!(Some(1.5_f64).param_value(), Some(Value::Float(1.5)));
+        assert_eq!(Option::<String>::None.param_value(), Some(Value::Null));
+        assert_eq!("hi".param_value(), Some(Value::Text("hi".to_owned())));
+        assert_eq!(u64::MAX.param_value(), None);
+        assert_eq!(Unknown.param_value(), None);
+        assert!(Unknown.param_type().ends_with("Unknown"));
+    }
+
+    #[test]
+    fn should_read_a_value_into_the_types_from_value_knows() {
+        assert_eq!(read_as::<i32>("id", &Value::Int(3)).unwrap(), 3);
+        assert_eq!(
+            read_as::<u64>("id", &Value::Text("9".to_owned())).unwrap(),
+            9
+        );
+        assert_eq!(read_as::<Option<bool>>("ok", &Value::Null).unwrap(), None);
+        assert_eq!(
+            read_as::<Value>("raw", &Value::Bytes(vec![1])).unwrap(),
+            Value::Bytes(vec![1])
+        );
+        assert!(read_as::<u8>("small", &Value::Int(300)).is_err());
+
+        let err = read_as::<std::time::Duration>("at", &Value::Int(1)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unable to decode column: at. a core::time::Duration can't be read from a copied value, only numbers, bools, text, bytes, uuids and dates can"
+        );
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn should_hold_a_uuid() {
+        let id = uuid::Uuid::from_bytes([0xab; 16]);
+        assert_eq!(id.param_value(), Some(Value::Uuid([0xab; 16])));
+        assert_eq!(
+            Value::Uuid([0xab; 16]).to_string(),
+            "abababab-abab-abab-abab-abababababab"
+        );
+        let text = Value::Text(id.to_string());
+        assert_eq!(read_as::<uuid::Uuid>("id", &text).unwrap(), id);
+        assert_eq!(
+            read_as::<String>("id", &id.to_value()).unwrap(),
+            id.to_string()
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn should_hold_a_date_as_the_same_text_in_any_timezone() {
+        use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+        let at = DateTime::parse_from_rfc3339("2024-05-01T12:30:00+02:00").unwrap();
+        let expected = Value::DateTime("2024-05-01T10:30:00Z".to_owned());
+        assert_eq!(at.param_value(), Some(expected.clone()));
+        assert_eq!(at.with_timezone(&Utc).param_value(), Some(expected.clone()));
+        assert_eq!(
+            read_as::<DateTime<FixedOffset>>("at", &expected).unwrap(),
+            at
+        );
+
+        let day = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        assert_eq!(day.to_value(), Value::DateTime("2024-05-01".to_owned()));
+        assert_eq!(read_as::<NaiveDate>("day", &day.to_value()).unwrap(), day);
+    }
+}
@@ -3,6 +3,7 @@ use crate::utils::as_typepath;
 use crate::{
     column::Column,
     hook::{Hook, HookKind},
+    mapping::{MappedField, Mapping},
     relation::Relation,
 };
 use proc_macro2::{Ident, Span};
@@ -117,17 +118,36 @@ pub(crate) fn get_relations(ast: &syn::DeriveInput) -> Result<Vec<Relation>> {
         .iter()
         .filter_map(|m| as_metalist_ref(m))
         .filter(|m| m.path.is_ident("HasMany"))
-        .map(|m| Relation::new(m, "HasMany"))
+        .map(|m| Relation::new(m, "HasMany", &ast.ident))
         .collect();
     let mut relations1 = relations1?;
     let relations2: Result<Vec<_>> = inners
         .iter()
         .filter_map(|m| as_metalist_ref(m))
         .filter(|m| m.path.is_ident("BelongsTo"))
-        .map(|m| Relation::new(m, "BelongsTo"))
+        .map(|m| Relation::new(m, "BelongsTo", &ast.ident))
         .collect();
     let mut relations2 = relations2?;
-    let relations: Vec<_> = relations1.drain(..).chain(relations2.drain(..)).collect();
+    let relations3: Result<Vec<_>> = inners
+        .iter()
+        .filter_map(|m| as_metalist_ref(m))
+        .filter(|m| m.path.is_ident("HasOne"))
+        .map(|m| Relation::new(m, "HasOne", &ast.ident))
+        .collect();
+    let mut relations3 = relations3?;
+    let relations4: Result<Vec<_>> = inners
+        .iter()
+        .filter_map(|m| as_metalist_ref(m))
+        .filter(|m| m.path.is_ident("BelongsToMany"))
+        .map(|m| Relation::many(m, &ast.ident))
+        .collect();
+    let mut relations4 = relations4?;
+    let relations: Vec<_> = relations1
+        .drain(..)
+        .chain(relations2.drain(..))
+        .chain(relations3.drain(..))
+        .chain(relations4.drain(..))
+        .collect();
 
     Ok(relations)
 }
@@ -246,6 +266,127 @@ pub(crate) fn get_readonly(ast: &syn::DeriveInput) -> bool {
     inners.iter().any(|&m| m.path().is_ident("readonly"))
 }
 
+pub(crate) fn get_partial_of(ast: &syn::DeriveInput) -> Option<syn::Path> {
+    let metas = welds_meta(&ast.attrs);
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    // read the model out of partial_of(a::User)
+    inners
+        .iter()
+        .filter_map(|m| as_metalist_ref(m))
+        .filter(|m| m.path.is_ident("partial_of"))
+        .flat_map(as_metalist_nested_meta)
+        .filter_map(|m| match m {
+            syn::Meta::Path(path) => Some(path.clone()),
+            _ => None,
+        })
+        .next()
+}
+
+pub(crate) fn get_default_scope(ast: &syn::DeriveInput) -> Option<syn::Path> {
+    let metas = welds_meta(&ast.attrs);
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    // read the function out of default_scope(tenant_filter)
+    inners
+        .iter()
+        .filter_map(|m| as_metalist_ref(m))
+        .filter(|m| m.path.is_ident("default_scope"))
+        .flat_map(as_metalist_nested_meta)
+        .filter_map(|m| match m {
+            syn::Meta::Path(path) => Some(path.clone()),
+            _ => None,
+        })
+        .next()
+}
+
+pub(crate) fn get_tree(ast: &syn::DeriveInput) -> Option<String> {
+    let metas = welds_meta(&ast.attrs);
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    // find the parent field in tree="parent_id"
+    inners
+        .iter()
+        .filter_map(|m| as_meta_namevalue_ref(m))
+        .filter(|m| m.path.is_ident("tree"))
+        .filter_map(|nv| lit_as_litstr(&nv.lit))
+        .map(|x| x.value())
+        .next()
+}
+
+pub(crate) fn get_soft_delete(ast: &syn::DeriveInput) -> Option<String> {
+    let metas = welds_meta(&ast.attrs);
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    // find the timestamp field in soft_delete="deleted_at"
+    inners
+        .iter()
+        .filter_map(|m| as_meta_namevalue_ref(m))
+        .filter(|m| m.path.is_ident("soft_delete"))
+        .filter_map(|nv| lit_as_litstr(&nv.lit))
+        .map(|x| x.value())
+        .next()
+}
+
+pub(crate) fn get_scopes(ast: &syn::DeriveInput) -> Vec<syn::Ident> {
+    let metas = welds_meta(&ast.attrs);
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    // read the names out of scopes(active, admins)
+    inners
+        .iter()
+        .filter_map(|m| as_metalist_ref(m))
+        .filter(|m| m.path.is_ident("scopes"))
+        .flat_map(as_metalist_nested_meta)
+        .filter_map(|m| m.path().get_ident().cloned())
+        .collect()
+}
+
+pub(crate) fn get_graphql(ast: &syn::DeriveInput) -> bool {
+    let metas = welds_meta(&ast.attrs);
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    inners.iter().any(|&m| m.path().is_ident("graphql"))
+}
+
+pub(crate) fn get_mapping(ast: &syn::DeriveInput) -> Mapping {
+    let metas = welds_meta(&ast.attrs);
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    // read the paths out of mapping(a::B, c::D)
+    let targets: Vec<syn::Path> = inners
+        .iter()
+        .filter_map(|m| as_metalist_ref(m))
+        .filter(|m| m.path.is_ident("mapping"))
+        .flat_map(as_metalist_nested_meta)
+        .filter_map(|m| match m {
+            syn::Meta::Path(path) => Some(path.clone()),
+            _ => None,
+        })
+        .collect();
+    if targets.is_empty() {
+        return Mapping::default();
+    }
+
+    let fields = match &ast.data {
+        syn::Data::Struct(d) => &d.fields,
+        _ => return Mapping::default(),
+    };
+    let fields = fields
+        .iter()
+        .filter_map(|f| {
+            let field = f.ident.clone()?;
+            let metas = welds_meta(&f.attrs);
+            let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+            let skip = inners.iter().any(|m| m.path().is_ident("mapping_skip"));
+            let other = inners
+                .iter()
+                .filter_map(|m| as_meta_namevalue_ref(m))
+                .filter(|m| m.path.is_ident("mapping_field"))
+                .filter_map(|nv| lit_as_litstr(&nv.lit))
+                .map(|x| Ident::new(&x.value(), x.span()))
+                .next()
+                .unwrap_or_else(|| field.clone());
+            Some(MappedField { field, other, skip })
+        })
+        .collect();
+
+    Mapping { targets, fields }
+}
+
 fn as_metalist(meta: syn::Meta) -> Option<syn::MetaList> {
     match meta {
         syn::Meta::List(inner) => Some(inner),
@@ -314,6 +455,29 @@ fn is_welds_ignore(attrs: &[Attribute]) -> bool {
         .any(|m| m.path().is_ident("ignore"))
 }
 
+/// The fields marked #[welds(version)]
+pub(crate) fn get_version_fields(ast: &syn::DeriveInput) -> Vec<String> {
+    let struct_def = match &ast.data {
+        syn::Data::Struct(d) => d,
+        _ => return Vec::default(),
+    };
+    struct_def
+        .fields
+        .iter()
+        .filter(|f| is_welds_version(&f.attrs))
+        .filter_map(|f| f.ident.as_ref())
+        .map(|ident| ident.to_string())
+        .collect()
+}
+
+fn is_welds_version(attrs: &[Attribute]) -> bool {
+    let metas = welds_meta(attrs);
+    metas
+        .iter()
+        .flat_map(as_metalist_nested_meta)
+        .any(|m| m.path().is_ident("version"))
+}
+
 fn is_welds_pk(attrs: &[Attribute]) -> bool {
     let metas = welds_meta(attrs);
     metas
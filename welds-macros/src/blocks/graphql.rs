@@ -0,0 +1,207 @@
+use crate::column::Column;
+use crate::info::Info;
+use crate::relation::Relation;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+pub(crate) fn write(info: &Info) -> TokenStream {
+    if !info.graphql {
+        return quote! {};
+    }
+    let object = write_object(info);
+    let input = write_input(info);
+    quote! {
+        #object
+        #input
+    }
+}
+
+fn write_object(info: &Info) -> TokenStream {
+    let wp = &info.welds_path;
+    let defstruct = &info.defstruct;
+    let agq = quote! { #wp::graphql::async_graphql };
+    let crate_path = agq.to_string().replace(' ', "");
+    let name = defstruct.to_string();
+
+    let fields: Vec<_> = info
+        .columns
+        .iter()
+        .filter(|c| !c.ignore)
+        .map(field_resolver)
+        .collect();
+    // the dataloader loads by one column, a many-to-many relationship
+    // or a key over several columns has no resolver
+    let relations: Vec<_> = info
+        .relations
+        .iter()
+        .filter(|r| r.through.is_none() && r.shared_keys.is_empty())
+        .map(|r| relation_resolver(info, r))
+        .collect();
+
+    quote! {
+        #[#agq::Object(crate = #crate_path, name = #name)]
+        impl #defstruct {
+            #(#fields)*
+            #(#relations)*
+        }
+    }
+}
+
+fn field_resolver(col: &Column) -> TokenStream {
+    let field = &col.field;
+    let ft = &col.field_type;
+    let ty = if col.is_option {
+        quote! { Option<#ft> }
+    } else {
+        quote! { #ft }
+    };
+    quote! {
+        async fn #field(&self) -> #ty {
+            self.#field.clone()
+        }
+    }
+}
+
+fn relation_resolver(info: &Info, relation: &Relation) -> TokenStream {
+    let wp = &info.welds_path;
+    let field = &relation.field;
+    let other = &relation.foreign_struct;
+    let fk = relation.foreign_key.as_str();
+    let agq = quote! { #wp::graphql::async_graphql };
+    let load = |key: TokenStream, column: TokenStream| {
+        quote! {
+            let loader = ctx.data::<#agq::dataloader::DataLoader<#wp::graphql::WeldsLoader>>()?;
+            let related = #wp::graphql::RelatedBy::<#other, _>::new(#column, #key);
+            let found = loader.load_one(related).await?.unwrap_or_default();
+        }
+    };
+
+    if relation.kind == "BelongsTo" {
+        let col = match info.columns.iter().find(|c| c.dbname == fk) {
+            Some(col) => col,
+            None => {
+                let err = format!(
+                    "graphql: BelongsTo({}) has no field for column {}",
+                    field, fk
+                );
+                return quote! { std::compile_error!(#err); };
+            }
+        };
+        let fk_field = &col.field;
+        let key = if col.is_option {
+            quote! {
+                match &self.#fk_field {
+                    Some(key) => key.clone(),
+                    None => return Ok(None),
+                }
+            }
+        } else {
+            quote! { self.#fk_field.clone() }
+        };
+        let column = quote! {
+            <<#other as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::UniqueIdentifier>::id_column().name()
+        };
+        let load = load(quote! { key }, column);
+        return quote! {
+            async fn #field(&self, ctx: &#agq::Context<'_>) -> #agq::Result<Option<std::sync::Arc<#other>>> {
+                let key = #key;
+                #load
+                Ok(found.into_iter().next())
+            }
+        };
+    }
+
+    if info.pks.len() != 1 {
+        let err = format!(
+            "graphql: {}({}) requires exactly one primary key",
+            relation.kind, field
+        );
+        return quote! { std::compile_error!(#err); };
+    }
+    let pk = &info.pks[0].field;
+    let load = load(quote! { self.#pk.clone() }, quote! { #fk });
+    if relation.kind == "HasOne" {
+        return quote! {
+            async fn #field(&self, ctx: &#agq::Context<'_>) -> #agq::Result<Option<std::sync::Arc<#other>>> {
+                #load
+                Ok(found.into_iter().next())
+            }
+        };
+    }
+    quote! {
+        async fn #field(&self, ctx: &#agq::Context<'_>) -> #agq::Result<Vec<std::sync::Arc<#other>>> {
+            #load
+            Ok(found)
+        }
+    }
+}
+
+fn write_input(info: &Info) -> TokenStream {
+    let wp = &info.welds_path;
+    let defstruct = &info.defstruct;
+    let agq = quote! { #wp::graphql::async_graphql };
+    let crate_path = agq.to_string().replace(' ', "");
+    let name = format!("{}Input", defstruct);
+    let input = Ident::new(&name, defstruct.span());
+
+    let pks: Vec<&Ident> = info.pks.iter().map(|c| &c.field).collect();
+    let cols: Vec<&Column> = info
+        .columns
+        .iter()
+        .filter(|c| !c.ignore && !pks.contains(&&c.field))
+        .collect();
+
+    let fields: Vec<_> = cols
+        .iter()
+        .map(|c| {
+            let field = &c.field;
+            let ft = &c.field_type;
+            if c.is_option {
+                quote! { pub #field: Option<#ft> }
+            } else {
+                quote! { pub #field: #ft }
+            }
+        })
+        .collect();
+    let assigns: Vec<_> = cols
+        .iter()
+        .map(|c| {
+            let field = &c.field;
+            quote! { model.#field = self.#field; }
+        })
+        .collect();
+
+    quote! {
+        #[derive(#agq::InputObject)]
+        #[graphql(crate = #crate_path, name = #name)]
+        pub struct #input {
+            #(#fields),*
+        }
+
+        impl #input {
+            /// Writes the values from this input onto the model
+            pub fn apply_to(self, model: &mut #defstruct) {
+                #(#assigns)*
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_only_write_graphql_types_when_asked() {
+        let mut info = Info::mock()
+            .add_pk("id", "i64")
+            .add_column("name", "String", false);
+        assert!(write(&info).is_empty());
+
+        info.graphql = true;
+        let code = write(&info).to_string();
+        assert!(code.contains("impl Mock"));
+        assert!(code.contains("pub struct MockInput { pub name : String }"));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::info::Info;
+use crate::relation::Relation;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+// attach_, detach_ and sync_ for each BelongsToMany, they write to its join table
+pub(crate) fn write(info: &Info) -> TokenStream {
+    let parts: Vec<_> = info
+        .relations
+        .iter()
+        .filter(|r| r.through.is_some())
+        .map(|r| write_relation(info, r))
+        .collect();
+    quote! { #(#parts)* }
+}
+
+fn write_relation(info: &Info, relation: &Relation) -> TokenStream {
+    let wp = &info.welds_path;
+    let field = &relation.field;
+    if info.pks.len() != 1 {
+        let err = format!("BelongsToMany({}) requires exactly one primary key", field);
+        return quote! { std::compile_error!(#err); };
+    }
+    let pk = &info.pks[0].field;
+    let attach = format_ident!("attach_{}", field);
+    let detach = format_ident!("detach_{}", field);
+    let sync = format_ident!("sync_{}", field);
+    let param = quote! { (dyn #wp::connections::Param + Sync + Send) };
+    let setup = quote! {
+        let relations = <Self as #wp::relations::HasRelations>::Relation::default();
+        let through = relations.#field.join_table();
+        let keys: Vec<&#param> = keys.iter().map(|k| k as &#param).collect();
+    };
+
+    quote! {
+
+    /// Links this row to the related rows with these primary keys, adding the rows of the join
+    /// table that are missing. Returns the number of rows added
+    pub async fn #attach<K>(&self, client: &dyn #wp::Client, keys: &[K]) -> #wp::errors::Result<u64>
+    where
+        K: #wp::connections::Param + Sync + Send,
+    {
+        #setup
+        through.attach(client, &self.#pk, &keys).await
+    }
+
+    /// Unlinks this row from the related rows with these primary keys, removing their rows
+    /// of the join table. Returns the number of rows removed
+    pub async fn #detach<K>(&self, client: &dyn #wp::Client, keys: &[K]) -> #wp::errors::Result<u64>
+    where
+        K: #wp::connections::Param + Sync + Send,
+    {
+        #setup
+        through.detach(client, &self.#pk, &keys).await
+    }
+
+    /// Links this row to exactly the related rows with these primary keys,
+    /// adding and removing rows of the join table
+    pub async fn #sync<K>(&self, client: &dyn #wp::Client, keys: &[K]) -> #wp::errors::Result<()>
+    where
+        K: #wp::connections::Param + Sync + Send,
+    {
+        #setup
+        through.sync(client, &self.#pk, &keys).await
+    }
+
+    }
+}
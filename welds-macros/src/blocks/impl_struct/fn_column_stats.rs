@@ -0,0 +1,22 @@
+use crate::info::Info;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn write(info: &Info) -> TokenStream {
+    let wp = &info.welds_path;
+
+    quote! {
+
+        /// Null fraction, distinct count, and min/max of the given columns (all columns if empty)
+        pub async fn column_stats(
+            client: &dyn #wp::Client,
+            columns: &[&str],
+        ) -> #wp::errors::Result<Vec<#wp::stats::ColumnStats>>
+        where
+            <Self as #wp::model_traits::HasSchema>::Schema: #wp::model_traits::TableInfo + #wp::model_traits::TableColumns,
+        {
+            #wp::stats::column_stats::<Self>(client, columns).await
+        }
+
+    }
+}
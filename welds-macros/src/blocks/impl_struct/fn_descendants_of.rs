@@ -0,0 +1,23 @@
+use crate::info::Info;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn write(info: &Info) -> TokenStream {
+    let parent = match &info.tree {
+        Some(parent) => parent,
+        None => return quote!(),
+    };
+    let wp = &info.welds_path;
+    let parent_col = &parent.dbname;
+    let ty = &info.pks[0].field_type;
+
+    quote! {
+
+        /// All the rows below the given one in the tree, flattened and ordered by depth
+        pub fn descendants_of(id: impl Into<#ty>) -> #wp::query::tree::TreeQuery<Self> {
+            let id: #ty = id.into();
+            #wp::query::tree::TreeQuery::descendants_of(#parent_col, id)
+        }
+
+    }
+}
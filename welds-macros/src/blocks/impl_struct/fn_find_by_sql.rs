@@ -0,0 +1,30 @@
+use crate::info::Info;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn write(info: &Info) -> TokenStream {
+    let wp = &info.welds_path;
+    quote! {
+
+    /// Runs your own SQL and reads the rows it returns into this model.
+    /// Columns are matched to fields by name, any extra columns are ignored
+    pub async fn find_by_sql(
+        client: &dyn #wp::Client,
+        sql: &str,
+        params: &[&(dyn #wp::connections::Param + Sync + Send)],
+    ) -> #wp::errors::Result<Vec<#wp::state::DbState<Self>>>
+    where
+        Self: Send + TryFrom<#wp::Row>,
+        #wp::WeldsError: From<<Self as TryFrom<#wp::Row>>::Error>
+    {
+        let rows: Vec<#wp::Row> = client.fetch_rows(sql, params).await?;
+        let mut data = Vec::with_capacity(rows.len());
+        for row in rows {
+            let model = Self::try_from(row)?;
+            data.push(#wp::state::DbState::db_loaded(model));
+        }
+        Ok(data)
+    }
+
+    }
+}
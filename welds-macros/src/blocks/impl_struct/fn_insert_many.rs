@@ -0,0 +1,28 @@
+use crate::info::Info;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn write(info: &Info) -> TokenStream {
+    // If this is a readonly model it can't be inserted
+    if info.readonly {
+        return quote!();
+    }
+    let wp = &info.welds_path;
+
+    quote! {
+
+        /// Inserts all the rows in one transaction, with as few statements as the database allows.
+        /// The rows are returned with the keys and defaults the database filled in
+        pub async fn insert_many<C>(
+            client: &C,
+            rows: Vec<Self>,
+        ) -> #wp::errors::Result<Vec<#wp::state::DbState<Self>>>
+        where
+            C: #wp::TransactStart + ?Sized,
+            <Self as #wp::model_traits::HasSchema>::Schema: #wp::model_traits::TableInfo + #wp::model_traits::TableColumns,
+        {
+            #wp::query::insert::insert_many(client, rows).await
+        }
+
+    }
+}
@@ -0,0 +1,31 @@
+use crate::info::Info;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn write(info: &Info) -> TokenStream {
+    let deleted_at = match &info.soft_delete {
+        Some(col) => &col.field,
+        None => return quote!(),
+    };
+    if info.readonly {
+        return quote!();
+    }
+    let wp = &info.welds_path;
+    let pks: Vec<_> = info.pks.iter().map(|c| &c.field).collect();
+
+    quote! {
+
+        /// Brings back a soft deleted row, clearing the time it was deleted
+        pub async fn restore(&mut self, client: &dyn #wp::Client) -> #wp::errors::Result<()> {
+            Self::all()
+                .with_deleted()
+                #( .where_col(|x| x.#pks.equal(self.#pks.clone())) )*
+                .set_null(|x| x.#deleted_at)
+                .run(client)
+                .await?;
+            self.#deleted_at = None;
+            Ok(())
+        }
+
+    }
+}
@@ -3,9 +3,15 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 pub(crate) mod fn_all;
+pub(crate) mod fn_belongs_to_many;
+pub(crate) mod fn_column_stats;
+pub(crate) mod fn_descendants_of;
 pub(crate) mod fn_find_by_id;
+pub(crate) mod fn_find_by_sql;
 pub(crate) mod fn_from_raw_sql;
+pub(crate) mod fn_insert_many;
 pub(crate) mod fn_new;
+pub(crate) mod fn_restore;
 pub(crate) mod fn_select;
 pub(crate) mod fn_where_col;
 
@@ -18,6 +24,12 @@ pub(crate) fn write(infos: &Info) -> TokenStream {
     let p4 = fn_find_by_id::write(infos);
     let p5 = fn_from_raw_sql::write(infos);
     let p6 = fn_select::write(infos);
+    let p7 = fn_column_stats::write(infos);
+    let p8 = fn_descendants_of::write(infos);
+    let p9 = fn_insert_many::write(infos);
+    let p10 = fn_restore::write(infos);
+    let p11 = fn_find_by_sql::write(infos);
+    let p12 = fn_belongs_to_many::write(infos);
 
     quote! {
 
@@ -28,6 +40,12 @@ pub(crate) fn write(infos: &Info) -> TokenStream {
             #p4
             #p5
             #p6
+            #p7
+            #p8
+            #p9
+            #p10
+            #p11
+            #p12
         }
 
     }
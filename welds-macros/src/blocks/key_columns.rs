@@ -0,0 +1,122 @@
+use crate::column::Column;
+use crate::info::Info;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn write(info: &Info) -> TokenStream {
+    let wp = &info.welds_path;
+    let def = &info.defstruct;
+
+    // the primary keys, and the columns of this model the relations link by
+    let mut keys: Vec<&Column> = info.pks.iter().collect();
+    for relation in &info.relations {
+        let mut names: Vec<&String> = relation.shared_keys.iter().collect();
+        if relation.kind == "BelongsTo" {
+            names.push(&relation.foreign_key);
+        }
+        for name in names {
+            let col = info.columns.iter().find(|c| &c.dbname == name && !c.ignore);
+            if let Some(col) = col {
+                if !keys.iter().any(|k| k.dbname == col.dbname) {
+                    keys.push(col);
+                }
+            }
+        }
+    }
+    let reads: Vec<_> = keys.iter().map(|col| read(info, col)).collect();
+    let sets: Vec<_> = info.columns.iter().filter(|c| !c.ignore).map(set).collect();
+
+    quote! {
+
+    impl #wp::model_traits::KeyColumns for #def {
+        fn key_value(&self, column: &str) -> Option<#wp::model_traits::KeyValue> {
+            match column {
+                #(#reads)*
+                _ => None,
+            }
+        }
+
+        fn set_key(
+            &mut self,
+            column: &str,
+            value: #wp::model_traits::KeyValue,
+        ) -> #wp::errors::Result<()> {
+            let invalid = || #wp::errors::WeldsError::InvalidMapping(column.to_owned());
+            match column {
+                #(#sets)*
+                _ => {
+                    return Err(#wp::errors::WeldsError::MissingDbColumn(
+                        column.to_owned(),
+                    ).into())
+                }
+            }
+            Ok(())
+        }
+    }
+
+    }
+}
+
+fn read(info: &Info, col: &Column) -> TokenStream {
+    let wp = &info.welds_path;
+    let dbname = col.dbname.as_str();
+    let field = &col.field;
+    quote! { #dbname => Some(#wp::model_traits::KeyValue::new(self.#field.clone())), }
+}
+
+fn set(col: &Column) -> TokenStream {
+    let dbname = col.dbname.as_str();
+    let field = &col.field;
+    let ft = &col.field_type;
+    if col.is_option {
+        return quote! { #dbname => self.#field = value.into_option::<#ft>().ok_or_else(invalid)?, };
+    }
+    quote! { #dbname => self.#field = value.into_value::<#ft>().ok_or_else(invalid)?, }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_read_the_keys_and_set_any_column() {
+        let info = Info::mock()
+            .add_pk("id", "i64")
+            .add_column("team_id", "i32", true);
+        let ts = write(&info);
+        let code = ts.to_string();
+        let expected: &str = r#"
+            impl welds::model_traits::KeyColumns for Mock {
+                fn key_value(&self, column: &str) -> Option<welds::model_traits::KeyValue> {
+                    match column {
+                        "id" => Some(welds::model_traits::KeyValue::new(self.id.clone())),
+                        _ => None,
+                    }
+                }
+
+                fn set_key(
+                    &mut self,
+                    column: &str,
+                    value: welds::model_traits::KeyValue,
+                ) -> welds::errors::Result<()> {
+                    let invalid = || welds::errors::WeldsError::InvalidMapping(column.to_owned());
+                    match column {
+                        "id" => self.id = value.into_value::<i64>().ok_or_else(invalid)?,
+                        "team_id" => self.team_id = value.into_option::<i32>().ok_or_else(invalid)?,
+                        _ => {
+                            return Err(welds::errors::WeldsError::MissingDbColumn(
+                                column.to_owned(),
+                            ).into())
+                        }
+                    }
+                    Ok(())
+                }
+            }
+        "#;
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
+    fn cleaned(input: &str) -> String {
+        input.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+}
@@ -0,0 +1,124 @@
+use crate::info::Info;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn write(info: &Info) -> TokenStream {
+    let parts: Vec<_> = info
+        .mapping
+        .targets
+        .iter()
+        .map(|target| write_for_target(info, target))
+        .collect();
+    quote! { #(#parts)* }
+}
+
+fn write_for_target(info: &Info, target: &syn::Path) -> TokenStream {
+    let wp = &info.welds_path;
+    let defstruct = &info.defstruct;
+    let fields = &info.mapping.fields;
+
+    let to_other: Vec<_> = fields
+        .iter()
+        .filter(|f| !f.skip)
+        .map(|f| {
+            let field = &f.field;
+            let other = &f.other;
+            quote! { #other: model.#field.into(), }
+        })
+        .collect();
+
+    let from_other: Vec<_> = fields
+        .iter()
+        .map(|f| {
+            let field = &f.field;
+            let other = &f.other;
+            if f.skip {
+                return quote! { #field: Default::default(), };
+            }
+            let name = field.to_string();
+            quote! {
+                #field: other.#other.try_into().map_err(|_| #wp::errors::WeldsError::InvalidMapping(#name.to_owned()))?,
+            }
+        })
+        .collect();
+
+    quote! {
+        impl From<#defstruct> for #target {
+            #[allow(clippy::needless_update, clippy::useless_conversion)]
+            fn from(model: #defstruct) -> Self {
+                Self {
+                    #(#to_other)*
+                    ..Default::default()
+                }
+            }
+        }
+
+        impl TryFrom<#target> for #defstruct {
+            type Error = #wp::errors::WeldsError;
+            #[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+            fn try_from(other: #target) -> std::result::Result<Self, Self::Error> {
+                Ok(Self {
+                    #(#from_other)*
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapping::MappedField;
+    use proc_macro2::{Ident, Span};
+
+    #[test]
+    fn should_write_nothing_without_a_mapping() {
+        let info = Info::mock().add_pk("id", "i64");
+        assert!(write(&info).is_empty());
+    }
+
+    #[test]
+    fn should_map_fields_to_and_from_the_other_struct() {
+        let mut info = Info::mock();
+        info.mapping.targets = vec![syn::parse_str("pb::Mock").unwrap()];
+        info.mapping.fields = vec![
+            MappedField {
+                field: Ident::new("id", Span::call_site()),
+                other: Ident::new("mock_id", Span::call_site()),
+                skip: false,
+            },
+            MappedField {
+                field: Ident::new("cache", Span::call_site()),
+                other: Ident::new("cache", Span::call_site()),
+                skip: true,
+            },
+        ];
+        let code = write(&info).to_string();
+        let expected: &str = r#"
+            impl From<Mock> for pb::Mock {
+                #[allow(clippy::needless_update, clippy::useless_conversion)]
+                fn from(model: Mock) -> Self {
+                    Self {
+                        mock_id: model.id.into(),
+                        ..Default::default()
+                    }
+                }
+            }
+            impl TryFrom<pb::Mock> for Mock {
+                type Error = welds::errors::WeldsError;
+                #[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+                fn try_from(other: pb::Mock) -> std::result::Result<Self, Self::Error> {
+                    Ok(Self {
+                        id: other.mock_id.try_into().map_err(|_| welds::errors::WeldsError::InvalidMapping("id".to_owned()))?,
+                        cache: Default::default(),
+                    })
+                }
+            }
+        "#;
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
+    fn cleaned(input: &str) -> String {
+        input.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+}
@@ -1,7 +1,12 @@
 mod define_schema;
+mod graphql;
 mod has_schema;
 mod impl_struct;
+mod key_columns;
+mod mapping;
+mod partial_of;
 mod relations;
+mod scopes;
 mod table_columns;
 mod table_info;
 mod unique_identifier;
@@ -13,9 +18,14 @@ mod write_hooks;
 mod write_to_args;
 
 pub(crate) use define_schema::write as define_schema;
+pub(crate) use graphql::write as graphql;
 pub(crate) use has_schema::write as has_schema;
 pub(crate) use impl_struct::write as impl_struct;
+pub(crate) use key_columns::write as key_columns;
+pub(crate) use mapping::write as mapping;
+pub(crate) use partial_of::write as partial_of;
 pub(crate) use relations::write as relations;
+pub(crate) use scopes::write as scopes;
 pub(crate) use table_columns::write as table_columns;
 pub(crate) use table_info::write as table_info;
 pub(crate) use unique_identifier::write as unique_identifier;
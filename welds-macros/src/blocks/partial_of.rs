@@ -0,0 +1,55 @@
+use crate::info::Info;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn write(info: &Info) -> TokenStream {
+    let model = match &info.partial_of {
+        Some(model) => model,
+        None => return quote! {},
+    };
+    let wp = &info.welds_path;
+    let defstruct = &info.defstruct;
+
+    // every field of the partial has to be a field of the model, checked at compile time
+    let checks: Vec<_> = info
+        .columns
+        .iter()
+        .filter(|c| !c.ignore)
+        .map(|c| {
+            let field = &c.field;
+            quote! { let _ = &schema.#field; }
+        })
+        .collect();
+
+    quote! {
+        impl #wp::model_traits::PartialOf<#model> for #defstruct {}
+
+        const _: fn() = || {
+            let schema = <<#model as #wp::model_traits::HasSchema>::Schema as Default>::default();
+            #(#checks)*
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_write_nothing_for_a_full_model() {
+        let info = Info::mock().add_pk("id", "i64");
+        assert!(write(&info).is_empty());
+    }
+
+    #[test]
+    fn should_check_the_fields_against_the_model() {
+        let mut info = Info::mock()
+            .add_pk("id", "i64")
+            .add_column("name", "String", false);
+        info.partial_of = Some(syn::parse_str("User").unwrap());
+        let code = write(&info).to_string();
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        assert!(code.contains("implwelds::model_traits::PartialOf<User>forMock{}"));
+        assert!(code.contains("let_=&schema.id;let_=&schema.name;"));
+    }
+}
@@ -15,6 +15,12 @@ pub(crate) fn write(info: &Info) -> TokenStream {
     let struct_fields = quote! { #(#struct_fields), * };
     let default_fields: Vec<_> = relations.iter().map(|x| defaultdef(info, x)).collect();
     let default_fields = quote! { #(#default_fields), * };
+    let foreign_keys: Vec<_> = relations
+        .iter()
+        .filter(|x| x.kind == "BelongsTo")
+        .map(|x| foreignkeydef(info, x))
+        .collect();
+    let foreign_keys = quote! { #(#foreign_keys), * };
 
     quote! {
 
@@ -34,6 +40,12 @@ pub(crate) fn write(info: &Info) -> TokenStream {
             }
         }
 
+        impl #wp::relations::ForeignKeys for #defstruct {
+            fn foreign_keys() -> Vec<#wp::relations::ForeignKey> {
+                vec![ #foreign_keys ]
+            }
+        }
+
     }
 }
 
@@ -52,7 +64,28 @@ fn defaultdef(info: &Info, relation: &Relation) -> TokenStream {
     let kind = &relation.kind;
     let field = &relation.field;
     let fk = &relation.foreign_key;
+    if let Some(through) = &relation.through {
+        let rk = &relation.related_key;
+        return quote! {
+            #field: #wp::relations::#kind::using(#through, #fk, #rk)
+        };
+    }
+    if !relation.shared_keys.is_empty() {
+        let shared = &relation.shared_keys;
+        return quote! {
+            #field: #wp::relations::#kind::using(#fk).sharing(&[ #(#shared),* ])
+        };
+    }
     quote! {
         #field: #wp::relations::#kind::using(#fk)
     }
 }
+
+fn foreignkeydef(info: &Info, relation: &Relation) -> TokenStream {
+    let wp = &info.welds_path;
+    let other = &relation.foreign_struct;
+    let fk = &relation.foreign_key;
+    quote! {
+        #wp::relations::ForeignKey::new::<<#other as #wp::model_traits::HasSchema>::Schema>(#fk)
+    }
+}
@@ -0,0 +1,72 @@
+use crate::info::Info;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+pub(crate) fn write(info: &Info) -> TokenStream {
+    if info.scopes.is_empty() {
+        return quote! {};
+    }
+    let wp = &info.welds_path;
+    let def = &info.defstruct;
+    let scopes_struct = Ident::new(&format!("{}Scopes", def), def.span());
+    let scopes_trait = Ident::new(&format!("{}ScopesExt", def), def.span());
+    let scopes = &info.scopes;
+
+    quote! {
+
+    pub struct #scopes_struct;
+
+    pub trait #scopes_trait {
+        #( fn #scopes(self) -> Self; )*
+    }
+
+    impl #scopes_trait for #wp::query::builder::QueryBuilder<#def> {
+        #( fn #scopes(self) -> Self { #scopes_struct::#scopes(self) } )*
+    }
+
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::Span;
+
+    #[test]
+    fn should_write_nothing_without_scopes() {
+        let info = Info::mock().add_pk("id", "i64");
+        let ts = write(&info);
+        assert!(ts.is_empty());
+    }
+
+    #[test]
+    fn should_add_the_scopes_to_the_query_builder() {
+        let mut info = Info::mock().add_pk("id", "i64");
+        info.scopes = vec![
+            Ident::new("active", Span::call_site()),
+            Ident::new("admins", Span::call_site()),
+        ];
+        let ts = write(&info);
+        let code = ts.to_string();
+
+        let expected: &str = r#"
+            pub struct MockScopes;
+
+            pub trait MockScopesExt {
+                fn active(self) -> Self;
+                fn admins(self) -> Self;
+            }
+
+            impl MockScopesExt for welds::query::builder::QueryBuilder<Mock> {
+                fn active(self) -> Self { MockScopes::active(self) }
+                fn admins(self) -> Self { MockScopes::admins(self) }
+            }
+        "#;
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
+    fn cleaned(input: &str) -> String {
+        input.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+}
@@ -5,7 +5,8 @@ use quote::quote;
 pub(crate) fn write(info: &Info) -> TokenStream {
     let columns = write_cols(info);
     let pks = write_pks(info);
-    write_for_db(info, &pks, &columns)
+    let fields = write_fields(info);
+    write_for_db(info, &pks, &columns, &fields)
 }
 
 pub(crate) fn write_cols(info: &Info) -> TokenStream {
@@ -45,10 +46,39 @@ pub(crate) fn write_pks(info: &Info) -> TokenStream {
     quote! { vec![ #(#parts),* ] }
 }
 
-pub(crate) fn write_for_db(info: &Info, pks: &TokenStream, columns: &TokenStream) -> TokenStream {
+pub(crate) fn write_fields(info: &Info) -> TokenStream {
+    let parts: Vec<_> = info
+        .columns
+        .iter()
+        .filter(|x| !x.ignore)
+        .map(|c| {
+            let field = c.field.to_string();
+            let field = field.trim_start_matches("r#");
+            let dbname = c.dbname.as_str();
+            quote! { #field => #dbname, }
+        })
+        .collect();
+    quote! { #(#parts)* }
+}
+
+pub(crate) fn write_for_db(
+    info: &Info,
+    pks: &TokenStream,
+    columns: &TokenStream,
+    fields: &TokenStream,
+) -> TokenStream {
     let wp = &info.welds_path;
     let def = &info.schemastruct;
 
+    let version = info.version.as_ref().map(|col| {
+        let dbname = &col.dbname;
+        quote! {
+            fn version_column() -> Option<&'static str> {
+                Some(#dbname)
+            }
+        }
+    });
+
     quote! {
 
         impl #wp::model_traits::TableColumns for #def {
@@ -60,6 +90,14 @@ pub(crate) fn write_for_db(info: &Info, pks: &TokenStream, columns: &TokenStream
                 use #wp::model_traits::Column;
                 #columns
             }
+            fn field_column(field: &str) -> Option<String> {
+                let col = match field {
+                    #fields
+                    _ => return None,
+                };
+                Some(col.to_owned())
+            }
+            #version
         }
 
     }
@@ -87,6 +125,14 @@ mod tests {
                     use welds::model_traits::Column;
                     vec![Column::new("id", "i64", false), Column::new("num", "f32",  true)]
                 }
+                fn field_column(field: &str) -> Option<String> {
+                    let col = match field {
+                        "id" => "id",
+                        "num" => "num",
+                        _ => return None,
+                    };
+                    Some(col.to_owned())
+                }
             }
         "#;
 
@@ -94,6 +140,21 @@ mod tests {
         assert_eq!(cleaned(&code), cleaned(expected));
     }
 
+    #[test]
+    fn should_write_the_version_column() {
+        let mut info = Info::mock()
+            .add_pk("id", "i64")
+            .add_column("lock_version", "i32", false);
+        info.version = info.columns.last().cloned();
+        let code = write(&info).to_string();
+        let expected = r#"fn version_column() -> Option<&'static str> { Some("lock_version") }"#;
+        assert!(
+            cleaned(&code).contains(&cleaned(expected)),
+            "CODE: \n{}\n",
+            code
+        );
+    }
+
     fn cleaned(input: &str) -> String {
         input.chars().filter(|c| !c.is_whitespace()).collect()
     }
@@ -4,6 +4,25 @@ use quote::quote;
 
 pub(crate) fn write(info: &Info) -> TokenStream {
     let wp = &info.welds_path;
+    let schema = &info.schemastruct;
+
+    // a partial reads from the table of the model it is a part of
+    if let Some(model) = &info.partial_of {
+        return quote! {
+            impl #wp::model_traits::TableInfo for #schema {
+                fn identifier() -> &'static [&'static str] {
+                    <<#model as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::TableInfo>::identifier()
+                }
+                fn soft_delete_column() -> Option<&'static str> {
+                    <<#model as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::TableInfo>::soft_delete_column()
+                }
+                fn default_scope() -> Option<Box<dyn #wp::query::clause::ClauseAdder>> {
+                    <<#model as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::TableInfo>::default_scope()
+                }
+            }
+        };
+    }
+
     let mut parts = Vec::default();
     let tn = &info.tablename;
     parts.push(quote! { #tn });
@@ -13,7 +32,25 @@ pub(crate) fn write(info: &Info) -> TokenStream {
     }
 
     let parts: Vec<_> = parts.drain(..).rev().collect();
-    let schema = &info.schemastruct;
+
+    let default_scope = info.default_scope.as_ref().map(|scope| {
+        quote! {
+            fn default_scope() -> Option<Box<dyn #wp::query::clause::ClauseAdder>> {
+                Some(#scope(Default::default()))
+            }
+        }
+    });
+    let counter_caches = counter_caches(info);
+    let parent_counters = parent_counters(info);
+    let touches = touches(info);
+    let soft_delete = info.soft_delete.as_ref().map(|col| {
+        let dbname = &col.dbname;
+        quote! {
+            fn soft_delete_column() -> Option<&'static str> {
+                Some(#dbname)
+            }
+        }
+    });
 
     quote! {
 
@@ -21,11 +58,93 @@ pub(crate) fn write(info: &Info) -> TokenStream {
             fn identifier() -> &'static [&'static str] {
                 &[#(#parts),*]
             }
+            #soft_delete
+            #default_scope
+            #counter_caches
+            #parent_counters
+            #touches
         }
 
     }
 }
 
+// the counters this model keeps of the rows of its HasMany relations
+fn counter_caches(info: &Info) -> Option<TokenStream> {
+    let wp = &info.welds_path;
+    let caches: Vec<_> = info
+        .relations
+        .iter()
+        .filter_map(|relation| {
+            let counter = relation.counter_cache.as_ref()?;
+            let child = &relation.foreign_struct;
+            let fk = &relation.foreign_key;
+            Some(quote! {
+                #wp::relations::CounterCache::new::<<#child as #wp::model_traits::HasSchema>::Schema>(#fk, #counter)
+            })
+        })
+        .collect();
+    if caches.is_empty() {
+        return None;
+    }
+    Some(quote! {
+        fn counter_caches() -> Vec<#wp::relations::CounterCache> {
+            vec![ #(#caches),* ]
+        }
+    })
+}
+
+// the counters of the parents this model's BelongsTo relations point at
+fn parent_counters(info: &Info) -> Option<TokenStream> {
+    let wp = &info.welds_path;
+    let parents: Vec<_> = info
+        .relations
+        .iter()
+        .filter(|relation| relation.kind == "BelongsTo")
+        .map(|relation| {
+            let parent = &relation.foreign_struct;
+            let fk = &relation.foreign_key;
+            quote! {
+                counters.extend(#wp::relations::ParentCounter::of::<<#parent as #wp::model_traits::HasSchema>::Schema>(Self::identifier(), #fk));
+            }
+        })
+        .collect();
+    if parents.is_empty() {
+        return None;
+    }
+    Some(quote! {
+        fn parent_counters() -> Vec<#wp::relations::ParentCounter> {
+            let mut counters = Vec::new();
+            #(#parents)*
+            counters
+        }
+    })
+}
+
+// the timestamps of the parents touched by this model's BelongsTo relations
+fn touches(info: &Info) -> Option<TokenStream> {
+    let wp = &info.welds_path;
+    let touches: Vec<_> = info
+        .relations
+        .iter()
+        .filter_map(|relation| {
+            let column = relation.touch.as_ref()?;
+            let parent = &relation.foreign_struct;
+            let fk = &relation.foreign_key;
+            Some(quote! {
+                #wp::relations::Touch::new::<<#parent as #wp::model_traits::HasSchema>::Schema>(#fk, #column)
+            })
+        })
+        .collect();
+    if touches.is_empty() {
+        return None;
+    }
+    Some(quote! {
+        fn touches() -> Vec<#wp::relations::Touch> {
+            vec![ #(#touches),* ]
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,6 +165,116 @@ mod tests {
         assert_eq!(cleaned(&code), cleaned(expected), "CODE: \n\n{}\n\n", code);
     }
 
+    #[test]
+    fn should_use_the_table_of_the_full_model_for_a_partial() {
+        let mut info = Info::mock().add_pk("id", "i64");
+        info.partial_of = Some(syn::parse_str("User").unwrap());
+        let ts = write(&info);
+        let code = ts.to_string();
+
+        let expected: &str = r#"
+        impl welds::model_traits::TableInfo for MockSchema {
+            fn identifier() -> &'static [&'static str] {
+                <<User as welds::model_traits::HasSchema>::Schema as welds::model_traits::TableInfo>::identifier()
+            }
+            fn soft_delete_column() -> Option<&'static str> {
+                <<User as welds::model_traits::HasSchema>::Schema as welds::model_traits::TableInfo>::soft_delete_column()
+            }
+            fn default_scope() -> Option<Box<dyn welds::query::clause::ClauseAdder>> {
+                <<User as welds::model_traits::HasSchema>::Schema as welds::model_traits::TableInfo>::default_scope()
+            }
+        }
+        "#;
+        assert_eq!(cleaned(&code), cleaned(expected), "CODE: \n\n{}\n\n", code);
+    }
+
+    #[test]
+    fn should_write_the_soft_delete_column() {
+        let mut info =
+            Info::mock()
+                .add_pk("id", "i64")
+                .add_column("deleted_at", "Option<String>", true);
+        info.soft_delete = info.columns.last().cloned();
+        let ts = write(&info);
+        let code = ts.to_string();
+
+        let expected: &str = r#"
+        impl welds::model_traits::TableInfo for MockSchema {
+            fn identifier() -> &'static [&'static str] {
+                &[ "daschema","datables"]
+            }
+            fn soft_delete_column() -> Option<&'static str> {
+                Some("deleted_at")
+            }
+        }
+        "#;
+        assert_eq!(cleaned(&code), cleaned(expected), "CODE: \n\n{}\n\n", code);
+    }
+
+    #[test]
+    fn should_write_the_counters_and_touches_of_the_relations() {
+        use crate::relation::Relation;
+        let mut info = Info::mock().add_pk("id", "i64");
+        let ident = syn::Ident::new("Mock", proc_macro2::Span::call_site());
+        let many: syn::MetaList = syn::parse_quote!(HasMany(
+            items,
+            Item,
+            "mock_id",
+            counter_cache = "items_count"
+        ));
+        let belongs: syn::MetaList = syn::parse_quote!(BelongsTo(team, Team, "team_id", touch));
+        info.relations = vec![
+            Relation::new(&many, "HasMany", &ident).unwrap(),
+            Relation::new(&belongs, "BelongsTo", &ident).unwrap(),
+        ];
+        let ts = write(&info);
+        let code = ts.to_string();
+
+        let expected: &str = r#"
+        impl welds::model_traits::TableInfo for MockSchema {
+            fn identifier() -> &'static [&'static str] {
+                &[ "daschema","datables"]
+            }
+            fn counter_caches() -> Vec<welds::relations::CounterCache> {
+                vec![
+                    welds::relations::CounterCache::new::<<Item as welds::model_traits::HasSchema>::Schema>("mock_id", "items_count")
+                ]
+            }
+            fn parent_counters() -> Vec<welds::relations::ParentCounter> {
+                let mut counters = Vec::new();
+                counters.extend(welds::relations::ParentCounter::of::<<Team as welds::model_traits::HasSchema>::Schema>(Self::identifier(), "team_id"));
+                counters
+            }
+            fn touches() -> Vec<welds::relations::Touch> {
+                vec![
+                    welds::relations::Touch::new::<<Team as welds::model_traits::HasSchema>::Schema>("team_id", "updated_at")
+                ]
+            }
+        }
+        "#;
+        assert_eq!(cleaned(&code), cleaned(expected), "CODE: \n\n{}\n\n", code);
+    }
+
+    #[test]
+    fn should_write_the_default_scope() {
+        let mut info = Info::mock().add_pk("id", "i64");
+        info.default_scope = Some(syn::parse_quote!(scopes::tenant));
+        let ts = write(&info);
+        let code = ts.to_string();
+
+        let expected: &str = r#"
+        impl welds::model_traits::TableInfo for MockSchema {
+            fn identifier() -> &'static [&'static str] {
+                &[ "daschema","datables"]
+            }
+            fn default_scope() -> Option<Box<dyn welds::query::clause::ClauseAdder>> {
+                Some(scopes::tenant(Default::default()))
+            }
+        }
+        "#;
+        assert_eq!(cleaned(&code), cleaned(expected), "CODE: \n\n{}\n\n", code);
+    }
+
     fn cleaned(input: &str) -> String {
         input.chars().filter(|c| !c.is_whitespace()).collect()
     }
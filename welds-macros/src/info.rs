@@ -2,6 +2,7 @@ use crate::attributes;
 use crate::column::Column;
 use crate::errors::Result;
 use crate::hook::Hook;
+use crate::mapping::Mapping;
 use crate::relation::Relation;
 use syn::Ident;
 
@@ -16,6 +17,14 @@ pub(crate) struct Info {
     pub tablename: String,
     pub schemaname: Option<String>,
     pub readonly: bool,
+    pub partial_of: Option<syn::Path>,
+    pub tree: Option<Column>,
+    pub soft_delete: Option<Column>,
+    pub version: Option<Column>,
+    pub default_scope: Option<syn::Path>,
+    pub graphql: bool,
+    pub scopes: Vec<Ident>,
+    pub mapping: Mapping,
     pub welds_path: syn::Path,
 }
 
@@ -32,7 +41,16 @@ impl Info {
         let schemaname = attributes::get_schemaname(ast);
         let columns = attributes::get_columns(ast);
         let pks = attributes::get_pks(ast);
-        let readonly = attributes::get_readonly(ast);
+        let partial_of = attributes::get_partial_of(ast);
+        // a partial can't be saved, it would write over the columns it left out
+        let readonly = attributes::get_readonly(ast) || partial_of.is_some();
+        let tree = get_tree(ast, &columns, &pks)?;
+        let soft_delete = get_soft_delete(ast, &columns, &pks)?;
+        let version = get_version(ast, &columns, &pks)?;
+        let default_scope = attributes::get_default_scope(ast);
+        let graphql = attributes::get_graphql(ast);
+        let scopes = attributes::get_scopes(ast);
+        let mapping = attributes::get_mapping(ast);
         let welds_path = attributes::get_welds_path(ast);
 
         Ok(Self {
@@ -46,11 +64,86 @@ impl Info {
             tablename,
             schemaname,
             readonly,
+            partial_of,
+            tree,
+            soft_delete,
+            version,
+            default_scope,
+            graphql,
+            scopes,
+            mapping,
             welds_path,
         })
     }
 }
 
+// The column pointing at the parent row of a self-referential model
+fn get_tree(ast: &syn::DeriveInput, columns: &[Column], pks: &[Column]) -> Result<Option<Column>> {
+    let field = match attributes::get_tree(ast) {
+        Some(field) => field,
+        None => return Ok(None),
+    };
+    if pks.len() != 1 {
+        return Err("welds(tree) requires a model with a single primary key".to_owned());
+    }
+    let parent = columns.iter().find(|c| c.field == field);
+    match parent {
+        Some(col) => Ok(Some(col.clone())),
+        None => Err(format!("welds(tree): no field named `{}`", field)),
+    }
+}
+
+// The nullable timestamp column set when a row is deleted
+fn get_soft_delete(
+    ast: &syn::DeriveInput,
+    columns: &[Column],
+    pks: &[Column],
+) -> Result<Option<Column>> {
+    let field = match attributes::get_soft_delete(ast) {
+        Some(field) => field,
+        None => return Ok(None),
+    };
+    if pks.is_empty() {
+        return Err("welds(soft_delete) requires a model with a primary key".to_owned());
+    }
+    let col = match columns.iter().find(|c| c.field == field) {
+        Some(col) => col,
+        None => return Err(format!("welds(soft_delete): no field named `{}`", field)),
+    };
+    if !col.is_option {
+        return Err(format!(
+            "welds(soft_delete): the field `{}` must be an Option",
+            field
+        ));
+    }
+    Ok(Some(col.clone()))
+}
+
+// The column counting the updates of a row, checked so an update can't write over a newer one
+fn get_version(
+    ast: &syn::DeriveInput,
+    columns: &[Column],
+    pks: &[Column],
+) -> Result<Option<Column>> {
+    let fields = attributes::get_version_fields(ast);
+    let field = match fields.as_slice() {
+        [] => return Ok(None),
+        [field] => field,
+        _ => return Err("welds(version) can only be on one field".to_owned()),
+    };
+    let col = match columns.iter().find(|c| c.field == *field) {
+        Some(col) => col,
+        None => return Err(format!("welds(version): no field named `{}`", field)),
+    };
+    if col.is_option || col.ignore || pks.iter().any(|pk| pk.field == *field) {
+        return Err(format!(
+            "welds(version): `{}` must be a non-optional column that isn't the primary key",
+            field
+        ));
+    }
+    Ok(Some(col.clone()))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -72,6 +165,14 @@ mod tests {
                 tablename: "datables".to_string(),
                 schemaname: Some("daschema".to_string()),
                 readonly: false,
+                partial_of: None,
+                tree: None,
+                soft_delete: None,
+                version: None,
+                default_scope: None,
+                graphql: false,
+                scopes: Vec::default(),
+                mapping: Default::default(),
                 welds_path: Ident::new("welds", Span::call_site()).into(),
             }
         }
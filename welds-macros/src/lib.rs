@@ -7,6 +7,7 @@ pub(crate) mod column;
 pub(crate) mod errors;
 pub(crate) mod hook;
 pub(crate) mod info;
+pub(crate) mod mapping;
 pub(crate) mod relation;
 pub(crate) mod utils;
 
@@ -20,6 +21,23 @@ pub fn model_gen(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Reads query results into a plain struct, each field from the column selected with its name.
+/// For projections that aren't a model, such as a join with aggregates.
+#[proc_macro_derive(WeldsProjection, attributes(welds, welds_path))]
+pub fn projection_gen(input: TokenStream) -> TokenStream {
+    match projection_gen_inner(input) {
+        Ok(q) => q,
+        Err(err) => quote! { std::compile_error!(#err); }.into(),
+    }
+}
+
+fn projection_gen_inner(input: TokenStream) -> errors::Result<TokenStream> {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let info = Info::new(&ast)?;
+    let q = blocks::try_from_row(&info);
+    Ok(q.into())
+}
+
 fn model_gen_inner(input: TokenStream) -> errors::Result<TokenStream> {
     // Gather the Info needed to build all the code snipits
     let ast: syn::DeriveInput = syn::parse(input).unwrap();
@@ -39,6 +57,11 @@ fn model_gen_inner(input: TokenStream) -> errors::Result<TokenStream> {
     let p11 = blocks::update_from_row(&info);
     let p12 = blocks::write_col_default_check(&info);
     let p13 = blocks::write_hooks(&info);
+    let p14 = blocks::graphql(&info);
+    let p15 = blocks::mapping(&info);
+    let p16 = blocks::partial_of(&info);
+    let p17 = blocks::key_columns(&info);
+    let p18 = blocks::scopes(&info);
 
     let q = quote! {
         #p1
@@ -54,6 +77,11 @@ fn model_gen_inner(input: TokenStream) -> errors::Result<TokenStream> {
         #p11
         #p12
         #p13
+        #p14
+        #p15
+        #p16
+        #p17
+        #p18
     };
 
     //  // Want to see what the macros generate?
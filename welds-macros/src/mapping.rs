@@ -0,0 +1,18 @@
+use syn::Ident;
+
+/// Other structs (protobuf messages, avro records, ...) the model converts to and from.
+/// `#[welds(mapping(pb::Product))]`
+#[derive(Default)]
+pub(crate) struct Mapping {
+    pub(crate) targets: Vec<syn::Path>,
+    pub(crate) fields: Vec<MappedField>,
+}
+
+pub(crate) struct MappedField {
+    /// the field on the model
+    pub(crate) field: Ident,
+    /// the field on the other struct. set with `#[welds(mapping_field = "name")]`
+    pub(crate) other: Ident,
+    /// the field is not on the other struct. set with `#[welds(mapping_skip)]`
+    pub(crate) skip: bool,
+}
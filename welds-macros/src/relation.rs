@@ -8,20 +8,27 @@ pub(crate) struct Relation {
     pub(crate) field: Ident,
     pub(crate) foreign_struct: syn::Path,
     pub(crate) foreign_key: String,
+    // the columns before the foreign key, for a key over several columns
+    pub(crate) shared_keys: Vec<String>,
+    // the join table and its column pointing at the foreign struct, for a BelongsToMany
+    pub(crate) through: Option<String>,
+    pub(crate) related_key: String,
+    // the column of the parent counting the rows of a HasMany
+    pub(crate) counter_cache: Option<String>,
+    // the timestamp of the parent set when a row of a BelongsTo is saved
+    pub(crate) touch: Option<String>,
 }
 
 impl Relation {
-    pub(crate) fn new(list: &MetaList, kind: &'static str) -> Result<Self> {
-        let badformat = || {
-            if kind == "BelongsTo" {
-                Err(FORMAT_ERR_BELONGS_TO.to_owned())
-            } else {
-                Err(FORMAT_ERR_HAS_MANY.to_owned())
-            }
+    pub(crate) fn new(list: &MetaList, kind: &'static str, structname: &Ident) -> Result<Self> {
+        let badformat = || match kind {
+            "BelongsTo" => Err(FORMAT_ERR_BELONGS_TO.to_owned()),
+            "HasOne" => Err(FORMAT_ERR_HAS_ONE.to_owned()),
+            _ => Err(FORMAT_ERR_HAS_MANY.to_owned()),
         };
 
         let inner: Vec<_> = list.nested.iter().collect();
-        if inner.len() != 3 {
+        if inner.len() < 3 {
             return badformat();
         }
 
@@ -43,35 +50,168 @@ impl Relation {
             _ => return badformat(),
         };
         let model = match model {
-            syn::Meta::Path(path) => path,
+            syn::Meta::Path(path) => resolve_self(path, structname),
             _ => return badformat(),
         };
 
-        let foreign_key = match inner[2] {
-            syn::NestedMeta::Lit(lit) => lit,
-            _ => return badformat(),
-        };
-        let foreign_key = match foreign_key {
-            syn::Lit::Str(s) => s,
-            _ => return badformat(),
+        // `"tenant_id", "order_id"`: the last column is the foreign key,
+        // the ones before it have the same name in both tables.
+        // A HasMany can end with `counter_cache = "items_count"`,
+        // a BelongsTo with `touch` or `touch = "modified_at"`
+        let mut shared_keys = Vec::default();
+        let mut counter_cache = None;
+        let mut touch = None;
+        for nested in &inner[2..] {
+            let options = counter_cache.is_some() || touch.is_some();
+            match nested {
+                syn::NestedMeta::Lit(syn::Lit::Str(s)) if !options => shared_keys.push(s.value()),
+                syn::NestedMeta::Meta(syn::Meta::Path(path))
+                    if kind == "BelongsTo" && !options && path.is_ident("touch") =>
+                {
+                    touch = Some("updated_at".to_owned())
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(nv))
+                    if kind == "BelongsTo" && !options && nv.path.is_ident("touch") =>
+                {
+                    match &nv.lit {
+                        syn::Lit::Str(s) => touch = Some(s.value()),
+                        _ => return badformat(),
+                    }
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(nv))
+                    if kind == "HasMany" && !options && nv.path.is_ident("counter_cache") =>
+                {
+                    match &nv.lit {
+                        syn::Lit::Str(s) => counter_cache = Some(s.value()),
+                        _ => return badformat(),
+                    }
+                }
+                _ => return badformat(),
+            }
+        }
+        let foreign_key = match shared_keys.pop() {
+            Some(foreign_key) => foreign_key,
+            None => return badformat(),
         };
-        let foreign_key = foreign_key.value();
 
         let kind = Ident::new(kind, field.span());
 
         Ok(Self {
             kind,
             field,
-            foreign_struct: model.clone(),
+            foreign_struct: model,
+            foreign_key,
+            shared_keys,
+            through: None,
+            related_key: String::default(),
+            counter_cache,
+            touch,
+        })
+    }
+
+    /// A `BelongsToMany(field, struct, through = "table")`. The join table's columns default to
+    /// the lowercased struct names with `_id`, they can be set with `foreign_key = ".."` and
+    /// `related_key = ".."`
+    pub(crate) fn many(list: &MetaList, structname: &Ident) -> Result<Self> {
+        let badformat = || Err(FORMAT_ERR_BELONGS_TO_MANY.to_owned());
+        let inner: Vec<_> = list.nested.iter().collect();
+        if inner.len() < 3 {
+            return badformat();
+        }
+
+        let path = |nested: &syn::NestedMeta| match nested {
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) => Some(path.clone()),
+            _ => None,
+        };
+        let field = match path(inner[0]) {
+            Some(field) if field.segments.len() == 1 => field.segments[0].ident.clone(),
+            _ => return badformat(),
+        };
+        let model = match path(inner[1]) {
+            Some(model) => resolve_self(&model, structname),
+            None => return badformat(),
+        };
+
+        let mut through = None;
+        let model_name = model.segments.last().unwrap().ident.to_string();
+        let mut foreign_key = format!("{}_id", structname.to_string().to_lowercase());
+        let mut related_key = format!("{}_id", model_name.to_lowercase());
+        for nested in &inner[2..] {
+            let nv = match nested {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) => nv,
+                _ => return badformat(),
+            };
+            let value = match &nv.lit {
+                syn::Lit::Str(s) => s.value(),
+                _ => return badformat(),
+            };
+            match nv.path.get_ident().map(|i| i.to_string()).as_deref() {
+                Some("through") => through = Some(value),
+                Some("foreign_key") => foreign_key = value,
+                Some("related_key") => related_key = value,
+                _ => return badformat(),
+            }
+        }
+        if through.is_none() {
+            return badformat();
+        }
+        // a model related to itself would default to the same column twice
+        if foreign_key == related_key {
+            return Err(FORMAT_ERR_BELONGS_TO_MANY_SELF.to_owned());
+        }
+
+        Ok(Self {
+            kind: Ident::new("BelongsToMany", field.span()),
+            field,
+            foreign_struct: model,
             foreign_key,
+            shared_keys: Vec::default(),
+            through,
+            related_key,
+            counter_cache: None,
+            touch: None,
         })
     }
 }
 
+// The relations are written on a struct of their own, `Self` there isn't the model.
+// A relation back to the same model (a parent or children) is pointed at it by name
+fn resolve_self(path: &syn::Path, structname: &Ident) -> syn::Path {
+    match path.is_ident("Self") {
+        true => structname.clone().into(),
+        false => path.clone(),
+    }
+}
+
 const FORMAT_ERR_HAS_MANY: &str = "Invalid Format For HasMany:
 HasMany should be in for format of
-[ welds(HasMany(field, struct, foreign_key_str) )]";
+[ welds(HasMany(field, struct, foreign_key_str) )]
+or for a key over several columns, the ones with the same name in both tables first
+[ welds(HasMany(field, struct, \"tenant_id\", foreign_key_str) )]
+optionally with counter_cache = \"..\" for a column of this struct counting the rows
+[ welds(HasMany(field, struct, foreign_key_str, counter_cache = \"items_count\") )]";
+
+const FORMAT_ERR_HAS_ONE: &str = "Invalid Format For HasOne:
+HasOne should be in for format of
+[ welds(HasOne(field, struct, foreign_key_str) )]
+or for a key over several columns, the ones with the same name in both tables first
+[ welds(HasOne(field, struct, \"tenant_id\", foreign_key_str) )]";
+
+const FORMAT_ERR_BELONGS_TO_MANY: &str = "Invalid Format For BelongsToMany:
+BelongsToMany should be in for format of
+[ welds(BelongsToMany(field, struct, through = \"join_table\") )]
+optionally with foreign_key = \"..\" and related_key = \"..\" for the columns of the join table";
+
+const FORMAT_ERR_BELONGS_TO_MANY_SELF: &str = "Invalid Format For BelongsToMany:
+the foreign_key and related_key are the same column of the join table.
+A model related to itself needs both of them set, e.g.
+[ welds(BelongsToMany(followers, Self, through = \"follows\", foreign_key = \"followed_id\", related_key = \"follower_id\") )]";
 
 const FORMAT_ERR_BELONGS_TO: &str = "Invalid Format For BelongsTo:
 BelongsTo should be in for format of
-[ welds(BelongsTo(field, struct, foreign_key_str) )]";
+[ welds(BelongsTo(field, struct, foreign_key_str) )]
+or for a key over several columns, the ones with the same name in both tables first
+[ welds(BelongsTo(field, struct, \"tenant_id\", foreign_key_str) )]
+optionally with touch, to set the parent's updated_at when this struct is saved
+[ welds(BelongsTo(field, struct, foreign_key_str, touch) )]
+or touch = \"..\" for another timestamp column of the parent";
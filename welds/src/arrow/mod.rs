@@ -0,0 +1,153 @@
+use crate::errors::Result;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::ParamArgs;
+use crate::writers::in_namespace;
+use crate::Row;
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array,
+    Int64Array, RecordBatch, StringArray,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use std::sync::Arc;
+use welds_connections::Client;
+
+pub use arrow_array;
+pub use arrow_schema;
+
+/// Returns the Arrow schema of a model. There is a field for each column in the database.
+///
+/// Only columns of simple types (ints, floats, bool, String, and `Vec<u8>`) are supported
+pub fn schema<T>() -> Result<Schema>
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableColumns,
+{
+    let fields: Result<Vec<Field>> = <T::Schema as TableColumns>::columns()
+        .iter()
+        .map(|col| {
+            let data_type = data_type(col.rust_type()).ok_or_else(|| {
+                ArrowError::SchemaError(format!(
+                    "column {} has type {} which can not be written to arrow",
+                    col.name(),
+                    col.rust_type()
+                ))
+            })?;
+            Ok(Field::new(col.name(), data_type, col.nullable()))
+        })
+        .collect();
+    Ok(Schema::new(fields?))
+}
+
+/// Builds a RecordBatch out of rows from the database, one column at a time.
+/// The rows must have a column for each field in the schema
+pub fn record_batch(schema: SchemaRef, rows: &[Row]) -> Result<RecordBatch> {
+    let columns: Result<Vec<ArrayRef>> = schema
+        .fields()
+        .iter()
+        .map(|field| read_column(rows, field))
+        .collect();
+    Ok(RecordBatch::try_new(schema, columns?)?)
+}
+
+// Reads the values of one column out of all the rows
+fn read_column(rows: &[Row], field: &Field) -> Result<ArrayRef> {
+    let name = field.name().as_str();
+    let array: ArrayRef = match field.data_type() {
+        DataType::Int16 => Arc::new(collect::<i16, Int16Array>(rows, name)?),
+        DataType::Int32 => Arc::new(collect::<i32, Int32Array>(rows, name)?),
+        DataType::Int64 => Arc::new(collect::<i64, Int64Array>(rows, name)?),
+        DataType::Float32 => Arc::new(collect::<f32, Float32Array>(rows, name)?),
+        DataType::Float64 => Arc::new(collect::<f64, Float64Array>(rows, name)?),
+        DataType::Boolean => Arc::new(collect::<bool, BooleanArray>(rows, name)?),
+        DataType::Utf8 => Arc::new(collect::<String, StringArray>(rows, name)?),
+        DataType::Binary => Arc::new(collect::<Vec<u8>, BinaryArray>(rows, name)?),
+        other => {
+            let msg = format!("{} can not be read from the database", other);
+            return Err(ArrowError::NotYetImplemented(msg).into());
+        }
+    };
+    Ok(array)
+}
+
+macro_rules! collect_fn {
+    ($($t:ty),*) => {
+        /// The column types that can be read into an arrow array
+        trait ReadColumn: Sized {
+            fn read(row: &Row, name: &str) -> Result<Option<Self>>;
+        }
+        $(
+            impl ReadColumn for $t {
+                fn read(row: &Row, name: &str) -> Result<Option<Self>> {
+                    Ok(row.get::<Option<$t>>(name)?)
+                }
+            }
+        )*
+    };
+}
+
+collect_fn!(i16, i32, i64, f32, f64, bool, String, Vec<u8>);
+
+fn collect<V, A>(rows: &[Row], name: &str) -> Result<A>
+where
+    V: ReadColumn,
+    A: FromIterator<Option<V>>,
+{
+    rows.iter().map(|row| V::read(row, name)).collect()
+}
+
+// The arrow type for the rust type of a column
+fn data_type(rust_type: &str) -> Option<DataType> {
+    let rust_type: String = rust_type.chars().filter(|c| !c.is_whitespace()).collect();
+    let data_type = match rust_type.as_str() {
+        "i16" => DataType::Int16,
+        "i32" => DataType::Int32,
+        "i64" => DataType::Int64,
+        "f32" => DataType::Float32,
+        "f64" => DataType::Float64,
+        "bool" => DataType::Boolean,
+        "String" => DataType::Utf8,
+        "Vec<u8>" => DataType::Binary,
+        _ => return None,
+    };
+    Some(data_type)
+}
+
+impl<T> QueryBuilder<T>
+where
+    T: Send + HasSchema,
+{
+    /// Executes the query returning the results as a single Arrow RecordBatch.
+    ///
+    /// The values are written straight into typed columns. No models are built
+    pub async fn run_arrow<'q, 'c>(&'q self, client: &'c dyn Client) -> Result<RecordBatch>
+    where
+        'q: 'c,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let schema = Arc::new(schema::<T>()?);
+        let syntax = client.syntax();
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+
+        let columns = <T as HasSchema>::Schema::columns();
+        let writer = self.select_writer(syntax);
+        let sql = in_namespace(client, || {
+            writer.sql(
+                &columns,
+                &self.wheres,
+                &self.exist_ins,
+                &self.limit,
+                &self.offset,
+                &self.orderby,
+                &mut args,
+            )
+        });
+
+        let args = args.unwrap();
+        let rows = client.fetch_rows(&sql, &args).await?;
+        record_batch(schema, &rows)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,54 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::{Syntax, WeldsModel};
+use arrow_array::Array;
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i64,
+    pub name: String,
+    #[welds(rename = "cost")]
+    pub price: Option<f64>,
+    pub active: bool,
+}
+
+#[test]
+fn should_write_a_field_for_each_column() {
+    let schema = schema::<Product>().unwrap();
+    let expected = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+        Field::new("cost", DataType::Float64, true),
+        Field::new("active", DataType::Boolean, false),
+    ]);
+    assert_eq!(schema, expected);
+}
+
+#[test]
+fn should_read_rows_into_columns() {
+    let client = MockClient::new(Syntax::Postgres);
+    let row = |id: i64, price: Option<f64>| {
+        MockRow::new()
+            .with("id", id)
+            .with("name", format!("product {}", id))
+            .with("cost", price)
+            .with("active", true)
+    };
+    client.enqueue_rows("products", vec![row(1, Some(2.5)), row(2, None)]);
+
+    let q = Product::where_col(|p| p.id.gt(0));
+    let batch = block_on(q.run_arrow(&client)).unwrap();
+    assert!(client.last_sql().unwrap().starts_with("SELECT"));
+
+    assert_eq!(batch.num_rows(), 2);
+    let names = batch.column(1).as_any().downcast_ref::<StringArray>();
+    assert_eq!(names.unwrap().value(1), "product 2");
+    let prices = batch.column(2).as_any().downcast_ref::<Float64Array>();
+    let prices = prices.unwrap();
+    assert_eq!(prices.value(0), 2.5);
+    assert!(prices.is_null(1));
+}
@@ -0,0 +1,244 @@
+use crate::errors::Result;
+use crate::fnv::Fnv;
+use crate::migrations::{self, MigrationFn, MigrationLog};
+use crate::{Client, Syntax, TransactStart};
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+#[cfg(feature = "check")]
+use crate::check::{Issue, Level};
+#[cfg(feature = "check")]
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+#[cfg(feature = "check")]
+use std::future::Future;
+#[cfg(feature = "check")]
+use std::pin::Pin;
+
+/// Data a service needs in its database before it can start (lookup tables, a default admin).
+///
+/// Seeds are run on every bootstrap, after the migrations, so write them to be re-runnable
+/// (`bulk_insert_ignore`, `INSERT ... WHERE NOT EXISTS`, ...).
+#[async_trait]
+pub trait Seed: Send + Sync {
+    /// Used in the logs
+    fn name(&self) -> &str;
+    async fn run(&self, client: &dyn Client) -> Result<()>;
+}
+
+#[cfg(feature = "check")]
+type Validator =
+    for<'a> fn(&'a dyn Client) -> Pin<Box<dyn Future<Output = Result<Vec<Issue>>> + Send + 'a>>;
+
+/// What `bootstrap` should do
+pub struct BootstrapOptions {
+    lock_name: String,
+    migrations: Vec<MigrationFn>,
+    seeds: Vec<Box<dyn Seed>>,
+    #[cfg(feature = "check")]
+    validators: Vec<Validator>,
+    #[cfg(feature = "check")]
+    fail_on: Level,
+}
+
+impl Default for BootstrapOptions {
+    fn default() -> Self {
+        Self {
+            lock_name: "welds_bootstrap".to_owned(),
+            migrations: Vec::default(),
+            seeds: Vec::default(),
+            #[cfg(feature = "check")]
+            validators: Vec::default(),
+            #[cfg(feature = "check")]
+            fail_on: Level::Critical,
+        }
+    }
+}
+
+impl BootstrapOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The migrations to bring the database up to, see `migrations::up`
+    pub fn migrations(mut self, migrations: &[MigrationFn]) -> Self {
+        self.migrations.extend_from_slice(migrations);
+        self
+    }
+
+    /// Adds a seed, run after the migrations. Seeds are run in the order they are added
+    pub fn seed(mut self, seed: impl Seed + 'static) -> Self {
+        self.seeds.push(Box::new(seed));
+        self
+    }
+
+    /// Checks the table of this model against the database once it is migrated
+    #[cfg(feature = "check")]
+    pub fn validate<T>(mut self) -> Self
+    where
+        T: Send + HasSchema + 'static,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.validators.push(validate_model::<T>);
+        self
+    }
+
+    /// Fails the bootstrap if validation finds an issue at this level or worse.
+    /// `Critical` by default (missing tables and columns)
+    #[cfg(feature = "check")]
+    pub fn fail_on(mut self, level: Level) -> Self {
+        self.fail_on = level;
+        self
+    }
+
+    /// Names the lock held while bootstrapping. Services sharing a database should share the name
+    pub fn lock_name(mut self, name: impl Into<String>) -> Self {
+        self.lock_name = name.into();
+        self
+    }
+}
+
+/// What `bootstrap` did
+#[derive(Debug, Default)]
+pub struct BootstrapReport {
+    /// The migrations this run applied, oldest first
+    pub applied: Vec<String>,
+    /// Everything validation found, including the issues below the `fail_on` level
+    #[cfg(feature = "check")]
+    pub issues: Vec<Issue>,
+}
+
+/// The startup path for a service: migrates the database, seeds it, then validates the models
+/// against it.
+///
+/// All three steps run while holding a lock (`pg_advisory_xact_lock`, MySql `GET_LOCK`,
+/// MSSQL `sp_getapplock`), so when several instances start at once only one of them migrates
+/// and the others wait for it. Sqlite and CockroachDB are not locked.
+/// The lock is held with a transaction of its own, so the client needs at least two connections.
+///
+/// ```rust,ignore
+/// let options = BootstrapOptions::new()
+///     .migrations(&[create_users, add_email_index])
+///     .seed(DefaultRoles)
+///     .validate::<User>();
+/// let report = welds::bootstrap(&client, options).await?;
+/// ```
+pub async fn bootstrap<C>(client: &C, options: BootstrapOptions) -> Result<BootstrapReport>
+where
+    C: Client + TransactStart,
+{
+    let lock = client.begin().await?;
+    let key = lock_key(&options.lock_name);
+    let syntax = lock.syntax();
+    match syntax {
+        Syntax::Postgres => {
+            lock.execute("SELECT pg_advisory_xact_lock($1)", &[&key])
+                .await?;
+        }
+        Syntax::Mysql | Syntax::MariaDb => {
+            lock.execute("SELECT GET_LOCK(?, -1)", &[&options.lock_name])
+                .await?;
+        }
+        Syntax::Mssql => {
+            let sql = "EXEC sp_getapplock @Resource = @p1, @LockMode = 'Exclusive', @LockOwner = 'Transaction', @LockTimeout = -1";
+            lock.execute(sql, &[&options.lock_name]).await?;
+        }
+        Syntax::Sqlite | Syntax::CockroachDb => {}
+    }
+
+    let result = run_steps(client, &options).await;
+
+    // the mysql lock belongs to the connection, it has to be given back before the pool gets it
+    if let Syntax::Mysql | Syntax::MariaDb = syntax {
+        lock.execute("SELECT RELEASE_LOCK(?)", &[&options.lock_name])
+            .await?;
+    }
+    lock.commit().await?;
+    result
+}
+
+async fn run_steps<C>(client: &C, options: &BootstrapOptions) -> Result<BootstrapReport>
+where
+    C: Client + TransactStart,
+{
+    let mut report = BootstrapReport::default();
+
+    // the migration table doesn't exist before the first run
+    let before: HashSet<String> = MigrationLog::all()
+        .run(client)
+        .await
+        .map(|logs| logs.into_iter().map(|l| l.into_inner().name).collect())
+        .unwrap_or_default();
+    migrations::up(client, &options.migrations).await?;
+    if !options.migrations.is_empty() {
+        let mut after = MigrationLog::all()
+            .order_by_asc(|m| m.id)
+            .run(client)
+            .await?;
+        report.applied = after
+            .drain(..)
+            .map(|l| l.into_inner().name)
+            .filter(|name| !before.contains(name))
+            .collect();
+    }
+    for name in &report.applied {
+        log::info!("Applied migration {}", name);
+    }
+
+    if !options.seeds.is_empty() {
+        let trans = client.begin().await?;
+        for seed in &options.seeds {
+            log::info!("Running seed {}", seed.name());
+            seed.run(&trans).await?;
+        }
+        trans.commit().await?;
+    }
+
+    #[cfg(feature = "check")]
+    {
+        for validate in &options.validators {
+            report.issues.extend(validate(client).await?);
+        }
+        let failing: Vec<Issue> = report
+            .issues
+            .iter()
+            .filter(|issue| severity(issue.level) >= severity(options.fail_on))
+            .cloned()
+            .collect();
+        if !failing.is_empty() {
+            return Err(crate::WeldsError::SchemaMismatch(failing));
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(feature = "check")]
+fn validate_model<T>(
+    client: &dyn Client,
+) -> Pin<Box<dyn Future<Output = Result<Vec<Issue>>> + Send + '_>>
+where
+    T: Send + HasSchema + 'static,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    Box::pin(crate::check::schema::<T>(client))
+}
+
+#[cfg(feature = "check")]
+fn severity(level: Level) -> u8 {
+    match level {
+        Level::Low => 0,
+        Level::Medium => 1,
+        Level::High => 2,
+        Level::Critical => 3,
+    }
+}
+
+// postgres advisory locks are keyed by a number, FNV-1a of the lock name
+fn lock_key(name: &str) -> i64 {
+    let mut hash = Fnv::default();
+    hash.write(name.as_bytes());
+    hash.finish() as i64
+}
+
+#[cfg(test)]
+mod tests;
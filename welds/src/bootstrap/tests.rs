@@ -0,0 +1,47 @@
+use super::*;
+use crate::connections::mock::MockClient;
+use futures::executor::block_on;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct Roles(Arc<AtomicUsize>);
+
+#[async_trait]
+impl Seed for Roles {
+    fn name(&self) -> &str {
+        "roles"
+    }
+    async fn run(&self, client: &dyn Client) -> Result<()> {
+        client
+            .execute("INSERT INTO roles (name) VALUES ('admin')", &[])
+            .await?;
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[test]
+fn should_seed_while_holding_the_lock() {
+    let client = MockClient::new(Syntax::Postgres);
+    let runs = Arc::new(AtomicUsize::new(0));
+    let options = BootstrapOptions::new().seed(Roles(runs.clone()));
+
+    let report = block_on(bootstrap(&client, options)).unwrap();
+
+    assert!(report.applied.is_empty());
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+    assert_eq!(sql[..2], ["BEGIN", "SELECT pg_advisory_xact_lock($1)"]);
+    assert!(sql.iter().any(|s| s.starts_with("INSERT INTO roles")));
+}
+
+#[test]
+fn should_release_the_mysql_lock_when_done() {
+    let client = MockClient::new(Syntax::Mysql);
+
+    block_on(bootstrap(&client, BootstrapOptions::new())).unwrap();
+
+    let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+    assert_eq!(sql[..2], ["BEGIN", "SELECT GET_LOCK(?, -1)"]);
+    assert_eq!(sql[sql.len() - 2..], ["SELECT RELEASE_LOCK(?)", "COMMIT"]);
+}
@@ -0,0 +1,110 @@
+use crate::errors::{Result, WeldsError};
+use crate::fnv::Fnv;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+mod query;
+pub(crate) use query::invalidate_table;
+pub use query::{remove_query_cache, set_query_cache, CachedQuery, MemoryCache, QueryCache};
+
+// Every encoded model starts with this, then the format version and the schema hash
+const MAGIC: &[u8; 4] = b"WLDS";
+const FORMAT_VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 8;
+
+/// Encodes models for a cache (Redis, memcached, ...) in a way that notices when the model
+/// has changed since the entry was written.
+///
+/// The bytes start with a small header: a format version and a hash of the model's schema
+/// (its table and the name, type and nullability of every column). The model itself follows
+/// as JSON. An entry written by a different version of the model is treated as a miss instead
+/// of being decoded into the wrong shape.
+///
+/// ```rust,ignore
+/// let bytes = product.to_cache_bytes()?;
+/// redis.set(key, bytes).await?;
+///
+/// let cached: Option<Product> = match redis.get::<Vec<u8>>(key).await? {
+///     Some(bytes) => Product::from_cache_bytes(&bytes)?,
+///     None => None,
+/// };
+/// ```
+pub trait CacheCodec: Sized {
+    /// The model with the version header in front
+    fn to_cache_bytes(&self) -> Result<Vec<u8>>;
+
+    /// Reads a model written by `to_cache_bytes`.
+    /// Returns None if it was written by another version of the model (a stale entry),
+    /// and fails if the bytes aren't a cache entry at all
+    fn from_cache_bytes(bytes: &[u8]) -> Result<Option<Self>>;
+}
+
+impl<T> CacheCodec for T
+where
+    T: HasSchema + Serialize + DeserializeOwned,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    fn to_cache_bytes(&self) -> Result<Vec<u8>> {
+        encode(schema_hash::<T>(), self)
+    }
+
+    fn from_cache_bytes(bytes: &[u8]) -> Result<Option<Self>> {
+        decode(schema_hash::<T>(), bytes)
+    }
+}
+
+// the header then the value as JSON
+pub(crate) fn encode<V: Serialize + ?Sized>(hash: u64, value: &V) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + 64);
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(FORMAT_VERSION);
+    bytes.extend_from_slice(&hash.to_le_bytes());
+    serde_json::to_writer(&mut bytes, value)
+        .map_err(|e| WeldsError::InvalidCacheEntry(e.to_string()))?;
+    Ok(bytes)
+}
+
+// None when the entry was written for another schema hash or format version
+pub(crate) fn decode<V: DeserializeOwned>(hash: u64, bytes: &[u8]) -> Result<Option<V>> {
+    if bytes.len() < HEADER_LEN || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(WeldsError::InvalidCacheEntry(
+            "missing the welds header".to_owned(),
+        ));
+    }
+    let version = bytes[MAGIC.len()];
+    let mut found = [0; 8];
+    found.copy_from_slice(&bytes[MAGIC.len() + 1..HEADER_LEN]);
+    if version != FORMAT_VERSION || u64::from_le_bytes(found) != hash {
+        return Ok(None);
+    }
+    let value = serde_json::from_slice(&bytes[HEADER_LEN..])
+        .map_err(|e| WeldsError::InvalidCacheEntry(e.to_string()))?;
+    Ok(Some(value))
+}
+
+/// A hash of the table and columns of a model. Changes whenever a column is added, removed,
+/// renamed, or changes type. Stable across builds and processes
+pub fn schema_hash<T>() -> u64
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let mut columns: Vec<String> = <T as HasSchema>::Schema::columns()
+        .iter()
+        .map(|c| format!("{}:{}:{}", c.name(), c.rust_type(), c.nullable()))
+        .collect();
+    // moving a field around in the struct doesn't change what is stored
+    columns.sort();
+
+    let mut hash = Fnv::default();
+    hash.write(<T as HasSchema>::Schema::identifier().join(".").as_bytes());
+    for col in &columns {
+        hash.write(b"\0");
+        hash.write(col.as_bytes());
+    }
+    hash.finish()
+}
+
+#[cfg(test)]
+mod tests;
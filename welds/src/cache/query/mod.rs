@@ -0,0 +1,318 @@
+use super::{decode, encode, schema_hash};
+use crate::connections::value::{self, Value};
+use crate::errors::Result;
+use crate::fnv::Fnv;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::state::DbState;
+use crate::{Client, Row, WeldsError};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Where the results of `cached` queries are kept.
+///
+/// Entries are tagged with the tables the query read. When a table is written to through welds
+/// (a save, delete, or bulk insert/update/delete) the cache is told to drop every entry tagged
+/// with it, for a write made in a transaction once the transaction commits.
+/// Writes made outside of welds aren't seen, those entries live out their ttl.
+///
+/// `MemoryCache` keeps them in this process. Anything shared (Redis, memcached, ...) can be used
+/// by implementing this trait, for example with a Redis SET of keys for each table.
+#[async_trait]
+pub trait QueryCache: Send + Sync {
+    /// The bytes stored under the key, if they haven't expired
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Stores the bytes under the key for `ttl`, tagged with the tables the query read
+    async fn set(&self, key: &str, value: Vec<u8>, tables: &[String], ttl: Duration) -> Result<()>;
+
+    /// Drops every entry tagged with the table
+    async fn invalidate(&self, table: &str) -> Result<()>;
+}
+
+static QUERY_CACHE: RwLock<Option<Arc<dyn QueryCache>>> = RwLock::new(None);
+
+/// Sets the cache used by `cached` queries, and told about writes made through welds.
+/// Until it is set, `cached` queries always run in the database
+///
+/// ```rust,ignore
+/// welds::cache::set_query_cache(MemoryCache::new(10_000));
+///
+/// let products = Product::where_col(|p| p.active.equal(true))
+///     .cached(Duration::from_secs(60))
+///     .run(&client)
+///     .await?;
+/// ```
+pub fn set_query_cache(cache: impl QueryCache + 'static) {
+    *QUERY_CACHE.write().unwrap() = Some(Arc::new(cache));
+}
+
+/// Stops caching, `cached` queries run in the database again
+pub fn remove_query_cache() {
+    *QUERY_CACHE.write().unwrap() = None;
+}
+
+fn query_cache() -> Option<Arc<dyn QueryCache>> {
+    QUERY_CACHE.read().unwrap().clone()
+}
+
+/// Drops the cached results that read the table. A cache that can't be reached is logged,
+/// the write has already happened
+pub(crate) async fn invalidate_table(table: &str) {
+    let cache = match query_cache() {
+        Some(cache) => cache,
+        None => return,
+    };
+    if let Err(err) = cache.invalidate(table).await {
+        log::warn!(
+            "Could not invalidate the cached queries of {}: {}",
+            table,
+            err
+        );
+    }
+}
+
+impl<T> QueryBuilder<T>
+where
+    T: Send + HasSchema,
+{
+    /// Keeps the results of this query in the query cache (see `set_query_cache`) for `ttl`.
+    ///
+    /// Running the same query with the same params again returns the cached rows until they
+    /// expire or one of the tables the query reads is written to through welds.
+    /// In a transaction the query always runs in the database, and isn't cached: it would see
+    /// the transaction's own writes. So do queries filtered with raw SQL that has a sub-query
+    /// in it (`where_raw`, `where_manual`), the tables it reads can't be known, and queries
+    /// with a param that isn't a number, bool, text or bytes (a date, a uuid, ...), its value
+    /// can't be read to tell the queries apart.
+    pub fn cached(self, ttl: Duration) -> CachedQuery<T> {
+        CachedQuery { query: self, ttl }
+    }
+}
+
+/// A query whose results are kept in the query cache. See `QueryBuilder::cached`
+pub struct CachedQuery<T> {
+    query: QueryBuilder<T>,
+    ttl: Duration,
+}
+
+impl<T> CachedQuery<T>
+where
+    T: Send + HasSchema + Serialize + DeserializeOwned,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    /// Returns the cached rows, or runs the query and caches them
+    pub async fn run(&self, client: &dyn Client) -> Result<Vec<DbState<T>>>
+    where
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let cache = match query_cache() {
+            Some(cache) if !client.in_transaction() => cache,
+            _ => return self.query.run(client).await,
+        };
+        let (tables, key) = match self.tables() {
+            Some(tables) => match self.key(client, &tables[0]) {
+                Some(key) => (tables, key),
+                None => return self.query.run(client).await,
+            },
+            None => return self.query.run(client).await,
+        };
+        let hash = schema_hash::<T>();
+
+        // a cache that is down or holds a bad entry is a miss, not a failed query
+        match cache.get(&key).await {
+            Ok(Some(bytes)) => match decode::<Vec<T>>(hash, &bytes) {
+                Ok(Some(rows)) => return Ok(rows.into_iter().map(DbState::db_loaded).collect()),
+                Ok(None) => {}
+                Err(err) => log::warn!("Ignoring the cached query {}: {}", key, err),
+            },
+            Ok(None) => {}
+            Err(err) => log::warn!("Could not read the cached query {}: {}", key, err),
+        }
+
+        let rows = self.query.run(client).await?;
+        let models: Vec<&T> = rows.iter().map(|row| row.as_ref()).collect();
+        let bytes = encode(hash, &models)?;
+        if let Err(err) = cache.set(&key, bytes, &tables, self.ttl).await {
+            log::warn!("Could not cache the query {}: {}", key, err);
+        }
+        Ok(rows)
+    }
+
+    // the model's table first, then the tables of its sub-queries.
+    // None if a sub-query reads tables that can't be named
+    fn tables(&self) -> Option<Vec<String>> {
+        let mut tables = vec![<T as HasSchema>::Schema::identifier().join(".")];
+        match self.query.subquery_tables(&mut tables) {
+            true => Some(tables),
+            false => None,
+        }
+    }
+
+    // the same SQL and params are the same query. The tables of a client with a namespace
+    // are other tables, so its queries get keys of their own. Writes invalidate the table in
+    // every namespace. None if the value of a param can't be read
+    fn key(&self, client: &dyn Client, table: &str) -> Option<String> {
+        let syntax = client.syntax();
+        let (sql, params) = self.query.to_sql_with_params(syntax);
+        let mut hash = Fnv::default();
+        hash.write(format!("{:?}", syntax).as_bytes());
+        hash.write(b"\0");
+        hash.write(sql.as_bytes());
+        hash.write(b"\0");
+        for param in params {
+            let value = value::of_param(param);
+            if let Value::Opaque(_) = value {
+                return None;
+            }
+            hash.write(format!("{:?}", value).as_bytes());
+            hash.write(b"\0");
+        }
+        if let Some(namespace) = client.namespace() {
+            hash.write(b"\0");
+            hash.write(format!("{:?}", namespace).as_bytes());
+        }
+        Some(format!("welds:{}:{:016x}", table, hash.finish()))
+    }
+}
+
+/// A `QueryCache` in the memory of this process, holding up to `capacity` queries.
+/// When it is full the least recently used query is dropped
+pub struct MemoryCache {
+    capacity: usize,
+    lru: Mutex<Lru>,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            lru: Mutex::new(Lru::default()),
+        }
+    }
+
+    /// How many queries are cached, including any that have expired but not been dropped yet
+    pub fn len(&self) -> usize {
+        self.lru.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl QueryCache for MemoryCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.lru.lock().unwrap().get(key, Instant::now()))
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, tables: &[String], ttl: Duration) -> Result<()> {
+        let expires = Instant::now() + ttl;
+        let mut lru = self.lru.lock().unwrap();
+        lru.set(self.capacity, key, value, tables, expires);
+        Ok(())
+    }
+
+    async fn invalidate(&self, table: &str) -> Result<()> {
+        self.lru.lock().unwrap().invalidate(table);
+        Ok(())
+    }
+}
+
+struct Entry {
+    value: Vec<u8>,
+    tables: Vec<String>,
+    expires: Instant,
+    used: u64,
+}
+
+#[derive(Default)]
+struct Lru {
+    tick: u64,
+    entries: HashMap<String, Entry>,
+    // the keys by when they were last used, oldest first
+    order: BTreeMap<u64, String>,
+    // the keys that read each table
+    tables: HashMap<String, HashSet<String>>,
+}
+
+impl Lru {
+    fn get(&mut self, key: &str, now: Instant) -> Option<Vec<u8>> {
+        let expired = self.entries.get(key)?.expires <= now;
+        if expired {
+            self.remove(key);
+            return None;
+        }
+        self.tick += 1;
+        let entry = self.entries.get_mut(key)?;
+        self.order.remove(&entry.used);
+        entry.used = self.tick;
+        self.order.insert(self.tick, key.to_owned());
+        Some(entry.value.clone())
+    }
+
+    fn set(
+        &mut self,
+        capacity: usize,
+        key: &str,
+        value: Vec<u8>,
+        tables: &[String],
+        expires: Instant,
+    ) {
+        self.remove(key);
+        if capacity == 0 {
+            return;
+        }
+        while self.entries.len() >= capacity {
+            match self.order.pop_first() {
+                Some((_, oldest)) => self.remove(&oldest),
+                None => break,
+            }
+        }
+        self.tick += 1;
+        for table in tables {
+            let keys = self.tables.entry(table.clone()).or_default();
+            keys.insert(key.to_owned());
+        }
+        self.order.insert(self.tick, key.to_owned());
+        let entry = Entry {
+            value,
+            tables: tables.to_vec(),
+            expires,
+            used: self.tick,
+        };
+        self.entries.insert(key.to_owned(), entry);
+    }
+
+    fn remove(&mut self, key: &str) {
+        let entry = match self.entries.remove(key) {
+            Some(entry) => entry,
+            None => return,
+        };
+        self.order.remove(&entry.used);
+        for table in &entry.tables {
+            if let Some(keys) = self.tables.get_mut(table) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.tables.remove(table);
+                }
+            }
+        }
+    }
+
+    fn invalidate(&mut self, table: &str) {
+        let keys = self.tables.remove(table).unwrap_or_default();
+        for key in keys {
+            self.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
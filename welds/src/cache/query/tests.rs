@@ -0,0 +1,187 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::TransactStart;
+use crate::query::builder::ManualWhereParam;
+use crate::{Syntax, WeldsModel};
+use futures::executor::block_on;
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, WeldsModel, Serialize, Deserialize)]
+#[welds(table = "cached_products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, PartialEq, WeldsModel, Serialize, Deserialize)]
+#[welds(table = "cached_orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub product_id: i32,
+}
+
+const MINUTE: Duration = Duration::from_secs(60);
+
+fn tables(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn should_drop_the_least_recently_used_query_when_full() {
+    block_on(async {
+        let cache = MemoryCache::new(2);
+        cache
+            .set("a", vec![1], &tables(&["t"]), MINUTE)
+            .await
+            .unwrap();
+        cache
+            .set("b", vec![2], &tables(&["t"]), MINUTE)
+            .await
+            .unwrap();
+        // reading a makes b the oldest
+        assert_eq!(cache.get("a").await.unwrap(), Some(vec![1]));
+        cache
+            .set("c", vec![3], &tables(&["t"]), MINUTE)
+            .await
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("b").await.unwrap(), None);
+        assert_eq!(cache.get("a").await.unwrap(), Some(vec![1]));
+        assert_eq!(cache.get("c").await.unwrap(), Some(vec![3]));
+    });
+}
+
+#[test]
+fn should_expire_queries_after_their_ttl() {
+    block_on(async {
+        let cache = MemoryCache::new(10);
+        cache.set("a", vec![1], &[], Duration::ZERO).await.unwrap();
+        assert_eq!(cache.get("a").await.unwrap(), None);
+        assert!(cache.is_empty());
+    });
+}
+
+#[test]
+fn should_invalidate_every_query_that_read_a_table() {
+    block_on(async {
+        let cache = MemoryCache::new(10);
+        let both = tables(&["orders", "customers"]);
+        cache.set("a", vec![1], &both, MINUTE).await.unwrap();
+        cache
+            .set("b", vec![2], &tables(&["orders"]), MINUTE)
+            .await
+            .unwrap();
+        cache
+            .set("c", vec![3], &tables(&["items"]), MINUTE)
+            .await
+            .unwrap();
+        cache.invalidate("customers").await.unwrap();
+        assert_eq!(cache.get("a").await.unwrap(), None);
+        assert_eq!(cache.get("b").await.unwrap(), Some(vec![2]));
+        cache.invalidate("orders").await.unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("c").await.unwrap(), Some(vec![3]));
+    });
+}
+
+#[test]
+fn should_tag_queries_with_the_tables_of_their_sub_queries() {
+    let ordered = || Order::all().select(|o| o.product_id);
+    let query = Product::where_col(|p| p.id.in_query(ordered()))
+        .where_any(|g| g.where_col(|p| p.id.not_in_query(ordered())));
+    let tables = query.cached(MINUTE).tables().unwrap();
+    assert_eq!(
+        tables,
+        ["cached_products", "cached_orders", "cached_orders"]
+    );
+}
+
+#[test]
+fn should_not_cache_raw_sql_with_a_sub_query() {
+    let raw = Product::all().where_raw("t1.id > ?", ManualWhereParam::new().push(1));
+    assert!(raw.cached(MINUTE).tables().is_some());
+
+    let sql = "t1.id IN (SELECT product_id FROM cached_orders)";
+    let raw = Product::all().where_any(|g| g.where_raw(sql, ManualWhereParam::new()));
+    assert!(raw.cached(MINUTE).tables().is_none());
+}
+
+// the only test that sets the global cache, the others would race it
+#[test]
+fn should_cache_query_results_until_the_table_is_written() {
+    block_on(async {
+        set_query_cache(MemoryCache::new(10));
+        let client = MockClient::new(Syntax::Postgres);
+        let rows = || vec![MockRow::new().with("id", 1).with("name", "Pen")];
+        let query = || Product::where_col(|p| p.id.gt(0)).cached(MINUTE);
+        let selects = |client: &MockClient| {
+            let queries = client.queries();
+            queries
+                .iter()
+                .filter(|q| q.sql.starts_with("SELECT"))
+                .count()
+        };
+
+        client.enqueue_rows("SELECT", rows());
+        let first = query().run(&client).await.unwrap();
+        let second = query().run(&client).await.unwrap();
+        assert_eq!(selects(&client), 1);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].as_ref(), first[0].as_ref());
+
+        // different params are a different query
+        let other = Product::where_col(|p| p.id.gt(5)).cached(MINUTE);
+        other.run(&client).await.unwrap();
+        assert_eq!(selects(&client), 2);
+
+        // a tenant's tables are other tables
+        let tenant = client.clone().with_schema("tenant_42").unwrap();
+        query().run(&tenant).await.unwrap();
+        assert_eq!(selects(&client), 3);
+        let last = client.last_sql().unwrap();
+        assert!(last.contains("FROM tenant_42.cached_products t1"));
+
+        Product::where_col(|p| p.id.equal(1))
+            .delete(&client)
+            .await
+            .unwrap();
+        client.enqueue_rows("SELECT", rows());
+        query().run(&client).await.unwrap();
+        assert_eq!(selects(&client), 4);
+
+        // a transaction reads its own writes, it doesn't use or fill the cache
+        let transaction = client.begin().await.unwrap();
+        client.enqueue_rows("SELECT", rows());
+        query().run(&transaction).await.unwrap();
+        assert_eq!(selects(&client), 5);
+        // the writes of a transaction drop the cached queries when it commits
+        Product::where_col(|p| p.id.equal(1))
+            .delete(&transaction)
+            .await
+            .unwrap();
+        query().run(&client).await.unwrap();
+        assert_eq!(selects(&client), 5);
+        transaction.commit().await.unwrap();
+        client.enqueue_rows("SELECT", rows());
+        query().run(&client).await.unwrap();
+        assert_eq!(selects(&client), 6);
+
+        // and are dropped with it when it rolls back
+        let transaction = client.begin().await.unwrap();
+        Product::where_col(|p| p.id.equal(1))
+            .delete(&transaction)
+            .await
+            .unwrap();
+        transaction.rollback().await.unwrap();
+        query().run(&client).await.unwrap();
+        assert_eq!(selects(&client), 6);
+
+        remove_query_cache();
+        query().run(&client).await.unwrap();
+        assert_eq!(selects(&client), 7);
+    });
+}
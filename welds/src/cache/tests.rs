@@ -0,0 +1,73 @@
+use super::*;
+use crate::WeldsModel;
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, WeldsModel, Serialize, Deserialize)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+// the same table once a column has been added
+#[derive(Debug, WeldsModel, Serialize, Deserialize)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct ProductWithPrice {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub price: f64,
+}
+
+// the same columns in another order
+#[derive(Debug, WeldsModel, Serialize, Deserialize)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct ProductReordered {
+    pub name: String,
+    pub description: Option<String>,
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+fn product() -> Product {
+    Product {
+        id: 7,
+        name: "plush".to_owned(),
+        description: None,
+    }
+}
+
+#[test]
+fn should_read_back_what_was_written() {
+    let bytes = product().to_cache_bytes().unwrap();
+    assert_eq!(&bytes[..4], b"WLDS");
+    let read = Product::from_cache_bytes(&bytes).unwrap();
+    assert_eq!(read, Some(product()));
+}
+
+#[test]
+fn should_miss_when_the_model_has_changed() {
+    let bytes = product().to_cache_bytes().unwrap();
+    // the JSON alone would happily decode, the price has a default
+    let read = ProductWithPrice::from_cache_bytes(&bytes).unwrap();
+    assert!(read.is_none());
+}
+
+#[test]
+fn should_not_care_about_the_order_of_the_fields() {
+    assert_eq!(schema_hash::<Product>(), schema_hash::<ProductReordered>());
+    assert_ne!(schema_hash::<Product>(), schema_hash::<ProductWithPrice>());
+}
+
+#[test]
+fn should_fail_on_bytes_that_are_not_a_cache_entry() {
+    let err = Product::from_cache_bytes(br#"{"id":7}"#).unwrap_err();
+    assert!(matches!(err, WeldsError::InvalidCacheEntry(_)));
+}
@@ -75,7 +75,7 @@ pub async fn find_table(
     args.push(&namespace);
 
     // Mysql query needs the namespace param twice
-    if let Syntax::Mysql = syntax {
+    if let Syntax::Mysql | Syntax::MariaDb = syntax {
         args.push(&namespace);
     }
 
@@ -117,7 +117,7 @@ pub async fn table_search(
     args.push(&namespace);
 
     // Mysql query needs the namespace param twice
-    if let Syntax::Mysql = syntax {
+    if let Syntax::Mysql | Syntax::MariaDb = syntax {
         args.push(&namespace);
     }
 
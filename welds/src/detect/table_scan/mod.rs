@@ -9,7 +9,7 @@ pub(crate) struct TableScan {
 impl TableScan {
     pub(crate) fn new(syntax: Syntax) -> TableScan {
         match syntax {
-            Syntax::Mysql => TableScan {
+            Syntax::Mysql | Syntax::MariaDb => TableScan {
                 table_scan_sql: MySql::table_scan_sql,
                 single_table_scan_sql: MySql::single_table_scan_sql,
                 fk_scan_sql: MySql::fk_scan_sql,
@@ -19,6 +19,11 @@ impl TableScan {
                 single_table_scan_sql: Postgres::single_table_scan_sql,
                 fk_scan_sql: Postgres::fk_scan_sql,
             },
+            Syntax::CockroachDb => TableScan {
+                table_scan_sql: CockroachDb::table_scan_sql,
+                single_table_scan_sql: Postgres::single_table_scan_sql,
+                fk_scan_sql: Postgres::fk_scan_sql,
+            },
             Syntax::Sqlite => TableScan {
                 table_scan_sql: Sqlite::table_scan_sql,
                 single_table_scan_sql: Sqlite::single_table_scan_sql,
@@ -45,6 +50,14 @@ impl TableScan {
     }
 }
 
+// CockroachDB has the same catalog as postgres, but its own internal schemas to hide
+struct CockroachDb;
+impl CockroachDb {
+    fn table_scan_sql() -> &'static str {
+        include_str!("./cockroachdb.sql")
+    }
+}
+
 struct Postgres;
 impl Postgres {
     fn table_scan_sql() -> &'static str {
@@ -1,5 +1,6 @@
 use crate::model_traits::TableIdent;
 use thiserror::Error;
+use welds_connections::retry::Retryable;
 use welds_connections::Error as ConnError;
 
 pub type Result<T> = std::result::Result<T, WeldsError>;
@@ -20,6 +21,8 @@ pub enum WeldsError {
     RowNowFound,
     #[error("A Primary key is required for this action")]
     NoPrimaryKey,
+    #[error("The row was changed or deleted since it was read")]
+    StaleRecord,
     #[error("There are multiple migrations with the same name")]
     DuplicateMigration,
     #[error("An underlying Hook canceled the action")]
@@ -28,6 +31,36 @@ pub enum WeldsError {
         "Multiple tables exist with this table. Use `table_search` to search return all results"
     )]
     AmbiguousTable,
+    #[error("Invalid patch: {0}")]
+    InvalidPatch(String),
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+    #[error("Could not convert the field {0}")]
+    InvalidMapping(String),
+    #[error("The write-behind writer is closed, the row was not saved")]
+    WriterClosed,
+    #[error("Server-side cursors are not supported on {0:?}")]
+    CursorsUnsupported(crate::Syntax),
+    #[error("Lateral joins are not supported on {0:?}")]
+    LateralUnsupported(crate::Syntax),
+    #[error("Returning the updated rows is not supported on {0:?}")]
+    UpdateReturningUnsupported(crate::Syntax),
+    #[error("This EXPLAIN is not supported on {0:?}")]
+    ExplainUnsupported(crate::Syntax),
+    #[error("Not a cached model: {0}")]
+    InvalidCacheEntry(String),
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(String),
+    #[error("The model has not been saved to the database yet")]
+    NotSaved,
+    #[error("Rows can not be linked by a key of type {0}")]
+    UnlinkableKey(String),
+    #[cfg(feature = "check")]
+    #[error("The database schema doesn't match the models, {} issue(s) found", .0.len())]
+    SchemaMismatch(Vec<crate::check::Issue>),
+    #[cfg(feature = "arrow")]
+    #[error("Arrow Error: {0}")]
+    Arrow(#[from] arrow_schema::ArrowError),
     #[error("Anyhow Error")]
     Other(#[from] anyhow::Error),
 }
@@ -37,3 +70,12 @@ impl From<ConnError> for WeldsError {
         WeldsError::Database(inner)
     }
 }
+
+impl Retryable for WeldsError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            WeldsError::Database(inner) => inner.is_retryable(),
+            _ => false,
+        }
+    }
+}
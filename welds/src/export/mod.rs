@@ -0,0 +1,235 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo, UniqueIdentifier};
+use crate::writers::{in_namespace, table_name, ColumnWriter, NextParam};
+use crate::{Row, Syntax};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use welds_connections::{Client, TransactStart, Transaction};
+
+/// Dumps a whole table, splitting it into ranges of primary keys that are read in chunks.
+///
+/// On Postgres the chunks are read concurrently on several connections. The connections share
+/// the snapshot of the first one (`pg_export_snapshot`), so every chunk sees the same data.
+/// Other databases read all the chunks in order in a single transaction, that sees the data
+/// as it was when the export started:
+///
+/// - MySQL and MariaDB start it `WITH CONSISTENT SNAPSHOT`. This only holds under the default
+///   REPEATABLE READ isolation, a session set to READ COMMITTED sees rows committed mid-export.
+/// - MSSQL reads under SNAPSHOT isolation, which needs `ALLOW_SNAPSHOT_ISOLATION` turned on
+///   for the database. The export fails on its first read if it isn't.
+/// - SQLite and CockroachDB transactions are already isolated from other writers.
+///
+/// The model must have a single integer primary key.
+///
+/// ```rust,ignore
+/// let exported = Exporter::<Product>::new()
+///     .chunk_size(50_000)
+///     .concurrency(4)
+///     .run(&client, |products| async move {
+///         write_to_file(products).await?;
+///         Ok(())
+///     })
+///     .await?;
+/// ```
+pub struct Exporter<T> {
+    chunk_size: i64,
+    concurrency: usize,
+    _t: PhantomData<T>,
+}
+
+impl<T> Default for Exporter<T> {
+    fn default() -> Self {
+        Self {
+            chunk_size: 10_000,
+            concurrency: 4,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T> Exporter<T>
+where
+    T: HasSchema + TryFrom<Row>,
+    WeldsError: From<<T as TryFrom<Row>>::Error>,
+    <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How wide (in primary key values) each chunk is. Defaults to 10,000
+    pub fn chunk_size(mut self, chunk_size: i64) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// How many connections are used to read chunks at the same time. Defaults to 4
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Reads the whole table, handing each chunk of models to `each` as it is read.
+    /// Chunks are not handed over in order.
+    ///
+    /// Returns the number of rows that were exported
+    pub async fn run<C, F, Fut>(&self, client: &C, each: F) -> Result<u64>
+    where
+        C: TransactStart + ?Sized,
+        F: Fn(Vec<T>) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let first = client.begin().await?;
+        let syntax = first.syntax();
+        let snapshot = match syntax {
+            Syntax::Postgres => Some(export_snapshot(&first).await?),
+            _ => {
+                consistent_snapshot(&first, syntax).await?;
+                None
+            }
+        };
+
+        let ranges = match self.ranges(&first).await? {
+            Some(ranges) => ranges,
+            None => {
+                first.commit().await?;
+                return Ok(0);
+            }
+        };
+
+        let mut transactions = vec![first];
+        if let Some(snapshot) = snapshot {
+            let extra = self.concurrency.min(ranges.len()).saturating_sub(1);
+            for _ in 0..extra {
+                let trans = client.begin().await?;
+                import_snapshot(&trans, &snapshot).await?;
+                transactions.push(trans);
+            }
+        }
+
+        let sql = in_namespace(&transactions[0], || select_range_sql::<T>(syntax));
+        let next = AtomicUsize::new(0);
+        let workers = transactions.iter().map(|trans| async {
+            let mut count = 0;
+            loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let (start, end) = match ranges.get(i) {
+                    Some(range) => *range,
+                    None => return Ok(count),
+                };
+                let rows = trans.fetch_rows(&sql, &[&start, &end]).await?;
+                count += rows.len() as u64;
+                let models: std::result::Result<Vec<T>, _> =
+                    rows.into_iter().map(T::try_from).collect();
+                each(models?).await?;
+            }
+        });
+        let counts: Vec<Result<u64>> = futures::future::join_all(workers).await;
+
+        let mut total = 0;
+        for count in counts {
+            total += count?;
+        }
+        for trans in transactions {
+            trans.commit().await?;
+        }
+        Ok(total)
+    }
+
+    // Splits the primary keys in the table into ranges (inclusive) of chunk_size
+    async fn ranges(&self, trans: &Transaction<'_>) -> Result<Option<Vec<(i64, i64)>>> {
+        let syntax = trans.syntax();
+        let writer = ColumnWriter::new(syntax);
+        let pk = writer.excape(<T::Schema as UniqueIdentifier>::id_column().name());
+        let int = match syntax {
+            Syntax::Mysql | Syntax::MariaDb => "SIGNED",
+            _ => "BIGINT",
+        };
+        let sql = format!(
+            "SELECT CAST(MIN({pk}) AS {int}), CAST(MAX({pk}) AS {int}) FROM {}",
+            in_namespace(trans, || table_name(
+                &<T::Schema as TableInfo>::identifier().join(".")
+            )),
+        );
+        let rows = trans.fetch_rows(&sql, &[]).await?;
+        let row = rows.first().ok_or(WeldsError::RowNowFound)?;
+        let min: Option<i64> = row.get_by_position(0)?;
+        let max: Option<i64> = row.get_by_position(1)?;
+        let (min, max) = match (min, max) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return Ok(None),
+        };
+
+        let mut ranges = Vec::default();
+        let mut start = min;
+        while start <= max {
+            let end = start.saturating_add(self.chunk_size - 1).min(max);
+            ranges.push((start, end));
+            if end == max {
+                break;
+            }
+            start = end + 1;
+        }
+        Ok(Some(ranges))
+    }
+}
+
+fn select_range_sql<T>(syntax: Syntax) -> String
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+{
+    let writer = ColumnWriter::new(syntax);
+    let next_param = NextParam::new(syntax);
+    let columns: Vec<String> = <T::Schema as TableColumns>::columns()
+        .iter()
+        .map(|c| writer.excape(c.name()))
+        .collect();
+    let pk = writer.excape(<T::Schema as UniqueIdentifier>::id_column().name());
+    format!(
+        "SELECT {} FROM {} WHERE {pk} >= {} AND {pk} <= {} ORDER BY {pk}",
+        columns.join(", "),
+        table_name(&<T::Schema as TableInfo>::identifier().join(".")),
+        next_param.next(),
+        next_param.next(),
+    )
+}
+
+// Makes the first transaction's snapshot available to other connections
+async fn export_snapshot(trans: &Transaction<'_>) -> Result<String> {
+    trans
+        .execute("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ", &[])
+        .await?;
+    let rows = trans.fetch_rows("SELECT pg_export_snapshot()", &[]).await?;
+    let row = rows.first().ok_or(WeldsError::RowNowFound)?;
+    Ok(row.get_by_position(0)?)
+}
+
+// Has a single transaction see the table as it was at the start, for the databases without
+// an exported snapshot
+async fn consistent_snapshot(trans: &Transaction<'_>, syntax: Syntax) -> Result<()> {
+    let sql = match syntax {
+        // replaces the transaction that was just started, MySQL commits it implicitly
+        Syntax::Mysql | Syntax::MariaDb => "START TRANSACTION WITH CONSISTENT SNAPSHOT",
+        // allowed before the transaction has read anything
+        Syntax::Mssql => "SET TRANSACTION ISOLATION LEVEL SNAPSHOT",
+        _ => return Ok(()),
+    };
+    trans.execute(sql, &[]).await?;
+    Ok(())
+}
+
+async fn import_snapshot(trans: &Transaction<'_>, snapshot: &str) -> Result<()> {
+    trans
+        .execute("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ", &[])
+        .await?;
+    // snapshot ids are made by postgres, but don't let a stray quote through
+    let snapshot = snapshot.replace('\'', "''");
+    let sql = format!("SET TRANSACTION SNAPSHOT '{}'", snapshot);
+    trans.execute(&sql, &[]).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,130 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::value::{ToValue, Value};
+use crate::WeldsModel;
+use futures::executor::block_on;
+use std::sync::Mutex;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+fn product(id: i32) -> MockRow {
+    MockRow::new().with("id", id).with("name", "plush")
+}
+
+fn bounds(min: i64, max: i64) -> Vec<MockRow> {
+    vec![MockRow::new().with("min", min).with("max", max)]
+}
+
+#[test]
+fn should_read_each_range_of_primary_keys() {
+    let client = MockClient::new(Syntax::Sqlite);
+    client.enqueue_rows("MIN", bounds(1, 25));
+    client.enqueue_rows("WHERE", vec![product(1), product(10)]);
+    client.enqueue_rows("WHERE", vec![product(11)]);
+    client.enqueue_rows("WHERE", vec![product(21), product(25)]);
+
+    let chunks = Mutex::new(Vec::default());
+    let exporter = Exporter::<Product>::new().chunk_size(10);
+    let total = block_on(exporter.run(&client, |products| {
+        chunks.lock().unwrap().push(products.len());
+        async { Ok(()) }
+    }))
+    .unwrap();
+
+    assert_eq!(total, 5);
+    assert_eq!(*chunks.lock().unwrap(), vec![2, 1, 2]);
+    let queries = client.queries();
+    assert_eq!(
+        queries[1].sql,
+        r#"SELECT CAST(MIN("id") AS BIGINT), CAST(MAX("id") AS BIGINT) FROM products"#
+    );
+    assert_eq!(
+        queries[2].sql,
+        r#"SELECT "id", "name" FROM products WHERE "id" >= ? AND "id" <= ? ORDER BY "id""#
+    );
+    let ranges: Vec<_> = queries[2..5].iter().map(|q| q.params.clone()).collect();
+    let expected: Vec<Vec<Value>> = vec![
+        vec![1_i64.to_value(), 10_i64.to_value()],
+        vec![11_i64.to_value(), 20_i64.to_value()],
+        vec![21_i64.to_value(), 25_i64.to_value()],
+    ];
+    assert_eq!(ranges, expected);
+    assert_eq!(queries.last().unwrap().sql, "COMMIT");
+}
+
+#[test]
+fn should_export_nothing_from_an_empty_table() {
+    let client = MockClient::new(Syntax::Sqlite);
+    client.enqueue_rows(
+        "MIN",
+        vec![MockRow::new()
+            .with("min", Value::Null)
+            .with("max", Value::Null)],
+    );
+    let exporter = Exporter::<Product>::new();
+    let total = block_on(exporter.run(&client, |_| async { Ok(()) })).unwrap();
+    assert_eq!(total, 0);
+}
+
+#[test]
+fn should_share_the_snapshot_on_postgres() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows(
+        "pg_export_snapshot",
+        vec![MockRow::new().with("snapshot", "0003-1B")],
+    );
+    client.enqueue_rows("MIN", bounds(1, 4));
+    let exporter = Exporter::<Product>::new().chunk_size(1).concurrency(2);
+    block_on(exporter.run(&client, |_| async { Ok(()) })).unwrap();
+
+    let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+    let setup: Vec<&str> = sql.iter().take(7).map(|s| s.as_str()).collect();
+    assert_eq!(
+        setup,
+        vec![
+            "BEGIN",
+            "SET TRANSACTION ISOLATION LEVEL REPEATABLE READ",
+            "SELECT pg_export_snapshot()",
+            r#"SELECT CAST(MIN("id") AS BIGINT), CAST(MAX("id") AS BIGINT) FROM products"#,
+            "BEGIN",
+            "SET TRANSACTION ISOLATION LEVEL REPEATABLE READ",
+            "SET TRANSACTION SNAPSHOT '0003-1B'",
+        ]
+    );
+    let chunks = sql.iter().filter(|s| s.contains("WHERE")).count();
+    assert_eq!(chunks, 4);
+    let commits = sql.iter().filter(|s| *s == "COMMIT").count();
+    assert_eq!(commits, 2);
+}
+
+#[test]
+fn should_read_from_a_consistent_snapshot_on_mysql_and_mssql() {
+    let setup = |syntax: Syntax| {
+        let client = MockClient::new(syntax);
+        client.enqueue_rows("MIN", bounds(1, 4));
+        let exporter = Exporter::<Product>::new().chunk_size(2);
+        block_on(exporter.run(&client, |_| async { Ok(()) })).unwrap();
+        let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+        assert_eq!(sql.iter().filter(|s| *s == "BEGIN").count(), 1);
+        sql[1].clone()
+    };
+    assert_eq!(
+        setup(Syntax::Mysql),
+        "START TRANSACTION WITH CONSISTENT SNAPSHOT"
+    );
+    assert_eq!(
+        setup(Syntax::MariaDb),
+        "START TRANSACTION WITH CONSISTENT SNAPSHOT"
+    );
+    assert_eq!(
+        setup(Syntax::Mssql),
+        "SET TRANSACTION ISOLATION LEVEL SNAPSHOT"
+    );
+}
@@ -0,0 +1,169 @@
+//! Support for serving models with async-graphql.
+//!
+//! Add `#[welds(graphql)]` to a model to generate its GraphQL object type, an input type named
+//! `{Model}Input`, and resolvers for its relations.
+//!
+//! Relations are loaded in batches through a `DataLoader<WeldsLoader>`,
+//! which must be added to the schema's data:
+//!
+//! ```rust,ignore
+//! let loader = DataLoader::new(WeldsLoader::new(client.clone()), tokio::spawn);
+//! let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+//!     .data(loader)
+//!     .finish();
+//! ```
+
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::writers::{in_namespace, table_name, ColumnWriter, NextParam};
+use crate::Row;
+use async_graphql::dataloader::Loader;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use welds_connections::{Client, Param};
+
+pub use async_graphql;
+
+/// Batch loads the models related to the ones being resolved
+pub struct WeldsLoader {
+    client: Arc<dyn Client>,
+}
+
+impl WeldsLoader {
+    pub fn new(client: Arc<dyn Client>) -> Self {
+        Self { client }
+    }
+}
+
+/// A request for the rows of T where `column` is equal to `key`
+pub struct RelatedBy<T, K> {
+    column: String,
+    key: K,
+    _t: PhantomData<fn() -> T>,
+}
+
+impl<T, K> RelatedBy<T, K> {
+    pub fn new(column: impl Into<String>, key: K) -> Self {
+        Self {
+            column: column.into(),
+            key,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T, K: Clone> Clone for RelatedBy<T, K> {
+    fn clone(&self) -> Self {
+        Self::new(self.column.clone(), self.key.clone())
+    }
+}
+
+impl<T, K: PartialEq> PartialEq for RelatedBy<T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.column == other.column && self.key == other.key
+    }
+}
+
+impl<T, K: Eq> Eq for RelatedBy<T, K> {}
+
+impl<T, K: Hash> Hash for RelatedBy<T, K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.column.hash(state);
+        self.key.hash(state);
+    }
+}
+
+/// The types that can be used to relate models when batch loading
+pub trait LoaderKey: Param + Clone + Hash + Eq + Send + Sync + 'static {
+    fn read(row: &Row, column: &str) -> Result<Self>;
+}
+
+macro_rules! loader_key {
+    ($($t:ty),*) => {
+        $(
+            impl LoaderKey for $t {
+                fn read(row: &Row, column: &str) -> Result<Self> {
+                    Ok(row.get(column)?)
+                }
+            }
+        )*
+    };
+}
+
+loader_key!(i16, i32, i64, String);
+
+impl<T, K> Loader<RelatedBy<T, K>> for WeldsLoader
+where
+    T: HasSchema + TryFrom<Row, Error = WeldsError> + Send + Sync + 'static,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    K: LoaderKey,
+{
+    type Value = Vec<Arc<T>>;
+    type Error = Arc<WeldsError>;
+
+    async fn load(
+        &self,
+        keys: &[RelatedBy<T, K>],
+    ) -> std::result::Result<HashMap<RelatedBy<T, K>, Self::Value>, Self::Error> {
+        let mut by_column: HashMap<&str, Vec<&K>> = HashMap::default();
+        for key in keys {
+            by_column.entry(&key.column).or_default().push(&key.key);
+        }
+
+        let mut found: HashMap<RelatedBy<T, K>, Self::Value> = HashMap::default();
+        // split up the keys so no query goes over the client's param limit
+        let max_params = self.client.capabilities().max_bind_params;
+        for (column, values) in by_column {
+            for values in values.chunks(max_params) {
+                let rows = fetch_where_in::<T, K>(self.client.as_ref(), column, values).await?;
+                for row in rows {
+                    let key = K::read(&row, column)?;
+                    let model = T::try_from(row)?;
+                    found
+                        .entry(RelatedBy::new(column, key))
+                        .or_default()
+                        .push(Arc::new(model));
+                }
+            }
+        }
+        Ok(found)
+    }
+}
+
+// SELECT the rows of T where the column is in the list of values
+async fn fetch_where_in<T, K>(client: &dyn Client, column: &str, values: &[&K]) -> Result<Vec<Row>>
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    K: LoaderKey,
+{
+    let syntax = client.syntax();
+    let writer = ColumnWriter::new(syntax);
+    let next_param = NextParam::new(syntax);
+
+    let columns: Vec<String> = <T::Schema as TableColumns>::columns()
+        .iter()
+        .map(|c| writer.excape(c.name()))
+        .collect();
+    let params: Vec<String> = values.iter().map(|_| next_param.next()).collect();
+    let sql = format!(
+        "SELECT {} FROM {} WHERE {} IN ({})",
+        columns.join(", "),
+        in_namespace(client, || table_name(
+            &<T::Schema as TableInfo>::identifier().join(".")
+        )),
+        writer.excape(column),
+        params.join(", ")
+    );
+
+    let args: Vec<&(dyn Param + Sync + Send)> = values
+        .iter()
+        .map(|v| *v as &(dyn Param + Sync + Send))
+        .collect();
+    Ok(client.fetch_rows(&sql, &args).await?)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,153 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::{Syntax, WeldsModel};
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+use futures::executor::block_on;
+
+#[derive(Debug, Clone, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(graphql)]
+#[welds(HasMany(orders, Order, "product_id"))]
+#[welds(HasOne(order, Order, "product_id"))]
+struct Product {
+    #[welds(primary_key)]
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(graphql)]
+#[welds(BelongsTo(product, Product, "product_id"))]
+struct Order {
+    #[welds(primary_key)]
+    pub id: i64,
+    pub product_id: Option<i64>,
+    pub quantity: i64,
+}
+
+fn order(id: i64, product_id: i64) -> MockRow {
+    MockRow::new()
+        .with("id", id)
+        .with("product_id", product_id)
+        .with("quantity", 1)
+}
+
+fn loader(client: &MockClient) -> DataLoader<WeldsLoader> {
+    let client: Arc<dyn Client> = Arc::new(client.clone());
+    DataLoader::new(WeldsLoader::new(client), |fut| {
+        std::thread::spawn(move || block_on(fut))
+    })
+}
+
+#[test]
+fn should_load_related_rows_in_one_query() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("orders", vec![order(1, 10), order(2, 10), order(3, 20)]);
+    let loader = loader(&client);
+
+    let keys = [10_i64, 20, 30].map(|key| RelatedBy::<Order, _>::new("product_id", key));
+    let found = block_on(loader.load_many(keys)).unwrap();
+
+    let queries = client.queries();
+    assert_eq!(queries.len(), 1);
+    assert_eq!(
+        queries[0].sql,
+        r#"SELECT "id", "product_id", "quantity" FROM orders WHERE "product_id" IN ($1, $2, $3)"#
+    );
+    assert_eq!(found[&RelatedBy::new("product_id", 10_i64)].len(), 2);
+    assert_eq!(found[&RelatedBy::new("product_id", 20_i64)].len(), 1);
+    assert!(!found.contains_key(&RelatedBy::new("product_id", 30_i64)));
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn orders(&self) -> Vec<Order> {
+        vec![
+            Order {
+                id: 1,
+                product_id: Some(10),
+                quantity: 2,
+            },
+            Order {
+                id: 2,
+                product_id: None,
+                quantity: 3,
+            },
+        ]
+    }
+}
+
+#[test]
+fn should_resolve_fields_and_relations() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows(
+        "products",
+        vec![MockRow::new().with("id", 10).with("name", "plush")],
+    );
+    client.enqueue_rows("orders", vec![order(1, 10), order(4, 10)]);
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(loader(&client))
+        .finish();
+    let query = "{ orders { id quantity product { name orders { id } } } }";
+    let response = block_on(schema.execute(query));
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let json = response.data.into_json().unwrap();
+    let expected = serde_json::json!({
+        "orders": [
+            { "id": 1, "quantity": 2, "product": { "name": "plush", "orders": [{ "id": 1 }, { "id": 4 }] } },
+            { "id": 2, "quantity": 3, "product": null },
+        ]
+    });
+    assert_eq!(json, expected);
+}
+
+#[test]
+fn should_resolve_a_has_one_relation_as_a_single_object() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows(
+        "products",
+        vec![MockRow::new().with("id", 10).with("name", "plush")],
+    );
+    client.enqueue_rows("orders", vec![order(4, 10)]);
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(loader(&client))
+        .finish();
+    let query = "{ orders { id product { order { id } } } }";
+    let response = block_on(schema.execute(query));
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let json = response.data.into_json().unwrap();
+    let expected = serde_json::json!({
+        "orders": [
+            { "id": 1, "product": { "order": { "id": 4 } } },
+            { "id": 2, "product": null },
+        ]
+    });
+    assert_eq!(json, expected);
+}
+
+#[test]
+fn should_apply_an_input_to_a_model() {
+    let mut model = Order {
+        id: 1,
+        product_id: Some(10),
+        quantity: 2,
+    };
+    let input = OrderInput {
+        product_id: None,
+        quantity: 5,
+    };
+    input.apply_to(&mut model);
+    assert_eq!(model.id, 1);
+    assert_eq!(model.product_id, None);
+    assert_eq!(model.quantity, 5);
+}
@@ -0,0 +1,240 @@
+use crate::errors::{Result, WeldsError};
+use crate::migrations::{create_table, types::Type, MigrationWriter};
+use crate::writers::NextParam;
+use crate::{Client, TransactStart};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The writes guarded by an idempotency key. Runs on the transaction the key is recorded in
+pub type Work<'t, R> = Pin<Box<dyn Future<Output = Result<R>> + Send + 't>>;
+
+/// The outcome of a write guarded by an idempotency key
+#[derive(Debug, Clone, PartialEq)]
+pub enum Idempotent<R> {
+    /// The work ran and was committed along with the key
+    Completed(R),
+    /// The key was used before. The work did not run, this is the response recorded back then
+    Replayed(R),
+}
+
+impl<R> Idempotent<R> {
+    pub fn into_inner(self) -> R {
+        match self {
+            Idempotent::Completed(r) => r,
+            Idempotent::Replayed(r) => r,
+        }
+    }
+
+    pub fn is_replay(&self) -> bool {
+        matches!(self, Idempotent::Replayed(_))
+    }
+}
+
+/// Makes writes safe to retry. Each key can complete once: the work runs in a transaction
+/// that also records the key and the serialized response, and later calls with the same key
+/// get the recorded response back without running anything.
+///
+/// If two calls with the same key race, the unique index on the key makes one of the commits
+/// fail. Its writes are rolled back and it replays the response of the one that won.
+///
+/// ```rust,ignore
+/// let keys = IdempotencyKeys::new().expire_after(Duration::from_secs(24 * 60 * 60));
+/// keys.ensure_table(&client).await?;
+///
+/// let order_id = keys
+///     .run(&client, &request_key, |trans| {
+///         Box::pin(async move {
+///             let mut order = DbState::new_uncreated(new_order);
+///             order.save(trans).await?;
+///             Ok(order.id)
+///         })
+///     })
+///     .await?
+///     .into_inner();
+/// ```
+pub struct IdempotencyKeys {
+    table: String,
+    expire_after: Option<Duration>,
+}
+
+impl Default for IdempotencyKeys {
+    fn default() -> Self {
+        Self {
+            table: "_welds_idempotency_keys".to_owned(),
+            expire_after: None,
+        }
+    }
+}
+
+impl IdempotencyKeys {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The table the keys are kept in. `_welds_idempotency_keys` by default
+    pub fn table(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    /// Forget keys after this long, so they can be used again. Keys are kept forever by default
+    pub fn expire_after(mut self, expire_after: Duration) -> Self {
+        self.expire_after = Some(expire_after);
+        self
+    }
+
+    /// Creates the keys table if it doesn't exist yet
+    pub async fn ensure_table(&self, client: &dyn Client) -> Result<()> {
+        let found = crate::detect::find_table(None::<String>, &self.table, client).await?;
+        if found.is_some() {
+            return Ok(());
+        }
+        let table = create_table(self.table.as_str())
+            .id(|c| c("id", Type::IntBig))
+            .column(|c| c("idempotency_key", Type::StringSized(255)).create_unique_index())
+            .column(|c| c("response", Type::Text))
+            .column(|c| c("created_at", Type::IntBig));
+        for sql in table.up_sql(client.syntax()) {
+            client.execute(&sql, &[]).await?;
+        }
+        Ok(())
+    }
+
+    /// Runs the work once for this key. See `IdempotencyKeys`
+    pub async fn run<C, R, F>(&self, client: &C, key: &str, work: F) -> Result<Idempotent<R>>
+    where
+        C: Client + TransactStart,
+        R: Serialize + DeserializeOwned,
+        F: for<'t> FnOnce(&'t dyn Client) -> Work<'t, R>,
+    {
+        if let Some(done) = self.completed(client, key).await? {
+            return Ok(Idempotent::Replayed(done));
+        }
+
+        let trans = client.begin().await?;
+        let value = match work(&trans).await {
+            Ok(value) => value,
+            Err(err) => {
+                trans.rollback().await?;
+                return Err(err);
+            }
+        };
+        let response = serde_json::to_string(&value).map_err(|e| WeldsError::Other(e.into()))?;
+
+        let recorded = match self.record(&trans, key, &response).await {
+            Ok(()) => trans.commit().await.map_err(WeldsError::from),
+            Err(err) => {
+                trans.rollback().await?;
+                Err(err)
+            }
+        };
+        match recorded {
+            Ok(()) => Ok(Idempotent::Completed(value)),
+            // lost the race for the key, hand back the response of whoever won it
+            Err(err) => match self.completed(client, key).await? {
+                Some(done) => Ok(Idempotent::Replayed(done)),
+                None => Err(err),
+            },
+        }
+    }
+
+    /// Deletes the keys that are past `expire_after`. Returns how many were removed
+    pub async fn purge_expired(&self, client: &dyn Client) -> Result<u64> {
+        let cutoff = match self.cutoff() {
+            Some(cutoff) => cutoff,
+            None => return Ok(0),
+        };
+        let next = NextParam::new(client.syntax());
+        let sql = format!(
+            "DELETE FROM {} WHERE created_at < {}",
+            self.table,
+            next.next()
+        );
+        let result = client.execute(&sql, &[&cutoff]).await?;
+        Ok(result.rows_affected())
+    }
+
+    // the recorded response for the key, if it has completed and hasn't expired
+    async fn completed<R>(&self, client: &dyn Client, key: &str) -> Result<Option<R>>
+    where
+        R: DeserializeOwned,
+    {
+        let next = NextParam::new(client.syntax());
+        let sql = format!(
+            "SELECT response, created_at FROM {} WHERE idempotency_key = {}",
+            self.table,
+            next.next()
+        );
+        let key = key.to_owned();
+        let mut rows = client.fetch_rows(&sql, &[&key]).await?;
+        let row = match rows.pop() {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+        let created_at: i64 = row.get("created_at")?;
+        if self.cutoff().map(|c| created_at < c).unwrap_or(false) {
+            return Ok(None);
+        }
+        let response: String = row.get("response")?;
+        let value = serde_json::from_str(&response).map_err(|e| WeldsError::Other(e.into()))?;
+        Ok(Some(value))
+    }
+
+    async fn record(&self, trans: &dyn Client, key: &str, response: &str) -> Result<()> {
+        let key = key.to_owned();
+        let response = response.to_owned();
+        let now = unixtime();
+
+        // an expired key is free to use again
+        if let Some(cutoff) = self.cutoff() {
+            let next = NextParam::new(trans.syntax());
+            let sql = format!(
+                "DELETE FROM {} WHERE idempotency_key = {} AND created_at < {}",
+                self.table,
+                next.next(),
+                next.next()
+            );
+            trans.execute(&sql, &[&key, &cutoff]).await?;
+        }
+
+        let next = NextParam::new(trans.syntax());
+        let sql = format!(
+            "INSERT INTO {} (idempotency_key, response, created_at) VALUES ({}, {}, {})",
+            self.table,
+            next.next(),
+            next.next(),
+            next.next()
+        );
+        trans.execute(&sql, &[&key, &response, &now]).await?;
+        Ok(())
+    }
+
+    fn cutoff(&self) -> Option<i64> {
+        let expire_after = self.expire_after?;
+        Some(unixtime() - expire_after.as_millis() as i64)
+    }
+}
+
+/// Runs the work once for this key, keeping the keys in the default table.
+/// See `IdempotencyKeys` for how duplicates are handled
+pub async fn with_idempotency_key<C, R, F>(client: &C, key: &str, work: F) -> Result<Idempotent<R>>
+where
+    C: Client + TransactStart,
+    R: Serialize + DeserializeOwned,
+    F: for<'t> FnOnce(&'t dyn Client) -> Work<'t, R>,
+{
+    IdempotencyKeys::new().run(client, key, work).await
+}
+
+fn unixtime() -> i64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() as i64,
+        Err(_) => panic!("System time is before the Unix epoch!"),
+    }
+}
+
+#[cfg(test)]
+mod tests;
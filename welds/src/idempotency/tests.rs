@@ -0,0 +1,73 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::Syntax;
+use futures::executor::block_on;
+
+fn charge(trans: &dyn Client) -> Work<'_, i64> {
+    Box::pin(async move {
+        trans
+            .execute("INSERT INTO charges (amount) VALUES (10)", &[])
+            .await?;
+        Ok(42)
+    })
+}
+
+#[test]
+fn should_record_the_key_with_the_work() {
+    let client = MockClient::new(Syntax::Postgres);
+
+    let done = block_on(with_idempotency_key(&client, "req-1", charge)).unwrap();
+
+    assert_eq!(done, Idempotent::Completed(42));
+    let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+    assert_eq!(
+        sql,
+        [
+            "SELECT response, created_at FROM _welds_idempotency_keys WHERE idempotency_key = $1",
+            "BEGIN",
+            "INSERT INTO charges (amount) VALUES (10)",
+            "INSERT INTO _welds_idempotency_keys (idempotency_key, response, created_at) VALUES ($1, $2, $3)",
+            "COMMIT",
+        ]
+    );
+}
+
+#[test]
+fn should_replay_a_completed_key() {
+    let client = MockClient::new(Syntax::Mysql);
+    let created_at = unixtime();
+    client.enqueue_rows(
+        "FROM _welds_idempotency_keys",
+        vec![MockRow::new()
+            .with("response", "42".to_string())
+            .with("created_at", created_at)],
+    );
+
+    let keys = IdempotencyKeys::new().expire_after(Duration::from_secs(60));
+    let done = block_on(keys.run(&client, "req-1", charge)).unwrap();
+
+    assert!(done.is_replay());
+    assert_eq!(done.into_inner(), 42);
+    assert_eq!(client.queries().len(), 1);
+}
+
+#[test]
+fn should_run_again_once_a_key_expires() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows(
+        "SELECT response",
+        vec![MockRow::new()
+            .with("response", "7".to_string())
+            .with("created_at", 0_i64)],
+    );
+
+    let keys = IdempotencyKeys::new()
+        .table("keys")
+        .expire_after(Duration::from_secs(60));
+    let done = block_on(keys.run(&client, "req-1", charge)).unwrap();
+
+    assert_eq!(done, Idempotent::Completed(42));
+    let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+    assert!(sql
+        .contains(&"DELETE FROM keys WHERE idempotency_key = $1 AND created_at < $2".to_string()));
+}
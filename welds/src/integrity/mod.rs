@@ -0,0 +1,251 @@
+use crate::errors::Result;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::quality::as_text;
+use crate::relations::{ForeignKey, ForeignKeys};
+use crate::writers::{in_namespace, table_alias, table_name, ColumnWriter, LimitSkipWriter};
+use crate::Syntax;
+use std::fmt;
+use welds_connections::Client;
+
+/// Finds orphaned foreign keys: rows whose `BelongsTo` parent row is missing.
+///
+/// Every `BelongsTo` relation of the registered models is scanned, a batch of rows at a time,
+/// and the orphans are collected into a `RepairPlan`. Nothing is changed until the plan is applied.
+///
+/// ```rust,ignore
+/// let plan = IntegrityChecker::new(&client)
+///     .model::<Order>()
+///     .model::<OrderLine>()
+///     .run()
+///     .await?;
+/// println!("{}", plan);
+/// plan.apply(&client).await?;
+/// ```
+pub struct IntegrityChecker<'c> {
+    client: &'c dyn Client,
+    batch_size: i64,
+    scans: Vec<Scan>,
+}
+
+// a single BelongsTo relation to scan
+struct Scan {
+    table: String,
+    primary_key: Option<String>,
+    nullable: bool,
+    foreign_key: ForeignKey,
+}
+
+impl<'c> IntegrityChecker<'c> {
+    pub fn new(client: &'c dyn Client) -> Self {
+        Self {
+            client,
+            batch_size: 1000,
+            scans: Vec::default(),
+        }
+    }
+
+    /// How many rows are read per query while scanning. Defaults to 1000
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1) as i64;
+        self
+    }
+
+    /// Adds all the `BelongsTo` relations of the model to the scan
+    pub fn model<T>(mut self) -> Self
+    where
+        T: HasSchema + ForeignKeys,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let table = <T::Schema as TableInfo>::identifier().join(".");
+        let columns = <T::Schema as TableColumns>::columns();
+        let pks = <T::Schema as TableColumns>::primary_keys();
+        let primary_key = match pks.as_slice() {
+            [pk] => Some(pk.name().to_owned()),
+            _ => None,
+        };
+        for foreign_key in T::foreign_keys() {
+            let nullable = columns
+                .iter()
+                .any(|c| c.name() == foreign_key.column && c.nullable());
+            self.scans.push(Scan {
+                table: table.clone(),
+                primary_key: primary_key.clone(),
+                nullable,
+                foreign_key,
+            });
+        }
+        self
+    }
+
+    /// Scans every registered relation, returning what it would take to repair them
+    pub async fn run(&self) -> Result<RepairPlan> {
+        let mut repairs = Vec::default();
+        for scan in &self.scans {
+            let orphans = self.find_orphans(scan).await?;
+            if orphans.is_empty() {
+                continue;
+            }
+            let action = match scan.nullable {
+                true => RepairAction::Nullify,
+                false => RepairAction::Delete,
+            };
+            repairs.push(Repair {
+                table: scan.table.clone(),
+                column: scan.foreign_key.column.clone(),
+                parent_table: scan.foreign_key.parent_table.clone(),
+                parent_column: scan.foreign_key.parent_column.clone(),
+                action,
+                orphans,
+            });
+        }
+        Ok(RepairPlan { repairs })
+    }
+
+    async fn find_orphans(&self, scan: &Scan) -> Result<Vec<String>> {
+        let syntax = self.client.syntax();
+        let writer = ColumnWriter::new(syntax);
+        // without a single primary key the orphans are reported by their foreign key
+        let key = scan
+            .primary_key
+            .as_deref()
+            .unwrap_or(&scan.foreign_key.column);
+        let (key, table, orphaned) = in_namespace(self.client, || {
+            let key = format!("{}.{}", table_alias(&scan.table), writer.excape(key));
+            let orphaned = orphaned_where(syntax, &scan.table, &scan.foreign_key);
+            (key, table_name(&scan.table), orphaned)
+        });
+        let limits = LimitSkipWriter::new(syntax);
+
+        let mut orphans = Vec::default();
+        let mut offset = 0;
+        loop {
+            let page = limits
+                .skiplimit(&Some(offset), &Some(self.batch_size))
+                .unwrap_or_default();
+            let sql = format!(
+                "SELECT {} FROM {} WHERE {} ORDER BY {} {}",
+                as_text(syntax, &key),
+                table,
+                orphaned,
+                key,
+                page
+            );
+            let rows = self.client.fetch_rows(sql.trim_end(), &[]).await?;
+            let count = rows.len() as i64;
+            for row in rows {
+                let key: Option<String> = row.get_by_position(0)?;
+                orphans.extend(key);
+            }
+            if count < self.batch_size {
+                return Ok(orphans);
+            }
+            offset += self.batch_size;
+        }
+    }
+}
+
+// the rows whose foreign key is set, but doesn't point at a parent
+fn orphaned_where(syntax: Syntax, table: &str, fk: &ForeignKey) -> String {
+    let writer = ColumnWriter::new(syntax);
+    let column = format!("{}.{}", table_alias(table), writer.excape(&fk.column));
+    format!(
+        "{} IS NOT NULL AND NOT EXISTS (SELECT 1 FROM {} welds_parent WHERE welds_parent.{} = {})",
+        column,
+        table_name(&fk.parent_table),
+        writer.excape(&fk.parent_column),
+        column
+    )
+}
+
+/// The orphans found by an `IntegrityChecker`, and how to fix them
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairPlan {
+    pub repairs: Vec<Repair>,
+}
+
+/// The orphans of a single relation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repair {
+    pub table: String,
+    pub column: String,
+    pub parent_table: String,
+    pub parent_column: String,
+    pub action: RepairAction,
+    /// The primary keys of the orphaned rows, as text.
+    /// The foreign keys if the table doesn't have a single primary key
+    pub orphans: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairAction {
+    /// Set the foreign key to NULL, used when the column is nullable
+    Nullify,
+    /// Delete the orphaned rows
+    Delete,
+}
+
+impl Repair {
+    /// The statement that repairs this relation
+    pub fn to_sql(&self, syntax: Syntax) -> String {
+        let fk = ForeignKey {
+            column: self.column.clone(),
+            parent_table: self.parent_table.clone(),
+            parent_column: self.parent_column.clone(),
+        };
+        let orphaned = orphaned_where(syntax, &self.table, &fk);
+        let table = table_name(&self.table);
+        match self.action {
+            RepairAction::Nullify => {
+                let column = ColumnWriter::new(syntax).excape(&self.column);
+                format!("UPDATE {} SET {} = NULL WHERE {}", table, column, orphaned)
+            }
+            RepairAction::Delete => format!("DELETE FROM {} WHERE {}", table, orphaned),
+        }
+    }
+}
+
+impl RepairPlan {
+    /// True if no orphans were found
+    pub fn is_empty(&self) -> bool {
+        self.repairs.is_empty()
+    }
+
+    /// Runs the repairs, returning the number of rows changed.
+    ///
+    /// Repairs are made by the same condition the scan used, so rows orphaned since the scan
+    /// are repaired as well.
+    pub async fn apply(&self, client: &dyn Client) -> Result<u64> {
+        let syntax = client.syntax();
+        let mut changed = 0;
+        for repair in &self.repairs {
+            let sql = in_namespace(client, || repair.to_sql(syntax));
+            changed += client.execute(&sql, &[]).await?.rows_affected();
+        }
+        Ok(changed)
+    }
+}
+
+impl fmt::Display for RepairPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for repair in &self.repairs {
+            let action = match repair.action {
+                RepairAction::Nullify => "nullify",
+                RepairAction::Delete => "delete",
+            };
+            writeln!(
+                f,
+                "{}.{} -> {}.{}: {} {} orphaned row(s)",
+                repair.table,
+                repair.column,
+                repair.parent_table,
+                repair.parent_column,
+                action,
+                repair.orphans.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
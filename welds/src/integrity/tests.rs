@@ -0,0 +1,111 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "products")]
+#[welds(HasMany(orders, Order, "product_id"))]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "orders")]
+#[welds(BelongsTo(product, Product, "product_id"))]
+#[welds(BelongsTo(line, Line, "line_id"))]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub product_id: Option<i32>,
+    pub line_id: i32,
+}
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "lines")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Line {
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+fn keys(keys: &[&str]) -> Vec<MockRow> {
+    keys.iter()
+        .map(|k| MockRow::new().with("key", *k))
+        .collect()
+}
+
+#[test]
+fn should_list_the_belongs_to_relations_of_a_model() {
+    assert_eq!(
+        Order::foreign_keys(),
+        vec![
+            ForeignKey {
+                column: "product_id".to_string(),
+                parent_table: "products".to_string(),
+                parent_column: "id".to_string(),
+            },
+            ForeignKey {
+                column: "line_id".to_string(),
+                parent_table: "lines".to_string(),
+                parent_column: "id".to_string(),
+            },
+        ]
+    );
+    assert!(Product::foreign_keys().is_empty());
+}
+
+#[test]
+fn should_scan_in_batches_and_plan_repairs() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("OFFSET 0 LIMIT 2", keys(&["1", "2"]));
+    client.enqueue_rows("OFFSET 2 LIMIT 2", keys(&["5"]));
+
+    let checker = IntegrityChecker::new(&client)
+        .batch_size(2)
+        .model::<Order>();
+    let plan = block_on(checker.run()).unwrap();
+
+    assert_eq!(
+        client.queries()[0].sql,
+        r#"SELECT CAST(orders."id" AS TEXT) FROM orders WHERE orders."product_id" IS NOT NULL AND NOT EXISTS (SELECT 1 FROM products welds_parent WHERE welds_parent."id" = orders."product_id") ORDER BY orders."id" OFFSET 0 LIMIT 2"#
+    );
+    // two batches for product_id, one for line_id
+    assert_eq!(client.queries().len(), 3);
+    assert_eq!(
+        plan.repairs,
+        vec![Repair {
+            table: "orders".to_string(),
+            column: "product_id".to_string(),
+            parent_table: "products".to_string(),
+            parent_column: "id".to_string(),
+            action: RepairAction::Nullify,
+            orphans: vec!["1".to_string(), "2".to_string(), "5".to_string()],
+        }]
+    );
+}
+
+#[test]
+fn should_delete_orphans_that_cant_be_nullified() {
+    let client = MockClient::new(Syntax::Mysql);
+    client.enqueue_rows("FROM lines", keys(&["9"]));
+    client.enqueue_rows_affected("DELETE FROM orders", 1);
+
+    let plan = block_on(IntegrityChecker::new(&client).model::<Order>().run()).unwrap();
+    assert_eq!(plan.repairs.len(), 1);
+    assert_eq!(plan.repairs[0].action, RepairAction::Delete);
+    assert_eq!(
+        plan.to_string(),
+        "orders.line_id -> lines.id: delete 1 orphaned row(s)\n"
+    );
+
+    let changed = block_on(plan.apply(&client)).unwrap();
+    assert_eq!(changed, 1);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "DELETE FROM orders WHERE orders.line_id IS NOT NULL AND NOT EXISTS (SELECT 1 FROM lines welds_parent WHERE welds_parent.id = orders.line_id)"
+    );
+}
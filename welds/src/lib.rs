@@ -25,6 +25,41 @@
 //! ```rust,ignore
 //! #[welds(readonly)]
 //! ```
+//! With the `graphql` feature, add graphql to generate async-graphql object and input types for the model
+//! ```rust,ignore
+//! #[welds(graphql)]
+//! ```
+//!
+//! ## Scopes
+//! Name the filters you use over and over with `scopes`. Write them on the `UserScopes` struct
+//! welds adds, they become methods of any query of users once `UserScopesExt` is in scope
+//! ```rust,ignore
+//! #[welds(scopes(active, admins))]
+//! struct User { ... }
+//!
+//! impl UserScopes {
+//!     fn active(q: QueryBuilder<User>) -> QueryBuilder<User> {
+//!         q.where_col(|u| u.active.equal(true))
+//!     }
+//!     fn admins(q: QueryBuilder<User>) -> QueryBuilder<User> {
+//!         q.where_col(|u| u.role.equal("admin"))
+//!     }
+//! }
+//!
+//! let admins = User::all().active().admins().run(&client).await?;
+//! ```
+//!
+//! A `default_scope` is a filter written into every query, update and delete of the model.
+//! It is called each time a query is started, so it can read the current tenant.
+//! `.unscoped()` leaves it out of a query
+//! ```rust,ignore
+//! #[welds(default_scope(current_tenant))]
+//! struct Account { ... }
+//!
+//! fn current_tenant(a: AccountSchema) -> Box<dyn ClauseAdder> {
+//!     a.tenant_id.equal(tenant::current())
+//! }
+//! ```
 //!
 //! ## Build Relations for Joining
 //! You can write queries that join across tables if you Wireup welds with relationships
@@ -32,12 +67,14 @@
 //! Welds Supports:
 //! - BelongsTo
 //! - HasMany
+//! - HasOne
+//! - BelongsToMany
 //!
-//! They are both in the format:
+//! `BelongsTo`, `HasMany` and `HasOne` are in the format:
 //! ```rust,ignore
 //! [field, rust_path_to_other_object, "str_of_foreign_key_column"]
 //! ```
-//! NOTE: Both `BelongsTo` and `HasMany` need to know the foreign_key column
+//! NOTE: All of them need to know the foreign_key column
 //!
 //! If you are working on a struct that has a foreign_key to another table use a `BelongsTo`
 //!
@@ -49,6 +86,13 @@
 //! }
 //! ```
 //!
+//! Add `touch` to a `BelongsTo` to set the parent's `updated_at` to the current time whenever the
+//! struct is saved or deleted (`touch = "column"` for another column). The parents are updated
+//! in one batch of statements right after the row
+//! ```rust,ignore
+//! #[welds(BelongsTo(product, super::product::Product, "product_id", touch))]
+//! ```
+//!
 //! If you are working on a struct that has a foreign_key pointed at it, use a `HasMany`
 //! ```rust,ignore
 //! #[welds(HasMany(orders, super::order::Order, "product_id"))]
@@ -57,7 +101,39 @@
 //! }
 //! ```
 //!
-//! If working with a many-to-many relationship, add a `HasMany` on both structs, and add two `BelongsTo`
+//! A `HasMany` can keep a count of its rows in a column with `counter_cache`. It is counted up
+//! when a row is created and down when one is deleted, with the client the row was saved with;
+//! save in a transaction to change both together. The rows need their `BelongsTo` back
+//! ```rust,ignore
+//! #[welds(HasMany(orders, super::order::Order, "product_id", counter_cache = "orders_count"))]
+//! struct Product {
+//!     orders_count: i32,
+//!     ...,
+//! }
+//! ```
+//!
+//! If at most one row points at your struct, use a `HasOne`. It is read as an `Option` instead of a `Vec`
+//! ```rust,ignore
+//! #[welds(HasOne(profile, super::profile::Profile, "user_id"))]
+//! struct User {
+//!     ...,
+//! }
+//! ```
+//!
+//! A many-to-many relationship can go through its join table with a `BelongsToMany`.
+//! The columns of the join table default to the lowercased struct names with `_id`
+//! (`user_id` and `role_id` here). `attach_roles`, `detach_roles` and `sync_roles` are written
+//! for you to add and remove its rows
+//! ```rust,ignore
+//! #[welds(BelongsToMany(roles, super::role::Role, through = "roles_users"))]
+//! // or with the columns of the join table
+//! #[welds(BelongsToMany(roles, super::role::Role, through = "roles_users", foreign_key = "user_id", related_key = "role_id"))]
+//! struct User {
+//!     ...,
+//! }
+//! ```
+//!
+//! The join table can also be a model of its own: add a `HasMany` on both structs, and add two `BelongsTo`
 //! on the join table struct
 //! ```rust,ignore
 //! #[welds(BelongsTo(order, super::order::Order, "order_id"))]
@@ -70,11 +146,52 @@
 //! }
 //! ```
 //!
+//! A foreign key over several columns lists all of them, the foreign key last.
+//! The columns before it have the same name in both tables
+//! ```rust,ignore
+//! #[welds(BelongsTo(order, super::order::Order, "tenant_id", "order_id"))]
+//! struct Item {
+//!     tenant_id: i32,
+//!     order_id: i32,
+//!     ...,
+//! }
+//! ```
+//!
+//! A relation can point back at the same struct, for a tree of rows. Use `Self` or the struct's name
+//! ```rust,ignore
+//! #[welds(BelongsTo(parent, Self, "parent_id"))]
+//! #[welds(HasMany(children, Self, "parent_id"))]
+//! struct Category {
+//!     parent_id: Option<i32>,
+//!     ...,
+//! }
+//! ```
+//! A `BelongsToMany` from a struct to itself needs both `foreign_key` and `related_key`,
+//! the defaults would be the same column
+//!
+//! The related rows can be fetched, created and linked from a `DbState`, the keys are copied for you
+//! ```rust,ignore
+//! let orders = customer.fetch_related(|c| c.orders, &client).await?;
+//! let item = order.create_related(|o| o.items, item, &client).await?;
+//! user.set_related(|u| u.team, &team, &client).await?;
+//! ```
+//!
 //! ## Fields Level Attributes
 //! There are a couple attributes you can add to your fields to help control how welds functions
 //! - `#[welds(primary_key)]` Important! Add this to the primary key of your table.
 //! - `#[welds(rename = "xyz")]` let welds know the underlying column has a different name than the field
 //! - `#[welds(ignore)]` Tell welds this fields it not in the database.
+//! - `#[welds(mapping_field = "xyz")]` the field has a different name on the mapped struct (see below)
+//! - `#[welds(mapping_skip)]` the field is not on the mapped struct (see below)
+//!
+//! ## Mapping to other structs (protobuf / avro)
+//! If your service speaks protobuf (prost) or avro, welds can write the conversions between your
+//! model and the generated struct. Fields are converted with `Into` and `TryInto`,
+//! fields missing from the model are left as their default.
+//! ```rust,ignore
+//! #[welds(mapping(pb::Product))]
+//! ```
+//! This adds `From<Product> for pb::Product` and `TryFrom<pb::Product> for Product`
 //!
 //!
 //! ## Putting it all together
@@ -102,6 +219,8 @@
 //! - [`Model::all()`](./query/builder/struct.QueryBuilder.html) start a query for a Model
 //! - [`Model::where_col()`](./query/builder/struct.QueryBuilder.html) start a query for a Model
 //! - `Model::from_raw_sql()` finds Model using raw custom SQL str
+//! - `Model::find_by_sql()` runs your own SQL with params and reads the rows into Model
+//! - [`Model::column_stats()`](./stats/struct.ColumnStats.html) null fraction, distinct count and min/max of columns
 //!
 //! Instances of your model are wrapped in a [welds::state::DbState](./state/struct.DbState.html).
 //! From your instance you can update, create, and delete.
@@ -135,9 +254,19 @@
 //! - mysql - enables MySql database connection. (requires sqlx setup)
 //! - sqlite - enables Sqlite database connection. (requires sqlx setup)
 //! - mssql - enables Microsoft SQL support. (requires tokio runtime.)
+//! - odbc - enables connecting to any database with an ODBC driver. (requires tokio runtime.)
+//! - tracing - emits a `tracing` span for every statement sent to the database
 //! - detect - enables scanning of the database to get schema info
 //! - check - enables checking your models against table in the database
-//! - migrations - adds all the migration structs and traits
+//! - migrations - adds all the migration structs and traits, and `bootstrap` for service startup
+//! - json - apply partial JSON documents (HTTP PATCH) onto models. (models need serde)
+//!   Also `query::filter`, for building a query from filters sent as JSON, and `QueryDefinition` for storing a query to run later.
+//!   With migrations, also `idempotency` keys for writes that are safe to retry.
+//!   Also `cache::CacheCodec`, for storing models in a cache that notices when the model changes
+//! - openapi - generate JSON Schema / OpenAPI component schemas from models
+//! - export - `Exporter` for fast full-table dumps in concurrent chunks
+//! - arrow - fetch query results as Arrow RecordBatches
+//! - graphql - derive async-graphql types for models marked with `#[welds(graphql)]`
 //! - full - all the features excluding (mock)
 //! - mock - Use for testing ONLY. Enables mocking out database schemas
 //!
@@ -147,12 +276,21 @@
 //! - If you are using one of the `sqlx` connections, you will need to setup sqlx. This is so you can pick an async runtime.
 //!
 
+#[cfg(feature = "json")]
+pub mod cache;
 pub mod errors;
+#[cfg(any(feature = "json", feature = "migrations"))]
+mod fnv;
 pub use errors::WeldsError;
+pub mod integrity;
 pub mod model_traits;
+pub mod n_plus_one;
+pub mod quality;
 pub mod query;
 pub mod relations;
 pub mod state;
+pub mod stats;
+pub mod write_behind;
 pub mod writers;
 
 pub mod prelude;
@@ -166,6 +304,26 @@ pub mod check;
 #[cfg(feature = "migrations")]
 pub mod migrations;
 
+#[cfg(feature = "migrations")]
+pub mod bootstrap;
+#[cfg(feature = "migrations")]
+pub use bootstrap::bootstrap;
+
+#[cfg(all(feature = "migrations", feature = "json"))]
+pub mod idempotency;
+
+#[cfg(feature = "openapi")]
+pub mod openapi;
+
+#[cfg(feature = "graphql")]
+pub mod graphql;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
+#[cfg(feature = "export")]
+pub mod export;
+
 pub use welds_connections as connections;
 
 /// Re-export welds_connections
@@ -173,3 +331,6 @@ pub use welds_connections::{Client, Row, Syntax, TransactStart};
 
 /// Re-export the Macro used to make models
 pub use welds_macros::WeldsModel;
+
+/// Re-export the Macro used to read query results into structs that aren't models
+pub use welds_macros::WeldsProjection;
@@ -20,6 +20,12 @@ fn should_create_basic_table() {
     CREATE TABLE s1.MyTable ( id SERIAL PRIMARY KEY, name TEXT NOT NULL )"#;
     assert_eq!(sql2, expected.trim());
 
+    //cockroachdb
+    let sql2 = MigrationWriter::up_sql(&m, Syntax::CockroachDb).join("; ");
+    let expected = r#"
+    CREATE TABLE s1.MyTable ( id INT4 GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY, name TEXT NOT NULL )"#;
+    assert_eq!(sql2, expected.trim());
+
     //mysql
     let sql = MigrationWriter::up_sql(&m, Syntax::Mssql).join("; ");
     let expected = r#"
@@ -21,11 +21,13 @@ pub fn write_up(
 
     match syntax {
         Syntax::Sqlite => sqlite_writer::up_sql(table, current_col, colname, ty, nullable),
-        Syntax::Postgres => pg_writer::up_sql(table, column, colname, ty, nullable),
+        Syntax::Postgres | Syntax::CockroachDb => {
+            pg_writer::up_sql(table, column, colname, ty, nullable)
+        }
         Syntax::Mssql => vec![format!(
             "ALTER TABLE {tablename} ALTER COLUMN {colname} {ty} {null}"
         )],
-        Syntax::Mysql => vec![format!(
+        Syntax::Mysql | Syntax::MariaDb => vec![format!(
             "ALTER TABLE {tablename} MODIFY COLUMN {colname} {ty} {null}"
         )],
     }
@@ -49,11 +51,13 @@ pub fn write_down(
 
     match syntax {
         Syntax::Sqlite => sqlite_writer::down_sql(table, current_col, colname, ty, nullable),
-        Syntax::Postgres => pg_writer::down_sql(table, column, colname, ty, nullable),
+        Syntax::Postgres | Syntax::CockroachDb => {
+            pg_writer::down_sql(table, column, colname, ty, nullable)
+        }
         Syntax::Mssql => vec![format!(
             "ALTER TABLE {tablename} ALTER COLUMN {colname} {ty} {null}"
         )],
-        Syntax::Mysql => vec![format!(
+        Syntax::Mysql | Syntax::MariaDb => vec![format!(
             "ALTER TABLE {tablename} MODIFY COLUMN {colname} {ty} {null}"
         )],
     }
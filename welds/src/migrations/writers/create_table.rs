@@ -69,8 +69,9 @@ fn build_id_column(syntax: Syntax, col: &IdBuilder) -> String {
     if col.ty == Type::Int || col.ty == Type::IntSmall || col.ty == Type::IntBig {
         tail = match syntax {
             Syntax::Mssql => "IDENTITY(1,1) PRIMARY KEY",
-            Syntax::Mysql => "AUTO_INCREMENT PRIMARY KEY",
+            Syntax::Mysql | Syntax::MariaDb => "AUTO_INCREMENT PRIMARY KEY",
             Syntax::Postgres => "PRIMARY KEY",
+            Syntax::CockroachDb => "GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY",
             Syntax::Sqlite => "PRIMARY KEY AUTOINCREMENT",
         }
     }
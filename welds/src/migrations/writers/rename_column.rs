@@ -14,7 +14,9 @@ pub fn write(
 
     match syntax {
         Syntax::Mssql => format!("EXEC sp_rename '{tablename}.{oldname}', '{newname}', 'COLUMN'"),
-        Syntax::Mysql => format!("ALTER TABLE {tablename} RENAME COLUMN {oldname} TO {newname}"),
+        Syntax::Mysql | Syntax::MariaDb => {
+            format!("ALTER TABLE {tablename} RENAME COLUMN {oldname} TO {newname}")
+        }
         _ => format!("ALTER TABLE {tablename} RENAME {oldname} TO {newname}"),
     }
 }
@@ -0,0 +1,56 @@
+use crate::errors::Result;
+use std::any::Any;
+use welds_connections::Param;
+
+/// Reads and sets the key columns of a model by their name, so the rows of a relationship can be
+/// linked without copying their keys by hand. Written for you by `WeldsModel`
+pub trait KeyColumns {
+    /// A copy of the value of a primary key, or of a column named by one of the model's relations.
+    /// None for any other column
+    fn key_value(&self, column: &str) -> Option<KeyValue>;
+
+    /// Sets a column to a value read from a related model.
+    /// Errors if there is no such column, or the value isn't of its type
+    fn set_key(&mut self, column: &str, value: KeyValue) -> Result<()>;
+}
+
+/// The value of a key column, read with `KeyColumns::key_value`
+pub struct KeyValue {
+    value: Box<dyn Any + Send + Sync>,
+    param: Box<dyn Param + Send + Sync>,
+}
+
+impl KeyValue {
+    pub fn new<V>(value: V) -> Self
+    where
+        V: Param + Clone + Send + Sync + 'static,
+    {
+        Self {
+            value: Box::new(value.clone()),
+            param: Box::new(value),
+        }
+    }
+
+    /// The value for a column of `V`s. A nullable value is read as the value it holds,
+    /// None if it is NULL or isn't a `V`
+    pub fn into_value<V: 'static>(self) -> Option<V> {
+        let value = match self.value.downcast::<V>() {
+            Ok(value) => return Some(*value),
+            Err(value) => value,
+        };
+        value.downcast::<Option<V>>().ok().and_then(|value| *value)
+    }
+
+    /// The value for a nullable column of `V`s, None if it isn't a `V`
+    pub fn into_option<V: 'static>(self) -> Option<Option<V>> {
+        let value = match self.value.downcast::<Option<V>>() {
+            Ok(value) => return Some(*value),
+            Err(value) => value,
+        };
+        value.downcast::<V>().ok().map(|value| Some(*value))
+    }
+
+    pub(crate) fn into_param(self) -> Box<dyn Param + Send + Sync> {
+        self.param
+    }
+}
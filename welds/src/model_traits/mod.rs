@@ -8,6 +8,35 @@ pub mod hooks;
 pub trait TableInfo {
     /// the unique name (schema + tablename) that identities this database object
     fn identifier() -> &'static [&'static str];
+
+    /// The column set to the time a row is deleted, when the model is soft deleted.
+    /// Rows with it set are left out of queries
+    fn soft_delete_column() -> Option<&'static str> {
+        None
+    }
+
+    /// The filter every query, update and delete of the model is written with,
+    /// set with `#[welds(default_scope(..))]`
+    fn default_scope() -> Option<Box<dyn crate::query::clause::ClauseAdder>> {
+        None
+    }
+
+    /// The `counter_cache` columns of this table, each counting the rows of a child table
+    fn counter_caches() -> Vec<crate::relations::CounterCache> {
+        Vec::new()
+    }
+
+    /// The counters of the parents this table's rows point at,
+    /// counted up and down as rows are inserted and deleted
+    fn parent_counters() -> Vec<crate::relations::ParentCounter> {
+        Vec::new()
+    }
+
+    /// The timestamps of the parents this table's rows point at, set to the current time
+    /// as rows are saved and deleted
+    fn touches() -> Vec<crate::relations::Touch> {
+        Vec::new()
+    }
 }
 
 /// The db column name to use for a field
@@ -49,6 +78,18 @@ pub trait TableColumns {
     // Used to identify models that have N columns in their primary_key
     fn primary_keys() -> Vec<Column>;
     fn columns() -> Vec<Column>;
+    /// The name of the db column a field on the model is stored in
+    fn field_column(field: &str) -> Option<String> {
+        Self::columns()
+            .into_iter()
+            .find(|c| c.name() == field)
+            .map(|c| c.name)
+    }
+    /// The column marked `#[welds(version)]`. An update only writes over the version of the row
+    /// that was read, and counts it up
+    fn version_column() -> Option<&'static str> {
+        None
+    }
 }
 
 /// If the model can be uniquely identifed by a single column,
@@ -80,5 +121,12 @@ pub trait HasSchema: Sync + Send {
     type Schema: Default + TableInfo;
 }
 
+/// A model that reads some of the columns of another model's table.
+/// Derived with `#[welds(partial_of(User))]`, queried with `QueryBuilder::select_only`
+pub trait PartialOf<T>: HasSchema {}
+
 mod tableident;
 pub use tableident::TableIdent;
+
+mod key_columns;
+pub use key_columns::{KeyColumns, KeyValue};
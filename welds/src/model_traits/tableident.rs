@@ -74,9 +74,9 @@ impl TableIdent {
     pub fn default_namespace(syntax: Syntax) -> Option<&'static str> {
         match syntax {
             Syntax::Mssql => Some("dbo"),
-            Syntax::Postgres => Some("public"),
+            Syntax::Postgres | Syntax::CockroachDb => Some("public"),
             // NOTE if schema is left out, the mysql query uses the name of the db in the connection
-            Syntax::Mysql => None,
+            Syntax::Mysql | Syntax::MariaDb => None,
             Syntax::Sqlite => None,
         }
     }
@@ -0,0 +1,218 @@
+use std::backtrace::Backtrace;
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use welds_connections::instrument::{add_query_hook, QueryHook};
+use welds_connections::value::{self, Value};
+use welds_connections::Param;
+
+/// Spots N+1 queries: the same SELECT being run over and over with different params,
+/// usually a lookup of related rows made once for each row of a result.
+///
+/// It is a `QueryHook`. `install` adds it to every client in debug builds, or add it to a
+/// single client with `with_query_hook`. A detection is logged (at WARN) with a backtrace
+/// to the loop that ran it, or panics when turned on with `panic_on_detect`.
+///
+/// ```rust,ignore
+/// let detector = NPlusOneDetector::new().threshold(5).install();
+///
+/// for order in Order::all().run(&client).await? {
+///     // runs one SELECT per order, reported on the 5th
+///     let customer = Customer::find_by_id(&client, order.customer_id).await?;
+/// }
+/// assert!(detector.detections().is_empty());
+/// ```
+pub struct NPlusOneDetector {
+    threshold: usize,
+    window: Duration,
+    panic: bool,
+    seen: Mutex<HashMap<String, Seen>>,
+    detections: Mutex<Vec<Detection>>,
+}
+
+// the runs of one statement since `since`
+struct Seen {
+    since: Instant,
+    params: HashSet<u64>,
+    // runs with params whose values can't be read, each is counted as different
+    opaque: usize,
+    reported: bool,
+}
+
+/// A statement that was reported as an N+1 query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+    pub sql: String,
+    /// How many times it ran, with different params, before it was reported
+    pub count: usize,
+    /// The table it reads, when it could be found in the SQL
+    pub table: Option<String>,
+    /// The column it looks the rows up by, when it could be found in the SQL
+    pub column: Option<String>,
+}
+
+impl Default for NPlusOneDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NPlusOneDetector {
+    /// Reports a SELECT that runs 10 times within a second
+    pub fn new() -> Self {
+        Self {
+            threshold: 10,
+            window: Duration::from_secs(1),
+            panic: false,
+            seen: Mutex::default(),
+            detections: Mutex::default(),
+        }
+    }
+
+    /// How many times the statement has to run, each with different params, to be reported.
+    /// When a param's value can't be read every run counts, the params can't be compared
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold.max(2);
+        self
+    }
+
+    /// How close together the runs have to be. The count starts over after this
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Panic instead of logging, to fail the test that ran the N+1 query
+    pub fn panic_on_detect(mut self) -> Self {
+        self.panic = true;
+        self
+    }
+
+    /// Adds the detector to every client in the process (`add_query_hook`).
+    /// Only in debug builds, release builds don't pay for the tracking
+    pub fn install(self) -> Arc<Self> {
+        let detector = Arc::new(self);
+        if cfg!(debug_assertions) {
+            add_query_hook(detector.clone());
+        }
+        detector
+    }
+
+    /// The statements that have been reported so far
+    pub fn detections(&self) -> Vec<Detection> {
+        self.detections.lock().unwrap().clone()
+    }
+
+    // Counts a run of the statement, returning the count when it should be reported.
+    // `params` is a hash of the values of its params, None when they can't all be read
+    fn observe(&self, sql: &str, params: Option<u64>) -> Option<usize> {
+        let now = Instant::now();
+        let mut seen = self.seen.lock().unwrap();
+        // forget the statements that haven't run in a while
+        if seen.len() > 1024 {
+            seen.retain(|_, s| now.duration_since(s.since) <= self.window);
+        }
+        let entry = seen.entry(sql.to_owned()).or_insert_with(|| Seen {
+            since: now,
+            params: HashSet::default(),
+            opaque: 0,
+            reported: false,
+        });
+        if now.duration_since(entry.since) > self.window {
+            entry.since = now;
+            entry.params.clear();
+            entry.opaque = 0;
+            entry.reported = false;
+        }
+        match params {
+            Some(params) => {
+                entry.params.insert(params);
+            }
+            None => entry.opaque += 1,
+        }
+        let count = entry.params.len() + entry.opaque;
+        if entry.reported || count < self.threshold {
+            return None;
+        }
+        entry.reported = true;
+        Some(count)
+    }
+
+    fn report(&self, sql: &str, count: usize) {
+        let (table, column) = match lookup_of(sql) {
+            Some((table, column)) => (Some(table), Some(column)),
+            None => (None, None),
+        };
+        let hint = match (&table, &column) {
+            (Some(table), Some(column)) => format!(
+                " It looks up `{}` by `{}` one row at a time, load them for all the rows in one query with `include` instead.",
+                table, column
+            ),
+            _ => String::default(),
+        };
+        let message = format!(
+            "Possible N+1 query: the same SELECT ran {} times with different params within {:?}.{}\n  {}\n{}",
+            count,
+            self.window,
+            hint,
+            sql,
+            Backtrace::capture()
+        );
+        self.detections.lock().unwrap().push(Detection {
+            sql: sql.to_owned(),
+            count,
+            table,
+            column,
+        });
+        if self.panic {
+            panic!("{}", message);
+        }
+        log::warn!("{}", message);
+    }
+}
+
+impl QueryHook for NPlusOneDetector {
+    fn before_query(&self, sql: &str, params: &[&(dyn Param + Sync + Send)]) {
+        // a per-row lookup is a SELECT with the key of the row as a param
+        let select = sql.trim_start().get(..6);
+        let select = select.is_some_and(|s| s.eq_ignore_ascii_case("SELECT"));
+        if !select || params.is_empty() {
+            return;
+        }
+        if let Some(count) = self.observe(sql, params_hash(params)) {
+            self.report(sql, count);
+        }
+    }
+}
+
+// A hash of the values of the params. None if one of them can't be read, a run with it can
+// only be told apart by the SQL
+fn params_hash(params: &[&(dyn Param + Sync + Send)]) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    for param in params {
+        match value::of_param(*param) {
+            Value::Opaque(_) => return None,
+            value => format!("{:?}", value).hash(&mut hasher),
+        }
+    }
+    Some(hasher.finish())
+}
+
+// The table and column of a welds lookup: `... FROM orders t1 WHERE ( t1.customer_id = $1 ...`
+fn lookup_of(sql: &str) -> Option<(String, String)> {
+    let (_, from) = sql.split_once(" FROM ")?;
+    let mut parts = from.split_whitespace();
+    let table = parts.next()?;
+    let alias = parts.next()?;
+    let (_, wheres) = from.split_once(" WHERE ")?;
+    let first = wheres
+        .trim_start_matches(['(', ' '])
+        .split_whitespace()
+        .next()?;
+    let column = first.strip_prefix(alias)?.strip_prefix('.')?;
+    Some((table.to_owned(), column.trim_matches('"').to_owned()))
+}
+
+#[cfg(test)]
+mod tests;
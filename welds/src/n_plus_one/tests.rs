@@ -0,0 +1,114 @@
+use super::*;
+use crate::connections::mock::MockClient;
+use crate::{Syntax, WeldsModel};
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "customers")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Customer {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub customer_id: i32,
+}
+
+fn client(detector: &Arc<NPlusOneDetector>) -> MockClient {
+    MockClient::new(Syntax::Postgres).with_query_hook(detector.clone())
+}
+
+#[test]
+fn should_report_a_lookup_run_once_per_row() {
+    block_on(async {
+        let detector = Arc::new(NPlusOneDetector::new().threshold(3));
+        let client = client(&detector);
+        for customer_id in 1..=5 {
+            Order::where_col(|o| o.customer_id.equal(customer_id))
+                .run(&client)
+                .await
+                .unwrap();
+        }
+        let detections = detector.detections();
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].count, 3);
+        assert_eq!(detections[0].table.as_deref(), Some("orders"));
+        assert_eq!(detections[0].column.as_deref(), Some("customer_id"));
+    });
+}
+
+#[test]
+fn should_not_report_the_same_query_run_again() {
+    block_on(async {
+        let detector = Arc::new(NPlusOneDetector::new().threshold(3));
+        let client = client(&detector);
+        for _ in 0..5 {
+            Customer::where_col(|c| c.id.equal(1))
+                .run(&client)
+                .await
+                .unwrap();
+        }
+        Customer::all().run(&client).await.unwrap();
+        assert!(detector.detections().is_empty());
+    });
+}
+
+#[test]
+fn should_start_counting_over_after_the_window() {
+    block_on(async {
+        let detector = NPlusOneDetector::new().threshold(2).window(Duration::ZERO);
+        let detector = Arc::new(detector);
+        let client = client(&detector);
+        for id in 1..=4 {
+            std::thread::sleep(Duration::from_millis(1));
+            Customer::where_col(|c| c.id.equal(id))
+                .run(&client)
+                .await
+                .unwrap();
+        }
+        assert!(detector.detections().is_empty());
+    });
+}
+
+#[test]
+fn should_panic_when_asked_to() {
+    let detector = Arc::new(NPlusOneDetector::new().threshold(2).panic_on_detect());
+    let client = client(&detector);
+    let run = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        block_on(async {
+            for id in 1..=2 {
+                let q = Customer::where_col(|c| c.id.equal(id));
+                q.run(&client).await.unwrap();
+            }
+        })
+    }));
+    assert!(run.is_err());
+}
+
+#[test]
+fn should_find_the_table_and_column_of_a_lookup() {
+    let sql = r#"SELECT t1."id", t1."name" FROM app.customers t1 WHERE ( t1.id = $1 )"#;
+    let found = lookup_of(sql);
+    assert_eq!(found, Some(("app.customers".to_owned(), "id".to_owned())));
+    assert_eq!(lookup_of("SELECT 1"), None);
+}
+
+#[test]
+fn should_count_every_run_with_params_that_cant_be_read() {
+    let detector = NPlusOneDetector::new().threshold(3);
+    // a param type a `Value` can't hold
+    let id: Option<Option<i32>> = Some(Some(1));
+    assert_eq!(params_hash(&[&id]), None);
+
+    let sql = "SELECT * FROM orders t1 WHERE ( t1.customer_id = $1 )";
+    assert_eq!(detector.observe(sql, params_hash(&[&id])), None);
+    assert_eq!(detector.observe(sql, params_hash(&[&id])), None);
+    assert_eq!(detector.observe(sql, params_hash(&[&id])), Some(3));
+}
@@ -0,0 +1,110 @@
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use serde_json::{json, Map, Value};
+
+/// Returns the JSON Schema of a model.
+///
+/// There is a property for each column using the name of the column in the database.
+/// Fields marked `#[welds(ignore)]` are left out. Nullable columns allow `null`,
+/// all others are required. The primary key is marked `readOnly`.
+///
+/// The schema is the JSON Schema dialect used by OpenAPI 3.1
+pub fn json_schema<T>() -> Value
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let pks = <T::Schema as TableColumns>::primary_keys();
+    let mut properties = Map::new();
+    let mut required = Vec::default();
+
+    for col in <T::Schema as TableColumns>::columns() {
+        let mut prop = type_schema(col.rust_type());
+        if col.nullable() {
+            allow_null(&mut prop);
+        } else {
+            required.push(Value::from(col.name()));
+        }
+        if pks.contains(&col) {
+            prop.insert("readOnly".to_owned(), Value::Bool(true));
+        }
+        properties.insert(col.name().to_owned(), Value::Object(prop));
+    }
+
+    json!({
+        "title": model_name::<T>(),
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Returns the name and schema of a model, ready to add to the `components/schemas` of an
+/// OpenAPI document
+///
+/// ```rust,ignore
+/// let schemas: serde_json::Map<_, _> = [component::<Product>(), component::<Order>()]
+///     .into_iter()
+///     .collect();
+/// let doc = json!({ "openapi": "3.1.0", "components": { "schemas": schemas } });
+/// ```
+pub fn component<T>() -> (String, Value)
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    (model_name::<T>(), json_schema::<T>())
+}
+
+// The name of the struct without its module path
+fn model_name<T>() -> String {
+    let full = std::any::type_name::<T>();
+    let without_generics = full.split('<').next().unwrap_or(full);
+    let name = without_generics.rsplit("::").next().unwrap_or(full);
+    name.to_owned()
+}
+
+fn allow_null(prop: &mut Map<String, Value>) {
+    // an unknown type already allows anything
+    if let Some(Value::String(ty)) = prop.get("type").cloned() {
+        prop.insert("type".to_owned(), json!([ty, "null"]));
+    }
+}
+
+/// Maps a rust type (as written on the model) to its JSON Schema
+fn type_schema(rust_type: &str) -> Map<String, Value> {
+    let (path, generic) = match rust_type.split_once('<') {
+        Some((path, rest)) => (path, rest.strip_suffix('>')),
+        None => (rust_type, None),
+    };
+    let name = path.rsplit("::").next().unwrap_or(path);
+
+    let schema = match (name, generic) {
+        ("Vec", Some("u8")) => json!({ "type": "string", "format": "byte" }),
+        ("Vec", Some(inner)) => json!({ "type": "array", "items": type_schema(inner) }),
+        ("i8" | "i16" | "i32" | "u8" | "u16", _) => json!({ "type": "integer", "format": "int32" }),
+        ("i64" | "u32" | "u64" | "isize" | "usize", _) => {
+            json!({ "type": "integer", "format": "int64" })
+        }
+        ("f32", _) => json!({ "type": "number", "format": "float" }),
+        ("f64", _) => json!({ "type": "number", "format": "double" }),
+        ("bool", _) => json!({ "type": "boolean" }),
+        ("String" | "str" | "char", _) => json!({ "type": "string" }),
+        ("Decimal" | "BigDecimal", _) => json!({ "type": "string", "format": "decimal" }),
+        ("Uuid", _) => json!({ "type": "string", "format": "uuid" }),
+        ("DateTime" | "NaiveDateTime" | "OffsetDateTime" | "PrimitiveDateTime", _) => {
+            json!({ "type": "string", "format": "date-time" })
+        }
+        ("NaiveDate" | "Date", _) => json!({ "type": "string", "format": "date" }),
+        ("NaiveTime" | "Time", _) => json!({ "type": "string", "format": "time" }),
+        // json columns and types we don't know about can hold anything
+        _ => json!({}),
+    };
+
+    match schema {
+        Value::Object(map) => map,
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,57 @@
+use super::*;
+use crate::WeldsModel;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i64,
+    pub name: String,
+    #[welds(rename = "cost")]
+    pub price: Option<f64>,
+    #[welds(ignore)]
+    #[allow(dead_code)]
+    pub cached: i32,
+}
+
+#[test]
+fn should_write_a_property_for_each_column() {
+    let schema = json_schema::<Product>();
+    let expected = json!({
+        "title": "Product",
+        "type": "object",
+        "properties": {
+            "id": { "type": "integer", "format": "int64", "readOnly": true },
+            "name": { "type": "string" },
+            "cost": { "type": ["number", "null"], "format": "double" },
+        },
+        "required": ["id", "name"],
+    });
+    assert_eq!(schema, expected);
+}
+
+#[test]
+fn should_name_the_component_after_the_model() {
+    let (name, _) = component::<Product>();
+    assert_eq!(name, "Product");
+}
+
+#[test]
+fn should_map_common_types() {
+    let ty = |rust: &str| Value::Object(type_schema(rust));
+    assert_eq!(ty("Vec<u8>"), json!({ "type": "string", "format": "byte" }));
+    assert_eq!(
+        ty("uuid::Uuid"),
+        json!({ "type": "string", "format": "uuid" })
+    );
+    assert_eq!(
+        ty("chrono::DateTime<chrono::Utc>"),
+        json!({ "type": "string", "format": "date-time" })
+    );
+    assert_eq!(
+        ty("Vec<String>"),
+        json!({ "type": "array", "items": { "type": "string" } })
+    );
+    assert_eq!(ty("serde_json::Value"), json!({}));
+}
@@ -1,4 +1,11 @@
+pub use crate::query::clause::{CompareColumn, JoinColumn};
 pub use crate::state::{DbState, VecStateExt};
 pub use crate::Client;
 pub use crate::TransactStart;
 pub use crate::WeldsModel;
+pub use crate::WeldsProjection;
+
+#[cfg(feature = "json")]
+pub use crate::cache::CacheCodec;
+#[cfg(feature = "json")]
+pub use crate::state::ApplyPatch;
@@ -0,0 +1,368 @@
+use crate::errors::Result;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::{AsFieldName, ParamArgs};
+use crate::query::helpers::{build_where, join_sql_parts};
+use crate::writers::{in_namespace, table_name, ColumnWriter, NextParam};
+use crate::Syntax;
+use async_trait::async_trait;
+use std::collections::BTreeSet;
+use std::fmt;
+use welds_connections::Client;
+
+/// How many of the failing values are kept in the report
+const SAMPLE_SIZE: usize = 5;
+
+/// Runs a list of named data quality checks and reports on all of them.
+///
+/// Checks are run one after the other. A check that errors is reported, it doesn't stop the run.
+///
+/// ```rust,ignore
+/// let report = DataChecks::new()
+///     .add("no negative prices", &client, expect(Product::where_col(|p| p.price.lt(0.0))).is_empty())
+///     .add("skus are unique", &client, expect(Product::all()).unique(|p| p.sku))
+///     .add(
+///         "orders have a product",
+///         &orders_db,
+///         expect(Order::all()).references(|o| o.product_id, &products_db, Product::all(), |p| p.id),
+///     )
+///     .run()
+///     .await;
+///
+/// assert!(report.passed(), "{}", report);
+/// ```
+#[derive(Default)]
+pub struct DataChecks<'c> {
+    checks: Vec<(String, &'c dyn Client, Box<dyn Check + 'c>)>,
+}
+
+impl<'c> DataChecks<'c> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a check that is run against the given client
+    pub fn add(
+        mut self,
+        name: impl Into<String>,
+        client: &'c dyn Client,
+        check: impl Check + 'c,
+    ) -> Self {
+        self.checks.push((name.into(), client, Box::new(check)));
+        self
+    }
+
+    /// Runs all the checks
+    pub async fn run(&self) -> Report {
+        let mut results = Vec::default();
+        for (name, client, check) in &self.checks {
+            let status = match check.check(*client).await {
+                Ok(found) if found.failing == 0 => CheckStatus::Passed,
+                Ok(found) => CheckStatus::Failed {
+                    failing: found.failing,
+                    samples: found.samples,
+                },
+                Err(err) => CheckStatus::Error(err.to_string()),
+            };
+            results.push(CheckResult {
+                name: name.clone(),
+                status,
+            });
+        }
+        Report { results }
+    }
+}
+
+/// A single data quality check. Implement this to add your own checks to `DataChecks`
+#[async_trait]
+pub trait Check: Send + Sync {
+    /// Looks for data that breaks the check
+    async fn check(&self, client: &dyn Client) -> Result<Found>;
+}
+
+/// The data a check found that breaks it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Found {
+    /// How many rows (or values) break the check. Zero if the check passed
+    pub failing: u64,
+    /// A few of the values that break the check, as text
+    pub samples: Vec<String>,
+}
+
+/// The results of running `DataChecks`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub results: Vec<CheckResult>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckStatus {
+    Passed,
+    Failed {
+        failing: u64,
+        samples: Vec<String>,
+    },
+    /// The check could not be run
+    Error(String),
+}
+
+impl Report {
+    /// True if every check passed
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.status == CheckStatus::Passed)
+    }
+
+    /// The checks that failed or could not be run
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results
+            .iter()
+            .filter(|r| r.status != CheckStatus::Passed)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.results {
+            match &result.status {
+                CheckStatus::Passed => writeln!(f, "PASS  {}", result.name)?,
+                CheckStatus::Failed { failing, samples } if samples.is_empty() => {
+                    writeln!(f, "FAIL  {} ({} failing)", result.name, failing)?
+                }
+                CheckStatus::Failed { failing, samples } => writeln!(
+                    f,
+                    "FAIL  {} ({} failing, e.g. {})",
+                    result.name,
+                    failing,
+                    samples.join(", ")
+                )?,
+                CheckStatus::Error(err) => writeln!(f, "ERROR {} ({})", result.name, err)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Start a check on the rows a query returns
+pub fn expect<T>(query: QueryBuilder<T>) -> Expect<T> {
+    Expect { query }
+}
+
+/// The rows of a query, waiting to be told what to expect of them
+pub struct Expect<T> {
+    query: QueryBuilder<T>,
+}
+
+impl<T> Expect<T>
+where
+    T: Send + Sync + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    /// Fails for every row the query returns
+    pub fn is_empty(self) -> IsEmpty<T> {
+        IsEmpty { query: self.query }
+    }
+
+    /// Fails for every value of the column that is on more than one row. NULLs are ignored
+    pub fn unique<V, FN: AsFieldName<V>>(
+        self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+    ) -> Unique<T> {
+        let column = lam(Default::default()).colname().to_string();
+        Unique {
+            query: self.query,
+            column,
+        }
+    }
+
+    /// Fails for every value of the column that isn't found in the parent's column.
+    /// The parent can be in another database. NULLs are ignored.
+    ///
+    /// Keys are compared as text, and both lists of keys are read into memory.
+    pub fn references<'p, R, V, FN, RV, RFN>(
+        self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        parent_client: &'p dyn Client,
+        parent: QueryBuilder<R>,
+        parent_lam: impl Fn(<R as HasSchema>::Schema) -> RFN,
+    ) -> References<'p, T, R>
+    where
+        R: Send + Sync + HasSchema,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+        FN: AsFieldName<V>,
+        RFN: AsFieldName<RV>,
+    {
+        References {
+            query: self.query,
+            column: lam(Default::default()).colname().to_string(),
+            parent_client,
+            parent,
+            parent_column: parent_lam(Default::default()).colname().to_string(),
+        }
+    }
+}
+
+/// See `Expect::is_empty`
+pub struct IsEmpty<T> {
+    query: QueryBuilder<T>,
+}
+
+#[async_trait]
+impl<T> Check for IsEmpty<T>
+where
+    T: Send + Sync + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    async fn check(&self, client: &dyn Client) -> Result<Found> {
+        let failing = self.query.count(client).await?;
+        Ok(Found {
+            failing,
+            samples: Vec::default(),
+        })
+    }
+}
+
+/// See `Expect::unique`
+pub struct Unique<T> {
+    query: QueryBuilder<T>,
+    column: String,
+}
+
+#[async_trait]
+impl<T> Check for Unique<T>
+where
+    T: Send + Sync + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    async fn check(&self, client: &dyn Client) -> Result<Found> {
+        let syntax = client.syntax();
+        let column = column_ref(syntax, &self.query, &self.column);
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let sql = in_namespace(client, || {
+            select_sql(
+                syntax,
+                &self.query,
+                &format!("{}, COUNT(*)", as_text(syntax, &column)),
+                Some(format!("GROUP BY {} HAVING COUNT(*) > 1", column)),
+                &mut args,
+            )
+        });
+        let args = args.unwrap();
+        let rows = client.fetch_rows(&sql, &args).await?;
+
+        let mut duplicates = Vec::default();
+        for row in rows {
+            let value: Option<String> = row.get_by_position(0)?;
+            duplicates.extend(value);
+        }
+        Ok(Found {
+            failing: duplicates.len() as u64,
+            samples: duplicates.into_iter().take(SAMPLE_SIZE).collect(),
+        })
+    }
+}
+
+/// See `Expect::references`
+pub struct References<'p, T, R> {
+    query: QueryBuilder<T>,
+    column: String,
+    parent_client: &'p dyn Client,
+    parent: QueryBuilder<R>,
+    parent_column: String,
+}
+
+#[async_trait]
+impl<T, R> Check for References<'_, T, R>
+where
+    T: Send + Sync + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    R: Send + Sync + HasSchema,
+    <R as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    async fn check(&self, client: &dyn Client) -> Result<Found> {
+        let keys = distinct_keys(client, &self.query, &self.column).await?;
+        let parent_keys =
+            distinct_keys(self.parent_client, &self.parent, &self.parent_column).await?;
+        let missing: Vec<String> = keys.difference(&parent_keys).cloned().collect();
+        Ok(Found {
+            failing: missing.len() as u64,
+            samples: missing.into_iter().take(SAMPLE_SIZE).collect(),
+        })
+    }
+}
+
+async fn distinct_keys<T>(
+    client: &dyn Client,
+    query: &QueryBuilder<T>,
+    column: &str,
+) -> Result<BTreeSet<String>>
+where
+    T: Send + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let syntax = client.syntax();
+    let column = column_ref(syntax, query, column);
+    let mut args: Option<ParamArgs> = Some(Vec::default());
+    let head = format!("DISTINCT {}", as_text(syntax, &column));
+    let sql = in_namespace(client, || select_sql(syntax, query, &head, None, &mut args));
+    let args = args.unwrap();
+    let rows = client.fetch_rows(&sql, &args).await?;
+
+    let mut keys = BTreeSet::default();
+    for row in rows {
+        let key: Option<String> = row.get_by_position(0)?;
+        keys.extend(key);
+    }
+    Ok(keys)
+}
+
+// SELECT {columns} FROM {table} WHERE {the query's filters} {tail}
+fn select_sql<'q, T>(
+    syntax: Syntax,
+    query: &'q QueryBuilder<T>,
+    columns: &str,
+    tail: Option<String>,
+    args: &mut Option<ParamArgs<'q>>,
+) -> String
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo,
+{
+    let next_params = NextParam::new(syntax);
+    let table = table_name(&<T::Schema as TableInfo>::identifier().join("."));
+    join_sql_parts(&[
+        Some(format!("SELECT {} FROM {} {}", columns, table, query.alias)),
+        build_where(
+            syntax,
+            &next_params,
+            &query.alias,
+            &query.wheres,
+            args,
+            &query.exist_ins,
+        ),
+        tail,
+    ])
+}
+
+fn column_ref<T>(syntax: Syntax, query: &QueryBuilder<T>, column: &str) -> String {
+    let writer = ColumnWriter::new(syntax);
+    format!("{}.{}", query.alias, writer.excape(column))
+}
+
+// values are read back as text so any column type can be compared and reported
+pub(crate) fn as_text(syntax: Syntax, column: &str) -> String {
+    let text = match syntax {
+        Syntax::Mysql | Syntax::MariaDb => "CHAR",
+        Syntax::Postgres | Syntax::CockroachDb | Syntax::Sqlite => "TEXT",
+        Syntax::Mssql => "NVARCHAR(MAX)",
+    };
+    format!("CAST({} AS {})", column, text)
+}
+
+#[cfg(test)]
+mod tests;
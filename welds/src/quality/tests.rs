@@ -0,0 +1,113 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub sku: String,
+    pub price: f64,
+}
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub product_id: Option<i32>,
+}
+
+fn value(text: &str) -> MockRow {
+    MockRow::new().with("value", text)
+}
+
+#[test]
+fn should_report_each_check() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("COUNT(*)", vec![MockRow::new().with("count", 3_i64)]);
+
+    let report = block_on(
+        DataChecks::new()
+            .add(
+                "no negative prices",
+                &client,
+                expect(Product::where_col(|p| p.price.lt(0.0))).is_empty(),
+            )
+            .add(
+                "skus are unique",
+                &client,
+                expect(Product::all()).unique(|p| p.sku),
+            )
+            .run(),
+    );
+
+    assert!(!report.passed());
+    assert_eq!(
+        report.results,
+        vec![
+            CheckResult {
+                name: "no negative prices".to_string(),
+                status: CheckStatus::Failed {
+                    failing: 3,
+                    samples: Vec::default()
+                },
+            },
+            CheckResult {
+                name: "skus are unique".to_string(),
+                status: CheckStatus::Passed,
+            },
+        ]
+    );
+    assert_eq!(report.failures().count(), 1);
+}
+
+#[test]
+fn should_find_duplicate_values() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("GROUP BY", vec![value("A1"), value("B2")]);
+
+    let check = expect(Product::where_col(|p| p.price.gt(1.0))).unique(|p| p.sku);
+    let found = block_on(check.check(&client)).unwrap();
+
+    assert_eq!(found.failing, 2);
+    assert_eq!(found.samples, vec!["A1".to_string(), "B2".to_string()]);
+    let query = client.queries().pop().unwrap();
+    assert_eq!(
+        query.sql,
+        r#"SELECT CAST(t1."sku" AS TEXT), COUNT(*) FROM products t1 WHERE ( t1.price > $1 ) GROUP BY t1."sku" HAVING COUNT(*) > 1"#
+    );
+    assert_eq!(query.params.len(), 1);
+}
+
+#[test]
+fn should_find_keys_missing_from_a_parent_in_another_database() {
+    let orders_db = MockClient::new(Syntax::Postgres);
+    orders_db.enqueue_rows("FROM orders", vec![value("1"), value("2"), value("7")]);
+    let products_db = MockClient::new(Syntax::Mysql);
+    products_db.enqueue_rows("FROM products", vec![value("1"), value("2")]);
+
+    let check =
+        expect(Order::all()).references(|o| o.product_id, &products_db, Product::all(), |p| p.id);
+    let found = block_on(check.check(&orders_db)).unwrap();
+
+    assert_eq!(
+        found,
+        Found {
+            failing: 1,
+            samples: vec!["7".to_string()],
+        }
+    );
+    assert_eq!(
+        orders_db.last_sql().unwrap(),
+        r#"SELECT DISTINCT CAST(t1."product_id" AS TEXT) FROM orders t1"#
+    );
+    assert_eq!(
+        products_db.last_sql().unwrap(),
+        "SELECT DISTINCT CAST(t1.id AS CHAR) FROM products t1"
+    );
+}
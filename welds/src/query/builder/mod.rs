@@ -1,10 +1,19 @@
 pub use super::clause::manualwhereparam::ManualWhereParam;
+pub use super::clause::WhereGroup;
 use super::clause::{self, AsOptField};
+use super::group_by::{Aggregate, GroupBuilder};
+use super::json::{ClauseJsonPath, JsonFilter, JsonPath};
+use super::keyset::KeysetBuilder;
+use super::order::Order;
+use super::search::SearchBuilder;
+use super::select::RowLock;
 use super::select_cols::SelectBuilder;
-use super::update::bulk::UpdateBuilder;
-use crate::model_traits::{HasSchema, TableColumns, TableInfo, UniqueIdentifier};
+use super::union::UnionBuilder;
+use super::update::bulk::{SetExpr, UpdateBuilder};
+use crate::model_traits::{HasSchema, PartialOf, TableColumns, TableInfo, UniqueIdentifier};
 use crate::query::clause::exists::ExistIn;
-use crate::query::clause::{AsFieldName, ClauseAdder, OrderBy};
+use crate::query::clause::sample::Sample;
+use crate::query::clause::{AsFieldName, ClauseAdder, FullText, OrderBy, SearchColumns};
 use crate::relations::{HasRelations, Relationship};
 use crate::writers::alias::TableAlias;
 use std::marker::PhantomData;
@@ -25,8 +34,21 @@ pub struct QueryBuilder<T> {
     pub(crate) limit: Option<i64>,
     pub(crate) offset: Option<i64>,
     pub(crate) orderby: Vec<OrderBy>,
+    pub(crate) late_row_lookup: bool,
+    pub(crate) statement_cache: Option<bool>,
+    pub(crate) nolock: bool,
+    pub(crate) lock: Option<RowLock>,
+    pub(crate) sample: Option<Sample>,
+    pub(crate) distinct: bool,
+    pub(crate) distinct_on: Vec<String>,
     pub(crate) alias: String,
     pub(crate) alias_asigner: Arc<TableAlias>,
+    // where in `wheres` the filter on the soft delete column is
+    pub(crate) soft_delete: Option<usize>,
+    // where in `wheres` the model's default scope is
+    pub(crate) default_scope: Option<usize>,
+    // deletes remove soft deleted models instead of marking them deleted
+    pub(crate) force_delete: bool,
 }
 
 impl<T> Default for QueryBuilder<T>
@@ -45,16 +67,87 @@ where
     pub fn new() -> Self {
         let ta = TableAlias::new();
         let alias = ta.next();
-        Self {
+        let mut qb = Self {
             _t: Default::default(),
             wheres: Vec::default(),
             limit: None,
             offset: None,
             orderby: Vec::default(),
+            late_row_lookup: false,
+            statement_cache: None,
+            nolock: false,
+            lock: None,
+            sample: None,
+            distinct: false,
+            distinct_on: Vec::default(),
             exist_ins: Default::default(),
             alias,
             alias_asigner: Arc::new(ta),
+            soft_delete: None,
+            default_scope: None,
+            force_delete: false,
+        };
+        // soft deleted rows are left out unless asked for
+        if let Some(col) = <T as HasSchema>::Schema::soft_delete_column() {
+            qb.soft_delete = Some(qb.wheres.len());
+            qb.wheres.push(soft_delete_clause(col, "IS NULL"));
+        }
+        if let Some(scope) = <T as HasSchema>::Schema::default_scope() {
+            qb.default_scope = Some(qb.wheres.len());
+            qb.wheres.push(scope);
+        }
+        qb
+    }
+
+    /// Includes the rows that have been soft deleted, which are left out by default
+    pub fn with_deleted(mut self) -> Self {
+        if let Some(index) = self.soft_delete.take() {
+            self.remove_where(index);
         }
+        self
+    }
+
+    /// Leaves out the model's `default_scope`, for the rows it would filter out
+    pub fn unscoped(mut self) -> Self {
+        if let Some(index) = self.default_scope.take() {
+            self.remove_where(index);
+        }
+        self
+    }
+
+    // removes a filter, keeping track of where the others are
+    fn remove_where(&mut self, index: usize) {
+        self.wheres.remove(index);
+        for kept in [&mut self.soft_delete, &mut self.default_scope] {
+            if let Some(kept) = kept.as_mut().filter(|k| **k > index) {
+                *kept -= 1;
+            }
+        }
+    }
+
+    /// Deletes the rows of a soft deleted model from the database (`DELETE FROM ...`) instead
+    /// of marking them deleted. Combine with `with_deleted` to also remove the rows already
+    /// marked deleted
+    pub fn force_delete(mut self) -> Self {
+        self.force_delete = true;
+        self
+    }
+
+    /// Only returns the rows that have been soft deleted
+    pub fn only_deleted(mut self) -> Self {
+        let col = match <T as HasSchema>::Schema::soft_delete_column() {
+            Some(col) => col,
+            None => return self,
+        };
+        let clause = soft_delete_clause(col, "IS NOT NULL");
+        match self.soft_delete {
+            Some(index) => self.wheres[index] = clause,
+            None => {
+                self.soft_delete = Some(self.wheres.len());
+                self.wheres.push(clause);
+            }
+        }
+        self
     }
 
     /// Filter the results returned by this query.
@@ -71,6 +164,26 @@ where
         self
     }
 
+    /// Filter the results with a group of conditions where any one of them has to match.
+    /// The group is written in parentheses and ANDed with the rest of the query.
+    ///
+    /// Example
+    /// ```rust,ignore
+    /// where_any(|g| g.where_col(|c| c.status.equal("new")).where_col(|c| c.status.equal("retry")))
+    /// // WHERE ( t1.status = $1 OR t1.status = $2 )
+    /// ```
+    pub fn where_any(mut self, lam: impl Fn(WhereGroup<T>) -> WhereGroup<T>) -> Self {
+        self.wheres.push(WhereGroup::build("OR", lam));
+        self
+    }
+
+    /// Filter the results with a group of conditions that all have to match.
+    /// Mostly useful nested inside of a `where_any` group.
+    pub fn where_all(mut self, lam: impl Fn(WhereGroup<T>) -> WhereGroup<T>) -> Self {
+        self.wheres.push(WhereGroup::build("AND", lam));
+        self
+    }
+
     /// write custom sql for the right side of a clauses in a where block
     /// NOTE: use '?' for params. They will be swapped out for the correct Syntax
     /// NOTE: use '$' for table prefix/alias. It will be swapped out for the prefix used at runtime
@@ -121,6 +234,96 @@ where
         self
     }
 
+    /// Filter the results with a fragment of SQL the query builder can't express.
+    /// NOTE: use '?' for params. They are numbered for the backend (`$1`, `@p1`, ...),
+    /// and counted along with the params of the rest of the query.
+    /// A '?' inside quotes is left alone, write '??' for a literal '?' (postgres jsonb operators)
+    ///
+    /// Example
+    /// ```rust,ignore
+    /// where_raw("lower(email) = lower(?)", ManualWhereParam::new().push(email.to_string()))
+    /// // WHERE ( lower(email) = lower($1) )
+    /// ```
+    ///
+    pub fn where_raw(mut self, sql: impl Into<String>, params: ManualWhereParam) -> Self {
+        let c = clause::raw::ClauseRaw {
+            sql: sql.into(),
+            params: params.into_inner(),
+        };
+        self.wheres.push(Box::new(c));
+        self
+    }
+
+    /// Filter on the value at a path in a JSON column.
+    /// The path uses the `$.key.nested[0]` syntax the databases share:
+    ///
+    /// - Postgres: `jsonb_path_query_first(col::jsonb, '$.status') #>> '{}'`
+    /// - MySQL: `JSON_UNQUOTE(JSON_EXTRACT(col, '$.status'))`
+    /// - Sqlite: `json_extract(col, '$.status')`
+    /// - MSSQL: `JSON_VALUE(col, '$.status')`
+    ///
+    /// The path is written into the SQL, it shouldn't come from user input.
+    ///
+    /// ```rust,ignore
+    /// use welds::query::json;
+    ///
+    /// Event::all()
+    ///     .where_json_path(|e| e.payload, "$.status", json::eq("active"))
+    ///     .where_json_path(|e| e.payload, "$.attempts", json::gt(3))
+    /// ```
+    pub fn where_json_path<V, FN: AsFieldName<V>>(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        path: impl Into<String>,
+        filter: JsonFilter,
+    ) -> Self {
+        let field = lam(Default::default());
+        let path = JsonPath {
+            col: field.colname().to_owned(),
+            path: path.into(),
+        };
+        self.wheres.push(Box::new(ClauseJsonPath { path, filter }));
+        self
+    }
+
+    /// Filter to the rows where the text columns match the search terms,
+    /// using the full-text search of the database:
+    ///
+    /// - Postgres: `to_tsvector(...) @@ plainto_tsquery(?)`, the terms are plain words
+    /// - MySQL: `MATCH (...) AGAINST (?)`, needs a FULLTEXT index of exactly these columns
+    /// - Sqlite: `MATCH`, the model has to be an FTS5 table. The terms use the FTS5 query syntax
+    /// - MSSQL: `CONTAINS((...), ?)`, needs a full-text index. The terms are a search condition
+    ///
+    /// ```rust,ignore
+    /// Post::all().where_matches(|p| (p.title, p.body), "rust orm")
+    /// ```
+    /// Use `search` to also put the best matches first
+    pub fn where_matches<C: SearchColumns>(
+        mut self,
+        cols: impl Fn(<T as HasSchema>::Schema) -> C,
+        terms: impl Into<String>,
+    ) -> Self {
+        let cols = cols(Default::default()).colnames();
+        let search = FullText::new::<T>(cols, terms.into());
+        self.wheres.push(Box::new(search));
+        self
+    }
+
+    /// Filters like `where_matches`, ordering the rows by how well they match.
+    /// The ordering of the query is kept to break ties. See `SearchBuilder`
+    pub fn search<C: SearchColumns>(
+        self,
+        cols: impl Fn(<T as HasSchema>::Schema) -> C,
+        terms: impl Into<String>,
+    ) -> SearchBuilder<T>
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let cols = cols(Default::default()).colnames();
+        let search = FullText::new::<T>(cols, terms.into());
+        SearchBuilder::new(self, search)
+    }
+
     /// Add a query to this query (JOIN on a relationship)
     /// results on a query that is filtered using the results of both queries
     pub fn where_relation<R, Ship>(
@@ -142,7 +345,87 @@ where
         let inner_tn = <R as HasSchema>::Schema::identifier();
         let inner_tn = inner_tn.join(".");
         let inner_col = ship.their_key::<R::Schema, T::Schema>();
-        let mut exist_in = ExistIn::new(filter, out_col, inner_tn, inner_col);
+        let mut exist_in = match ship.through() {
+            None => ExistIn::new(filter, out_col, inner_tn, inner_col).sharing(ship.shared_keys()),
+            Some(through) => {
+                let link = through.their_key.to_owned();
+                let inner = ExistIn::new(filter, link, inner_tn, inner_col);
+                ExistIn::through(inner, out_col, through.table, through.my_key)
+            }
+        };
+        exist_in.set_aliases(&self.alias_asigner);
+        self.exist_ins.push(exist_in);
+        self
+    }
+
+    /// Filter the results to the rows that have at least one related row matching the filter.
+    /// Writes `EXISTS ( SELECT 1 FROM ... )`
+    ///
+    /// ```rust,ignore
+    /// let with_open_orders = Customer::all()
+    ///     .where_exists(|c| c.orders, Order::where_col(|o| o.status.equal("open")));
+    /// ```
+    pub fn where_exists<R, Ship>(
+        self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        filter: QueryBuilder<R>,
+    ) -> Self
+    where
+        T: HasRelations,
+        Ship: Relationship<R>,
+        R: Send + Sync + HasSchema,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        <T as HasRelations>::Relation: Default,
+    {
+        self.push_exists(relationship, filter, false)
+    }
+
+    /// Filter the results to the rows that have no related row matching the filter,
+    /// such as customers without any orders. Writes `NOT EXISTS ( SELECT 1 FROM ... )`
+    pub fn where_not_exists<R, Ship>(
+        self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        filter: QueryBuilder<R>,
+    ) -> Self
+    where
+        T: HasRelations,
+        Ship: Relationship<R>,
+        R: Send + Sync + HasSchema,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        <T as HasRelations>::Relation: Default,
+    {
+        self.push_exists(relationship, filter, true)
+    }
+
+    fn push_exists<R, Ship>(
+        mut self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        filter: QueryBuilder<R>,
+        negate: bool,
+    ) -> Self
+    where
+        T: HasRelations,
+        Ship: Relationship<R>,
+        R: Send + Sync + HasSchema,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        <T as HasRelations>::Relation: Default,
+    {
+        let ship = relationship(Default::default());
+        let out_col = ship.my_key::<R::Schema, T::Schema>();
+        let inner_tn = <R as HasSchema>::Schema::identifier().join(".");
+        let inner_col = ship.their_key::<R::Schema, T::Schema>();
+        let mut exist_in = match ship.through() {
+            None => ExistIn::exists(filter, out_col, inner_tn, inner_col, negate)
+                .sharing(ship.shared_keys()),
+            Some(through) => {
+                let link = through.their_key.to_owned();
+                let inner = ExistIn::exists(filter, link, inner_tn, inner_col, false);
+                ExistIn::through(inner, out_col, through.table, through.my_key).testing(negate)
+            }
+        };
         exist_in.set_aliases(&self.alias_asigner);
         self.exist_ins.push(exist_in);
         self
@@ -169,12 +452,35 @@ where
         let out_col = ship.their_key::<R::Schema, T::Schema>();
         let inner_tn = <T as HasSchema>::Schema::identifier().join(".");
         let inner_col = ship.my_key::<R::Schema, T::Schema>();
-        let exist_in = ExistIn::new(self, out_col, inner_tn, inner_col);
+        let exist_in = match ship.through() {
+            None => ExistIn::new(self, out_col, inner_tn, inner_col).sharing(ship.shared_keys()),
+            Some(through) => {
+                let link = through.my_key.to_owned();
+                let alias = qb.alias_asigner.next();
+                let inner = ExistIn::new(self, link, inner_tn, inner_col);
+                let mut exist_in =
+                    ExistIn::through(inner, out_col, through.table, through.their_key);
+                exist_in.inner_tablealias = alias;
+                exist_in
+            }
+        };
 
         qb.exist_ins.push(exist_in);
         qb
     }
 
+    // the tables the sub-queries of the filters read, false if some can't be named
+    pub(crate) fn subquery_tables(&self, tables: &mut Vec<String>) -> bool {
+        let mut known = true;
+        for w in &self.wheres {
+            known &= w.tables(tables);
+        }
+        for exist_in in &self.exist_ins {
+            known &= exist_in.tables(tables);
+        }
+        known
+    }
+
     pub(crate) fn set_aliases(&mut self, alias_asigner: &Arc<TableAlias>) {
         self.alias_asigner = alias_asigner.clone();
         self.alias = self.alias_asigner.next();
@@ -195,6 +501,100 @@ where
         self
     }
 
+    /// Speeds up deep OFFSET pagination (a "late row lookup").
+    ///
+    /// The page is first found by reading only the primary keys, then the full rows are
+    /// joined back in for just that page. Big win on wide tables in MySQL and MSSQL.
+    /// Only used when the query has an offset and the model has a single primary key.
+    pub fn late_row_lookup(mut self) -> Self {
+        self.late_row_lookup = true;
+        self
+    }
+
+    /// **Dirty reads.** MSSQL only: reads the table `WITH (NOLOCK)`, so the query doesn't wait
+    /// on (or block) writers.
+    ///
+    /// The results may include uncommitted changes, and rows may be read twice or skipped
+    /// while pages move. Only use it for reporting queries that can live with that.
+    /// Ignored on the other databases.
+    pub fn with_nolock_dirty_reads(mut self) -> Self {
+        self.nolock = true;
+        self
+    }
+
+    /// Locks the rows this query reads until the transaction ends (`SELECT ... FOR UPDATE`),
+    /// so they can be read and then updated without another transaction changing them in between.
+    /// Only useful when run inside of a transaction.
+    ///
+    /// MSSQL reads the table `WITH (UPDLOCK, ROWLOCK)` instead. SQLite locks the whole database
+    /// for a write transaction and ignores it.
+    pub fn lock_for_update(mut self) -> Self {
+        self.lock = Some(RowLock::Update);
+        self
+    }
+
+    /// Locks the rows like `lock_for_update`, leaving out any another transaction already has
+    /// locked (`FOR UPDATE SKIP LOCKED`, `READPAST` on MSSQL).
+    /// Lets several workers pull jobs off of a queue table without waiting on each other.
+    ///
+    /// ```rust,ignore
+    /// let transaction = client.begin().await?;
+    /// let jobs = Job::where_col(|j| j.status.equal("pending"))
+    ///     .order_by_asc(|j| j.id)
+    ///     .limit(10)
+    ///     .skip_locked()
+    ///     .run(&transaction)
+    ///     .await?;
+    /// ```
+    pub fn skip_locked(mut self) -> Self {
+        self.lock = Some(RowLock::SkipLocked);
+        self
+    }
+
+    /// Locks the rows like `lock_for_update`, failing right away if another transaction
+    /// has any of them locked instead of waiting (`FOR UPDATE NOWAIT`)
+    pub fn no_wait(mut self) -> Self {
+        self.lock = Some(RowLock::NoWait);
+        self
+    }
+
+    /// Leaves duplicate rows out of the results (`SELECT DISTINCT`)
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Keeps only the first row for each value of the column (`SELECT DISTINCT ON`),
+    /// first in the order of the query. Multiple calls keep a row for each combination.
+    ///
+    /// ```rust,ignore
+    /// // the latest order of each customer
+    /// Order::all()
+    ///     .distinct_on(|o| o.customer_id)
+    ///     .order_by_desc(|o| o.created_at)
+    /// // SELECT DISTINCT ON (t1.customer_id) ... ORDER BY customer_id ASC, created_at DESC
+    /// ```
+    ///
+    /// Postgres needs the rows sorted by the DISTINCT ON columns first, they are added to the
+    /// front of the ORDER BY. The other databases don't have DISTINCT ON, the first row of each
+    /// is picked with a `ROW_NUMBER()` instead.
+    pub fn distinct_on<V, FN: AsFieldName<V>>(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+    ) -> Self {
+        let field = lam(Default::default());
+        self.distinct_on.push(field.colname().to_owned());
+        self
+    }
+
+    /// Turns caching of the prepared statement on or off for this query,
+    /// overriding the client's `with_statement_cache`.
+    /// Worth turning off for one-off queries with unusual shapes.
+    pub fn statement_cache(mut self, enabled: bool) -> Self {
+        self.statement_cache = Some(enabled);
+        self
+    }
+
     /// Order the results of the query by a given column
     ///
     /// multiple calls will result in multiple OrderBys
@@ -253,12 +653,55 @@ where
         self
     }
 
+    /// Order the results by a column or expression, choosing where NULLs go and the collation.
+    /// See `welds::query::order`
+    ///
+    /// multiple calls will result in multiple OrderBys
+    pub fn order_by(mut self, lam: impl Fn(<T as HasSchema>::Schema) -> Order) -> Self {
+        let order = lam(Default::default());
+        self.orderby.push(order.by);
+        self
+    }
+
+    /// Order the results randomly (`random()`, `RAND()`, `NEWID()` on MSSQL).
+    /// Sorts the whole table, slow on big ones
+    pub fn order_by_random(mut self) -> Self {
+        self.orderby.push(OrderBy::random());
+        self
+    }
+
+    /// Returns `n` random rows, or all the rows if there are fewer
+    ///
+    /// ```rust,ignore
+    /// let winner = Entry::all().sample(1).run(&client).await?;
+    /// ```
+    pub fn sample(self, n: i64) -> Self {
+        self.order_by_random().limit(n)
+    }
+
+    /// Reads about `percent` percent of the rows, picked at random.
+    /// Uses TABLESAMPLE on Postgres and MSSQL (MSSQL samples whole pages of the table), on the
+    /// other databases each row is kept with that chance. The number of rows varies from run to run
+    pub fn sample_percent(mut self, percent: f64) -> Self {
+        self.sample = Some(Sample { percent });
+        self
+    }
+
     /// Manually write the order by part of the query
     pub fn order_manual(mut self, sql: &str) -> Self {
         self.orderby.push(OrderBy::new(sql.to_string(), ""));
         self
     }
 
+    /// Pages through the results with keyset pagination, `per_page` rows at a time.
+    /// See `KeysetBuilder`
+    pub fn cursor_paginate(self, per_page: i64) -> KeysetBuilder<T>
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        KeysetBuilder::new(self, per_page)
+    }
+
     /// Select only the specific columns
     pub fn select<V, FN: AsFieldName<V>>(
         self,
@@ -268,6 +711,102 @@ where
         sb.select(lam)
     }
 
+    /// Reads only the columns of a partial model, a struct with some of the fields of this one.
+    /// Handy for leaving the big blob columns of a wide table in the database.
+    ///
+    /// ```rust,ignore
+    /// #[derive(WeldsModel)]
+    /// #[welds(partial_of(User))]
+    /// struct UserName {
+    ///     #[welds(primary_key)]
+    ///     pub id: i32,
+    ///     pub name: String,
+    /// }
+    ///
+    /// let names = User::where_col(|u| u.active.equal(true))
+    ///     .select_only::<UserName>()
+    ///     .run(&client)
+    ///     .await?;
+    /// ```
+    pub fn select_only<P>(self) -> QueryBuilder<P>
+    where
+        P: PartialOf<T>,
+    {
+        QueryBuilder {
+            _t: Default::default(),
+            wheres: self.wheres,
+            exist_ins: self.exist_ins,
+            limit: self.limit,
+            offset: self.offset,
+            orderby: self.orderby,
+            late_row_lookup: self.late_row_lookup,
+            statement_cache: self.statement_cache,
+            nolock: self.nolock,
+            lock: self.lock,
+            sample: self.sample,
+            distinct: self.distinct,
+            distinct_on: self.distinct_on,
+            alias: self.alias,
+            alias_asigner: self.alias_asigner,
+            soft_delete: self.soft_delete,
+            default_scope: self.default_scope,
+            force_delete: self.force_delete,
+        }
+    }
+
+    /// Select a column as another name. See `SelectBuilder::select_as`
+    pub fn select_as<V, FN: AsFieldName<V>>(
+        self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        alias: impl Into<String>,
+    ) -> SelectBuilder<T> {
+        SelectBuilder::new(self).select_as(lam, alias)
+    }
+
+    /// Select the value at a path in a JSON column. See `SelectBuilder::select_json_path`
+    pub fn select_json_path<V, FN: AsFieldName<V>>(
+        self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        path: impl Into<String>,
+        alias: impl Into<String>,
+    ) -> SelectBuilder<T> {
+        SelectBuilder::new(self).select_json_path(lam, path, alias)
+    }
+
+    /// Select an aggregate of a column. See `SelectBuilder::select_agg`
+    pub fn select_agg(
+        self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> Aggregate,
+    ) -> SelectBuilder<T> {
+        SelectBuilder::new(self).select_agg(lam)
+    }
+
+    /// Groups the rows of this query by a column, to select aggregates of each group
+    pub fn group_by<V, FN: AsFieldName<V>>(
+        self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+    ) -> GroupBuilder<T> {
+        GroupBuilder::new(self).group_by(lam)
+    }
+
+    /// Combines the rows of this query with the rows of another (UNION), leaving out duplicates
+    pub fn union(self, other: QueryBuilder<T>) -> UnionBuilder<T>
+    where
+        T: Sync,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        UnionBuilder::new(self).union(other)
+    }
+
+    /// Combines the rows of this query with all the rows of another (UNION ALL)
+    pub fn union_all(self, other: QueryBuilder<T>) -> UnionBuilder<T>
+    where
+        T: Sync,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        UnionBuilder::new(self).union_all(other)
+    }
+
     /// Changes this query Into a sql UPDATE.
     /// sets the value from the lambda in the database
     pub fn set<V, FIELD>(
@@ -284,6 +823,16 @@ where
         ub.set(lam, value)
     }
 
+    /// Changes this query Into a sql UPDATE.
+    /// sets the column to an expression of its current value, `set_expr(|t| t.price.mul(1.1))`
+    pub fn set_expr(self, lam: impl Fn(<T as HasSchema>::Schema) -> SetExpr) -> UpdateBuilder<T>
+    where
+        <T as HasSchema>::Schema: Default,
+    {
+        let ub = UpdateBuilder::new(self);
+        ub.set_expr(lam)
+    }
+
     /// Nulls out the value from the lambda in the database
     pub fn set_null<V, FIELD>(
         self,
@@ -298,3 +847,11 @@ where
         ub.set_null(lam)
     }
 }
+
+fn soft_delete_clause(col: &str, sql: &str) -> Box<dyn ClauseAdder> {
+    Box::new(clause::ClauseColManual {
+        col: Some(col.to_owned()),
+        sql: sql.to_owned(),
+        params: Vec::default(),
+    })
+}
@@ -1,4 +1,7 @@
+use super::subquery::ClauseInQuery;
 use super::{AsFieldName, ClauseAdder, ClauseColVal};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::select_cols::SelectBuilder;
 use std::marker::PhantomData;
 use welds_connections::Param;
 
@@ -56,4 +59,22 @@ where
         };
         Box::new(cv)
     }
+
+    /// Will write SQL checking the value is one of the values the query selects (IN)
+    pub fn in_query<R>(self, query: SelectBuilder<R>) -> Box<dyn ClauseAdder>
+    where
+        R: Send + Sync + HasSchema + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        ClauseInQuery::boxed(self.col, "IN", query)
+    }
+
+    /// Will write SQL checking the value is none of the values the query selects (NOT IN)
+    pub fn not_in_query<R>(self, query: SelectBuilder<R>) -> Box<dyn ClauseAdder>
+    where
+        R: Send + Sync + HasSchema + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        ClauseInQuery::boxed(self.col, "NOT IN", query)
+    }
 }
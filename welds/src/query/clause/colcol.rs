@@ -0,0 +1,75 @@
+use super::{AsFieldName, ClauseAdder, ParamArgs};
+use crate::writers::NextParam;
+use crate::Syntax;
+
+/// Compares two columns of the same row, `t1.a > t1.b`. It has no params
+pub(crate) struct ClauseColCol {
+    pub(crate) col: String,
+    pub(crate) operator: &'static str,
+    pub(crate) other: String,
+}
+
+impl ClauseAdder for ClauseColCol {
+    fn bind<'lam, 'args, 'p>(&'lam self, _args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+    }
+
+    fn clause(&self, _syntax: Syntax, alias: &str, _next_params: &NextParam) -> Option<String> {
+        Some(format!(
+            "{}.{} {} {}.{}",
+            alias, self.col, self.operator, alias, self.other
+        ))
+    }
+}
+
+/// Adds comparisons to another column of the same table, for filters like
+/// `where_col(|t| t.updated_at.gt_col(t.created_at))`.
+///
+/// In a joined query the columns are those of the joined table
+pub trait CompareColumn<V>: AsFieldName<V> + Sized {
+    /// This column equals the other column (=)
+    fn eq_col(self, other: impl AsFieldName<V>) -> Box<dyn ClauseAdder> {
+        compare(self, "=", other)
+    }
+
+    /// This column does not equal the other column (!=)
+    fn ne_col(self, other: impl AsFieldName<V>) -> Box<dyn ClauseAdder> {
+        compare(self, "!=", other)
+    }
+
+    /// This column is greater than the other column (>)
+    fn gt_col(self, other: impl AsFieldName<V>) -> Box<dyn ClauseAdder> {
+        compare(self, ">", other)
+    }
+
+    /// This column is greater than or equal to the other column (>=)
+    fn gte_col(self, other: impl AsFieldName<V>) -> Box<dyn ClauseAdder> {
+        compare(self, ">=", other)
+    }
+
+    /// This column is less than the other column (<)
+    fn lt_col(self, other: impl AsFieldName<V>) -> Box<dyn ClauseAdder> {
+        compare(self, "<", other)
+    }
+
+    /// This column is less than or equal to the other column (<=)
+    fn lte_col(self, other: impl AsFieldName<V>) -> Box<dyn ClauseAdder> {
+        compare(self, "<=", other)
+    }
+}
+
+impl<V, F: AsFieldName<V>> CompareColumn<V> for F {}
+
+fn compare<V>(
+    col: impl AsFieldName<V>,
+    operator: &'static str,
+    other: impl AsFieldName<V>,
+) -> Box<dyn ClauseAdder> {
+    Box::new(ClauseColCol {
+        col: col.colname().to_owned(),
+        operator,
+        other: other.colname().to_owned(),
+    })
+}
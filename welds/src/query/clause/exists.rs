@@ -3,6 +3,7 @@ use crate::query::builder::QueryBuilder;
 use crate::query::clause::OrderBy;
 use crate::query::clause::ParamArgs;
 use crate::writers::alias::TableAlias;
+use crate::writers::table_name;
 use crate::writers::NextParam;
 use crate::Syntax;
 use std::sync::Arc;
@@ -12,6 +13,8 @@ use std::sync::Arc;
 pub struct ExistIn {
     outer_column: String,
     inner_column: String,
+    // more columns that are equal on both sides, for a key over several columns
+    shared_columns: Vec<String>,
     inner_tablename: String,
     pub(crate) inner_tablealias: String,
     wheres: Vec<Box<dyn ClauseAdder>>,
@@ -19,6 +22,8 @@ pub struct ExistIn {
     limit: Option<i64>,
     offset: Option<i64>,
     orderby: Vec<OrderBy>,
+    // set for where_exists / where_not_exists, always written as a `SELECT 1` EXISTS
+    test: Option<&'static str>,
 }
 
 impl ExistIn {
@@ -31,6 +36,7 @@ impl ExistIn {
         ExistIn {
             outer_column,
             inner_column,
+            shared_columns: Vec::default(),
             inner_tablename,
             inner_tablealias: sb.alias,
             wheres: sb.wheres,
@@ -38,9 +44,59 @@ impl ExistIn {
             limit: sb.limit,
             offset: sb.offset,
             orderby: sb.orderby,
+            test: None,
         }
     }
 
+    /// An `EXISTS` (or `NOT EXISTS`) test for related rows. Paging of the inner query is ignored
+    pub(crate) fn exists<T>(
+        sb: QueryBuilder<T>,
+        outer_column: String,
+        inner_tablename: String,
+        inner_column: String,
+        negate: bool,
+    ) -> Self {
+        Self::new(sb, outer_column, inner_tablename, inner_column).testing(negate)
+    }
+
+    /// Makes this an `EXISTS` (or `NOT EXISTS`) test, dropping any paging
+    pub(crate) fn testing(mut self, negate: bool) -> Self {
+        self.limit = None;
+        self.offset = None;
+        self.orderby = Vec::default();
+        self.test = Some(if negate { "NOT EXISTS" } else { "EXISTS" });
+        self
+    }
+
+    /// Goes through the join table of a many-to-many relationship,
+    /// `inner` is the test of the rows on the other side of it
+    pub(crate) fn through(
+        inner: ExistIn,
+        outer_column: String,
+        join_table: &str,
+        join_column: &str,
+    ) -> Self {
+        ExistIn {
+            outer_column,
+            inner_column: join_column.to_owned(),
+            shared_columns: Vec::default(),
+            inner_tablename: join_table.to_owned(),
+            inner_tablealias: String::default(),
+            wheres: Vec::default(),
+            inner_exists_ins: vec![inner],
+            limit: None,
+            offset: None,
+            orderby: Vec::default(),
+            test: None,
+        }
+    }
+
+    /// Columns that have to match as well as the key, they have the same name in both tables
+    pub(crate) fn sharing(mut self, columns: &[&str]) -> Self {
+        self.shared_columns = columns.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
     // re-assign all the alias and alias for sub-tables
     pub(crate) fn set_aliases(&mut self, alias_asigner: &Arc<TableAlias>) {
         self.inner_tablealias = alias_asigner.next();
@@ -50,10 +106,17 @@ impl ExistIn {
     }
 
     fn inner_fk_equal(&self, tablealias: &str) -> String {
-        format!(
+        let mut equal = format!(
             "{}.{} = {}.{}",
             self.inner_tablealias, self.inner_column, tablealias, self.outer_column
-        )
+        );
+        for column in &self.shared_columns {
+            equal = format!(
+                "{} AND {}.{} = {}.{}",
+                equal, self.inner_tablealias, column, tablealias, column
+            );
+        }
+        equal
     }
 
     fn tails(&self, syntax: Syntax) -> String {
@@ -62,10 +125,23 @@ impl ExistIn {
     }
 
     fn exists_clause(&self, syntax: Syntax, _tablealias: &str, inner_clauses: &str) -> String {
+        if let Some(test) = self.test {
+            return format!(
+                "{} ( SELECT 1 FROM {} {} WHERE {} )",
+                test,
+                table_name(&self.inner_tablename),
+                self.inner_tablealias,
+                inner_clauses
+            );
+        }
         let tails = self.tails(syntax);
         format!(
             "EXISTS ( SELECT {} FROM {} {} WHERE {} {})",
-            self.inner_column, self.inner_tablename, self.inner_tablealias, inner_clauses, tails
+            self.inner_column,
+            table_name(&self.inner_tablename),
+            self.inner_tablealias,
+            inner_clauses,
+            tails
         )
     }
 
@@ -77,13 +153,65 @@ impl ExistIn {
         if !inner_clauses.is_empty() {
             wheres = format!("WHERE {}", inner_clauses);
         }
+        if !self.shared_columns.is_empty() {
+            let mut inner = vec![self.inner_column.clone()];
+            inner.extend(self.shared_columns.iter().cloned());
+            let mut outer = vec![outcol];
+            let shared = self.shared_columns.iter();
+            outer.extend(shared.map(|c| format!("{}.{}", tablealias, c)));
+            let cols: Vec<String> = inner
+                .iter()
+                .map(|c| format!("{}.{}", self.inner_tablealias, c))
+                .collect();
+            let select = format!(
+                "SELECT {} FROM {} {} {} {}",
+                cols.join(", "),
+                table_name(&self.inner_tablename),
+                self.inner_tablealias,
+                wheres,
+                tails
+            );
+            return format!(" {} ", keys_in(syntax, &outer, &inner, &select));
+        }
         format!(
             " {} IN (SELECT {} FROM {} {} {} {}) ",
-            outcol, innercol, self.inner_tablename, self.inner_tablealias, wheres, tails
+            outcol,
+            innercol,
+            table_name(&self.inner_tablename),
+            self.inner_tablealias,
+            wheres,
+            tails
         )
     }
 }
 
+/// `outer IN ( subquery )`, for keys over several columns `(a, b) IN ( subquery )`.
+/// MSSQL has no row values, the keys are matched in an `EXISTS` over the subquery.
+/// `outer` are the columns with their table alias, `inner` the columns the subquery selects
+pub(crate) fn keys_in(
+    syntax: Syntax,
+    outer: &[String],
+    inner: &[String],
+    subquery: &str,
+) -> String {
+    if outer.len() == 1 {
+        return format!("{} IN ( {} )", outer[0], subquery);
+    }
+    if syntax != Syntax::Mssql {
+        return format!("({}) IN ( {} )", outer.join(", "), subquery);
+    }
+    let equals: Vec<String> = outer
+        .iter()
+        .zip(inner)
+        .map(|(outer, inner)| format!("welds_in.{} = {}", inner, outer))
+        .collect();
+    format!(
+        "EXISTS ( SELECT 1 FROM ( {} ) welds_in WHERE {} )",
+        subquery,
+        equals.join(" AND ")
+    )
+}
+
 impl ClauseAdder for ExistIn {
     fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
     where
@@ -97,6 +225,18 @@ impl ClauseAdder for ExistIn {
         }
     }
 
+    fn tables(&self, tables: &mut Vec<String>) -> bool {
+        tables.push(self.inner_tablename.clone());
+        let mut known = true;
+        for w in &self.wheres {
+            known &= w.tables(tables);
+        }
+        for sub in &self.inner_exists_ins {
+            known &= sub.tables(tables);
+        }
+        known
+    }
+
     fn clause(&self, syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
         let using_in = self.limit.is_some();
         let self_tablealias = alias;
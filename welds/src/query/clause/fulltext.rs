@@ -0,0 +1,177 @@
+use super::{AsFieldName, ClauseAdder, ParamArgs};
+use crate::model_traits::{HasSchema, TableIdent, TableInfo};
+use crate::writers::{table_name, NextParam};
+use crate::Syntax;
+
+/// The text columns searched by `where_matches`. A single column or a tuple of up to four
+pub trait SearchColumns {
+    fn colnames(&self) -> Vec<String>;
+}
+
+impl<A: AsFieldName<String>> SearchColumns for A {
+    fn colnames(&self) -> Vec<String> {
+        vec![self.colname().to_owned()]
+    }
+}
+
+impl<A: AsFieldName<String>, B: AsFieldName<String>> SearchColumns for (A, B) {
+    fn colnames(&self) -> Vec<String> {
+        vec![self.0.colname().to_owned(), self.1.colname().to_owned()]
+    }
+}
+
+impl<A, B, C> SearchColumns for (A, B, C)
+where
+    A: AsFieldName<String>,
+    B: AsFieldName<String>,
+    C: AsFieldName<String>,
+{
+    fn colnames(&self) -> Vec<String> {
+        vec![
+            self.0.colname().to_owned(),
+            self.1.colname().to_owned(),
+            self.2.colname().to_owned(),
+        ]
+    }
+}
+
+impl<A, B, C, D> SearchColumns for (A, B, C, D)
+where
+    A: AsFieldName<String>,
+    B: AsFieldName<String>,
+    C: AsFieldName<String>,
+    D: AsFieldName<String>,
+{
+    fn colnames(&self) -> Vec<String> {
+        vec![
+            self.0.colname().to_owned(),
+            self.1.colname().to_owned(),
+            self.2.colname().to_owned(),
+            self.3.colname().to_owned(),
+        ]
+    }
+}
+
+/// A full-text search of some columns of a table
+#[derive(Clone)]
+pub(crate) struct FullText {
+    // the schema and name of the table
+    pub(crate) ident: String,
+    pub(crate) cols: Vec<String>,
+    pub(crate) terms: String,
+}
+
+impl FullText {
+    pub(crate) fn new<T: HasSchema>(cols: Vec<String>, terms: String) -> FullText {
+        FullText {
+            ident: <T as HasSchema>::Schema::identifier().join("."),
+            cols,
+            terms,
+        }
+    }
+
+    // the name of the table without its schema, Sqlite searches the FTS5 column named after it
+    fn table(&self) -> String {
+        let table = TableIdent::parse(&table_name(&self.ident));
+        table.name().to_owned()
+    }
+
+    fn qualified(&self, alias: &str) -> Vec<String> {
+        self.cols
+            .iter()
+            .map(|c| format!("{}.{}", alias, c))
+            .collect()
+    }
+
+    // postgres searches all the columns as one document
+    fn tsvector(&self, alias: &str) -> String {
+        let cols: Vec<String> = self
+            .qualified(alias)
+            .iter()
+            .map(|c| format!("coalesce({}, '')", c))
+            .collect();
+        format!("to_tsvector({})", cols.join(" || ' ' || "))
+    }
+
+    /// The expression the rows are sorted by and its direction, the best match first.
+    /// `param` is None on Sqlite, which ranks the rows of the MATCH without it
+    pub(crate) fn rank(
+        &self,
+        syntax: Syntax,
+        alias: &str,
+        pk: &str,
+        param: Option<String>,
+    ) -> (String, &'static str) {
+        let param = param.unwrap_or_default();
+        match syntax {
+            Syntax::Postgres | Syntax::CockroachDb => (
+                format!(
+                    "ts_rank({}, plainto_tsquery({}))",
+                    self.tsvector(alias),
+                    param
+                ),
+                "DESC",
+            ),
+            Syntax::Mysql | Syntax::MariaDb => (
+                format!(
+                    "MATCH ({}) AGAINST ({})",
+                    self.qualified(alias).join(", "),
+                    param
+                ),
+                "DESC",
+            ),
+            // bm25 is lower for a better match
+            Syntax::Sqlite => (format!("bm25({}.{})", alias, self.table()), "ASC"),
+            Syntax::Mssql => (
+                format!(
+                    "(SELECT k.[RANK] FROM CONTAINSTABLE({}, ({}), {}) k WHERE k.[KEY] = {}.{})",
+                    table_name(&self.ident),
+                    self.cols.join(", "),
+                    param,
+                    alias,
+                    pk
+                ),
+                "DESC",
+            ),
+        }
+    }
+
+    /// Sqlite doesn't need the terms again to rank the rows
+    pub(crate) fn rank_has_param(syntax: Syntax) -> bool {
+        syntax != Syntax::Sqlite
+    }
+}
+
+impl ClauseAdder for FullText {
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        args.push(&self.terms);
+    }
+
+    fn clause(&self, syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
+        let p = next_params.next();
+        let sql = match syntax {
+            Syntax::Postgres | Syntax::CockroachDb => {
+                format!("{} @@ plainto_tsquery({})", self.tsvector(alias), p)
+            }
+            Syntax::Mysql | Syntax::MariaDb => {
+                format!(
+                    "MATCH ({}) AGAINST ({})",
+                    self.qualified(alias).join(", "),
+                    p
+                )
+            }
+            Syntax::Sqlite => format!(
+                "{}.{} MATCH '{{{}}} : (' || {} || ')'",
+                alias,
+                self.table(),
+                self.cols.join(" "),
+                p
+            ),
+            Syntax::Mssql => format!("CONTAINS(({}), {})", self.qualified(alias).join(", "), p),
+        };
+        Some(sql)
+    }
+}
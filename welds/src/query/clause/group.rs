@@ -0,0 +1,126 @@
+use super::raw::ClauseRaw;
+use super::{ClauseAdder, ClauseColManual, ParamArgs};
+use crate::model_traits::HasSchema;
+use crate::query::builder::ManualWhereParam;
+use crate::writers::NextParam;
+use crate::Syntax;
+use std::marker::PhantomData;
+
+/// A set of conditions joined with AND or OR, written in parentheses
+pub(crate) struct ClauseGroup {
+    joiner: &'static str,
+    clauses: Vec<Box<dyn ClauseAdder>>,
+}
+
+impl ClauseGroup {
+    pub(crate) fn new(joiner: &'static str, clauses: Vec<Box<dyn ClauseAdder>>) -> Self {
+        Self { joiner, clauses }
+    }
+}
+
+impl ClauseAdder for ClauseGroup {
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        for clause in &self.clauses {
+            clause.bind(args);
+        }
+    }
+
+    fn clause(&self, syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
+        let parts: Vec<String> = self
+            .clauses
+            .iter()
+            .filter_map(|c| c.clause(syntax, alias, next_params))
+            .collect();
+        if parts.is_empty() {
+            return None;
+        }
+        let joiner = format!(" {} ", self.joiner);
+        Some(format!("( {} )", parts.join(&joiner)))
+    }
+
+    fn tables(&self, tables: &mut Vec<String>) -> bool {
+        let mut known = true;
+        for clause in &self.clauses {
+            known &= clause.tables(tables);
+        }
+        known
+    }
+}
+
+/// Collects the conditions of a `where_any` or `where_all` group.
+///
+/// Groups can be nested to write any mix of AND and OR:
+/// ```rust,ignore
+/// Order::where_col(|o| o.created_at.gt(since))
+///     .where_any(|g| {
+///         g.where_col(|o| o.status.equal("new"))
+///             .where_col(|o| o.status.equal("retry"))
+///     })
+/// // WHERE ( t1.created_at > $1 AND ( t1.status = $2 OR t1.status = $3 ) )
+/// ```
+pub struct WhereGroup<T> {
+    _t: PhantomData<T>,
+    clauses: Vec<Box<dyn ClauseAdder>>,
+}
+
+impl<T> WhereGroup<T>
+where
+    T: HasSchema,
+{
+    pub(crate) fn build(
+        joiner: &'static str,
+        lam: impl Fn(WhereGroup<T>) -> WhereGroup<T>,
+    ) -> Box<dyn ClauseAdder> {
+        let group = lam(WhereGroup {
+            _t: Default::default(),
+            clauses: Vec::default(),
+        });
+        Box::new(ClauseGroup::new(joiner, group.clauses))
+    }
+
+    /// Adds a condition on one of the columns of the table to this group
+    pub fn where_col(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> Box<dyn ClauseAdder>,
+    ) -> Self
+    where
+        <T as HasSchema>::Schema: Default,
+    {
+        self.clauses.push(lam(Default::default()));
+        self
+    }
+
+    /// Adds custom sql to this group. Works like `QueryBuilder::where_manual2`
+    pub fn where_manual2(mut self, sql: &'static str, params: ManualWhereParam) -> Self {
+        self.clauses.push(Box::new(ClauseColManual {
+            col: None,
+            sql: sql.to_string(),
+            params: params.into_inner(),
+        }));
+        self
+    }
+
+    /// Adds a fragment of raw SQL to this group. Works like `QueryBuilder::where_raw`
+    pub fn where_raw(mut self, sql: impl Into<String>, params: ManualWhereParam) -> Self {
+        self.clauses.push(Box::new(ClauseRaw {
+            sql: sql.into(),
+            params: params.into_inner(),
+        }));
+        self
+    }
+
+    /// Adds a nested group to this group, where any one of its conditions has to match
+    pub fn where_any(mut self, lam: impl Fn(WhereGroup<T>) -> WhereGroup<T>) -> Self {
+        self.clauses.push(Self::build("OR", lam));
+        self
+    }
+
+    /// Adds a nested group to this group, where all of its conditions have to match
+    pub fn where_all(mut self, lam: impl Fn(WhereGroup<T>) -> WhereGroup<T>) -> Self {
+        self.clauses.push(Self::build("AND", lam));
+        self
+    }
+}
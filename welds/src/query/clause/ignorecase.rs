@@ -0,0 +1,60 @@
+use super::{ClauseAdder, ParamArgs};
+use crate::writers::NextParam;
+use crate::Syntax;
+use welds_connections::Param;
+
+/// How a case-insensitive clause compares the column to its value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum IgnoreCase {
+    Like,
+    Equal,
+}
+
+/// Compares a text column to a value ignoring case.
+/// Postgres has ILIKE, the other backends compare both sides lowercased
+pub(crate) struct ClauseIgnoreCase<T> {
+    pub(crate) null_clause: bool,
+    pub(crate) not_clause: bool,
+    pub(crate) col: String,
+    pub(crate) compare: IgnoreCase,
+    pub(crate) val: Option<T>,
+}
+
+impl<T> ClauseAdder for ClauseIgnoreCase<T>
+where
+    T: Clone + Send + Sync + Param,
+{
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        if !self.null_clause {
+            if let Some(val) = &self.val {
+                args.push(val);
+            }
+        }
+    }
+
+    fn clause(&self, syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
+        let col = format!("{}.{}", alias, self.col);
+
+        // a null value can't have a case
+        if self.null_clause {
+            let not = if self.not_clause { "NOT " } else { "" };
+            return Some(format!("{} IS {}NULL", col, not));
+        }
+
+        let np = next_params.next();
+        let pg = matches!(syntax, Syntax::Postgres | Syntax::CockroachDb);
+        let clause = match (self.compare, self.not_clause) {
+            (IgnoreCase::Like, false) if pg => format!("{} ilike {}", col, np),
+            (IgnoreCase::Like, true) if pg => format!("{} not ilike {}", col, np),
+            (IgnoreCase::Like, false) => format!("LOWER({}) like LOWER({})", col, np),
+            (IgnoreCase::Like, true) => format!("LOWER({}) not like LOWER({})", col, np),
+            // ILIKE would treat a % or _ in the value as a wildcard
+            (IgnoreCase::Equal, false) => format!("LOWER({}) = LOWER({})", col, np),
+            (IgnoreCase::Equal, true) => format!("LOWER({}) != LOWER({})", col, np),
+        };
+        Some(clause)
+    }
+}
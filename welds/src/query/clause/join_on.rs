@@ -0,0 +1,33 @@
+use super::AsFieldName;
+
+/// The columns two tables are joined on. Made with `eq` on a column of each table,
+/// more columns are added with `and`
+///
+/// ```rust,ignore
+/// Order::all()
+///     .select(|o| o.id)
+///     .join_on(|o, s| o.ship_to.eq(s.code).and(o.region.eq(s.region)), Store::all().select(|s| s.name))
+/// ```
+pub struct JoinOn {
+    pub(crate) pairs: Vec<(String, String)>,
+}
+
+impl JoinOn {
+    /// Also join on the columns of another condition
+    pub fn and(mut self, other: JoinOn) -> JoinOn {
+        self.pairs.extend(other.pairs);
+        self
+    }
+}
+
+/// Adds `eq` to the columns of a schema, for writing the condition of a join
+pub trait JoinColumn<V>: AsFieldName<V> + Sized {
+    /// Join the rows where this column equals the column of the other table
+    fn eq(self, other: impl AsFieldName<V>) -> JoinOn {
+        JoinOn {
+            pairs: vec![(self.colname().to_owned(), other.colname().to_owned())],
+        }
+    }
+}
+
+impl<V, F: AsFieldName<V>> JoinColumn<V> for F {}
@@ -17,10 +17,26 @@ pub use text::Text;
 mod textopt;
 pub use textopt::TextOpt;
 
+pub(crate) mod ignorecase;
+
 pub(crate) mod manualwhereparam;
 
+mod fulltext;
+pub(crate) use fulltext::FullText;
+pub use fulltext::SearchColumns;
+mod colcol;
+pub use colcol::CompareColumn;
+mod join_on;
+pub use join_on::{JoinColumn, JoinOn};
+
+pub(crate) mod group;
+pub use group::WhereGroup;
+pub(crate) mod raw;
+pub(crate) mod sample;
+
 //  Relationships / SubQueries
 pub(crate) mod exists;
+pub(crate) mod subquery;
 pub(crate) mod wherein;
 
 pub(crate) mod orderby;
@@ -51,6 +67,16 @@ pub trait AsFieldName<T> {
     fn fieldname(&self) -> &str;
 }
 
+// lets a field be used more than once in the same lambda, `avg(&x.total), max(&x.total)`
+impl<T, F: AsFieldName<T>> AsFieldName<T> for &F {
+    fn colname(&self) -> &str {
+        (*self).colname()
+    }
+    fn fieldname(&self) -> &str {
+        (*self).fieldname()
+    }
+}
+
 // marker trait to make sure a field is nullable
 pub trait AsOptField {}
 
@@ -62,6 +88,12 @@ pub trait ClauseAdder: Send + Sync {
 
     /// Returns the SQL snipit for this clause
     fn clause(&self, syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String>;
+
+    /// Adds the tables the sub-queries of this clause read, for tagging cached queries.
+    /// Returns false if it might read tables that can't be named, like raw SQL with a SELECT
+    fn tables(&self, _tables: &mut Vec<String>) -> bool {
+        true
+    }
 }
 
 impl<T> ClauseAdder for ClauseColVal<T>
@@ -141,6 +173,10 @@ impl ClauseAdder for ClauseColManual {
         }
     }
 
+    fn tables(&self, _tables: &mut Vec<String>) -> bool {
+        !raw::has_subquery(&self.sql)
+    }
+
     fn clause(&self, _syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
         // build the column name
         let mut parts = vec![];
@@ -1,4 +1,8 @@
+use super::subquery::ClauseInQuery;
 use super::{AsFieldName, ClauseAdder, ClauseColVal, ClauseColValList};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::select_cols::SelectBuilder;
+use crate::query::update::bulk::SetExpr;
 use std::marker::PhantomData;
 use welds_connections::Param;
 
@@ -158,4 +162,56 @@ where
         };
         Box::new(cv)
     }
+
+    /// Adds to the current value of the column, for `set_expr`
+    pub fn increment(self, v: impl Into<T>) -> SetExpr
+    where
+        T: Param,
+    {
+        SetExpr::new(self.col, "+", v.into())
+    }
+
+    /// Subtracts from the current value of the column, for `set_expr`
+    pub fn decrement(self, v: impl Into<T>) -> SetExpr
+    where
+        T: Param,
+    {
+        SetExpr::new(self.col, "-", v.into())
+    }
+
+    /// Multiplies the current value of the column, for `set_expr`
+    #[allow(clippy::should_implement_trait)]
+    pub fn mul(self, v: impl Into<T>) -> SetExpr
+    where
+        T: Param,
+    {
+        SetExpr::new(self.col, "*", v.into())
+    }
+
+    /// Divides the current value of the column, for `set_expr`
+    #[allow(clippy::should_implement_trait)]
+    pub fn div(self, v: impl Into<T>) -> SetExpr
+    where
+        T: Param,
+    {
+        SetExpr::new(self.col, "/", v.into())
+    }
+
+    /// Will write SQL checking the value is one of the values the query selects (IN)
+    pub fn in_query<R>(self, query: SelectBuilder<R>) -> Box<dyn ClauseAdder>
+    where
+        R: Send + Sync + HasSchema + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        ClauseInQuery::boxed(self.col, "IN", query)
+    }
+
+    /// Will write SQL checking the value is none of the values the query selects (NOT IN)
+    pub fn not_in_query<R>(self, query: SelectBuilder<R>) -> Box<dyn ClauseAdder>
+    where
+        R: Send + Sync + HasSchema + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        ClauseInQuery::boxed(self.col, "NOT IN", query)
+    }
 }
@@ -1,6 +1,19 @@
+use crate::Syntax;
+
+#[derive(Clone)]
 pub struct OrderBy {
     pub(crate) field: String,
     pub(crate) direction: String,
+    pub(crate) nulls: Option<Nulls>,
+    pub(crate) collate: Option<String>,
+    pub(crate) random: bool,
+}
+
+/// Where the NULLs go in an ordering
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Nulls {
+    First,
+    Last,
 }
 
 impl OrderBy {
@@ -8,44 +21,83 @@ impl OrderBy {
         Self {
             field: field.into(),
             direction: dir.into(),
+            nulls: None,
+            collate: None,
+            random: false,
+        }
+    }
+
+    /// Orders the rows randomly. It has no direction, keyset paging turns it down
+    pub(crate) fn random() -> Self {
+        Self {
+            random: true,
+            ..Self::new("random()", "")
+        }
+    }
+
+    pub(crate) fn write(&self, syntax: Syntax) -> String {
+        if self.random {
+            let random = match syntax {
+                Syntax::Postgres | Syntax::CockroachDb | Syntax::Sqlite => "random()",
+                Syntax::Mysql | Syntax::MariaDb => "RAND()",
+                Syntax::Mssql => "NEWID()",
+            };
+            return random.to_owned();
+        }
+        let mut expr = self.field.clone();
+        if let Some(collate) = &self.collate {
+            // postgres collations are identifiers, "C" would be folded to lowercase
+            expr = match syntax {
+                Syntax::Postgres | Syntax::CockroachDb => {
+                    format!("{} COLLATE \"{}\"", expr, collate)
+                }
+                _ => format!("{} COLLATE {}", expr, collate),
+            };
+        }
+        let ordered = format!("{} {}", expr, self.direction);
+        let nulls = match self.nulls {
+            Some(nulls) => nulls,
+            None => return ordered,
+        };
+        match syntax {
+            Syntax::Postgres | Syntax::CockroachDb | Syntax::Sqlite => match nulls {
+                Nulls::First => format!("{} NULLS FIRST", ordered),
+                Nulls::Last => format!("{} NULLS LAST", ordered),
+            },
+            // no NULLS FIRST/LAST, the NULLs are sorted on their own first
+            Syntax::Mysql | Syntax::MariaDb | Syntax::Mssql => {
+                let (null, not_null) = match nulls {
+                    Nulls::First => (0, 1),
+                    Nulls::Last => (1, 0),
+                };
+                format!(
+                    "CASE WHEN {} IS NULL THEN {} ELSE {} END, {}",
+                    self.field, null, not_null, ordered
+                )
+            }
         }
     }
 }
 
-pub(crate) fn to_sql(parts: &[OrderBy]) -> String {
+pub(crate) fn to_sql(syntax: Syntax, parts: &[OrderBy]) -> String {
     if parts.is_empty() {
         return "".to_owned();
     }
-    let bys: Vec<String> = parts
-        .iter()
-        .map(|p| format!("{} {}", p.field, p.direction))
-        .collect();
+    let bys: Vec<String> = parts.iter().map(|p| p.write(syntax)).collect();
     let bys = bys.join(", ");
     format!("ORDER BY {}", bys)
 }
 
 #[test]
 fn single_order_by_field() {
-    let parts = vec![OrderBy {
-        field: "f1".to_owned(),
-        direction: "desc".to_owned(),
-    }];
-    let clause = to_sql(&parts);
+    let parts = vec![OrderBy::new("f1", "desc")];
+    let clause = to_sql(Syntax::Postgres, &parts);
     assert_eq!(clause.as_str(), "ORDER BY f1 desc")
 }
 
 #[test]
 fn order_by_field_two_fields() {
-    let parts = vec![
-        OrderBy {
-            field: "f1".to_owned(),
-            direction: "desc".to_owned(),
-        },
-        OrderBy {
-            field: "f2".to_owned(),
-            direction: "asc".to_owned(),
-        },
-    ];
-    let clause = to_sql(&parts);
+    let parts = vec![OrderBy::new("f1", "desc"), OrderBy::new("f2", "asc")];
+    let clause = to_sql(Syntax::Postgres, &parts);
     assert_eq!(clause.as_str(), "ORDER BY f1 desc, f2 asc")
 }
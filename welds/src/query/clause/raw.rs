@@ -0,0 +1,60 @@
+use super::{ClauseAdder, ParamArgs};
+use crate::writers::NextParam;
+use crate::Syntax;
+use welds_connections::Param;
+
+/// A where fragment written by hand. Each `?` is swapped for the next placeholder of the
+/// backend (`$3`, `@p3`, `?`). Quoted text is left alone and `??` is written as a literal `?`
+pub(crate) struct ClauseRaw {
+    pub(crate) sql: String,
+    pub(crate) params: Vec<Box<dyn Param + Send + Sync>>,
+}
+
+// SQL written by hand with a SELECT in it reads tables that can't be named
+pub(crate) fn has_subquery(sql: &str) -> bool {
+    sql.to_ascii_uppercase().contains("SELECT")
+}
+
+impl ClauseAdder for ClauseRaw {
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        for p in &self.params {
+            args.push(p.as_ref());
+        }
+    }
+
+    fn tables(&self, _tables: &mut Vec<String>) -> bool {
+        !has_subquery(&self.sql)
+    }
+
+    fn clause(&self, _syntax: Syntax, _alias: &str, next_params: &NextParam) -> Option<String> {
+        let mut out = String::with_capacity(self.sql.len());
+        let mut chars = self.sql.chars().peekable();
+        let mut quote: Option<char> = None;
+
+        while let Some(c) = chars.next() {
+            match (quote, c) {
+                (Some(q), c) => {
+                    out.push(c);
+                    if c == q {
+                        quote = None;
+                    }
+                }
+                (None, '\'' | '"') => {
+                    out.push(c);
+                    quote = Some(c);
+                }
+                (None, '?') if chars.peek() == Some(&'?') => {
+                    chars.next();
+                    out.push('?');
+                }
+                (None, '?') => out.push_str(&next_params.next()),
+                (None, c) => out.push(c),
+            }
+        }
+
+        Some(format!("( {} )", out))
+    }
+}
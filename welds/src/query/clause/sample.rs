@@ -0,0 +1,35 @@
+use crate::Syntax;
+
+/// A random share of the rows of a table, made with `QueryBuilder::sample_percent`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Sample {
+    pub(crate) percent: f64,
+}
+
+impl Sample {
+    /// The sampled table and its alias, as written after the FROM.
+    /// Postgres and MSSQL read the share with TABLESAMPLE, the others select from the rows
+    /// that are each kept by chance
+    pub(crate) fn write_table(&self, syntax: Syntax, table: &str, alias: &str) -> String {
+        let fraction = self.percent / 100.0;
+        let keep = match syntax {
+            Syntax::Postgres => {
+                return format!(
+                    "{} {} TABLESAMPLE BERNOULLI ({})",
+                    table, alias, self.percent
+                )
+            }
+            Syntax::Mssql => {
+                return format!("{} {} TABLESAMPLE ({} PERCENT)", table, alias, self.percent)
+            }
+            Syntax::CockroachDb => format!("random() < {}", fraction),
+            Syntax::Mysql | Syntax::MariaDb => format!("RAND() < {}", fraction),
+            // random() is a 64 bit integer
+            Syntax::Sqlite => format!(
+                "(ABS(RANDOM()) % 1000000) < {}",
+                (fraction * 1000000.0) as i64
+            ),
+        };
+        format!("(SELECT * FROM {} WHERE {}) {}", table, keep, alias)
+    }
+}
@@ -0,0 +1,61 @@
+use super::{ClauseAdder, ParamArgs};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::select_cols::SelectBuilder;
+use crate::writers::alias::TableAlias;
+use crate::writers::NextParam;
+use crate::Syntax;
+use std::sync::Arc;
+
+/// `col IN (SELECT ...)`, the sub-query selecting the values to match against.
+///
+/// The tables of the sub-query are aliased s1, s2, ... so it can refer to the outer query's
+/// tables (t1, ...) in a `where_raw` when it needs to be correlated
+pub(crate) struct ClauseInQuery<R> {
+    col: String,
+    operator: &'static str,
+    query: SelectBuilder<R>,
+}
+
+impl<R> ClauseInQuery<R>
+where
+    R: Send + Sync + HasSchema + 'static,
+    <R as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    pub(crate) fn boxed(
+        col: String,
+        operator: &'static str,
+        mut query: SelectBuilder<R>,
+    ) -> Box<dyn ClauseAdder> {
+        query.set_aliases(&Arc::new(TableAlias::with_prefix("s")));
+        Box::new(Self {
+            col,
+            operator,
+            query,
+        })
+    }
+}
+
+impl<R> ClauseAdder for ClauseInQuery<R>
+where
+    R: Send + Sync + HasSchema,
+    <R as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        self.query.bind_subquery(args);
+    }
+
+    fn clause(&self, syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
+        let inner = self.query.subquery_sql(syntax, next_params);
+        Some(format!(
+            "{}.{} {} ({})",
+            alias, self.col, self.operator, inner
+        ))
+    }
+
+    fn tables(&self, tables: &mut Vec<String>) -> bool {
+        self.query.tables(tables)
+    }
+}
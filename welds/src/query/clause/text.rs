@@ -1,4 +1,8 @@
+use super::ignorecase::{ClauseIgnoreCase, IgnoreCase};
+use super::subquery::ClauseInQuery;
 use super::{AsFieldName, ClauseAdder, ClauseColVal};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::select_cols::SelectBuilder;
 use std::marker::PhantomData;
 use welds_connections::Param;
 
@@ -85,31 +89,65 @@ where
         Box::new(cv)
     }
 
+    /// Case-insensitive LIKE. ILIKE on Postgres, `LOWER(col) like LOWER(?)` elsewhere
     pub fn ilike(self, v: impl Into<T>) -> Box<dyn ClauseAdder>
     where
         T: Param,
     {
-        let cv = ClauseColVal::<T> {
-            null_clause: false,
-            not_clause: false,
-            col: self.col,
-            operator: "ilike",
-            val: Some(v.into()),
-        };
-        Box::new(cv)
+        self.ignore_case(IgnoreCase::Like, false, v.into())
     }
 
     pub fn not_ilike(self, v: impl Into<T>) -> Box<dyn ClauseAdder>
     where
         T: Param,
     {
-        let cv = ClauseColVal::<T> {
+        self.ignore_case(IgnoreCase::Like, true, v.into())
+    }
+
+    /// Equal ignoring case, `LOWER(col) = LOWER(?)`
+    pub fn eq_ignore_case(self, v: impl Into<T>) -> Box<dyn ClauseAdder>
+    where
+        T: Param,
+    {
+        self.ignore_case(IgnoreCase::Equal, false, v.into())
+    }
+
+    pub fn not_eq_ignore_case(self, v: impl Into<T>) -> Box<dyn ClauseAdder>
+    where
+        T: Param,
+    {
+        self.ignore_case(IgnoreCase::Equal, true, v.into())
+    }
+
+    fn ignore_case(self, compare: IgnoreCase, not_clause: bool, v: T) -> Box<dyn ClauseAdder>
+    where
+        T: Param,
+    {
+        let cv = ClauseIgnoreCase::<T> {
             null_clause: false,
-            not_clause: true,
+            not_clause,
             col: self.col,
-            operator: "not ilike",
-            val: Some(v.into()),
+            compare,
+            val: Some(v),
         };
         Box::new(cv)
     }
+
+    /// Will write SQL checking the value is one of the values the query selects (IN)
+    pub fn in_query<R>(self, query: SelectBuilder<R>) -> Box<dyn ClauseAdder>
+    where
+        R: Send + Sync + HasSchema + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        ClauseInQuery::boxed(self.col, "IN", query)
+    }
+
+    /// Will write SQL checking the value is none of the values the query selects (NOT IN)
+    pub fn not_in_query<R>(self, query: SelectBuilder<R>) -> Box<dyn ClauseAdder>
+    where
+        R: Send + Sync + HasSchema + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        ClauseInQuery::boxed(self.col, "NOT IN", query)
+    }
 }
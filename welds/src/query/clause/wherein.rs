@@ -8,6 +8,7 @@ use crate::query::clause::ParamArgs;
 use crate::query::helpers::{build_tail, build_where, join_sql_parts};
 use crate::writers::ColumnWriter;
 use crate::writers::NextParam;
+use crate::writers::{table_alias, table_name};
 use crate::Syntax;
 
 /// Used to generated a SQL IN clause.
@@ -76,8 +77,9 @@ fn build_head_select<S>(syntax: Syntax, tablealias: &str) -> Option<String>
 where
     S: TableInfo + UniqueIdentifier,
 {
-    let mut tablename = S::identifier().join(".");
-    if tablename != tablealias {
+    let table = S::identifier().join(".");
+    let mut tablename = table_name(&table);
+    if table_alias(&table) != tablealias {
         tablename = format!("{} {}", tablename, tablealias);
     }
     let writer = ColumnWriter::new(syntax);
@@ -1,18 +1,21 @@
 use super::super::{
     builder::QueryBuilder,
     clause::{wherein::WhereIn, ClauseAdder},
-    helpers::{build_where, join_sql_parts},
+    helpers::{build_where, invalidate_cached, join_sql_parts},
 };
-use crate::errors::Result;
+use crate::errors::{Result, WeldsError};
 use crate::model_traits::UniqueIdentifier;
 use crate::model_traits::{HasSchema, TableColumns, TableInfo};
 use crate::query::clause::ParamArgs;
+use crate::relations::{has_parents, leave_parents_of, parents_transaction};
+use crate::state::DbState;
 use crate::writers::ColumnWriter;
 use crate::writers::CountWriter;
 use crate::writers::LimitSkipWriter;
 use crate::writers::NextParam;
+use crate::writers::{in_namespace, table_alias, table_name};
 use crate::Syntax;
-use welds_connections::Client;
+use welds_connections::{Client, Row, TransactStart};
 
 // ******************************************************************************************
 // This file contains code on a Query builder to allow it to bulk delete
@@ -24,7 +27,9 @@ where
 {
     /// The SQL to delete a `DELETE FROM ... `
     ///
-    /// return SQL to delete all the resulting rows from the database
+    /// return SQL to delete all the resulting rows from the database.
+    /// The rows of a soft deleted model are marked deleted (`UPDATE ... SET deleted_at = CURRENT_TIMESTAMP`)
+    /// unless `force_delete` is used
     pub fn delete_sql(&self, syntax: Syntax) -> String
     where
         <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
@@ -33,7 +38,7 @@ where
         // This is needed if the user has a limit
         let mut w_in = WhereIn::new(self);
 
-        self.delete_sql_internal(syntax, &mut w_in, &mut None)
+        self.delete_sql_internal(syntax, &mut w_in, &mut None, false)
     }
 
     fn delete_sql_internal<'s, 'w, 'args, 'p>(
@@ -41,6 +46,7 @@ where
         syntax: Syntax,
         w_in: &'w mut WhereIn<T>,
         args: &'args mut Option<ParamArgs<'p>>,
+        returning: bool,
     ) -> String
     where
         'w: 'p,
@@ -50,17 +56,39 @@ where
         let next_params = NextParam::new(syntax);
 
         // Note: for deletes we can't alias the FROM tablename
-        let alias = <T as HasSchema>::Schema::identifier().join(".");
+        let alias = table_alias(&<T as HasSchema>::Schema::identifier().join("."));
+
+        // a soft deleted row is kept, marked with the time it was deleted
+        let soft_delete = match self.force_delete {
+            false => <T as HasSchema>::Schema::soft_delete_column(),
+            true => None,
+        };
+
+        // MSSQL outputs the rows before the WHERE, everyone else returns them at the end
+        let (output, returning) = match (returning, syntax) {
+            (false, _) => (None, None),
+            (true, Syntax::Mssql) => {
+                let prefix = match soft_delete {
+                    Some(_) => "Inserted",
+                    None => "Deleted",
+                };
+                (Some(build_output::<<T as HasSchema>::Schema>(prefix)), None)
+            }
+            (true, _) => (None, Some("RETURNING *".to_owned())),
+        };
 
         join_sql_parts(&[
-            build_head_delete::<<T as HasSchema>::Schema>(syntax),
+            build_head_delete::<<T as HasSchema>::Schema>(syntax, soft_delete),
+            output,
             build_where_delete(syntax, &next_params, &alias, args, self, w_in),
+            returning,
         ])
     }
 
     /// Executes a `DELETE FROM ... `
     ///
-    /// deletes all the resulting rows from the database
+    /// deletes all the resulting rows from the database. The rows of a soft deleted model are
+    /// marked deleted instead, unless `force_delete` is used
     pub async fn delete<'s, 'c>(&'s self, client: &'c dyn Client) -> Result<()>
     where
         <T as HasSchema>::Schema: UniqueIdentifier + TableInfo + TableColumns,
@@ -72,19 +100,119 @@ where
 
         let syntax = client.syntax();
         let mut args: Option<ParamArgs> = Some(Vec::default());
-        let sql = self.delete_sql_internal(syntax, &mut w_in, &mut args);
+        let sql = in_namespace(client, || {
+            self.delete_sql_internal(syntax, &mut w_in, &mut args, false)
+        });
         let args: ParamArgs = args.unwrap();
-        client.execute(&sql, &args).await?;
+
+        // the rows and the parents they count are written together
+        match parents_transaction::<T>(client) {
+            Some(start) => {
+                let trans = start.begin().await?;
+                leave_parents_of(self, &trans).await?;
+                trans.execute(&sql, &args).await?;
+                trans.commit().await?;
+            }
+            None => {
+                leave_parents_of(self, client).await?;
+                client.execute(&sql, &args).await?;
+            }
+        }
+        invalidate_cached::<T>(client).await;
         Ok(())
     }
+
+    /// Deletes all the resulting rows from the database, returning the rows that were deleted
+    ///
+    /// Uses `RETURNING` (`OUTPUT` on MSSQL). MySql doesn't have either, and MariaDB can't
+    /// return the rows a soft delete updates, they select the rows `FOR UPDATE` and delete them
+    /// in a transaction
+    pub async fn delete_returning<C>(&self, client: &C) -> Result<Vec<DbState<T>>>
+    where
+        C: Client + TransactStart + ?Sized,
+        <T as HasSchema>::Schema: UniqueIdentifier + TableInfo + TableColumns,
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let syntax = client.syntax();
+        let rows = match syntax {
+            Syntax::Mysql | Syntax::MariaDb => {
+                let trans = client.begin().await?;
+                let mut args: Option<ParamArgs> = Some(Vec::default());
+                let mut select = in_namespace(client, || self.select_sql(syntax, &mut args));
+                if self.lock.is_none() {
+                    select = format!("{} FOR UPDATE", select);
+                }
+                let rows = trans.fetch_rows(&select, &args.unwrap()).await?;
+                self.delete(&trans).await?;
+                trans.commit().await?;
+                rows
+            }
+            // the rows and the parents they count are written together
+            _ if has_parents::<T>() => {
+                let trans = client.begin().await?;
+                leave_parents_of(self, &trans).await?;
+                let rows = self.delete_rows_returning(&trans).await?;
+                trans.commit().await?;
+                invalidate_cached::<T>(client).await;
+                rows
+            }
+            _ => {
+                let rows = self.delete_rows_returning(client).await?;
+                invalidate_cached::<T>(client).await;
+                rows
+            }
+        };
+
+        let mut objs = Vec::default();
+        for row in rows {
+            let obj: T = T::try_from(row)?;
+            objs.push(DbState::db_loaded(obj));
+        }
+        Ok(objs)
+    }
+
+    // deletes the rows with RETURNING (OUTPUT on MSSQL)
+    async fn delete_rows_returning<C>(&self, client: &C) -> Result<Vec<Row>>
+    where
+        C: Client + ?Sized,
+        <T as HasSchema>::Schema: UniqueIdentifier + TableInfo + TableColumns,
+    {
+        let syntax = client.syntax();
+        let mut w_in = WhereIn::new(self);
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let sql = in_namespace(client, || {
+            self.delete_sql_internal(syntax, &mut w_in, &mut args, true)
+        });
+        Ok(client.fetch_rows(&sql, &args.unwrap()).await?)
+    }
+}
+
+// the deleted rows, as MSSQL writes them. `Inserted` for the rows a soft delete updated
+fn build_output<S>(prefix: &str) -> String
+where
+    S: TableColumns,
+{
+    let writer = ColumnWriter::new(Syntax::Mssql);
+    let cols: Vec<String> = S::columns()
+        .iter()
+        .map(|c| writer.write(prefix, c))
+        .collect();
+    format!("OUTPUT {}", cols.join(", "))
 }
 
-fn build_head_delete<S>(syntax: Syntax) -> Option<String>
+fn build_head_delete<S>(syntax: Syntax, soft_delete: Option<&str>) -> Option<String>
 where
     S: TableInfo + TableColumns,
 {
-    let identifier = S::identifier().join(".");
-    Some(format!("DELETE FROM {}", identifier))
+    let identifier = table_name(&S::identifier().join("."));
+    Some(match soft_delete {
+        Some(col) => {
+            let col = ColumnWriter::new(syntax).excape(col);
+            format!("UPDATE {} SET {} = CURRENT_TIMESTAMP", identifier, col)
+        }
+        None => format!("DELETE FROM {}", identifier),
+    })
 }
 
 fn build_where_delete<'args, 'p, 'qb, 'w, T>(
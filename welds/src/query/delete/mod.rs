@@ -1,31 +1,57 @@
 use crate::errors::Result;
 use crate::errors::WeldsError;
 use crate::model_traits::hooks::{AfterDelete, BeforeDelete};
-use crate::model_traits::{HasSchema, TableColumns, TableInfo, WriteToArgs};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo, UpdateFromRow, WriteToArgs};
 use crate::query::clause::ParamArgs;
+use crate::query::helpers::{invalidate_cached, push_default_scope};
+use crate::query::update::bulk::build_output;
+use crate::relations::{parents_transaction, update_parents};
 use crate::writers::ColumnWriter;
 use crate::writers::NextParam;
-use welds_connections::Client;
+use crate::writers::{in_namespace, table_alias, table_name};
+use crate::Syntax;
+use welds_connections::{Client, Row};
 
 pub mod bulk;
 
-pub async fn delete_one<T>(obj: &T, client: &dyn Client) -> Result<()>
+pub async fn delete_one<T>(obj: &mut T, client: &dyn Client) -> Result<()>
 where
-    T: HasSchema + WriteToArgs,
+    T: HasSchema + WriteToArgs + UpdateFromRow,
     <T as HasSchema>::Schema: TableInfo + TableColumns,
     T: AfterDelete + BeforeDelete,
 {
     BeforeDelete::before(obj)?;
+    // the row and the parents it counts are written together
+    match parents_transaction::<T>(client) {
+        Some(start) => {
+            let trans = start.begin().await?;
+            delete_with_parents(obj, &trans).await?;
+            trans.commit().await?;
+        }
+        None => delete_with_parents(obj, client).await?,
+    }
+    AfterDelete::after(obj);
+    Ok(())
+}
+
+async fn delete_with_parents<T>(obj: &mut T, client: &dyn Client) -> Result<()>
+where
+    T: HasSchema + WriteToArgs + UpdateFromRow,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
     let syntax = client.syntax();
     let col_writer = ColumnWriter::new(syntax);
     let next_params = NextParam::new(syntax);
-    let identifier = <<T as HasSchema>::Schema>::identifier().join(".");
+    let table = <<T as HasSchema>::Schema>::identifier().join(".");
+    let identifier = in_namespace(client, || table_name(&table));
+    let alias = in_namespace(client, || table_alias(&table));
 
     let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
     if pks.is_empty() {
         return Err(WeldsError::NoPrimaryKey);
     }
 
+    let scope = <<T as HasSchema>::Schema as TableInfo>::default_scope();
     let mut args: ParamArgs = Vec::default();
     let mut wheres = Vec::default();
     for col in pks {
@@ -34,15 +60,86 @@ where
         let colname = col_writer.excape(col.name());
         wheres.push(format!("{}={}", colname, p));
     }
+    push_default_scope(&scope, syntax, &alias, &next_params, &mut wheres, &mut args);
 
     let wheres = wheres.join(" AND ");
 
-    let sql = format!("DELETE FROM {} where {}", identifier, wheres);
+    let deleted = match <<T as HasSchema>::Schema as TableInfo>::soft_delete_column() {
+        Some(col) => {
+            let mut rows = soft_delete(&*obj, col, &identifier, &wheres, &args, client).await?;
+            match rows.first_mut() {
+                Some(row) => {
+                    obj.update_from_row(row)?;
+                    1
+                }
+                None => 0,
+            }
+        }
+        None => {
+            let sql = format!("DELETE FROM {} where {}", identifier, wheres);
+            client.execute(&sql, &args).await?.rows_affected()
+        }
+    };
+    invalidate_cached::<T>(client).await;
+    if deleted > 0 {
+        update_parents(obj, -1, client).await?;
+    }
+    Ok(())
+}
 
-    client.execute(&sql, &args).await?;
-    AfterDelete::after(obj);
+// A soft deleted row is kept, marked with the time it was deleted. Gives back the row, read
+// back for that time. A row that was already deleted isn't marked again, and isn't given back
+async fn soft_delete<T>(
+    obj: &T,
+    col: &str,
+    identifier: &str,
+    wheres: &str,
+    args: &ParamArgs<'_>,
+    client: &dyn Client,
+) -> Result<Vec<Row>>
+where
+    T: HasSchema + WriteToArgs,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let syntax = client.syntax();
+    let col_writer = ColumnWriter::new(syntax);
+    let col = col_writer.excape(col);
+    let set = format!("UPDATE {} SET {} = CURRENT_TIMESTAMP", identifier, col);
+    let wheres = format!("{} AND {} IS NULL", wheres, col);
 
-    Ok(())
+    let rows = match syntax {
+        Syntax::Mssql => {
+            let output = build_output::<<T as HasSchema>::Schema>();
+            let sql = format!("{} {} where {}", set, output, wheres);
+            client.fetch_rows(&sql, args).await?
+        }
+        // MySQL and MariaDB have no RETURNING on an UPDATE
+        Syntax::Mysql | Syntax::MariaDb => {
+            let sql = format!("{} where {}", set, wheres);
+            if client.execute(&sql, args).await?.rows_affected() == 0 {
+                return Ok(Vec::default());
+            }
+            let mut args: ParamArgs = Vec::default();
+            let next_params = NextParam::new(syntax);
+            let mut wheres = Vec::default();
+            for pk in <<T as HasSchema>::Schema as TableColumns>::primary_keys() {
+                obj.bind(pk.name(), &mut args)?;
+                let colname = col_writer.excape(pk.name());
+                wheres.push(format!("{}={}", colname, next_params.next()));
+            }
+            let sql = format!(
+                "SELECT * FROM {} where {}",
+                identifier,
+                wheres.join(" AND ")
+            );
+            client.fetch_rows(&sql, &args).await?
+        }
+        _ => {
+            let sql = format!("{} where {} RETURNING *", set, wheres);
+            client.fetch_rows(&sql, args).await?
+        }
+    };
+    Ok(rows)
 }
 
 #[cfg(test)]
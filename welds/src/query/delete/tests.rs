@@ -61,3 +61,63 @@ fn should_be_able_to_write_delete_query_with_limit() {
         assert_eq!(args_count, 1);
     });
 }
+
+#[test]
+fn should_return_the_deleted_rows() {
+    use crate::connections::mock::{MockClient, MockRow};
+    use crate::query::builder::QueryBuilder;
+    let client = MockClient::new(Syntax::Postgres);
+    let row = MockRow::new().with("id", 3).with("a", 5).with("b", 0);
+    client.enqueue_rows("RETURNING", vec![row]);
+    let q = QueryBuilder::<Product>::new().where_col(|c| c.a.gt(1));
+    let deleted = futures::executor::block_on(q.delete_returning(&client)).unwrap();
+    assert_eq!(deleted[0].id, 3);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "DELETE FROM nums WHERE ( nums.a > $1 ) RETURNING *"
+    );
+}
+
+#[test]
+fn should_output_the_deleted_rows_on_mssql() {
+    use crate::connections::mock::MockClient;
+    use crate::query::builder::QueryBuilder;
+    let client = MockClient::new(Syntax::Mssql);
+    let q = QueryBuilder::<Product>::new().where_col(|c| c.a.gt(1));
+    futures::executor::block_on(q.delete_returning(&client)).unwrap();
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "DELETE FROM nums OUTPUT Deleted.\"id\", Deleted.\"a\", Deleted.\"b\" WHERE ( nums.a > @p1 )"
+    );
+}
+
+#[test]
+fn should_select_then_delete_in_a_transaction_on_mysql() {
+    select_then_delete_in_a_transaction(Syntax::Mysql);
+}
+
+#[test]
+fn should_select_then_delete_in_a_transaction_on_mariadb() {
+    select_then_delete_in_a_transaction(Syntax::MariaDb);
+}
+
+fn select_then_delete_in_a_transaction(syntax: Syntax) {
+    use crate::connections::mock::{MockClient, MockRow};
+    use crate::query::builder::QueryBuilder;
+    let client = MockClient::new(syntax);
+    let row = MockRow::new().with("id", 3).with("a", 5).with("b", 0);
+    client.enqueue_rows("SELECT", vec![row]);
+    let q = QueryBuilder::<Product>::new().where_col(|c| c.a.gt(1));
+    let deleted = futures::executor::block_on(q.delete_returning(&client)).unwrap();
+    assert_eq!(deleted.len(), 1);
+    let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+    assert_eq!(
+        sql,
+        vec![
+            "BEGIN",
+            "SELECT t1.id, t1.a, t1.b FROM nums t1 WHERE ( t1.a > ? ) FOR UPDATE",
+            "DELETE FROM nums WHERE ( nums.a > ? )",
+            "COMMIT",
+        ]
+    );
+}
@@ -0,0 +1,141 @@
+use super::{invalid, Filter};
+use crate::errors::Result;
+use crate::model_traits::{HasSchema, TableColumns};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::OrderBy;
+use serde::{Deserialize, Serialize};
+
+/// The filters, ordering and paging of a query, in a form that can be stored
+/// (a saved search, the query of a background job) and turned back into a `QueryBuilder` later.
+///
+/// Nothing is checked against the model until the definition is turned into a query,
+/// so a definition saved before a field was removed fails then, not when it is read.
+///
+/// ```rust,ignore
+/// let saved = QueryDefinition::new()
+///     .filter(Filter::field("status", FilterOp::Eq, "new"))
+///     .order_by_desc("created_at")
+///     .limit(50);
+/// let json = serde_json::to_string(&saved)?;
+///
+/// // later
+/// let saved: QueryDefinition = serde_json::from_str(&json)?;
+/// let orders = Order::where_col(|o| o.tenant_id.equal(tenant))
+///     .apply_definition(&saved)?
+///     .run(&client)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QueryDefinition {
+    /// All of these have to match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<Filter>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<Sort>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+}
+
+/// Orders the rows of a `QueryDefinition` by a field of the model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Sort {
+    /// The name of the field on the model
+    pub field: String,
+    #[serde(default)]
+    pub direction: Direction,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl QueryDefinition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a filter, all of them have to match
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Orders the rows by a field, smallest first. Multiple calls order by each field in turn
+    pub fn order_by_asc(mut self, field: impl Into<String>) -> Self {
+        self.order.push(Sort {
+            field: field.into(),
+            direction: Direction::Asc,
+        });
+        self
+    }
+
+    /// Orders the rows by a field, largest first. Multiple calls order by each field in turn
+    pub fn order_by_desc(mut self, field: impl Into<String>) -> Self {
+        self.order.push(Sort {
+            field: field.into(),
+            direction: Direction::Desc,
+        });
+        self
+    }
+
+    pub fn limit(mut self, x: i64) -> Self {
+        self.limit = Some(x);
+        self
+    }
+
+    pub fn offset(mut self, x: i64) -> Self {
+        self.offset = Some(x);
+        self
+    }
+
+    /// A query of the rows of `T` this definition describes
+    pub fn to_query<T>(&self) -> Result<QueryBuilder<T>>
+    where
+        T: Send + HasSchema,
+        <T as HasSchema>::Schema: TableColumns,
+    {
+        QueryBuilder::new().apply_definition(self)
+    }
+}
+
+impl<T> QueryBuilder<T>
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableColumns,
+{
+    /// Adds the filters, ordering and paging of a stored definition to the query.
+    /// The filters are added to the ones the query already has, the ordering comes after its ordering
+    pub fn apply_definition(mut self, definition: &QueryDefinition) -> Result<Self> {
+        self = self.where_filters(&definition.filters)?;
+        for sort in &definition.order {
+            let column = <T::Schema as TableColumns>::field_column(&sort.field)
+                .ok_or_else(|| invalid(format!("unknown field `{}`", sort.field)))?;
+            let direction = match sort.direction {
+                Direction::Asc => "ASC",
+                Direction::Desc => "DESC",
+            };
+            self.orderby.push(OrderBy::new(column, direction));
+        }
+        if let Some(limit) = definition.limit {
+            if limit < 0 {
+                return Err(invalid("the limit can't be negative"));
+            }
+            self.limit = Some(limit);
+        }
+        if let Some(offset) = definition.offset {
+            if offset < 0 {
+                return Err(invalid("the offset can't be negative"));
+            }
+            self.offset = Some(offset);
+        }
+        Ok(self)
+    }
+}
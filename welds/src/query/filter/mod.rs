@@ -0,0 +1,368 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{HasSchema, TableColumns};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::group::ClauseGroup;
+use crate::query::clause::ignorecase::{ClauseIgnoreCase, IgnoreCase};
+use crate::query::clause::{ClauseAdder, ClauseColManual};
+use crate::query::helpers::type_name;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use welds_connections::Param;
+
+mod definition;
+pub use definition::{Direction, QueryDefinition, Sort};
+
+/// A filter on the fields of a model, as a client would send it in the body or query string of
+/// a request. Written in JSON as a condition on a field, or as a group of filters where `all` or
+/// `any` of them have to match.
+///
+/// Filters are checked against the model before they are added to a query: the field has to
+/// exist, and the value has to fit its type. Values are always sent as params.
+///
+/// ```rust,ignore
+/// let filters: Vec<Filter> = serde_json::from_str(r#"[
+///     { "field": "status", "op": "in", "value": ["new", "retry"] },
+///     { "any": [
+///         { "field": "total", "op": "gte", "value": 100 },
+///         { "field": "rush", "op": "eq", "value": true }
+///     ] }
+/// ]"#)?;
+/// let orders = Order::all().where_filters(&filters)?.run(&client).await?;
+/// // WHERE ( t1.status IN ($1, $2) AND ( t1.total >= $3 OR t1.rush = $4 ) )
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Filter {
+    /// Every one of the filters has to match
+    All { all: Vec<Filter> },
+    /// At least one of the filters has to match
+    Any { any: Vec<Filter> },
+    /// A condition on a single field
+    Field(FieldFilter),
+}
+
+/// A condition on a single field of a model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldFilter {
+    /// The name of the field on the model
+    pub field: String,
+    pub op: FilterOp,
+    /// A list for `in` and `not_in`, left out for `is_null` and `is_not_null`
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub value: Value,
+}
+
+/// How a field is compared to the value of a filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Like,
+    NotLike,
+    /// Case-insensitive LIKE
+    Ilike,
+    In,
+    NotIn,
+    IsNull,
+    IsNotNull,
+}
+
+impl<T> QueryBuilder<T>
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableColumns,
+{
+    /// Adds filters to the query, all of them have to match. Any field of the model can be filtered on
+    pub fn where_filters(self, filters: &[Filter]) -> Result<Self> {
+        self.add_filters(filters, None)
+    }
+
+    /// Adds filters to the query, all of them have to match.
+    /// Filters on fields that are not in `filterable` are rejected
+    pub fn where_filters_only(self, filters: &[Filter], filterable: &[&str]) -> Result<Self> {
+        self.add_filters(filters, Some(filterable))
+    }
+
+    fn add_filters(mut self, filters: &[Filter], filterable: Option<&[&str]>) -> Result<Self> {
+        for filter in filters {
+            let clause = filter.clause::<T::Schema>(filterable)?;
+            self.wheres.push(clause);
+        }
+        Ok(self)
+    }
+}
+
+impl Filter {
+    /// A condition on a single field
+    pub fn field(field: impl Into<String>, op: FilterOp, value: impl Into<Value>) -> Filter {
+        Filter::Field(FieldFilter {
+            field: field.into(),
+            op,
+            value: value.into(),
+        })
+    }
+
+    fn clause<S: TableColumns>(&self, filterable: Option<&[&str]>) -> Result<Box<dyn ClauseAdder>> {
+        let (joiner, filters) = match self {
+            Filter::All { all } => ("AND", all),
+            Filter::Any { any } => ("OR", any),
+            Filter::Field(field) => return field.clause::<S>(filterable),
+        };
+        if filters.is_empty() {
+            return Err(invalid("a group of filters can't be empty"));
+        }
+        let clauses = filters
+            .iter()
+            .map(|f| f.clause::<S>(filterable))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Box::new(ClauseGroup::new(joiner, clauses)))
+    }
+}
+
+impl FieldFilter {
+    fn clause<S: TableColumns>(&self, filterable: Option<&[&str]>) -> Result<Box<dyn ClauseAdder>> {
+        let field = self.field.as_str();
+        let unknown = || invalid(format!("unknown field `{}`", field));
+        let column = S::field_column(field).ok_or_else(unknown)?;
+        if filterable.is_some_and(|f| !f.contains(&field)) {
+            return Err(invalid(format!("`{}` can't be filtered on", field)));
+        }
+        let column = S::columns()
+            .into_iter()
+            .find(|c| c.name() == column)
+            .ok_or_else(unknown)?;
+        let kind = Kind::of(column.rust_type()).ok_or_else(|| {
+            invalid(format!(
+                "`{}` can't be filtered on, filtering on `{}` isn't supported",
+                field,
+                column.rust_type()
+            ))
+        })?;
+        let col = column.name().to_owned();
+
+        let operator = match self.op {
+            FilterOp::IsNull | FilterOp::IsNotNull => {
+                if !column.nullable() {
+                    return Err(invalid(format!("`{}` can't be null", field)));
+                }
+                let sql = match self.op {
+                    FilterOp::IsNull => "IS NULL",
+                    _ => "IS NOT NULL",
+                };
+                return Ok(manual(Some(col), sql, Vec::default()));
+            }
+            FilterOp::In | FilterOp::NotIn => return self.in_list(col, kind),
+            FilterOp::Ilike => {
+                let val = self.text(kind)?;
+                return Ok(Box::new(ClauseIgnoreCase {
+                    null_clause: false,
+                    not_clause: false,
+                    col,
+                    compare: IgnoreCase::Like,
+                    val: Some(val),
+                }));
+            }
+            FilterOp::Like => return Ok(manual(Some(col), "like ?", vec![self.text_param(kind)?])),
+            FilterOp::NotLike => {
+                return Ok(manual(
+                    Some(col),
+                    "not like ?",
+                    vec![self.text_param(kind)?],
+                ))
+            }
+            FilterOp::Eq => "=",
+            FilterOp::Ne => "!=",
+            FilterOp::Gt => ">",
+            FilterOp::Gte => ">=",
+            FilterOp::Lt => "<",
+            FilterOp::Lte => "<=",
+        };
+        if kind == Kind::Bool && !matches!(self.op, FilterOp::Eq | FilterOp::Ne) {
+            return Err(invalid(format!(
+                "`{}` can only be compared with eq and ne",
+                field
+            )));
+        }
+        let param = kind.param(field, &self.value)?;
+        Ok(manual(Some(col), &format!("{} ?", operator), vec![param]))
+    }
+
+    fn in_list(&self, col: String, kind: Kind) -> Result<Box<dyn ClauseAdder>> {
+        let values = self
+            .value
+            .as_array()
+            .ok_or_else(|| invalid(format!("`{}` has to be compared with a list", self.field)))?;
+        let not = self.op == FilterOp::NotIn;
+        // IN () isn't valid SQL. Nothing is in an empty list
+        if values.is_empty() {
+            let sql = if not { "1 = 1" } else { "1 = 0" };
+            return Ok(manual(None, sql, Vec::default()));
+        }
+        let params = values
+            .iter()
+            .map(|v| kind.param(&self.field, v))
+            .collect::<Result<Vec<_>>>()?;
+        let marks = vec!["?"; params.len()].join(", ");
+        let sql = format!("{}IN ({})", if not { "NOT " } else { "" }, marks);
+        Ok(manual(Some(col), &sql, params))
+    }
+
+    fn text(&self, kind: Kind) -> Result<String> {
+        if kind != Kind::Text {
+            return Err(invalid(format!(
+                "`{}` is not text, it can't be LIKE",
+                self.field
+            )));
+        }
+        match &self.value {
+            Value::String(text) => Ok(text.clone()),
+            other => Err(mismatch(&self.field, kind, other)),
+        }
+    }
+
+    fn text_param(&self, kind: Kind) -> Result<Box<dyn Param + Send + Sync>> {
+        Ok(Box::new(self.text(kind)?))
+    }
+}
+
+fn manual(
+    col: Option<String>,
+    sql: &str,
+    params: Vec<Box<dyn Param + Send + Sync>>,
+) -> Box<dyn ClauseAdder> {
+    Box::new(ClauseColManual {
+        col,
+        sql: sql.to_owned(),
+        params,
+    })
+}
+
+/// The types of fields that can be filtered on. Values are read into the type of the field,
+/// so the param has the type the database is expecting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    Bool,
+    Text,
+    #[cfg(feature = "uuid")]
+    Uuid,
+    #[cfg(feature = "chrono")]
+    DateTime,
+    #[cfg(feature = "chrono")]
+    NaiveDateTime,
+    #[cfg(feature = "chrono")]
+    NaiveDate,
+}
+
+impl Kind {
+    fn of(rust_type: &str) -> Option<Kind> {
+        let name = type_name(rust_type);
+        let name = name
+            .strip_prefix("Option<")
+            .and_then(|t| t.strip_suffix('>'))
+            .unwrap_or(&name);
+        Some(match name {
+            "i16" => Kind::I16,
+            "i32" => Kind::I32,
+            "i64" => Kind::I64,
+            "f32" => Kind::F32,
+            "f64" => Kind::F64,
+            "bool" => Kind::Bool,
+            "String" => Kind::Text,
+            #[cfg(feature = "uuid")]
+            "Uuid" => Kind::Uuid,
+            #[cfg(feature = "chrono")]
+            "DateTime<Utc>" => Kind::DateTime,
+            #[cfg(feature = "chrono")]
+            "NaiveDateTime" => Kind::NaiveDateTime,
+            #[cfg(feature = "chrono")]
+            "NaiveDate" => Kind::NaiveDate,
+            _ => return None,
+        })
+    }
+
+    fn param(self, field: &str, value: &Value) -> Result<Box<dyn Param + Send + Sync>> {
+        let wrong = || mismatch(field, self, value);
+        Ok(match self {
+            Kind::I16 => Box::new(int::<i16>(value).ok_or_else(wrong)?),
+            Kind::I32 => Box::new(int::<i32>(value).ok_or_else(wrong)?),
+            Kind::I64 => Box::new(value.as_i64().ok_or_else(wrong)?),
+            Kind::F32 => Box::new(value.as_f64().ok_or_else(wrong)? as f32),
+            Kind::F64 => Box::new(value.as_f64().ok_or_else(wrong)?),
+            Kind::Bool => Box::new(value.as_bool().ok_or_else(wrong)?),
+            Kind::Text => Box::new(value.as_str().ok_or_else(wrong)?.to_owned()),
+            #[cfg(feature = "uuid")]
+            Kind::Uuid => {
+                Box::new(parse(value, |s| uuid::Uuid::parse_str(s).ok()).ok_or_else(wrong)?)
+            }
+            #[cfg(feature = "chrono")]
+            Kind::DateTime => Box::new(
+                parse(value, |s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                    .ok_or_else(wrong)?
+                    .with_timezone(&chrono::Utc),
+            ),
+            #[cfg(feature = "chrono")]
+            Kind::NaiveDateTime => Box::new(
+                parse(value, |s| s.parse::<chrono::NaiveDateTime>().ok()).ok_or_else(wrong)?,
+            ),
+            #[cfg(feature = "chrono")]
+            Kind::NaiveDate => {
+                Box::new(parse(value, |s| s.parse::<chrono::NaiveDate>().ok()).ok_or_else(wrong)?)
+            }
+        })
+    }
+
+    fn expected(self) -> &'static str {
+        match self {
+            Kind::I16 | Kind::I32 | Kind::I64 => "an integer",
+            Kind::F32 | Kind::F64 => "a number",
+            Kind::Bool => "true or false",
+            Kind::Text => "a string",
+            #[cfg(feature = "uuid")]
+            Kind::Uuid => "a uuid",
+            #[cfg(feature = "chrono")]
+            Kind::DateTime => "an RFC 3339 timestamp",
+            #[cfg(feature = "chrono")]
+            Kind::NaiveDateTime => "a timestamp",
+            #[cfg(feature = "chrono")]
+            Kind::NaiveDate => "a date",
+        }
+    }
+}
+
+// a uuid or a date, written in JSON as a string
+#[cfg(any(feature = "uuid", feature = "chrono"))]
+fn parse<T>(value: &Value, read: impl Fn(&str) -> Option<T>) -> Option<T> {
+    value.as_str().and_then(read)
+}
+
+// an integer that fits in the type of the field
+fn int<I: TryFrom<i64>>(value: &Value) -> Option<I> {
+    value.as_i64().and_then(|v| I::try_from(v).ok())
+}
+
+fn mismatch(field: &str, kind: Kind, value: &Value) -> WeldsError {
+    invalid(format!(
+        "`{}` expects {}, found {}",
+        field,
+        kind.expected(),
+        value
+    ))
+}
+
+fn invalid(reason: impl ToString) -> WeldsError {
+    WeldsError::InvalidFilter(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,217 @@
+use super::*;
+use crate::connections::mock::MockClient;
+use crate::connections::value::Value as Param;
+use crate::{Syntax, WeldsModel};
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub status: String,
+    pub total: f64,
+    pub rush: bool,
+    pub note: Option<String>,
+    pub quantity: i16,
+}
+
+fn filters(json: &str) -> Vec<Filter> {
+    serde_json::from_str(json).unwrap()
+}
+
+fn reason<T>(result: Result<QueryBuilder<T>>) -> String {
+    match result {
+        Err(WeldsError::InvalidFilter(reason)) => reason,
+        Err(err) => panic!("not a filter error: {}", err),
+        Ok(_) => panic!("the filter was accepted"),
+    }
+}
+
+#[test]
+fn should_build_a_query_from_json_filters() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let filters = filters(
+            r#"[
+                { "field": "status", "op": "in", "value": ["new", "retry"] },
+                { "any": [
+                    { "field": "total", "op": "gte", "value": 100 },
+                    { "field": "rush", "op": "eq", "value": true }
+                ] },
+                { "field": "note", "op": "is_null" }
+            ]"#,
+        );
+        let q = Order::all().where_filters(&filters).unwrap();
+        q.run(&client).await.unwrap();
+        let query = &client.queries()[0];
+        assert_eq!(
+            query.sql,
+            r#"SELECT t1."id", t1."status", t1."total", t1."rush", t1."note", t1."quantity" FROM orders t1 WHERE ( t1.status IN ($1, $2) AND ( t1.total >= $3 OR t1.rush = $4 ) AND t1.note IS NULL )"#
+        );
+        assert_eq!(
+            query.params,
+            [
+                Param::Text("new".to_owned()),
+                Param::Text("retry".to_owned()),
+                Param::Float(100.0),
+                Param::Bool(true),
+            ]
+        );
+    });
+}
+
+#[test]
+fn should_compare_text_ignoring_case() {
+    let filters = filters(r#"[{ "field": "status", "op": "ilike", "value": "new%" }]"#);
+    let q = Order::all().where_filters(&filters).unwrap();
+    assert_eq!(q.to_sql(Syntax::Mysql), "SELECT t1.id, t1.status, t1.total, t1.rush, t1.note, t1.quantity FROM orders t1 WHERE ( LOWER(t1.status) like LOWER(?) )");
+}
+
+#[test]
+fn should_match_nothing_with_an_empty_list() {
+    let filters = filters(r#"[{ "field": "id", "op": "in", "value": [] }]"#);
+    let q = Order::all().where_filters(&filters).unwrap();
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."status", t1."total", t1."rush", t1."note", t1."quantity" FROM orders t1 WHERE ( 1 = 0 )"#
+    );
+}
+
+#[test]
+fn should_reject_values_that_do_not_fit_the_field() {
+    let bad = |json: &str| reason(Order::all().where_filters(&filters(json)));
+    assert_eq!(
+        bad(r#"[{ "field": "total", "op": "gt", "value": "lots" }]"#),
+        r#"`total` expects a number, found "lots""#
+    );
+    assert_eq!(
+        bad(r#"[{ "field": "quantity", "op": "eq", "value": 70000 }]"#),
+        "`quantity` expects an integer, found 70000"
+    );
+    assert_eq!(
+        bad(r#"[{ "field": "total", "op": "like", "value": "1%" }]"#),
+        "`total` is not text, it can't be LIKE"
+    );
+    assert_eq!(
+        bad(r#"[{ "field": "status", "op": "is_null" }]"#),
+        "`status` can't be null"
+    );
+    assert_eq!(
+        bad(r#"[{ "field": "rush", "op": "gt", "value": false }]"#),
+        "`rush` can only be compared with eq and ne"
+    );
+}
+
+#[test]
+fn should_reject_fields_that_can_not_be_filtered_on() {
+    let json = r#"[{ "field": "password", "op": "eq", "value": "x" }]"#;
+    assert_eq!(
+        reason(Order::all().where_filters(&filters(json))),
+        "unknown field `password`"
+    );
+
+    let json = r#"[{ "any": [{ "field": "total", "op": "gt", "value": 1 }] }]"#;
+    let q = Order::all().where_filters_only(&filters(json), &["status"]);
+    assert_eq!(reason(q), "`total` can't be filtered on");
+}
+
+#[test]
+fn should_rebuild_a_stored_query_definition() {
+    let definition = QueryDefinition::new()
+        .filter(Filter::field("status", FilterOp::Eq, "new"))
+        .order_by_desc("total")
+        .limit(50);
+    let json = serde_json::to_string(&definition).unwrap();
+    assert_eq!(
+        json,
+        r#"{"filters":[{"field":"status","op":"eq","value":"new"}],"order":[{"field":"total","direction":"desc"}],"limit":50}"#
+    );
+
+    let stored: QueryDefinition = serde_json::from_str(&json).unwrap();
+    assert_eq!(stored, definition);
+    let q = Order::where_col(|o| o.rush.equal(true))
+        .apply_definition(&stored)
+        .unwrap();
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."status", t1."total", t1."rush", t1."note", t1."quantity" FROM orders t1 WHERE ( t1.rush = $1 AND t1.status = $2 ) ORDER BY total DESC OFFSET 0 LIMIT 50"#
+    );
+}
+
+#[test]
+fn should_check_a_stored_query_definition_against_the_model() {
+    let json = r#"{ "order": [{ "field": "placed_at", "direction": "desc" }] }"#;
+    let stored: QueryDefinition = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        reason(stored.to_query::<Order>()),
+        "unknown field `placed_at`"
+    );
+
+    let stored = QueryDefinition::new().offset(-1);
+    assert_eq!(
+        reason(stored.to_query::<Order>()),
+        "the offset can't be negative"
+    );
+}
+
+#[cfg(all(feature = "uuid", feature = "chrono"))]
+mod typed {
+    use super::*;
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "events")]
+    #[welds_path(crate)] // needed only within the welds crate.
+    struct Event {
+        #[welds(primary_key)]
+        pub id: uuid::Uuid,
+        pub kind: std::string::String,
+        pub at: chrono::DateTime<chrono::Utc>,
+        pub day: Option<chrono::NaiveDate>,
+    }
+
+    #[test]
+    fn should_read_uuids_and_dates_into_the_type_of_the_field() {
+        block_on(async {
+            let client = MockClient::new(Syntax::Postgres);
+            let filters = filters(
+                r#"[
+                    { "field": "id", "op": "eq", "value": "67e55044-10b1-426f-9247-bb680e5fe0c8" },
+                    { "field": "kind", "op": "like", "value": "sign%" },
+                    { "field": "at", "op": "gte", "value": "2024-05-01T12:30:00+02:00" },
+                    { "field": "day", "op": "in", "value": ["2024-05-01"] }
+                ]"#,
+            );
+            let q = Event::all().where_filters(&filters).unwrap();
+            q.run(&client).await.unwrap();
+            let query = &client.queries()[0];
+            assert!(query.sql.ends_with(
+                "WHERE ( t1.id = $1 AND t1.kind like $2 AND t1.at >= $3 AND t1.day IN ($4) )"
+            ));
+            let id = uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+            assert_eq!(
+                query.params,
+                [
+                    Param::Uuid(*id.as_bytes()),
+                    Param::Text("sign%".to_owned()),
+                    Param::DateTime("2024-05-01T10:30:00Z".to_owned()),
+                    Param::DateTime("2024-05-01".to_owned()),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn should_reject_uuids_and_dates_that_do_not_parse() {
+        let bad = |json: &str| reason(Event::all().where_filters(&filters(json)));
+        assert_eq!(
+            bad(r#"[{ "field": "id", "op": "eq", "value": "nope" }]"#),
+            r#"`id` expects a uuid, found "nope""#
+        );
+        assert_eq!(
+            bad(r#"[{ "field": "at", "op": "lt", "value": "2024-05-01" }]"#),
+            r#"`at` expects an RFC 3339 timestamp, found "2024-05-01""#
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::query::clause::{AsFieldName, ClauseAdder, ParamArgs};
+use crate::writers::{ColumnWriter, NextParam};
+use crate::Syntax;
+use welds_connections::Param;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Func {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+    StringAgg { separator: String },
+}
+
+/// An aggregate of a column, selected into a grouped query with `select_agg`.
+///
+/// The result is selected as `{func}_{field}` (`count_id`, `sum_total`) unless it is renamed
+/// with `alias`. Counts are always read back as an `i64`, averages as an `f64`, and
+/// `string_agg` as a `String`.
+/// Sums, mins and maxes have the type the database gives them
+/// (a sum of integers is a `NUMERIC` on Postgres and a `DECIMAL` on MySQL).
+#[derive(Debug, Clone)]
+pub struct Aggregate {
+    func: Func,
+    // None is COUNT(*)
+    col: Option<String>,
+    alias: String,
+}
+
+impl Aggregate {
+    fn new<V>(func: Func, name: &str, field: impl AsFieldName<V>) -> Self {
+        Self {
+            func,
+            col: Some(field.colname().to_owned()),
+            alias: format!("{}_{}", name, field.fieldname()),
+        }
+    }
+
+    /// The name this aggregate is selected as
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.alias = alias.into();
+        self
+    }
+
+    /// A `having` condition that the aggregate equals the value
+    pub fn equal(self, v: impl Param + Send + Sync + 'static) -> Box<dyn ClauseAdder> {
+        self.compare("=", v)
+    }
+
+    /// A `having` condition that the aggregate does not equal the value
+    pub fn not_equal(self, v: impl Param + Send + Sync + 'static) -> Box<dyn ClauseAdder> {
+        self.compare("!=", v)
+    }
+
+    /// A `having` condition that the aggregate is greater than the value
+    pub fn gt(self, v: impl Param + Send + Sync + 'static) -> Box<dyn ClauseAdder> {
+        self.compare(">", v)
+    }
+
+    /// A `having` condition that the aggregate is less than the value
+    pub fn lt(self, v: impl Param + Send + Sync + 'static) -> Box<dyn ClauseAdder> {
+        self.compare("<", v)
+    }
+
+    /// A `having` condition that the aggregate is greater than or equal to the value
+    pub fn gte(self, v: impl Param + Send + Sync + 'static) -> Box<dyn ClauseAdder> {
+        self.compare(">=", v)
+    }
+
+    /// A `having` condition that the aggregate is less than or equal to the value
+    pub fn lte(self, v: impl Param + Send + Sync + 'static) -> Box<dyn ClauseAdder> {
+        self.compare("<=", v)
+    }
+
+    fn compare(
+        self,
+        operator: &'static str,
+        v: impl Param + Send + Sync + 'static,
+    ) -> Box<dyn ClauseAdder> {
+        Box::new(ClauseAgg {
+            agg: self,
+            operator,
+            val: Box::new(v),
+        })
+    }
+
+    /// the name the aggregate is selected as
+    pub(crate) fn selected_name(&self) -> &str {
+        &self.alias
+    }
+
+    /// writes the expression of this aggregate selected as its alias
+    pub(crate) fn write_selected(&self, syntax: Syntax, tablealias: &str) -> String {
+        let writer = ColumnWriter::new(syntax);
+        format!(
+            "{} AS {}",
+            self.write(syntax, tablealias),
+            writer.excape(&self.alias)
+        )
+    }
+
+    /// writes the expression of this aggregate, without its alias
+    pub(crate) fn write(&self, syntax: Syntax, tablealias: &str) -> String {
+        let writer = ColumnWriter::new(syntax);
+        let col = match &self.col {
+            Some(col) => format!("{}.{}", tablealias, writer.excape(col)),
+            None => "*".to_owned(),
+        };
+        match (&self.func, syntax) {
+            (Func::Count, Syntax::Mssql) => format!("COUNT_BIG({})", col),
+            (Func::Count, _) => format!("COUNT({})", col),
+            (Func::Sum, _) => format!("SUM({})", col),
+            (Func::Avg, Syntax::Postgres | Syntax::CockroachDb) => {
+                format!("CAST(AVG({}) AS DOUBLE PRECISION)", col)
+            }
+            (Func::Avg, Syntax::Mysql | Syntax::MariaDb) => {
+                format!("CAST(AVG({}) AS DOUBLE)", col)
+            }
+            (Func::Avg, Syntax::Mssql) => format!("AVG(CAST({} AS FLOAT))", col),
+            (Func::Avg, Syntax::Sqlite) => format!("AVG({})", col),
+            (Func::Min, _) => format!("MIN({})", col),
+            (Func::Max, _) => format!("MAX({})", col),
+            (Func::StringAgg { separator }, _) => {
+                let sep = separator_literal(syntax, separator);
+                match syntax {
+                    Syntax::Postgres | Syntax::CockroachDb => {
+                        format!("string_agg(CAST({} AS TEXT), {})", col, sep)
+                    }
+                    Syntax::Mysql | Syntax::MariaDb => {
+                        format!("GROUP_CONCAT({} SEPARATOR {})", col, sep)
+                    }
+                    Syntax::Mssql => {
+                        format!("STRING_AGG(CAST({} AS NVARCHAR(MAX)), {})", col, sep)
+                    }
+                    Syntax::Sqlite => format!("group_concat({}, {})", col, sep),
+                }
+            }
+        }
+    }
+}
+
+/// The number of rows in the group with a value in this column (`COUNT(col)`)
+pub fn count<V>(field: impl AsFieldName<V>) -> Aggregate {
+    Aggregate::new(Func::Count, "count", field)
+}
+
+/// The number of rows in the group (`COUNT(*)`), selected as `count`
+pub fn count_all() -> Aggregate {
+    Aggregate {
+        func: Func::Count,
+        col: None,
+        alias: "count".to_owned(),
+    }
+}
+
+/// The total of this column over the group
+pub fn sum<V>(field: impl AsFieldName<V>) -> Aggregate {
+    Aggregate::new(Func::Sum, "sum", field)
+}
+
+/// The average of this column over the group
+pub fn avg<V>(field: impl AsFieldName<V>) -> Aggregate {
+    Aggregate::new(Func::Avg, "avg", field)
+}
+
+/// The smallest value of this column in the group
+pub fn min<V>(field: impl AsFieldName<V>) -> Aggregate {
+    Aggregate::new(Func::Min, "min", field)
+}
+
+/// The largest value of this column in the group
+pub fn max<V>(field: impl AsFieldName<V>) -> Aggregate {
+    Aggregate::new(Func::Max, "max", field)
+}
+
+/// The values of this column in the group joined into one string, with the separator between
+/// them (`string_agg`, `GROUP_CONCAT` on MySQL). NULLs are left out, and the order of the
+/// values is up to the database.
+///
+/// ```rust,ignore
+/// // the names of the tags of each post, "rust, sql"
+/// Post::all()
+///     .select(|p| p.id)
+///     .left_join(|p| p.tags, Tag::all().select_agg(|t| string_agg(t.name, ", ").alias("tags")))
+/// ```
+pub fn string_agg<V>(field: impl AsFieldName<V>, separator: impl Into<String>) -> Aggregate {
+    let func = Func::StringAgg {
+        separator: separator.into(),
+    };
+    Aggregate::new(func, "string_agg", field)
+}
+
+// the separator as a string literal
+fn separator_literal(syntax: Syntax, separator: &str) -> String {
+    let mut separator = separator.replace('\'', "''");
+    if matches!(syntax, Syntax::Mysql | Syntax::MariaDb) {
+        separator = separator.replace('\\', "\\\\");
+    }
+    format!("'{}'", separator)
+}
+
+/// A condition on an aggregate, written in the HAVING of a grouped query
+struct ClauseAgg {
+    agg: Aggregate,
+    operator: &'static str,
+    val: Box<dyn Param + Send + Sync>,
+}
+
+impl ClauseAdder for ClauseAgg {
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        args.push(self.val.as_ref());
+    }
+
+    fn clause(&self, syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
+        Some(format!(
+            "{} {} {}",
+            self.agg.write(syntax, alias),
+            self.operator,
+            next_params.next()
+        ))
+    }
+}
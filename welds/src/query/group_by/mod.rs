@@ -0,0 +1,225 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::{AsFieldName, ClauseAdder, OrderBy, ParamArgs};
+use crate::query::helpers::{build_tail, build_where_clauses, from_table, join_sql_parts};
+use crate::writers::in_namespace;
+use crate::writers::{ColumnWriter, NextParam};
+use crate::{Client, Row, Syntax};
+
+mod aggregate;
+pub use aggregate::{avg, count, count_all, max, min, string_agg, sum, Aggregate};
+mod selection;
+pub use selection::{AggColumn, AggSelection};
+
+/// An un-executed query that groups the rows of a table and selects aggregates of each group.
+///
+/// ```rust,ignore
+/// use welds::query::group_by::{count, sum};
+///
+/// let totals: Vec<CustomerTotals> = Order::where_col(|o| o.status.equal("paid"))
+///     .group_by(|o| o.customer_id)
+///     .select_agg(|o| (o.customer_id, count(o.id).alias("orders"), sum(o.total)))
+///     .run_into(&client)
+///     .await?;
+/// // SELECT t1.customer_id, COUNT(t1.id) AS orders, SUM(t1.total) AS sum_total
+/// // FROM orders t1 WHERE ( t1.status = $1 ) GROUP BY t1.customer_id
+/// ```
+pub struct GroupBuilder<T> {
+    qb: QueryBuilder<T>,
+    groups: Vec<String>,
+    selects: Vec<AggColumn>,
+    havings: Vec<Box<dyn ClauseAdder>>,
+}
+
+impl<T> GroupBuilder<T>
+where
+    T: Send + HasSchema,
+{
+    pub(crate) fn new(qb: QueryBuilder<T>) -> Self {
+        Self {
+            qb,
+            groups: Vec::default(),
+            selects: Vec::default(),
+            havings: Vec::default(),
+        }
+    }
+
+    /// Also group by this column
+    pub fn group_by<V, FN: AsFieldName<V>>(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+    ) -> Self {
+        let field = lam(Default::default());
+        self.groups.push(field.colname().to_owned());
+        self
+    }
+
+    /// Adds columns and aggregates to the select list. Takes a single column or aggregate
+    /// or a tuple of them. Without it the grouped columns are selected
+    pub fn select_agg<S: AggSelection>(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> S,
+    ) -> Self {
+        self.selects.extend(lam(Default::default()).into_columns());
+        self
+    }
+
+    /// Filter the groups on their aggregates (HAVING), such as customers with more than 5 orders.
+    /// Multiple calls must all match
+    ///
+    /// ```rust,ignore
+    /// Order::all()
+    ///     .group_by(|o| o.customer_id)
+    ///     .select_agg(|o| (o.customer_id, count(&o.id)))
+    ///     .having(|o| count(&o.id).gt(5))
+    /// ```
+    pub fn having(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> Box<dyn ClauseAdder>,
+    ) -> Self {
+        self.havings.push(lam(Default::default()));
+        self
+    }
+
+    /// Order the groups by an aggregate, using the name it is selected as
+    pub fn order_by_agg_asc(mut self, alias: impl Into<String>) -> Self {
+        self.qb.orderby.push(OrderBy::new(alias, "ASC"));
+        self
+    }
+
+    /// Order the groups by an aggregate, largest first, using the name it is selected as
+    pub fn order_by_agg_desc(mut self, alias: impl Into<String>) -> Self {
+        self.qb.orderby.push(OrderBy::new(alias, "DESC"));
+        self
+    }
+
+    /// Limit the number of groups returned
+    pub fn limit(mut self, x: i64) -> Self {
+        self.qb.limit = Some(x);
+        self
+    }
+
+    /// Skip this many groups
+    pub fn offset(mut self, x: i64) -> Self {
+        self.qb.offset = Some(x);
+        self
+    }
+
+    fn sql_internal<'s, 'args, 'p>(
+        &'s self,
+        syntax: Syntax,
+        args: &'args mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        's: 'p,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let next_params = NextParam::new(syntax);
+        let alias = &self.qb.alias;
+        let writer = ColumnWriter::new(syntax);
+
+        let group_cols: Vec<String> = self
+            .groups
+            .iter()
+            .map(|g| format!("{}.{}", alias, writer.excape(g)))
+            .collect();
+
+        let cols: Vec<String> = if self.selects.is_empty() {
+            group_cols.clone()
+        } else {
+            self.selects
+                .iter()
+                .map(|s| s.write(syntax, alias))
+                .collect()
+        };
+        let tn = <T as HasSchema>::Schema::identifier().join(".");
+        let head = format!(
+            "SELECT {} FROM {}",
+            cols.join(", "),
+            from_table(syntax, &tn, alias, self.qb.nolock, self.qb.sample)
+        );
+
+        let wheres = build_where_clauses(
+            syntax,
+            &next_params,
+            alias,
+            &self.qb.wheres,
+            args,
+            &self.qb.exist_ins,
+        );
+        let where_sql = if wheres.is_empty() {
+            None
+        } else {
+            Some(format!("WHERE ( {} )", wheres.join(" AND ")))
+        };
+        let group_sql = if group_cols.is_empty() {
+            None
+        } else {
+            Some(format!("GROUP BY {}", group_cols.join(", ")))
+        };
+
+        let mut havings = Vec::default();
+        for clause in &self.havings {
+            if let Some(args) = args {
+                clause.bind(args);
+            }
+            if let Some(p) = clause.clause(syntax, alias, &next_params) {
+                havings.push(p);
+            }
+        }
+        let having_sql = if havings.is_empty() {
+            None
+        } else {
+            Some(format!("HAVING ( {} )", havings.join(" AND ")))
+        };
+
+        join_sql_parts(&[
+            Some(head),
+            where_sql,
+            group_sql,
+            having_sql,
+            build_tail(syntax, &self.qb),
+        ])
+    }
+
+    /// Get a copy of the SQL that will be executed when this query runs
+    pub fn to_sql(&self, syntax: Syntax) -> String
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.sql_internal(syntax, &mut None)
+    }
+
+    /// Executes the query in the database returning a row for each group
+    pub async fn run(&self, client: &dyn Client) -> Result<Vec<Row>>
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let syntax = client.syntax();
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let sql = in_namespace(client, || self.sql_internal(syntax, &mut args));
+        let args = args.unwrap();
+        let rows = client.fetch_rows(&sql, &args).await?;
+        Ok(rows)
+    }
+
+    /// Executes the query, reading each group into a result struct.
+    /// The fields of the struct are matched to the names the columns are selected as
+    pub async fn run_into<R>(&self, client: &dyn Client) -> Result<Vec<R>>
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        R: TryFrom<Row>,
+        WeldsError: From<<R as TryFrom<Row>>::Error>,
+    {
+        let rows = self.run(client).await?;
+        let mut objs = Vec::default();
+        for row in rows {
+            objs.push(R::try_from(row)?);
+        }
+        Ok(objs)
+    }
+}
+
+#[cfg(test)]
+mod tests;
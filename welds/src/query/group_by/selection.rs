@@ -0,0 +1,79 @@
+use super::Aggregate;
+use crate::query::clause::{AsFieldName, Basic, BasicOpt, Numeric, NumericOpt, Text, TextOpt};
+use crate::writers::ColumnWriter;
+use crate::Syntax;
+
+/// One column in the select list of a grouped query
+pub enum AggColumn {
+    Column { col: String, field: String },
+    Aggregate(Aggregate),
+}
+
+impl AggColumn {
+    pub(crate) fn write(&self, syntax: Syntax, tablealias: &str) -> String {
+        let writer = ColumnWriter::new(syntax);
+        match self {
+            AggColumn::Column { col, field } => {
+                let col = writer.excape(col);
+                let field = writer.excape(field);
+                if col == field {
+                    format!("{}.{}", tablealias, col)
+                } else {
+                    format!("{}.{} as {}", tablealias, col, field)
+                }
+            }
+            AggColumn::Aggregate(agg) => agg.write_selected(syntax, tablealias),
+        }
+    }
+}
+
+/// What can be selected into a grouped query: a column, an aggregate, or a tuple of them
+pub trait AggSelection {
+    fn into_columns(self) -> Vec<AggColumn>;
+}
+
+impl AggSelection for Aggregate {
+    fn into_columns(self) -> Vec<AggColumn> {
+        vec![AggColumn::Aggregate(self)]
+    }
+}
+
+macro_rules! field_selection {
+    ($($field:ident),*) => {
+        $(
+            impl<V> AggSelection for $field<V> {
+                fn into_columns(self) -> Vec<AggColumn> {
+                    vec![AggColumn::Column {
+                        col: self.colname().to_owned(),
+                        field: self.fieldname().to_owned(),
+                    }]
+                }
+            }
+        )*
+    };
+}
+
+field_selection!(Basic, BasicOpt, Numeric, NumericOpt, Text, TextOpt);
+
+macro_rules! tuple_selection {
+    ($($name:ident),+) => {
+        impl<$($name: AggSelection),+> AggSelection for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_columns(self) -> Vec<AggColumn> {
+                let ($($name,)+) = self;
+                let mut cols = Vec::default();
+                $(cols.extend($name.into_columns());)+
+                cols
+            }
+        }
+    };
+}
+
+tuple_selection!(A);
+tuple_selection!(A, B);
+tuple_selection!(A, B, C);
+tuple_selection!(A, B, C, D);
+tuple_selection!(A, B, C, D, E);
+tuple_selection!(A, B, C, D, E, F);
+tuple_selection!(A, B, C, D, E, F, G);
+tuple_selection!(A, B, C, D, E, F, G, H);
@@ -0,0 +1,165 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::value::Value;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub customer_id: i32,
+    pub total: f64,
+    pub status: String,
+}
+
+struct CustomerTotals {
+    customer_id: i32,
+    orders: i64,
+}
+
+impl TryFrom<Row> for CustomerTotals {
+    type Error = WeldsError;
+    fn try_from(row: Row) -> std::result::Result<Self, Self::Error> {
+        Ok(CustomerTotals {
+            customer_id: row.get("customer_id")?,
+            orders: row.get("orders")?,
+        })
+    }
+}
+
+#[test]
+fn should_select_aggregates_of_each_group() {
+    let q = Order::where_col(|o| o.status.equal("paid"))
+        .group_by(|o| o.customer_id)
+        .select_agg(|o| (o.customer_id, count(o.id).alias("orders"), sum(o.total)));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."customer_id", COUNT(t1."id") AS "orders", SUM(t1."total") AS "sum_total" FROM orders t1 WHERE ( t1.status = $1 ) GROUP BY t1."customer_id""#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        r#"SELECT t1.customer_id, COUNT(t1.id) AS orders, SUM(t1.total) AS sum_total FROM orders t1 WHERE ( t1.status = ? ) GROUP BY t1.customer_id"#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."customer_id", COUNT_BIG(t1."id") AS "orders", SUM(t1."total") AS "sum_total" FROM orders t1 WHERE ( t1.status = @p1 ) GROUP BY t1."customer_id""#
+    );
+}
+
+#[test]
+fn should_read_averages_as_floats_on_every_backend() {
+    let q = Order::all()
+        .group_by(|o| o.status)
+        .select_agg(|o| (o.status, avg(&o.total), max(&o.total)));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."status", CAST(AVG(t1."total") AS DOUBLE PRECISION) AS "avg_total", MAX(t1."total") AS "max_total" FROM orders t1 GROUP BY t1."status""#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."status", AVG(t1."total") AS "avg_total", MAX(t1."total") AS "max_total" FROM orders t1 GROUP BY t1."status""#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."status", AVG(CAST(t1."total" AS FLOAT)) AS "avg_total", MAX(t1."total") AS "max_total" FROM orders t1 GROUP BY t1."status""#
+    );
+}
+
+#[test]
+fn should_join_the_values_of_each_group_into_a_string() {
+    let q = Order::all()
+        .group_by(|o| o.customer_id)
+        .select_agg(|o| (o.customer_id, string_agg(o.status, ", ")));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."customer_id", string_agg(CAST(t1."status" AS TEXT), ', ') AS "string_agg_status" FROM orders t1 GROUP BY t1."customer_id""#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        r#"SELECT t1.customer_id, GROUP_CONCAT(t1.status SEPARATOR ', ') AS string_agg_status FROM orders t1 GROUP BY t1.customer_id"#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."customer_id", STRING_AGG(CAST(t1."status" AS NVARCHAR(MAX)), ', ') AS "string_agg_status" FROM orders t1 GROUP BY t1."customer_id""#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."customer_id", group_concat(t1."status", ', ') AS "string_agg_status" FROM orders t1 GROUP BY t1."customer_id""#
+    );
+}
+
+#[test]
+fn should_escape_the_string_agg_separator() {
+    let q = Order::all()
+        .group_by(|o| o.customer_id)
+        .select_agg(|o| string_agg(o.status, r"'\").alias("statuses"));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT string_agg(CAST(t1."status" AS TEXT), '''\') AS "statuses" FROM orders t1 GROUP BY t1."customer_id""#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        r#"SELECT GROUP_CONCAT(t1.status SEPARATOR '''\\') AS statuses FROM orders t1 GROUP BY t1.customer_id"#
+    );
+}
+
+#[test]
+fn should_order_and_page_groups_by_an_aggregate() {
+    let q = Order::all()
+        .limit(10)
+        .group_by(|o| o.customer_id)
+        .group_by(|o| o.status)
+        .select_agg(|_| count_all())
+        .order_by_agg_desc("count");
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT COUNT_BIG(*) AS "count" FROM orders t1 GROUP BY t1."customer_id", t1."status" ORDER BY count DESC OFFSET 0 ROWS FETCH FIRST 10 ROWS ONLY"#
+    );
+}
+
+#[test]
+fn should_read_groups_into_a_result_struct() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows(
+        "GROUP BY",
+        vec![
+            MockRow::new().with("customer_id", 1).with("orders", 3_i64),
+            MockRow::new().with("customer_id", 2).with("orders", 1_i64),
+        ],
+    );
+    let q = Order::all()
+        .group_by(|o| o.customer_id)
+        .select_agg(|o| (o.customer_id, count(o.id).alias("orders")));
+    let totals: Vec<CustomerTotals> = block_on(q.run_into(&client)).unwrap();
+    assert_eq!(totals.len(), 2);
+    assert_eq!((totals[0].customer_id, totals[0].orders), (1, 3));
+    assert_eq!((totals[1].customer_id, totals[1].orders), (2, 1));
+}
+
+#[test]
+fn should_filter_groups_on_their_aggregates() {
+    let client = MockClient::new(Syntax::Postgres);
+    let q = Order::where_col(|o| o.status.equal("paid"))
+        .group_by(|o| o.customer_id)
+        .select_agg(|o| (o.customer_id, count(&o.id).alias("orders")))
+        .having(|o| count(&o.id).gt(5))
+        .having(|o| sum(&o.total).lte(1000.0));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."customer_id", COUNT(t1."id") AS "orders" FROM orders t1 WHERE ( t1.status = $1 ) GROUP BY t1."customer_id" HAVING ( COUNT(t1."id") > $2 AND SUM(t1."total") <= $3 )"#
+    );
+
+    block_on(q.run(&client)).unwrap();
+    let params = client.queries().pop().unwrap().params;
+    assert_eq!(
+        params,
+        vec![
+            Value::Text("paid".to_string()),
+            Value::Int(5),
+            Value::Float(1000.0)
+        ]
+    );
+}
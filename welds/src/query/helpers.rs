@@ -1,10 +1,34 @@
 use super::builder::QueryBuilder;
 use super::clause::ParamArgs;
-use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::model_traits::{HasSchema, TableInfo};
 use crate::query::clause::exists::ExistIn;
+use crate::query::clause::sample::Sample;
 use crate::query::clause::ClauseAdder;
-use crate::writers::NextParam;
+use crate::query::clause::OrderBy;
+use crate::writers::table_name;
+use crate::writers::{ColumnWriter, NextParam};
 use crate::Syntax;
+use welds_connections::Client;
+
+/// The rust type of a column without its paths or spaces, the way it is written in a model.
+/// `std :: option :: Option < chrono :: DateTime < chrono :: Utc > >` is `Option<DateTime<Utc>>`
+pub(crate) fn type_name(rust_type: &str) -> String {
+    let last = |path: &str| path.rsplit("::").next().unwrap_or(path).to_owned();
+    let mut name = String::with_capacity(rust_type.len());
+    let mut path = String::default();
+    for c in rust_type.chars().filter(|c| !c.is_whitespace()) {
+        match c {
+            '<' | '>' | ',' => {
+                name.push_str(&last(&path));
+                name.push(c);
+                path.clear();
+            }
+            _ => path.push(c),
+        }
+    }
+    name.push_str(&last(&path));
+    name
+}
 
 pub(crate) fn join_sql_parts(parts: &[Option<String>]) -> String {
     // Join al the parts into
@@ -73,3 +97,131 @@ where
 {
     super::tail::write(syntax, &select.limit, &select.offset, &select.orderby)
 }
+
+/// `table alias`, with the `WITH (NOLOCK)` table hint on MSSQL when asked for,
+/// reading a random share of the table when the query is sampled
+pub(crate) fn from_table(
+    syntax: Syntax,
+    table: &str,
+    alias: &str,
+    nolock: bool,
+    sample: Option<Sample>,
+) -> String {
+    let table = table_name(table);
+    let from = match sample {
+        Some(sample) => sample.write_table(syntax, &table, alias),
+        None => format!("{} {}", table, alias),
+    };
+    match (syntax, nolock) {
+        (Syntax::Mssql, true) => format!("{} WITH (NOLOCK)", from),
+        _ => from,
+    }
+}
+
+/// The SELECT keyword, with the DISTINCT of the query
+pub(crate) fn select_keyword(
+    syntax: Syntax,
+    alias: &str,
+    distinct: bool,
+    distinct_on: &[String],
+) -> String {
+    if !distinct_on.is_empty() {
+        let writer = ColumnWriter::new(syntax);
+        let cols: Vec<String> = distinct_on
+            .iter()
+            .map(|c| format!("{}.{}", alias, writer.excape(c)))
+            .collect();
+        return format!("SELECT DISTINCT ON ({})", cols.join(", "));
+    }
+    match distinct {
+        true => "SELECT DISTINCT".to_owned(),
+        false => "SELECT".to_owned(),
+    }
+}
+
+/// DISTINCT ON is only built into Postgres, the others keep the first row of each partition
+/// with a `ROW_NUMBER()`
+pub(crate) fn emulate_distinct_on(syntax: Syntax, distinct_on: &[String]) -> bool {
+    !distinct_on.is_empty() && !matches!(syntax, Syntax::Postgres | Syntax::CockroachDb)
+}
+
+/// Postgres wants the rows sorted by the DISTINCT ON columns first.
+/// The ordering with any of them that are missing from the front added
+pub(crate) fn distinct_on_orders(distinct_on: &[String], orders: &[OrderBy]) -> Vec<OrderBy> {
+    let mut sorted: Vec<OrderBy> = Vec::default();
+    for (i, col) in distinct_on.iter().enumerate() {
+        let leading = orders.get(i).map(|o| &o.field == col).unwrap_or(false);
+        if !leading {
+            sorted.push(OrderBy::new(col, "ASC"));
+        }
+    }
+    sorted.extend(orders.iter().cloned());
+    sorted
+}
+
+/// The `ROW_NUMBER()` that picks the first row of each DISTINCT ON partition
+pub(crate) fn first_row_number(
+    syntax: Syntax,
+    alias: &str,
+    distinct_on: &[String],
+    orders: &[OrderBy],
+) -> String {
+    let writer = ColumnWriter::new(syntax);
+    let partition: Vec<String> = distinct_on
+        .iter()
+        .map(|c| format!("{}.{}", alias, writer.excape(c)))
+        .collect();
+    let order = match orders.is_empty() {
+        true => "(SELECT NULL)".to_owned(),
+        false => {
+            let parts: Vec<String> = orders.iter().map(|o| o.write(syntax)).collect();
+            parts.join(", ")
+        }
+    };
+    format!(
+        "ROW_NUMBER() OVER (PARTITION BY {} ORDER BY {}) AS welds_rn",
+        partition.join(", "),
+        order
+    )
+}
+
+/// Adds the model's default scope to the wheres of an update or delete of a single row,
+/// the row is left alone when it is out of the scope
+pub(crate) fn push_default_scope<'s>(
+    scope: &'s Option<Box<dyn ClauseAdder>>,
+    syntax: Syntax,
+    table: &str,
+    next_params: &NextParam,
+    wheres: &mut Vec<String>,
+    args: &mut ParamArgs<'s>,
+) {
+    let scope = match scope {
+        Some(scope) => scope,
+        None => return,
+    };
+    if let Some(clause) = scope.clause(syntax, table, next_params) {
+        scope.bind(args);
+        wheres.push(clause);
+    }
+}
+
+/// Drops the cached queries that read the table of T, after welds has written to it
+pub(crate) async fn invalidate_cached<T: HasSchema>(client: &(impl Client + ?Sized)) {
+    let table = <T as HasSchema>::Schema::identifier().join(".");
+    invalidate_cached_table(client, &table).await;
+}
+
+/// Drops the cached queries that read the table, after welds has written to it.
+/// A write made in a transaction drops them when the transaction commits
+#[cfg_attr(not(feature = "json"), allow(unused_variables))]
+pub(crate) async fn invalidate_cached_table(client: &(impl Client + ?Sized), table: &str) {
+    #[cfg(feature = "json")]
+    {
+        let table = table.to_owned();
+        let work: crate::connections::AfterCommit =
+            Box::new(move || Box::pin(async move { crate::cache::invalidate_table(&table).await }));
+        if let Some(work) = client.after_commit(work) {
+            work().await;
+        }
+    }
+}
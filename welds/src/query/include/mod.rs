@@ -0,0 +1,751 @@
+use crate::errors::Result;
+use crate::model_traits::{
+    Column, HasSchema, TableColumns, TableInfo, UniqueIdentifier, WriteToArgs,
+};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::exists::keys_in;
+use crate::query::clause::{ClauseAdder, OrderBy, ParamArgs};
+use crate::relations::{key_columns, read_link, LinkKey, Through};
+use crate::relations::{BelongsTo, BelongsToMany, HasMany, HasOne, HasRelations, Relationship};
+use crate::state::DbState;
+use crate::writers::{in_namespace, table_name};
+use crate::writers::{ColumnWriter, NextParam};
+use crate::{Syntax, WeldsError};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+use welds_connections::{Client, Fetch, Row};
+
+/// A query that loads related models along with its rows, see `QueryBuilder::include`.
+///
+/// Each level of includes is loaded with one query for all the rows of the level above it,
+/// and all the queries are sent together with `fetch_many`.
+/// Loading orders, their customers, their items and the items' products is four queries
+/// no matter how many orders there are.
+///
+/// ```rust,ignore
+/// let orders = Order::all()
+///     .include(|o| o.customer)
+///     .include_nested(|o| o.items, |items| items.include(|i| i.product))
+///     .run(&client)
+///     .await?;
+/// for order in orders.iter() {
+///     let customer = order.get(|o| o.customer);
+///     for item in order.get(|o| o.items) {
+///         let product = item.get(|i| i.product);
+///     }
+/// }
+/// ```
+pub struct IncludeBuilder<T> {
+    query: Arc<QueryBuilder<T>>,
+    per_parent: Option<PerParent>,
+    includes: Vec<Include>,
+}
+
+// the limit and offset of an `include_where`, applied to the related rows of each parent
+struct PerParent {
+    columns: Vec<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+// a relation to load, and the columns on the parent its rows are linked by
+struct Include {
+    id: RelationId,
+    parent_keys: Vec<String>,
+    node: Box<dyn Node>,
+}
+
+// tells the relations of a model apart. Two relations to the same model use different keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RelationId {
+    model: TypeId,
+    my_keys: Vec<String>,
+    their_keys: Vec<String>,
+}
+
+impl RelationId {
+    fn of<R: 'static>(my_keys: &[String], their_keys: &[String]) -> Self {
+        Self {
+            model: TypeId::of::<R>(),
+            my_keys: my_keys.to_vec(),
+            their_keys: their_keys.to_vec(),
+        }
+    }
+}
+
+impl<T> QueryBuilder<T>
+where
+    T: Send + Sync + HasSchema + HasRelations + 'static,
+    <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+{
+    /// Loads the related rows of a relationship along with the rows of this query,
+    /// in one query for all the rows.
+    ///
+    /// The related rows are read with `get` on the rows of the `DataSet` that is returned
+    pub fn include<R, Ship>(
+        self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+    ) -> IncludeBuilder<T>
+    where
+        Ship: Relationship<R> + Related<R>,
+        R: Send + Sync + HasSchema + WriteToArgs + TryFrom<Row> + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        WeldsError: From<<R as TryFrom<Row>>::Error>,
+    {
+        IncludeBuilder::new(self).include(relationship)
+    }
+
+    /// Like `include`, and includes the relations of the related rows as well.
+    /// Each level is loaded in one query
+    ///
+    /// ```rust,ignore
+    /// Order::all().include_nested(|o| o.items, |items| items.include(|i| i.product))
+    /// ```
+    pub fn include_nested<R, Ship>(
+        self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        nested: impl FnOnce(IncludeBuilder<R>) -> IncludeBuilder<R>,
+    ) -> IncludeBuilder<T>
+    where
+        Ship: Relationship<R> + Related<R>,
+        R: Send + Sync + HasSchema + WriteToArgs + TryFrom<Row> + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        WeldsError: From<<R as TryFrom<Row>>::Error>,
+    {
+        IncludeBuilder::new(self).include_nested(relationship, nested)
+    }
+
+    /// Like `include`, loading only the related rows the query in `filter` finds,
+    /// in its order.
+    ///
+    /// A limit or offset is applied to the related rows of each row on their own,
+    /// except through the join table of a `BelongsToMany` where it limits all of them
+    ///
+    /// ```rust,ignore
+    /// // the three newest active items of each order
+    /// Order::all().include_where(|o| o.items, |items| {
+    ///     items.where_col(|i| i.active.equal(true)).order_by_desc(|i| i.created_at).limit(3)
+    /// })
+    /// ```
+    pub fn include_where<R, Ship>(
+        self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        filter: impl FnOnce(QueryBuilder<R>) -> QueryBuilder<R>,
+    ) -> IncludeBuilder<T>
+    where
+        Ship: Relationship<R> + Related<R>,
+        R: Send + Sync + HasSchema + WriteToArgs + TryFrom<Row> + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        WeldsError: From<<R as TryFrom<Row>>::Error>,
+    {
+        IncludeBuilder::new(self).include_where(relationship, filter)
+    }
+}
+
+impl<T> IncludeBuilder<T>
+where
+    T: Send + Sync + HasSchema + 'static,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    fn new(query: QueryBuilder<T>) -> Self {
+        Self {
+            query: Arc::new(query),
+            per_parent: None,
+            includes: Vec::default(),
+        }
+    }
+
+    /// Loads the related rows of another relationship
+    pub fn include<R, Ship>(
+        self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+    ) -> Self
+    where
+        T: HasRelations,
+        <T as HasSchema>::Schema: UniqueIdentifier,
+        Ship: Relationship<R> + Related<R>,
+        R: Send + Sync + HasSchema + WriteToArgs + TryFrom<Row> + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        WeldsError: From<<R as TryFrom<Row>>::Error>,
+    {
+        self.include_nested(relationship, |related| related)
+    }
+
+    /// Loads the related rows of another relationship, along with their own relations
+    pub fn include_nested<R, Ship>(
+        self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        nested: impl FnOnce(IncludeBuilder<R>) -> IncludeBuilder<R>,
+    ) -> Self
+    where
+        T: HasRelations,
+        <T as HasSchema>::Schema: UniqueIdentifier,
+        Ship: Relationship<R> + Related<R>,
+        R: Send + Sync + HasSchema + WriteToArgs + TryFrom<Row> + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        WeldsError: From<<R as TryFrom<Row>>::Error>,
+    {
+        self.include_with(relationship, |query| query, nested)
+    }
+
+    /// Loads the related rows of another relationship the query in `filter` finds.
+    /// A limit or offset is applied to the related rows of each row on their own
+    /// (all of them for a `BelongsToMany`)
+    pub fn include_where<R, Ship>(
+        self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        filter: impl FnOnce(QueryBuilder<R>) -> QueryBuilder<R>,
+    ) -> Self
+    where
+        T: HasRelations,
+        <T as HasSchema>::Schema: UniqueIdentifier,
+        Ship: Relationship<R> + Related<R>,
+        R: Send + Sync + HasSchema + WriteToArgs + TryFrom<Row> + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        WeldsError: From<<R as TryFrom<Row>>::Error>,
+    {
+        self.include_with(relationship, filter, |related| related)
+    }
+
+    fn include_with<R, Ship>(
+        mut self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        filter: impl FnOnce(QueryBuilder<R>) -> QueryBuilder<R>,
+        nested: impl FnOnce(IncludeBuilder<R>) -> IncludeBuilder<R>,
+    ) -> Self
+    where
+        T: HasRelations,
+        <T as HasSchema>::Schema: UniqueIdentifier,
+        Ship: Relationship<R> + Related<R>,
+        R: Send + Sync + HasSchema + WriteToArgs + TryFrom<Row> + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        WeldsError: From<<R as TryFrom<Row>>::Error>,
+    {
+        let ship = relationship(Default::default());
+        let (my_keys, their_keys) = key_columns::<R, R::Schema, T::Schema>(&ship);
+        let through = ship.through();
+
+        // the related rows of all the rows the parent query finds
+        let parent: Arc<dyn KeySource> = Arc::new(KeysOf {
+            query: self.query.clone(),
+            columns: my_keys.clone(),
+        });
+        let keys: Arc<dyn KeySource> = match through {
+            None => parent.clone(),
+            Some(through) => Arc::new(ThroughKeys {
+                through,
+                parent: parent.clone(),
+            }),
+        };
+        let mut related: QueryBuilder<R> = QueryBuilder::new();
+        related.wheres.push(Box::new(ParentIn {
+            columns: their_keys.clone(),
+            parent: keys,
+        }));
+        let mut related = filter(related);
+
+        // a limit is for the rows of each parent, not all of them.
+        // The rows on the other side of a join table don't know their parent, it stays as is
+        let (mut limit, mut offset) = (None, None);
+        if through.is_none() {
+            limit = related.limit.take();
+            offset = related.offset.take();
+        }
+        let mut related = IncludeBuilder::new(related);
+        if limit.is_some() || offset.is_some() {
+            related.per_parent = Some(PerParent {
+                columns: their_keys.clone(),
+                limit,
+                offset,
+            });
+        }
+        let related = nested(related);
+
+        self.includes.push(Include {
+            id: RelationId::of::<R>(&my_keys, &their_keys),
+            parent_keys: my_keys,
+            node: Box::new(Child {
+                builder: related,
+                keys: their_keys,
+                links: through.map(|through| Links { through, parent }),
+            }),
+        });
+        self
+    }
+
+    /// Executes the query and the queries of all the includes, as one batch
+    pub async fn run(&self, client: &dyn Client) -> Result<DataSet<T>>
+    where
+        T: WriteToArgs + TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let mut statements = Vec::default();
+        in_namespace(client, || self.statements(client.syntax(), &mut statements));
+        let fetches: Vec<Fetch> = statements
+            .iter()
+            .map(|(sql, args)| Fetch::new(sql, args))
+            .collect();
+        let results = client.fetch_many(&fetches).await?;
+        self.load(&mut results.into_iter())
+    }
+
+    // the SELECT of this level, then the SELECTs of each include in order
+    fn statements<'q>(&'q self, syntax: Syntax, out: &mut Vec<(String, ParamArgs<'q>)>) {
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let query = &self.query;
+        let sql = match &self.per_parent {
+            None => query.select_sql(syntax, &mut args),
+            Some(per) => query.select_writer(syntax).sql_limited_per(
+                &<T as HasSchema>::Schema::columns(),
+                &per.columns,
+                &per.limit,
+                &per.offset,
+                &query.wheres,
+                &query.exist_ins,
+                &query.orderby,
+                &mut args,
+            ),
+        };
+        out.push((sql, args.unwrap()));
+        for include in &self.includes {
+            include.node.statements(syntax, out);
+        }
+    }
+
+    // reads the results back in the order of the statements
+    fn load(&self, results: &mut dyn Iterator<Item = Vec<Row>>) -> Result<DataSet<T>>
+    where
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let mut rows = Vec::default();
+        for row in results.next().unwrap_or_default() {
+            rows.push(DbState::db_loaded(T::try_from(row)?));
+        }
+        let mut related = Vec::default();
+        for include in &self.includes {
+            related.push(LoadedRelation {
+                id: include.id.clone(),
+                parent_keys: include.parent_keys.clone(),
+                rows: include.node.load(results)?,
+            });
+        }
+        Ok(DataSet { rows, related })
+    }
+}
+
+// one included relation, hiding the type of its model
+trait Node: Send + Sync {
+    fn statements<'q>(&'q self, syntax: Syntax, out: &mut Vec<(String, ParamArgs<'q>)>);
+    fn load(
+        &self,
+        results: &mut dyn Iterator<Item = Vec<Row>>,
+    ) -> Result<Box<dyn Any + Send + Sync>>;
+}
+
+struct Child<R> {
+    builder: IncludeBuilder<R>,
+    keys: Vec<String>,
+    links: Option<Links>,
+}
+
+// the rows of the join table of a many-to-many relationship, for the rows of the parent query
+struct Links {
+    through: Through,
+    parent: Arc<dyn KeySource>,
+}
+
+impl Links {
+    fn statement<'q>(&'q self, syntax: Syntax) -> (String, ParamArgs<'q>) {
+        let writer = ColumnWriter::new(syntax);
+        let my_key = writer.excape(self.through.my_key);
+        let their_key = writer.excape(self.through.their_key);
+        let keys = self.parent.write(syntax, &NextParam::new(syntax));
+        let sql = format!(
+            "SELECT welds_link.{}, welds_link.{} FROM {} welds_link WHERE welds_link.{} IN ( {} )",
+            my_key,
+            their_key,
+            table_name(self.through.table),
+            my_key,
+            keys
+        );
+        let mut args: ParamArgs = Vec::default();
+        self.parent.bind(&mut args);
+        (sql, args)
+    }
+}
+
+impl<R> Node for Child<R>
+where
+    R: Send + Sync + HasSchema + WriteToArgs + TryFrom<Row> + 'static,
+    <R as HasSchema>::Schema: TableInfo + TableColumns,
+    WeldsError: From<<R as TryFrom<Row>>::Error>,
+{
+    fn statements<'q>(&'q self, syntax: Syntax, out: &mut Vec<(String, ParamArgs<'q>)>) {
+        if let Some(links) = &self.links {
+            out.push(links.statement(syntax));
+        }
+        self.builder.statements(syntax, out)
+    }
+
+    fn load(
+        &self,
+        results: &mut dyn Iterator<Item = Vec<Row>>,
+    ) -> Result<Box<dyn Any + Send + Sync>> {
+        let pairs = match &self.links {
+            Some(_) => results.next().unwrap_or_default(),
+            None => Vec::default(),
+        };
+        let set = self.builder.load(results)?;
+        let mut by_link: HashMap<Vec<LinkKey>, Vec<usize>> = HashMap::default();
+        for (index, row) in set.rows.iter().enumerate() {
+            if let Some(link) = link_of(row.deref(), &self.keys)? {
+                by_link.entry(link).or_default().push(index);
+            }
+        }
+        if self.links.is_none() {
+            return Ok(Box::new(RelatedRows { set, by_link }));
+        }
+
+        // the parents are linked to the rows through the pairs of keys in the join table
+        let mut by_parent: HashMap<Vec<LinkKey>, Vec<usize>> = HashMap::default();
+        for pair in &pairs {
+            let (parent, key) = match (read_link(pair, 0)?, read_link(pair, 1)?) {
+                (Some(parent), Some(key)) => (parent, key),
+                _ => continue,
+            };
+            if let Some(found) = by_link.get(&vec![key]) {
+                by_parent.entry(vec![parent]).or_default().extend(found);
+            }
+        }
+        // in the order of the related query
+        for found in by_parent.values_mut() {
+            found.sort_unstable();
+            found.dedup();
+        }
+        Ok(Box::new(RelatedRows {
+            set,
+            by_link: by_parent,
+        }))
+    }
+}
+
+// The values of the key columns, None if any of them is NULL
+fn link_of<T: WriteToArgs>(obj: &T, columns: &[String]) -> Result<Option<Vec<LinkKey>>> {
+    let mut args: ParamArgs = Vec::default();
+    for column in columns {
+        obj.bind(column, &mut args)?;
+    }
+    let keys: Result<Vec<Option<LinkKey>>> = args.iter().map(|v| LinkKey::of_param(*v)).collect();
+    Ok(keys?.into_iter().collect())
+}
+
+/// Limits the related rows to the ones linked to the rows of the parent query
+/// `alias.column IN ( SELECT key FROM parent ... )`
+struct ParentIn {
+    columns: Vec<String>,
+    parent: Arc<dyn KeySource>,
+}
+
+impl ClauseAdder for ParentIn {
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        self.parent.bind(args);
+    }
+
+    fn clause(&self, syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
+        let keys = self.parent.write(syntax, next_params);
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|c| format!("{}.{}", alias, c))
+            .collect();
+        Some(keys_in(
+            syntax,
+            &columns,
+            &self.parent.columns(syntax),
+            &keys,
+        ))
+    }
+}
+
+trait KeySource: Send + Sync {
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p;
+    fn write(&self, syntax: Syntax, next_params: &NextParam) -> String;
+    // the columns the keys are selected as
+    fn columns(&self, syntax: Syntax) -> Vec<String>;
+}
+
+// the key columns of the rows a query finds
+struct KeysOf<T> {
+    query: Arc<QueryBuilder<T>>,
+    columns: Vec<String>,
+}
+
+impl<T> KeySource for KeysOf<T>
+where
+    T: Send + Sync + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        // in the order build_where writes them
+        for clause in &self.query.wheres {
+            clause.bind(args);
+        }
+        for clause in &self.query.exist_ins {
+            clause.bind(args);
+        }
+    }
+
+    fn write(&self, syntax: Syntax, next_params: &NextParam) -> String {
+        let query = &self.query;
+        let known = <T as HasSchema>::Schema::columns();
+        let columns: Vec<Column> = self
+            .columns
+            .iter()
+            .map(|name| {
+                let found = known.iter().find(|c| c.name() == name).cloned();
+                found.unwrap_or_else(|| Column::new(name.as_str(), "", false))
+            })
+            .collect();
+        // the ordering only matters to which rows a limit or offset leaves
+        let limited = query.limit.is_some() || query.offset.is_some();
+        let orders: &[OrderBy] = if limited { &query.orderby } else { &[] };
+        let sql = query.select_writer(syntax).sql_with_params(
+            &columns,
+            &query.wheres,
+            &query.exist_ins,
+            &query.limit,
+            &query.offset,
+            orders,
+            next_params,
+            &mut None,
+        );
+        if !limited {
+            return sql;
+        }
+        // MySQL can't LIMIT a subquery of an IN, it can a derived table
+        let columns: Vec<String> = self
+            .columns(syntax)
+            .iter()
+            .map(|c| format!("welds_keys.{}", c))
+            .collect();
+        format!("SELECT {} FROM ( {} ) welds_keys", columns.join(", "), sql)
+    }
+
+    fn columns(&self, syntax: Syntax) -> Vec<String> {
+        let writer = ColumnWriter::new(syntax);
+        self.columns.iter().map(|c| writer.excape(c)).collect()
+    }
+}
+
+// the keys of the rows linked to the rows of the parent query in a join table
+struct ThroughKeys {
+    through: Through,
+    parent: Arc<dyn KeySource>,
+}
+
+impl KeySource for ThroughKeys {
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        self.parent.bind(args);
+    }
+
+    fn write(&self, syntax: Syntax, next_params: &NextParam) -> String {
+        let writer = ColumnWriter::new(syntax);
+        let my_key = writer.excape(self.through.my_key);
+        let their_key = writer.excape(self.through.their_key);
+        let keys = self.parent.write(syntax, next_params);
+        format!(
+            "SELECT welds_link.{} FROM {} welds_link WHERE welds_link.{} IN ( {} )",
+            their_key,
+            table_name(self.through.table),
+            my_key,
+            keys
+        )
+    }
+
+    fn columns(&self, syntax: Syntax) -> Vec<String> {
+        vec![ColumnWriter::new(syntax).excape(self.through.their_key)]
+    }
+}
+
+/// The rows of a query run with `include`, along with the related rows that were loaded.
+///
+/// The related rows are read with `get` on each row
+pub struct DataSet<T> {
+    rows: Vec<DbState<T>>,
+    related: Vec<LoadedRelation>,
+}
+
+struct LoadedRelation {
+    id: RelationId,
+    parent_keys: Vec<String>,
+    rows: Box<dyn Any + Send + Sync>,
+}
+
+// the rows of a relation, by the key they are linked to their parent with
+struct RelatedRows<R> {
+    set: DataSet<R>,
+    by_link: HashMap<Vec<LinkKey>, Vec<usize>>,
+}
+
+impl<T> DataSet<T> {
+    /// The number of rows the query found
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The row at this index
+    pub fn get(&self, index: usize) -> Option<Loaded<'_, T>> {
+        match index < self.rows.len() {
+            true => Some(Loaded { set: self, index }),
+            false => None,
+        }
+    }
+
+    /// The rows the query found, in the order it returned them
+    pub fn iter(&self) -> impl Iterator<Item = Loaded<'_, T>> {
+        (0..self.rows.len()).map(move |index| Loaded { set: self, index })
+    }
+
+    /// The rows the query found, without the related rows
+    pub fn into_inner(self) -> Vec<DbState<T>> {
+        self.rows
+    }
+}
+
+/// A row of a `DataSet`. Derefs to the model, and can read the related rows that were included
+pub struct Loaded<'a, T> {
+    set: &'a DataSet<T>,
+    index: usize,
+}
+
+impl<T> Clone for Loaded<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Loaded<'_, T> {}
+
+impl<T> Deref for Loaded<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.set.rows[self.index]
+    }
+}
+
+impl<'a, T> Loaded<'a, T> {
+    /// The row, as it was loaded from the database
+    pub fn state(&self) -> &'a DbState<T> {
+        &self.set.rows[self.index]
+    }
+
+    /// The rows of a relationship that are linked to this row.
+    /// An `Option` for a `BelongsTo` or `HasOne`, a `Vec` for a `HasMany` or `BelongsToMany`
+    ///
+    /// Panics if the relationship wasn't included in the query
+    pub fn get<R, Ship>(
+        &self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+    ) -> Ship::Loaded<'a>
+    where
+        T: HasSchema + HasRelations + WriteToArgs,
+        <T as HasSchema>::Schema: UniqueIdentifier,
+        Ship: Relationship<R> + Related<R>,
+        R: HasSchema + 'static,
+        <R as HasSchema>::Schema: UniqueIdentifier,
+    {
+        let ship = relationship(Default::default());
+        let (my_keys, their_keys) = key_columns::<R, R::Schema, T::Schema>(&ship);
+        let id = RelationId::of::<R>(&my_keys, &their_keys);
+        let set: &'a DataSet<T> = self.set;
+        let loaded = set.related.iter().find(|r| r.id == id);
+        let loaded = loaded.unwrap_or_else(|| {
+            panic!(
+                "{} was not included in the query",
+                std::any::type_name::<R>()
+            )
+        });
+        let related: &'a RelatedRows<R> = loaded.rows.downcast_ref().unwrap();
+        let link = link_of(&*set.rows[self.index], &loaded.parent_keys)
+            .ok()
+            .flatten();
+        let found = link.and_then(|link| related.by_link.get(&link));
+        let found = found.map(|f| f.as_slice()).unwrap_or_default();
+        Ship::pick(&related.set, found)
+    }
+}
+
+/// What reading an included relationship of a row gives you
+pub trait Related<R> {
+    type Loaded<'a>
+    where
+        R: 'a;
+
+    /// The related rows at these indexes of the set
+    fn pick<'a>(set: &'a DataSet<R>, found: &[usize]) -> Self::Loaded<'a>;
+}
+
+impl<R> Related<R> for BelongsTo<R> {
+    type Loaded<'a>
+        = Option<Loaded<'a, R>>
+    where
+        R: 'a;
+
+    fn pick<'a>(set: &'a DataSet<R>, found: &[usize]) -> Self::Loaded<'a> {
+        found.first().map(|&index| Loaded { set, index })
+    }
+}
+
+impl<R> Related<R> for HasOne<R> {
+    type Loaded<'a>
+        = Option<Loaded<'a, R>>
+    where
+        R: 'a;
+
+    fn pick<'a>(set: &'a DataSet<R>, found: &[usize]) -> Self::Loaded<'a> {
+        found.first().map(|&index| Loaded { set, index })
+    }
+}
+
+impl<R> Related<R> for BelongsToMany<R> {
+    type Loaded<'a>
+        = Vec<Loaded<'a, R>>
+    where
+        R: 'a;
+
+    fn pick<'a>(set: &'a DataSet<R>, found: &[usize]) -> Self::Loaded<'a> {
+        found.iter().map(|&index| Loaded { set, index }).collect()
+    }
+}
+
+impl<R> Related<R> for HasMany<R> {
+    type Loaded<'a>
+        = Vec<Loaded<'a, R>>
+    where
+        R: 'a;
+
+    fn pick<'a>(set: &'a DataSet<R>, found: &[usize]) -> Self::Loaded<'a> {
+        found.iter().map(|&index| Loaded { set, index }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests;
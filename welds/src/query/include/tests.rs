@@ -0,0 +1,538 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::value::Value;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "customers")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(HasMany(orders, Order, "customer_id"))]
+struct Customer {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "customers")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(HasOne(profile, Profile, "customer_id"))]
+struct Member {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "profiles")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Profile {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub customer_id: i32,
+    pub bio: String,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsTo(customer, Customer, "customer_id"))]
+#[welds(HasMany(items, Item, "order_id"))]
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub customer_id: Option<i32>,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "items")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsTo(order, Order, "order_id"))]
+#[welds(BelongsTo(product, Product, "product_id"))]
+struct Item {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub order_id: i32,
+    pub product_id: i32,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+fn order(id: i32, customer_id: Option<i32>) -> MockRow {
+    MockRow::new()
+        .with("id", id)
+        .with("customer_id", customer_id)
+}
+
+fn item(id: i32, order_id: i32, product_id: i32) -> MockRow {
+    MockRow::new()
+        .with("id", id)
+        .with("order_id", order_id)
+        .with("product_id", product_id)
+}
+
+fn named(id: i32, name: &str) -> MockRow {
+    MockRow::new().with("id", id).with("name", name)
+}
+
+#[test]
+fn should_select_the_related_rows_of_every_level_in_one_batch() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let q = Order::where_col(|o| o.id.gt(10))
+            .include(|o| o.customer)
+            .include_nested(|o| o.items, |items| items.include(|i| i.product));
+        q.run(&client).await.unwrap();
+
+        let queries = client.queries();
+        let sqls: Vec<&str> = queries.iter().map(|q| q.sql.as_str()).collect();
+        let items = r#"t1.order_id IN ( SELECT t1."id" FROM orders t1 WHERE ( t1.id > $1 ) )"#;
+        assert_eq!(
+            sqls,
+            vec![
+                r#"SELECT t1."id", t1."customer_id" FROM orders t1 WHERE ( t1.id > $1 )"#.to_owned(),
+                r#"SELECT t1."id", t1."name" FROM customers t1 WHERE ( t1.id IN ( SELECT t1."customer_id" FROM orders t1 WHERE ( t1.id > $1 ) ) )"#.to_owned(),
+                format!(r#"SELECT t1."id", t1."order_id", t1."product_id" FROM items t1 WHERE ( {} )"#, items),
+                format!(r#"SELECT t1."id", t1."name" FROM products t1 WHERE ( t1.id IN ( SELECT t1."product_id" FROM items t1 WHERE ( {} ) ) )"#, items),
+            ]
+        );
+        for query in &queries {
+            assert_eq!(query.params, vec![Value::Int(10)]);
+        }
+    });
+}
+
+#[test]
+fn should_link_the_related_rows_to_their_parents() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows(
+            "FROM orders",
+            vec![order(1, Some(7)), order(2, None), order(3, Some(8))],
+        );
+        client.enqueue_rows("FROM customers", vec![named(7, "Ann"), named(8, "Bob")]);
+        client.enqueue_rows(
+            "FROM items",
+            vec![item(10, 1, 100), item(11, 3, 100), item(12, 1, 101)],
+        );
+        client.enqueue_rows("FROM products", vec![named(100, "Pen"), named(101, "Ink")]);
+
+        let orders = Order::all()
+            .include(|o| o.customer)
+            .include_nested(|o| o.items, |items| items.include(|i| i.product))
+            .run(&client)
+            .await
+            .unwrap();
+        assert_eq!(orders.len(), 3);
+
+        let customers: Vec<Option<String>> = orders
+            .iter()
+            .map(|o| o.get(|o| o.customer).map(|c| c.name.clone()))
+            .collect();
+        assert_eq!(
+            customers,
+            vec![Some("Ann".into()), None, Some("Bob".into())]
+        );
+
+        let products = |index: usize| -> Vec<String> {
+            let order = orders.get(index).unwrap();
+            order
+                .get(|o| o.items)
+                .iter()
+                .map(|i| i.get(|i| i.product).unwrap().name.clone())
+                .collect()
+        };
+        assert_eq!(products(0), vec!["Pen", "Ink"]);
+        assert!(products(1).is_empty());
+        assert_eq!(products(2), vec!["Pen"]);
+    });
+}
+
+#[test]
+fn should_select_the_keys_of_a_limited_parent_in_a_derived_table() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Mysql);
+        Customer::all()
+            .order_by_asc(|c| c.name)
+            .limit(5)
+            .include(|c| c.orders)
+            .run(&client)
+            .await
+            .unwrap();
+        let sql = client.last_sql().unwrap();
+        let expected = "SELECT t1.id, t1.customer_id FROM orders t1 WHERE ( t1.customer_id IN ( SELECT welds_keys.id FROM ( SELECT t1.id FROM customers t1 ORDER BY name ASC LIMIT 0, 5 ) welds_keys ) )";
+        assert_eq!(sql, expected);
+    });
+}
+
+#[test]
+#[should_panic(expected = "was not included")]
+fn should_panic_reading_a_relation_that_was_not_included() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("FROM orders", vec![order(1, Some(7))]);
+        let orders = Order::all()
+            .include(|o| o.customer)
+            .run(&client)
+            .await
+            .unwrap();
+        orders.get(0).unwrap().get(|o| o.items);
+    });
+}
+
+#[test]
+fn should_filter_and_order_the_related_rows() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("FROM orders", vec![order(1, None)]);
+        client.enqueue_rows(
+            "FROM items",
+            vec![item(12, 1, 101), item(11, 1, 100), item(10, 1, 100)],
+        );
+        let orders = Order::all()
+            .include_where(
+                |o| o.items,
+                |items| {
+                    items
+                        .where_col(|i| i.product_id.gte(100))
+                        .order_by_desc(|i| i.id)
+                },
+            )
+            .run(&client)
+            .await
+            .unwrap();
+
+        let sql = client.last_sql().unwrap();
+        let expected = r#"SELECT t1."id", t1."order_id", t1."product_id" FROM items t1 WHERE ( t1.order_id IN ( SELECT t1."id" FROM orders t1 ) AND t1.product_id >= $1 ) ORDER BY id DESC"#;
+        assert_eq!(sql, expected);
+
+        let order = orders.get(0).unwrap();
+        let ids: Vec<i32> = order.get(|o| o.items).iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![12, 11, 10]);
+    });
+}
+
+#[test]
+fn should_limit_the_related_rows_of_each_parent() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        Order::where_col(|o| o.id.gt(10))
+            .include_where(|o| o.items, |items| items.order_by_desc(|i| i.id).limit(3))
+            .run(&client)
+            .await
+            .unwrap();
+        let sql = client.last_sql().unwrap();
+        let cols = r#"t1."id", t1."order_id", t1."product_id""#;
+        let expected = format!(
+            r#"SELECT {cols} FROM ( SELECT {cols}, ROW_NUMBER() OVER (PARTITION BY t1."order_id" ORDER BY id DESC) AS welds_rn FROM items t1 WHERE ( t1.order_id IN ( SELECT t1."id" FROM orders t1 WHERE ( t1.id > $1 ) ) ) ) t1 WHERE t1.welds_rn > 0 AND t1.welds_rn <= 3 ORDER BY id DESC"#
+        );
+        assert_eq!(sql, expected);
+    });
+}
+
+#[test]
+fn should_read_a_has_one_relation_as_an_option() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("FROM customers", vec![named(7, "Ann"), named(8, "Bob")]);
+        client.enqueue_rows(
+            "FROM profiles",
+            vec![MockRow::new()
+                .with("id", 1)
+                .with("customer_id", 8)
+                .with("bio", "hi")],
+        );
+        let members = Member::all()
+            .include(|m| m.profile)
+            .run(&client)
+            .await
+            .unwrap();
+
+        let sql = client.last_sql().unwrap();
+        let expected = r#"SELECT t1."id", t1."customer_id", t1."bio" FROM profiles t1 WHERE ( t1.customer_id IN ( SELECT t1."id" FROM customers t1 ) )"#;
+        assert_eq!(sql, expected);
+
+        let bios: Vec<Option<String>> = members
+            .iter()
+            .map(|m| m.get(|m| m.profile).map(|p| p.bio.clone()))
+            .collect();
+        assert_eq!(bios, vec![None, Some("hi".into())]);
+    });
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "users")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsToMany(roles, Role, through = "roles_users"))]
+struct User {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "roles")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Role {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+#[test]
+fn should_load_the_rows_of_a_many_to_many_relation() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("FROM users", vec![named(1, "Ann"), named(2, "Bob")]);
+        let link =
+            |user: i32, role: i32| MockRow::new().with("user_id", user).with("role_id", role);
+        client.enqueue_rows(
+            "FROM roles_users",
+            vec![link(1, 10), link(2, 10), link(1, 11)],
+        );
+        client.enqueue_rows("FROM roles", vec![named(11, "admin"), named(10, "staff")]);
+
+        let users = User::all()
+            .include_where(|u| u.roles, |roles| roles.order_by_asc(|r| r.name))
+            .run(&client)
+            .await
+            .unwrap();
+
+        let queries = client.queries();
+        let sqls: Vec<&str> = queries.iter().map(|q| q.sql.as_str()).collect();
+        assert_eq!(
+            sqls[1..],
+            [
+                r#"SELECT welds_link."user_id", welds_link."role_id" FROM roles_users welds_link WHERE welds_link."user_id" IN ( SELECT t1."id" FROM users t1 )"#,
+                r#"SELECT t1."id", t1."name" FROM roles t1 WHERE ( t1.id IN ( SELECT welds_link."role_id" FROM roles_users welds_link WHERE welds_link."user_id" IN ( SELECT t1."id" FROM users t1 ) ) ) ORDER BY name ASC"#,
+            ]
+        );
+
+        let roles: Vec<Vec<String>> = users
+            .iter()
+            .map(|u| u.get(|u| u.roles).iter().map(|r| r.name.clone()).collect())
+            .collect();
+        assert_eq!(roles, vec![vec!["admin", "staff"], vec!["staff"]]);
+    });
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "categories")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(HasMany(children, Self, "parent_id"))]
+struct Category {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub parent_id: Option<i32>,
+    pub name: String,
+}
+
+#[test]
+fn should_load_the_children_of_the_children_from_the_same_table() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let category = |id: i32, parent_id: Option<i32>, name: &str| {
+            MockRow::new()
+                .with("id", id)
+                .with("parent_id", parent_id)
+                .with("name", name)
+        };
+        client.enqueue_rows("FROM categories", vec![category(1, None, "root")]);
+        client.enqueue_rows(
+            "FROM categories",
+            vec![category(2, Some(1), "a"), category(3, Some(1), "b")],
+        );
+        client.enqueue_rows("FROM categories", vec![category(4, Some(2), "a1")]);
+
+        let roots = Category::where_col(|c| c.parent_id.equal(None))
+            .include_nested(|c| c.children, |children| children.include(|c| c.children))
+            .run(&client)
+            .await
+            .unwrap();
+
+        let sql = client.last_sql().unwrap();
+        let expected = r#"SELECT t1."id", t1."parent_id", t1."name" FROM categories t1 WHERE ( t1.parent_id IN ( SELECT t1."id" FROM categories t1 WHERE ( t1.parent_id IN ( SELECT t1."id" FROM categories t1 WHERE ( t1.parent_id IS NULL ) ) ) ) )"#;
+        assert_eq!(sql, expected);
+
+        let root = roots.get(0).unwrap();
+        let tree: Vec<(String, Vec<String>)> = root
+            .get(|c| c.children)
+            .iter()
+            .map(|c| {
+                let children = c.get(|c| c.children);
+                let names = children.iter().map(|c| c.name.clone());
+                (c.name.clone(), names.collect())
+            })
+            .collect();
+        assert_eq!(
+            tree,
+            vec![("a".into(), vec!["a1".into()]), ("b".into(), vec![])]
+        );
+    });
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(HasMany(lines, Line, "tenant_id", "order_id"))]
+struct TenantOrder {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub tenant_id: i32,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "lines")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Line {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub tenant_id: i32,
+    pub order_id: i32,
+}
+
+#[test]
+fn should_link_the_related_rows_by_every_column_of_the_key() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let order =
+            |id: i32, tenant_id: i32| MockRow::new().with("id", id).with("tenant_id", tenant_id);
+        let line = |id: i32, tenant_id: i32, order_id: i32| {
+            MockRow::new()
+                .with("id", id)
+                .with("tenant_id", tenant_id)
+                .with("order_id", order_id)
+        };
+        // the same order id for two tenants
+        client.enqueue_rows("FROM orders", vec![order(1, 1), order(1, 2)]);
+        client.enqueue_rows(
+            "FROM lines",
+            vec![line(10, 1, 1), line(11, 2, 1), line(12, 2, 1)],
+        );
+
+        let orders = TenantOrder::all()
+            .include(|o| o.lines)
+            .run(&client)
+            .await
+            .unwrap();
+
+        let sql = client.last_sql().unwrap();
+        let expected = r#"SELECT t1."id", t1."tenant_id", t1."order_id" FROM lines t1 WHERE ( (t1.order_id, t1.tenant_id) IN ( SELECT t1."id", t1."tenant_id" FROM orders t1 ) )"#;
+        assert_eq!(sql, expected);
+
+        let lines: Vec<Vec<i32>> = orders
+            .iter()
+            .map(|o| o.get(|o| o.lines).iter().map(|l| l.id).collect())
+            .collect();
+        assert_eq!(lines, vec![vec![10], vec![11, 12]]);
+    });
+}
+
+#[cfg(feature = "uuid")]
+mod uuid_keys {
+    use super::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "accounts")]
+    #[welds_path(crate)] // needed only within the welds crate.
+    #[welds(HasMany(notes, Note, "account_id"))]
+    #[welds(BelongsToMany(tags, Tag, through = "accounts_tags"))]
+    struct Account {
+        #[welds(primary_key)]
+        pub id: Uuid,
+        pub name: String,
+    }
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "notes")]
+    #[welds_path(crate)] // needed only within the welds crate.
+    struct Note {
+        #[welds(primary_key)]
+        pub id: i32,
+        pub account_id: Uuid,
+    }
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "tags")]
+    #[welds_path(crate)] // needed only within the welds crate.
+    struct Tag {
+        #[welds(primary_key)]
+        pub id: Uuid,
+        pub name: String,
+    }
+
+    fn id(n: u8) -> Uuid {
+        Uuid::from_bytes([n; 16])
+    }
+
+    fn account(n: u8, name: &str) -> MockRow {
+        MockRow::new().with("id", id(n)).with("name", name)
+    }
+
+    #[test]
+    fn should_link_the_related_rows_by_a_uuid_key() {
+        block_on(async {
+            let client = MockClient::new(Syntax::Postgres);
+            client.enqueue_rows("FROM accounts", vec![account(1, "Ann"), account(2, "Bob")]);
+            let note =
+                |n: i32, account: u8| MockRow::new().with("id", n).with("account_id", id(account));
+            client.enqueue_rows("FROM notes", vec![note(10, 2), note(11, 1), note(12, 2)]);
+
+            let accounts = Account::all()
+                .include(|a| a.notes)
+                .run(&client)
+                .await
+                .unwrap();
+            let notes: Vec<Vec<i32>> = accounts
+                .iter()
+                .map(|a| a.get(|a| a.notes).iter().map(|n| n.id).collect())
+                .collect();
+            assert_eq!(notes, vec![vec![11], vec![10, 12]]);
+        });
+    }
+
+    #[test]
+    fn should_link_the_rows_of_a_join_table_that_keeps_uuids_as_text() {
+        block_on(async {
+            let client = MockClient::new(Syntax::Sqlite);
+            client.enqueue_rows("FROM accounts", vec![account(1, "Ann"), account(2, "Bob")]);
+            let link = |account: u8, tag: u8| {
+                MockRow::new()
+                    .with("account_id", id(account).to_string())
+                    .with("tag_id", id(tag).to_string())
+            };
+            client.enqueue_rows(
+                "FROM accounts_tags",
+                vec![link(1, 3), link(2, 3), link(2, 4)],
+            );
+            let tag = |n: u8, name: &str| MockRow::new().with("id", id(n)).with("name", name);
+            client.enqueue_rows("FROM tags", vec![tag(3, "new"), tag(4, "vip")]);
+
+            let accounts = Account::all()
+                .include(|a| a.tags)
+                .run(&client)
+                .await
+                .unwrap();
+            let tags: Vec<Vec<String>> = accounts
+                .iter()
+                .map(|a| a.get(|a| a.tags).iter().map(|t| t.name.clone()).collect())
+                .collect();
+            assert_eq!(tags, vec![vec!["new"], vec!["new", "vip"]]);
+        });
+    }
+
+    #[test]
+    fn should_fail_to_link_rows_by_a_key_of_a_type_a_value_cant_hold() {
+        let key = LinkKey::of(Value::Opaque("rust_decimal::Decimal".to_owned()));
+        assert!(matches!(key, Err(WeldsError::UnlinkableKey(_))));
+    }
+}
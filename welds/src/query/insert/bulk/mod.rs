@@ -0,0 +1,700 @@
+use super::single::insert_row;
+use crate::errors::Result;
+use crate::errors::WeldsError::InsertFailed;
+use crate::model_traits::{ColumnDefaultCheck, UpdateFromRow};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo, WriteToArgs};
+use crate::query::clause::ParamArgs;
+use crate::query::helpers::invalidate_cached;
+use crate::query::update::bulk::build_output;
+use crate::relations::{parents_transaction, recount_parents};
+use crate::state::DbState;
+use crate::writers::ColumnWriter;
+use crate::writers::NextParam;
+use crate::writers::{in_namespace, table_name};
+use crate::Client;
+use crate::Syntax;
+use welds_connections::{Fetch, TransactStart};
+
+/// Executes the query in the database Bulk Inserting values.
+///
+/// Large inserts are split into several statements so none of them go over the
+/// number of params the client can send (`Client::capabilities`).
+pub async fn run<T>(conn: &dyn Client, data: &[T]) -> Result<()>
+where
+    T: WriteToArgs + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    if data.is_empty() {
+        return Ok(());
+    }
+
+    // // If postgres do the fast bulk insert
+    // if let Syntax::Postgres = conn.syntax() {
+    //     return run_fast(conn, data).await;
+    // }
+
+    // the rows and the parents they count are written together
+    match parents_transaction::<T>(conn) {
+        Some(start) => {
+            let trans = start.begin().await?;
+            insert_rows(&trans, data).await?;
+            trans.commit().await?;
+        }
+        None => insert_rows(conn, data).await?,
+    }
+    invalidate_cached::<T>(conn).await;
+
+    Ok(())
+}
+
+async fn insert_rows<T>(conn: &dyn Client, data: &[T]) -> Result<()>
+where
+    T: WriteToArgs + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let writer = BulkWriter::new::<T>(conn);
+    for chunk in data.chunks(writer.chunk_size) {
+        let mut args: ParamArgs = Vec::default();
+        let sql = writer.write(chunk, &mut args)?;
+        conn.execute(&sql, &args).await?;
+    }
+    recount_parents(data, conn).await
+}
+
+/// The outcome of `bulk_insert_ignore`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InsertIgnored {
+    /// Rows that were written
+    pub inserted: u64,
+    /// Rows that were left out because they conflict with a row already in the table
+    pub skipped: u64,
+}
+
+/// Bulk inserts the values, skipping the ones that conflict with rows already in the table.
+/// Re-running the same insert is safe, which makes this a good fit for idempotent ingestion.
+///
+/// Unlike `bulk_insert`, the primary keys of the values ARE written, they are what makes a
+/// row a duplicate. Postgres, CockroachDB and Sqlite use `ON CONFLICT DO NOTHING`, MySql and
+/// MariaDB use `INSERT IGNORE`. MSSQL has neither, so rows whose primary key already exists
+/// are filtered out with `WHERE NOT EXISTS`; other unique constraints are not checked there.
+pub async fn run_ignore<T>(conn: &dyn Client, data: &[T]) -> Result<InsertIgnored>
+where
+    T: WriteToArgs + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    if data.is_empty() {
+        return Ok(InsertIgnored::default());
+    }
+
+    // the rows and the parents they count are written together
+    let report = match parents_transaction::<T>(conn) {
+        Some(start) => {
+            let trans = start.begin().await?;
+            let report = insert_rows_ignore(&trans, data).await?;
+            trans.commit().await?;
+            report
+        }
+        None => insert_rows_ignore(conn, data).await?,
+    };
+    invalidate_cached::<T>(conn).await;
+    Ok(report)
+}
+
+async fn insert_rows_ignore<T>(conn: &dyn Client, data: &[T]) -> Result<InsertIgnored>
+where
+    T: WriteToArgs + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let mut report = InsertIgnored::default();
+    let writer = BulkWriter::with_primary_keys::<T>(conn);
+    for chunk in data.chunks(writer.chunk_size) {
+        let mut args: ParamArgs = Vec::default();
+        let sql = writer.write_ignore(chunk, &mut args)?;
+        let result = conn.execute(&sql, &args).await?;
+        let inserted = result.rows_affected().min(chunk.len() as u64);
+        report.inserted += inserted;
+        report.skipped += chunk.len() as u64 - inserted;
+    }
+    // the skipped rows aren't counted, the parents are recounted
+    recount_parents(data, conn).await?;
+    Ok(report)
+}
+
+/// Bulk inserts the values, updating each of them with the row the database created.
+///
+/// Generated primary keys and columns the database fills in (timestamps, defaults)
+/// are written back onto the values, in the same order they were given.
+/// Useful when you need the keys of new rows to link up their children.
+///
+/// Each row is numbered with an ordinal column, and inserted in that order.
+/// Postgres and MariaDB use `RETURNING`, the rows come back in the order they were inserted.
+/// MSSQL inserts with a `MERGE` that `OUTPUT`s the ordinal of each row, and Sqlite returns the
+/// `rowid` it gave each row, they are sorted by it. CockroachDB doesn't keep the order of the
+/// rows it inserts, the rows it returns are paired back up with the ordinals by the values
+/// that were written (values that are all the same are interchangeable). MySql computes the
+/// keys from `LAST_INSERT_ID()` when the server hands out consecutive ids
+/// (innodb_autoinc_lock_mode < 2) and reads the rows back by key. Otherwise MySql falls back
+/// to inserting one row at a time.
+pub async fn run_returning<T>(conn: &dyn Client, data: &mut [T]) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck + UpdateFromRow,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    if data.is_empty() {
+        return Ok(());
+    }
+    // the rows and the parents they count are written together
+    match parents_transaction::<T>(conn) {
+        Some(start) => {
+            let trans = start.begin().await?;
+            insert_rows_returning(&trans, data).await?;
+            trans.commit().await?;
+        }
+        None => insert_rows_returning(conn, data).await?,
+    }
+    invalidate_cached::<T>(conn).await;
+    Ok(())
+}
+
+async fn insert_rows_returning<T>(conn: &dyn Client, data: &mut [T]) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck + UpdateFromRow,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let syntax = conn.syntax();
+    let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
+
+    match syntax {
+        Syntax::Mysql if pks.len() == 1 && mysql_ids_are_consecutive(conn).await? => {
+            run_with_last_insert_id(conn, data).await?;
+        }
+        Syntax::Mysql => {
+            for obj in data.iter_mut() {
+                insert_row(obj, conn).await?;
+            }
+        }
+        _ => {
+            run_with_returning(conn, data).await?;
+        }
+    }
+    recount_parents(data, conn).await
+}
+
+/// Inserts all the values in one transaction, returning them with the rows the database created.
+/// Inserts like `bulk_insert_returning`, in as few statements as the backend allows.
+/// If any of the statements fail none of the values are inserted.
+pub async fn insert_many<T, C>(client: &C, mut data: Vec<T>) -> Result<Vec<DbState<T>>>
+where
+    C: TransactStart + ?Sized,
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck + UpdateFromRow,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let trans = client.begin().await?;
+    run_returning(&trans, &mut data).await?;
+    trans.commit().await?;
+    Ok(data.into_iter().map(DbState::db_loaded).collect())
+}
+
+async fn run_with_returning<T>(conn: &dyn Client, data: &mut [T]) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + UpdateFromRow,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let writer = BulkWriter::new::<T>(conn);
+    let output = build_output::<<T as HasSchema>::Schema>();
+    for chunk in data.chunks_mut(writer.chunk_size) {
+        let mut args: ParamArgs = Vec::default();
+        let rows = match writer.syntax {
+            // the rows are output in any order, with the ordinal they were given
+            Syntax::Mssql => {
+                let sql = writer.write_output(chunk, &output, &mut args)?;
+                let rows = conn.fetch_rows(&sql, &args).await?;
+                sort_rows_by(rows, "welds_ord")?
+            }
+            // the rowids are handed out in the order the rows are inserted
+            Syntax::Sqlite => {
+                let sql = writer.write_ordered(chunk, &mut args)?;
+                let sql = format!("{} RETURNING rowid AS welds_rowid, *", sql);
+                let rows = conn.fetch_rows(&sql, &args).await?;
+                sort_rows_by(rows, "welds_rowid")?
+            }
+            // paired up with their ordinals, and sorted by them
+            Syntax::CockroachDb => {
+                let sql = writer.write_paired(chunk, &mut args)?;
+                conn.fetch_rows(&sql, &args).await?
+            }
+            _ => {
+                let sql = writer.write_ordered(chunk, &mut args)?;
+                let sql = format!("{} RETURNING *", sql);
+                conn.fetch_rows(&sql, &args).await?
+            }
+        };
+        drop(args);
+        update_all_from_rows(chunk, rows)?;
+    }
+    Ok(())
+}
+
+// The rows in the order of a column that numbers them
+fn sort_rows_by(rows: Vec<crate::Row>, column: &str) -> Result<Vec<crate::Row>> {
+    let mut numbered = Vec::with_capacity(rows.len());
+    for row in rows {
+        let n: i64 = row.get(column)?;
+        numbered.push((n, row));
+    }
+    numbered.sort_by_key(|(n, _)| *n);
+    Ok(numbered.into_iter().map(|(_, row)| row).collect())
+}
+
+// With innodb_autoinc_lock_mode 0 or 1, a multi-row insert is given consecutive ids
+async fn mysql_ids_are_consecutive(conn: &dyn Client) -> Result<bool> {
+    let sql = "SELECT CAST(@@innodb_autoinc_lock_mode AS SIGNED) AS lock_mode";
+    let rows = conn.fetch_rows(sql, &[]).await?;
+    let lock_mode: Option<i64> = match rows.first() {
+        Some(row) => row.get("lock_mode")?,
+        None => None,
+    };
+    Ok(matches!(lock_mode, Some(0) | Some(1)))
+}
+
+async fn run_with_last_insert_id<T>(conn: &dyn Client, data: &mut [T]) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + UpdateFromRow,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let syntax = conn.syntax();
+    let writer = BulkWriter::new::<T>(conn);
+    let col_writer = ColumnWriter::new(syntax);
+    let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
+    let pk = col_writer.excape(pks[0].name());
+    let first_id_sql = "SELECT CAST(LAST_INSERT_ID() AS SIGNED) AS first_id, CAST(@@auto_increment_increment AS SIGNED) AS step";
+
+    for chunk in data.chunks_mut(writer.chunk_size) {
+        let mut args: ParamArgs = Vec::default();
+        let insert = writer.write(chunk, &mut args)?;
+
+        // WARNING: LAST_INSERT_ID() only works on the connection that did the insert
+        let statements = [
+            Fetch::new(&insert, &args).with_label("insert"),
+            Fetch::new(first_id_sql, &[]).with_label("last_insert_id"),
+        ];
+        let mut datasets = conn.fetch_many(&statements).await?;
+        drop(args);
+
+        let ids_row = datasets.pop().and_then(|mut rows| rows.pop());
+        let ids_row =
+            ids_row.ok_or_else(|| InsertFailed("Unable to read LAST_INSERT_ID()".to_owned()))?;
+        let first_id: i64 = ids_row.get("first_id")?;
+        let step: i64 = ids_row.get("step")?;
+        let ids: Vec<i64> = (0..chunk.len() as i64)
+            .map(|i| first_id + i * step)
+            .collect();
+
+        let next_params = NextParam::new(syntax);
+        let placeholders: Vec<String> = ids.iter().map(|_| next_params.next()).collect();
+        let sql = format!(
+            "SELECT * FROM {} WHERE {} IN ({}) ORDER BY {}",
+            writer.identifier,
+            pk,
+            placeholders.join(", "),
+            pk
+        );
+        let mut args: ParamArgs = Vec::default();
+        for id in &ids {
+            args.push(id);
+        }
+        let rows = conn.fetch_rows(&sql, &args).await?;
+        update_all_from_rows(chunk, rows)?;
+    }
+    Ok(())
+}
+
+// The rows are in the order of the values, sorted by their ordinal or by their keys
+fn update_all_from_rows<T>(chunk: &mut [T], mut rows: Vec<crate::Row>) -> Result<()>
+where
+    T: UpdateFromRow,
+{
+    if rows.len() != chunk.len() {
+        return Err(InsertFailed(format!(
+            "Expected {} inserted rows, Found {}",
+            chunk.len(),
+            rows.len()
+        )));
+    }
+    for (obj, row) in chunk.iter_mut().zip(rows.iter_mut()) {
+        UpdateFromRow::update_from_row(obj, row)?;
+    }
+    Ok(())
+}
+
+/// Writes the multi-row `INSERT` used by bulk inserts. Primary keys are left to the database
+pub(super) struct BulkWriter {
+    pub(super) syntax: Syntax,
+    pub(super) identifier: String,
+    pub(super) columns: Vec<String>,
+    pub(super) colnames: String,
+    pub(super) primary_keys: Vec<String>,
+    pub(super) chunk_size: usize,
+}
+
+impl BulkWriter {
+    fn new<T>(conn: &dyn Client) -> Self
+    where
+        T: HasSchema,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        Self::build::<T>(conn, false)
+    }
+
+    /// A writer that also writes the primary keys of the rows
+    fn with_primary_keys<T>(conn: &dyn Client) -> Self
+    where
+        T: HasSchema,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        Self::build::<T>(conn, true)
+    }
+
+    pub(super) fn build<T>(conn: &dyn Client, include_pks: bool) -> Self
+    where
+        T: HasSchema,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let syntax = conn.syntax();
+        let col_writer = ColumnWriter::new(syntax);
+        let all_columns = <<T as HasSchema>::Schema as TableColumns>::columns();
+        let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
+        let columns: Vec<String> = all_columns
+            .iter()
+            .filter(|c| include_pks || !pks.contains(c))
+            .map(|c| c.name().to_owned())
+            .collect();
+        let primary_keys: Vec<String> = pks.iter().map(|c| c.name().to_owned()).collect();
+
+        let identifier = in_namespace(conn, || {
+            table_name(&<<T as HasSchema>::Schema>::identifier().join("."))
+        });
+
+        let colnames: Vec<String> = columns.iter().map(|c| col_writer.excape(c)).collect();
+        let colnames = colnames.join(", ");
+
+        // number of rows per insert, keeping under the most params the client can send
+        let max_params = conn.capabilities().max_bind_params;
+        let chunk_size = (max_params / columns.len().max(1)).clamp(1, max_rows(syntax));
+
+        Self {
+            syntax,
+            identifier,
+            columns,
+            colnames,
+            primary_keys,
+            chunk_size,
+        }
+    }
+
+    fn write<'a, T>(&self, chunk: &'a [T], args: &mut ParamArgs<'a>) -> Result<String>
+    where
+        T: WriteToArgs,
+    {
+        let rows = self.write_values(chunk, args)?;
+        Ok(format!(
+            "INSERT INTO {} ({}) VALUES {}",
+            self.identifier, self.colnames, rows
+        ))
+    }
+
+    /// An insert of the rows in the order they are given. Each row is numbered with a
+    /// `welds_ord` column, and inserted sorted by it
+    fn write_ordered<'a, T>(&self, chunk: &'a [T], args: &mut ParamArgs<'a>) -> Result<String>
+    where
+        T: WriteToArgs,
+    {
+        let col_writer = ColumnWriter::new(self.syntax);
+        let next_params = NextParam::new(self.syntax);
+        let (table, cols) = (&self.identifier, &self.colnames);
+        let mut rows: Vec<String> = Vec::default();
+        for (ord, d) in chunk.iter().enumerate() {
+            let mut row: Vec<String> = Vec::default();
+            for col in &self.columns {
+                d.bind(col, args)?;
+                row.push(match (self.syntax, ord) {
+                    // MariaDB has no column list for a derived table, the first row names them
+                    (Syntax::MariaDb, 0) => {
+                        format!("{} AS {}", next_params.next(), col_writer.excape(col))
+                    }
+                    _ => next_params.next(),
+                });
+            }
+            rows.push(match self.syntax {
+                Syntax::MariaDb if ord == 0 => {
+                    format!("SELECT {},{} AS welds_ord", row.join(","), ord)
+                }
+                Syntax::MariaDb => format!("SELECT {},{}", row.join(","), ord),
+                _ => format!("({},{})", row.join(","), ord),
+            });
+        }
+        Ok(match self.syntax {
+            Syntax::MariaDb => format!(
+                "INSERT INTO {} ({}) SELECT {} FROM ({}) AS welds_rows ORDER BY welds_ord",
+                table,
+                cols,
+                cols,
+                rows.join(" UNION ALL ")
+            ),
+            // nor does Sqlite, the columns of VALUES are column1, column2, ...
+            Syntax::Sqlite => {
+                let n = self.columns.len();
+                let values: Vec<String> = (1..=n).map(|i| format!("column{}", i)).collect();
+                format!(
+                    "INSERT INTO {} ({}) SELECT {} FROM (VALUES {}) ORDER BY column{}",
+                    table,
+                    cols,
+                    values.join(", "),
+                    rows.join(","),
+                    n + 1
+                )
+            }
+            _ => format!(
+                "INSERT INTO {} ({}) SELECT {} FROM (VALUES {}) AS welds_rows ({}, welds_ord) ORDER BY welds_ord",
+                table,
+                cols,
+                cols,
+                rows.join(","),
+                cols
+            ),
+        })
+    }
+
+    /// A `MERGE` that inserts the rows, outputting the ordinal (`welds_ord`) of each one with
+    /// the row it created. MSSQL can't output the columns of the rows inserted from in an INSERT
+    fn write_output<'a, T>(
+        &self,
+        chunk: &'a [T],
+        output: &str,
+        args: &mut ParamArgs<'a>,
+    ) -> Result<String>
+    where
+        T: WriteToArgs,
+    {
+        let col_writer = ColumnWriter::new(self.syntax);
+        let next_params = NextParam::new(self.syntax);
+        let (table, cols) = (&self.identifier, &self.colnames);
+        let mut rows: Vec<String> = Vec::default();
+        for (ord, d) in chunk.iter().enumerate() {
+            let mut row: Vec<String> = Vec::default();
+            for col in &self.columns {
+                d.bind(col, args)?;
+                row.push(next_params.next());
+            }
+            rows.push(format!("({},{})", row.join(","), ord));
+        }
+        let values: Vec<String> = self
+            .columns
+            .iter()
+            .map(|c| format!("welds_rows.{}", col_writer.excape(c)))
+            .collect();
+        Ok(format!(
+            "MERGE INTO {} AS welds_target USING (VALUES {}) AS welds_rows ({}, welds_ord) ON 1 = 0 WHEN NOT MATCHED THEN INSERT ({}) VALUES ({}) {}, CAST(welds_rows.welds_ord AS BIGINT) AS welds_ord;",
+            table,
+            rows.join(","),
+            cols,
+            cols,
+            values.join(", "),
+            output
+        ))
+    }
+
+    /// An insert that returns the rows it created with the ordinal (`welds_ord`) of the values
+    /// they were created from, sorted by it. CockroachDB returns the rows in any order, they are
+    /// paired with the values by the columns that were written. Rows with the same values are
+    /// paired in order, which one goes with which doesn't matter
+    fn write_paired<'a, T>(&self, chunk: &'a [T], args: &mut ParamArgs<'a>) -> Result<String>
+    where
+        T: WriteToArgs,
+    {
+        let col_writer = ColumnWriter::new(self.syntax);
+        let next_params = NextParam::new(self.syntax);
+        let (table, cols) = (&self.identifier, &self.colnames);
+        let mut rows: Vec<String> = Vec::default();
+        for (ord, d) in chunk.iter().enumerate() {
+            let mut row: Vec<String> = Vec::default();
+            for col in &self.columns {
+                d.bind(col, args)?;
+                row.push(next_params.next());
+            }
+            rows.push(format!("({},{})", row.join(","), ord));
+        }
+        let (partition, list) = match self.columns.is_empty() {
+            true => (String::default(), String::default()),
+            false => (format!("PARTITION BY {}", cols), format!("{}, ", cols)),
+        };
+        let ordered = format!("{} ORDER BY welds_ord", partition);
+        let mut matches: Vec<String> = self
+            .columns
+            .iter()
+            .map(|c| {
+                let c = col_writer.excape(c);
+                format!("welds_inserted.{} IS NOT DISTINCT FROM welds_rows.{}", c, c)
+            })
+            .collect();
+        matches.push("welds_inserted.welds_n = welds_rows.welds_n".to_owned());
+        Ok(format!(
+            "WITH welds_rows ({list}welds_ord) AS ( VALUES {} ), \
+            welds_inserted AS ( INSERT INTO {} ({}) SELECT {} FROM welds_rows ORDER BY welds_ord RETURNING * ) \
+            SELECT welds_inserted.*, welds_rows.welds_ord \
+            FROM ( SELECT *, row_number() OVER ({partition}) AS welds_n FROM welds_inserted ) AS welds_inserted \
+            JOIN ( SELECT {list}welds_ord, row_number() OVER ({}) AS welds_n FROM welds_rows ) AS welds_rows \
+            ON {} ORDER BY welds_rows.welds_ord",
+            rows.join(","),
+            table,
+            cols,
+            cols,
+            ordered.trim_start(),
+            matches.join(" AND "),
+            list = list,
+            partition = partition,
+        ))
+    }
+
+    /// An insert that leaves out the rows that conflict with existing rows
+    fn write_ignore<'a, T>(&self, chunk: &'a [T], args: &mut ParamArgs<'a>) -> Result<String>
+    where
+        T: WriteToArgs,
+    {
+        let rows = self.write_values(chunk, args)?;
+        let (table, cols) = (&self.identifier, &self.colnames);
+        Ok(match self.syntax {
+            Syntax::Mysql | Syntax::MariaDb => {
+                format!("INSERT IGNORE INTO {} ({}) VALUES {}", table, cols, rows)
+            }
+            Syntax::Postgres | Syntax::CockroachDb | Syntax::Sqlite => format!(
+                "INSERT INTO {} ({}) VALUES {} ON CONFLICT DO NOTHING",
+                table, cols, rows
+            ),
+            Syntax::Mssql => {
+                let col_writer = ColumnWriter::new(self.syntax);
+                let values: Vec<String> = self
+                    .columns
+                    .iter()
+                    .map(|c| format!("welds_rows.{}", col_writer.excape(c)))
+                    .collect();
+                let matches: Vec<String> = self
+                    .primary_keys
+                    .iter()
+                    .map(|c| {
+                        let c = col_writer.excape(c);
+                        format!("welds_existing.{} = welds_rows.{}", c, c)
+                    })
+                    .collect();
+                format!(
+                    "INSERT INTO {} ({}) SELECT {} FROM (VALUES {}) AS welds_rows ({}) WHERE NOT EXISTS (SELECT 1 FROM {} welds_existing WHERE {})",
+                    table,
+                    cols,
+                    values.join(", "),
+                    rows,
+                    cols,
+                    table,
+                    matches.join(" AND ")
+                )
+            }
+        })
+    }
+
+    // (?,?),(?,?) with the values of each row bound
+    pub(super) fn write_values<'a, T>(
+        &self,
+        chunk: &'a [T],
+        args: &mut ParamArgs<'a>,
+    ) -> Result<String>
+    where
+        T: WriteToArgs,
+    {
+        let next_params = NextParam::new(self.syntax);
+        let mut rows: Vec<String> = Vec::default();
+        for d in chunk {
+            let mut row: Vec<String> = Vec::default();
+            for col in &self.columns {
+                d.bind(col, args)?;
+                row.push(next_params.next());
+            }
+            rows.push(format!("({})", row.join(",")));
+        }
+        Ok(rows.join(","))
+    }
+}
+
+// The most rows a single `VALUES` list can hold
+fn max_rows(syntax: Syntax) -> usize {
+    match syntax {
+        Syntax::Mssql => 1000,
+        _ => usize::MAX,
+    }
+}
+
+//  /// Executes the query in the database Bulk Inserting the values
+//  /// This method of bulk inserting is faster, but is not available for all data structures.
+//  /// This method is preferred if available
+//  pub(crate) async fn run_fast<T, C>(conn: &C, data: &[T]) -> Result<()>
+//  where
+//      C: Client,
+//      T: WriteToArgs + HasSchema,
+//      <T as HasSchema>::Schema: TableInfo + TableColumns,
+//      //'c: 'r,
+//      //DB: Database,
+//      //T: WriteBulkArrayToArgs<DB> + HasSchema,
+//      //<T as HasSchema>::Schema: TableInfo + TableColumns<DB>,
+//      //<DB as HasArguments<'r>>::Arguments: IntoArguments<'args, DB>,
+//      //C: Connection<DB>,
+//  {
+//      if data.is_empty() {
+//          return Ok(());
+//      }
+//      let syntax = conn.syntax();
+//
+//      let col_writer = ColumnWriter::new(syntax);
+//
+//      let all_columns = <<T as HasSchema>::Schema as TableColumns>::columns();
+//      let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
+//      let columns: Vec<_> = all_columns.iter().filter(|c| !pks.contains(c)).collect();
+//
+//      let identifier = <<T as HasSchema>::Schema>::identifier().join(".");
+//      let next_params = NextParam::new(syntax);
+//
+//      //let mut args: <DB as HasArguments>::Arguments = Default::default();
+//      let mut args: ParamArgs = Vec::default();
+//
+//      let colnames: Vec<String> = columns
+//          .iter()
+//          .map(|c| col_writer.excape(c.name()))
+//          .collect();
+//      let colnames = colnames.join(", ");
+//
+//      let mut nest_parts: Vec<String> = Vec::default();
+//      let data: Vec<&T> = data.iter().collect();
+//      for column in columns {
+//          T::bind(&data, column, &mut args)?;
+//          nest_parts.push(format!("{}::{}[]", next_params.next(), column.dbtype()));
+//      }
+//
+//      //format!("INSERT INTO {} ({}) (select * from unnest($1::int[], $2::int[]))"
+//      let nest = nest_parts.join(", ");
+//      let sql = format!(
+//          "INSERT INTO {} ({}) (select * from unnest({}))",
+//          identifier, colnames, nest
+//      );
+//
+//      // lifetime hacks - Remove if you can
+//      // We know the use of sql and conn do not exceed the underlying call to fetch
+//      // sqlx if wants to hold the borrow for much longer than what is needed.
+//      // This hack prevents the borrow from exceeding the life of this call
+//      let sql_len = sql.len();
+//      let sqlp = sql.as_ptr();
+//      let sql_hack: &[u8] = unsafe { std::slice::from_raw_parts(sqlp, sql_len) };
+//      let sql: &str = std::str::from_utf8(sql_hack).unwrap();
+//      conn.execute(sql, args).await?;
+//
+//      Ok(())
+//  }
+
+#[cfg(test)]
+mod tests;
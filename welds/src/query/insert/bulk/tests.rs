@@ -0,0 +1,208 @@
+use super::*;
+use crate::Syntax;
+use crate::WeldsModel;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "nums")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub a: i32,
+    pub b: i32,
+}
+
+#[test]
+fn should_write_multi_row_insert() {
+    futures::executor::block_on(async move {
+        let data = vec![Product::default(), Product::default()];
+        let client = welds_connections::noop::build(Syntax::Postgres);
+        run(&client, &data).await.unwrap();
+        let ran_sql = client.last_sql().unwrap();
+
+        let expected = "INSERT INTO nums (\"a\", \"b\") VALUES ($1,$2),($3,$4)";
+        assert_eq!(expected, &ran_sql);
+        assert_eq!(client.args_count(), Some(4));
+    });
+}
+
+#[test]
+fn should_return_inserted_rows_with_returning() {
+    futures::executor::block_on(async move {
+        let mut data = vec![Product::default(), Product::default()];
+        let client = welds_connections::noop::build(Syntax::MariaDb);
+        // The noop client doesn't give back any rows
+        let err = run_returning(&client, &mut data).await;
+        assert!(err.is_err());
+        let ran_sql = client.last_sql().unwrap();
+
+        let expected = "INSERT INTO nums (a, b) SELECT a, b FROM (SELECT ? AS a,? AS b,0 AS welds_ord UNION ALL SELECT ?,?,1) AS welds_rows ORDER BY welds_ord RETURNING *";
+        assert_eq!(expected, &ran_sql);
+    });
+}
+
+fn numbered(column: &str, ids: &[i32]) -> Vec<crate::connections::mock::MockRow> {
+    use crate::connections::mock::MockRow;
+    ids.iter()
+        .map(|id| {
+            MockRow::new()
+                .with("id", *id)
+                .with("a", *id * 10)
+                .with("b", 0)
+                .with(column, (*id - 1) as i64)
+        })
+        .collect()
+}
+
+#[test]
+fn should_merge_the_rows_with_their_ordinal_on_mssql() {
+    use crate::connections::mock::MockClient;
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Mssql);
+        // output in any order, matched back by the ordinal
+        client.enqueue_rows("MERGE", numbered("welds_ord", &[2, 3, 1]));
+        let mut data = vec![Product::default(), Product::default(), Product::default()];
+        run_returning(&client, &mut data).await.unwrap();
+
+        let ids: Vec<(i32, i32)> = data.iter().map(|p| (p.id, p.a)).collect();
+        assert_eq!(ids, vec![(1, 10), (2, 20), (3, 30)]);
+        let expected = "MERGE INTO nums AS welds_target USING (VALUES (@p1,@p2,0),(@p3,@p4,1),(@p5,@p6,2)) AS welds_rows (\"a\", \"b\", welds_ord) ON 1 = 0 WHEN NOT MATCHED THEN INSERT (\"a\", \"b\") VALUES (welds_rows.\"a\", welds_rows.\"b\") OUTPUT Inserted.\"id\", Inserted.\"a\", Inserted.\"b\", CAST(welds_rows.welds_ord AS BIGINT) AS welds_ord;";
+        assert_eq!(client.last_sql().unwrap(), expected);
+        assert_eq!(client.queries().len(), 1);
+    });
+}
+
+#[test]
+fn should_sort_the_rows_by_their_rowid_on_sqlite() {
+    use crate::connections::mock::MockClient;
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Sqlite);
+        client.enqueue_rows("RETURNING", numbered("welds_rowid", &[3, 1, 2]));
+        let mut data = vec![Product::default(), Product::default(), Product::default()];
+        run_returning(&client, &mut data).await.unwrap();
+
+        let ids: Vec<(i32, i32)> = data.iter().map(|p| (p.id, p.a)).collect();
+        assert_eq!(ids, vec![(1, 10), (2, 20), (3, 30)]);
+        let expected = "INSERT INTO nums (\"a\", \"b\") SELECT column1, column2 FROM (VALUES (?,?,0),(?,?,1),(?,?,2)) ORDER BY column3 RETURNING rowid AS welds_rowid, *";
+        assert_eq!(client.last_sql().unwrap(), expected);
+        assert_eq!(client.queries().len(), 1);
+    });
+}
+
+#[test]
+fn should_pair_the_rows_with_their_ordinal_on_cockroachdb() {
+    use crate::connections::mock::MockClient;
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::CockroachDb);
+        client.enqueue_rows("RETURNING", numbered("welds_ord", &[1, 2]));
+        let mut data = vec![Product::default(), Product::default()];
+        run_returning(&client, &mut data).await.unwrap();
+
+        let ids: Vec<i32> = data.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        let expected = "WITH welds_rows (\"a\", \"b\", welds_ord) AS ( VALUES ($1,$2,0),($3,$4,1) ), welds_inserted AS ( INSERT INTO nums (\"a\", \"b\") SELECT \"a\", \"b\" FROM welds_rows ORDER BY welds_ord RETURNING * ) SELECT welds_inserted.*, welds_rows.welds_ord FROM ( SELECT *, row_number() OVER (PARTITION BY \"a\", \"b\") AS welds_n FROM welds_inserted ) AS welds_inserted JOIN ( SELECT \"a\", \"b\", welds_ord, row_number() OVER (PARTITION BY \"a\", \"b\" ORDER BY welds_ord) AS welds_n FROM welds_rows ) AS welds_rows ON welds_inserted.\"a\" IS NOT DISTINCT FROM welds_rows.\"a\" AND welds_inserted.\"b\" IS NOT DISTINCT FROM welds_rows.\"b\" AND welds_inserted.welds_n = welds_rows.welds_n ORDER BY welds_rows.welds_ord";
+        assert_eq!(client.last_sql().unwrap(), expected);
+        assert_eq!(client.queries().len(), 1);
+    });
+}
+
+#[test]
+fn should_report_rows_skipped_by_insert_ignore() {
+    futures::executor::block_on(async move {
+        let data = vec![Product::default(), Product::default(), Product::default()];
+        let client = crate::connections::mock::MockClient::new(Syntax::Postgres);
+        client.enqueue_rows_affected("ON CONFLICT DO NOTHING", 2);
+        let report = run_ignore(&client, &data).await.unwrap();
+        assert_eq!(
+            report,
+            InsertIgnored {
+                inserted: 2,
+                skipped: 1
+            }
+        );
+
+        let expected = "INSERT INTO nums (\"id\", \"a\", \"b\") VALUES ($1,$2,$3),($4,$5,$6),($7,$8,$9) ON CONFLICT DO NOTHING";
+        assert_eq!(expected, client.last_sql().unwrap());
+    });
+}
+
+#[test]
+fn should_write_insert_ignore_for_mysql() {
+    futures::executor::block_on(async move {
+        let data = vec![Product::default()];
+        let client = welds_connections::noop::build(Syntax::Mysql);
+        run_ignore(&client, &data).await.unwrap();
+        let expected = "INSERT IGNORE INTO nums (id, a, b) VALUES (?,?,?)";
+        assert_eq!(expected, client.last_sql().unwrap());
+    });
+}
+
+#[test]
+fn should_filter_existing_keys_on_mssql() {
+    futures::executor::block_on(async move {
+        let data = vec![Product::default(), Product::default()];
+        let client = welds_connections::noop::build(Syntax::Mssql);
+        run_ignore(&client, &data).await.unwrap();
+        let expected = "INSERT INTO nums (\"id\", \"a\", \"b\") SELECT welds_rows.\"id\", welds_rows.\"a\", welds_rows.\"b\" FROM (VALUES (@p1,@p2,@p3),(@p4,@p5,@p6)) AS welds_rows (\"id\", \"a\", \"b\") WHERE NOT EXISTS (SELECT 1 FROM nums welds_existing WHERE welds_existing.\"id\" = welds_rows.\"id\")";
+        assert_eq!(expected, client.last_sql().unwrap());
+    });
+}
+
+#[test]
+fn should_split_large_inserts_to_stay_under_the_param_limit() {
+    futures::executor::block_on(async move {
+        let data: Vec<Product> = (0..1500).map(|_| Product::default()).collect();
+        let client = crate::connections::mock::MockClient::new(Syntax::Mssql);
+        run(&client, &data).await.unwrap();
+
+        // MSSQL takes at most 1000 rows per VALUES list
+        let params: Vec<usize> = client.queries().iter().map(|q| q.params.len()).collect();
+        assert_eq!(params, vec![2000, 1000]);
+    });
+}
+
+#[test]
+fn should_chunk_by_the_number_of_columns() {
+    let client = crate::connections::mock::MockClient::new(Syntax::Postgres);
+    let writer = BulkWriter::new::<Product>(&client);
+    assert_eq!(writer.chunk_size, 65_535 / 2);
+}
+
+#[test]
+fn should_insert_many_in_one_transaction() {
+    use crate::connections::mock::{MockClient, MockRow};
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Postgres);
+        let rows = (1..=2)
+            .map(|id| MockRow::new().with("id", id).with("a", 0).with("b", 0))
+            .collect();
+        client.enqueue_rows("RETURNING", rows);
+        let data = vec![Product::default(), Product::default()];
+        let inserted = Product::insert_many(&client, data).await.unwrap();
+
+        let ids: Vec<i32> = inserted.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+        assert_eq!(
+            sql,
+            vec![
+                "BEGIN",
+                "INSERT INTO nums (\"a\", \"b\") SELECT \"a\", \"b\" FROM (VALUES ($1,$2,0),($3,$4,1)) AS welds_rows (\"a\", \"b\", welds_ord) ORDER BY welds_ord RETURNING *",
+                "COMMIT",
+            ]
+        );
+    });
+}
+
+#[test]
+fn should_not_commit_a_failed_insert_many() {
+    use crate::connections::mock::MockClient;
+    futures::executor::block_on(async move {
+        // no rows come back, so the ids can't be filled in
+        let client = MockClient::new(Syntax::Postgres);
+        let data = vec![Product::default(), Product::default()];
+        assert!(Product::insert_many(&client, data).await.is_err());
+        let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+        assert!(!sql.contains(&"COMMIT".to_owned()));
+    });
+}
@@ -1,7 +1,15 @@
 mod bulk;
 mod single;
+mod upsert;
 
+pub(crate) use single::{insert_ignore_one, insert_omitting};
 pub use single::insert_one;
 
 pub use bulk::run as bulk_insert;
+pub use bulk::run_ignore as bulk_insert_ignore;
+pub use bulk::run_returning as bulk_insert_returning;
+pub use bulk::insert_many;
+pub use bulk::InsertIgnored;
+pub(crate) use upsert::upsert_one;
+pub use upsert::{run as bulk_upsert, Upsert};
 //pub use bulk::run_fast as bulk_insert_fast;
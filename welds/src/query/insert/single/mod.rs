@@ -1,17 +1,33 @@
 use crate::errors::Result;
-use crate::errors::WeldsError::InsertFailed;
+use crate::errors::WeldsError::{InsertFailed, MissingDbColumn, NoPrimaryKey};
 use crate::model_traits::hooks::{AfterCreate, BeforeCreate};
 use crate::model_traits::{ColumnDefaultCheck, UpdateFromRow};
 use crate::model_traits::{HasSchema, TableColumns, TableInfo, WriteToArgs};
 use crate::query::clause::ParamArgs;
+use crate::query::helpers::invalidate_cached;
+use crate::relations::{parents_transaction, update_parents};
 use crate::writers::column::ColumnWriter;
 use crate::writers::insert::{ColArg, InsertWriter};
 use crate::writers::NextParam;
+use crate::writers::{in_namespace, table_name};
 use crate::Row;
+use crate::Syntax;
 use welds_connections::Client;
 use welds_connections::Fetch;
 
 pub async fn insert_one<T>(obj: &mut T, client: &dyn Client) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    T: UpdateFromRow,
+    T: BeforeCreate + AfterCreate,
+{
+    insert_omitting(obj, &[], client).await
+}
+
+/// Inserts a single object unless it conflicts with a row already in the table.
+/// The hooks only run after the insert when the row was inserted
+pub(crate) async fn insert_ignore_one<T>(obj: &mut T, client: &dyn Client) -> Result<bool>
 where
     T: WriteToArgs + HasSchema + ColumnDefaultCheck,
     <T as HasSchema>::Schema: TableInfo + TableColumns,
@@ -19,29 +35,175 @@ where
     T: BeforeCreate + AfterCreate,
 {
     BeforeCreate::before(obj)?;
+    let inserted = match parents_transaction::<T>(client) {
+        Some(start) => {
+            let trans = start.begin().await?;
+            let inserted = insert_ignore_with_parents(obj, &trans).await?;
+            trans.commit().await?;
+            inserted
+        }
+        None => insert_ignore_with_parents(obj, client).await?,
+    };
+    if inserted {
+        AfterCreate::after(obj);
+    }
+    Ok(inserted)
+}
+
+async fn insert_ignore_with_parents<T>(obj: &mut T, client: &dyn Client) -> Result<bool>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    T: UpdateFromRow,
+{
+    let inserted = insert_ignore_row(obj, client).await?;
+    if inserted {
+        invalidate_cached::<T>(client).await;
+        update_parents(obj, 1, client).await?;
+    }
+    Ok(inserted)
+}
+
+/// Inserts a single object without the given columns, letting the database fill them in
+pub(crate) async fn insert_omitting<T>(
+    obj: &mut T,
+    omit: &[&str],
+    client: &dyn Client,
+) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    T: UpdateFromRow,
+    T: BeforeCreate + AfterCreate,
+{
+    BeforeCreate::before(obj)?;
+    // the row and the parents it counts are written together
+    match parents_transaction::<T>(client) {
+        Some(start) => {
+            let trans = start.begin().await?;
+            insert_with_parents(obj, omit, &trans).await?;
+            trans.commit().await?;
+        }
+        None => insert_with_parents(obj, omit, client).await?,
+    }
+    AfterCreate::after(obj);
+    Ok(())
+}
+
+async fn insert_with_parents<T>(obj: &mut T, omit: &[&str], client: &dyn Client) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    T: UpdateFromRow,
+{
+    insert_row_omitting(obj, omit, client).await?;
+    update_parents(obj, 1, client).await
+}
+
+/// Inserts a single object, updating it with the row the database created. Hooks are not ran.
+pub(crate) async fn insert_row<T>(obj: &mut T, client: &dyn Client) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    T: UpdateFromRow,
+{
+    insert_row_omitting(obj, &[], client).await
+}
 
+async fn insert_row_omitting<T>(obj: &mut T, omit: &[&str], client: &dyn Client) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    T: UpdateFromRow,
+{
     let syntax = client.syntax();
     let mut args: ParamArgs = Vec::default();
     let args2: ParamArgs = Vec::default();
 
-    let col_writer = ColumnWriter::new(syntax);
-    let next_params = NextParam::new(syntax);
     let writer = InsertWriter::new(syntax);
 
-    let identifier = <<T as HasSchema>::Schema>::identifier().join(".");
+    let identifier = in_namespace(client, || {
+        table_name(&<<T as HasSchema>::Schema>::identifier().join("."))
+    });
     let columns = <<T as HasSchema>::Schema as TableColumns>::columns();
     let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
 
+    let (colargs, id_return_required) = write_colargs(obj, omit, syntax, &mut args)?;
+
+    let (insert, select) = writer.write(&identifier, &colargs, &columns, &pks);
+
+    let mut statements = vec![Fetch::new(&insert, &args).with_label("insert")];
+
+    // If this insert needs a second select command to get the id, add it to the vec of sql to run
+    let sql2: String;
+    if id_return_required {
+        if let Some(select) = select {
+            sql2 = select.to_owned();
+            statements.push(Fetch::new(&sql2, &args2).with_label("select_inserted_id"))
+        }
+    }
+
+    // WARNING: these statements MUST be ran on the same DB connection in the pool
+    // If this isn't done, you will not get back the last_id.
+    // That is why we are using fetch_many
+    let mut datasets = client.fetch_many(&statements).await?;
+    invalidate_cached::<T>(client).await;
+    let mut rows: Vec<Row> = datasets.drain(..).flatten().collect();
+
+    // If we are providing the DB with the ID, (string/uuid) it doesn't need to return the id, and will not
+    // Backends that return the inserted row still hand back the values of the omitted columns
+    if !id_return_required {
+        if let (false, Some(mut row)) = (omit.is_empty(), rows.pop()) {
+            UpdateFromRow::update_from_row(obj, &mut row)?;
+        }
+        return Ok(());
+    }
+
+    let row = rows.pop();
+    let mut row =
+        row.ok_or_else(|| InsertFailed("Insert didn't return inserted ID/Row".to_owned()))?;
+    UpdateFromRow::update_from_row(obj, &mut row)?;
+
+    Ok(())
+}
+
+// The columns to insert and their params. The primary keys are left out when they are the
+// default value, the database picks them. Returns if the database has to hand back the key.
+fn write_colargs<'a, T>(
+    obj: &'a T,
+    omit: &[&str],
+    syntax: Syntax,
+    args: &mut ParamArgs<'a>,
+) -> Result<(Vec<ColArg>, bool)>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let col_writer = ColumnWriter::new(syntax);
+    let next_params = NextParam::new(syntax);
+    let columns = <<T as HasSchema>::Schema as TableColumns>::columns();
+    let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
+
+    if let Some(unknown) = omit
+        .iter()
+        .find(|o| !columns.iter().any(|c| c.name() == **o))
+    {
+        return Err(MissingDbColumn(unknown.to_string()));
+    }
+
     let mut colargs = Vec::default();
     let mut id_return_required = true;
 
     for col in &columns {
+        if omit.contains(&col.name()) {
+            continue;
+        }
         let pk = pks.iter().find(|p| p == &col);
 
         match pk {
             None => {
                 // column isn't PK just insert it
-                obj.bind(col.name(), &mut args)?;
+                obj.bind(col.name(), args)?;
                 let col = col_writer.excape(col.name());
                 colargs.push(ColArg(col, next_params.next()));
             }
@@ -53,52 +215,119 @@ where
                 // it in the query for insertion
                 if !obj.col_is_default(col.name())? {
                     id_return_required = false;
-                    obj.bind(col.name(), &mut args)?;
+                    obj.bind(col.name(), args)?;
                     let col = col_writer.excape(col.name());
                     colargs.push(ColArg(col, next_params.next()));
                 }
             }
         }
     }
+    Ok((colargs, id_return_required))
+}
 
-    let (insert, select) = writer.write(&identifier, &colargs, &columns, &pks);
+/// Inserts a single object unless it conflicts with a row already in the table (any unique
+/// constraint, not only the primary key). Returns if it was inserted, updating the object with
+/// the row the database created when it was. Hooks are not ran.
+pub(crate) async fn insert_ignore_row<T>(obj: &mut T, client: &dyn Client) -> Result<bool>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    T: UpdateFromRow,
+{
+    let syntax = client.syntax();
+    let identifier = in_namespace(client, || {
+        table_name(&<<T as HasSchema>::Schema>::identifier().join("."))
+    });
+    let columns = <<T as HasSchema>::Schema as TableColumns>::columns();
+    let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
 
-    let mut statements = vec![Fetch {
-        sql: &insert,
-        params: &args,
-    }];
+    let mut rows = {
+        let mut args: ParamArgs = Vec::default();
+        let (colargs, id_return_required) = write_colargs(&*obj, &[], syntax, &mut args)?;
+        let cols: Vec<_> = colargs.iter().map(|x| x.0.as_str()).collect();
+        let values: Vec<_> = colargs.iter().map(|x| x.1.as_str()).collect();
+        let (cols, values) = (cols.join(", "), values.join(", "));
 
-    // If this insert needs a second select command to get the id, add it to the vec of sql to run
-    let sql2: String;
-    if id_return_required {
-        if let Some(select) = select {
-            sql2 = select.to_owned();
-            statements.push(Fetch {
-                sql: &sql2,
-                params: &args2,
-            })
+        match syntax {
+            Syntax::Postgres | Syntax::CockroachDb | Syntax::Sqlite => {
+                let sql = format!(
+                    "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT DO NOTHING RETURNING *",
+                    identifier, cols, values
+                );
+                client.fetch_rows(&sql, &args).await?
+            }
+            Syntax::MariaDb => {
+                let sql = format!(
+                    "INSERT IGNORE INTO {} ({}) VALUES ({}) RETURNING *",
+                    identifier, cols, values
+                );
+                client.fetch_rows(&sql, &args).await?
+            }
+            Syntax::Mysql => {
+                // the row is read back by LAST_INSERT_ID(), the one generated key
+                if id_return_required && pks.len() != 1 {
+                    return match pks.is_empty() {
+                        true => Err(NoPrimaryKey),
+                        false => Err(InsertFailed(
+                            "MySQL can't read back a generated composite primary key".to_owned(),
+                        )),
+                    };
+                }
+                let insert = format!(
+                    "INSERT IGNORE INTO {} ({}) VALUES ({})",
+                    identifier, cols, values
+                );
+                // WARNING: ROW_COUNT() and LAST_INSERT_ID() only work on the connection that did the insert
+                let counts = "SELECT CAST(ROW_COUNT() AS SIGNED) AS inserted, CAST(LAST_INSERT_ID() AS SIGNED) AS id";
+                let statements = [
+                    Fetch::new(&insert, &args).with_label("insert"),
+                    Fetch::new(counts, &[]).with_label("row_count"),
+                ];
+                let mut datasets = client.fetch_many(&statements).await?;
+                let counts = datasets.pop().and_then(|mut rows| rows.pop());
+                let counts =
+                    counts.ok_or_else(|| InsertFailed("Unable to read ROW_COUNT()".to_owned()))?;
+                let inserted: i64 = counts.get("inserted")?;
+                if inserted < 1 {
+                    return Ok(false);
+                }
+                if !id_return_required {
+                    return Ok(true);
+                }
+                let id: i64 = counts.get("id")?;
+                let col_writer = ColumnWriter::new(syntax);
+                let sql = format!(
+                    "SELECT * FROM {} WHERE {} = ?",
+                    identifier,
+                    col_writer.excape(pks[0].name())
+                );
+                client.fetch_rows(&sql, &[&id]).await?
+            }
+            Syntax::Mssql => {
+                // a duplicate key is error 2627 (constraint) or 2601 (unique index)
+                let col_writer = ColumnWriter::new(syntax);
+                let outputs: Vec<String> = columns
+                    .iter()
+                    .map(|c| col_writer.write("Inserted", c))
+                    .collect();
+                let sql = format!(
+                    "BEGIN TRY INSERT INTO {} ({}) OUTPUT {} VALUES ({}) END TRY BEGIN CATCH IF ERROR_NUMBER() NOT IN (2601, 2627) THROW; END CATCH",
+                    identifier,
+                    cols,
+                    outputs.join(", "),
+                    values
+                );
+                client.fetch_rows(&sql, &args).await?
+            }
         }
-    }
-
-    // WARNING: these statements MUST be ran on the same DB connection in the pool
-    // If this isn't done, you will not get back the last_id.
-    // That is why we are using fetch_many
-    let mut datasets = client.fetch_many(&statements).await?;
-    let mut rows: Vec<Row> = datasets.drain(..).flatten().collect();
+    };
 
-    // If we are providing the DB with the ID, (string/uuid) it doesn't need to return the id, and will not
-    if !id_return_required {
-        AfterCreate::after(obj);
-        return Ok(());
-    }
-
-    let row = rows.pop();
-    let mut row =
-        row.ok_or_else(|| InsertFailed("Insert didn't return inserted ID/Row".to_owned()))?;
+    let mut row = match rows.pop() {
+        Some(row) => row,
+        None => return Ok(false),
+    };
     UpdateFromRow::update_from_row(obj, &mut row)?;
-    AfterCreate::after(obj);
-
-    Ok(())
+    Ok(true)
 }
 
 #[cfg(test)]
@@ -31,3 +31,187 @@ fn should_be_able_to_insert_simple_object() {
         assert_eq!(expected, &ran_sql);
     });
 }
+
+#[test]
+fn should_use_returning_on_mariadb_insert() {
+    futures::executor::block_on(async move {
+        let obj = Product::default();
+        let mut obj = DbState::new_uncreated(obj);
+        let client = welds_connections::noop::build(Syntax::MariaDb);
+        let _ = obj.save(&client).await;
+        let ran_sql = client.last_sql().unwrap();
+
+        let expected = "INSERT INTO nums (a, b) VALUES (?, ?) RETURNING *";
+        assert_eq!(expected, &ran_sql);
+    });
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_label_the_statements_of_an_insert() {
+    use crate::connections::instrument::QueryHook;
+    use crate::connections::mock::{MockClient, MockRow};
+    use crate::connections::Param;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Labels(Mutex<Vec<String>>);
+    impl QueryHook for Labels {
+        fn before_labeled_query(&self, label: &str, _: &str, _: &[&(dyn Param + Sync + Send)]) {
+            self.0.lock().unwrap().push(label.to_owned());
+        }
+    }
+
+    futures::executor::block_on(async move {
+        let labels = Arc::new(Labels::default());
+        let client = MockClient::new(Syntax::Mysql).with_query_hook(labels.clone());
+        client.enqueue_rows(
+            "LAST_INSERT_ID",
+            vec![MockRow::new().with("id", 3).with("a", 1).with("b", 2)],
+        );
+        let mut obj = DbState::new_uncreated(Product::default());
+        obj.save(&client).await.unwrap();
+
+        assert_eq!(obj.id, 3);
+        let labels = labels.0.lock().unwrap();
+        assert_eq!(*labels, vec!["insert", "select_inserted_id"]);
+    });
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_leave_omitted_columns_to_the_database() {
+    use crate::connections::mock::{MockClient, MockRow};
+
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows(
+            "RETURNING",
+            vec![MockRow::new().with("id", 4).with("a", 1).with("b", 9)],
+        );
+        let mut obj = DbState::new_uncreated(Product { id: 0, a: 1, b: 0 });
+        obj.insert_omitting(&["b"], &client).await.unwrap();
+
+        assert_eq!(
+            client.last_sql().unwrap(),
+            "INSERT INTO nums (\"a\") VALUES ($1) RETURNING *"
+        );
+        assert_eq!(obj.id, 4);
+        assert_eq!(obj.b, 9);
+        assert!(obj.insert_omitting(&["b"], &client).await.is_err());
+    });
+}
+
+#[test]
+fn should_reject_omitting_an_unknown_column() {
+    futures::executor::block_on(async move {
+        let client = welds_connections::noop::build(Syntax::Postgres);
+        let mut obj = DbState::new_uncreated(Product::default());
+        let err = obj.insert_omitting(&["nope"], &client).await.unwrap_err();
+        assert!(matches!(err, crate::WeldsError::MissingDbColumn(c) if c == "nope"));
+        assert!(client.last_sql().is_none());
+    });
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_report_a_row_skipped_by_insert_ignore() {
+    use crate::connections::mock::{MockClient, MockRow};
+    use crate::state::DbStatus;
+
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Sqlite);
+        let mut obj = DbState::new_uncreated(Product { id: 0, a: 1, b: 2 });
+        assert!(!obj.insert_ignore(&client).await.unwrap());
+        assert_eq!(
+            client.last_sql().unwrap(),
+            "INSERT INTO nums (\"a\", \"b\") VALUES (?, ?) ON CONFLICT DO NOTHING RETURNING *"
+        );
+        assert_eq!(obj.db_status(), DbStatus::NotInDatabase);
+
+        client.enqueue_rows(
+            "RETURNING",
+            vec![MockRow::new().with("id", 5).with("a", 1).with("b", 2)],
+        );
+        assert!(obj.insert_ignore(&client).await.unwrap());
+        assert_eq!(obj.id, 5);
+        assert_eq!(obj.db_status(), DbStatus::NotModified);
+    });
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_read_the_row_count_of_insert_ignore_on_mysql() {
+    use crate::connections::mock::{MockClient, MockRow};
+
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Mysql);
+        let counts = MockRow::new().with("inserted", 1).with("id", 8);
+        client.enqueue_rows("ROW_COUNT", vec![counts]);
+        let row = MockRow::new().with("id", 8).with("a", 1).with("b", 2);
+        client.enqueue_rows("SELECT *", vec![row]);
+        let mut obj = DbState::new_uncreated(Product { id: 0, a: 1, b: 2 });
+        assert!(obj.insert_ignore(&client).await.unwrap());
+        assert_eq!(obj.id, 8);
+        let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+        assert_eq!(sql[0], "INSERT IGNORE INTO nums (a, b) VALUES (?, ?)");
+        assert_eq!(sql[2], "SELECT * FROM nums WHERE id = ?");
+    });
+}
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "events")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Event {
+    pub name: String,
+}
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "tags")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Tag {
+    #[welds(primary_key)]
+    pub post_id: i32,
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_refuse_to_read_back_a_row_without_a_single_key_on_mysql() {
+    use crate::connections::mock::{MockClient, MockRow};
+
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Mysql);
+        let mut obj = DbState::new_uncreated(Event::default());
+        let err = obj.insert_ignore(&client).await.unwrap_err();
+        assert!(matches!(err, crate::WeldsError::NoPrimaryKey));
+
+        let mut obj = DbState::new_uncreated(Tag::default());
+        let err = obj.insert_ignore(&client).await.unwrap_err();
+        assert!(matches!(err, crate::WeldsError::InsertFailed(_)));
+        assert!(client.queries().is_empty());
+
+        // a composite key that is set doesn't need reading back
+        let counts = MockRow::new().with("inserted", 1).with("id", 0);
+        client.enqueue_rows("ROW_COUNT", vec![counts]);
+        let mut obj = DbState::new_uncreated(Tag { post_id: 1, id: 2 });
+        assert!(obj.insert_ignore(&client).await.unwrap());
+    });
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_catch_duplicate_keys_on_mssql() {
+    use crate::connections::mock::MockClient;
+
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Mssql);
+        let mut obj = DbState::new_uncreated(Product { id: 3, a: 1, b: 2 });
+        assert!(!obj.insert_ignore(&client).await.unwrap());
+        assert_eq!(
+            client.last_sql().unwrap(),
+            "BEGIN TRY INSERT INTO nums (\"id\", \"a\", \"b\") OUTPUT Inserted.\"id\", Inserted.\"a\", Inserted.\"b\" VALUES (@p1, @p2, @p3) END TRY BEGIN CATCH IF ERROR_NUMBER() NOT IN (2601, 2627) THROW; END CATCH"
+        );
+    });
+}
@@ -0,0 +1,269 @@
+use super::bulk::BulkWriter;
+use crate::errors::Result;
+use crate::errors::WeldsError::{InsertFailed, MissingDbColumn, NoPrimaryKey};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo, UpdateFromRow, WriteToArgs};
+use crate::query::clause::ParamArgs;
+use crate::query::helpers::invalidate_cached;
+use crate::writers::ColumnWriter;
+use crate::writers::NextParam;
+use crate::Client;
+use crate::Syntax;
+
+/// How an upsert finds the row that is already in the table, and what it changes on that row.
+///
+/// By default rows with the same primary key are the same row, and all the other columns are
+/// updated. The primary keys are only written when rows conflict on them.
+///
+/// ```rust,ignore
+/// let upsert = Upsert::new().on_conflict(&["email"]).update_only(&["name"]);
+/// user.upsert_with(&upsert, &client).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Upsert {
+    conflict: Vec<String>,
+    update: Option<Vec<String>>,
+}
+
+impl Upsert {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The columns of the unique constraint rows conflict on.
+    /// MySql and MariaDB check every unique key of the table, they ignore this
+    pub fn on_conflict(mut self, columns: &[&str]) -> Self {
+        self.conflict = columns.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Only update these columns of a row that is already in the table
+    pub fn update_only(mut self, columns: &[&str]) -> Self {
+        self.update = Some(columns.iter().map(|c| c.to_string()).collect());
+        self
+    }
+}
+
+/// The upsert of a model, with its columns worked out
+struct UpsertWriter {
+    writer: BulkWriter,
+    conflict: Vec<String>,
+    update: Vec<String>,
+    // all the columns of the table, the ones MSSQL outputs
+    returned: Vec<String>,
+}
+
+impl UpsertWriter {
+    fn new<T>(conn: &dyn Client, upsert: &Upsert) -> Result<Self>
+    where
+        T: HasSchema,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let columns = <<T as HasSchema>::Schema as TableColumns>::columns();
+        let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
+        let conflict: Vec<String> = match upsert.conflict.is_empty() {
+            true => pks.iter().map(|c| c.name().to_owned()).collect(),
+            false => upsert.conflict.clone(),
+        };
+        if conflict.is_empty() {
+            return Err(NoPrimaryKey);
+        }
+
+        let include_pks = pks.iter().any(|pk| conflict.iter().any(|c| c == pk.name()));
+        let writer = BulkWriter::build::<T>(conn, include_pks);
+        let update: Vec<String> = match &upsert.update {
+            Some(update) => update.clone(),
+            None => writer
+                .columns
+                .iter()
+                .filter(|c| !conflict.contains(c))
+                .cloned()
+                .collect(),
+        };
+
+        let known = |c: &String| columns.iter().any(|col| col.name() == c);
+        if let Some(unknown) = conflict.iter().find(|c| !known(c)) {
+            return Err(MissingDbColumn(unknown.to_owned()));
+        }
+        if let Some(unknown) = update.iter().find(|c| !writer.columns.contains(c)) {
+            return Err(MissingDbColumn(unknown.to_owned()));
+        }
+
+        Ok(Self {
+            writer,
+            conflict,
+            update,
+            returned: columns.iter().map(|c| c.name().to_owned()).collect(),
+        })
+    }
+
+    fn write<'a, T>(
+        &self,
+        chunk: &'a [T],
+        args: &mut ParamArgs<'a>,
+        returning: bool,
+    ) -> Result<String>
+    where
+        T: WriteToArgs,
+    {
+        let w = &self.writer;
+        let rows = w.write_values(chunk, args)?;
+        let col_writer = ColumnWriter::new(w.syntax);
+        let conflict: Vec<String> = self.conflict.iter().map(|c| col_writer.excape(c)).collect();
+        let update: Vec<String> = self.update.iter().map(|c| col_writer.excape(c)).collect();
+        let (table, cols) = (&w.identifier, &w.colnames);
+
+        // with nothing to update a conflicting key is set to the same value, so the row is still returned
+        let sets = |value: &dyn Fn(&str) -> String| -> String {
+            let sets: Vec<String> = match update.is_empty() {
+                true => vec![format!("{} = {}", conflict[0], value(&conflict[0]))],
+                false => update
+                    .iter()
+                    .map(|c| format!("{} = {}", c, value(c)))
+                    .collect(),
+            };
+            sets.join(", ")
+        };
+
+        Ok(match w.syntax {
+            Syntax::Postgres | Syntax::CockroachDb | Syntax::Sqlite => {
+                let sql = format!(
+                    "INSERT INTO {} ({}) VALUES {} ON CONFLICT ({}) DO UPDATE SET {}",
+                    table,
+                    cols,
+                    rows,
+                    conflict.join(", "),
+                    sets(&|c| format!("excluded.{}", c))
+                );
+                match returning {
+                    true => format!("{} RETURNING *", sql),
+                    false => sql,
+                }
+            }
+            Syntax::Mysql | Syntax::MariaDb => format!(
+                "INSERT INTO {} ({}) VALUES {} ON DUPLICATE KEY UPDATE {}",
+                table,
+                cols,
+                rows,
+                sets(&|c| format!("VALUES({})", c))
+            ),
+            Syntax::Mssql => {
+                let on: Vec<String> = conflict
+                    .iter()
+                    .map(|c| format!("welds_target.{} = welds_rows.{}", c, c))
+                    .collect();
+                let values: Vec<String> = w
+                    .columns
+                    .iter()
+                    .map(|c| format!("welds_rows.{}", col_writer.excape(c)))
+                    .collect();
+                // with nothing to update the key is set to the one the row already has,
+                // a MERGE only OUTPUTs the rows it writes
+                let source = match update.is_empty() {
+                    true => "welds_target",
+                    false => "welds_rows",
+                };
+                let matched = sets(&|c| format!("{}.{}", source, c));
+                let output = match returning {
+                    true => {
+                        let returned: Vec<String> = self
+                            .returned
+                            .iter()
+                            .map(|c| format!("Inserted.{}", col_writer.excape(c)))
+                            .collect();
+                        format!(" OUTPUT {}", returned.join(", "))
+                    }
+                    false => String::default(),
+                };
+                format!(
+                    "MERGE INTO {} WITH (HOLDLOCK) AS welds_target USING (VALUES {}) AS welds_rows ({}) ON {} WHEN MATCHED THEN UPDATE SET {} WHEN NOT MATCHED THEN INSERT ({}) VALUES ({}){};",
+                    table,
+                    rows,
+                    cols,
+                    on.join(" AND "),
+                    matched,
+                    cols,
+                    values.join(", "),
+                    output
+                )
+            }
+        })
+    }
+
+    // MySql can't return the row it wrote, it is read back by the columns it conflicts on
+    fn write_select<'a, T>(&self, obj: &'a T, args: &mut ParamArgs<'a>) -> Result<String>
+    where
+        T: WriteToArgs,
+    {
+        let w = &self.writer;
+        let col_writer = ColumnWriter::new(w.syntax);
+        let next_params = NextParam::new(w.syntax);
+        let mut wheres: Vec<String> = Vec::default();
+        for c in &self.conflict {
+            obj.bind(c, args)?;
+            wheres.push(format!("{} = {}", col_writer.excape(c), next_params.next()));
+        }
+        Ok(format!(
+            "SELECT * FROM {} WHERE {}",
+            w.identifier,
+            wheres.join(" AND ")
+        ))
+    }
+}
+
+/// Inserts the values, updating the rows already in the table that they conflict with.
+/// `ON CONFLICT ... DO UPDATE` on Postgres, CockroachDB and Sqlite,
+/// `ON DUPLICATE KEY UPDATE` on MySql and MariaDB, and `MERGE` on MSSQL.
+///
+/// Large upserts are split into several statements like `bulk_insert`. Hooks are not ran.
+pub async fn run<T>(conn: &dyn Client, data: &[T], upsert: &Upsert) -> Result<()>
+where
+    T: WriteToArgs + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    if data.is_empty() {
+        return Ok(());
+    }
+    let writer = UpsertWriter::new::<T>(conn, upsert)?;
+    for chunk in data.chunks(writer.writer.chunk_size) {
+        let mut args: ParamArgs = Vec::default();
+        let sql = writer.write(chunk, &mut args, false)?;
+        conn.execute(&sql, &args).await?;
+    }
+    invalidate_cached::<T>(conn).await;
+    Ok(())
+}
+
+/// Upserts a single object, updating it with the row that is in the database afterwards
+pub(crate) async fn upsert_one<T>(obj: &mut T, upsert: &Upsert, client: &dyn Client) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + UpdateFromRow,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let writer = UpsertWriter::new::<T>(client, upsert)?;
+    let mut rows = {
+        let chunk = std::slice::from_ref(&*obj);
+        let mut args: ParamArgs = Vec::default();
+        match client.syntax() {
+            Syntax::Mysql | Syntax::MariaDb => {
+                let sql = writer.write(chunk, &mut args, false)?;
+                client.execute(&sql, &args).await?;
+                let mut args: ParamArgs = Vec::default();
+                let sql = writer.write_select(&chunk[0], &mut args)?;
+                client.fetch_rows(&sql, &args).await?
+            }
+            _ => {
+                let sql = writer.write(chunk, &mut args, true)?;
+                client.fetch_rows(&sql, &args).await?
+            }
+        }
+    };
+    invalidate_cached::<T>(client).await;
+    let mut row = rows
+        .pop()
+        .ok_or_else(|| InsertFailed("Upsert didn't return the row".to_owned()))?;
+    UpdateFromRow::update_from_row(obj, &mut row)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,119 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::state::DbState;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "users")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct User {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub email: String,
+    pub name: String,
+}
+
+fn user(id: i32, email: &str) -> User {
+    User {
+        id,
+        email: email.to_owned(),
+        name: "Bob".to_owned(),
+    }
+}
+
+#[test]
+fn should_update_the_row_with_the_same_primary_key() {
+    let client = MockClient::new(Syntax::Postgres);
+    let row = MockRow::new()
+        .with("id", 1)
+        .with("email", "b@x.com")
+        .with("name", "Bob");
+    client.enqueue_rows("ON CONFLICT", vec![row]);
+    let mut u = DbState::new_uncreated(user(1, "b@x.com"));
+    block_on(u.upsert(&client)).unwrap();
+    assert_eq!(
+        client.last_sql().unwrap(),
+        r#"INSERT INTO users ("id", "email", "name") VALUES ($1,$2,$3) ON CONFLICT ("id") DO UPDATE SET "email" = excluded."email", "name" = excluded."name" RETURNING *"#
+    );
+}
+
+#[test]
+fn should_read_back_the_generated_id_when_conflicting_on_another_column() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let row = MockRow::new()
+        .with("id", 7)
+        .with("email", "b@x.com")
+        .with("name", "Bob");
+    client.enqueue_rows("ON CONFLICT", vec![row]);
+    let upsert = Upsert::new().on_conflict(&["email"]).update_only(&["name"]);
+    let mut u = DbState::new_uncreated(user(0, "b@x.com"));
+    block_on(u.upsert_with(&upsert, &client)).unwrap();
+    assert_eq!(u.id, 7);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        r#"INSERT INTO users ("email", "name") VALUES (?,?) ON CONFLICT ("email") DO UPDATE SET "name" = excluded."name" RETURNING *"#
+    );
+}
+
+#[test]
+fn should_select_the_row_back_on_mysql() {
+    let client = MockClient::new(Syntax::Mysql);
+    let row = MockRow::new()
+        .with("id", 7)
+        .with("email", "b@x.com")
+        .with("name", "Bob");
+    client.enqueue_rows("SELECT", vec![row]);
+    let upsert = Upsert::new().on_conflict(&["email"]);
+    let mut u = DbState::new_uncreated(user(0, "b@x.com"));
+    block_on(u.upsert_with(&upsert, &client)).unwrap();
+    assert_eq!(u.id, 7);
+    let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+    assert_eq!(
+        sql,
+        vec![
+            "INSERT INTO users (email, name) VALUES (?,?) ON DUPLICATE KEY UPDATE name = VALUES(name)",
+            "SELECT * FROM users WHERE email = ?",
+        ]
+    );
+}
+
+#[test]
+fn should_merge_on_mssql() {
+    let client = MockClient::new(Syntax::Mssql);
+    let data = vec![user(1, "a@x.com"), user(2, "b@x.com")];
+    block_on(run(&client, &data, &Upsert::new())).unwrap();
+    assert_eq!(
+        client.last_sql().unwrap(),
+        r#"MERGE INTO users WITH (HOLDLOCK) AS welds_target USING (VALUES (@p1,@p2,@p3),(@p4,@p5,@p6)) AS welds_rows ("id", "email", "name") ON welds_target."id" = welds_rows."id" WHEN MATCHED THEN UPDATE SET "email" = welds_rows."email", "name" = welds_rows."name" WHEN NOT MATCHED THEN INSERT ("id", "email", "name") VALUES (welds_rows."id", welds_rows."email", welds_rows."name");"#
+    );
+}
+
+#[test]
+fn should_output_a_conflicting_row_with_nothing_to_update_on_mssql() {
+    let client = MockClient::new(Syntax::Mssql);
+    let row = MockRow::new()
+        .with("id", 7)
+        .with("email", "b@x.com")
+        .with("name", "Ann");
+    client.enqueue_rows("MERGE", vec![row]);
+    let upsert = Upsert::new().on_conflict(&["email"]).update_only(&[]);
+    let mut u = DbState::new_uncreated(user(0, "b@x.com"));
+    block_on(u.upsert_with(&upsert, &client)).unwrap();
+    // the row already in the table is read back, as it was
+    assert_eq!(u.id, 7);
+    assert_eq!(u.name, "Ann");
+    let sql = client.last_sql().unwrap();
+    assert!(sql.contains(
+        r#"WHEN MATCHED THEN UPDATE SET "email" = welds_target."email" WHEN NOT MATCHED"#
+    ));
+    assert!(sql.contains(" OUTPUT Inserted."));
+}
+
+#[test]
+fn should_reject_unknown_columns() {
+    let client = MockClient::new(Syntax::Postgres);
+    let upsert = Upsert::new().on_conflict(&["nope"]);
+    let result = block_on(run(&client, &[user(1, "a@x.com")], &upsert));
+    assert!(matches!(result, Err(MissingDbColumn(c)) if c == "nope"));
+}
@@ -0,0 +1,150 @@
+use crate::query::clause::{ClauseAdder, ParamArgs};
+use crate::writers::{ColumnWriter, NextParam};
+use crate::Syntax;
+use welds_connections::Param;
+
+/// A value found at a path in a JSON column
+#[derive(Debug, Clone)]
+pub(crate) struct JsonPath {
+    pub(crate) col: String,
+    pub(crate) path: String,
+}
+
+impl JsonPath {
+    /// The value at the path, as text (or a number on Sqlite)
+    pub(crate) fn write(&self, syntax: Syntax, alias: &str) -> String {
+        let writer = ColumnWriter::new(syntax);
+        let col = format!("{}.{}", alias, writer.excape(&self.col));
+        let path = quote(syntax, &self.path);
+        match syntax {
+            Syntax::Postgres | Syntax::CockroachDb => {
+                format!(
+                    "jsonb_path_query_first({}::jsonb, {}) #>> '{{}}'",
+                    col, path
+                )
+            }
+            Syntax::Mysql | Syntax::MariaDb => {
+                format!("JSON_UNQUOTE(JSON_EXTRACT({}, {}))", col, path)
+            }
+            Syntax::Sqlite => format!("json_extract({}, {})", col, path),
+            Syntax::Mssql => format!("JSON_VALUE({}, {})", col, path),
+        }
+    }
+}
+
+// the path as a string literal
+fn quote(syntax: Syntax, path: &str) -> String {
+    let mut path = path.replace('\'', "''");
+    if matches!(syntax, Syntax::Mysql | Syntax::MariaDb) {
+        path = path.replace('\\', "\\\\");
+    }
+    format!("'{}'", path)
+}
+
+/// A value the JSON can be compared to. Text, or a number which the JSON is read as
+pub trait JsonValue {
+    fn into_param(self) -> (Box<dyn Param + Send + Sync>, bool);
+}
+
+impl JsonValue for &str {
+    fn into_param(self) -> (Box<dyn Param + Send + Sync>, bool) {
+        (Box::new(self.to_owned()), false)
+    }
+}
+
+impl JsonValue for String {
+    fn into_param(self) -> (Box<dyn Param + Send + Sync>, bool) {
+        (Box::new(self), false)
+    }
+}
+
+macro_rules! numeric_json_value {
+    ($($t:ty),*) => {
+        $(
+            impl JsonValue for $t {
+                fn into_param(self) -> (Box<dyn Param + Send + Sync>, bool) {
+                    (Box::new(self), true)
+                }
+            }
+        )*
+    };
+}
+numeric_json_value!(i32, i64, f64);
+
+/// The comparison of `where_json_path`, made with `eq`, `ne`, `gt`, `gte`, `lt`, and `lte`
+pub struct JsonFilter {
+    operator: &'static str,
+    value: Box<dyn Param + Send + Sync>,
+    numeric: bool,
+}
+
+fn filter(operator: &'static str, value: impl JsonValue) -> JsonFilter {
+    let (value, numeric) = value.into_param();
+    JsonFilter {
+        operator,
+        value,
+        numeric,
+    }
+}
+
+/// The value at the path equals this (=)
+pub fn eq(value: impl JsonValue) -> JsonFilter {
+    filter("=", value)
+}
+
+/// The value at the path is not equal to this (!=)
+pub fn ne(value: impl JsonValue) -> JsonFilter {
+    filter("!=", value)
+}
+
+/// The value at the path is greater than this (>)
+pub fn gt(value: impl JsonValue) -> JsonFilter {
+    filter(">", value)
+}
+
+/// The value at the path is greater than or equal to this (>=)
+pub fn gte(value: impl JsonValue) -> JsonFilter {
+    filter(">=", value)
+}
+
+/// The value at the path is less than this (<)
+pub fn lt(value: impl JsonValue) -> JsonFilter {
+    filter("<", value)
+}
+
+/// The value at the path is less than or equal to this (<=)
+pub fn lte(value: impl JsonValue) -> JsonFilter {
+    filter("<=", value)
+}
+
+pub(crate) struct ClauseJsonPath {
+    pub(crate) path: JsonPath,
+    pub(crate) filter: JsonFilter,
+}
+
+impl ClauseAdder for ClauseJsonPath {
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        args.push(self.filter.value.as_ref());
+    }
+
+    fn clause(&self, syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
+        let mut value = self.path.write(syntax, alias);
+        // postgres reads the path as text, it won't compare that to a number
+        let pg = matches!(syntax, Syntax::Postgres | Syntax::CockroachDb);
+        if self.filter.numeric && pg {
+            value = format!("CAST({} AS numeric)", value);
+        }
+        Some(format!(
+            "{} {} {}",
+            value,
+            self.filter.operator,
+            next_params.next()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,70 @@
+use crate::connections::mock::MockClient;
+use crate::connections::value::Value;
+use crate::query::json;
+use crate::Syntax;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "events")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Event {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub payload: String,
+}
+
+#[test]
+fn should_filter_on_a_json_path() {
+    let q = Event::all().where_json_path(|e| e.payload, "$.status", json::eq("active"));
+    assert!(q.to_sql(Syntax::Postgres).ends_with(
+        r#"WHERE ( jsonb_path_query_first(t1."payload"::jsonb, '$.status') #>> '{}' = $1 )"#
+    ));
+    assert!(q
+        .to_sql(Syntax::Mysql)
+        .ends_with("WHERE ( JSON_UNQUOTE(JSON_EXTRACT(t1.payload, '$.status')) = ? )"));
+    assert!(q
+        .to_sql(Syntax::Sqlite)
+        .ends_with(r#"WHERE ( json_extract(t1."payload", '$.status') = ? )"#));
+    assert!(q
+        .to_sql(Syntax::Mssql)
+        .ends_with(r#"WHERE ( JSON_VALUE(t1."payload", '$.status') = @p1 )"#));
+}
+
+#[test]
+fn should_compare_numbers_as_numbers_on_postgres() {
+    let q = Event::all().where_json_path(|e| e.payload, "$.items[0].qty", json::gte(3));
+    assert!(q.to_sql(Syntax::Postgres).ends_with(
+        r#"WHERE ( CAST(jsonb_path_query_first(t1."payload"::jsonb, '$.items[0].qty') #>> '{}' AS numeric) >= $1 )"#
+    ));
+}
+
+#[test]
+fn should_quote_the_path() {
+    let q = Event::all().where_json_path(|e| e.payload, "$.\"it's\"", json::ne("x"));
+    assert!(q
+        .to_sql(Syntax::Sqlite)
+        .contains(r#"json_extract(t1."payload", '$."it''s"')"#));
+}
+
+#[test]
+fn should_select_a_json_path() {
+    let q = Event::where_col(|e| e.id.gt(5))
+        .select(|e| e.id)
+        .select_json_path(|e| e.payload, "$.customer.email", "email");
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."id", JSON_VALUE(t1."payload", '$.customer.email') AS "email" FROM events t1 WHERE ( t1.id > @p1 )"#
+    );
+}
+
+#[test]
+fn should_bind_the_compared_value() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let q = Event::all()
+        .where_json_path(|e| e.payload, "$.status", json::eq("active"))
+        .where_json_path(|e| e.payload, "$.attempts", json::lt(3));
+    block_on(q.run(&client)).unwrap();
+    let expected = vec![Value::Text("active".to_owned()), Value::Int(3)];
+    assert_eq!(client.queries()[0].params, expected);
+}
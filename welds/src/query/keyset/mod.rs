@@ -0,0 +1,514 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::{ClauseAdder, OrderBy, ParamArgs};
+use crate::query::helpers::type_name;
+use crate::state::DbState;
+use crate::writers::in_namespace;
+use crate::writers::NextParam;
+use crate::{Client, Row, Syntax};
+use std::future::Future;
+
+/// Where a page of a keyset paginated query ended.
+///
+/// Pass it to `after_cursor` to fetch the next page. `encode` turns it into an opaque,
+/// URL safe string for handing to a client, `decode` reads it back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeysetCursor {
+    values: Vec<KeyValue>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum KeyValue {
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    Text(String),
+    #[cfg(feature = "uuid")]
+    Uuid(uuid::Uuid),
+    #[cfg(feature = "chrono")]
+    DateTime(chrono::DateTime<chrono::Utc>),
+    #[cfg(feature = "chrono")]
+    NaiveDateTime(chrono::NaiveDateTime),
+    #[cfg(feature = "chrono")]
+    NaiveDate(chrono::NaiveDate),
+}
+
+impl KeysetCursor {
+    /// The cursor as an opaque string
+    pub fn encode(&self) -> String {
+        let parts: Vec<String> = self
+            .values
+            .iter()
+            .map(|v| {
+                let (tag, bytes) = match v {
+                    KeyValue::I16(x) => ("i16", x.to_be_bytes().to_vec()),
+                    KeyValue::I32(x) => ("i32", x.to_be_bytes().to_vec()),
+                    KeyValue::I64(x) => ("i64", x.to_be_bytes().to_vec()),
+                    KeyValue::F32(x) => ("f32", x.to_bits().to_be_bytes().to_vec()),
+                    KeyValue::F64(x) => ("f64", x.to_bits().to_be_bytes().to_vec()),
+                    KeyValue::Bool(x) => ("b", vec![*x as u8]),
+                    KeyValue::Text(x) => ("s", x.as_bytes().to_vec()),
+                    #[cfg(feature = "uuid")]
+                    KeyValue::Uuid(x) => ("u", x.as_bytes().to_vec()),
+                    #[cfg(feature = "chrono")]
+                    KeyValue::DateTime(x) => ("dt", timestamp_bytes(&x.naive_utc())),
+                    #[cfg(feature = "chrono")]
+                    KeyValue::NaiveDateTime(x) => ("ndt", timestamp_bytes(x)),
+                    #[cfg(feature = "chrono")]
+                    KeyValue::NaiveDate(x) => {
+                        use chrono::Datelike;
+                        ("nd", x.num_days_from_ce().to_be_bytes().to_vec())
+                    }
+                };
+                let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+                format!("{}.{}", tag, hex)
+            })
+            .collect();
+        parts.join("-")
+    }
+
+    /// Reads back a cursor made by `encode`
+    pub fn decode(encoded: &str) -> Result<KeysetCursor> {
+        let invalid = || WeldsError::InvalidCursor(encoded.to_owned());
+        let mut values = Vec::default();
+        for part in encoded.split('-') {
+            let (tag, hex) = part.split_once('.').ok_or_else(invalid)?;
+            let bytes = from_hex(hex).ok_or_else(invalid)?;
+            let value = match tag {
+                "i16" => KeyValue::I16(i16::from_be_bytes(sized(&bytes).ok_or_else(invalid)?)),
+                "i32" => KeyValue::I32(i32::from_be_bytes(sized(&bytes).ok_or_else(invalid)?)),
+                "i64" => KeyValue::I64(i64::from_be_bytes(sized(&bytes).ok_or_else(invalid)?)),
+                "f32" => KeyValue::F32(f32::from_bits(u32::from_be_bytes(
+                    sized(&bytes).ok_or_else(invalid)?,
+                ))),
+                "f64" => KeyValue::F64(f64::from_bits(u64::from_be_bytes(
+                    sized(&bytes).ok_or_else(invalid)?,
+                ))),
+                "b" => KeyValue::Bool(bytes == [1]),
+                "s" => KeyValue::Text(String::from_utf8(bytes).map_err(|_| invalid())?),
+                #[cfg(feature = "uuid")]
+                "u" => KeyValue::Uuid(uuid::Uuid::from_bytes(sized(&bytes).ok_or_else(invalid)?)),
+                #[cfg(feature = "chrono")]
+                "dt" => KeyValue::DateTime(from_timestamp(&bytes).ok_or_else(invalid)?.and_utc()),
+                #[cfg(feature = "chrono")]
+                "ndt" => KeyValue::NaiveDateTime(from_timestamp(&bytes).ok_or_else(invalid)?),
+                #[cfg(feature = "chrono")]
+                "nd" => KeyValue::NaiveDate(
+                    chrono::NaiveDate::from_num_days_from_ce_opt(i32::from_be_bytes(
+                        sized(&bytes).ok_or_else(invalid)?,
+                    ))
+                    .ok_or_else(invalid)?,
+                ),
+                _ => return Err(invalid()),
+            };
+            values.push(value);
+        }
+        Ok(KeysetCursor { values })
+    }
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).ok().filter(|p| p.len() == 2)?;
+            u8::from_str_radix(pair, 16).ok()
+        })
+        .collect()
+}
+
+fn sized<const N: usize>(bytes: &[u8]) -> Option<[u8; N]> {
+    bytes.try_into().ok()
+}
+
+// a timestamp as its seconds and nanoseconds, so it is read back to the nanosecond
+#[cfg(feature = "chrono")]
+fn timestamp_bytes(x: &chrono::NaiveDateTime) -> Vec<u8> {
+    let utc = x.and_utc();
+    let mut bytes = utc.timestamp().to_be_bytes().to_vec();
+    bytes.extend(utc.timestamp_subsec_nanos().to_be_bytes());
+    bytes
+}
+
+#[cfg(feature = "chrono")]
+fn from_timestamp(bytes: &[u8]) -> Option<chrono::NaiveDateTime> {
+    if bytes.len() != 12 {
+        return None;
+    }
+    let secs = i64::from_be_bytes(sized(&bytes[..8])?);
+    let nanos = u32::from_be_bytes(sized(&bytes[8..])?);
+    Some(chrono::DateTime::from_timestamp(secs, nanos)?.naive_utc())
+}
+
+// a column the pages are sorted on
+#[derive(Debug, Clone)]
+struct KeyColumn {
+    name: String,
+    rust_type: String,
+    descending: bool,
+}
+
+impl KeyColumn {
+    fn read(&self, row: &Row) -> Result<KeyValue> {
+        let name = self.name.as_str();
+        let value = match type_name(&self.rust_type).as_str() {
+            "i16" => KeyValue::I16(row.get(name)?),
+            "i32" => KeyValue::I32(row.get(name)?),
+            "i64" => KeyValue::I64(row.get(name)?),
+            "f32" => KeyValue::F32(row.get(name)?),
+            "f64" => KeyValue::F64(row.get(name)?),
+            "bool" => KeyValue::Bool(row.get(name)?),
+            "String" => KeyValue::Text(row.get(name)?),
+            #[cfg(feature = "uuid")]
+            "Uuid" => KeyValue::Uuid(row.get(name)?),
+            #[cfg(feature = "chrono")]
+            "DateTime<Utc>" => KeyValue::DateTime(row.get(name)?),
+            #[cfg(feature = "chrono")]
+            "NaiveDateTime" => KeyValue::NaiveDateTime(row.get(name)?),
+            #[cfg(feature = "chrono")]
+            "NaiveDate" => KeyValue::NaiveDate(row.get(name)?),
+            other => {
+                return Err(WeldsError::InvalidCursor(format!(
+                    "column {} has type {} which can not be used in a cursor",
+                    name, other
+                )))
+            }
+        };
+        Ok(value)
+    }
+}
+
+/// A page of results from a keyset paginated query
+pub struct CursorPage<T> {
+    pub items: Vec<DbState<T>>,
+    /// Where to continue from. None on the last page
+    pub next_cursor: Option<KeysetCursor>,
+}
+
+/// Keyset (seek method) pagination. Made with `QueryBuilder::cursor_paginate`.
+///
+/// Instead of an OFFSET, each page starts after the last row of the page before it,
+/// so deep pages are as fast as the first one. The pages are sorted by the ordering of the
+/// query, with the primary key added to break ties.
+///
+/// ```rust,ignore
+/// let page = Order::where_col(|o| o.status.equal("open"))
+///     .order_by_asc(|o| o.created_at)
+///     .cursor_paginate(50)
+///     .after_cursor(last_cursor)
+///     .run(&client)
+///     .await?;
+/// // SELECT ... WHERE ( t1.status = $1 AND (t1.created_at, t1.id) > ($2, $3) )
+/// // ORDER BY created_at ASC, id ASC LIMIT 51
+/// ```
+///
+/// Only columns of simple types (ints, floats, bool, String, and with their features uuids
+/// and chrono's `DateTime<Utc>`, `NaiveDateTime` and `NaiveDate`) can be sorted on.
+/// Nullable columns and manual orderings can't be used.
+pub struct KeysetBuilder<T> {
+    qb: QueryBuilder<T>,
+    keys: Vec<KeyColumn>,
+    per_page: i64,
+    invalid: Option<String>,
+}
+
+impl<T> KeysetBuilder<T>
+where
+    T: Send + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    pub(crate) fn new(mut qb: QueryBuilder<T>, per_page: i64) -> Self {
+        let columns = <T as HasSchema>::Schema::columns();
+        let mut invalid = None;
+        let mut keys: Vec<KeyColumn> = Vec::default();
+        for order in &qb.orderby {
+            // a collation changes how the keys compare, the cursor couldn't follow it
+            if order.collate.is_some() {
+                invalid = Some(format!("can't page on the ordering {}", order.field));
+                continue;
+            }
+            let descending = match order.direction.as_str() {
+                "ASC" => false,
+                "DESC" => true,
+                _ => {
+                    invalid = Some(format!("can't page on the ordering {}", order.field));
+                    continue;
+                }
+            };
+            match columns.iter().find(|c| c.name() == order.field) {
+                Some(col) if !col.nullable() => keys.push(KeyColumn {
+                    name: col.name().to_owned(),
+                    rust_type: col.rust_type().to_owned(),
+                    descending,
+                }),
+                _ => invalid = Some(format!("can't page on the column {}", order.field)),
+            }
+        }
+        // the primary key makes the ordering unique, in the direction of the last ordering
+        let descending = keys.last().map(|k| k.descending).unwrap_or_default();
+        for pk in <T as HasSchema>::Schema::primary_keys() {
+            if !keys.iter().any(|k| k.name == pk.name()) {
+                keys.push(KeyColumn {
+                    name: pk.name().to_owned(),
+                    rust_type: pk.rust_type().to_owned(),
+                    descending,
+                });
+            }
+        }
+        qb.orderby = keys
+            .iter()
+            .map(|k| OrderBy::new(&k.name, if k.descending { "DESC" } else { "ASC" }))
+            .collect();
+        // one extra row tells us if there is another page
+        qb.limit = Some(per_page + 1);
+        qb.offset = None;
+        Self {
+            qb,
+            keys,
+            per_page,
+            invalid,
+        }
+    }
+
+    /// Starts the page after the row the cursor was taken from. `None` starts from the beginning
+    pub fn after_cursor(mut self, cursor: impl Into<Option<KeysetCursor>>) -> Self {
+        let cursor = match cursor.into() {
+            Some(cursor) => cursor,
+            None => return self,
+        };
+        if cursor.values.len() != self.keys.len() {
+            self.invalid = Some("the cursor is from a query with a different ordering".to_owned());
+            return self;
+        }
+        self.qb.wheres.push(Box::new(AfterCursor {
+            keys: self.keys.clone(),
+            values: cursor.values,
+        }));
+        self
+    }
+
+    /// Get a copy of the SQL that will be executed when this query runs
+    pub fn to_sql(&self, syntax: Syntax) -> String {
+        self.qb.to_sql(syntax)
+    }
+
+    /// Executes the query, returning the page and the cursor of the next one
+    pub async fn run(&self, client: &dyn Client) -> Result<CursorPage<T>>
+    where
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        if let Some(invalid) = &self.invalid {
+            return Err(WeldsError::InvalidCursor(invalid.clone()));
+        }
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let sql = in_namespace(client, || self.qb.select_sql(client.syntax(), &mut args));
+        let args = args.unwrap();
+        let mut rows = client.fetch_rows(&sql, &args).await?;
+
+        let more = rows.len() as i64 > self.per_page;
+        rows.truncate(self.per_page.max(0) as usize);
+        let next_cursor = match (more, rows.last()) {
+            (true, Some(last)) => {
+                let values: Result<Vec<KeyValue>> =
+                    self.keys.iter().map(|k| k.read(last)).collect();
+                Some(KeysetCursor { values: values? })
+            }
+            _ => None,
+        };
+
+        let mut items = Vec::default();
+        for row in rows {
+            let obj: T = T::try_from(row)?;
+            items.push(DbState::db_loaded(obj));
+        }
+        Ok(CursorPage { items, next_cursor })
+    }
+}
+
+impl<T> QueryBuilder<T>
+where
+    T: Send + HasSchema,
+{
+    /// Runs the query `batch_size` rows at a time, handing each batch to `each` before the next
+    /// one is read. Batches are read with keyset pagination (see `KeysetBuilder`), in the order
+    /// of the query and then by primary key, so large tables can be worked through without
+    /// holding them in memory or paying for deep offsets.
+    ///
+    /// The primary key, and the columns the query is ordered by, can be of any type a cursor
+    /// can hold: uuid and chrono keys included.
+    /// A limit on the query caps the number of rows read, its offset is ignored.
+    /// Returns the number of rows that were read.
+    ///
+    /// ```rust,ignore
+    /// let copied = Order::where_col(|o| o.status.equal("closed"))
+    ///     .in_batches(1000, &client, |orders| async move {
+    ///         warehouse.load(orders).await?;
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn in_batches<F, Fut>(
+        self,
+        batch_size: i64,
+        client: &dyn Client,
+        mut each: F,
+    ) -> Result<u64>
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+        F: FnMut(Vec<DbState<T>>) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let batch_size = batch_size.max(1);
+        let cap = self.limit;
+        let mut pages = KeysetBuilder::new(self, batch_size);
+        let mut seeking = false;
+        let mut read: u64 = 0;
+        loop {
+            if let Some(cap) = cap {
+                let left = cap - read as i64;
+                if left <= 0 {
+                    break;
+                }
+                pages.per_page = batch_size.min(left);
+                pages.qb.limit = Some(pages.per_page + 1);
+            }
+            let page = pages.run(client).await?;
+            read += page.items.len() as u64;
+            if !page.items.is_empty() {
+                each(page.items).await?;
+            }
+            let cursor = match page.next_cursor {
+                Some(cursor) => cursor,
+                None => break,
+            };
+            // the last where is the seek past the batch before this one
+            if seeking {
+                pages.qb.wheres.pop();
+            }
+            pages = pages.after_cursor(cursor);
+            seeking = true;
+        }
+        Ok(read)
+    }
+}
+
+// the rows after the cursor, in the order of the keys
+struct AfterCursor {
+    keys: Vec<KeyColumn>,
+    values: Vec<KeyValue>,
+}
+
+impl AfterCursor {
+    fn param(&self, i: usize) -> &(dyn welds_connections::Param + Sync + Send) {
+        match &self.values[i] {
+            KeyValue::I16(x) => x,
+            KeyValue::I32(x) => x,
+            KeyValue::I64(x) => x,
+            KeyValue::F32(x) => x,
+            KeyValue::F64(x) => x,
+            KeyValue::Bool(x) => x,
+            KeyValue::Text(x) => x,
+            #[cfg(feature = "uuid")]
+            KeyValue::Uuid(x) => x,
+            #[cfg(feature = "chrono")]
+            KeyValue::DateTime(x) => x,
+            #[cfg(feature = "chrono")]
+            KeyValue::NaiveDateTime(x) => x,
+            #[cfg(feature = "chrono")]
+            KeyValue::NaiveDate(x) => x,
+        }
+    }
+
+    // all the keys sorted the same way. Each value is bound once
+    fn uniform(&self) -> bool {
+        let first = self.keys[0].descending;
+        self.keys.iter().all(|k| k.descending == first)
+    }
+}
+
+impl ClauseAdder for AfterCursor {
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        if self.keys.is_empty() {
+            return;
+        }
+        if self.uniform() {
+            for i in 0..self.values.len() {
+                args.push(self.param(i));
+            }
+            return;
+        }
+        // the expanded form repeats the earlier keys, see `clause`
+        for i in 0..self.values.len() {
+            for j in 0..=i {
+                args.push(self.param(j));
+            }
+        }
+    }
+
+    fn clause(&self, syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
+        if self.keys.is_empty() {
+            return None;
+        }
+        let op = |k: &KeyColumn| if k.descending { "<" } else { ">" };
+        let col = |k: &KeyColumn| format!("{}.{}", alias, k.name);
+
+        if !self.uniform() {
+            // (a > ? OR (a = ? AND b < ?) OR ...)
+            let ors: Vec<String> = (0..self.keys.len())
+                .map(|i| {
+                    let mut ands: Vec<String> = self.keys[..i]
+                        .iter()
+                        .map(|k| format!("{} = {}", col(k), next_params.next()))
+                        .collect();
+                    let k = &self.keys[i];
+                    ands.push(format!("{} {} {}", col(k), op(k), next_params.next()));
+                    and_group(ands)
+                })
+                .collect();
+            return Some(format!("({})", ors.join(" OR ")));
+        }
+
+        let params: Vec<String> = self.keys.iter().map(|_| next_params.next()).collect();
+        if syntax != Syntax::Mssql {
+            let cols: Vec<String> = self.keys.iter().map(col).collect();
+            return Some(format!(
+                "({}) {} ({})",
+                cols.join(", "),
+                op(&self.keys[0]),
+                params.join(", ")
+            ));
+        }
+        // MSSQL doesn't have row values, its named params are reused instead
+        let ors: Vec<String> = (0..self.keys.len())
+            .map(|i| {
+                let mut ands: Vec<String> = (0..i)
+                    .map(|j| format!("{} = {}", col(&self.keys[j]), params[j]))
+                    .collect();
+                let k = &self.keys[i];
+                ands.push(format!("{} {} {}", col(k), op(k), params[i]));
+                and_group(ands)
+            })
+            .collect();
+        Some(format!("({})", ors.join(" OR ")))
+    }
+}
+
+fn and_group(mut ands: Vec<String>) -> String {
+    match ands.len() {
+        1 => ands.remove(0),
+        _ => format!("({})", ands.join(" AND ")),
+    }
+}
+
+#[cfg(test)]
+mod tests;
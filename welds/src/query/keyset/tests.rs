@@ -0,0 +1,288 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::value::Value;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "events")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Event {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub created_at: i64,
+    pub kind: String,
+    pub note: Option<String>,
+}
+
+fn event(id: i32, created_at: i64) -> MockRow {
+    MockRow::new()
+        .with("id", id)
+        .with("created_at", created_at)
+        .with("kind", "click")
+        .with("note", Value::Null)
+}
+
+fn cursor(created_at: i64, id: i32) -> KeysetCursor {
+    KeysetCursor {
+        values: vec![KeyValue::I64(created_at), KeyValue::I32(id)],
+    }
+}
+
+#[test]
+fn should_order_by_the_primary_key_without_an_ordering() {
+    let q = Event::all().cursor_paginate(20);
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."created_at", t1."kind", t1."note" FROM events t1 ORDER BY id ASC OFFSET 0 LIMIT 21"#
+    );
+}
+
+#[test]
+fn should_seek_past_the_cursor_with_row_values() {
+    let q = Event::where_col(|e| e.kind.equal("click"))
+        .order_by_asc(|e| e.created_at)
+        .cursor_paginate(50)
+        .after_cursor(cursor(100, 7));
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id, t1.created_at, t1.kind, t1.note FROM events t1 WHERE ( t1.kind = ? AND (t1.created_at, t1.id) > (?, ?) ) ORDER BY created_at ASC, id ASC LIMIT 0, 51"
+    );
+}
+
+#[test]
+fn should_expand_the_comparison_when_the_directions_differ() {
+    let q = Event::all()
+        .order_by_asc(|e| e.kind)
+        .order_by_desc(|e| e.created_at)
+        .cursor_paginate(10)
+        .after_cursor(KeysetCursor {
+            values: vec![
+                KeyValue::Text("click".to_owned()),
+                KeyValue::I64(100),
+                KeyValue::I32(7),
+            ],
+        });
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."id", t1."created_at", t1."kind", t1."note" FROM events t1 WHERE ( (t1.kind > ? OR (t1.kind = ? AND t1.created_at < ?) OR (t1.kind = ? AND t1.created_at = ? AND t1.id < ?)) ) ORDER BY kind ASC, created_at DESC, id DESC LIMIT 11 OFFSET 0 "#
+    );
+}
+
+#[test]
+fn should_reuse_the_named_params_on_mssql() {
+    let q = Event::all()
+        .order_by_desc(|e| e.created_at)
+        .cursor_paginate(10)
+        .after_cursor(cursor(100, 7));
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."id", t1."created_at", t1."kind", t1."note" FROM events t1 WHERE ( (t1.created_at < @p1 OR (t1.created_at = @p1 AND t1.id < @p2)) ) ORDER BY created_at DESC, id DESC OFFSET 0 ROWS FETCH FIRST 11 ROWS ONLY"#
+    );
+}
+
+#[test]
+fn should_return_the_cursor_of_the_last_row_when_there_is_more() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("events", vec![event(1, 10), event(2, 20), event(3, 30)]);
+    client.enqueue_rows("events", vec![event(3, 30)]);
+
+    let q = Event::all()
+        .order_by_asc(|e| e.created_at)
+        .cursor_paginate(2);
+    let page = block_on(q.run(&client)).unwrap();
+    assert_eq!(page.items.len(), 2);
+    let next = page.next_cursor.unwrap();
+    assert_eq!(next, cursor(20, 2));
+
+    let decoded = KeysetCursor::decode(&next.encode()).unwrap();
+    let q = Event::all()
+        .order_by_asc(|e| e.created_at)
+        .cursor_paginate(2)
+        .after_cursor(decoded);
+    let page = block_on(q.run(&client)).unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert!(page.next_cursor.is_none());
+    assert_eq!(client.queries()[1].params.len(), 2);
+}
+
+#[test]
+fn should_not_page_on_a_nullable_column() {
+    let client = MockClient::new(Syntax::Postgres);
+    let q = Event::all().order_by_asc(|e| e.note).cursor_paginate(2);
+    let err = block_on(q.run(&client)).err().unwrap();
+    assert!(matches!(err, WeldsError::InvalidCursor(_)));
+}
+
+#[test]
+fn should_read_back_an_encoded_cursor() {
+    let c = KeysetCursor {
+        values: vec![
+            KeyValue::Text("a-b.c".to_owned()),
+            KeyValue::F64(1.5),
+            KeyValue::Bool(true),
+        ],
+    };
+    let encoded = c.encode();
+    assert!(encoded
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || ch == '.' || ch == '-'));
+    assert_eq!(KeysetCursor::decode(&encoded).unwrap(), c);
+    assert!(KeysetCursor::decode("i64.zz").is_err());
+}
+
+#[test]
+fn should_read_the_table_in_batches_by_primary_key() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("events", vec![event(1, 0), event(2, 0), event(3, 0)]);
+        client.enqueue_rows("events", vec![event(3, 0), event(4, 0), event(5, 0)]);
+        client.enqueue_rows("events", vec![event(5, 0)]);
+
+        let mut batches = Vec::default();
+        let read = Event::where_col(|e| e.kind.equal("click"))
+            .in_batches(2, &client, |batch| {
+                batches.push(batch.iter().map(|e| e.id).collect::<Vec<_>>());
+                async { Ok(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(read, 5);
+        assert_eq!(batches, [vec![1, 2], vec![3, 4], vec![5]]);
+
+        let queries = client.queries();
+        assert_eq!(
+            queries[2].sql,
+            r#"SELECT t1."id", t1."created_at", t1."kind", t1."note" FROM events t1 WHERE ( t1.kind = $1 AND (t1.id) > ($2) ) ORDER BY id ASC OFFSET 0 LIMIT 3"#
+        );
+        assert_eq!(
+            queries[2].params,
+            [Value::Text("click".to_owned()), Value::Int(4)]
+        );
+    });
+}
+
+#[test]
+fn should_stop_reading_batches_at_the_limit() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("events", vec![event(1, 0), event(2, 0), event(3, 0)]);
+        client.enqueue_rows("events", vec![event(3, 0), event(4, 0)]);
+
+        let read = Event::all()
+            .limit(3)
+            .in_batches(2, &client, |_| async { Ok(()) })
+            .await
+            .unwrap();
+        assert_eq!(read, 3);
+        let queries = client.queries();
+        assert_eq!(queries.len(), 2);
+        assert!(queries[1].sql.ends_with("ORDER BY id ASC OFFSET 0 LIMIT 2"));
+    });
+}
+
+#[cfg(all(feature = "uuid", feature = "chrono"))]
+#[test]
+fn should_read_back_a_cursor_of_uuids_and_dates() {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    let at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap();
+    let at = at + chrono::Duration::nanoseconds(123_456_789);
+    let c = KeysetCursor {
+        values: vec![
+            KeyValue::Uuid(uuid::Uuid::from_u128(0x1234)),
+            KeyValue::DateTime(at),
+            KeyValue::NaiveDateTime(at.naive_utc()),
+            KeyValue::NaiveDate(NaiveDate::from_ymd_opt(1999, 12, 31).unwrap()),
+        ],
+    };
+    assert_eq!(KeysetCursor::decode(&c.encode()).unwrap(), c);
+}
+
+#[cfg(feature = "chrono")]
+mod dates {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "visits")]
+    #[welds_path(crate)] // needed only within the welds crate.
+    struct Visit {
+        #[welds(primary_key)]
+        pub id: i32,
+        pub seen_at: chrono::DateTime<chrono::Utc>,
+    }
+
+    fn visit(id: i32, hour: u32) -> MockRow {
+        let seen_at = Utc.with_ymd_and_hms(2024, 5, 1, hour, 0, 0).unwrap();
+        MockRow::new().with("id", id).with("seen_at", seen_at)
+    }
+
+    #[test]
+    fn should_page_on_a_date() {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("visits", vec![visit(1, 8), visit(2, 9), visit(3, 10)]);
+        let q = Visit::all().order_by_asc(|v| v.seen_at).cursor_paginate(2);
+        let page = block_on(q.run(&client)).unwrap();
+        let next = page.next_cursor.unwrap();
+        let seen_at = Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap();
+        assert_eq!(
+            next.values,
+            vec![KeyValue::DateTime(seen_at), KeyValue::I32(2)]
+        );
+
+        let q = Visit::all()
+            .order_by_asc(|v| v.seen_at)
+            .cursor_paginate(2)
+            .after_cursor(KeysetCursor::decode(&next.encode()).unwrap());
+        block_on(q.run(&client)).unwrap();
+        let params = &client.queries()[1].params;
+        assert_eq!(params[1], Value::Int(2));
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuids {
+    use super::*;
+    use crate::connections::value::ToValue;
+    use uuid::Uuid;
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "sessions")]
+    #[welds_path(crate)] // needed only within the welds crate.
+    struct Session {
+        #[welds(primary_key)]
+        pub id: Uuid,
+    }
+
+    fn session(id: u128) -> MockRow {
+        MockRow::new().with("id", Uuid::from_u128(id))
+    }
+
+    #[test]
+    fn should_read_the_table_in_batches_by_a_uuid_key() {
+        block_on(async {
+            let client = MockClient::new(Syntax::Postgres);
+            client.enqueue_rows("sessions", vec![session(1), session(2), session(3)]);
+            client.enqueue_rows("sessions", vec![session(3)]);
+
+            let mut read_ids = Vec::default();
+            let read = Session::all()
+                .in_batches(2, &client, |batch| {
+                    read_ids.extend(batch.iter().map(|s| s.id));
+                    async { Ok(()) }
+                })
+                .await
+                .unwrap();
+            assert_eq!(read, 3);
+            assert_eq!(read_ids[2], Uuid::from_u128(3));
+
+            // the next batch starts after the last uuid of the one before it
+            let queries = client.queries();
+            assert_eq!(
+                queries[1].sql,
+                r#"SELECT t1."id" FROM sessions t1 WHERE ( (t1.id) > ($1) ) ORDER BY id ASC OFFSET 0 LIMIT 3"#
+            );
+            assert_eq!(queries[1].params, [Uuid::from_u128(2).to_value()]);
+        });
+    }
+}
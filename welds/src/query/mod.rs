@@ -1,10 +1,21 @@
 pub mod builder;
 pub mod clause;
 pub(crate) mod delete;
+#[cfg(feature = "json")]
+pub mod filter;
+pub mod group_by;
 pub(crate) mod helpers;
+pub mod include;
 pub mod insert;
+pub mod json;
+pub mod keyset;
 pub mod optional;
+pub mod order;
+pub mod search;
 pub mod select;
 pub mod select_cols;
 pub(crate) mod tail;
+pub mod tree;
+pub mod union;
 pub(crate) mod update;
+pub mod window;
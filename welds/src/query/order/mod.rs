@@ -0,0 +1,73 @@
+use crate::query::clause::orderby::Nulls;
+use crate::query::clause::{AsFieldName, OrderBy};
+
+/// An ordering of the rows of a query, used with `order_by`.
+///
+/// Orders by a column or an expression, and can choose where the NULLs go and the collation
+/// the values are compared with.
+///
+/// ```rust,ignore
+/// use welds::query::order::{asc, asc_expr, desc};
+///
+/// let users = User::all()
+///     .order_by(|u| desc(u.last_login).nulls_last())
+///     .order_by(|_| asc_expr("lower(name)"))
+///     .order_by(|u| asc(u.email).collate("C"))
+///     .run(&client)
+///     .await?;
+/// // ORDER BY last_login DESC NULLS LAST, lower(name) ASC, email COLLATE "C" ASC
+/// ```
+///
+/// MySQL and MSSQL don't have `NULLS FIRST/LAST`, the NULLs are sorted on their own with a
+/// `CASE WHEN .. IS NULL` ahead of the ordering.
+pub struct Order {
+    pub(crate) by: OrderBy,
+}
+
+impl Order {
+    /// Puts the rows with a NULL first
+    pub fn nulls_first(mut self) -> Self {
+        self.by.nulls = Some(Nulls::First);
+        self
+    }
+
+    /// Puts the rows with a NULL last
+    pub fn nulls_last(mut self) -> Self {
+        self.by.nulls = Some(Nulls::Last);
+        self
+    }
+
+    /// Compares the values with this collation (`COLLATE`).
+    /// The name is written as is, collations are specific to each database
+    pub fn collate(mut self, collation: impl Into<String>) -> Self {
+        self.by.collate = Some(collation.into());
+        self
+    }
+}
+
+/// Orders by the column, lowest first
+pub fn asc<V>(field: impl AsFieldName<V>) -> Order {
+    asc_expr(field.colname())
+}
+
+/// Orders by the column, highest first
+pub fn desc<V>(field: impl AsFieldName<V>) -> Order {
+    desc_expr(field.colname())
+}
+
+/// Orders by an SQL expression, lowest first. The SQL is written as is
+pub fn asc_expr(sql: impl Into<String>) -> Order {
+    Order {
+        by: OrderBy::new(sql, "ASC"),
+    }
+}
+
+/// Orders by an SQL expression, highest first. The SQL is written as is
+pub fn desc_expr(sql: impl Into<String>) -> Order {
+    Order {
+        by: OrderBy::new(sql, "DESC"),
+    }
+}
+
+#[cfg(test)]
+mod tests;
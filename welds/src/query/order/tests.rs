@@ -0,0 +1,68 @@
+use super::*;
+use crate::Syntax;
+use crate::WeldsModel;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "users")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct User {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub last_login: Option<String>,
+}
+
+#[test]
+fn should_order_by_an_expression() {
+    let q = User::all()
+        .select(|u| u.id)
+        .order_by(|_| asc_expr("lower(name)"));
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."id" FROM users t1 ORDER BY lower(name) ASC"#
+    );
+}
+
+#[test]
+fn should_put_nulls_last() {
+    let q = User::all()
+        .select(|u| u.id)
+        .order_by(|u| desc(u.last_login).nulls_last());
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id" FROM users t1 ORDER BY last_login DESC NULLS LAST"#
+    );
+}
+
+#[test]
+fn should_sort_the_nulls_on_their_own_without_nulls_first() {
+    let q = User::all()
+        .select(|u| u.id)
+        .order_by(|u| asc(u.last_login).nulls_first());
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id FROM users t1 ORDER BY CASE WHEN last_login IS NULL THEN 0 ELSE 1 END, last_login ASC"
+    );
+    let q = User::all()
+        .select(|u| u.id)
+        .order_by(|u| desc(u.last_login).nulls_last())
+        .limit(5);
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        "SELECT t1.\"id\" FROM users t1 ORDER BY CASE WHEN last_login IS NULL THEN 1 ELSE 0 END, last_login DESC OFFSET 0 ROWS FETCH FIRST 5 ROWS ONLY"
+    );
+}
+
+#[test]
+fn should_order_with_a_collation() {
+    let q = User::all().order_by(|u| asc(u.name).collate("C"));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."name", t1."last_login" FROM users t1 ORDER BY name COLLATE "C" ASC"#
+    );
+    let q = User::all().order_by(|u| asc(u.name).collate("NOCASE"));
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."id", t1."name", t1."last_login" FROM users t1 ORDER BY name COLLATE NOCASE ASC"#
+    );
+}
@@ -0,0 +1,117 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::{FullText, OrderBy, ParamArgs};
+use crate::query::tail;
+use crate::state::DbState;
+use crate::writers::in_namespace;
+use crate::writers::NextParam;
+use crate::{Client, Row, Syntax};
+
+/// A full-text search, with the best matches first. Made with `QueryBuilder::search`.
+///
+/// The ordering of the query breaks ties between rows that match equally well.
+///
+/// ```rust,ignore
+/// let posts = Post::where_col(|p| p.published.equal(true))
+///     .search(|p| (p.title, p.body), "rust orm")
+///     .limit(20)
+///     .run(&client)
+///     .await?;
+/// ```
+pub struct SearchBuilder<T> {
+    qb: QueryBuilder<T>,
+    search: FullText,
+}
+
+impl<T> SearchBuilder<T>
+where
+    T: Send + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    pub(crate) fn new(mut qb: QueryBuilder<T>, search: FullText) -> Self {
+        qb.wheres.push(Box::new(search.clone()));
+        Self { qb, search }
+    }
+
+    /// Limit the number of rows returned by this query
+    pub fn limit(mut self, x: i64) -> Self {
+        self.qb = self.qb.limit(x);
+        self
+    }
+
+    /// Offset the starting point for the results returned by this query
+    pub fn offset(mut self, x: i64) -> Self {
+        self.qb = self.qb.offset(x);
+        self
+    }
+
+    fn sql_internal<'s, 'p>(&'s self, syntax: Syntax, args: &mut Option<ParamArgs<'p>>) -> String
+    where
+        's: 'p,
+    {
+        let qb = &self.qb;
+        let columns = <T as HasSchema>::Schema::columns();
+        let next_params = NextParam::new(syntax);
+        let writer = qb.select_writer(syntax);
+        let sql = writer.sql_with_params(
+            &columns,
+            &qb.wheres,
+            &qb.exist_ins,
+            &None,
+            &None,
+            &[],
+            &next_params,
+            args,
+        );
+
+        // the ORDER BY comes after the WHERE, so do its param
+        let param = match FullText::rank_has_param(syntax) {
+            true => {
+                if let Some(args) = args {
+                    args.push(&self.search.terms);
+                }
+                Some(next_params.next())
+            }
+            false => None,
+        };
+        let pks = <T as HasSchema>::Schema::primary_keys();
+        let pk = pks.first().map(|c| c.name()).unwrap_or_default();
+        let (rank, direction) = self.search.rank(syntax, &qb.alias, pk, param);
+
+        let mut orders = vec![OrderBy::new(rank, direction)];
+        for o in &qb.orderby {
+            orders.push(o.clone());
+        }
+        match tail::write(syntax, &qb.limit, &qb.offset, &orders) {
+            Some(tail) => format!("{} {}", sql.trim(), tail),
+            None => sql,
+        }
+    }
+
+    /// Get a copy of the SQL that will be executed when this query runs
+    pub fn to_sql(&self, syntax: Syntax) -> String {
+        self.sql_internal(syntax, &mut None)
+    }
+
+    /// Executes the query in the database returning the results
+    pub async fn run(&self, client: &dyn Client) -> Result<Vec<DbState<T>>>
+    where
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let sql = in_namespace(client, || self.sql_internal(client.syntax(), &mut args));
+        let args = args.unwrap();
+        let rows = client.fetch_rows(&sql, &args).await?;
+        let mut objs = Vec::default();
+        for row in rows {
+            let obj: T = T::try_from(row)?;
+            objs.push(DbState::db_loaded(obj));
+        }
+        Ok(objs)
+    }
+}
+
+#[cfg(test)]
+mod tests;
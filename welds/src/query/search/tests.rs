@@ -0,0 +1,64 @@
+use crate::connections::mock::MockClient;
+use crate::connections::value::Value;
+use crate::Syntax;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "posts")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Post {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub title: String,
+    pub body: Option<String>,
+    pub published: bool,
+}
+
+#[test]
+fn should_filter_with_the_search_of_each_database() {
+    let q = Post::all().where_matches(|p| (p.title, p.body), "rust orm");
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."title", t1."body", t1."published" FROM posts t1 WHERE ( to_tsvector(coalesce(t1.title, '') || ' ' || coalesce(t1.body, '')) @@ plainto_tsquery($1) )"#
+    );
+    assert!(q
+        .to_sql(Syntax::Mysql)
+        .ends_with("WHERE ( MATCH (t1.title, t1.body) AGAINST (?) )"));
+    assert!(q
+        .to_sql(Syntax::Sqlite)
+        .ends_with("WHERE ( t1.posts MATCH '{title body} : (' || ? || ')' )"));
+    assert!(q
+        .to_sql(Syntax::Mssql)
+        .ends_with("WHERE ( CONTAINS((t1.title, t1.body), @p1) )"));
+}
+
+#[test]
+fn should_order_by_relevance() {
+    let q = Post::where_col(|p| p.published.equal(true))
+        .order_by_desc(|p| p.id)
+        .search(|p| p.title, "rust")
+        .limit(5);
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."title", t1."body", t1."published" FROM posts t1 WHERE ( t1.published = $1 AND to_tsvector(coalesce(t1.title, '')) @@ plainto_tsquery($2) ) ORDER BY ts_rank(to_tsvector(coalesce(t1.title, '')), plainto_tsquery($3)) DESC, id DESC OFFSET 0 LIMIT 5"#
+    );
+    assert!(q
+        .to_sql(Syntax::Sqlite)
+        .ends_with("ORDER BY bm25(t1.posts) ASC, id DESC LIMIT 5 OFFSET 0 "));
+    assert!(q.to_sql(Syntax::Mssql).contains(
+        "ORDER BY (SELECT k.[RANK] FROM CONTAINSTABLE(posts, (title), @p3) k WHERE k.[KEY] = t1.id) DESC"
+    ));
+}
+
+#[test]
+fn should_bind_the_terms_for_the_ranking() {
+    let client = MockClient::new(Syntax::Mysql);
+    block_on(Post::all().search(|p| p.title, "rust").run(&client)).unwrap();
+    let terms = Value::Text("rust".to_owned());
+    assert_eq!(client.queries()[0].params, vec![terms.clone(), terms]);
+
+    let client = MockClient::new(Syntax::Sqlite);
+    block_on(Post::all().search(|p| p.title, "rust").run(&client)).unwrap();
+    assert_eq!(client.queries()[0].params.len(), 1);
+}
@@ -0,0 +1,202 @@
+use crate::errors::Result;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::{AsFieldName, ParamArgs};
+use crate::query::group_by::{self, Aggregate};
+use crate::writers::in_namespace;
+use crate::writers::ColumnWriter;
+use crate::{Client, Row, Syntax, WeldsError};
+
+/// A numeric column type that can be summed and averaged with `QueryBuilder::sum`, `avg`,
+/// `min` and `max`.
+///
+/// Databases widen a sum (a sum of `INT` is a `BIGINT` on Postgres, a `DECIMAL` on MySQL),
+/// so sums are cast to a `BIGINT` or a double and read back as an `i64` or an `f64`.
+pub trait Aggregatable: Sized {
+    /// What the sum of the column is read as
+    type Sum;
+
+    /// The SQL type the sum is cast to, and the zero used when there are no rows
+    fn sum_type(syntax: Syntax) -> (&'static str, &'static str);
+
+    fn read_sum(row: &Row) -> Result<Self::Sum>;
+
+    fn read(row: &Row) -> Result<Option<Self>>;
+}
+
+macro_rules! aggregatable_int {
+    ($($t:ty),*) => {
+        $(
+            impl Aggregatable for $t {
+                type Sum = i64;
+
+                fn sum_type(syntax: Syntax) -> (&'static str, &'static str) {
+                    match syntax {
+                        Syntax::Mysql | Syntax::MariaDb => ("SIGNED", "0"),
+                        _ => ("BIGINT", "0"),
+                    }
+                }
+
+                fn read_sum(row: &Row) -> Result<i64> {
+                    Ok(row.get_by_position(0)?)
+                }
+
+                fn read(row: &Row) -> Result<Option<$t>> {
+                    Ok(row.get_by_position(0)?)
+                }
+            }
+        )*
+    };
+}
+aggregatable_int!(i16, i32, i64);
+
+macro_rules! aggregatable_float {
+    ($($t:ty),*) => {
+        $(
+            impl Aggregatable for $t {
+                type Sum = f64;
+
+                fn sum_type(syntax: Syntax) -> (&'static str, &'static str) {
+                    match syntax {
+                        Syntax::Postgres | Syntax::CockroachDb => ("DOUBLE PRECISION", "0.0"),
+                        Syntax::Mysql | Syntax::MariaDb => ("DOUBLE", "0.0"),
+                        Syntax::Mssql => ("FLOAT", "0.0"),
+                        Syntax::Sqlite => ("REAL", "0.0"),
+                    }
+                }
+
+                fn read_sum(row: &Row) -> Result<f64> {
+                    Ok(row.get_by_position(0)?)
+                }
+
+                fn read(row: &Row) -> Result<Option<$t>> {
+                    Ok(row.get_by_position(0)?)
+                }
+            }
+        )*
+    };
+}
+aggregatable_float!(f32, f64);
+
+impl<T> QueryBuilder<T>
+where
+    T: Send + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    /// Executes a `SELECT SUM(...)` of a column over the results of your query.
+    /// The sum of no rows is zero
+    ///
+    /// ```rust,ignore
+    /// let total: i64 = Order::where_col(|o| o.paid.equal(true)).sum(|o| o.cents, &client).await?;
+    /// ```
+    pub async fn sum<V, FN>(
+        &self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        client: &dyn Client,
+    ) -> Result<V::Sum>
+    where
+        V: Aggregatable,
+        FN: AsFieldName<V>,
+    {
+        let syntax = client.syntax();
+        let column = lam(Default::default()).colname().to_owned();
+        let (cast, zero) = V::sum_type(syntax);
+        let writer = ColumnWriter::new(syntax);
+        let expr = |alias: &str| {
+            let col = format!("{}.{}", alias, writer.excape(&column));
+            format!("COALESCE(CAST(SUM({}) AS {}), {})", col, cast, zero)
+        };
+        let row = self.fetch_aggregate(expr, client).await?;
+        V::read_sum(&row)
+    }
+
+    /// Executes a `SELECT AVG(...)` of a column over the results of your query.
+    /// None if there are no rows
+    pub async fn avg<V, FN>(
+        &self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        client: &dyn Client,
+    ) -> Result<Option<f64>>
+    where
+        V: Aggregatable,
+        FN: AsFieldName<V>,
+    {
+        let agg = group_by::avg(lam(Default::default()));
+        let syntax = client.syntax();
+        let row = self
+            .fetch_aggregate(|alias| agg.write(syntax, alias), client)
+            .await?;
+        Ok(row.get_by_position(0)?)
+    }
+
+    /// Executes a `SELECT MIN(...)` of a column over the results of your query.
+    /// None if there are no rows
+    pub async fn min<V, FN>(
+        &self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        client: &dyn Client,
+    ) -> Result<Option<V>>
+    where
+        V: Aggregatable,
+        FN: AsFieldName<V>,
+    {
+        let agg = group_by::min(lam(Default::default()));
+        self.fetch_extreme(agg, client).await
+    }
+
+    /// Executes a `SELECT MAX(...)` of a column over the results of your query.
+    /// None if there are no rows
+    pub async fn max<V, FN>(
+        &self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        client: &dyn Client,
+    ) -> Result<Option<V>>
+    where
+        V: Aggregatable,
+        FN: AsFieldName<V>,
+    {
+        let agg = group_by::max(lam(Default::default()));
+        self.fetch_extreme(agg, client).await
+    }
+
+    async fn fetch_extreme<V: Aggregatable>(
+        &self,
+        agg: Aggregate,
+        client: &dyn Client,
+    ) -> Result<Option<V>> {
+        let syntax = client.syntax();
+        let row = self
+            .fetch_aggregate(|alias| agg.write(syntax, alias), client)
+            .await?;
+        V::read(&row)
+    }
+
+    // Runs a single aggregate over the rows of the query, `expr` is given the alias of the table.
+    // A limit, offset or distinct picks the rows first, they are aggregated in a derived table
+    async fn fetch_aggregate(
+        &self,
+        expr: impl Fn(&str) -> String,
+        client: &dyn Client,
+    ) -> Result<Row> {
+        let syntax = client.syntax();
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let writer = self.select_writer(syntax);
+        let limited = self.limit.is_some() || self.offset.is_some();
+        let sql = in_namespace(client, || match limited || writer.is_distinct() {
+            true => {
+                let inner = self.select_sql(syntax, &mut args);
+                format!(
+                    "SELECT {} FROM ( {} ) welds_rows",
+                    expr("welds_rows"),
+                    inner
+                )
+            }
+            false => {
+                writer.sql_aggregate(&expr(&self.alias), &self.wheres, &self.exist_ins, &mut args)
+            }
+        });
+        let args = args.unwrap();
+        let rows = self.fetch_rows(client, &sql, &args).await?;
+        rows.into_iter().next().ok_or(WeldsError::RowNowFound)
+    }
+}
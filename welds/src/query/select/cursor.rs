@@ -0,0 +1,132 @@
+use crate::errors::Result;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::ParamArgs;
+use crate::state::DbState;
+use crate::writers::in_namespace;
+use crate::{Client, Row, Syntax, TransactStart, WeldsError};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use welds_connections::Transaction;
+
+static NEXT_CURSOR: AtomicU64 = AtomicU64::new(1);
+
+impl<T> QueryBuilder<T>
+where
+    T: Send + HasSchema,
+{
+    /// Opens a server-side cursor over the results of this query (Postgres, CockroachDB and MSSQL).
+    ///
+    /// The cursor holds a transaction (and its connection) until it is closed, and the rows are
+    /// only read from the database as they are fetched, so a slow consumer can work through a
+    /// large result at its own pace.
+    ///
+    /// ```rust,ignore
+    /// let mut cursor = Order::where_col(|o| o.status.equal("open")).cursor(&client).await?;
+    /// loop {
+    ///     let batch = cursor.fetch_next(500).await?;
+    ///     if batch.is_empty() {
+    ///         break;
+    ///     }
+    ///     process(batch).await?;
+    /// }
+    /// cursor.close().await?;
+    /// ```
+    pub async fn cursor<'c, C>(&self, client: &'c C) -> Result<Cursor<'c, T>>
+    where
+        C: TransactStart + ?Sized,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let trans = client.begin().await?;
+        let syntax = trans.syntax();
+        let name = format!(
+            "welds_cursor_{}",
+            NEXT_CURSOR.fetch_add(1, Ordering::Relaxed)
+        );
+
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let select = in_namespace(&trans, || self.select_sql(syntax, &mut args));
+        let args = args.unwrap();
+        let sql = match syntax {
+            Syntax::Postgres | Syntax::CockroachDb => {
+                format!("DECLARE {} NO SCROLL CURSOR FOR {}", name, select)
+            }
+            Syntax::Mssql => format!(
+                "DECLARE {name} CURSOR GLOBAL FORWARD_ONLY READ_ONLY FOR {select}; OPEN {name}"
+            ),
+            Syntax::Mysql | Syntax::MariaDb | Syntax::Sqlite => {
+                return Err(WeldsError::CursorsUnsupported(syntax));
+            }
+        };
+        trans.execute(&sql, &args).await?;
+
+        Ok(Cursor {
+            _t: PhantomData,
+            trans,
+            name,
+            syntax,
+            done: false,
+        })
+    }
+}
+
+/// A server-side cursor over the results of a query. See `QueryBuilder::cursor`.
+///
+/// Dropping the cursor without closing it rolls back its transaction, which also ends the cursor
+pub struct Cursor<'t, T> {
+    _t: PhantomData<T>,
+    trans: Transaction<'t>,
+    name: String,
+    syntax: Syntax,
+    done: bool,
+}
+
+impl<'t, T> Cursor<'t, T> {
+    /// Reads up to the next `n` rows. Returns an empty list once every row has been read
+    pub async fn fetch_next(&mut self, n: usize) -> Result<Vec<DbState<T>>>
+    where
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        if self.done || n == 0 {
+            return Ok(Vec::default());
+        }
+        let rows = match self.syntax {
+            Syntax::Mssql => {
+                // each FETCH is a result set of its own, with one row
+                let sql = format!(
+                    "DECLARE @i INT = 1; FETCH NEXT FROM {name}; \
+                     WHILE @@FETCH_STATUS = 0 AND @i < {n} BEGIN SET @i += 1; FETCH NEXT FROM {name}; END",
+                    name = self.name,
+                    n = n
+                );
+                let sets = self.trans.fetch_result_sets(&sql, &[]).await?;
+                sets.into_iter().flatten().collect()
+            }
+            _ => {
+                let sql = format!("FETCH FORWARD {} FROM {}", n, self.name);
+                self.trans.fetch_rows(&sql, &[]).await?
+            }
+        };
+
+        if rows.len() < n {
+            self.done = true;
+        }
+        let mut objs = Vec::default();
+        for row in rows {
+            objs.push(DbState::db_loaded(T::try_from(row)?));
+        }
+        Ok(objs)
+    }
+
+    /// Closes the cursor and ends its transaction
+    pub async fn close(self) -> Result<()> {
+        let sql = match self.syntax {
+            Syntax::Mssql => format!("CLOSE {name}; DEALLOCATE {name}", name = self.name),
+            _ => format!("CLOSE {}", self.name),
+        };
+        self.trans.execute(&sql, &[]).await?;
+        self.trans.commit().await?;
+        Ok(())
+    }
+}
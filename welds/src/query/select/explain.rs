@@ -0,0 +1,100 @@
+use crate::errors::Result;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::ParamArgs;
+use crate::writers::in_namespace;
+use crate::{Client, Row, Syntax, WeldsError};
+use std::collections::HashMap;
+use std::fmt;
+
+impl<T> QueryBuilder<T>
+where
+    T: Send + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    /// Asks the database how it would run this query (`EXPLAIN`), without running it.
+    ///
+    /// The plan is read back as lines of text, in the format of each database:
+    /// the plan text on Postgres, `FORMAT=TREE` on MySQL, `FORMAT=JSON` on MariaDB, and
+    /// `EXPLAIN QUERY PLAN` on Sqlite (indented by its nesting). Not supported on MSSQL.
+    ///
+    /// ```rust,ignore
+    /// let plan = Order::where_col(|o| o.customer_id.equal(4)).explain(&client).await?;
+    /// println!("{}", plan);
+    /// ```
+    pub async fn explain(&self, client: &dyn Client) -> Result<QueryPlan> {
+        let syntax = client.syntax();
+        let prefix = match syntax {
+            Syntax::Postgres | Syntax::CockroachDb => "EXPLAIN",
+            Syntax::Mysql => "EXPLAIN FORMAT=TREE",
+            Syntax::MariaDb => "EXPLAIN FORMAT=JSON",
+            Syntax::Sqlite => "EXPLAIN QUERY PLAN",
+            Syntax::Mssql => return Err(WeldsError::ExplainUnsupported(syntax)),
+        };
+        self.fetch_plan(prefix, client).await
+    }
+
+    /// Runs this query and returns the plan with what actually happened (`EXPLAIN ANALYZE`),
+    /// the real row counts and timings of each step.
+    ///
+    /// The query is executed, its rows are thrown away.
+    /// Not supported on Sqlite or MSSQL.
+    pub async fn explain_analyze(&self, client: &dyn Client) -> Result<QueryPlan> {
+        let syntax = client.syntax();
+        let prefix = match syntax {
+            Syntax::Postgres | Syntax::CockroachDb | Syntax::Mysql => "EXPLAIN ANALYZE",
+            Syntax::MariaDb => "ANALYZE FORMAT=JSON",
+            Syntax::Sqlite | Syntax::Mssql => return Err(WeldsError::ExplainUnsupported(syntax)),
+        };
+        self.fetch_plan(prefix, client).await
+    }
+
+    async fn fetch_plan(&self, prefix: &str, client: &dyn Client) -> Result<QueryPlan> {
+        let syntax = client.syntax();
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let select = in_namespace(client, || self.select_sql(syntax, &mut args));
+        let sql = format!("{} {}", prefix, select);
+        let args = args.unwrap();
+        let rows = client.fetch_rows(&sql, &args).await?;
+        let lines = match syntax {
+            Syntax::Sqlite => sqlite_plan(&rows)?,
+            _ => {
+                let mut lines = Vec::default();
+                for row in &rows {
+                    let text: String = row.get_by_position(0)?;
+                    lines.extend(text.lines().map(|l| l.to_owned()));
+                }
+                lines
+            }
+        };
+        Ok(QueryPlan { lines })
+    }
+}
+
+// each step of a sqlite plan is indented under the step it is part of
+fn sqlite_plan(rows: &[Row]) -> Result<Vec<String>> {
+    let mut depths: HashMap<i64, usize> = HashMap::default();
+    let mut lines = Vec::default();
+    for row in rows {
+        let id: i64 = row.get("id")?;
+        let parent: i64 = row.get("parent")?;
+        let detail: String = row.get("detail")?;
+        let depth = depths.get(&parent).map(|d| d + 1).unwrap_or(0);
+        depths.insert(id, depth);
+        lines.push(format!("{}{}", "  ".repeat(depth), detail));
+    }
+    Ok(lines)
+}
+
+/// The plan of a query, from `QueryBuilder::explain` or `explain_analyze`.
+/// Displays as the lines of the plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlan {
+    pub lines: Vec<String>,
+}
+
+impl fmt::Display for QueryPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines.join("\n"))
+    }
+}
@@ -1,17 +1,28 @@
 use super::builder::QueryBuilder;
-use super::clause::ParamArgs;
+use super::clause::{AsFieldName, OrderBy, ParamArgs};
 use super::helpers::{build_tail, build_where, join_sql_parts};
 use crate::errors::Result;
 use crate::model_traits::{HasSchema, TableColumns, TableIdent, TableInfo};
 use crate::state::DbState;
 use crate::writers::column::ColumnWriter;
 use crate::writers::nextparam::NextParam;
+use crate::writers::CountWriter;
+use crate::writers::{in_namespace, table_name};
 use crate::{Syntax, WeldsError};
+use welds_connections::statement_cache::cache_statements;
 use welds_connections::Client;
 use welds_connections::Row;
 
 mod writer;
-pub use writer::SelectWriter;
+pub use writer::{RowLock, SelectWriter};
+mod aggregate;
+pub use aggregate::Aggregatable;
+mod cursor;
+pub use cursor::Cursor;
+mod explain;
+pub use explain::QueryPlan;
+mod page;
+pub use page::Page;
 
 // ******************************************************************************************
 // This file contains all the stuff added onto the Querybuilder to allow it to run SELECTs
@@ -27,8 +38,10 @@ where
         T: HasSchema,
         <T as HasSchema>::Schema: TableInfo + TableColumns,
     {
-        let table = TableIdent::from_model::<T>();
-        let writer = SelectWriter::new_with_alias(syntax, &table, &self.alias);
+        let writer = self.select_writer(syntax);
+        if writer.is_distinct() {
+            return self.distinct_count_sql(&writer, &self.limit, &self.offset, &mut None);
+        }
         writer.sql_count(
             &self.wheres,
             &self.exist_ins,
@@ -50,19 +63,98 @@ where
         let syntax = client.syntax();
         let mut args: Option<ParamArgs> = Some(Vec::default());
 
+        let writer = self.select_writer(syntax);
+        let sql = in_namespace(client, || match writer.is_distinct() {
+            true => self.distinct_count_sql(&writer, &self.limit, &self.offset, &mut args),
+            false => writer.sql_count(
+                &self.wheres,
+                &self.exist_ins,
+                &self.limit,
+                &self.offset,
+                &self.orderby,
+                &mut args,
+            ),
+        });
+
+        let args = args.unwrap();
+        let rows = self.fetch_rows(client, &sql, &args).await?;
+        let row = rows.first().ok_or(WeldsError::RowNowFound)?;
+        let count: i64 = row.get_by_position(0)?;
+        Ok(count as u64)
+    }
+
+    /// Checks if the query has any results, with a `SELECT EXISTS ( ... )`.
+    ///
+    /// Cheaper than a `count` or fetching a row, the database stops at the first match.
+    pub async fn exists<'q, 'c>(&'q self, client: &'c dyn Client) -> Result<bool>
+    where
+        'q: 'c,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let syntax = client.syntax();
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+
+        // the ordering only matters to which rows a limit or offset leaves
+        let limited = self.limit.is_some() || self.offset.is_some();
+        let orders: &[OrderBy] = if limited { &self.orderby } else { &[] };
+        let writer = self.select_writer(syntax);
+        let sql = in_namespace(client, || {
+            writer.sql_exists(
+                &self.wheres,
+                &self.exist_ins,
+                &self.limit,
+                &self.offset,
+                orders,
+                &mut args,
+            )
+        });
+
+        let args = args.unwrap();
+        let rows = self.fetch_rows(client, &sql, &args).await?;
+        let row = rows.first().ok_or(WeldsError::RowNowFound)?;
+        let found = match syntax {
+            Syntax::Postgres | Syntax::CockroachDb => row.get_by_position(0)?,
+            _ => row.get_by_position::<i64>(0)? != 0,
+        };
+        Ok(found)
+    }
+
+    /// Counts the distinct values of a column in the results of your query, cheaply.
+    ///
+    /// The result is an estimate where the backend can make one:
+    /// MSSQL uses `APPROX_COUNT_DISTINCT`, and Postgres reads the planner's statistics
+    /// (`pg_stats`) when the query has no filters and the table has been analyzed.
+    /// Everything else falls back to an exact `COUNT(DISTINCT ...)`.
+    pub async fn approx_count_distinct<'q, 'c, V, FN: AsFieldName<V>>(
+        &'q self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        client: &'c dyn Client,
+    ) -> Result<u64>
+    where
+        'q: 'c,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let syntax = client.syntax();
+        let column = lam(Default::default()).colname().to_string();
         let table = TableIdent::from_model::<T>();
-        let writer = SelectWriter::new_with_alias(syntax, &table, &self.alias);
-        let sql = writer.sql_count(
-            &self.wheres,
-            &self.exist_ins,
-            &self.limit,
-            &self.offset,
-            &self.orderby,
-            &mut args,
-        );
+
+        let unfiltered = self.wheres.is_empty() && self.exist_ins.is_empty();
+        if syntax == Syntax::Postgres && unfiltered {
+            if let Some(estimate) = estimate_from_pg_stats(client, &table, &column).await? {
+                return Ok(estimate);
+            }
+        }
+
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let writer = SelectWriter::new_with_alias(syntax, &table, &self.alias)
+            .with_nolock(self.nolock)
+            .with_sample(self.sample);
+        let sql = in_namespace(client, || {
+            writer.sql_count_distinct(&column, true, &self.wheres, &self.exist_ins, &mut args)
+        });
 
         let args = args.unwrap();
-        let rows = client.fetch_rows(&sql, &args).await?;
+        let rows = self.fetch_rows(client, &sql, &args).await?;
         let row = rows.first().ok_or(WeldsError::RowNowFound)?;
         let count: i64 = row.get_by_position(0)?;
         Ok(count as u64)
@@ -73,18 +165,23 @@ where
     where
         <T as HasSchema>::Schema: TableInfo + TableColumns,
     {
-        let table = TableIdent::from_model::<T>();
-        let columns = <T as HasSchema>::Schema::columns();
-        let writer = SelectWriter::new_with_alias(syntax, &table, &self.alias);
-        writer.sql(
-            &columns,
-            &self.wheres,
-            &self.exist_ins,
-            &self.limit,
-            &self.offset,
-            &self.orderby,
-            &mut None,
-        )
+        self.select_sql(syntax, &mut None)
+    }
+
+    /// Get a copy of the SQL that will be executed when this query runs, along with the
+    /// params bound to it (in order). `connections::value::of_param` reads the value of a param
+    ///
+    /// ```rust,ignore
+    /// let (sql, params) = Product::where_col(|p| p.price.gt(3.5)).to_sql_with_params(Syntax::Postgres);
+    /// // SELECT ... FROM products t1 WHERE ( t1.price > $1 ), [3.5]
+    /// ```
+    pub fn to_sql_with_params(&self, syntax: Syntax) -> (String, ParamArgs<'_>)
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let sql = self.select_sql(syntax, &mut args);
+        (sql, args.unwrap())
     }
 
     /// Executes the query in the database returning the results
@@ -98,21 +195,10 @@ where
         let syntax = client.syntax();
         let mut args: Option<ParamArgs> = Some(Vec::default());
 
-        let table = TableIdent::from_model::<T>();
-        let columns = <T as HasSchema>::Schema::columns();
-        let writer = SelectWriter::new_with_alias(syntax, &table, &self.alias);
-        let sql = writer.sql(
-            &columns,
-            &self.wheres,
-            &self.exist_ins,
-            &self.limit,
-            &self.offset,
-            &self.orderby,
-            &mut args,
-        );
+        let sql = in_namespace(client, || self.select_sql(syntax, &mut args));
 
         let args = args.unwrap();
-        let rows = client.fetch_rows(&sql, &args).await?;
+        let rows = self.fetch_rows(client, &sql, &args).await?;
 
         let mut objs = Vec::default();
         for row in rows {
@@ -121,6 +207,163 @@ where
         }
         Ok(objs)
     }
+
+    // Runs the SQL with this query's statement caching
+    async fn fetch_rows(
+        &self,
+        client: &dyn Client,
+        sql: &str,
+        args: &ParamArgs<'_>,
+    ) -> Result<Vec<Row>> {
+        let rows = match self.statement_cache {
+            Some(enabled) => cache_statements(enabled, client.fetch_rows(sql, args)).await?,
+            None => client.fetch_rows(sql, args).await?,
+        };
+        Ok(rows)
+    }
+
+    /// The writer for the SELECTs of this query
+    pub(crate) fn select_writer(&self, syntax: Syntax) -> SelectWriter
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let table = TableIdent::from_model::<T>();
+        SelectWriter::new_with_alias(syntax, &table, &self.alias)
+            .with_nolock(self.nolock)
+            .with_lock(self.lock)
+            .with_sample(self.sample)
+            .with_distinct(self.distinct, &self.distinct_on)
+    }
+
+    // Counts the rows left once they are made distinct
+    fn distinct_count_sql<'q>(
+        &'q self,
+        writer: &SelectWriter,
+        limit: &Option<i64>,
+        offset: &Option<i64>,
+        args: &mut Option<ParamArgs<'q>>,
+    ) -> String
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let columns = <T as HasSchema>::Schema::columns();
+        let next_params = NextParam::new(writer.syntax());
+        // a derived table can't be ordered unless it is also limited (MSSQL)
+        let limited = limit.is_some() || offset.is_some();
+        let orders: &[OrderBy] = if limited { &self.orderby } else { &[] };
+        let rows = writer.sql_with_params(
+            &columns,
+            &self.wheres,
+            &self.exist_ins,
+            limit,
+            offset,
+            orders,
+            &next_params,
+            args,
+        );
+        let count = CountWriter::new(writer.syntax()).count(None, Some("*"));
+        format!("SELECT {} FROM ( {} ) welds_rows", count, rows)
+    }
+
+    // The SQL to select the full rows of this query
+    pub(crate) fn select_sql<'q>(
+        &'q self,
+        syntax: Syntax,
+        args: &mut Option<ParamArgs<'q>>,
+    ) -> String
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let columns = <T as HasSchema>::Schema::columns();
+        let writer = self.select_writer(syntax);
+
+        let pks = <T as HasSchema>::Schema::primary_keys();
+        let distinct = writer.is_distinct();
+        let locked = self.lock.is_some();
+        if self.late_row_lookup && self.offset.is_some() && pks.len() == 1 && !distinct && !locked {
+            return writer.sql_late_row_lookup(
+                &columns,
+                &pks[0],
+                &self.wheres,
+                &self.exist_ins,
+                &self.limit,
+                &self.offset,
+                &self.orderby,
+                args,
+            );
+        }
+
+        writer.sql(
+            &columns,
+            &self.wheres,
+            &self.exist_ins,
+            &self.limit,
+            &self.offset,
+            &self.orderby,
+            args,
+        )
+    }
+
+    // The SQL to select just the given columns of the rows of this query, to be read from a
+    // derived table. It isn't locked, and only ordered when it is limited (MSSQL)
+    pub(crate) fn select_columns_sql<'q>(
+        &'q self,
+        names: &[&str],
+        syntax: Syntax,
+        next_params: &NextParam,
+        args: &mut Option<ParamArgs<'q>>,
+    ) -> String
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let all = <T as HasSchema>::Schema::columns();
+        let columns: Vec<_> = names
+            .iter()
+            .filter_map(|name| all.iter().find(|c| c.name() == *name).cloned())
+            .collect();
+        let table = TableIdent::from_model::<T>();
+        let writer = SelectWriter::new_with_alias(syntax, &table, &self.alias)
+            .with_distinct(self.distinct, &self.distinct_on);
+        let limited = self.limit.is_some() || self.offset.is_some();
+        let orders: &[OrderBy] = if limited { &self.orderby } else { &[] };
+        writer.sql_with_params(
+            &columns,
+            &self.wheres,
+            &self.exist_ins,
+            &self.limit,
+            &self.offset,
+            orders,
+            next_params,
+            args,
+        )
+    }
+}
+
+// n_distinct is either a count, or (when negative) the fraction of the rows that are distinct.
+// None if the column has no statistics or the table has never been analyzed
+async fn estimate_from_pg_stats(
+    client: &dyn Client,
+    table: &TableIdent,
+    column: &str,
+) -> Result<Option<u64>> {
+    let sql = "SELECT CAST( CASE WHEN s.n_distinct >= 0 THEN s.n_distinct ELSE -s.n_distinct * c.reltuples END as BIGINT ) \
+        FROM pg_stats s \
+        JOIN pg_namespace n ON n.nspname = s.schemaname \
+        JOIN pg_class c ON c.relnamespace = n.oid AND c.relname = s.tablename \
+        WHERE s.schemaname = COALESCE(NULLIF($1, ''), current_schema()) \
+        AND s.tablename = $2 AND s.attname = $3 AND c.reltuples >= 0";
+    // the table in the client's namespace, pg_stats itself is read as written
+    let table = TableIdent::parse(&in_namespace(client, || table_name(&table.to_string())));
+    let schema = table.schema().unwrap_or_default().to_string();
+    let name = table.name().to_string();
+    let column = column.to_string();
+    let args: ParamArgs = vec![&schema, &name, &column];
+    let rows = client.fetch_rows(sql, &args).await?;
+    let estimate: Option<i64> = match rows.first() {
+        Some(row) => row.get_by_position(0)?,
+        None => None,
+    };
+    Ok(estimate.map(|x| x as u64))
 }
 
 #[cfg(test)]
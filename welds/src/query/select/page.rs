@@ -0,0 +1,118 @@
+use crate::errors::Result;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::ParamArgs;
+use crate::state::DbState;
+use crate::writers::in_namespace;
+use crate::{Client, Row, WeldsError};
+use welds_connections::statement_cache::cache_statements;
+use welds_connections::Fetch;
+
+/// One page of the results of a query, with the total count of the results.
+/// Made with `QueryBuilder::paginate`
+pub struct Page<T> {
+    pub items: Vec<DbState<T>>,
+    /// the number of rows in all the pages
+    pub total: u64,
+    /// the number of this page, starting at 1
+    pub page: u64,
+    pub per_page: u64,
+    pub total_pages: u64,
+}
+
+impl<T> Page<T> {
+    /// There is a page after this one
+    pub fn has_next(&self) -> bool {
+        self.page < self.total_pages
+    }
+
+    /// There is a page before this one
+    pub fn has_prev(&self) -> bool {
+        self.page > 1
+    }
+}
+
+impl<T> QueryBuilder<T>
+where
+    T: Send + HasSchema,
+{
+    /// Runs one page of this query, along with a count of all the results.
+    /// Pages start at 1, a page of 0 is treated as the first.
+    ///
+    /// The count and the page are run on the same connection, one after the other.
+    /// The limit and offset of the query are replaced by the page.
+    ///
+    /// ```rust,ignore
+    /// let page = Product::where_col(|p| p.active.equal(true))
+    ///     .order_by_asc(|p| p.name)
+    ///     .paginate(3, 25, &client)
+    ///     .await?;
+    /// println!("page {} of {}, {} products", page.page, page.total_pages, page.total);
+    /// ```
+    pub async fn paginate(
+        mut self,
+        page: u64,
+        per_page: u64,
+        client: &dyn Client,
+    ) -> Result<Page<T>>
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let syntax = client.syntax();
+        let page = page.max(1);
+        self.limit = Some(per_page as i64);
+        self.offset = Some(((page - 1) * per_page) as i64);
+
+        let mut count_args: Option<ParamArgs> = Some(Vec::default());
+        let writer = self.select_writer(syntax);
+        let count_sql = in_namespace(client, || match writer.is_distinct() {
+            true => self.distinct_count_sql(&writer, &None, &None, &mut count_args),
+            false => writer.sql_count(
+                &self.wheres,
+                &self.exist_ins,
+                &None,
+                &None,
+                &[],
+                &mut count_args,
+            ),
+        });
+        let count_args = count_args.unwrap();
+
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let sql = in_namespace(client, || self.select_sql(syntax, &mut args));
+        let args = args.unwrap();
+
+        let statements = [
+            Fetch::new(&count_sql, &count_args).with_label("count"),
+            Fetch::new(&sql, &args).with_label("page"),
+        ];
+        let mut datasets = match self.statement_cache {
+            Some(enabled) => cache_statements(enabled, client.fetch_many(&statements)).await?,
+            None => client.fetch_many(&statements).await?,
+        };
+        let rows = datasets.pop().unwrap_or_default();
+        let counted = datasets.pop().unwrap_or_default();
+        let row = counted.first().ok_or(WeldsError::RowNowFound)?;
+        let total: i64 = row.get_by_position(0)?;
+        let total = total as u64;
+
+        let mut items = Vec::default();
+        for row in rows {
+            let obj: T = T::try_from(row)?;
+            items.push(DbState::db_loaded(obj));
+        }
+        let total_pages = match per_page {
+            0 => 0,
+            _ => total.div_ceil(per_page),
+        };
+        Ok(Page {
+            items,
+            total,
+            page,
+            per_page,
+            total_pages,
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub cents: i32,
+    pub weight: Option<f64>,
+}
+
+#[test]
+fn should_sum_a_column_as_a_bigint() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("SUM", vec![MockRow::new().with("sum", 12_i64)]);
+        let q = Order::where_col(|o| o.id.gt(1));
+        let total = q.sum(|o| o.cents, &client).await.unwrap();
+        assert_eq!(total, 12);
+        assert_eq!(
+            client.last_sql().unwrap(),
+            r#"SELECT COALESCE(CAST(SUM(t1."cents") AS BIGINT), 0) FROM orders t1 WHERE ( t1.id > $1 )"#
+        );
+    });
+}
+
+#[test]
+fn should_sum_a_float_column_as_a_double() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Mysql);
+        client.enqueue_rows("SUM", vec![MockRow::new().with("sum", 1.5)]);
+        let total = Order::all().sum(|o| o.weight, &client).await.unwrap();
+        assert_eq!(total, 1.5);
+        assert_eq!(
+            client.last_sql().unwrap(),
+            "SELECT COALESCE(CAST(SUM(t1.weight) AS DOUBLE), 0.0) FROM orders t1"
+        );
+    });
+}
+
+#[test]
+fn should_read_no_average_of_no_rows() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Mssql);
+        client.enqueue_rows("AVG", vec![MockRow::new().with("avg", None::<f64>)]);
+        let avg = Order::all().avg(|o| o.cents, &client).await.unwrap();
+        assert_eq!(avg, None);
+        assert_eq!(
+            client.last_sql().unwrap(),
+            r#"SELECT AVG(CAST(t1."cents" AS FLOAT)) FROM orders t1"#
+        );
+    });
+}
+
+#[test]
+fn should_read_the_min_and_max_as_the_column_type() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Sqlite);
+        client.enqueue_rows("MIN", vec![MockRow::new().with("min", 3)]);
+        client.enqueue_rows("MAX", vec![MockRow::new().with("max", 9)]);
+        let q = Order::all();
+        let min: Option<i32> = q.min(|o| o.cents, &client).await.unwrap();
+        let max: Option<i32> = q.max(|o| o.cents, &client).await.unwrap();
+        assert_eq!((min, max), (Some(3), Some(9)));
+    });
+}
+
+#[test]
+fn should_aggregate_the_rows_a_limit_picks() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("MAX", vec![MockRow::new().with("max", 9)]);
+        let q = Order::all().order_by_desc(|o| o.id).limit(5);
+        q.max(|o| o.cents, &client).await.unwrap();
+        assert_eq!(
+            client.last_sql().unwrap(),
+            r#"SELECT MAX(welds_rows."cents") FROM ( SELECT t1."id", t1."cents", t1."weight" FROM orders t1 ORDER BY id DESC OFFSET 0 LIMIT 5 ) welds_rows"#
+        );
+    });
+}
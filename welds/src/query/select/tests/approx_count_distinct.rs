@@ -0,0 +1,67 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub price: f64,
+}
+
+fn count(value: i64) -> Vec<MockRow> {
+    vec![MockRow::new().with("count", value)]
+}
+
+#[test]
+fn should_use_the_native_estimate_on_mssql() {
+    let client = MockClient::new(Syntax::Mssql);
+    client.enqueue_rows("APPROX_COUNT_DISTINCT", count(42));
+    let q = Product::where_col(|x| x.price.gt(1.0));
+    let found = block_on(q.approx_count_distinct(|x| x.name, &client)).unwrap();
+    assert_eq!(found, 42);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        r#"SELECT CAST( APPROX_COUNT_DISTINCT(t1."name") as BIGINT ) FROM products t1 WHERE ( t1.price > @p1 )"#
+    );
+}
+
+#[test]
+fn should_fall_back_to_an_exact_count() {
+    let client = MockClient::new(Syntax::Mysql);
+    client.enqueue_rows("COUNT(DISTINCT", count(7));
+    let found = block_on(Product::all().approx_count_distinct(|x| x.name, &client)).unwrap();
+    assert_eq!(found, 7);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "SELECT COUNT(DISTINCT t1.name) FROM products t1"
+    );
+}
+
+#[test]
+fn should_read_planner_stats_for_unfiltered_postgres_queries() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("pg_stats", count(1200));
+    let found = block_on(Product::all().approx_count_distinct(|x| x.name, &client)).unwrap();
+    assert_eq!(found, 1200);
+    let queries = client.queries();
+    assert_eq!(queries.len(), 1);
+    assert_eq!(queries[0].params.len(), 3);
+}
+
+#[test]
+fn should_count_exactly_when_postgres_has_no_stats() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("pg_stats", vec![]);
+    client.enqueue_rows("COUNT(DISTINCT", count(3));
+    let found = block_on(Product::all().approx_count_distinct(|x| x.name, &client)).unwrap();
+    assert_eq!(found, 3);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        r#"SELECT CAST( COUNT(DISTINCT t1."name") as BIGINT ) FROM products t1"#
+    );
+}
@@ -0,0 +1,62 @@
+use super::*;
+use crate::connections::mock::MockClient;
+use crate::query::clause::CompareColumn;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "shipments")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Shipment {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub order_id: i32,
+    pub ordered_qty: i32,
+    pub shipped_qty: Option<i32>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(HasMany(shipments, Shipment, "order_id"))]
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+#[test]
+fn should_compare_two_columns() {
+    let q = Shipment::where_col(|s| s.updated_at.gt_col(s.created_at)).select(|s| s.id);
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id" FROM shipments t1 WHERE ( t1.updated_at > t1.created_at )"#
+    );
+}
+
+#[test]
+fn should_compare_to_a_nullable_column_without_params() {
+    let client = MockClient::new(Syntax::Mssql);
+    let q = Shipment::where_col(|s| s.shipped_qty.lt_col(s.ordered_qty))
+        .where_col(|s| s.id.gt(5))
+        .select(|s| s.id);
+    block_on(q.run(&client)).unwrap();
+    assert_eq!(
+        client.last_sql().unwrap(),
+        r#"SELECT t1."id" FROM shipments t1 WHERE ( t1.shipped_qty < t1.ordered_qty AND t1.id > @p1 )"#
+    );
+    assert_eq!(client.queries()[0].params.len(), 1);
+}
+
+#[test]
+fn should_compare_the_columns_of_a_joined_table() {
+    let q = Order::all().select(|o| o.id).join(
+        |o| o.shipments,
+        Shipment::where_col(|s| s.ordered_qty.ne_col(s.shipped_qty)).select(|s| s.ordered_qty),
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id, t2.ordered_qty FROM orders t1 JOIN shipments t2 ON t1.id = t2.order_id WHERE ( t2.ordered_qty != t2.shipped_qty )"
+    );
+}
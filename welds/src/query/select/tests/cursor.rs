@@ -0,0 +1,61 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub status: String,
+}
+
+fn order(id: i32) -> MockRow {
+    MockRow::new()
+        .with("id", id)
+        .with("status", "open".to_string())
+}
+
+#[test]
+fn should_fetch_through_a_postgres_cursor() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("FETCH FORWARD", vec![order(1), order(2)]);
+    client.enqueue_rows("FETCH FORWARD", vec![order(3)]);
+
+    block_on(async {
+        let q = Order::where_col(|o| o.status.equal("open"));
+        let mut cursor = q.cursor(&client).await.unwrap();
+        let first = cursor.fetch_next(2).await.unwrap();
+        let second = cursor.fetch_next(2).await.unwrap();
+        let third = cursor.fetch_next(2).await.unwrap();
+        cursor.close().await.unwrap();
+
+        assert_eq!(first.iter().map(|o| o.id).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(second.iter().map(|o| o.id).collect::<Vec<_>>(), [3]);
+        assert!(third.is_empty());
+    });
+
+    let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+    // cursor names are numbered across the whole process
+    let name = sql[1].split(' ').nth(1).unwrap();
+    assert_eq!(
+        sql,
+        [
+            "BEGIN".to_string(),
+            format!("DECLARE {name} NO SCROLL CURSOR FOR SELECT t1.\"id\", t1.\"status\" FROM orders t1 WHERE ( t1.status = $1 )"),
+            format!("FETCH FORWARD 2 FROM {name}"),
+            format!("FETCH FORWARD 2 FROM {name}"),
+            format!("CLOSE {name}"),
+            "COMMIT".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn should_not_open_cursors_on_mysql() {
+    let client = MockClient::new(Syntax::Mysql);
+    let err = block_on(Order::all().cursor(&client)).err().unwrap();
+    assert!(matches!(err, WeldsError::CursorsUnsupported(Syntax::Mysql)));
+}
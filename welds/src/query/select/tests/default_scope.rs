@@ -0,0 +1,89 @@
+use super::*;
+use crate::connections::mock::MockClient;
+use crate::query::clause::ClauseAdder;
+use crate::state::DbState;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "accounts")]
+#[welds(soft_delete = "deleted_at")]
+#[welds(default_scope(current_tenant))]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Account {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub tenant_id: i32,
+    pub name: String,
+    pub deleted_at: Option<String>,
+}
+
+fn current_tenant(a: AccountSchema) -> Box<dyn ClauseAdder> {
+    a.tenant_id.equal(7)
+}
+
+#[test]
+fn should_filter_every_query_by_the_default_scope() {
+    let q = Account::where_col(|a| a.id.gt(1));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."tenant_id", t1."name", t1."deleted_at" FROM accounts t1 WHERE ( t1.deleted_at IS NULL AND t1.tenant_id = $1 AND t1.id > $2 )"#
+    );
+}
+
+#[test]
+fn should_leave_out_the_default_scope_when_unscoped() {
+    let q = Account::where_col(|a| a.id.gt(1)).unscoped();
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."tenant_id", t1."name", t1."deleted_at" FROM accounts t1 WHERE ( t1.deleted_at IS NULL AND t1.id > $1 )"#
+    );
+}
+
+#[test]
+fn should_keep_track_of_the_scope_when_deleted_rows_are_included() {
+    let q = Account::all().with_deleted().unscoped();
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."tenant_id", t1."name", t1."deleted_at" FROM accounts t1"#
+    );
+    let q = Account::all().unscoped().only_deleted();
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."tenant_id", t1."name", t1."deleted_at" FROM accounts t1 WHERE ( t1.deleted_at IS NOT NULL )"#
+    );
+}
+
+#[test]
+fn should_scope_bulk_updates_and_deletes() {
+    let q = Account::all().with_deleted().force_delete();
+    assert_eq!(
+        q.delete_sql(Syntax::Postgres),
+        "DELETE FROM accounts WHERE ( accounts.tenant_id = $1 )"
+    );
+    let q = Account::all().set(|a| a.name, "renamed");
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"UPDATE accounts SET "name"=$1 WHERE ( accounts.deleted_at IS NULL AND accounts.tenant_id = $2 )"#
+    );
+}
+
+#[test]
+fn should_scope_the_update_and_delete_of_a_row() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let mut account = DbState::db_loaded(Account::default());
+        account.name = "renamed".to_owned();
+        account.save(&client).await.unwrap();
+        account.delete(&client).await.unwrap();
+        let queries = client.queries();
+        let sqls: Vec<&str> = queries.iter().map(|q| q.sql.as_str()).collect();
+        assert_eq!(
+            sqls,
+            [
+                r#"UPDATE accounts SET "tenant_id"=$1, "name"=$2, "deleted_at"=$3 where "id"=$4 AND accounts.tenant_id = $5"#,
+                r#"UPDATE accounts SET "deleted_at" = CURRENT_TIMESTAMP where "id"=$1 AND accounts.tenant_id = $2 AND "deleted_at" IS NULL RETURNING *"#,
+            ]
+        );
+    });
+}
@@ -0,0 +1,82 @@
+use super::*;
+use crate::WeldsModel;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub customer_id: i32,
+    pub created_at: i64,
+}
+
+#[test]
+fn should_select_distinct_rows() {
+    let q = Order::all().distinct();
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT DISTINCT t1.id, t1.customer_id, t1.created_at FROM orders t1"
+    );
+    let q = Order::all().select(|o| o.customer_id).distinct();
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT DISTINCT t1."customer_id" FROM orders t1"#
+    );
+}
+
+#[test]
+fn should_count_what_is_left_once_distinct() {
+    let q = Order::where_col(|o| o.id.gt(5)).distinct();
+    assert_eq!(
+        q.to_sql_count(Syntax::Postgres),
+        r#"SELECT CAST( COUNT(*) as BIGINT ) FROM ( SELECT DISTINCT t1."id", t1."customer_id", t1."created_at" FROM orders t1 WHERE ( t1.id > $1 ) ) welds_rows"#
+    );
+}
+
+#[test]
+fn should_sort_by_the_distinct_on_columns_first_on_postgres() {
+    let q = Order::all()
+        .distinct_on(|o| o.customer_id)
+        .order_by_desc(|o| o.created_at);
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT DISTINCT ON (t1."customer_id") t1."id", t1."customer_id", t1."created_at" FROM orders t1 ORDER BY customer_id ASC, created_at DESC"#
+    );
+}
+
+#[test]
+fn should_keep_an_order_that_already_starts_with_the_distinct_on_columns() {
+    let q = Order::all()
+        .distinct_on(|o| o.customer_id)
+        .order_by_desc(|o| o.customer_id)
+        .order_by_desc(|o| o.created_at);
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT DISTINCT ON (t1."customer_id") t1."id", t1."customer_id", t1."created_at" FROM orders t1 ORDER BY customer_id DESC, created_at DESC"#
+    );
+}
+
+#[test]
+fn should_emulate_distinct_on_with_a_row_number() {
+    let q = Order::where_col(|o| o.id.gt(5))
+        .distinct_on(|o| o.customer_id)
+        .order_by_desc(|o| o.created_at)
+        .limit(10);
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."id", t1."customer_id", t1."created_at" FROM ( SELECT t1."id", t1."customer_id", t1."created_at", ROW_NUMBER() OVER (PARTITION BY t1."customer_id" ORDER BY created_at DESC) AS welds_rn FROM orders t1 WHERE ( t1.id > @p1 ) ) t1 WHERE t1.welds_rn = 1 ORDER BY created_at DESC OFFSET 0 ROWS FETCH FIRST 10 ROWS ONLY"#
+    );
+}
+
+#[test]
+fn should_emulate_distinct_on_for_selected_columns() {
+    let q = Order::all()
+        .select(|o| o.customer_id)
+        .select(|o| o.created_at)
+        .distinct_on(|o| o.customer_id);
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT * FROM ( SELECT t1."customer_id", t1."created_at", ROW_NUMBER() OVER (PARTITION BY t1."customer_id" ORDER BY (SELECT NULL)) AS welds_rn FROM orders t1 ) t1 WHERE ( t1.welds_rn = 1 )"#
+    );
+}
@@ -0,0 +1,49 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub status: String,
+}
+
+#[test]
+fn should_select_exists_on_postgres() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("EXISTS", vec![MockRow::new().with("exists", true)]);
+    let found = block_on(Order::where_col(|o| o.status.equal("open")).exists(&client)).unwrap();
+    assert!(found);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "SELECT EXISTS ( SELECT 1 FROM orders t1 WHERE ( t1.status = $1 ) )"
+    );
+}
+
+#[test]
+fn should_select_a_case_when_exists_on_mssql() {
+    let client = MockClient::new(Syntax::Mssql);
+    client.enqueue_rows("EXISTS", vec![MockRow::new().with("found", 0_i64)]);
+    let found = block_on(Order::where_col(|o| o.id.gt(10)).exists(&client)).unwrap();
+    assert!(!found);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "SELECT CAST( CASE WHEN EXISTS ( SELECT 1 FROM orders t1 WHERE ( t1.id > @p1 ) ) THEN 1 ELSE 0 END as BIGINT )"
+    );
+}
+
+#[test]
+fn should_keep_the_offset_of_the_query() {
+    let client = MockClient::new(Syntax::Mysql);
+    client.enqueue_rows("EXISTS", vec![MockRow::new().with("found", 1_i64)]);
+    let q = Order::all().order_by_asc(|o| o.id).offset(100).limit(1);
+    assert!(block_on(q.exists(&client)).unwrap());
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "SELECT EXISTS ( SELECT 1 FROM orders t1 ORDER BY id ASC LIMIT 100, 1 )"
+    );
+}
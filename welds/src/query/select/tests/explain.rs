@@ -0,0 +1,100 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub customer_id: i32,
+}
+
+#[test]
+fn should_explain_the_query_with_its_params() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows(
+            "EXPLAIN",
+            vec![
+                MockRow::new().with("QUERY PLAN", "Index Scan using orders_pkey on orders t1"),
+                MockRow::new().with("QUERY PLAN", "  Index Cond: (id > 1)"),
+            ],
+        );
+        let plan = Order::where_col(|o| o.id.gt(1))
+            .explain(&client)
+            .await
+            .unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "Index Scan using orders_pkey on orders t1\n  Index Cond: (id > 1)"
+        );
+        assert_eq!(
+            client.last_sql().unwrap(),
+            r#"EXPLAIN SELECT t1."id", t1."customer_id" FROM orders t1 WHERE ( t1.id > $1 )"#
+        );
+        assert_eq!(client.queries().pop().unwrap().params.len(), 1);
+    });
+}
+
+#[test]
+fn should_split_a_mysql_tree_plan_into_lines() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Mysql);
+        let tree = "-> Filter: (t1.customer_id = 4)\n    -> Table scan on t1";
+        client.enqueue_rows("EXPLAIN", vec![MockRow::new().with("EXPLAIN", tree)]);
+        let q = Order::where_col(|o| o.customer_id.equal(4));
+        let plan = q.explain_analyze(&client).await.unwrap();
+        assert_eq!(plan.lines.len(), 2);
+        assert!(client
+            .last_sql()
+            .unwrap()
+            .starts_with("EXPLAIN ANALYZE SELECT t1.id"));
+    });
+}
+
+#[test]
+fn should_indent_sqlite_plans_by_their_parent() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Sqlite);
+        let step = |id: i64, parent: i64, detail: &str| {
+            MockRow::new()
+                .with("id", id)
+                .with("parent", parent)
+                .with("notused", 0_i64)
+                .with("detail", detail)
+        };
+        client.enqueue_rows(
+            "EXPLAIN QUERY PLAN",
+            vec![
+                step(2, 0, "SCAN t1"),
+                step(5, 0, "CORRELATED SCALAR SUBQUERY 1"),
+                step(8, 5, "SEARCH orders USING INTEGER PRIMARY KEY (rowid=?)"),
+            ],
+        );
+        let plan = Order::all().explain(&client).await.unwrap();
+        assert_eq!(
+            plan.lines,
+            vec![
+                "SCAN t1",
+                "CORRELATED SCALAR SUBQUERY 1",
+                "  SEARCH orders USING INTEGER PRIMARY KEY (rowid=?)",
+            ]
+        );
+    });
+}
+
+#[test]
+fn should_not_explain_analyze_on_sqlite() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Sqlite);
+        let err = Order::all().explain_analyze(&client).await.unwrap_err();
+        assert!(matches!(
+            err,
+            WeldsError::ExplainUnsupported(Syntax::Sqlite)
+        ));
+        assert!(client.queries().is_empty());
+    });
+}
@@ -0,0 +1,47 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::value::Value;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    #[welds(rename = "product_name")]
+    pub name: String,
+}
+
+#[test]
+fn should_read_rows_of_raw_sql_into_the_model() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows(
+            "ranked",
+            vec![MockRow::new()
+                .with("id", 2)
+                .with("product_name", "Pen")
+                .with("rank", 1_i64)],
+        );
+        let sql = "SELECT *, rank() OVER (ORDER BY id) AS rank FROM products ranked WHERE id > $1";
+        let found = Product::find_by_sql(&client, sql, &[&1]).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].id, found[0].name.as_str()), (2, "Pen"));
+
+        let query = client.queries().pop().unwrap();
+        assert_eq!(query.sql, sql);
+        assert_eq!(query.params, vec![Value::Int(1)]);
+    });
+}
+
+#[test]
+fn should_fail_when_raw_sql_is_missing_a_column() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("SELECT", vec![MockRow::new().with("id", 2)]);
+        let found = Product::find_by_sql(&client, "SELECT id FROM products", &[]).await;
+        assert!(found.is_err());
+    });
+}
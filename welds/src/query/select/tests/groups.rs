@@ -0,0 +1,66 @@
+use super::*;
+use crate::connections::value::Value;
+use crate::WeldsModel;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub status: String,
+    pub total: i32,
+}
+
+#[test]
+fn should_or_the_conditions_of_a_group() {
+    let q = Order::where_col(|o| o.total.gt(10)).where_any(|g| {
+        g.where_col(|o| o.status.equal("new"))
+            .where_col(|o| o.status.equal("retry"))
+    });
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        "SELECT t1.\"id\", t1.\"status\", t1.\"total\" FROM orders t1 WHERE ( t1.total > $1 AND ( t1.status = $2 OR t1.status = $3 ) )"
+    );
+}
+
+#[test]
+fn should_nest_groups() {
+    let q = QueryBuilder::<Order>::new().where_any(|g| {
+        g.where_col(|o| o.status.equal("new")).where_all(|g| {
+            g.where_col(|o| o.status.equal("retry"))
+                .where_col(|o| o.total.lt(3))
+        })
+    });
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        "SELECT t1.\"id\", t1.\"status\", t1.\"total\" FROM orders t1 WHERE ( ( t1.status = @p1 OR ( t1.status = @p2 AND t1.total < @p3 ) ) )"
+    );
+}
+
+#[test]
+fn should_bind_the_params_of_a_group_in_order() {
+    futures::executor::block_on(async move {
+        let client = crate::connections::mock::MockClient::new(Syntax::Postgres);
+        let q = Order::where_col(|o| o.total.gt(10)).where_any(|g| {
+            g.where_col(|o| o.status.equal("new"))
+                .where_col(|o| o.status.equal("retry"))
+        });
+        q.run(&client).await.unwrap();
+        let expected = vec![
+            Value::Int(10),
+            Value::Text("new".to_owned()),
+            Value::Text("retry".to_owned()),
+        ];
+        assert_eq!(client.queries()[0].params, expected);
+    });
+}
+
+#[test]
+fn should_skip_an_empty_group() {
+    let q = QueryBuilder::<Order>::new().where_any(|g| g);
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        "SELECT t1.\"id\", t1.\"status\", t1.\"total\" FROM orders t1"
+    );
+}
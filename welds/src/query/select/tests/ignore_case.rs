@@ -0,0 +1,65 @@
+use super::*;
+use crate::connections::mock::MockClient;
+use crate::connections::value::Value;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "users")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct User {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub email: String,
+    pub nickname: Option<String>,
+}
+
+#[test]
+fn should_use_ilike_on_postgres() {
+    let q = User::where_col(|u| u.email.ilike("%@EXAMPLE.com"));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."email", t1."nickname" FROM users t1 WHERE ( t1.email ilike $1 )"#
+    );
+}
+
+#[test]
+fn should_lowercase_both_sides_of_ilike_elsewhere() {
+    let q = User::where_col(|u| u.email.not_ilike("%@EXAMPLE.com"));
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."id", t1."email", t1."nickname" FROM users t1 WHERE ( LOWER(t1.email) not like LOWER(?) )"#
+    );
+    let q = User::where_col(|u| u.email.ilike("%@EXAMPLE.com"));
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."id", t1."email", t1."nickname" FROM users t1 WHERE ( LOWER(t1.email) like LOWER(@p1) )"#
+    );
+}
+
+#[test]
+fn should_compare_equal_ignoring_case() {
+    let client = MockClient::new(Syntax::Postgres);
+    let q = User::where_col(|u| u.email.eq_ignore_case("Bob@Example.com"));
+    block_on(q.run(&client)).unwrap();
+    assert_eq!(
+        client.last_sql().unwrap(),
+        r#"SELECT t1."id", t1."email", t1."nickname" FROM users t1 WHERE ( LOWER(t1.email) = LOWER($1) )"#
+    );
+    let expected = vec![Value::Text("Bob@Example.com".to_owned())];
+    assert_eq!(client.queries()[0].params, expected);
+}
+
+#[test]
+fn should_ignore_case_on_nullable_columns() {
+    let q = User::where_col(|u| u.nickname.not_eq_ignore_case("BOB"));
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id, t1.email, t1.nickname FROM users t1 WHERE ( LOWER(t1.nickname) != LOWER(?) )"
+    );
+    let q = User::where_col(|u| u.nickname.eq_ignore_case(None));
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id, t1.email, t1.nickname FROM users t1 WHERE ( t1.nickname IS NULL )"
+    );
+}
@@ -0,0 +1,71 @@
+use super::*;
+use crate::connections::mock::MockClient;
+use crate::connections::value::Value;
+use crate::query::clause::JoinColumn;
+use crate::query::select_cols::Join;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub ship_to: String,
+    pub region: Option<String>,
+    pub total: f64,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "stores")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Store {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub code: String,
+    pub region: String,
+    pub name: String,
+}
+
+#[test]
+fn should_join_without_a_relationship() {
+    let q = Order::all()
+        .select(|o| o.id)
+        .join_on(|o, s| o.ship_to.eq(s.code), Store::all().select(|s| s.name));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t2."name" FROM orders t1 JOIN stores t2 ON t1."ship_to" = t2."code""#
+    );
+}
+
+#[test]
+fn should_join_on_more_than_one_column() {
+    let q = Order::all().select(|o| o.id).left_join_on(
+        |o, s| o.ship_to.eq(s.code).and(o.region.eq(s.region)),
+        Store::all().select(|s| s.name),
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id, t2.name FROM orders t1 LEFT JOIN stores t2 ON t1.ship_to = t2.code AND t1.region = t2.region"
+    );
+}
+
+#[test]
+fn should_filter_on_both_sides_of_the_join() {
+    let client = MockClient::new(Syntax::Postgres);
+    let q = Order::where_col(|o| o.total.gt(10.0))
+        .select(|o| o.id)
+        .join_on_with(
+            |o, s| o.ship_to.eq(s.code),
+            Store::where_col(|s| s.region.equal("west")).select(|s| s.name),
+            Join::Right,
+        );
+    block_on(q.run(&client)).unwrap();
+    assert_eq!(
+        client.last_sql().unwrap(),
+        r#"SELECT t1."id", t2."name" FROM orders t1 RIGHT JOIN stores t2 ON t1."ship_to" = t2."code" WHERE ( t1.total > $1 AND t2.region = $2 )"#
+    );
+    let expected = vec![Value::Float(10.0), Value::Text("west".to_owned())];
+    assert_eq!(client.queries()[0].params, expected);
+}
@@ -0,0 +1,53 @@
+use super::*;
+use crate::WeldsModel;
+
+// Testing deep pagination that only reads the primary keys before joining back for the rows
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+fn ran_sql(syntax: Syntax, q: QueryBuilder<Product>) -> String {
+    futures::executor::block_on(async move {
+        let client = welds_connections::noop::build(syntax);
+        q.run(&client).await.unwrap();
+        client.last_sql()
+    })
+    .unwrap()
+}
+
+#[test]
+fn should_find_the_page_by_primary_key_first() {
+    let q = Product::where_col(|x| x.name.like("%a%"))
+        .order_by_desc(|x| x.name)
+        .limit(10)
+        .offset(5000)
+        .late_row_lookup();
+    assert_eq!(
+        ran_sql(Syntax::Mysql, q),
+        "SELECT t1.id, t1.name FROM products t1 INNER JOIN ( SELECT t1.id AS welds_key FROM products t1 WHERE ( t1.name like ? ) ORDER BY name DESC LIMIT 5000, 10 ) welds_keys ON t1.id = welds_keys.welds_key ORDER BY name DESC"
+    );
+}
+
+#[test]
+fn should_order_by_primary_key_when_no_order_is_given() {
+    let q = Product::all().limit(10).offset(20).late_row_lookup();
+    assert_eq!(
+        ran_sql(Syntax::Mssql, q),
+        "SELECT t1.\"id\", t1.\"name\" FROM products t1 INNER JOIN ( SELECT t1.\"id\" AS welds_key FROM products t1 ORDER BY 1 OFFSET 20 ROWS FETCH FIRST 10 ROWS ONLY ) welds_keys ON t1.\"id\" = welds_keys.welds_key ORDER BY t1.\"id\""
+    );
+}
+
+#[test]
+fn should_not_change_queries_without_an_offset() {
+    let q = Product::all().limit(10).late_row_lookup();
+    assert_eq!(
+        ran_sql(Syntax::Postgres, q),
+        "SELECT t1.\"id\", t1.\"name\" FROM products t1 ORDER BY 1 OFFSET 0 LIMIT 10"
+    );
+}
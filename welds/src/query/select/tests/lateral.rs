@@ -0,0 +1,73 @@
+use super::*;
+use crate::connections::mock::MockClient;
+use crate::connections::value::Value;
+use crate::errors::WeldsError;
+use crate::query::clause::JoinColumn;
+use crate::query::select_cols::SelectBuilder;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "users")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct User {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub total: f64,
+}
+
+fn last_orders() -> SelectBuilder<User> {
+    User::where_col(|u| u.name.like("a%"))
+        .select(|u| u.name)
+        .join_lateral(
+            |u, o| u.id.eq(o.user_id),
+            Order::where_col(|o| o.total.gt(5.0))
+                .select(|o| o.total)
+                .order_by_desc(|o| o.id)
+                .limit(3),
+        )
+}
+
+#[test]
+fn should_write_a_lateral_join() {
+    assert_eq!(
+        last_orders().to_sql(Syntax::Postgres),
+        r#"SELECT t1."name", t2."total" FROM users t1 LEFT JOIN LATERAL ( SELECT t2."total" FROM orders t2 WHERE ( t2.total > $1 AND t2.user_id = t1.id ) ORDER BY id DESC OFFSET 0 LIMIT 3 ) t2 ON true WHERE ( t1.name like $2 )"#
+    );
+}
+
+#[test]
+fn should_write_outer_apply_on_mssql() {
+    assert_eq!(
+        last_orders().to_sql(Syntax::Mssql),
+        r#"SELECT t1."name", t2."total" FROM users t1 OUTER APPLY ( SELECT t2."total" FROM orders t2 WHERE ( t2.total > @p1 AND t2.user_id = t1.id ) ORDER BY id DESC OFFSET 0 ROWS FETCH FIRST 3 ROWS ONLY ) t2 WHERE ( t1.name like @p2 )"#
+    );
+}
+
+#[test]
+fn should_bind_the_lateral_params_first() {
+    let client = MockClient::new(Syntax::Mysql);
+    block_on(last_orders().run(&client)).unwrap();
+    let expected = vec![Value::Float(5.0), Value::Text("a%".to_owned())];
+    assert_eq!(client.queries()[0].params, expected);
+}
+
+#[test]
+fn should_not_run_a_lateral_join_on_sqlite() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let result = block_on(last_orders().run(&client));
+    assert!(matches!(
+        result,
+        Err(WeldsError::LateralUnsupported(Syntax::Sqlite))
+    ));
+}
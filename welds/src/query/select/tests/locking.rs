@@ -0,0 +1,60 @@
+use super::*;
+use crate::WeldsModel;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "jobs")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Job {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub status: String,
+}
+
+fn pending() -> QueryBuilder<Job> {
+    Job::where_col(|j| j.status.equal("pending"))
+        .order_by_asc(|j| j.id)
+        .limit(10)
+}
+
+#[test]
+fn should_lock_the_rows_for_update() {
+    assert_eq!(
+        pending().lock_for_update().to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."status" FROM jobs t1 WHERE ( t1.status = $1 ) ORDER BY id ASC OFFSET 0 LIMIT 10 FOR UPDATE"#
+    );
+}
+
+#[test]
+fn should_skip_locked_rows() {
+    assert_eq!(
+        pending().skip_locked().to_sql(Syntax::Mysql),
+        "SELECT t1.id, t1.status FROM jobs t1 WHERE ( t1.status = ? ) ORDER BY id ASC LIMIT 0, 10 FOR UPDATE SKIP LOCKED"
+    );
+}
+
+#[test]
+fn should_not_wait_for_locked_rows() {
+    assert_eq!(
+        Job::all().no_wait().to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."status" FROM jobs t1 FOR UPDATE NOWAIT"#
+    );
+}
+
+#[test]
+fn should_use_table_hints_on_mssql() {
+    assert_eq!(
+        pending()
+            .with_nolock_dirty_reads()
+            .skip_locked()
+            .to_sql(Syntax::Mssql),
+        r#"SELECT t1."id", t1."status" FROM jobs t1 WITH (UPDLOCK, ROWLOCK, READPAST) WHERE ( t1.status = @p1 ) ORDER BY id ASC OFFSET 0 ROWS FETCH FIRST 10 ROWS ONLY"#
+    );
+}
+
+#[test]
+fn should_ignore_locks_on_sqlite() {
+    assert_eq!(
+        Job::all().lock_for_update().to_sql(Syntax::Sqlite),
+        r#"SELECT t1."id", t1."status" FROM jobs t1"#
+    );
+}
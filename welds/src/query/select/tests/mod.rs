@@ -1,5 +1,31 @@
 use super::*;
+mod aggregate;
+mod approx_count_distinct;
 mod basic;
 mod basicopt;
+mod compare_columns;
+mod cursor;
+mod default_scope;
+mod distinct;
+mod exists;
+mod explain;
+mod find_by_sql;
+mod groups;
+mod ignore_case;
+mod join_on;
+mod late_row_lookup;
+mod lateral;
+mod locking;
+mod namespace;
+mod nolock;
+mod page;
+mod projection;
+mod random;
 mod relationships;
+mod scopes;
+mod select_only;
+mod self_join;
+mod soft_delete;
 mod sql_tails;
+mod subquery;
+mod where_raw;
@@ -0,0 +1,157 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::value::Value;
+use crate::connections::TransactStart;
+use crate::state::DbState;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "customers")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(HasMany(orders, Order, "customer_id"))]
+struct Customer {
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsTo(customer, Customer, "customer_id"))]
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub customer_id: i32,
+    pub total: i32,
+}
+
+#[test]
+fn should_read_the_tables_of_the_schema() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres)
+            .with_schema("tenant_42")
+            .unwrap();
+        let q = Customer::where_col(|c| c.id.equal(1)).map_query(|c| c.orders);
+        q.run(&client).await.unwrap();
+        assert_eq!(
+            client.last_sql().unwrap(),
+            r#"SELECT t2."id", t2."customer_id", t2."total" FROM tenant_42.orders t2 WHERE ( EXISTS ( SELECT id FROM tenant_42.customers t1 WHERE t1.id = $1 AND t1.id = t2.customer_id ) )"#
+        );
+    });
+}
+
+#[test]
+fn should_write_the_tables_of_the_schema() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres)
+            .with_schema("tenant_42")
+            .unwrap();
+        let row = MockRow::new()
+            .with("id", 1)
+            .with("customer_id", 2)
+            .with("total", 10);
+        client.enqueue_rows("INSERT", vec![row]);
+        let transaction = client.begin().await.unwrap();
+        let mut order = DbState::new_uncreated(Order {
+            customer_id: 2,
+            total: 10,
+            ..Default::default()
+        });
+        order.save(&transaction).await.unwrap();
+        order.total = 12;
+        order.save(&transaction).await.unwrap();
+        order.delete(&transaction).await.unwrap();
+        transaction.commit().await.unwrap();
+
+        let queries = client.queries();
+        let sql: Vec<&str> = queries.iter().map(|q| q.sql.as_str()).collect();
+        assert_eq!(
+            sql,
+            [
+                "BEGIN",
+                r#"INSERT INTO tenant_42.orders ("customer_id", "total") VALUES ($1, $2) RETURNING *"#,
+                r#"UPDATE tenant_42.orders SET "customer_id"=$1, "total"=$2 where "id"=$3"#,
+                r#"DELETE FROM tenant_42.orders where "id"=$1"#,
+                "COMMIT",
+            ]
+        );
+    });
+}
+
+#[test]
+fn should_rename_the_tables_and_their_columns_with_a_prefix() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres)
+            .with_table_prefix("t42_")
+            .unwrap();
+        Order::where_col(|o| o.total.gt(1))
+            .set(|o| o.total, 0)
+            .run(&client)
+            .await
+            .unwrap();
+        assert_eq!(
+            client.last_sql().unwrap(),
+            r#"UPDATE t42_orders SET "total"=$1 WHERE ( t42_orders.total > $2 )"#
+        );
+    });
+}
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(schema = "audit", table = "events")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Event {
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+#[test]
+fn should_read_pg_stats_as_written() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres)
+            .with_schema("tenant_42")
+            .unwrap();
+        let stats = vec![MockRow::new().with("count", 1200_i64)];
+        client.enqueue_rows("pg_stats", stats);
+        let found = Order::all()
+            .approx_count_distinct(|o| o.customer_id, &client)
+            .await
+            .unwrap();
+        assert_eq!(found, 1200);
+        let queries = client.queries();
+        assert!(queries[0]
+            .sql
+            .contains(" FROM pg_stats s JOIN pg_namespace n "));
+        assert_eq!(
+            queries[0].params,
+            [
+                Value::Text("tenant_42".to_owned()),
+                Value::Text("orders".to_owned()),
+                Value::Text("customer_id".to_owned()),
+            ]
+        );
+    });
+}
+
+#[test]
+fn should_prefix_the_tables_of_a_model_with_a_schema() {
+    block_on(async {
+        let schema = MockClient::new(Syntax::Postgres)
+            .with_schema("tenant_42")
+            .unwrap();
+        Event::all().run(&schema).await.unwrap();
+        assert_eq!(
+            schema.last_sql().unwrap(),
+            r#"SELECT t1."id" FROM audit.events t1"#
+        );
+
+        let prefix = MockClient::new(Syntax::Postgres)
+            .with_table_prefix("t42_")
+            .unwrap();
+        Event::all().run(&prefix).await.unwrap();
+        assert_eq!(
+            prefix.last_sql().unwrap(),
+            r#"SELECT t1."id" FROM audit.t42_events t1"#
+        );
+    });
+}
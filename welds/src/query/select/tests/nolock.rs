@@ -0,0 +1,33 @@
+use super::*;
+use crate::WeldsModel;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub total: f64,
+}
+
+#[test]
+fn should_read_with_nolock_on_mssql() {
+    let q = Order::where_col(|x| x.total.gt(100.0)).with_nolock_dirty_reads();
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        "SELECT t1.\"id\", t1.\"total\" FROM orders t1 WITH (NOLOCK) WHERE ( t1.total > @p1 )"
+    );
+    assert_eq!(
+        q.to_sql_count(Syntax::Mssql),
+        "SELECT CAST( COUNT(*) as BIGINT ) FROM orders t1 WITH (NOLOCK) WHERE ( t1.total > @p1 )"
+    );
+}
+
+#[test]
+fn should_ignore_nolock_on_other_databases() {
+    let q = Order::all().with_nolock_dirty_reads();
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        "SELECT t1.\"id\", t1.\"total\" FROM orders t1"
+    );
+}
@@ -0,0 +1,57 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+fn product(id: i32) -> MockRow {
+    MockRow::new()
+        .with("id", id)
+        .with("name", format!("p{}", id))
+}
+
+#[test]
+fn should_count_and_fetch_the_page_together() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows("COUNT", vec![MockRow::new().with("count", 23_i64)]);
+    client.enqueue_rows("products", vec![product(11), product(12)]);
+
+    let q = Product::where_col(|p| p.id.gt(0)).order_by_asc(|p| p.id);
+    let page = block_on(q.paginate(2, 10, &client)).unwrap();
+    assert_eq!(page.total, 23);
+    assert_eq!(page.total_pages, 3);
+    assert_eq!(page.items.len(), 2);
+    assert!(page.has_next());
+    assert!(page.has_prev());
+
+    let queries = client.queries();
+    assert_eq!(
+        queries[0].sql,
+        r#"SELECT CAST( COUNT(t1.*) as BIGINT ) FROM products t1 WHERE ( t1.id > $1 )"#
+    );
+    assert_eq!(
+        queries[1].sql,
+        r#"SELECT t1."id", t1."name" FROM products t1 WHERE ( t1.id > $1 ) ORDER BY id ASC OFFSET 10 LIMIT 10"#
+    );
+}
+
+#[test]
+fn should_treat_page_zero_as_the_first() {
+    let client = MockClient::new(Syntax::Mysql);
+    client.enqueue_rows("COUNT", vec![MockRow::new().with("count", 0_i64)]);
+
+    let page = block_on(Product::all().paginate(0, 10, &client)).unwrap();
+    assert_eq!(page.page, 1);
+    assert_eq!(page.total_pages, 0);
+    assert!(!page.has_next());
+    assert!(!page.has_prev());
+    assert!(client.last_sql().unwrap().ends_with("LIMIT 0, 10"));
+}
@@ -0,0 +1,94 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::query::group_by::{count, sum};
+use crate::{WeldsModel, WeldsProjection};
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "users")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(HasMany(orders, Order, "user_id"))]
+struct User {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsTo(user, User, "user_id"))]
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub total: f64,
+}
+
+#[derive(Debug, WeldsProjection)]
+#[welds_path(crate)] // needed only within the welds crate.
+struct UserSummary {
+    name: String,
+    order_count: i64,
+    #[welds(rename = "spent")]
+    total: f64,
+}
+
+#[test]
+fn should_select_a_column_as_another_name() {
+    let q = User::all().select_as(|u| u.name, "user_name");
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."name" as "user_name" FROM users t1"#
+    );
+}
+
+#[test]
+fn should_group_by_the_plain_columns_once_aggregated() {
+    let q = User::where_col(|u| u.id.gt(10))
+        .select(|u| u.name)
+        .left_join(
+            |u| u.orders,
+            Order::all()
+                .select_agg(|o| count(o.id).alias("order_count"))
+                .select_agg(|o| sum(o.total).alias("spent")),
+        );
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.name, COUNT(t2.id) AS order_count, SUM(t2.total) AS spent FROM users t1 LEFT JOIN orders t2 ON t1.id = t2.user_id WHERE ( t1.id > ? ) GROUP BY t1.name"
+    );
+}
+
+#[test]
+fn should_not_group_without_an_aggregate() {
+    let q = User::all()
+        .select(|u| u.name)
+        .join(|u| u.orders, Order::all().select(|o| o.total));
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."name", t2."total" FROM users t1 JOIN orders t2 ON t1."id" = t2."user_id""#
+    );
+}
+
+#[test]
+fn should_read_the_rows_into_a_projection() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows(
+        "GROUP BY",
+        vec![MockRow::new()
+            .with("name", "ada")
+            .with("order_count", 3_i64)
+            .with("spent", 12.5)],
+    );
+    let q = User::all().select(|u| u.name).left_join(
+        |u| u.orders,
+        Order::all()
+            .select_agg(|o| count(o.id).alias("order_count"))
+            .select_agg(|o| sum(o.total).alias("spent")),
+    );
+    let summaries: Vec<UserSummary> = block_on(q.run_into(&client)).unwrap();
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].name, "ada");
+    assert_eq!(summaries[0].order_count, 3);
+    assert_eq!(summaries[0].total, 12.5);
+}
@@ -0,0 +1,62 @@
+use super::*;
+use crate::WeldsModel;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "entries")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Entry {
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+#[test]
+fn should_order_randomly() {
+    let q = Entry::all().order_by_random();
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id" FROM entries t1 ORDER BY random()"#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id FROM entries t1 ORDER BY RAND()"
+    );
+}
+
+#[test]
+fn should_sample_a_number_of_rows() {
+    let q = Entry::all().sample(3);
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."id" FROM entries t1 ORDER BY NEWID() OFFSET 0 ROWS FETCH FIRST 3 ROWS ONLY"#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."id" FROM entries t1 ORDER BY random() LIMIT 3 OFFSET 0 "#
+    );
+}
+
+#[test]
+fn should_sample_a_percent_with_tablesample() {
+    let q = Entry::where_col(|e| e.id.gt(1)).sample_percent(10.0);
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id" FROM entries t1 TABLESAMPLE BERNOULLI (10) WHERE ( t1.id > $1 )"#
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."id" FROM entries t1 TABLESAMPLE (10 PERCENT) WHERE ( t1.id > @p1 )"#
+    );
+}
+
+#[test]
+fn should_sample_a_percent_by_chance_without_tablesample() {
+    let q = Entry::all().sample_percent(2.5);
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id FROM (SELECT * FROM entries WHERE RAND() < 0.025) t1"
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."id" FROM (SELECT * FROM entries WHERE (ABS(RANDOM()) % 1000000) < 25000) t1"#
+    );
+}
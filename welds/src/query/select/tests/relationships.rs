@@ -51,3 +51,199 @@ fn should_be_able_to_query_with_sub_queries() {
         assert_eq!(expected, &ran_sql);
     });
 }
+
+#[test]
+fn should_filter_on_related_rows_existing() {
+    let orders = QueryBuilder::<Order>::new().where_col(|o| o.oid.gt(10));
+    let q = QueryBuilder::<Product>::new().where_exists(|p| p.orders, orders);
+    let expected = r#"SELECT t1.pid FROM product t1 WHERE ( EXISTS ( SELECT 1 FROM order t2 WHERE t2.oid > ? AND t2.p_fk_id = t1.pid ) )"#;
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[test]
+fn should_filter_on_related_rows_not_existing() {
+    let q = QueryBuilder::<Product>::new()
+        .where_col(|p| p.pid.gt(1))
+        .where_not_exists(|p| p.orders, QueryBuilder::<Order>::new().limit(1));
+    let expected = r#"SELECT t1."pid" FROM product t1 WHERE ( t1.pid > $1 AND NOT EXISTS ( SELECT 1 FROM order t2 WHERE t2.p_fk_id = t1.pid ) )"#;
+    assert_eq!(expected, q.to_sql(Syntax::Postgres));
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(HasOne(profile, Profile, "user_id"))]
+struct User {
+    #[welds(primary_key)]
+    pub id: i64,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsTo(user, User, "user_id"))]
+struct Profile {
+    #[welds(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+}
+
+#[test]
+fn should_filter_on_a_has_one_relation() {
+    let profiles = QueryBuilder::<Profile>::new().where_col(|p| p.id.gt(5));
+    let q = QueryBuilder::<User>::new().where_relation(|u| u.profile, profiles);
+    let expected = r#"SELECT t1.id FROM user t1 WHERE ( EXISTS ( SELECT user_id FROM profile t2 WHERE t2.id > ? AND t2.user_id = t1.id ) )"#;
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[test]
+fn should_select_through_a_has_one_relation() {
+    let q = QueryBuilder::<User>::new()
+        .where_col(|u| u.id.equal(1))
+        .map_query(|u| u.profile);
+    let expected = r#"SELECT t2.id, t2.user_id FROM profile t2 WHERE ( EXISTS ( SELECT id FROM user t1 WHERE t1.id = ? AND t1.id = t2.user_id ) )"#;
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(table = "users")]
+#[welds(BelongsToMany(roles, Role, through = "roles_users"))]
+struct Member {
+    #[welds(primary_key)]
+    pub id: i64,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(table = "roles")]
+#[welds(BelongsToMany(
+    members,
+    Member,
+    through = "roles_users",
+    foreign_key = "role_id",
+    related_key = "user_id"
+))]
+struct Role {
+    #[welds(primary_key)]
+    pub id: i64,
+    pub name: String,
+}
+
+#[test]
+fn should_filter_through_a_join_table() {
+    let roles = QueryBuilder::<Role>::new().where_col(|r| r.name.equal("admin"));
+    let q = QueryBuilder::<Member>::new().where_relation(|m| m.roles, roles);
+    let expected = "SELECT t1.id FROM users t1 WHERE ( EXISTS ( SELECT member_id FROM roles_users t2 WHERE t2.member_id = t1.id AND EXISTS ( SELECT id FROM roles t3 WHERE t3.name = ? AND t3.id = t2.role_id ) ) )";
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[test]
+fn should_test_for_rows_through_a_join_table() {
+    let q = QueryBuilder::<Member>::new().where_not_exists(|m| m.roles, QueryBuilder::new());
+    let expected = "SELECT t1.id FROM users t1 WHERE ( NOT EXISTS ( SELECT 1 FROM roles_users t2 WHERE t2.member_id = t1.id AND EXISTS ( SELECT 1 FROM roles t3 WHERE t3.id = t2.role_id ) ) )";
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[test]
+fn should_select_through_a_join_table() {
+    let q = QueryBuilder::<Role>::new()
+        .where_col(|r| r.id.equal(1))
+        .map_query(|r| r.members);
+    let expected = "SELECT t2.id FROM users t2 WHERE ( EXISTS ( SELECT user_id FROM roles_users t3 WHERE t3.user_id = t2.id AND EXISTS ( SELECT id FROM roles t1 WHERE t1.id = ? AND t1.id = t3.role_id ) ) )";
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[test]
+fn should_join_through_a_join_table() {
+    let q = Member::all()
+        .select(|m| m.id)
+        .join(|m| m.roles, Role::all().select(|r| r.name));
+    let expected = "SELECT t1.id, t2.name FROM users t1 JOIN roles_users t3 ON t1.id = t3.member_id JOIN roles t2 ON t3.role_id = t2.id";
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(table = "categories")]
+#[welds(BelongsTo(parent, Self, "parent_id"))]
+#[welds(HasMany(children, Self, "parent_id"))]
+struct Category {
+    #[welds(primary_key)]
+    pub id: i64,
+    pub parent_id: Option<i64>,
+}
+
+#[test]
+fn should_filter_on_a_relation_to_the_same_table() {
+    let roots = QueryBuilder::<Category>::new().where_col(|c| c.parent_id.equal(None));
+    let parents = QueryBuilder::<Category>::new().where_relation(|c| c.parent, roots);
+    let q = QueryBuilder::<Category>::new().where_relation(|c| c.parent, parents);
+    let expected = "SELECT t1.id, t1.parent_id FROM categories t1 WHERE ( EXISTS ( SELECT id FROM categories t2 WHERE t2.id = t1.parent_id AND EXISTS ( SELECT id FROM categories t3 WHERE t3.parent_id IS NULL AND t3.id = t2.parent_id ) ) )";
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[test]
+fn should_select_the_children_of_a_row() {
+    let q = QueryBuilder::<Category>::new()
+        .where_col(|c| c.id.equal(1))
+        .map_query(|c| c.children);
+    let expected = "SELECT t2.id, t2.parent_id FROM categories t2 WHERE ( EXISTS ( SELECT id FROM categories t1 WHERE t1.id = ? AND t1.id = t2.parent_id ) )";
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[test]
+fn should_join_a_row_to_its_parent() {
+    let q = Category::all()
+        .select(|c| c.id)
+        .join(|c| c.parent, Category::all().select_as(|c| c.id, "parent"));
+    let expected = "SELECT t1.id, t2.id as parent FROM categories t1 JOIN categories t2 ON t1.parent_id = t2.id";
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(table = "orders")]
+#[welds(HasMany(items, Item, "tenant_id", "order_id"))]
+struct TenantOrder {
+    #[welds(primary_key)]
+    pub id: i64,
+    pub tenant_id: i64,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(table = "items")]
+#[welds(BelongsTo(order, TenantOrder, "tenant_id", "order_id"))]
+struct Item {
+    #[welds(primary_key)]
+    pub id: i64,
+    pub tenant_id: i64,
+    pub order_id: i64,
+}
+
+#[test]
+fn should_filter_on_a_key_over_several_columns() {
+    let orders = QueryBuilder::<TenantOrder>::new().where_col(|o| o.id.gt(5));
+    let q = QueryBuilder::<Item>::new().where_relation(|i| i.order, orders);
+    let expected = "SELECT t1.id, t1.tenant_id, t1.order_id FROM items t1 WHERE ( EXISTS ( SELECT id FROM orders t2 WHERE t2.id > ? AND t2.id = t1.order_id AND t2.tenant_id = t1.tenant_id ) )";
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
+
+#[test]
+fn should_select_through_a_limited_key_over_several_columns() {
+    let q = QueryBuilder::<TenantOrder>::new()
+        .limit(2)
+        .map_query(|o| o.items);
+    let expected = r#"SELECT t2."id", t2."tenant_id", t2."order_id" FROM items t2 WHERE (  (t2.order_id, t2.tenant_id) IN ( SELECT t1.id, t1.tenant_id FROM orders t1  ORDER BY 1 OFFSET 0 LIMIT 2 )  )"#;
+    assert_eq!(expected, q.to_sql(Syntax::Postgres));
+    let expected = r#"SELECT t2."id", t2."tenant_id", t2."order_id" FROM items t2 WHERE (  EXISTS ( SELECT 1 FROM ( SELECT t1.id, t1.tenant_id FROM orders t1  ORDER BY 1 OFFSET 0 ROWS FETCH FIRST 2 ROWS ONLY ) welds_in WHERE welds_in.id = t2.order_id AND welds_in.tenant_id = t2.tenant_id )  )"#;
+    assert_eq!(expected, q.to_sql(Syntax::Mssql));
+}
+
+#[test]
+fn should_join_on_a_key_over_several_columns() {
+    let q = Item::all()
+        .select(|i| i.id)
+        .join(|i| i.order, TenantOrder::all().select_as(|o| o.id, "oid"));
+    let expected = "SELECT t1.id, t2.id as oid FROM items t1 JOIN orders t2 ON t1.order_id = t2.id AND t1.tenant_id = t2.tenant_id";
+    assert_eq!(expected, q.to_sql(Syntax::Mysql));
+}
@@ -0,0 +1,41 @@
+use super::*;
+use crate::WeldsModel;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "users")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(scopes(active, admins))]
+struct User {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub active: bool,
+    pub role: String,
+}
+
+impl UserScopes {
+    fn active(q: QueryBuilder<User>) -> QueryBuilder<User> {
+        q.where_col(|u| u.active.equal(true))
+    }
+
+    fn admins(q: QueryBuilder<User>) -> QueryBuilder<User> {
+        q.where_col(|u| u.role.equal("admin"))
+    }
+}
+
+#[test]
+fn should_chain_the_scopes_of_a_model() {
+    let q = User::all().active().admins().limit(5);
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."active", t1."role" FROM users t1 WHERE ( t1.active = $1 AND t1.role = $2 ) ORDER BY 1 OFFSET 0 LIMIT 5"#
+    );
+}
+
+#[test]
+fn should_combine_scopes_with_other_filters() {
+    let q = User::where_col(|u| u.id.gt(10)).admins();
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."active", t1."role" FROM users t1 WHERE ( t1.id > $1 AND t1.role = $2 )"#
+    );
+}
@@ -0,0 +1,61 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(schema = "app", table = "documents")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Document {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub title: String,
+    #[welds(rename = "body_bytes")]
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(partial_of(Document))]
+#[welds_path(crate)] // needed only within the welds crate.
+struct DocumentTitle {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub title: String,
+}
+
+#[test]
+fn should_read_only_the_columns_of_the_partial() {
+    let q = Document::where_col(|d| d.id.gt(5))
+        .order_by_desc(|d| d.id)
+        .limit(10)
+        .select_only::<DocumentTitle>();
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id, t1.title FROM app.documents t1 WHERE ( t1.id > ? ) ORDER BY id DESC LIMIT 0, 10"
+    );
+}
+
+#[test]
+fn should_keep_filtering_on_the_partial() {
+    let q = Document::all()
+        .select_only::<DocumentTitle>()
+        .where_col(|d| d.title.like("%draft%"));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."title" FROM app.documents t1 WHERE ( t1.title like $1 )"#
+    );
+}
+
+#[test]
+fn should_decode_the_rows_into_the_partial() {
+    let client = MockClient::new(Syntax::Sqlite);
+    client.enqueue_rows(
+        "documents",
+        vec![MockRow::new().with("id", 3).with("title", "notes")],
+    );
+    let q = Document::all().select_only::<DocumentTitle>();
+    let titles = block_on(q.run(&client)).unwrap();
+    assert_eq!(titles.len(), 1);
+    assert_eq!(titles[0].title, "notes");
+    assert!(!client.last_sql().unwrap().contains("body_bytes"));
+}
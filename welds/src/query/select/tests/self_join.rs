@@ -0,0 +1,70 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "employees")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsTo(manager, Employee, "manager_id"))]
+#[welds(HasMany(reports, Employee, "manager_id"))]
+struct Employee {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub manager_id: Option<i32>,
+    pub name: String,
+}
+
+#[test]
+fn should_alias_each_side_of_a_self_join() {
+    let q = Employee::where_col(|e| e.name.like("a%"))
+        .select(|e| e.id)
+        .join(
+            |e| e.manager,
+            Employee::where_col(|m| m.name.equal("Ann")).select_as(|m| m.name, "manager"),
+        );
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t2."name" as "manager" FROM employees t1 JOIN employees t2 ON t1."manager_id" = t2."id" WHERE ( t1.name like $1 AND t2.name = $2 )"#
+    );
+}
+
+#[test]
+fn should_prefix_a_joined_column_that_is_already_selected() {
+    let q = Employee::all().select(|e| e.name).join(
+        |e| e.manager,
+        Employee::all()
+            .select(|m| m.name)
+            .join(|m| m.manager, Employee::all().select(|b| b.name)),
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.name, t2.name as t2_name, t3.name as t3_name FROM employees t1 JOIN employees t2 ON t1.manager_id = t2.id JOIN employees t3 ON t2.manager_id = t3.id"
+    );
+}
+
+#[test]
+fn should_read_both_sides_of_a_self_join() {
+    let client = MockClient::new(Syntax::Sqlite);
+    client.enqueue_rows(
+        "JOIN",
+        vec![MockRow::new().with("name", "Bob").with("t2_name", "Ann")],
+    );
+    let q = Employee::all()
+        .select(|e| e.name)
+        .join(|e| e.manager, Employee::all().select(|m| m.name));
+    let rows = block_on(q.run(&client)).unwrap();
+    let name: String = rows[0].get("name").unwrap();
+    let manager: String = rows[0].get("t2_name").unwrap();
+    assert_eq!((name.as_str(), manager.as_str()), ("Bob", "Ann"));
+}
+
+#[test]
+fn should_filter_on_a_related_row_of_the_same_table() {
+    let q =
+        Employee::all().where_exists(|e| e.reports, Employee::where_col(|r| r.name.equal("Bob")));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."manager_id", t1."name" FROM employees t1 WHERE ( EXISTS ( SELECT 1 FROM employees t2 WHERE t2.name = $1 AND t2.manager_id = t1.id ) )"#
+    );
+}
@@ -0,0 +1,140 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::state::{DbState, DbStatus};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "posts")]
+#[welds(soft_delete = "deleted_at")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Post {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub title: String,
+    pub deleted_at: Option<String>,
+}
+
+#[test]
+fn should_leave_out_soft_deleted_rows() {
+    let q = Post::where_col(|p| p.id.gt(1));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        "SELECT t1.\"id\", t1.\"title\", t1.\"deleted_at\" FROM posts t1 WHERE ( t1.deleted_at IS NULL AND t1.id > $1 )"
+    );
+}
+
+#[test]
+fn should_include_soft_deleted_rows_with_deleted() {
+    let q = Post::where_col(|p| p.id.gt(1)).with_deleted();
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        "SELECT t1.\"id\", t1.\"title\", t1.\"deleted_at\" FROM posts t1 WHERE ( t1.id > ? )"
+    );
+}
+
+#[test]
+fn should_select_only_soft_deleted_rows() {
+    let q = Post::all().with_deleted().only_deleted();
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        "SELECT t1.\"id\", t1.\"title\", t1.\"deleted_at\" FROM posts t1 WHERE ( t1.deleted_at IS NOT NULL )"
+    );
+}
+
+#[test]
+fn should_set_the_timestamp_instead_of_deleting() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        client.enqueue_rows("RETURNING", vec![deleted_row("2024-01-01")]);
+        let mut post = DbState::db_loaded(Post::default());
+        post.delete(&client).await.unwrap();
+        assert_eq!(
+            client.last_sql().unwrap(),
+            "UPDATE posts SET \"deleted_at\" = CURRENT_TIMESTAMP where \"id\"=$1 AND \"deleted_at\" IS NULL RETURNING *"
+        );
+        // still in the database, with the time it was deleted
+        assert_eq!(post.deleted_at.as_deref(), Some("2024-01-01"));
+        assert_eq!(post.db_status(), DbStatus::NotModified);
+    });
+}
+
+#[test]
+fn should_read_back_the_timestamp_of_a_soft_delete_on_mariadb() {
+    block_on(async {
+        let client = MockClient::new(Syntax::MariaDb);
+        client.enqueue_rows_affected("UPDATE", 1);
+        client.enqueue_rows("SELECT", vec![deleted_row("2024-01-01")]);
+        let mut post = DbState::db_loaded(Post::default());
+        post.delete(&client).await.unwrap();
+        let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+        assert_eq!(
+            sql,
+            vec![
+                "UPDATE posts SET deleted_at = CURRENT_TIMESTAMP where id=? AND deleted_at IS NULL",
+                "SELECT * FROM posts where id=?",
+            ]
+        );
+        assert_eq!(post.deleted_at.as_deref(), Some("2024-01-01"));
+    });
+}
+
+#[test]
+fn should_not_mark_a_row_that_was_already_deleted() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Mysql);
+        let mut post = DbState::db_loaded(Post::default());
+        post.delete(&client).await.unwrap();
+        assert_eq!(client.queries().len(), 1);
+        assert_eq!(post.deleted_at, None);
+    });
+}
+
+fn deleted_row(at: &str) -> MockRow {
+    MockRow::new()
+        .with("id", 0)
+        .with("title", "")
+        .with("deleted_at", at)
+}
+
+#[test]
+fn should_restore_a_soft_deleted_row() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let mut post = Post {
+            id: 3,
+            title: "hi".to_owned(),
+            deleted_at: Some("2024-01-01".to_owned()),
+        };
+        post.restore(&client).await.unwrap();
+        assert_eq!(post.deleted_at, None);
+        assert_eq!(
+            client.last_sql().unwrap(),
+            "UPDATE posts SET \"deleted_at\"=NULL WHERE ( posts.id = $1 )"
+        );
+    });
+}
+
+#[test]
+fn should_mark_the_rows_of_a_bulk_delete_deleted() {
+    assert_eq!(
+        Post::all().delete_sql(Syntax::Postgres),
+        "UPDATE posts SET \"deleted_at\" = CURRENT_TIMESTAMP WHERE ( posts.deleted_at IS NULL )"
+    );
+}
+
+#[test]
+fn should_remove_the_rows_of_a_forced_delete() {
+    let q = Post::all().with_deleted().force_delete();
+    assert_eq!(q.delete_sql(Syntax::Postgres), "DELETE FROM posts");
+}
+
+#[test]
+fn should_output_the_rows_marked_deleted_on_mssql() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Mssql);
+        let q = Post::where_col(|p| p.id.gt(1));
+        q.delete_returning(&client).await.unwrap();
+        assert_eq!(client.last_sql().unwrap(), "UPDATE posts SET \"deleted_at\" = CURRENT_TIMESTAMP OUTPUT Inserted.\"id\", Inserted.\"title\", Inserted.\"deleted_at\" WHERE ( posts.deleted_at IS NULL AND posts.id > @p1 )");
+    });
+}
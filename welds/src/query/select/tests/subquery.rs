@@ -0,0 +1,88 @@
+use super::*;
+use crate::connections::mock::MockClient;
+use crate::connections::value::Value;
+use crate::query::builder::ManualWhereParam;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "customers")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Customer {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub region: String,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub customer_id: i32,
+    pub region: String,
+}
+
+#[test]
+fn should_write_an_in_subselect() {
+    let q = Order::where_col(|o| o.id.gt(5)).where_col(|o| {
+        o.customer_id
+            .in_query(Customer::where_col(|c| c.region.equal("west")).select(|c| c.id))
+    });
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        "SELECT t1.\"id\", t1.\"customer_id\", t1.\"region\" FROM orders t1 WHERE ( t1.id > $1 AND t1.customer_id IN (SELECT s1.\"id\" FROM customers s1 WHERE ( s1.region = $2 )) )"
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        "SELECT t1.\"id\", t1.\"customer_id\", t1.\"region\" FROM orders t1 WHERE ( t1.id > @p1 AND t1.customer_id IN (SELECT s1.\"id\" FROM customers s1 WHERE ( s1.region = @p2 )) )"
+    );
+}
+
+#[test]
+fn should_write_a_not_in_subselect() {
+    let q = Order::where_col(|o| o.customer_id.not_in_query(Customer::all().select(|c| c.id)));
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id, t1.customer_id, t1.region FROM orders t1 WHERE ( t1.customer_id NOT IN (SELECT s1.id FROM customers s1) )"
+    );
+}
+
+#[test]
+fn should_allow_a_correlated_subselect() {
+    let q = Order::where_col(|o| {
+        let customers = Customer::all()
+            .where_raw("s1.region = t1.region", ManualWhereParam::new())
+            .select(|c| c.id);
+        o.customer_id.in_query(customers)
+    });
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        "SELECT t1.\"id\", t1.\"customer_id\", t1.\"region\" FROM orders t1 WHERE ( t1.customer_id IN (SELECT s1.\"id\" FROM customers s1 WHERE ( ( s1.region = t1.region ) )) )"
+    );
+}
+
+#[test]
+fn should_bind_the_subquery_params_in_order() {
+    let client = MockClient::new(Syntax::Postgres);
+    let q = Order::where_col(|o| o.id.gt(5))
+        .where_col(|o| {
+            let customers = Customer::where_col(|c| c.region.equal("west"))
+                .where_col(|c| c.id.lt(100))
+                .select(|c| c.id);
+            o.customer_id.in_query(customers)
+        })
+        .where_col(|o| o.region.not_equal("east"));
+    block_on(q.run(&client)).unwrap();
+    let params = client.queries().pop().unwrap().params;
+    assert_eq!(
+        params,
+        vec![
+            Value::Int(5),
+            Value::Text("west".to_string()),
+            Value::Int(100),
+            Value::Text("east".to_string()),
+        ]
+    );
+}
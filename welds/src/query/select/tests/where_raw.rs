@@ -0,0 +1,58 @@
+use super::*;
+use crate::connections::value;
+use crate::query::builder::ManualWhereParam;
+use crate::WeldsModel;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "users")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct User {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub email: String,
+}
+
+#[test]
+fn should_number_raw_params_after_the_other_clauses() {
+    let q = User::where_col(|u| u.id.gt(10)).where_raw(
+        "lower(email) = lower(?)",
+        ManualWhereParam::new().push("A@B.COM".to_string()),
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        "SELECT t1.\"id\", t1.\"email\" FROM users t1 WHERE ( t1.id > $1 AND ( lower(email) = lower($2) ) )"
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        "SELECT t1.\"id\", t1.\"email\" FROM users t1 WHERE ( t1.id > @p1 AND ( lower(email) = lower(@p2) ) )"
+    );
+}
+
+#[test]
+fn should_leave_quoted_and_escaped_question_marks_alone() {
+    let q = QueryBuilder::<User>::new().where_raw(
+        "email != 'who?' AND tags ?? ?",
+        ManualWhereParam::new().push("admin".to_string()),
+    );
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        "SELECT t1.\"id\", t1.\"email\" FROM users t1 WHERE ( ( email != 'who?' AND tags ? $1 ) )"
+    );
+}
+
+#[test]
+fn should_preview_the_sql_with_its_params_in_order() {
+    let q = User::where_col(|u| u.id.gt(10))
+        .where_raw(
+            "lower(email) = lower(?)",
+            ManualWhereParam::new().push("A@B.COM".to_string()),
+        )
+        .limit(5);
+    let (sql, params) = q.to_sql_with_params(Syntax::Postgres);
+    assert_eq!(sql, q.to_sql(Syntax::Postgres));
+    let params: Vec<String> = params
+        .iter()
+        .map(|p| value::of_param(*p).to_string())
+        .collect();
+    assert_eq!(params, ["10", r#""A@B.COM""#]);
+}
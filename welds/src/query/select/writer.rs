@@ -1,10 +1,14 @@
 use crate::model_traits::Column;
 use crate::model_traits::TableIdent;
 use crate::query::clause::exists::ExistIn;
+use crate::query::clause::sample::Sample;
 use crate::query::clause::ClauseAdder;
 use crate::query::clause::OrderBy;
 use crate::query::clause::ParamArgs;
-use crate::query::helpers::{build_where, join_sql_parts};
+use crate::query::helpers::{
+    build_where, distinct_on_orders, emulate_distinct_on, first_row_number, from_table,
+    join_sql_parts, select_keyword,
+};
 use crate::query::tail;
 use crate::writers::alias::TableAlias;
 use crate::writers::ColumnWriter;
@@ -12,12 +16,49 @@ use crate::writers::CountWriter;
 use crate::writers::NextParam;
 use crate::Syntax;
 
+/// How the rows read by a query are locked until the end of the transaction.
+/// See `QueryBuilder::lock_for_update`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowLock {
+    /// `FOR UPDATE`, waits for rows other transactions have locked
+    Update,
+    /// `FOR UPDATE SKIP LOCKED`, leaves out rows other transactions have locked
+    SkipLocked,
+    /// `FOR UPDATE NOWAIT`, fails if any of the rows are locked
+    NoWait,
+}
+
+impl RowLock {
+    // the locking clause at the end of the SELECT. MSSQL uses table hints instead
+    fn clause(self, syntax: Syntax) -> Option<&'static str> {
+        match (syntax, self) {
+            (Syntax::Mssql | Syntax::Sqlite, _) => None,
+            (_, RowLock::Update) => Some("FOR UPDATE"),
+            (_, RowLock::SkipLocked) => Some("FOR UPDATE SKIP LOCKED"),
+            (_, RowLock::NoWait) => Some("FOR UPDATE NOWAIT"),
+        }
+    }
+
+    fn mssql_hints(self) -> &'static str {
+        match self {
+            RowLock::Update => "WITH (UPDLOCK, ROWLOCK)",
+            RowLock::SkipLocked => "WITH (UPDLOCK, ROWLOCK, READPAST)",
+            RowLock::NoWait => "WITH (UPDLOCK, ROWLOCK, NOWAIT)",
+        }
+    }
+}
+
 /// take all info from a query and translates it into SQL
 /// can build the params Vec as needed
 pub struct SelectWriter {
     syntax: Syntax,
     table_ident: TableIdent,
     tablealias: String,
+    nolock: bool,
+    lock: Option<RowLock>,
+    sample: Option<Sample>,
+    distinct: bool,
+    distinct_on: Vec<String>,
 }
 
 impl SelectWriter {
@@ -28,6 +69,11 @@ impl SelectWriter {
             syntax,
             table_ident: table_ident.clone(),
             tablealias: ta.next(),
+            nolock: false,
+            lock: None,
+            sample: None,
+            distinct: false,
+            distinct_on: Vec::default(),
         }
     }
 
@@ -41,6 +87,64 @@ impl SelectWriter {
             syntax,
             table_ident: table_ident.clone(),
             tablealias: alias.into(),
+            nolock: false,
+            lock: None,
+            sample: None,
+            distinct: false,
+            distinct_on: Vec::default(),
+        }
+    }
+
+    /// Reads the table with the `NOLOCK` hint on MSSQL. See `QueryBuilder::with_nolock_dirty_reads`
+    pub fn with_nolock(mut self, nolock: bool) -> Self {
+        self.nolock = nolock;
+        self
+    }
+
+    /// Locks the rows that are read. Takes the place of `with_nolock`
+    pub fn with_lock(mut self, lock: Option<RowLock>) -> Self {
+        self.lock = lock;
+        self
+    }
+
+    /// Reads a random share of the table. See `QueryBuilder::sample_percent`
+    pub(crate) fn with_sample(mut self, sample: Option<Sample>) -> Self {
+        self.sample = sample;
+        self
+    }
+
+    /// Leaves out duplicate rows, or with `on` columns keeps only the first row for each of their
+    /// values. See `QueryBuilder::distinct_on`
+    pub fn with_distinct(mut self, distinct: bool, on: &[String]) -> Self {
+        self.distinct = distinct;
+        self.distinct_on = on.to_vec();
+        self
+    }
+
+    pub(crate) fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+
+    /// True if the rows are made distinct, a count has to count what is left after that
+    pub(crate) fn is_distinct(&self) -> bool {
+        self.distinct || !self.distinct_on.is_empty()
+    }
+
+    // the table (and alias) to select from
+    fn from(&self) -> String {
+        let table = self.table_ident.to_string();
+        match (self.syntax, self.lock) {
+            (Syntax::Mssql, Some(lock)) => {
+                let from = from_table(self.syntax, &table, &self.tablealias, false, self.sample);
+                format!("{} {}", from, lock.mssql_hints())
+            }
+            _ => from_table(
+                self.syntax,
+                &table,
+                &self.tablealias,
+                self.nolock,
+                self.sample,
+            ),
         }
     }
 
@@ -61,7 +165,7 @@ impl SelectWriter {
     {
         let next_params = NextParam::new(self.syntax);
         join_sql_parts(&[
-            build_head_count(&self.table_ident, &self.tablealias, self.syntax),
+            build_head_count(&self.from(), &self.tablealias, self.syntax),
             build_where(
                 self.syntax,
                 &next_params,
@@ -74,6 +178,104 @@ impl SelectWriter {
         ])
     }
 
+    /// Write a `SELECT {expr} FROM ...` of a single aggregate over the filtered rows
+    pub(crate) fn sql_aggregate<'lam, 'exist, 'args, 'p>(
+        &self,
+        expr: &str,
+        wheres: &'lam [Box<dyn ClauseAdder>],
+        exist_ins: &'exist [ExistIn],
+        args: &'args mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        'lam: 'p,
+        'exist: 'p,
+    {
+        let next_params = NextParam::new(self.syntax);
+        join_sql_parts(&[
+            Some(format!("SELECT {} FROM {}", expr, self.from())),
+            build_where(
+                self.syntax,
+                &next_params,
+                &self.tablealias,
+                wheres,
+                args,
+                exist_ins,
+            ),
+        ])
+    }
+
+    /// Write a `SELECT EXISTS ( ... )` SQL String from its parts.
+    /// MSSQL doesn't have booleans, it selects a 1 or 0 with a `CASE WHEN EXISTS`
+    pub fn sql_exists<'lam, 'exist, 'args, 'p>(
+        &self,
+        wheres: &'lam [Box<dyn ClauseAdder>],
+        exist_ins: &'exist [ExistIn],
+        limit: &Option<i64>,
+        offset: &Option<i64>,
+        orders: &[OrderBy],
+        args: &'args mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        'lam: 'p,
+        'exist: 'p,
+    {
+        let next_params = NextParam::new(self.syntax);
+        let inner = join_sql_parts(&[
+            Some(format!("SELECT 1 FROM {}", self.from())),
+            build_where(
+                self.syntax,
+                &next_params,
+                &self.tablealias,
+                wheres,
+                args,
+                exist_ins,
+            ),
+            tail::write(self.syntax, limit, offset, orders),
+        ]);
+        match self.syntax {
+            Syntax::Mssql => format!(
+                "SELECT CAST( CASE WHEN EXISTS ( {} ) THEN 1 ELSE 0 END as BIGINT )",
+                inner.trim()
+            ),
+            _ => format!("SELECT EXISTS ( {} )", inner.trim()),
+        }
+    }
+
+    /// Write a `Select count(DISTINCT column)` SQL String from its parts.
+    /// When approximate, uses the backend's estimating aggregate if it has one
+    pub fn sql_count_distinct<'lam, 'exist, 'args, 'p>(
+        &self,
+        column: &str,
+        approximate: bool,
+        wheres: &'lam [Box<dyn ClauseAdder>],
+        exist_ins: &'exist [ExistIn],
+        args: &'args mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        'lam: 'p,
+        'exist: 'p,
+    {
+        let next_params = NextParam::new(self.syntax);
+        let writer = ColumnWriter::new(self.syntax);
+        let column = format!("{}.{}", self.tablealias, writer.excape(column));
+        let cw = CountWriter::new(self.syntax);
+        let count = match approximate {
+            true => cw.approx_count_distinct(&column),
+            false => cw.count_distinct(&column),
+        };
+        join_sql_parts(&[
+            Some(format!("SELECT {} FROM {}", count, self.from())),
+            build_where(
+                self.syntax,
+                &next_params,
+                &self.tablealias,
+                wheres,
+                args,
+                exist_ins,
+            ),
+        ])
+    }
+
     /// Write a `Select ... FROM ...` SQL String from its parts
     /// Will fill in the args to be sent to the database if provided
     pub fn sql<'col, 'lam, 'exist, 'args, 'p>(
@@ -91,38 +293,240 @@ impl SelectWriter {
         'exist: 'p,
     {
         let next_params = NextParam::new(self.syntax);
+        self.sql_with_params(
+            columns,
+            wheres,
+            exist_ins,
+            limit,
+            offset,
+            orders,
+            &next_params,
+            args,
+        )
+    }
+
+    /// Like `sql`, numbering the params with the given `NextParam`
+    /// (the statement is part of a larger one)
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn sql_with_params<'col, 'lam, 'exist, 'args, 'p>(
+        &self,
+        columns: &'col [Column],
+        wheres: &'lam [Box<dyn ClauseAdder>],
+        exist_ins: &'exist [ExistIn],
+        limit: &Option<i64>,
+        offset: &Option<i64>,
+        orders: &[OrderBy],
+        next_params: &NextParam,
+        args: &'args mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        'lam: 'p,
+        'exist: 'p,
+    {
+        if emulate_distinct_on(self.syntax, &self.distinct_on) {
+            return self.sql_distinct_on_emulated(
+                columns,
+                wheres,
+                exist_ins,
+                limit,
+                offset,
+                orders,
+                next_params,
+                args,
+            );
+        }
+
+        // postgres wants the rows sorted by the DISTINCT ON columns first
+        let sorted = distinct_on_orders(&self.distinct_on, orders);
+        let select = select_keyword(
+            self.syntax,
+            &self.tablealias,
+            self.distinct,
+            &self.distinct_on,
+        );
         join_sql_parts(&[
-            build_head_select(self.syntax, &self.table_ident, &self.tablealias, columns),
+            build_head_select(
+                self.syntax,
+                &select,
+                &self.from(),
+                &self.tablealias,
+                columns,
+            ),
             build_where(
                 self.syntax,
-                &next_params,
+                next_params,
                 &self.tablealias,
                 wheres,
                 args,
                 exist_ins,
             ),
+            tail::write(self.syntax, limit, offset, &sorted),
+            self.lock
+                .and_then(|l| l.clause(self.syntax))
+                .map(|l| l.to_owned()),
+        ])
+    }
+
+    // Numbers the rows of each DISTINCT ON partition in the order of the query,
+    // then keeps the first one
+    #[allow(clippy::too_many_arguments)]
+    fn sql_distinct_on_emulated<'col, 'lam, 'exist, 'args, 'p>(
+        &self,
+        columns: &'col [Column],
+        wheres: &'lam [Box<dyn ClauseAdder>],
+        exist_ins: &'exist [ExistIn],
+        limit: &Option<i64>,
+        offset: &Option<i64>,
+        orders: &[OrderBy],
+        next_params: &NextParam,
+        args: &'args mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        'lam: 'p,
+        'exist: 'p,
+    {
+        let writer = ColumnWriter::new(self.syntax);
+        let alias = &self.tablealias;
+        let row_number = first_row_number(self.syntax, alias, &self.distinct_on, orders);
+        let cols: Vec<String> = columns.iter().map(|c| writer.write(alias, c)).collect();
+        let cols = cols.join(", ");
+        let inner = join_sql_parts(&[
+            Some(format!(
+                "SELECT {}, {} FROM {}",
+                cols,
+                row_number,
+                self.from()
+            )),
+            build_where(self.syntax, next_params, alias, wheres, args, exist_ins),
+        ]);
+        // the inner query has its own scope, it can reuse the alias
+        join_sql_parts(&[
+            Some(format!(
+                "SELECT {} FROM ( {} ) {} WHERE {}.welds_rn = 1",
+                cols, inner, alias, alias
+            )),
+            tail::write(self.syntax, limit, offset, orders),
+        ])
+    }
+
+    /// Write a `Select ... FROM ...` that keeps the rows of each value of the `partition`
+    /// column on their own: the `limit` rows after `offset` of each, in the order of the query.
+    /// See `include_where`
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn sql_limited_per<'col, 'lam, 'exist, 'args, 'p>(
+        &self,
+        columns: &'col [Column],
+        partition: &[String],
+        limit: &Option<i64>,
+        offset: &Option<i64>,
+        wheres: &'lam [Box<dyn ClauseAdder>],
+        exist_ins: &'exist [ExistIn],
+        orders: &[OrderBy],
+        args: &'args mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        'lam: 'p,
+        'exist: 'p,
+    {
+        let next_params = NextParam::new(self.syntax);
+        let writer = ColumnWriter::new(self.syntax);
+        let alias = &self.tablealias;
+        let row_number = first_row_number(self.syntax, alias, partition, orders);
+        let cols: Vec<String> = columns.iter().map(|c| writer.write(alias, c)).collect();
+        let cols = cols.join(", ");
+        let inner = join_sql_parts(&[
+            Some(format!(
+                "SELECT {}, {} FROM {}",
+                cols,
+                row_number,
+                self.from()
+            )),
+            build_where(self.syntax, &next_params, alias, wheres, args, exist_ins),
+        ]);
+        let skipped = offset.unwrap_or(0);
+        let kept = match limit {
+            Some(limit) => format!(
+                "{}.welds_rn > {} AND {}.welds_rn <= {}",
+                alias,
+                skipped,
+                alias,
+                skipped + limit
+            ),
+            None => format!("{}.welds_rn > {}", alias, skipped),
+        };
+        // the inner query has its own scope, it can reuse the alias
+        join_sql_parts(&[
+            Some(format!(
+                "SELECT {} FROM ( {} ) {} WHERE {}",
+                cols, inner, alias, kept
+            )),
+            tail::write(self.syntax, &None, &None, orders),
+        ])
+    }
+
+    /// Write a `Select ... FROM ...` that finds the page using only the primary key,
+    /// then joins back to the table for the full rows of the page
+    #[allow(clippy::too_many_arguments)]
+    pub fn sql_late_row_lookup<'col, 'lam, 'exist, 'args, 'p>(
+        &self,
+        columns: &'col [Column],
+        primary_key: &Column,
+        wheres: &'lam [Box<dyn ClauseAdder>],
+        exist_ins: &'exist [ExistIn],
+        limit: &Option<i64>,
+        offset: &Option<i64>,
+        orders: &[OrderBy],
+        args: &'args mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        'lam: 'p,
+        'exist: 'p,
+    {
+        let next_params = NextParam::new(self.syntax);
+        let writer = ColumnWriter::new(self.syntax);
+        let alias = &self.tablealias;
+        let pk = writer.write(alias, primary_key);
+
+        // the inner query has its own scope, it can reuse the alias
+        let keys = join_sql_parts(&[
+            Some(format!("SELECT {} AS welds_key FROM {}", pk, self.from())),
+            build_where(self.syntax, &next_params, alias, wheres, args, exist_ins),
             tail::write(self.syntax, limit, offset, orders),
+        ]);
+
+        // the page is re-sorted, the join doesn't keep the order of the keys
+        let outer_order = match orders.is_empty() {
+            true => format!("ORDER BY {}", pk),
+            false => tail::write(self.syntax, &None, &None, orders).unwrap_or_default(),
+        };
+
+        join_sql_parts(&[
+            build_head_select(self.syntax, "SELECT", &self.from(), alias, columns),
+            Some(format!(
+                "INNER JOIN ( {} ) welds_keys ON {} = welds_keys.welds_key",
+                keys, pk
+            )),
+            Some(outer_order),
         ])
     }
 }
 
-fn build_head_count(table: &TableIdent, tablealias: &str, syntax: Syntax) -> Option<String> {
-    let tn = table.to_string();
-    let identifier = format!("{} {}", tn, &tablealias);
+fn build_head_count(from: &str, tablealias: &str, syntax: Syntax) -> Option<String> {
     let cw = CountWriter::new(syntax);
     let count_star = cw.count(Some(tablealias), Some("*"));
-    Some(format!("SELECT {} FROM {}", count_star, identifier))
+    Some(format!("SELECT {} FROM {}", count_star, from))
 }
 
 fn build_head_select(
     syntax: Syntax,
-    table: &TableIdent,
+    select: &str,
+    from: &str,
     tablealias: &str,
     cols_info: &[Column],
 ) -> Option<String> {
     let writer = ColumnWriter::new(syntax);
     let mut head: Vec<&str> = Vec::default();
-    head.push("SELECT");
+    head.push(select);
     //let cols_info = S::columns();
     let cols: Vec<_> = cols_info
         .iter()
@@ -131,8 +535,6 @@ fn build_head_select(
     let cols = cols.join(", ");
     head.push(&cols);
     head.push("FROM");
-    let tn = table.to_string();
-    let identifier = format!("{} {}", tn, tablealias);
-    head.push(&identifier);
+    head.push(from);
     Some(head.join(" "))
 }
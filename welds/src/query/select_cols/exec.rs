@@ -1,8 +1,13 @@
-use crate::errors::Result;
+use crate::errors::{Result, WeldsError};
 use crate::model_traits::{HasSchema, TableColumns, TableInfo};
-use crate::query::clause::ParamArgs;
-use crate::query::helpers::{build_tail, build_where_clauses, join_sql_parts};
+use crate::query::clause::{ClauseAdder, ParamArgs};
+use crate::query::helpers::{
+    build_where_clauses, distinct_on_orders, emulate_distinct_on, first_row_number, from_table,
+    join_sql_parts, select_keyword,
+};
 use crate::query::select_cols::SelectBuilder;
+use crate::query::tail;
+use crate::writers::in_namespace;
 use crate::writers::ColumnWriter;
 use crate::writers::LimitSkipWriter;
 use crate::writers::NextParam;
@@ -28,28 +33,165 @@ where
         <T as HasSchema>::Schema: TableInfo + TableColumns,
     {
         let next_params = NextParam::new(syntax);
+        self.sql_with_params(syntax, &next_params, args, true)
+    }
+
+    /// The SQL of this query used inside of another query, numbering its params after the outer ones
+    pub(crate) fn subquery_sql(&self, syntax: Syntax, next_params: &NextParam) -> String
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.sql_with_params(syntax, next_params, &mut None, true)
+    }
+
+    /// The SQL of this query as a part of a UNION. Without its limit, offset and ordering
+    /// unless `with_tail`, in which case the caller has to make it a derived table
+    pub(crate) fn union_part_sql<'s, 'p>(
+        &'s self,
+        syntax: Syntax,
+        next_params: &NextParam,
+        args: &mut Option<ParamArgs<'p>>,
+        with_tail: bool,
+    ) -> String
+    where
+        's: 'p,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.sql_with_params(syntax, next_params, args, with_tail)
+    }
+
+    /// The SQL of this query as the subquery of a lateral join, filtered by the `correlation`
+    /// to the outer row
+    pub(crate) fn lateral_sql<'s, 'p>(
+        &'s self,
+        syntax: Syntax,
+        next_params: &NextParam,
+        args: &mut Option<ParamArgs<'p>>,
+        correlation: &[String],
+    ) -> String
+    where
+        's: 'p,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.sql_with_filters(syntax, next_params, args, true, correlation)
+    }
+
+    pub(crate) fn has_limit(&self) -> bool {
+        self.qb.limit.is_some() || self.qb.offset.is_some()
+    }
+
+    /// Adds the params of this query, in the order `subquery_sql` numbers them
+    pub(crate) fn bind_subquery<'s, 'p>(&'s self, args: &mut ParamArgs<'p>)
+    where
+        's: 'p,
+    {
+        for join in &self.joins {
+            join.bind_jointable(args);
+        }
+        for clause in &self.qb.wheres {
+            clause.bind(args);
+        }
+        for clause in &self.qb.exist_ins {
+            clause.bind(args);
+        }
+        for join in &self.joins {
+            join.bind(args);
+        }
+    }
+
+    fn sql_with_params<'s, 'args, 'p>(
+        &'s self,
+        syntax: Syntax,
+        next_params: &NextParam,
+        args: &'args mut Option<ParamArgs<'p>>,
+        with_tail: bool,
+    ) -> String
+    where
+        's: 'p,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.sql_with_filters(syntax, next_params, args, with_tail, &[])
+    }
+
+    // `filters` are added to the WHERE as they are
+    fn sql_with_filters<'s, 'args, 'p>(
+        &'s self,
+        syntax: Syntax,
+        next_params: &NextParam,
+        args: &'args mut Option<ParamArgs<'p>>,
+        with_tail: bool,
+        filters: &[String],
+    ) -> String
+    where
+        's: 'p,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        // the joins come first, a lateral join has params
+        let join_sql = build_joins(syntax, self, next_params, args);
         let wheres = self.qb.wheres.as_slice();
         let exists_in = self.qb.exist_ins.as_slice();
         let alias = &self.qb.alias;
 
-        let mut wheres = build_where_clauses(syntax, &next_params, alias, wheres, args, exists_in);
+        let mut wheres = build_where_clauses(syntax, next_params, alias, wheres, args, exists_in);
         for j in &self.joins {
-            j.append_where(syntax, &mut wheres, &next_params, args);
+            j.append_where(syntax, &mut wheres, next_params, args);
         }
+        wheres.extend(filters.iter().cloned());
         let where_sql = if wheres.is_empty() {
             None
         } else {
             Some(format!("WHERE ( {} )", wheres.join(" AND ")))
         };
+        let head_select = build_head_select(syntax, self);
+        let group_sql = build_group_by(syntax, self);
+
+        if !self.window_filters.is_empty() || emulate_distinct_on(syntax, &self.qb.distinct_on) {
+            let inner = join_sql_parts(&[head_select, join_sql, where_sql, group_sql]);
+            return self.wrap_window_filters(syntax, inner, with_tail);
+        }
 
-        join_sql_parts(&[
-            build_head_select(syntax, self),
-            build_joins(syntax, self),
-            where_sql,
-            build_tail(syntax, &self.qb),
-        ])
-        .trim()
-        .to_owned()
+        let tail = match with_tail {
+            true => self.tail(syntax),
+            false => None,
+        };
+        join_sql_parts(&[head_select, join_sql, where_sql, group_sql, tail])
+            .trim()
+            .to_owned()
+    }
+
+    // Window functions can't be used in the WHERE of the query that computes them,
+    // so the query becomes a derived table and is filtered by an outer query
+    fn wrap_window_filters(&self, syntax: Syntax, inner: String, with_tail: bool) -> String {
+        let alias = &self.qb.alias;
+        let mut filters: Vec<String> = self
+            .window_filters
+            .iter()
+            .map(|f| f.write(syntax, alias))
+            .collect();
+        if emulate_distinct_on(syntax, &self.qb.distinct_on) {
+            filters.push(format!("{}.welds_rn = 1", alias));
+        }
+        let outer = format!(
+            "SELECT * FROM ( {} ) {} WHERE ( {} )",
+            inner.trim(),
+            alias,
+            filters.join(" AND ")
+        );
+        let tail = match with_tail {
+            true => self.tail(syntax),
+            false => None,
+        };
+        join_sql_parts(&[Some(outer), tail])
+    }
+
+    // the ORDER BY, LIMIT and OFFSET. On Postgres the DISTINCT ON columns are sorted first
+    fn tail(&self, syntax: Syntax) -> Option<String> {
+        let qb = &self.qb;
+        let orders = match emulate_distinct_on(syntax, &qb.distinct_on) {
+            true => distinct_on_orders(&[], &qb.orderby),
+            false => distinct_on_orders(&qb.distinct_on, &qb.orderby),
+        };
+        tail::write(syntax, &qb.limit, &qb.offset, &orders)
     }
 
     /// Get a copy of the SQL that will be executed when this query runs
@@ -66,12 +208,32 @@ where
         <T as HasSchema>::Schema: TableInfo + TableColumns,
     {
         let syntax = client.syntax();
+        let lateral = self.joins.iter().any(|j| j.has_lateral());
+        if lateral && matches!(syntax, Syntax::Sqlite | Syntax::MariaDb) {
+            return Err(WeldsError::LateralUnsupported(syntax));
+        }
         let mut args: Option<ParamArgs> = Some(Vec::default());
-        let sql = self.sql_internal(syntax, &mut args);
+        let sql = in_namespace(client, || self.sql_internal(syntax, &mut args));
         let args = args.unwrap();
         let rows = client.fetch_rows(&sql, &args).await?;
         Ok(rows)
     }
+
+    /// Executes the query, reading each row into a struct, usually one that derives `WeldsProjection`.
+    /// The fields of the struct are matched to the names the columns are selected as
+    pub async fn run_into<R>(&self, client: &dyn Client) -> Result<Vec<R>>
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        R: TryFrom<Row>,
+        WeldsError: From<<R as TryFrom<Row>>::Error>,
+    {
+        let rows = self.run(client).await?;
+        let mut objs = Vec::default();
+        for row in rows {
+            objs.push(R::try_from(row)?);
+        }
+        Ok(objs)
+    }
 }
 
 fn build_head_select<T>(syntax: Syntax, sb: &SelectBuilder<T>) -> Option<String>
@@ -81,10 +243,16 @@ where
 {
     let writer = ColumnWriter::new(syntax);
     let mut head: Vec<&str> = Vec::default();
-    head.push("SELECT");
+    let qb = &sb.qb;
+    let alias = &qb.alias;
+    let emulated = emulate_distinct_on(syntax, &qb.distinct_on);
+    let select = match emulated {
+        true => select_keyword(syntax, alias, qb.distinct, &[]),
+        false => select_keyword(syntax, alias, qb.distinct, &qb.distinct_on),
+    };
+    head.push(&select);
 
     let mut cols: Vec<_> = Vec::default();
-    let alias = &sb.qb.alias;
 
     // Add these columns
     for col in &sb.selects {
@@ -99,9 +267,31 @@ where
         }
     }
 
+    for (path, name) in &sb.json_paths {
+        let name = writer.excape(name);
+        cols.push(format!("{} AS {}", path.write(syntax, alias), name));
+    }
+
+    for agg in &sb.aggs {
+        cols.push(agg.write_selected(syntax, alias));
+    }
+
+    for window in &sb.windows {
+        cols.push(window.write(syntax, alias));
+    }
+    if emulated {
+        cols.push(first_row_number(
+            syntax,
+            alias,
+            &qb.distinct_on,
+            &qb.orderby,
+        ));
+    }
+
     // Add columns from joins
+    let mut names: Vec<String> = sb.selects.iter().map(|c| c.field_name.clone()).collect();
     for join in &sb.joins {
-        join.append_columns(syntax, &mut cols);
+        join.append_columns(syntax, &mut cols, &mut names);
     }
 
     let cols_text = cols.join(", ");
@@ -109,21 +299,58 @@ where
 
     head.push("FROM");
     let tn = <T as HasSchema>::Schema::identifier().join(".");
-    let identifier = format!("{} {}", tn, alias);
+    let identifier = from_table(syntax, &tn, alias, sb.qb.nolock, sb.qb.sample);
     head.push(&identifier);
     Some(head.join(" "))
 }
 
-fn build_joins<T>(syntax: Syntax, sb: &SelectBuilder<T>) -> Option<String>
+// With an aggregate selected, all the plain columns are grouped by
+fn build_group_by<T>(syntax: Syntax, sb: &SelectBuilder<T>) -> Option<String>
 where
     T: HasSchema,
+{
+    let aggregated = !sb.aggs.is_empty() || sb.joins.iter().any(|j| j.has_aggregates());
+    if !aggregated {
+        return None;
+    }
+    let writer = ColumnWriter::new(syntax);
+    let alias = &sb.qb.alias;
+    let mut groups: Vec<String> = sb
+        .selects
+        .iter()
+        .map(|col| format!("{}.{}", alias, writer.excape(&col.col_name)))
+        .collect();
+    for (path, _) in &sb.json_paths {
+        groups.push(path.write(syntax, alias));
+    }
+    for join in &sb.joins {
+        join.append_groups(syntax, &mut groups);
+    }
+    if groups.is_empty() {
+        return None;
+    }
+    Some(format!("GROUP BY {}", groups.join(", ")))
+}
+
+fn build_joins<'s, 'p, T>(
+    syntax: Syntax,
+    sb: &'s SelectBuilder<T>,
+    next_params: &NextParam,
+    args: &mut Option<ParamArgs<'p>>,
+) -> Option<String>
+where
+    's: 'p,
+    T: HasSchema,
     <T as HasSchema>::Schema: TableInfo + TableColumns,
 {
     let mut list = Vec::default();
     let alias = &sb.qb.alias;
     // Add columns from joins
     for join in &sb.joins {
-        join.append_jointable(syntax, &mut list, alias);
+        join.append_jointable(syntax, &mut list, alias, next_params, args);
+    }
+    if list.is_empty() {
+        return None;
     }
     Some(list.join(" "))
 }
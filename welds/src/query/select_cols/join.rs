@@ -1,9 +1,11 @@
 use super::SelectBuilder;
 use super::SelectColumn;
-use crate::model_traits::{HasSchema, TableInfo};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
 use crate::query::clause::ClauseAdder;
 use crate::query::clause::ParamArgs;
+use crate::query::group_by::Aggregate;
 use crate::writers::alias::TableAlias;
+use crate::writers::table_name;
 use crate::writers::ColumnWriter;
 use crate::writers::NextParam;
 use crate::Syntax;
@@ -11,14 +13,82 @@ use std::sync::Arc;
 
 pub(crate) struct JoinBuilder {
     pub(crate) alias_asigner: Arc<TableAlias>,
-    pub(crate) outer_key: String,
     pub(crate) inner_alias: String,
     pub(crate) inner_table: String,
-    pub(crate) inner_key: String,
+    // (outer column, inner column) pairs that are equal
+    pub(crate) on: Vec<(String, String)>,
     pub(crate) wheres: Vec<Box<dyn ClauseAdder>>,
     pub(crate) selects: Vec<SelectColumn>,
+    pub(crate) aggs: Vec<Aggregate>,
     pub(crate) ty: Join,
     pub(crate) subs: Vec<JoinBuilder>,
+    // a LATERAL join is to a query, its filters and joins are written inside of it
+    pub(crate) lateral: Option<Box<dyn LateralQuery>>,
+}
+
+/// A query that is joined LATERAL (OUTER APPLY on MSSQL), so it can refer to the outer row
+pub(crate) trait LateralQuery: Send + Sync {
+    /// The SELECT of the query with the `correlation` added to its filters
+    fn write_lateral<'s, 'p>(
+        &'s self,
+        syntax: Syntax,
+        next_params: &NextParam,
+        args: &mut Option<ParamArgs<'p>>,
+        correlation: &[String],
+    ) -> String
+    where
+        's: 'p;
+
+    /// Adds the params of the query, in the order `write_lateral` numbers them
+    fn bind_lateral<'s, 'p>(&'s self, args: &mut ParamArgs<'p>)
+    where
+        's: 'p;
+
+    /// The alias of the table the query selects from
+    fn alias(&self) -> &str;
+
+    fn set_aliases(&mut self, alias_asigner: &Arc<TableAlias>);
+
+    /// Adds the tables the query reads, false if some can't be named
+    fn tables(&self, tables: &mut Vec<String>) -> bool;
+}
+
+impl<T> LateralQuery for SelectBuilder<T>
+where
+    T: Send + Sync + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    fn write_lateral<'s, 'p>(
+        &'s self,
+        syntax: Syntax,
+        next_params: &NextParam,
+        args: &mut Option<ParamArgs<'p>>,
+        correlation: &[String],
+    ) -> String
+    where
+        's: 'p,
+    {
+        self.lateral_sql(syntax, next_params, args, correlation)
+    }
+
+    fn bind_lateral<'s, 'p>(&'s self, args: &mut ParamArgs<'p>)
+    where
+        's: 'p,
+    {
+        self.bind_subquery(args);
+    }
+
+    fn alias(&self) -> &str {
+        &self.qb.alias
+    }
+
+    fn set_aliases(&mut self, alias_asigner: &Arc<TableAlias>) {
+        SelectBuilder::set_aliases(self, alias_asigner);
+    }
+
+    fn tables(&self, tables: &mut Vec<String>) -> bool {
+        SelectBuilder::tables(self, tables)
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -41,18 +111,55 @@ impl Join {
 }
 
 impl JoinBuilder {
+    // the joined table (or the tables of the lateral query) and of its filters and joins
+    pub(crate) fn tables(&self, tables: &mut Vec<String>) -> bool {
+        let mut known = match &self.lateral {
+            Some(lateral) => lateral.tables(tables),
+            None => {
+                tables.push(self.inner_table.clone());
+                true
+            }
+        };
+        for w in &self.wheres {
+            known &= w.tables(tables);
+        }
+        for sub in &self.subs {
+            known &= sub.tables(tables);
+        }
+        known
+    }
+
     pub(crate) fn set_aliases(&mut self, alias_asigner: &Arc<TableAlias>) {
         self.alias_asigner = alias_asigner.clone();
         self.inner_alias = self.alias_asigner.next();
+        if let Some(lateral) = &mut self.lateral {
+            lateral.set_aliases(alias_asigner);
+        }
+    }
+
+    pub(super) fn has_lateral(&self) -> bool {
+        self.lateral.is_some() || self.subs.iter().any(|s| s.has_lateral())
     }
 
-    pub(super) fn append_columns(&self, syntax: Syntax, list: &mut Vec<String>) {
+    // `names` are the names already selected. A column of the same name, like the other side
+    // of a self join, is prefixed with the alias of its table so the row can tell them apart
+    pub(super) fn append_columns(
+        &self,
+        syntax: Syntax,
+        list: &mut Vec<String>,
+        names: &mut Vec<String>,
+    ) {
         let writer = ColumnWriter::new(syntax);
         let alias = &self.inner_alias;
         // Add these columns
         for col in &self.selects {
+            let mut name = col.field_name.clone();
+            if names.contains(&name) {
+                name = format!("{}_{}", alias, name);
+            }
             let colname = writer.excape(&col.col_name);
-            let fieldname = writer.excape(&col.field_name);
+            let fieldname = writer.excape(&name);
+            names.push(name);
             if colname == fieldname {
                 let col = format!("{}.{}", alias, colname);
                 list.push(col);
@@ -61,30 +168,90 @@ impl JoinBuilder {
                 list.push(col);
             }
         }
+        for agg in &self.aggs {
+            list.push(agg.write_selected(syntax, alias));
+        }
         for sub in &self.subs {
-            sub.append_columns(syntax, list);
+            sub.append_columns(syntax, list, names);
         }
     }
 
-    pub(super) fn append_jointable(
-        &self,
+    pub(super) fn has_aggregates(&self) -> bool {
+        !self.aggs.is_empty() || self.subs.iter().any(|s| s.has_aggregates())
+    }
+
+    // the plain columns, grouped by when aggregates are selected
+    pub(super) fn append_groups(&self, syntax: Syntax, list: &mut Vec<String>) {
+        let writer = ColumnWriter::new(syntax);
+        for col in &self.selects {
+            list.push(format!(
+                "{}.{}",
+                self.inner_alias,
+                writer.excape(&col.col_name)
+            ));
+        }
+        for sub in &self.subs {
+            sub.append_groups(syntax, list);
+        }
+    }
+
+    // Any params are for a lateral join, they come before the ones of the WHERE
+    pub(super) fn append_jointable<'s, 'args, 'p>(
+        &'s self,
         syntax: Syntax,
         list: &mut Vec<String>,
         outer_alias: &str,
-    ) {
+        next_params: &NextParam,
+        args: &'args mut Option<ParamArgs<'p>>,
+    ) where
+        's: 'p,
+    {
         let writer = ColumnWriter::new(syntax);
+        if let Some(lateral) = &self.lateral {
+            let correlation: Vec<String> = self
+                .on
+                .iter()
+                .map(|(outer_key, inner_key)| {
+                    format!(
+                        "{}.{} = {}.{}",
+                        lateral.alias(),
+                        inner_key,
+                        outer_alias,
+                        outer_key
+                    )
+                })
+                .collect();
+            let sql = lateral.write_lateral(syntax, next_params, args, &correlation);
+            let sql = match syntax {
+                Syntax::Mssql => format!("OUTER APPLY ( {} ) {}", sql, self.inner_alias),
+                _ => format!("LEFT JOIN LATERAL ( {} ) {} ON true", sql, self.inner_alias),
+            };
+            list.push(sql);
+            return;
+        }
+        let on: Vec<String> = self
+            .on
+            .iter()
+            .map(|(outer_key, inner_key)| {
+                format!(
+                    "{}.{} = {}.{}",
+                    outer_alias,
+                    writer.excape(outer_key),
+                    self.inner_alias,
+                    writer.excape(inner_key)
+                )
+            })
+            .collect();
         let sql = format!(
-            "{jointy} {itn} {ita} ON {ota}.{otk} = {ita}.{itk}",
-            jointy = self.ty.to_sql(),
-            itn = self.inner_table,
-            ita = self.inner_alias,
-            ota = outer_alias,
-            otk = writer.excape(&self.outer_key),
-            itk = writer.excape(&self.inner_key)
+            "{} {} {} ON {}",
+            self.ty.to_sql(),
+            table_name(&self.inner_table),
+            self.inner_alias,
+            on.join(" AND ")
         );
         list.push(sql);
         for sub in &self.subs {
-            sub.append_jointable(syntax, list, &self.inner_alias);
+            sub.append_jointable(syntax, list, &self.inner_alias, next_params, args);
         }
     }
 
@@ -110,7 +277,33 @@ impl JoinBuilder {
         }
     }
 
-    pub(super) fn new<T>(sb: SelectBuilder<T>, outer_key: String, inner_key: String) -> JoinBuilder
+    // binds in the same order as append_where
+    pub(super) fn bind<'s, 'p>(&'s self, args: &mut ParamArgs<'p>)
+    where
+        's: 'p,
+    {
+        for clause in &self.wheres {
+            clause.bind(args);
+        }
+        for sub in &self.subs {
+            sub.bind(args);
+        }
+    }
+
+    // binds in the same order as append_jointable
+    pub(super) fn bind_jointable<'s, 'p>(&'s self, args: &mut ParamArgs<'p>)
+    where
+        's: 'p,
+    {
+        if let Some(lateral) = &self.lateral {
+            lateral.bind_lateral(args);
+        }
+        for sub in &self.subs {
+            sub.bind_jointable(args);
+        }
+    }
+
+    pub(super) fn new<T>(sb: SelectBuilder<T>, on: Vec<(String, String)>) -> JoinBuilder
     where
         T: Send + HasSchema,
         <T as HasSchema>::Schema: TableInfo,
@@ -120,12 +313,65 @@ impl JoinBuilder {
             alias_asigner: sb.qb.alias_asigner.clone(),
             inner_alias: sb.qb.alias.clone(),
             inner_table: tn,
-            outer_key,
-            inner_key,
+            on,
             wheres: sb.qb.wheres,
             selects: sb.selects,
+            aggs: sb.aggs,
             ty: Join::Inner,
             subs: sb.joins,
+            lateral: None,
+        }
+    }
+
+    // the join table of a many-to-many relationship, the related table is joined to it
+    pub(super) fn through(
+        related: JoinBuilder,
+        table: &str,
+        on: Vec<(String, String)>,
+    ) -> JoinBuilder {
+        let alias_asigner = related.alias_asigner.clone();
+        JoinBuilder {
+            inner_alias: alias_asigner.next(),
+            alias_asigner,
+            inner_table: table.to_owned(),
+            on,
+            wheres: Vec::default(),
+            selects: Vec::default(),
+            aggs: Vec::default(),
+            ty: related.ty,
+            subs: vec![related],
+            lateral: None,
+        }
+    }
+
+    // The outer query only sees the columns the lateral query selects, by the names they are selected as
+    pub(super) fn lateral<T>(sb: SelectBuilder<T>, on: Vec<(String, String)>) -> JoinBuilder
+    where
+        T: Send + Sync + HasSchema + 'static,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let names = sb
+            .selects
+            .iter()
+            .map(|c| c.field_name.clone())
+            .chain(sb.aggs.iter().map(|a| a.selected_name().to_owned()));
+        let selects = names
+            .map(|name| SelectColumn {
+                col_name: name.clone(),
+                field_name: name,
+            })
+            .collect();
+        JoinBuilder {
+            alias_asigner: sb.qb.alias_asigner.clone(),
+            inner_alias: sb.qb.alias.clone(),
+            inner_table: String::default(),
+            on,
+            wheres: Vec::default(),
+            selects,
+            aggs: Vec::default(),
+            ty: Join::Left,
+            subs: Vec::default(),
+            lateral: Some(Box::new(sb)),
         }
     }
 }
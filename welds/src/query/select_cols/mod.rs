@@ -1,6 +1,11 @@
 use crate::model_traits::{HasSchema, TableColumns, TableInfo, UniqueIdentifier};
 use crate::query::builder::QueryBuilder;
-use crate::query::clause::{AsFieldName, ClauseAdder};
+use crate::query::clause::{AsFieldName, ClauseAdder, JoinOn, WhereGroup};
+use crate::query::group_by::Aggregate;
+use crate::query::json::{JsonFilter, JsonPath};
+use crate::query::order::Order;
+use crate::query::union::SelectUnion;
+use crate::query::window::{Window, WindowFilter};
 use crate::relations::{HasRelations, Relationship};
 use crate::writers::alias::TableAlias;
 pub use join::Join;
@@ -22,6 +27,10 @@ mod select_column;
 pub struct SelectBuilder<T> {
     qb: QueryBuilder<T>,
     selects: Vec<SelectColumn>,
+    aggs: Vec<Aggregate>,
+    json_paths: Vec<(JsonPath, String)>,
+    windows: Vec<Window>,
+    window_filters: Vec<WindowFilter>,
     joins: Vec<JoinBuilder>,
 }
 
@@ -33,6 +42,10 @@ where
         Self {
             qb,
             selects: Vec::default(),
+            aggs: Vec::default(),
+            json_paths: Vec::default(),
+            windows: Vec::default(),
+            window_filters: Vec::default(),
             joins: Vec::default(),
         }
     }
@@ -50,6 +63,112 @@ where
         self
     }
 
+    /// Add a column to the list of columns that will be selected, selected as the given name.
+    /// Useful for reading into a `WeldsProjection` whose fields are named differently
+    pub fn select_as<V, FN: AsFieldName<V>>(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        alias: impl Into<String>,
+    ) -> SelectBuilder<T> {
+        let field = lam(Default::default());
+        self.selects.push(SelectColumn {
+            col_name: field.colname().to_string(),
+            field_name: alias.into(),
+        });
+        self
+    }
+
+    /// Select the value at a path in a JSON column, as the given name.
+    /// See `QueryBuilder::where_json_path` for how the path is read
+    ///
+    /// ```rust,ignore
+    /// Event::all()
+    ///     .select(|e| e.id)
+    ///     .select_json_path(|e| e.payload, "$.customer.email", "email")
+    /// ```
+    pub fn select_json_path<V, FN: AsFieldName<V>>(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        path: impl Into<String>,
+        alias: impl Into<String>,
+    ) -> SelectBuilder<T> {
+        let field = lam(Default::default());
+        let path = JsonPath {
+            col: field.colname().to_owned(),
+            path: path.into(),
+        };
+        self.json_paths.push((path, alias.into()));
+        self
+    }
+
+    /// Add an aggregate (`count`, `sum`, ...) to the columns that will be selected.
+    /// Also works on the query of a join.
+    ///
+    /// Once there is an aggregate, the rows are grouped by all the other selected columns
+    ///
+    /// ```rust,ignore
+    /// use welds::query::group_by::count;
+    ///
+    /// #[derive(WeldsProjection)]
+    /// struct UserSummary {
+    ///     name: String,
+    ///     order_count: i64,
+    /// }
+    ///
+    /// let summaries: Vec<UserSummary> = User::all()
+    ///     .select(|u| u.name)
+    ///     .left_join(|u| u.orders, Order::all().select_agg(|o| count(o.id).alias("order_count")))
+    ///     .run_into(&client)
+    ///     .await?;
+    /// // SELECT t1.name, COUNT(t2.id) AS order_count FROM users t1
+    /// // LEFT JOIN orders t2 ON t1.id = t2.user_id GROUP BY t1.name
+    /// ```
+    pub fn select_agg(mut self, lam: impl Fn(<T as HasSchema>::Schema) -> Aggregate) -> Self {
+        self.aggs.push(lam(Default::default()));
+        self
+    }
+
+    /// Add a window function (`row_number`, `rank`, `lag`, ...) to the columns that will be selected.
+    /// See `query::window::Window`
+    pub fn select_window(mut self, lam: impl Fn(<T as HasSchema>::Schema) -> Window) -> Self {
+        self.windows.push(lam(Default::default()));
+        self
+    }
+
+    /// Only keep the rows where the selected window function equals the value.
+    /// Keeps the newest row of each partition with a `row_number` ordered newest first:
+    ///
+    /// ```rust,ignore
+    /// Reading::all()
+    ///     .select(|r| r.sensor_id)
+    ///     .select(|r| r.value)
+    ///     .select_window(|r| row_number().partition_by(&r.sensor_id).order_by_desc(&r.taken_at))
+    ///     .where_window_eq("row_number", 1)
+    /// // SELECT * FROM ( SELECT t1.sensor_id, t1.value, ROW_NUMBER() OVER (...) AS row_number
+    /// //   FROM readings t1 ) t1 WHERE ( t1.row_number = 1 )
+    /// ```
+    ///
+    /// Window functions are computed after the WHERE, so the query is wrapped in an outer query
+    /// that filters on them. The limit, offset, and ordering are applied by the outer query.
+    pub fn where_window_eq(self, alias: impl Into<String>, value: i64) -> Self {
+        self.where_window(alias.into(), "=", value)
+    }
+
+    /// Only keep the rows where the selected window function is at most the value,
+    /// such as the top 3 of each partition with a `rank`. Works like `where_window_eq`
+    pub fn where_window_lte(self, alias: impl Into<String>, value: i64) -> Self {
+        self.where_window(alias.into(), "<=", value)
+    }
+
+    fn where_window(mut self, alias: String, operator: &'static str, value: i64) -> Self {
+        self.window_filters.push(WindowFilter {
+            alias,
+            operator,
+            value,
+        });
+        self
+    }
+
     /// Filter the results returned by this query.
     /// Used when you want to filter on the columns of this table.
     pub fn where_col(
@@ -63,6 +182,29 @@ where
         self
     }
 
+    /// Filter on the value at a path in a JSON column. See `QueryBuilder::where_json_path`
+    pub fn where_json_path<V, FN: AsFieldName<V>>(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+        path: impl Into<String>,
+        filter: JsonFilter,
+    ) -> Self {
+        self.qb = self.qb.where_json_path(lam, path, filter);
+        self
+    }
+
+    /// Filter the results with a group of conditions where any one of them has to match
+    pub fn where_any(mut self, lam: impl Fn(WhereGroup<T>) -> WhereGroup<T>) -> Self {
+        self.qb = self.qb.where_any(lam);
+        self
+    }
+
+    /// Filter the results with a group of conditions that all have to match
+    pub fn where_all(mut self, lam: impl Fn(WhereGroup<T>) -> WhereGroup<T>) -> Self {
+        self.qb = self.qb.where_all(lam);
+        self
+    }
+
     /// Add a query to this query (JOIN on a relationship)
     /// results on a query that is filtered using the results of both queries
     pub fn where_relation<R, Ship>(
@@ -84,7 +226,47 @@ where
         self
     }
 
+    /// Filter the results to the rows that have at least one related row matching the filter
+    pub fn where_exists<R, Ship>(
+        mut self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        filter: QueryBuilder<R>,
+    ) -> Self
+    where
+        T: HasRelations,
+        Ship: Relationship<R>,
+        R: Send + Sync + HasSchema,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        <T as HasRelations>::Relation: Default,
+    {
+        self.qb = self.qb.where_exists(relationship, filter);
+        self
+    }
+
+    /// Filter the results to the rows that have no related row matching the filter
+    pub fn where_not_exists<R, Ship>(
+        mut self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        filter: QueryBuilder<R>,
+    ) -> Self
+    where
+        T: HasRelations,
+        Ship: Relationship<R>,
+        R: Send + Sync + HasSchema,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        <T as HasRelations>::Relation: Default,
+    {
+        self.qb = self.qb.where_not_exists(relationship, filter);
+        self
+    }
+
     /// Inner Join to another table to be able to select additional columns
+    ///
+    /// The table can be joined to itself, each side gets its own alias. A joined column with the
+    /// same name as one already selected is returned prefixed with the alias, such as `t2_name`.
+    /// Use `select_as` on the joined query to pick a better name.
     pub fn join<R, Ship>(
         self,
         relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
@@ -138,12 +320,145 @@ where
         sb.set_aliases(&self.qb.alias_asigner);
         let outer_key = ship.my_key::<R::Schema, T::Schema>();
         let inner_key = ship.their_key::<R::Schema, T::Schema>();
-        let mut jb = JoinBuilder::new(sb, outer_key, inner_key);
+        let jb = match ship.through() {
+            None => {
+                let mut on = vec![(outer_key, inner_key)];
+                let shared = ship.shared_keys().iter();
+                on.extend(shared.map(|c| (c.to_string(), c.to_string())));
+                let mut jb = JoinBuilder::new(sb, on);
+                jb.ty = join_type;
+                jb
+            }
+            Some(through) => {
+                let mut related = JoinBuilder::new(sb, vec![(through.their_key.into(), inner_key)]);
+                related.ty = join_type;
+                JoinBuilder::through(
+                    related,
+                    through.table,
+                    vec![(outer_key, through.my_key.into())],
+                )
+            }
+        };
+        self.joins.push(jb);
+        self
+    }
+
+    /// Inner Join to any table on the given columns, without needing a relationship
+    ///
+    /// ```rust,ignore
+    /// let rows = Order::all()
+    ///     .select(|o| o.id)
+    ///     .join_on(|o, s| o.ship_to.eq(s.code), Store::all().select(|s| s.name))
+    ///     .run(&client)
+    ///     .await?;
+    /// // SELECT t1.id, t2.name FROM orders t1 JOIN stores t2 ON t1.ship_to = t2.code
+    /// ```
+    pub fn join_on<R>(
+        self,
+        on: impl Fn(<T as HasSchema>::Schema, <R as HasSchema>::Schema) -> JoinOn,
+        sb: SelectBuilder<R>,
+    ) -> Self
+    where
+        R: Send + HasSchema,
+        <R as HasSchema>::Schema: TableInfo,
+    {
+        self.join_on_with(on, sb, Join::Inner)
+    }
+
+    /// Left Join to any table on the given columns. See `join_on`
+    pub fn left_join_on<R>(
+        self,
+        on: impl Fn(<T as HasSchema>::Schema, <R as HasSchema>::Schema) -> JoinOn,
+        sb: SelectBuilder<R>,
+    ) -> Self
+    where
+        R: Send + HasSchema,
+        <R as HasSchema>::Schema: TableInfo,
+    {
+        self.join_on_with(on, sb, Join::Left)
+    }
+
+    /// Right Join to any table on the given columns. See `join_on`
+    pub fn right_join_on<R>(
+        self,
+        on: impl Fn(<T as HasSchema>::Schema, <R as HasSchema>::Schema) -> JoinOn,
+        sb: SelectBuilder<R>,
+    ) -> Self
+    where
+        R: Send + HasSchema,
+        <R as HasSchema>::Schema: TableInfo,
+    {
+        self.join_on_with(on, sb, Join::Right)
+    }
+
+    /// Join to any table on the given columns, with a manual selection of the Join Type
+    pub fn join_on_with<R>(
+        mut self,
+        on: impl Fn(<T as HasSchema>::Schema, <R as HasSchema>::Schema) -> JoinOn,
+        mut sb: SelectBuilder<R>,
+        join_type: Join,
+    ) -> Self
+    where
+        R: Send + HasSchema,
+        <R as HasSchema>::Schema: TableInfo,
+    {
+        let on = on(Default::default(), Default::default());
+        sb.set_aliases(&self.qb.alias_asigner);
+        let mut jb = JoinBuilder::new(sb, on.pairs);
         jb.ty = join_type;
         self.joins.push(jb);
         self
     }
 
+    /// Left Join to the rows of a query that is run for each row (`LEFT JOIN LATERAL`,
+    /// `OUTER APPLY` on MSSQL). The `on` columns filter the query to the outer row, so its
+    /// ordering and limit apply to each row on their own, such as the last 3 orders of each user:
+    ///
+    /// ```rust,ignore
+    /// let rows = User::all()
+    ///     .select(|u| u.name)
+    ///     .join_lateral(
+    ///         |u, o| u.id.eq(o.user_id),
+    ///         Order::all().select(|o| o.total).order_by_desc(|o| o.placed_at).limit(3),
+    ///     )
+    ///     .run(&client)
+    ///     .await?;
+    /// // SELECT t1.name, t2.total FROM users t1 LEFT JOIN LATERAL ( SELECT t2.total FROM orders t2
+    /// //   WHERE ( t2.user_id = t1.id ) ORDER BY placed_at DESC LIMIT 0, 3 ) t2 ON true
+    /// ```
+    ///
+    /// Only the columns the query selects can be read. Not available on Sqlite and MariaDb
+    pub fn join_lateral<R>(
+        mut self,
+        on: impl Fn(<T as HasSchema>::Schema, <R as HasSchema>::Schema) -> JoinOn,
+        mut sb: SelectBuilder<R>,
+    ) -> Self
+    where
+        R: Send + Sync + HasSchema + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let on = on(Default::default(), Default::default());
+        sb.set_aliases(&self.qb.alias_asigner);
+        self.joins.push(JoinBuilder::lateral(sb, on.pairs));
+        self
+    }
+
+    /// Leaves duplicate rows out of the results (`SELECT DISTINCT`)
+    pub fn distinct(mut self) -> Self {
+        self.qb = self.qb.distinct();
+        self
+    }
+
+    /// Keeps only the first row for each value of the column. See `QueryBuilder::distinct_on`.
+    /// Where it is emulated, the rows also have a `welds_rn` column
+    pub fn distinct_on<V, FN: AsFieldName<V>>(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+    ) -> Self {
+        self.qb = self.qb.distinct_on(lam);
+        self
+    }
+
     /// Limit the number of rows returned by this query
     pub fn limit(mut self, x: i64) -> Self {
         self.qb = self.qb.limit(x);
@@ -178,6 +493,49 @@ where
         self
     }
 
+    /// Order the results by a column or expression. See `QueryBuilder::order_by`
+    pub fn order_by(mut self, lam: impl Fn(<T as HasSchema>::Schema) -> Order) -> Self {
+        self.qb = self.qb.order_by(lam);
+        self
+    }
+
+    /// Combines the rows of this projection with the rows of another (UNION), leaving out duplicates.
+    /// See `SelectUnion`
+    pub fn union<R>(self, other: SelectBuilder<R>) -> SelectUnion
+    where
+        T: Sync + 'static,
+        R: Send + Sync + HasSchema + 'static,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        SelectUnion::new(self).union(other)
+    }
+
+    /// Combines the rows of this projection with all the rows of another (UNION ALL)
+    pub fn union_all<R>(self, other: SelectBuilder<R>) -> SelectUnion
+    where
+        T: Sync + 'static,
+        R: Send + Sync + HasSchema + 'static,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        SelectUnion::new(self).union_all(other)
+    }
+
+    // the tables this reads: its own, the joined ones and those of the sub-queries.
+    // false if some can't be named
+    pub(crate) fn tables(&self, tables: &mut Vec<String>) -> bool
+    where
+        <T as HasSchema>::Schema: TableInfo,
+    {
+        tables.push(<T as HasSchema>::Schema::identifier().join("."));
+        let mut known = self.qb.subquery_tables(tables);
+        for join in &self.joins {
+            known &= join.tables(tables);
+        }
+        known
+    }
+
     pub(crate) fn set_aliases(&mut self, alias_asigner: &Arc<TableAlias>) {
         self.qb.set_aliases(alias_asigner);
         for join in &mut self.joins {
@@ -24,7 +24,7 @@ pub(crate) fn write(
     }
 
     if !orders.is_empty() {
-        parts.push_front(orderby::to_sql(orders));
+        parts.push_front(orderby::to_sql(syntax, orders));
     }
 
     if parts.is_empty() {
@@ -0,0 +1,145 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo, UniqueIdentifier};
+use crate::query::clause::ParamArgs;
+use crate::query::helpers::from_table;
+use crate::state::DbState;
+use crate::writers::in_namespace;
+use crate::writers::{ColumnWriter, NextParam};
+use crate::{Client, Row, Syntax};
+use std::marker::PhantomData;
+use welds_connections::Param;
+
+/// The rows below a given row of a self-referential model, found with a recursive CTE.
+/// Made with the `descendants_of` method of a model marked `#[welds(tree = "parent_id")]`.
+///
+/// The tree is returned flattened, ordered by depth.
+///
+/// ```rust,ignore
+/// #[derive(WeldsModel)]
+/// #[welds(table = "categories", tree = "parent_id")]
+/// struct Category {
+///     #[welds(primary_key)]
+///     pub id: i32,
+///     pub parent_id: Option<i32>,
+///     pub name: String,
+/// }
+///
+/// let below_books = Category::descendants_of(books.id).run(&client).await?;
+/// ```
+pub struct TreeQuery<T> {
+    parent_col: &'static str,
+    root: Box<dyn Param + Send + Sync>,
+    max_depth: Option<i64>,
+    _t: PhantomData<T>,
+}
+
+impl<T> TreeQuery<T>
+where
+    T: Send + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+{
+    /// The rows whose `parent_col` leads back to the row with the id `root`
+    pub fn descendants_of<V>(parent_col: &'static str, root: V) -> Self
+    where
+        V: Param + Send + Sync + 'static,
+    {
+        Self {
+            parent_col,
+            root: Box::new(root),
+            max_depth: None,
+            _t: Default::default(),
+        }
+    }
+
+    /// Only walk this many levels down the tree. The children of the root are depth 1.
+    ///
+    /// Recursion doesn't end on its own if the parent links contain a cycle, this is the guard
+    pub fn max_depth(mut self, depth: i64) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    fn sql_internal<'s, 'p>(&'s self, syntax: Syntax, args: &mut Option<ParamArgs<'p>>) -> String
+    where
+        's: 'p,
+    {
+        let writer = ColumnWriter::new(syntax);
+        let next_params = NextParam::new(syntax);
+        let tn = <T as HasSchema>::Schema::identifier().join(".");
+        let table = from_table(syntax, &tn, "t1", false, None);
+        let pk = <T as HasSchema>::Schema::id_column();
+        let columns = <T as HasSchema>::Schema::columns();
+        let parent = writer.excape(self.parent_col);
+
+        let cols: Vec<String> = columns.iter().map(|c| writer.write("t1", c)).collect();
+        let cols = cols.join(", ");
+
+        if let Some(args) = args {
+            args.push(self.root.as_ref());
+        }
+        let anchor = format!(
+            "SELECT {}, 1 AS welds_depth FROM {} WHERE t1.{} = {}",
+            cols,
+            table,
+            parent,
+            next_params.next()
+        );
+        let mut step = format!(
+            "SELECT {}, welds_tree.welds_depth + 1 FROM {} JOIN welds_tree ON t1.{} = welds_tree.{}",
+            cols,
+            table,
+            parent,
+            writer.excape(pk.name())
+        );
+        if let Some(depth) = self.max_depth {
+            step.push_str(&format!(" WHERE welds_tree.welds_depth < {}", depth));
+        }
+
+        let outer: Vec<String> = columns
+            .iter()
+            .map(|c| writer.write("welds_tree", c))
+            .collect();
+        format!(
+            "{} welds_tree AS ( {} UNION ALL {} ) SELECT {} FROM welds_tree ORDER BY welds_tree.welds_depth, {}",
+            with_keyword(syntax),
+            anchor,
+            step,
+            outer.join(", "),
+            writer.write("welds_tree", &pk)
+        )
+    }
+
+    /// Get a copy of the SQL that will be executed when this query runs
+    pub fn to_sql(&self, syntax: Syntax) -> String {
+        self.sql_internal(syntax, &mut None)
+    }
+
+    /// Executes the query in the database returning the results
+    pub async fn run(&self, client: &dyn Client) -> Result<Vec<DbState<T>>>
+    where
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let sql = in_namespace(client, || self.sql_internal(client.syntax(), &mut args));
+        let args = args.unwrap();
+        let rows = client.fetch_rows(&sql, &args).await?;
+        let mut objs = Vec::default();
+        for row in rows {
+            let obj: T = T::try_from(row)?;
+            objs.push(DbState::db_loaded(obj));
+        }
+        Ok(objs)
+    }
+}
+
+// MSSQL finds the recursion on its own and doesn't accept the RECURSIVE keyword
+fn with_keyword(syntax: Syntax) -> &'static str {
+    match syntax {
+        Syntax::Mssql => "WITH",
+        _ => "WITH RECURSIVE",
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::value::Value;
+use crate::Syntax;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "categories", tree = "parent_id")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Category {
+    #[welds(primary_key)]
+    pub id: i32,
+    #[welds(rename = "parent")]
+    pub parent_id: Option<i32>,
+    pub name: String,
+}
+
+#[test]
+fn should_write_a_recursive_cte() {
+    let q = Category::descendants_of(3);
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"WITH RECURSIVE welds_tree AS ( SELECT t1."id", t1."parent", t1."name", 1 AS welds_depth FROM categories t1 WHERE t1."parent" = $1 UNION ALL SELECT t1."id", t1."parent", t1."name", welds_tree.welds_depth + 1 FROM categories t1 JOIN welds_tree ON t1."parent" = welds_tree."id" ) SELECT welds_tree."id", welds_tree."parent", welds_tree."name" FROM welds_tree ORDER BY welds_tree.welds_depth, welds_tree."id""#
+    );
+}
+
+#[test]
+fn should_leave_out_recursive_on_mssql() {
+    let q = Category::descendants_of(3);
+    let sql = q.to_sql(Syntax::Mssql);
+    assert!(sql.starts_with("WITH welds_tree AS ( "));
+    assert!(sql.contains(r#"WHERE t1."parent" = @p1 UNION ALL"#));
+}
+
+#[test]
+fn should_stop_at_the_max_depth() {
+    let q = Category::descendants_of(3).max_depth(2);
+    assert!(q.to_sql(Syntax::Mysql).contains(
+        "JOIN welds_tree ON t1.parent = welds_tree.id WHERE welds_tree.welds_depth < 2 )"
+    ));
+}
+
+#[test]
+fn should_run_with_the_root_bound() {
+    let client = MockClient::new(Syntax::Sqlite);
+    client.enqueue_rows(
+        "welds_tree",
+        vec![
+            MockRow::new()
+                .with("id", 4)
+                .with("parent", 3)
+                .with("name", "fiction"),
+            MockRow::new()
+                .with("id", 7)
+                .with("parent", 4)
+                .with("name", "sci-fi"),
+        ],
+    );
+    let rows = block_on(Category::descendants_of(3).run(&client)).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1].name, "sci-fi");
+    assert_eq!(client.queries()[0].params, vec![Value::Int(3)]);
+}
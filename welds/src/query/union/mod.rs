@@ -0,0 +1,358 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::{AsFieldName, OrderBy, ParamArgs};
+use crate::query::select_cols::SelectBuilder;
+use crate::query::tail;
+use crate::state::DbState;
+use crate::writers::in_namespace;
+use crate::writers::NextParam;
+use crate::{Client, Row, Syntax};
+
+/// A query that can be one of the parts of a UNION
+pub(crate) trait UnionPart: Send + Sync {
+    /// The SELECT of this part, numbering its params after the parts before it.
+    /// Its own limit and offset are kept, its ordering is dropped
+    fn write_part<'s, 'p>(
+        &'s self,
+        syntax: Syntax,
+        next_params: &NextParam,
+        args: &mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        's: 'p;
+}
+
+impl<T> UnionPart for QueryBuilder<T>
+where
+    T: Send + Sync + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    fn write_part<'s, 'p>(
+        &'s self,
+        syntax: Syntax,
+        next_params: &NextParam,
+        args: &mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        's: 'p,
+    {
+        let columns = <T as HasSchema>::Schema::columns();
+        let writer = self.select_writer(syntax);
+        let limited = self.limit.is_some() || self.offset.is_some();
+        if !limited {
+            return writer.sql_with_params(
+                &columns,
+                &self.wheres,
+                &self.exist_ins,
+                &None,
+                &None,
+                &[],
+                next_params,
+                args,
+            );
+        }
+        let sql = writer.sql_with_params(
+            &columns,
+            &self.wheres,
+            &self.exist_ins,
+            &self.limit,
+            &self.offset,
+            &self.orderby,
+            next_params,
+            args,
+        );
+        limited_part(&sql)
+    }
+}
+
+impl<T> UnionPart for SelectBuilder<T>
+where
+    T: Send + Sync + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    fn write_part<'s, 'p>(
+        &'s self,
+        syntax: Syntax,
+        next_params: &NextParam,
+        args: &mut Option<ParamArgs<'p>>,
+    ) -> String
+    where
+        's: 'p,
+    {
+        let limited = self.has_limit();
+        let sql = self.union_part_sql(syntax, next_params, args, limited);
+        match limited {
+            true => limited_part(&sql),
+            false => sql,
+        }
+    }
+}
+
+// a part with a limit of its own is a derived table, the limit would apply to the whole union
+fn limited_part(sql: &str) -> String {
+    format!("SELECT * FROM ( {} ) welds_part", sql)
+}
+
+fn write_union<'s, 'p>(
+    syntax: Syntax,
+    parts: &[(&'s dyn UnionPart, &'static str)],
+    orderby: &[OrderBy],
+    limit: &Option<i64>,
+    offset: &Option<i64>,
+    args: &mut Option<ParamArgs<'p>>,
+) -> String
+where
+    's: 'p,
+{
+    let next_params = NextParam::new(syntax);
+    let mut sql = Vec::default();
+    for (i, (part, op)) in parts.iter().enumerate() {
+        if i > 0 {
+            sql.push(op.to_string());
+        }
+        sql.push(part.write_part(syntax, &next_params, args));
+    }
+    if let Some(tail) = tail::write(syntax, limit, offset, orderby) {
+        sql.push(tail);
+    }
+    sql.join(" ")
+}
+
+/// Queries of the same model combined with UNION or UNION ALL.
+/// Made with `QueryBuilder::union` and `union_all`.
+///
+/// The ordering, limit, and offset apply to the combined results.
+/// A part keeps its own limit and offset, but its ordering is dropped.
+///
+/// ```rust,ignore
+/// let flagged = Order::where_col(|o| o.status.equal("flagged"))
+///     .union(Order::where_col(|o| o.total.gt(10_000)))
+///     .order_by_desc(|o| o.created_at)
+///     .limit(50)
+///     .run(&client)
+///     .await?;
+/// ```
+pub struct UnionBuilder<T> {
+    parts: Vec<(QueryBuilder<T>, &'static str)>,
+    orderby: Vec<OrderBy>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+impl<T> UnionBuilder<T>
+where
+    T: Send + Sync + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    pub(crate) fn new(first: QueryBuilder<T>) -> Self {
+        Self {
+            parts: vec![(first, "")],
+            orderby: Vec::default(),
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Adds the rows of another query, leaving out any already in the results
+    pub fn union(mut self, other: QueryBuilder<T>) -> Self {
+        self.parts.push((other, "UNION"));
+        self
+    }
+
+    /// Adds all the rows of another query, keeping duplicates
+    pub fn union_all(mut self, other: QueryBuilder<T>) -> Self {
+        self.parts.push((other, "UNION ALL"));
+        self
+    }
+
+    /// Order the combined results by a given column
+    pub fn order_by_asc<V, FN: AsFieldName<V>>(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+    ) -> Self {
+        let field = lam(Default::default());
+        self.orderby.push(OrderBy::new(field.colname(), "ASC"));
+        self
+    }
+
+    /// Order the combined results by a given column, largest first
+    pub fn order_by_desc<V, FN: AsFieldName<V>>(
+        mut self,
+        lam: impl Fn(<T as HasSchema>::Schema) -> FN,
+    ) -> Self {
+        let field = lam(Default::default());
+        self.orderby.push(OrderBy::new(field.colname(), "DESC"));
+        self
+    }
+
+    /// Limit the number of rows returned from the combined results
+    pub fn limit(mut self, x: i64) -> Self {
+        self.limit = Some(x);
+        self
+    }
+
+    /// Skip this many rows of the combined results
+    pub fn offset(mut self, x: i64) -> Self {
+        self.offset = Some(x);
+        self
+    }
+
+    fn sql_internal<'s, 'p>(&'s self, syntax: Syntax, args: &mut Option<ParamArgs<'p>>) -> String
+    where
+        's: 'p,
+    {
+        let parts: Vec<(&dyn UnionPart, &'static str)> = self
+            .parts
+            .iter()
+            .map(|(qb, op)| (qb as &dyn UnionPart, *op))
+            .collect();
+        write_union(
+            syntax,
+            &parts,
+            &self.orderby,
+            &self.limit,
+            &self.offset,
+            args,
+        )
+    }
+
+    /// Get a copy of the SQL that will be executed when this query runs
+    pub fn to_sql(&self, syntax: Syntax) -> String {
+        self.sql_internal(syntax, &mut None)
+    }
+
+    /// Executes the query in the database returning the results
+    pub async fn run(&self, client: &dyn Client) -> Result<Vec<DbState<T>>>
+    where
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let sql = in_namespace(client, || self.sql_internal(client.syntax(), &mut args));
+        let args = args.unwrap();
+        let rows = client.fetch_rows(&sql, &args).await?;
+        let mut objs = Vec::default();
+        for row in rows {
+            let obj: T = T::try_from(row)?;
+            objs.push(DbState::db_loaded(obj));
+        }
+        Ok(objs)
+    }
+}
+
+/// Projections combined with UNION or UNION ALL. Made with `SelectBuilder::union` and `union_all`.
+///
+/// The parts can select from different tables, but they need to select the same number of
+/// columns, with compatible types, in the same order. The columns are named after the first part.
+///
+/// ```rust,ignore
+/// let contacts = Customer::all()
+///     .select(|c| c.name)
+///     .select(|c| c.email)
+///     .union(Supplier::all().select(|s| s.name).select(|s| s.email))
+///     .order_by_asc("name")
+///     .run(&client)
+///     .await?;
+/// ```
+pub struct SelectUnion {
+    parts: Vec<(Box<dyn UnionPart>, &'static str)>,
+    orderby: Vec<OrderBy>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+impl SelectUnion {
+    pub(crate) fn new<T>(first: SelectBuilder<T>) -> Self
+    where
+        T: Send + Sync + HasSchema + 'static,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        Self {
+            parts: vec![(Box::new(first), "")],
+            orderby: Vec::default(),
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Adds the rows of another projection, leaving out any already in the results
+    pub fn union<R>(mut self, other: SelectBuilder<R>) -> Self
+    where
+        R: Send + Sync + HasSchema + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.parts.push((Box::new(other), "UNION"));
+        self
+    }
+
+    /// Adds all the rows of another projection, keeping duplicates
+    pub fn union_all<R>(mut self, other: SelectBuilder<R>) -> Self
+    where
+        R: Send + Sync + HasSchema + 'static,
+        <R as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.parts.push((Box::new(other), "UNION ALL"));
+        self
+    }
+
+    /// Order the combined results by one of the columns, using the name it is selected as
+    pub fn order_by_asc(mut self, column: impl Into<String>) -> Self {
+        self.orderby.push(OrderBy::new(column, "ASC"));
+        self
+    }
+
+    /// Order the combined results by one of the columns, largest first
+    pub fn order_by_desc(mut self, column: impl Into<String>) -> Self {
+        self.orderby.push(OrderBy::new(column, "DESC"));
+        self
+    }
+
+    /// Limit the number of rows returned from the combined results
+    pub fn limit(mut self, x: i64) -> Self {
+        self.limit = Some(x);
+        self
+    }
+
+    /// Skip this many rows of the combined results
+    pub fn offset(mut self, x: i64) -> Self {
+        self.offset = Some(x);
+        self
+    }
+
+    fn sql_internal<'s, 'p>(&'s self, syntax: Syntax, args: &mut Option<ParamArgs<'p>>) -> String
+    where
+        's: 'p,
+    {
+        let parts: Vec<(&dyn UnionPart, &'static str)> = self
+            .parts
+            .iter()
+            .map(|(part, op)| (part.as_ref(), *op))
+            .collect();
+        write_union(
+            syntax,
+            &parts,
+            &self.orderby,
+            &self.limit,
+            &self.offset,
+            args,
+        )
+    }
+
+    /// Get a copy of the SQL that will be executed when this query runs
+    pub fn to_sql(&self, syntax: Syntax) -> String {
+        self.sql_internal(syntax, &mut None)
+    }
+
+    /// Executes the query in the database returning the results
+    pub async fn run(&self, client: &dyn Client) -> Result<Vec<Row>> {
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let sql = in_namespace(client, || self.sql_internal(client.syntax(), &mut args));
+        let args = args.unwrap();
+        let rows = client.fetch_rows(&sql, &args).await?;
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests;
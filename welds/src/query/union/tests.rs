@@ -0,0 +1,104 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub status: String,
+    pub total: f64,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "customers")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Customer {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "suppliers")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Supplier {
+    #[welds(primary_key)]
+    pub id: i32,
+    #[welds(rename = "company")]
+    pub name: String,
+}
+
+#[test]
+fn should_number_the_params_across_the_parts() {
+    let q = Order::where_col(|o| o.status.equal("flagged"))
+        .union(Order::where_col(|o| o.total.gt(100.0)))
+        .union_all(Order::where_col(|o| o.id.lt(5)));
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."id", t1."status", t1."total" FROM orders t1 WHERE ( t1.status = $1 ) UNION SELECT t1."id", t1."status", t1."total" FROM orders t1 WHERE ( t1.total > $2 ) UNION ALL SELECT t1."id", t1."status", t1."total" FROM orders t1 WHERE ( t1.id < $3 )"#
+    );
+}
+
+#[test]
+fn should_order_and_limit_the_combined_results() {
+    let q = Order::where_col(|o| o.status.equal("flagged"))
+        .order_by_asc(|o| o.id)
+        .union(Order::where_col(|o| o.total.gt(100.0)))
+        .order_by_desc(|o| o.total)
+        .limit(10)
+        .offset(20);
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."id", t1."status", t1."total" FROM orders t1 WHERE ( t1.status = @p1 ) UNION SELECT t1."id", t1."status", t1."total" FROM orders t1 WHERE ( t1.total > @p2 ) ORDER BY total DESC OFFSET 20 ROWS FETCH FIRST 10 ROWS ONLY"#
+    );
+}
+
+#[test]
+fn should_keep_the_limit_of_a_part() {
+    let q = Order::where_col(|o| o.status.equal("flagged"))
+        .union_all(Order::all().order_by_desc(|o| o.total).limit(3));
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id, t1.status, t1.total FROM orders t1 WHERE ( t1.status = ? ) UNION ALL SELECT * FROM ( SELECT t1.id, t1.status, t1.total FROM orders t1 ORDER BY total DESC LIMIT 0, 3 ) welds_part"
+    );
+}
+
+#[test]
+fn should_combine_projections_of_different_tables() {
+    let q = Customer::where_col(|c| c.id.gt(1))
+        .select(|c| c.name)
+        .union(Supplier::where_col(|s| s.id.gt(2)).select(|s| s.name))
+        .order_by_asc("name");
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."name" FROM customers t1 WHERE ( t1.id > ? ) UNION SELECT t1."company" as "name" FROM suppliers t1 WHERE ( t1.id > ? ) ORDER BY name ASC"#
+    );
+}
+
+#[test]
+fn should_run_the_union_with_all_the_params() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows(
+        "UNION ALL",
+        vec![
+            MockRow::new()
+                .with("id", 1)
+                .with("status", "flagged")
+                .with("total", 5.0),
+            MockRow::new()
+                .with("id", 2)
+                .with("status", "new")
+                .with("total", 500.0),
+        ],
+    );
+    let q = Order::where_col(|o| o.status.equal("flagged"))
+        .union_all(Order::where_col(|o| o.total.gt(100.0)));
+    let orders = block_on(q.run(&client)).unwrap();
+    assert_eq!(orders.len(), 2);
+    assert_eq!(orders[1].status, "new");
+    assert_eq!(client.queries()[0].params.len(), 2);
+}
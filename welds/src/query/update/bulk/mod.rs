@@ -1,4 +1,4 @@
-use crate::errors::Result;
+use crate::errors::{Result, WeldsError};
 use crate::model_traits::UniqueIdentifier;
 use crate::model_traits::{HasSchema, TableColumns, TableInfo};
 use crate::query::builder::QueryBuilder;
@@ -6,10 +6,13 @@ use crate::query::clause::wherein::WhereIn;
 use crate::query::clause::ClauseAdder;
 use crate::query::clause::ParamArgs;
 use crate::query::clause::{AsFieldName, AsOptField};
-use crate::query::helpers::{build_where, join_sql_parts};
+use crate::query::helpers::{build_where, invalidate_cached, join_sql_parts};
+use crate::state::DbState;
 use crate::writers::ColumnWriter;
 use crate::writers::NextParam;
+use crate::writers::{in_namespace, table_alias, table_name};
 use crate::Client;
+use crate::Row;
 use crate::Syntax;
 use std::marker::PhantomData;
 use welds_connections::Param;
@@ -53,6 +56,17 @@ where
         self
     }
 
+    /// sets the column to an expression of its current value, such as
+    /// `set_expr(|t| t.counter.increment(1))` writing `SET counter=counter + 1`
+    pub fn set_expr(mut self, lam: impl Fn(<T as HasSchema>::Schema) -> SetExpr) -> Self
+    where
+        <T as HasSchema>::Schema: Default,
+    {
+        let expr = lam(Default::default());
+        self.sets.push(Box::new(expr));
+        self
+    }
+
     /// Nulls out the value from the lambda in the database
     pub fn set_null<V, FIELD>(mut self, lam: impl Fn(<T as HasSchema>::Schema) -> FIELD) -> Self
     where
@@ -73,7 +87,7 @@ where
     {
         let mut w_in = WhereIn::new(&self.query_builder);
 
-        self.sql_internal(syntax, &mut w_in, &mut None)
+        self.sql_internal(syntax, &mut w_in, &mut None, false)
     }
 
     fn sql_internal<'s, 'w, 'args, 'p>(
@@ -81,6 +95,7 @@ where
         syntax: Syntax,
         w_in: &'w mut WhereIn<T>,
         args: &'args mut Option<ParamArgs<'p>>,
+        returning: bool,
     ) -> String
     where
         'w: 'p,
@@ -89,10 +104,18 @@ where
     {
         let next_params = NextParam::new(syntax);
         let sets = self.sets.as_slice();
-        let alias = <T as HasSchema>::Schema::identifier().join(".");
+        let alias = table_alias(&<T as HasSchema>::Schema::identifier().join("."));
+
+        // MSSQL outputs the rows before the WHERE, everyone else returns them at the end
+        let (output, returning) = match (returning, syntax) {
+            (false, _) => (None, None),
+            (true, Syntax::Mssql) => (Some(build_output::<<T as HasSchema>::Schema>()), None),
+            (true, _) => (None, Some("RETURNING *".to_owned())),
+        };
 
         join_sql_parts(&[
             build_head::<<T as HasSchema>::Schema>(syntax, &next_params, &alias, args, sets),
+            output,
             build_where_update(
                 syntax,
                 w_in,
@@ -101,22 +124,106 @@ where
                 args,
                 &self.query_builder,
             ),
+            returning,
         ])
     }
 
     /// Executes the query in the database Bulk updating the values
     pub async fn run<'s, 'c>(&'s self, client: &'c dyn Client) -> Result<()>
+    where
+        <T as HasSchema>::Schema: UniqueIdentifier + TableInfo + TableColumns,
+    {
+        self.run_count(client).await?;
+        Ok(())
+    }
+
+    /// Executes the query in the database Bulk updating the values.
+    /// Returns the number of rows that were updated
+    pub async fn run_count(&self, client: &dyn Client) -> Result<u64>
     where
         <T as HasSchema>::Schema: UniqueIdentifier + TableInfo + TableColumns,
     {
         let syntax = client.syntax();
         let mut args: Option<ParamArgs> = Some(Vec::default());
         let mut w_in = WhereIn::new(&self.query_builder);
-        let sql = self.sql_internal(syntax, &mut w_in, &mut args);
+        let sql = in_namespace(client, || {
+            self.sql_internal(syntax, &mut w_in, &mut args, false)
+        });
         let args = args.unwrap();
-        let _results = client.execute(&sql, &args).await?;
+        let results = client.execute(&sql, &args).await?;
+        invalidate_cached::<T>(client).await;
+        Ok(results.rows_affected())
+    }
 
-        Ok(())
+    /// Executes the query in the database Bulk updating the values.
+    /// Returns the rows as they are after the update.
+    ///
+    /// Uses `RETURNING` (`OUTPUT` on MSSQL). Not available on MySql and MariaDb
+    pub async fn run_returning(&self, client: &dyn Client) -> Result<Vec<DbState<T>>>
+    where
+        <T as HasSchema>::Schema: UniqueIdentifier + TableInfo + TableColumns,
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let syntax = client.syntax();
+        if matches!(syntax, Syntax::Mysql | Syntax::MariaDb) {
+            return Err(WeldsError::UpdateReturningUnsupported(syntax));
+        }
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let mut w_in = WhereIn::new(&self.query_builder);
+        let sql = in_namespace(client, || {
+            self.sql_internal(syntax, &mut w_in, &mut args, true)
+        });
+        let args = args.unwrap();
+        let rows = client.fetch_rows(&sql, &args).await?;
+        invalidate_cached::<T>(client).await;
+        let mut objs = Vec::default();
+        for row in rows {
+            let obj: T = T::try_from(row)?;
+            objs.push(DbState::db_loaded(obj));
+        }
+        Ok(objs)
+    }
+}
+
+impl<T> QueryBuilder<T>
+where
+    T: Send + HasSchema,
+{
+    /// Updates all the rows of this query with a single UPDATE, returning how many were updated
+    ///
+    /// ```rust,ignore
+    /// let count = Order::where_col(|o| o.status.equal("pending"))
+    ///     .update_all(&client, |set| set.set(|o| o.status, "late").set_expr(|o| o.retries.increment(1)))
+    ///     .await?;
+    /// ```
+    pub async fn update_all(
+        self,
+        client: &dyn Client,
+        set: impl FnOnce(UpdateBuilder<T>) -> UpdateBuilder<T>,
+    ) -> Result<u64>
+    where
+        <T as HasSchema>::Schema: UniqueIdentifier + TableInfo + TableColumns,
+    {
+        let ub = set(UpdateBuilder::new(self));
+        ub.run_count(client).await
+    }
+
+    /// Updates all the rows of this query with a single UPDATE, returning the updated rows.
+    ///
+    /// Uses `RETURNING` (`OUTPUT` on MSSQL). Not available on MySql and MariaDb
+    pub async fn update_all_returning(
+        self,
+        client: &dyn Client,
+        set: impl FnOnce(UpdateBuilder<T>) -> UpdateBuilder<T>,
+    ) -> Result<Vec<DbState<T>>>
+    where
+        <T as HasSchema>::Schema: UniqueIdentifier + TableInfo + TableColumns,
+        T: TryFrom<Row>,
+        WeldsError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let ub = set(UpdateBuilder::new(self));
+        ub.run_returning(client).await
     }
 }
 
@@ -131,7 +238,7 @@ where
     's: 'p,
     S: TableInfo + TableColumns,
 {
-    let tn = S::identifier().join(".");
+    let tn = table_name(&S::identifier().join("."));
 
     let mut set_parts: Vec<String> = Vec::default();
 
@@ -148,6 +255,19 @@ where
     Some(format!("UPDATE {tn} SET {sets}", tn = tn, sets = set_sql))
 }
 
+// the updated rows, as MSSQL writes them
+pub(crate) fn build_output<S>() -> String
+where
+    S: TableColumns,
+{
+    let writer = ColumnWriter::new(Syntax::Mssql);
+    let cols: Vec<String> = S::columns()
+        .iter()
+        .map(|c| writer.write("Inserted", c))
+        .collect();
+    format!("OUTPUT {}", cols.join(", "))
+}
+
 pub struct SetColVal<T> {
     pub col_raw: String,
     pub val: T,
@@ -173,6 +293,51 @@ where
     }
 }
 
+/// A new value for a column computed from its current value.
+/// Made with `increment`, `decrement`, `mul`, and `div` on a numeric column
+pub struct SetExpr {
+    col_raw: String,
+    operator: &'static str,
+    val: Box<dyn Param + Send + Sync>,
+}
+
+impl SetExpr {
+    pub(crate) fn new(
+        col_raw: impl Into<String>,
+        operator: &'static str,
+        val: impl Param + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            col_raw: col_raw.into(),
+            operator,
+            val: Box::new(val),
+        }
+    }
+}
+
+impl ClauseAdder for SetExpr {
+    /// Add the argument to the list of Arguments to send to the database
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        args.push(self.val.as_ref());
+    }
+
+    /// Returns the SQL snipit for this clause
+    fn clause(&self, syntax: Syntax, _alias: &str, next_params: &NextParam) -> Option<String> {
+        let colname = ColumnWriter::new(syntax).excape(&self.col_raw);
+        let sql = format!(
+            "{}={} {} {}",
+            colname,
+            colname,
+            self.operator,
+            next_params.next()
+        );
+        Some(sql)
+    }
+}
+
 pub struct SetColNull {
     pub col_raw: String,
 }
@@ -223,7 +388,7 @@ where
 
     // use fulltable name for alias when updating
     let tableparts = T::Schema::identifier();
-    let outer_tablealias = tableparts.join(".");
+    let outer_tablealias = table_alias(&tableparts.join("."));
 
     if let Some(p) = w_in.clause(syntax, &outer_tablealias, next_params) {
         where_sql.push(p);
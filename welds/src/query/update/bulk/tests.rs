@@ -1,5 +1,7 @@
+use crate::connections::mock::{MockClient, MockRow};
 use crate::query::builder::QueryBuilder;
 use crate::Syntax;
+use futures::executor::block_on;
 
 // Testing that the tail end of the SQL is correct
 // Limits / skips / orders
@@ -60,3 +62,80 @@ fn should_be_able_to_write_complex_set_value() {
         assert_eq!(client.args_count().unwrap(), 3);
     });
 }
+
+#[test]
+fn should_be_able_to_set_an_expression_of_the_current_value() {
+    futures::executor::block_on(async move {
+        let q = QueryBuilder::<Product>::new().where_col(|c| c.id.gt(10));
+        let bulk = q.set_expr(|p| p.a.increment(1)).set_expr(|p| p.b.mul(3));
+
+        let client = welds_connections::noop::build(Syntax::Postgres);
+        let _ = bulk.run(&client).await;
+
+        let ran_sql = client
+            .last_sql()
+            .expect("Expected to get SQL back from client");
+
+        let expected = "UPDATE nums SET \"a\"=\"a\" + $1, \"b\"=\"b\" * $2 WHERE ( nums.id > $3 )";
+        assert_eq!(expected, &ran_sql);
+
+        assert_eq!(client.args_count().unwrap(), 3);
+    });
+}
+
+#[test]
+fn should_be_able_to_mix_expressions_and_values() {
+    let q = QueryBuilder::<Product>::new();
+    let bulk = q.set(|p| p.a, 0).set_expr(|p| p.b.decrement(2));
+    assert_eq!(bulk.to_sql(Syntax::Mysql), "UPDATE nums SET a=?, b=b - ?");
+}
+
+#[test]
+fn should_return_the_number_of_rows_updated() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows_affected("UPDATE", 4);
+    let q = Product::where_col(|p| p.b.gt(2));
+    let count = block_on(q.update_all(&client, |set| set.set(|p| p.a, 0))).unwrap();
+    assert_eq!(count, 4);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "UPDATE nums SET \"a\"=$1 WHERE ( nums.b > $2 )"
+    );
+}
+
+#[test]
+fn should_return_the_updated_rows() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let row = MockRow::new().with("id", 1).with("a", 6).with("b", 3);
+    client.enqueue_rows("RETURNING", vec![row]);
+    let q = Product::where_col(|p| p.b.gt(2));
+    let update = q.update_all_returning(&client, |set| set.set_expr(|p| p.a.increment(1)));
+    let rows = block_on(update).unwrap();
+    assert_eq!(rows[0].a, 6);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "UPDATE nums SET \"a\"=\"a\" + ? WHERE ( nums.b > ? ) RETURNING *"
+    );
+}
+
+#[test]
+fn should_output_the_updated_rows_on_mssql() {
+    let client = MockClient::new(Syntax::Mssql);
+    let q = Product::where_col(|p| p.b.gt(2)).set(|p| p.a, 0);
+    block_on(q.run_returning(&client)).unwrap();
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "UPDATE nums SET \"a\"=@p1 OUTPUT Inserted.\"id\", Inserted.\"a\", Inserted.\"b\" WHERE ( nums.b > @p2 )"
+    );
+}
+
+#[test]
+fn should_not_return_updated_rows_on_mysql() {
+    let client = MockClient::new(Syntax::Mysql);
+    let q = Product::all().set(|p| p.a, 0);
+    let result = block_on(q.run_returning(&client));
+    assert!(matches!(
+        result,
+        Err(crate::WeldsError::UpdateReturningUnsupported(Syntax::Mysql))
+    ));
+}
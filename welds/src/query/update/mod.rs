@@ -2,3 +2,4 @@ pub mod bulk;
 mod single;
 
 pub use single::update_one;
+pub(crate) use single::update_columns;
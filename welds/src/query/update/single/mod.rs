@@ -2,11 +2,31 @@ use crate::errors::{Result, WeldsError};
 use crate::model_traits::hooks::{AfterUpdate, BeforeUpdate};
 use crate::model_traits::{HasSchema, TableColumns, TableInfo, UpdateFromRow, WriteToArgs};
 use crate::query::clause::ParamArgs;
+use crate::query::helpers::{invalidate_cached, push_default_scope};
+use crate::query::update::bulk::build_output;
+use crate::relations::{join_parents, leave_parents, parents_transaction};
 use crate::writers::ColumnWriter;
 use crate::writers::NextParam;
+use crate::writers::{in_namespace, table_alias, table_name};
+use crate::Syntax;
 use welds_connections::Client;
 
 pub async fn update_one<T>(obj: &mut T, client: &dyn Client) -> Result<()>
+where
+    T: WriteToArgs + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    T: UpdateFromRow,
+    T: AfterUpdate + BeforeUpdate,
+{
+    update_columns(obj, None, client).await
+}
+
+/// Updates the row for obj. When `only` is given, just those columns are written.
+pub(crate) async fn update_columns<T>(
+    obj: &mut T,
+    only: Option<&[String]>,
+    client: &dyn Client,
+) -> Result<()>
 where
     T: WriteToArgs + HasSchema,
     <T as HasSchema>::Schema: TableInfo + TableColumns,
@@ -14,22 +34,49 @@ where
     T: AfterUpdate + BeforeUpdate,
 {
     BeforeUpdate::before(obj)?;
+    // the row and the parents it counts are written together
+    match parents_transaction::<T>(client) {
+        Some(start) => {
+            let trans = start.begin().await?;
+            update_with_parents(obj, only, &trans).await?;
+            trans.commit().await?;
+        }
+        None => update_with_parents(obj, only, client).await?,
+    }
+    AfterUpdate::after(obj);
+    Ok(())
+}
 
+async fn update_with_parents<T>(
+    obj: &mut T,
+    only: Option<&[String]>,
+    client: &dyn Client,
+) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + UpdateFromRow,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
     let syntax = client.syntax();
+    let scope = <<T as HasSchema>::Schema as TableInfo>::default_scope();
     let mut args: ParamArgs = Vec::default();
     let col_writer = ColumnWriter::new(syntax);
     let next_params = NextParam::new(syntax);
 
-    let identifier = <<T as HasSchema>::Schema>::identifier().join(".");
+    let table = <<T as HasSchema>::Schema>::identifier().join(".");
+    let identifier = in_namespace(client, || table_name(&table));
+    let alias = in_namespace(client, || table_alias(&table));
     let columns = <<T as HasSchema>::Schema as TableColumns>::columns();
     let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
     if pks.is_empty() {
         return Err(WeldsError::NoPrimaryKey);
     }
+    let version = <<T as HasSchema>::Schema as TableColumns>::version_column();
     let mut sets = Vec::default();
 
+    let selected = |col: &str| only.map(|o| o.iter().any(|c| c == col)).unwrap_or(true);
+
     for col in columns {
-        if !pks.contains(&col) {
+        if !pks.contains(&col) && selected(col.name()) && version != Some(col.name()) {
             obj.bind(col.name(), &mut args)?;
             let p = next_params.next();
             let colname = col_writer.excape(col.name());
@@ -40,22 +87,85 @@ where
     if sets.is_empty() {
         return Ok(());
     }
+    if let Some(version) = version {
+        let colname = col_writer.excape(version);
+        sets.push(format!("{}={}+1", colname, colname));
+    }
     let mut wheres = Vec::default();
-    for col in pks {
+    for col in &pks {
         obj.bind(col.name(), &mut args)?;
         let p = next_params.next();
         let colname = col_writer.excape(col.name());
         wheres.push(format!("{}={}", colname, p));
     }
+    push_default_scope(&scope, syntax, &alias, &next_params, &mut wheres, &mut args);
+
+    // the parents the row moves away from are counted down while it still points at them
+    let moved = leave_parents(&*obj, only, client).await?;
+
+    let version = match version {
+        Some(version) => version,
+        None => {
+            let sets = sets.join(", ");
+            let wheres = wheres.join(" AND ");
+            let sql = format!("UPDATE {} SET {} where {}", identifier, sets, wheres);
+            client.execute(&sql, &args).await?;
+            invalidate_cached::<T>(client).await;
+            join_parents(&*obj, &moved, client).await?;
+            return Ok(());
+        }
+    };
+
+    // only the version that was read is written over
+    obj.bind(version, &mut args)?;
+    let p = next_params.next();
+    wheres.push(format!("{}={}", col_writer.excape(version), p));
 
     let sets = sets.join(", ");
     let wheres = wheres.join(" AND ");
 
-    let sql = format!("UPDATE {} SET {} where {}", identifier, sets, wheres);
-
-    client.execute(&sql, &args).await?;
+    // the row is read back for its new version. No row means it was updated or deleted since
+    let mut rows = match syntax {
+        Syntax::Mssql => {
+            let output = build_output::<<T as HasSchema>::Schema>();
+            let sql = format!(
+                "UPDATE {} SET {} {} where {}",
+                identifier, sets, output, wheres
+            );
+            client.fetch_rows(&sql, &args).await?
+        }
+        // MariaDB has no RETURNING on an UPDATE
+        Syntax::Mysql | Syntax::MariaDb => {
+            let sql = format!("UPDATE {} SET {} where {}", identifier, sets, wheres);
+            let updated = client.execute(&sql, &args).await?;
+            if updated.rows_affected() == 0 {
+                return Err(WeldsError::StaleRecord);
+            }
+            let mut args: ParamArgs = Vec::default();
+            let next_params = NextParam::new(syntax);
+            let mut wheres = Vec::default();
+            for col in &pks {
+                obj.bind(col.name(), &mut args)?;
+                let colname = col_writer.excape(col.name());
+                wheres.push(format!("{}={}", colname, next_params.next()));
+            }
+            let wheres = wheres.join(" AND ");
+            let sql = format!("SELECT * FROM {} where {}", identifier, wheres);
+            client.fetch_rows(&sql, &args).await?
+        }
+        _ => {
+            let sql = format!(
+                "UPDATE {} SET {} where {} RETURNING *",
+                identifier, sets, wheres
+            );
+            client.fetch_rows(&sql, &args).await?
+        }
+    };
 
-    AfterUpdate::after(obj);
+    let row = rows.first_mut().ok_or(WeldsError::StaleRecord)?;
+    obj.update_from_row(row)?;
+    invalidate_cached::<T>(client).await;
+    join_parents(&*obj, &moved, client).await?;
     Ok(())
 }
 
@@ -45,3 +45,94 @@ fn changing_a_value_should_update() {
         assert_eq!(expected, &ran_sql);
     });
 }
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "docs")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Doc {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub body: String,
+    #[welds(version)]
+    pub version: i32,
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_update_only_the_version_that_was_read() {
+    use crate::connections::mock::{MockClient, MockRow};
+
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Postgres);
+        let row = MockRow::new()
+            .with("id", 1)
+            .with("body", "new")
+            .with("version", 4);
+        client.enqueue_rows("RETURNING", vec![row]);
+        let mut doc = DbState::db_loaded(Doc {
+            id: 1,
+            body: "old".to_owned(),
+            version: 3,
+        });
+        doc.body = "new".to_owned();
+        doc.save(&client).await.unwrap();
+
+        let expected = "UPDATE docs SET \"body\"=$1, \"version\"=\"version\"+1 where \"id\"=$2 AND \"version\"=$3 RETURNING *";
+        assert_eq!(client.last_sql().unwrap(), expected);
+        assert_eq!(doc.version, 4);
+    });
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_fail_to_update_a_stale_row() {
+    use crate::connections::mock::MockClient;
+
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Postgres);
+        let mut doc = DbState::db_loaded(Doc::default());
+        doc.body = "new".to_owned();
+        let err = doc.save(&client).await.unwrap_err();
+        assert!(matches!(err, crate::WeldsError::StaleRecord));
+    });
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_read_back_the_version_on_mysql() {
+    read_back_the_version(Syntax::Mysql);
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn should_read_back_the_version_on_mariadb() {
+    read_back_the_version(Syntax::MariaDb);
+}
+
+#[cfg(feature = "mock")]
+fn read_back_the_version(syntax: Syntax) {
+    use crate::connections::mock::{MockClient, MockRow};
+
+    futures::executor::block_on(async move {
+        let client = MockClient::new(syntax);
+        client.enqueue_rows_affected("UPDATE", 1);
+        let row = MockRow::new()
+            .with("id", 0)
+            .with("body", "new")
+            .with("version", 1);
+        client.enqueue_rows("SELECT", vec![row]);
+        let mut doc = DbState::db_loaded(Doc::default());
+        doc.body = "new".to_owned();
+        doc.save(&client).await.unwrap();
+
+        let sql: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+        assert_eq!(
+            sql,
+            vec![
+                "UPDATE docs SET body=?, version=version+1 where id=? AND version=?",
+                "SELECT * FROM docs where id=?",
+            ]
+        );
+        assert_eq!(doc.version, 1);
+    });
+}
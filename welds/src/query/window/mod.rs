@@ -0,0 +1,174 @@
+use crate::query::clause::AsFieldName;
+use crate::writers::ColumnWriter;
+use crate::Syntax;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Func {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag { col: String, offset: i64 },
+    Lead { col: String, offset: i64 },
+}
+
+/// A window function, selected next to the columns of a query with `select_window`.
+///
+/// The function is computed over the rows that share the values of the `partition_by`
+/// columns (or over all the rows if there are none), in the order of its own `order_by`.
+///
+/// ```rust,ignore
+/// use welds::query::window::{lag, rank};
+///
+/// // a leaderboard for each game
+/// let rows = Score::all()
+///     .select(|s| s.game_id)
+///     .select(|s| s.player_id)
+///     .select_window(|s| rank().partition_by(&s.game_id).order_by_desc(&s.points).alias("place"))
+///     .run(&client)
+///     .await?;
+/// // SELECT t1.game_id, t1.player_id,
+/// //   RANK() OVER (PARTITION BY t1.game_id ORDER BY t1.points DESC) AS place FROM scores t1
+/// ```
+#[derive(Debug, Clone)]
+pub struct Window {
+    func: Func,
+    partitions: Vec<String>,
+    orders: Vec<(String, &'static str)>,
+    alias: String,
+}
+
+impl Window {
+    fn new(func: Func, alias: String) -> Self {
+        Self {
+            func,
+            partitions: Vec::default(),
+            orders: Vec::default(),
+            alias,
+        }
+    }
+
+    /// The name this window function is selected as
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.alias = alias.into();
+        self
+    }
+
+    /// Computes the function separately for each value of this column.
+    /// Multiple calls partition by all the columns
+    pub fn partition_by<V>(mut self, field: impl AsFieldName<V>) -> Self {
+        self.partitions.push(field.colname().to_owned());
+        self
+    }
+
+    /// Orders the rows of each partition by this column
+    pub fn order_by_asc<V>(mut self, field: impl AsFieldName<V>) -> Self {
+        self.orders.push((field.colname().to_owned(), "ASC"));
+        self
+    }
+
+    /// Orders the rows of each partition by this column, largest first
+    pub fn order_by_desc<V>(mut self, field: impl AsFieldName<V>) -> Self {
+        self.orders.push((field.colname().to_owned(), "DESC"));
+        self
+    }
+
+    /// writes the window function and its alias
+    pub(crate) fn write(&self, syntax: Syntax, tablealias: &str) -> String {
+        let writer = ColumnWriter::new(syntax);
+        let col = |c: &str| format!("{}.{}", tablealias, writer.excape(c));
+        let func = match &self.func {
+            Func::RowNumber => "ROW_NUMBER()".to_owned(),
+            Func::Rank => "RANK()".to_owned(),
+            Func::DenseRank => "DENSE_RANK()".to_owned(),
+            Func::Lag { col: c, offset } => format!("LAG({}, {})", col(c), offset),
+            Func::Lead { col: c, offset } => format!("LEAD({}, {})", col(c), offset),
+        };
+
+        let mut over = Vec::default();
+        if !self.partitions.is_empty() {
+            let cols: Vec<String> = self.partitions.iter().map(|c| col(c)).collect();
+            over.push(format!("PARTITION BY {}", cols.join(", ")));
+        }
+        if !self.orders.is_empty() {
+            let cols: Vec<String> = self
+                .orders
+                .iter()
+                .map(|(c, dir)| format!("{} {}", col(c), dir))
+                .collect();
+            over.push(format!("ORDER BY {}", cols.join(", ")));
+        } else if syntax == Syntax::Mssql {
+            // MSSQL won't run these functions without an ORDER BY
+            over.push("ORDER BY (SELECT NULL)".to_owned());
+        }
+
+        format!(
+            "{} OVER ({}) AS {}",
+            func,
+            over.join(" "),
+            quote_alias(syntax, &self.alias)
+        )
+    }
+}
+
+/// The position of the row in its partition, starting at 1. Selected as `row_number`
+pub fn row_number() -> Window {
+    Window::new(Func::RowNumber, "row_number".to_owned())
+}
+
+/// The position of the row in its partition, with ties sharing a rank and leaving gaps after them.
+/// Selected as `rank`
+pub fn rank() -> Window {
+    Window::new(Func::Rank, "rank".to_owned())
+}
+
+/// Like `rank`, without gaps after ties. Selected as `dense_rank`
+pub fn dense_rank() -> Window {
+    Window::new(Func::DenseRank, "dense_rank".to_owned())
+}
+
+/// The value of the column `offset` rows before this one in the partition, NULL if there isn't one.
+/// Selected as `lag_{field}`
+pub fn lag<V>(field: impl AsFieldName<V>, offset: i64) -> Window {
+    let alias = format!("lag_{}", field.fieldname());
+    let col = field.colname().to_owned();
+    Window::new(Func::Lag { col, offset }, alias)
+}
+
+/// The value of the column `offset` rows after this one in the partition, NULL if there isn't one.
+/// Selected as `lead_{field}`
+pub fn lead<V>(field: impl AsFieldName<V>, offset: i64) -> Window {
+    let alias = format!("lead_{}", field.fieldname());
+    let col = field.colname().to_owned();
+    Window::new(Func::Lead { col, offset }, alias)
+}
+
+/// A filter on the value of a window function, applied once it has been computed
+#[derive(Debug, Clone)]
+pub(crate) struct WindowFilter {
+    pub(crate) alias: String,
+    pub(crate) operator: &'static str,
+    pub(crate) value: i64,
+}
+
+impl WindowFilter {
+    pub(crate) fn write(&self, syntax: Syntax, tablealias: &str) -> String {
+        format!(
+            "{}.{} {} {}",
+            tablealias,
+            quote_alias(syntax, &self.alias),
+            self.operator,
+            self.value
+        )
+    }
+}
+
+// The default aliases (rank, row_number, ...) are reserved words in MySQL, so they are always quoted
+fn quote_alias(syntax: Syntax, alias: &str) -> String {
+    match syntax {
+        Syntax::Mysql | Syntax::MariaDb => format!("`{}`", alias),
+        _ => ColumnWriter::new(syntax).excape(alias),
+    }
+}
+
+#[cfg(test)]
+mod tests;
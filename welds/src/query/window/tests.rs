@@ -0,0 +1,129 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::value::Value;
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "scores")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Score {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub game_id: i32,
+    pub player_id: i32,
+    pub points: i32,
+}
+
+#[test]
+fn should_select_a_rank_over_a_partition() {
+    let q = Score::all()
+        .select(|s| s.game_id)
+        .select(|s| s.player_id)
+        .select_window(|s| {
+            rank()
+                .partition_by(&s.game_id)
+                .order_by_desc(&s.points)
+                .alias("place")
+        });
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT t1."game_id", t1."player_id", RANK() OVER (PARTITION BY t1."game_id" ORDER BY t1."points" DESC) AS "place" FROM scores t1"#
+    );
+}
+
+#[test]
+fn should_quote_the_default_names_on_mysql() {
+    let q = Score::all()
+        .select(|s| s.id)
+        .select_window(|s| row_number().order_by_asc(&s.points))
+        .select_window(|s| dense_rank().order_by_asc(&s.points));
+    assert_eq!(
+        q.to_sql(Syntax::Mysql),
+        "SELECT t1.id, ROW_NUMBER() OVER (ORDER BY t1.points ASC) AS `row_number`, DENSE_RANK() OVER (ORDER BY t1.points ASC) AS `dense_rank` FROM scores t1"
+    );
+}
+
+#[test]
+fn should_select_lag_and_lead() {
+    let q = Score::all()
+        .select(|s| s.points)
+        .select_window(|s| {
+            lag(&s.points, 1)
+                .partition_by(&s.player_id)
+                .order_by_asc(&s.id)
+        })
+        .select_window(|s| {
+            lead(&s.points, 2)
+                .partition_by(&s.player_id)
+                .order_by_asc(&s.id)
+        });
+    assert_eq!(
+        q.to_sql(Syntax::Sqlite),
+        r#"SELECT t1."points", LAG(t1."points", 1) OVER (PARTITION BY t1."player_id" ORDER BY t1."id" ASC) AS "lag_points", LEAD(t1."points", 2) OVER (PARTITION BY t1."player_id" ORDER BY t1."id" ASC) AS "lead_points" FROM scores t1"#
+    );
+}
+
+#[test]
+fn should_give_mssql_an_order_when_there_is_none() {
+    let q = Score::all()
+        .select(|s| s.id)
+        .select_window(|s| row_number().partition_by(&s.game_id));
+    assert_eq!(
+        q.to_sql(Syntax::Mssql),
+        r#"SELECT t1."id", ROW_NUMBER() OVER (PARTITION BY t1."game_id" ORDER BY (SELECT NULL)) AS "row_number" FROM scores t1"#
+    );
+}
+
+#[test]
+fn should_filter_on_a_window_in_an_outer_query() {
+    let q = Score::where_col(|s| s.points.gt(0))
+        .select(|s| s.game_id)
+        .select(|s| s.player_id)
+        .select(|s| s.points)
+        .select_window(|s| {
+            row_number()
+                .partition_by(&s.player_id)
+                .order_by_desc(&s.points)
+        })
+        .where_window_eq("row_number", 1)
+        .order_by_desc(|s| s.points)
+        .limit(10);
+    assert_eq!(
+        q.to_sql(Syntax::Postgres),
+        r#"SELECT * FROM ( SELECT t1."game_id", t1."player_id", t1."points", ROW_NUMBER() OVER (PARTITION BY t1."player_id" ORDER BY t1."points" DESC) AS "row_number" FROM scores t1 WHERE ( t1.points > $1 ) ) t1 WHERE ( t1."row_number" = 1 ) ORDER BY points DESC OFFSET 0 LIMIT 10"#
+    );
+}
+
+#[test]
+fn should_keep_the_top_of_each_partition() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.enqueue_rows(
+        "RANK()",
+        vec![
+            MockRow::new()
+                .with("game_id", Value::Int(1))
+                .with("place", Value::Int(1)),
+            MockRow::new()
+                .with("game_id", Value::Int(1))
+                .with("place", Value::Int(2)),
+        ],
+    );
+    let q = Score::all()
+        .select(|s| s.game_id)
+        .select_window(|s| {
+            rank()
+                .partition_by(&s.game_id)
+                .order_by_desc(&s.points)
+                .alias("place")
+        })
+        .where_window_lte("place", 3);
+    let rows = block_on(q.run(&client)).unwrap();
+    assert_eq!(rows.len(), 2);
+    let place: i64 = rows[1].get("place").unwrap();
+    assert_eq!(place, 2);
+    assert!(client
+        .last_sql()
+        .unwrap()
+        .ends_with(r#") t1 WHERE ( t1."place" <= 3 )"#));
+}
@@ -0,0 +1,57 @@
+use crate::model_traits::{TableInfo, UniqueIdentifier};
+
+/// The `counter_cache` of a `HasMany`: a column on the parent counting the child rows pointing at it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CounterCache {
+    /// The table of the child rows
+    pub child_table: String,
+    /// The column of the child table pointing at the parent
+    pub foreign_key: String,
+    /// The column of the parent holding the count
+    pub counter: String,
+}
+
+impl CounterCache {
+    /// A counter of the rows of the child schema `C`
+    pub fn new<C: TableInfo>(foreign_key: &str, counter: &str) -> CounterCache {
+        CounterCache {
+            child_table: C::identifier().join("."),
+            foreign_key: foreign_key.to_owned(),
+            counter: counter.to_owned(),
+        }
+    }
+}
+
+/// A counter on the parent of a row, counted up when the row is inserted and down when it is deleted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParentCounter {
+    /// The table of the parent
+    pub table: String,
+    /// The primary key of the parent
+    pub primary_key: String,
+    /// The column of the parent holding the count
+    pub counter: String,
+    /// The column of the row pointing at the parent
+    pub foreign_key: String,
+}
+
+impl ParentCounter {
+    /// The counters the parent schema `P` keeps of the rows of `child` pointing at it
+    /// with `foreign_key`
+    pub fn of<P>(child: &[&str], foreign_key: &str) -> Vec<ParentCounter>
+    where
+        P: TableInfo + UniqueIdentifier,
+    {
+        let child = child.join(".");
+        P::counter_caches()
+            .into_iter()
+            .filter(|c| c.child_table == child && c.foreign_key == foreign_key)
+            .map(|c| ParentCounter {
+                table: P::identifier().join("."),
+                primary_key: P::id_column().name().to_owned(),
+                counter: c.counter,
+                foreign_key: c.foreign_key,
+            })
+            .collect()
+    }
+}
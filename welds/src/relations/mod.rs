@@ -1,9 +1,23 @@
-use crate::model_traits::UniqueIdentifier;
+use crate::model_traits::{TableInfo, UniqueIdentifier};
 use std::marker::PhantomData;
 
+mod counter_cache;
+pub use counter_cache::{CounterCache, ParentCounter};
+mod parents;
+pub(crate) use parents::{
+    has_parents, join_parents, leave_parents, leave_parents_of, parents_transaction,
+    recount_parents, update_parents,
+};
+mod through;
+pub(crate) use through::{read_link, LinkKey};
+pub use through::{BelongsToMany, Through};
+mod touch;
+pub use touch::Touch;
+
 pub struct BelongsTo<T> {
     _t: PhantomData<T>,
     foreign_key: &'static str,
+    shared: &'static [&'static str],
 }
 
 impl<T> BelongsTo<T> {
@@ -11,8 +25,15 @@ impl<T> BelongsTo<T> {
         BelongsTo {
             _t: Default::default(),
             foreign_key: fk,
+            shared: &[],
         }
     }
+
+    /// Columns that have to match in both tables as well, for a key over several columns
+    pub fn sharing(mut self, columns: &'static [&'static str]) -> BelongsTo<T> {
+        self.shared = columns;
+        self
+    }
 }
 
 impl<R> Relationship<R> for BelongsTo<R> {
@@ -30,11 +51,15 @@ impl<R> Relationship<R> for BelongsTo<R> {
     {
         ME::id_column().name().to_owned()
     }
+    fn shared_keys(&self) -> &'static [&'static str] {
+        self.shared
+    }
 }
 
 pub struct HasMany<T> {
     _t: PhantomData<T>,
     foreign_key: &'static str,
+    shared: &'static [&'static str],
 }
 
 impl<T> HasMany<T> {
@@ -42,8 +67,15 @@ impl<T> HasMany<T> {
         HasMany {
             _t: Default::default(),
             foreign_key: fk,
+            shared: &[],
         }
     }
+
+    /// Columns that have to match in both tables as well, for a key over several columns
+    pub fn sharing(mut self, columns: &'static [&'static str]) -> HasMany<T> {
+        self.shared = columns;
+        self
+    }
 }
 
 impl<R> Relationship<R> for HasMany<R> {
@@ -61,6 +93,52 @@ impl<R> Relationship<R> for HasMany<R> {
     {
         self.foreign_key.to_owned()
     }
+    fn shared_keys(&self) -> &'static [&'static str] {
+        self.shared
+    }
+}
+
+/// Like a `HasMany`, for when there is at most one row pointing at this one
+pub struct HasOne<T> {
+    _t: PhantomData<T>,
+    foreign_key: &'static str,
+    shared: &'static [&'static str],
+}
+
+impl<T> HasOne<T> {
+    pub fn using(fk: &'static str) -> HasOne<T> {
+        HasOne {
+            _t: Default::default(),
+            foreign_key: fk,
+            shared: &[],
+        }
+    }
+
+    /// Columns that have to match in both tables as well, for a key over several columns
+    pub fn sharing(mut self, columns: &'static [&'static str]) -> HasOne<T> {
+        self.shared = columns;
+        self
+    }
+}
+
+impl<R> Relationship<R> for HasOne<R> {
+    fn my_key<ME, THEM>(&self) -> String
+    where
+        ME: UniqueIdentifier,
+        THEM: UniqueIdentifier,
+    {
+        THEM::id_column().name().to_owned()
+    }
+    fn their_key<ME, THEM>(&self) -> String
+    where
+        ME: UniqueIdentifier,
+        THEM: UniqueIdentifier,
+    {
+        self.foreign_key.to_owned()
+    }
+    fn shared_keys(&self) -> &'static [&'static str] {
+        self.shared
+    }
 }
 
 pub trait Relationship<R> {
@@ -73,10 +151,79 @@ pub trait Relationship<R> {
     where
         T: UniqueIdentifier,
         R2: UniqueIdentifier;
+
+    /// The join table the relationship goes through, for a many-to-many relationship.
+    /// The keys are then the primary keys of both sides
+    fn through(&self) -> Option<Through> {
+        None
+    }
+
+    /// Columns that have to match in both tables along with the keys, for a foreign key over
+    /// several columns (a `tenant_id` along with an `order_id`). They have the same name on both sides
+    fn shared_keys(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// The key columns of a relationship on both sides, the key and then the shared columns.
+/// They pair up by position
+pub(crate) fn key_columns<M, R, T>(ship: &impl Relationship<M>) -> (Vec<String>, Vec<String>)
+where
+    R: UniqueIdentifier,
+    T: UniqueIdentifier,
+{
+    let shared = ship.shared_keys().iter().map(|c| c.to_string());
+    let mut mine = vec![ship.my_key::<R, T>()];
+    mine.extend(shared.clone());
+    let mut theirs = vec![ship.their_key::<R, T>()];
+    theirs.extend(shared);
+    (mine, theirs)
 }
 
+/// A relationship rows can be created through, the new row points back at the model it is
+/// created from (`DbState::create_related`)
+pub trait CreateRelated<R>: Relationship<R> {}
+
+impl<R> CreateRelated<R> for HasMany<R> {}
+impl<R> CreateRelated<R> for HasOne<R> {}
+impl<R> CreateRelated<R> for BelongsToMany<R> {}
+
 pub trait HasRelations {
     type Relation: Default;
 }
 
 pub trait RelationAdder {}
+
+/// The `BelongsTo` relations of a model, as plain column/table names.
+/// Written for you by `WeldsModel`
+pub trait ForeignKeys {
+    fn foreign_keys() -> Vec<ForeignKey>;
+}
+
+/// A column that points at the primary key of a row in another table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKey {
+    /// The column on this model
+    pub column: String,
+    /// The table the column points at
+    pub parent_table: String,
+    /// The primary key column of the parent table
+    pub parent_column: String,
+}
+
+impl ForeignKey {
+    /// A foreign key pointing to the schema of the parent model
+    pub fn new<P>(column: &str) -> ForeignKey
+    where
+        P: TableInfo + UniqueIdentifier,
+    {
+        ForeignKey {
+            column: column.to_owned(),
+            parent_table: P::identifier().join("."),
+            parent_column: P::id_column().name().to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,376 @@
+use super::{read_link, LinkKey, ParentCounter};
+use crate::errors::Result;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo, WriteToArgs};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::ParamArgs;
+use crate::query::helpers::invalidate_cached_table;
+use crate::writers::{in_namespace, table_alias, table_name, ColumnWriter, NextParam};
+use crate::Syntax;
+use std::collections::HashSet;
+use welds_connections::{Client, Fetch, TransactStart};
+
+/// If rows of `T` have parents to update when they are written
+pub(crate) fn has_parents<T>() -> bool
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo,
+{
+    let counters = <T::Schema as TableInfo>::parent_counters();
+    !counters.is_empty() || !<T::Schema as TableInfo>::touches().is_empty()
+}
+
+/// The client to start a transaction on before writing rows of `T`, so the rows and their
+/// parents are written together or not at all. None when `T` has no parents to update, or
+/// when the client is already a transaction (or can't start one)
+pub(crate) fn parents_transaction<T>(client: &dyn Client) -> Option<&(dyn TransactStart + Sync)>
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo,
+{
+    if !has_parents::<T>() || client.in_transaction() {
+        return None;
+    }
+    client.as_transact_start()
+}
+
+/// Updates the parents `obj` points at after it was written: its `counter_cache`s are counted
+/// by `counted` (1 for an insert, -1 for a delete, 0 for an update) and its `touch`es set to now.
+/// They are sent as one batch, with the same client the row was written with
+pub(crate) async fn update_parents<T>(obj: &T, counted: i32, client: &dyn Client) -> Result<()>
+where
+    T: HasSchema + WriteToArgs,
+    <T as HasSchema>::Schema: TableInfo,
+{
+    let counters = match counted {
+        0 => Vec::default(),
+        _ => <T::Schema as TableInfo>::parent_counters(),
+    };
+    write_parents(obj, &counters, counted, client).await
+}
+
+/// Counts down the parents an update moves `obj` away from, before the update is written:
+/// the `counter_cache`s whose foreign key is written with a different value than the row has.
+/// Gives back those counters, the parents the row moves to are counted up by `join_parents`
+pub(crate) async fn leave_parents<T>(
+    obj: &T,
+    only: Option<&[String]>,
+    client: &dyn Client,
+) -> Result<Vec<ParentCounter>>
+where
+    T: HasSchema + WriteToArgs,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let selected = |col: &str| only.map(|o| o.iter().any(|c| c == col)).unwrap_or(true);
+    let counters: Vec<ParentCounter> = <T::Schema as TableInfo>::parent_counters()
+        .into_iter()
+        .filter(|c| selected(&c.foreign_key))
+        .collect();
+    if counters.is_empty() {
+        return Ok(counters);
+    }
+
+    let syntax = client.syntax();
+    let writer = ColumnWriter::new(syntax);
+    let table = <T::Schema as TableInfo>::identifier().join(".");
+    let table = in_namespace(client, || table_name(&table));
+
+    // the row as it is in the database, found by its primary key
+    let (find, find_args) = find_row(obj, syntax)?;
+    let keys: Vec<String> = counters
+        .iter()
+        .map(|c| writer.excape(&c.foreign_key))
+        .collect();
+    let sql = format!("SELECT {} FROM {} WHERE {}", keys.join(", "), table, find);
+    let rows = client.fetch_rows(&sql, &find_args).await?;
+    let Some(row) = rows.first() else {
+        return Ok(Vec::default());
+    };
+
+    let mut moved = Vec::default();
+    for (index, counter) in counters.into_iter().enumerate() {
+        let mut args: ParamArgs = Vec::default();
+        obj.bind(&counter.foreign_key, &mut args)?;
+        if read_link(row, index)? != LinkKey::of_param(args[0])? {
+            moved.push(counter);
+        }
+    }
+
+    // the old parent is read by the statement itself. The row is read from a derived table so
+    // MySql lets it be used while a parent of the same table is updated
+    let mut statements = Vec::default();
+    for counter in &moved {
+        let (find, args) = find_row(obj, syntax)?;
+        let counted = writer.excape(&counter.counter);
+        let key = writer.excape(&counter.foreign_key);
+        let sql = format!(
+            "UPDATE {} SET {} = {} - 1 WHERE {} = (SELECT welds_old.{} FROM (SELECT {} FROM {} WHERE {}) welds_old)",
+            in_namespace(client, || table_name(&counter.table)),
+            counted,
+            counted,
+            writer.excape(&counter.primary_key),
+            key,
+            key,
+            table,
+            find
+        );
+        statements.push((sql, args, "counter_cache", counter.table.clone()));
+    }
+    send(statements, client).await?;
+    Ok(moved)
+}
+
+/// Updates the parents of `obj` after an update: the counters of the parents it was moved to
+/// (see `leave_parents`) are counted up, and its `touch`es set to now
+pub(crate) async fn join_parents<T>(
+    obj: &T,
+    moved: &[ParentCounter],
+    client: &dyn Client,
+) -> Result<()>
+where
+    T: HasSchema + WriteToArgs,
+    <T as HasSchema>::Schema: TableInfo,
+{
+    write_parents(obj, moved, 1, client).await
+}
+
+/// Updates the parents of rows that were bulk inserted. Each parent is counted once: its
+/// counter is set to the number of rows pointing at it, which stays right when some of the
+/// rows were skipped. The parents are touched once as well
+pub(crate) async fn recount_parents<T>(objs: &[T], client: &dyn Client) -> Result<()>
+where
+    T: HasSchema + WriteToArgs,
+    <T as HasSchema>::Schema: TableInfo,
+{
+    let syntax = client.syntax();
+    let writer = ColumnWriter::new(syntax);
+    let child = <T::Schema as TableInfo>::identifier().join(".");
+    let child = in_namespace(client, || table_name(&child));
+    let soft_delete = <T::Schema as TableInfo>::soft_delete_column();
+    let mut statements = Vec::default();
+
+    for counter in <T::Schema as TableInfo>::parent_counters() {
+        let key = writer.excape(&counter.foreign_key);
+        for args in distinct_parents(objs, &counter.foreign_key)? {
+            let next_params = NextParam::new(syntax);
+            let mut wheres = vec![format!("{} = {}", key, next_params.next())];
+            if let Some(col) = soft_delete {
+                wheres.push(format!("{} IS NULL", writer.excape(col)));
+            }
+            let sql = format!(
+                "UPDATE {} SET {} = (SELECT COUNT(*) FROM (SELECT {} FROM {} WHERE {}) welds_rows) WHERE {} = {}",
+                in_namespace(client, || table_name(&counter.table)),
+                writer.excape(&counter.counter),
+                key,
+                child,
+                wheres.join(" AND "),
+                writer.excape(&counter.primary_key),
+                next_params.next()
+            );
+            let args = vec![args[0], args[0]];
+            statements.push((sql, args, "counter_cache", counter.table.clone()));
+        }
+    }
+
+    for touch in <T::Schema as TableInfo>::touches() {
+        for args in distinct_parents(objs, &touch.foreign_key)? {
+            let sql = touch_sql(
+                syntax,
+                client,
+                &touch.table,
+                &touch.column,
+                &touch.primary_key,
+            );
+            statements.push((sql, args, "touch", touch.table.clone()));
+        }
+    }
+
+    send(statements, client).await
+}
+
+/// Updates the parents of the rows a bulk delete is about to delete: each parent is counted
+/// down by the number of its rows that are deleted, and touched. Sent before the delete, while
+/// the rows can still be read
+pub(crate) async fn leave_parents_of<T>(qb: &QueryBuilder<T>, client: &dyn Client) -> Result<()>
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let syntax = client.syntax();
+    let writer = ColumnWriter::new(syntax);
+    let soft_delete = <T::Schema as TableInfo>::soft_delete_column();
+    let mut statements = Vec::default();
+
+    for counter in <T::Schema as TableInfo>::parent_counters() {
+        let parent = in_namespace(client, || table_name(&counter.table));
+        let alias = in_namespace(client, || table_alias(&counter.table));
+        let key = writer.excape(&counter.foreign_key);
+        let pk = writer.excape(&counter.primary_key);
+        // rows that were already soft deleted aren't counted
+        let mut columns = vec![counter.foreign_key.as_str()];
+        columns.extend(soft_delete);
+        let live = soft_delete.map(|col| format!("welds_gone.{} IS NULL", writer.excape(col)));
+        let counted_where = match &live {
+            Some(live) => format!(" AND {}", live),
+            None => String::default(),
+        };
+        let gone_where = match &live {
+            Some(live) => format!(" WHERE {}", live),
+            None => String::default(),
+        };
+        let next_params = NextParam::new(syntax);
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let gone = in_namespace(client, || {
+            qb.select_columns_sql(&columns, syntax, &next_params, &mut args)
+        });
+        let gone_again = in_namespace(client, || {
+            qb.select_columns_sql(&columns, syntax, &next_params, &mut args)
+        });
+        let counted = writer.excape(&counter.counter);
+        let sql = format!(
+            "UPDATE {} SET {} = {} - (SELECT COUNT(*) FROM ( {} ) welds_gone WHERE welds_gone.{} = {}.{}{}) WHERE {} IN (SELECT welds_gone.{} FROM ( {} ) welds_gone{})",
+            parent, counted, counted, gone, key, alias, pk, counted_where, pk, key, gone_again, gone_where
+        );
+        statements.push((sql, args.unwrap(), "counter_cache", counter.table.clone()));
+    }
+
+    for touch in <T::Schema as TableInfo>::touches() {
+        let key = writer.excape(&touch.foreign_key);
+        let next_params = NextParam::new(syntax);
+        let mut args: Option<ParamArgs> = Some(Vec::default());
+        let columns = [touch.foreign_key.as_str()];
+        let gone = in_namespace(client, || {
+            qb.select_columns_sql(&columns, syntax, &next_params, &mut args)
+        });
+        let sql = format!(
+            "UPDATE {} SET {} = CURRENT_TIMESTAMP WHERE {} IN (SELECT welds_gone.{} FROM ( {} ) welds_gone)",
+            in_namespace(client, || table_name(&touch.table)),
+            writer.excape(&touch.column),
+            writer.excape(&touch.primary_key),
+            key,
+            gone
+        );
+        statements.push((sql, args.unwrap(), "touch", touch.table.clone()));
+    }
+
+    send(statements, client).await
+}
+
+// counts the given counters of the parents of obj by `counted`, and touches its parents
+async fn write_parents<T>(
+    obj: &T,
+    counters: &[ParentCounter],
+    counted: i32,
+    client: &dyn Client,
+) -> Result<()>
+where
+    T: HasSchema + WriteToArgs,
+    <T as HasSchema>::Schema: TableInfo,
+{
+    let syntax = client.syntax();
+    let writer = ColumnWriter::new(syntax);
+    let mut statements = Vec::default();
+
+    let op = if counted < 0 { "-" } else { "+" };
+    for parent in counters {
+        let mut args: ParamArgs = Vec::default();
+        obj.bind(&parent.foreign_key, &mut args)?;
+        let counter = writer.excape(&parent.counter);
+        let sql = format!(
+            "UPDATE {} SET {} = {} {} {} WHERE {} = {}",
+            in_namespace(client, || table_name(&parent.table)),
+            counter,
+            counter,
+            op,
+            counted.abs(),
+            writer.excape(&parent.primary_key),
+            NextParam::new(syntax).next()
+        );
+        statements.push((sql, args, "counter_cache", parent.table.clone()));
+    }
+
+    for touch in <T::Schema as TableInfo>::touches() {
+        let mut args: ParamArgs = Vec::default();
+        obj.bind(&touch.foreign_key, &mut args)?;
+        let sql = touch_sql(
+            syntax,
+            client,
+            &touch.table,
+            &touch.column,
+            &touch.primary_key,
+        );
+        statements.push((sql, args, "touch", touch.table));
+    }
+
+    send(statements, client).await
+}
+
+fn touch_sql(syntax: Syntax, client: &dyn Client, table: &str, column: &str, pk: &str) -> String {
+    let writer = ColumnWriter::new(syntax);
+    format!(
+        "UPDATE {} SET {} = CURRENT_TIMESTAMP WHERE {} = {}",
+        in_namespace(client, || table_name(table)),
+        writer.excape(column),
+        writer.excape(pk),
+        NextParam::new(syntax).next()
+    )
+}
+
+// the WHERE finding the row of obj by its primary key
+fn find_row<T>(obj: &T, syntax: Syntax) -> Result<(String, ParamArgs<'_>)>
+where
+    T: HasSchema + WriteToArgs,
+    <T as HasSchema>::Schema: TableColumns,
+{
+    let writer = ColumnWriter::new(syntax);
+    let next_params = NextParam::new(syntax);
+    let mut args: ParamArgs = Vec::default();
+    let mut wheres = Vec::default();
+    for col in <T::Schema as TableColumns>::primary_keys() {
+        obj.bind(col.name(), &mut args)?;
+        wheres.push(format!(
+            "{} = {}",
+            writer.excape(col.name()),
+            next_params.next()
+        ));
+    }
+    Ok((wheres.join(" AND "), args))
+}
+
+// the foreign keys the objs point at, each once. Rows that don't point at a parent are left out
+fn distinct_parents<'a, T>(objs: &'a [T], foreign_key: &str) -> Result<Vec<ParamArgs<'a>>>
+where
+    T: WriteToArgs,
+{
+    let mut seen: HashSet<LinkKey> = HashSet::default();
+    let mut parents = Vec::default();
+    for obj in objs {
+        let mut args: ParamArgs = Vec::default();
+        obj.bind(foreign_key, &mut args)?;
+        let Some(key) = LinkKey::of_param(args[0])? else {
+            continue;
+        };
+        if seen.insert(key) {
+            parents.push(args);
+        }
+    }
+    Ok(parents)
+}
+
+// sends the statements as one batch, then drops what the cache holds of the tables they wrote
+async fn send(
+    statements: Vec<(String, ParamArgs<'_>, &str, String)>,
+    client: &dyn Client,
+) -> Result<()> {
+    if statements.is_empty() {
+        return Ok(());
+    }
+    let fetches: Vec<Fetch> = statements
+        .iter()
+        .map(|(sql, args, label, _)| Fetch::new(sql, args).with_label(label))
+        .collect();
+    client.fetch_many(&fetches).await?;
+    for (_, _, _, table) in &statements {
+        invalidate_cached_table(client, table).await;
+    }
+    Ok(())
+}
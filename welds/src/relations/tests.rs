@@ -0,0 +1,417 @@
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::value::Value;
+use crate::state::DbState;
+use crate::{Syntax, WeldsModel};
+use futures::executor::block_on;
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "users")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsToMany(roles, Role, through = "roles_users"))]
+struct User {
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "roles")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Role {
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+fn linked(client: &MockClient, roles: &[i32]) {
+    let rows = roles
+        .iter()
+        .map(|r| MockRow::new().with("role_id", *r))
+        .collect();
+    client.enqueue_rows("SELECT", rows);
+}
+
+#[test]
+fn should_attach_only_the_rows_that_are_not_linked() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        linked(&client, &[2]);
+        client.enqueue_rows_affected("INSERT", 2);
+        let user = User { id: 1 };
+        let added = user.attach_roles(&client, &[2, 3, 4, 3]).await.unwrap();
+        assert_eq!(added, 2);
+
+        let queries = client.queries();
+        assert_eq!(
+            queries[0].sql,
+            r#"SELECT "role_id" FROM roles_users WHERE "user_id" = $1"#
+        );
+        assert_eq!(
+            queries[1].sql,
+            r#"INSERT INTO roles_users ("user_id", "role_id") VALUES ($1, $2), ($3, $4)"#
+        );
+        let expected = [1, 3, 1, 4].map(Value::Int);
+        assert_eq!(queries[1].params, expected);
+    });
+}
+
+#[test]
+fn should_not_insert_when_everything_is_attached() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        linked(&client, &[2]);
+        let added = User { id: 1 }.attach_roles(&client, &[2]).await.unwrap();
+        assert_eq!(added, 0);
+        assert_eq!(client.queries().len(), 1);
+    });
+}
+
+#[test]
+fn should_detach_the_links() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Mysql);
+        User { id: 1 }.detach_roles(&client, &[2, 3]).await.unwrap();
+        let sql = client.last_sql().unwrap();
+        assert_eq!(
+            sql,
+            "DELETE FROM roles_users WHERE user_id = ? AND role_id IN (?, ?)"
+        );
+    });
+}
+
+#[test]
+fn should_sync_the_links() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        linked(&client, &[2]);
+        User { id: 1 }.sync_roles(&client, &[2, 5]).await.unwrap();
+        let sqls: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+        assert_eq!(
+            sqls,
+            vec![
+                r#"DELETE FROM roles_users WHERE "user_id" = $1 AND "role_id" NOT IN ($2, $3)"#,
+                r#"SELECT "role_id" FROM roles_users WHERE "user_id" = $1"#,
+                r#"INSERT INTO roles_users ("user_id", "role_id") VALUES ($1, $2)"#,
+            ]
+        );
+    });
+}
+
+#[test]
+fn should_sync_to_nothing_by_removing_every_link() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        User { id: 1 }
+            .sync_roles::<i32>(&client, &[])
+            .await
+            .unwrap();
+        let sql = &client.queries()[0].sql;
+        assert_eq!(sql, r#"DELETE FROM roles_users WHERE "user_id" = $1"#);
+    });
+}
+
+#[cfg(feature = "uuid")]
+mod uuid_keys {
+    use super::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "accounts")]
+    #[welds_path(crate)] // needed only within the welds crate.
+    #[welds(BelongsToMany(tags, Tag, through = "accounts_tags"))]
+    struct Account {
+        #[welds(primary_key)]
+        pub id: Uuid,
+    }
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "tags")]
+    #[welds_path(crate)] // needed only within the welds crate.
+    struct Tag {
+        #[welds(primary_key)]
+        pub id: Uuid,
+    }
+
+    fn id(n: u8) -> Uuid {
+        Uuid::from_bytes([n; 16])
+    }
+
+    #[test]
+    fn should_attach_only_the_uuids_that_are_not_linked() {
+        block_on(async {
+            let client = MockClient::new(Syntax::Postgres);
+            // one link read back as a uuid, one as the text of a uuid
+            let rows = vec![
+                MockRow::new().with("tag_id", id(2)),
+                MockRow::new().with("tag_id", id(3).to_string()),
+            ];
+            client.enqueue_rows("SELECT", rows);
+            client.enqueue_rows_affected("INSERT", 1);
+            let account = Account { id: id(1) };
+            let tags = [id(2), id(3), id(4), id(4)];
+            let added = account.attach_tags(&client, &tags).await.unwrap();
+            assert_eq!(added, 1);
+
+            let insert = &client.queries()[1];
+            assert_eq!(
+                insert.sql,
+                r#"INSERT INTO accounts_tags ("account_id", "tag_id") VALUES ($1, $2)"#
+            );
+            assert_eq!(
+                insert.params,
+                vec![Value::Uuid([1; 16]), Value::Uuid([4; 16])]
+            );
+        });
+    }
+
+    #[test]
+    fn should_sync_the_uuid_links() {
+        block_on(async {
+            let client = MockClient::new(Syntax::Postgres);
+            client.enqueue_rows("SELECT", vec![MockRow::new().with("tag_id", id(2))]);
+            let account = Account { id: id(1) };
+            account.sync_tags(&client, &[id(2)]).await.unwrap();
+            let sqls: Vec<String> = client.queries().into_iter().map(|q| q.sql).collect();
+            assert_eq!(
+                sqls,
+                vec![
+                    r#"DELETE FROM accounts_tags WHERE "account_id" = $1 AND "tag_id" NOT IN ($2)"#,
+                    r#"SELECT "tag_id" FROM accounts_tags WHERE "account_id" = $1"#,
+                ]
+            );
+        });
+    }
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(HasMany(items, Item, "order_id", counter_cache = "items_count"))]
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub items_count: i32,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "items")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsTo(order, Order, "order_id"))]
+struct Item {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub order_id: i32,
+}
+
+#[test]
+fn should_count_up_the_parent_when_a_child_is_created() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let row = MockRow::new().with("id", 4).with("order_id", 2);
+        client.enqueue_rows("INSERT", vec![row]);
+        let mut item = DbState::new_uncreated(Item { id: 0, order_id: 2 });
+        item.save(&client).await.unwrap();
+
+        // the row and its parent are written in one transaction
+        let queries = client.queries();
+        assert_eq!(queries[0].sql, "BEGIN");
+        assert_eq!(
+            queries[2].sql,
+            r#"UPDATE orders SET "items_count" = "items_count" + 1 WHERE "id" = $1"#
+        );
+        assert_eq!(queries[2].params, [Value::Int(2)]);
+        assert_eq!(queries[3].sql, "COMMIT");
+    });
+}
+
+#[test]
+fn should_count_down_the_parent_when_a_child_is_deleted() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Mysql);
+        client.enqueue_rows_affected("DELETE", 1);
+        let mut item = DbState::db_loaded(Item { id: 4, order_id: 2 });
+        item.delete(&client).await.unwrap();
+        let queries = client.queries();
+        assert_eq!(
+            queries[2].sql,
+            "UPDATE orders SET items_count = items_count - 1 WHERE id = ?"
+        );
+        assert_eq!(client.last_sql().unwrap(), "COMMIT");
+    });
+}
+
+#[test]
+fn should_not_count_down_when_nothing_was_deleted() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let mut item = DbState::db_loaded(Item { id: 4, order_id: 2 });
+        item.delete(&client).await.unwrap();
+        let queries = client.queries();
+        let sqls: Vec<&str> = queries.iter().map(|q| q.sql.as_str()).collect();
+        assert_eq!(sqls[0], "BEGIN");
+        assert!(sqls[1].starts_with("DELETE"));
+        assert_eq!(sqls[2], "COMMIT");
+        assert_eq!(sqls.len(), 3);
+    });
+}
+
+#[test]
+fn should_not_start_a_transaction_within_one() {
+    block_on(async {
+        use crate::TransactStart;
+        let client = MockClient::new(Syntax::Postgres);
+        let row = MockRow::new().with("id", 4).with("order_id", 2);
+        client.enqueue_rows("INSERT", vec![row]);
+        let trans = client.begin().await.unwrap();
+        let mut item = DbState::new_uncreated(Item { id: 0, order_id: 2 });
+        item.save(&trans).await.unwrap();
+        trans.commit().await.unwrap();
+        let queries = client.queries();
+        let begins = queries.iter().filter(|q| q.sql == "BEGIN").count();
+        assert_eq!(begins, 1);
+    });
+}
+
+#[test]
+fn should_move_the_count_when_the_foreign_key_changes() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let row = MockRow::new().with("order_id", 2);
+        client.enqueue_rows("SELECT", vec![row]);
+        let mut item = DbState::db_loaded(Item { id: 4, order_id: 2 });
+        item.order_id = 5;
+        item.save(&client).await.unwrap();
+
+        let queries = client.queries();
+        let sqls: Vec<&str> = queries.iter().map(|q| q.sql.as_str()).collect();
+        assert_eq!(
+            sqls,
+            vec![
+                "BEGIN",
+                r#"SELECT "order_id" FROM items WHERE "id" = $1"#,
+                r#"UPDATE orders SET "items_count" = "items_count" - 1 WHERE "id" = (SELECT welds_old."order_id" FROM (SELECT "order_id" FROM items WHERE "id" = $1) welds_old)"#,
+                r#"UPDATE items SET "order_id"=$1 where "id"=$2"#,
+                r#"UPDATE orders SET "items_count" = "items_count" + 1 WHERE "id" = $1"#,
+                "COMMIT",
+            ]
+        );
+        assert_eq!(queries[2].params, [Value::Int(4)]);
+        assert_eq!(queries[4].params, [Value::Int(5)]);
+    });
+}
+
+#[test]
+fn should_keep_the_count_when_the_foreign_key_is_the_same() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let row = MockRow::new().with("order_id", 2);
+        client.enqueue_rows("SELECT", vec![row]);
+        let mut item = DbState::db_loaded(Item { id: 4, order_id: 1 });
+        item.order_id = 2;
+        item.save(&client).await.unwrap();
+
+        let queries = client.queries();
+        assert!(queries.iter().all(|q| !q.sql.starts_with("UPDATE orders")));
+    });
+}
+
+#[test]
+fn should_recount_the_parents_of_bulk_inserted_rows() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let items = [
+            Item { id: 0, order_id: 2 },
+            Item { id: 0, order_id: 3 },
+            Item { id: 0, order_id: 2 },
+        ];
+        crate::query::insert::bulk_insert(&client, &items)
+            .await
+            .unwrap();
+
+        let queries = client.queries();
+        let recounts: Vec<_> = queries
+            .iter()
+            .filter(|q| q.sql.starts_with("UPDATE orders"))
+            .collect();
+        assert_eq!(
+            recounts[0].sql,
+            r#"UPDATE orders SET "items_count" = (SELECT COUNT(*) FROM (SELECT "order_id" FROM items WHERE "order_id" = $1) welds_rows) WHERE "id" = $2"#
+        );
+        // each parent is counted once
+        assert_eq!(recounts.len(), 2);
+        assert_eq!(recounts[0].params, [Value::Int(2), Value::Int(2)]);
+        assert_eq!(recounts[1].params, [Value::Int(3), Value::Int(3)]);
+        assert_eq!(queries[0].sql, "BEGIN");
+        assert_eq!(client.last_sql().unwrap(), "COMMIT");
+    });
+}
+
+#[test]
+fn should_count_down_the_parents_of_bulk_deleted_rows() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        Item::where_col(|i| i.id.gt(3))
+            .delete(&client)
+            .await
+            .unwrap();
+
+        let queries = client.queries();
+        let sqls: Vec<&str> = queries.iter().map(|q| q.sql.as_str()).collect();
+        let gone = r#"SELECT t1."order_id" FROM items t1 WHERE ( t1.id > $1 )"#;
+        let again = r#"SELECT t1."order_id" FROM items t1 WHERE ( t1.id > $2 )"#;
+        let counted = format!(
+            r#"UPDATE orders SET "items_count" = "items_count" - (SELECT COUNT(*) FROM ( {} ) welds_gone WHERE welds_gone."order_id" = orders."id") WHERE "id" IN (SELECT welds_gone."order_id" FROM ( {} ) welds_gone)"#,
+            gone, again
+        );
+        assert_eq!(sqls[0], "BEGIN");
+        assert_eq!(sqls[1], counted);
+        assert_eq!(queries[1].params, [Value::Int(3), Value::Int(3)]);
+        assert!(sqls[2].starts_with("DELETE FROM items"));
+        assert_eq!(sqls[3], "COMMIT");
+    });
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "posts")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Post {
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "comments")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsTo(post, Post, "post_id", touch = "commented_at"))]
+struct Comment {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub post_id: i32,
+    pub body: String,
+}
+
+#[test]
+fn should_touch_the_parent_when_a_child_is_saved() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let comment = Comment {
+            id: 3,
+            post_id: 8,
+            body: "first".to_owned(),
+        };
+        let mut comment = DbState::db_loaded(comment);
+        comment.body = "second".to_owned();
+        comment.save(&client).await.unwrap();
+
+        let queries = client.queries();
+        let sqls: Vec<&str> = queries.iter().map(|q| q.sql.as_str()).collect();
+        assert_eq!(
+            sqls,
+            vec![
+                "BEGIN",
+                r#"UPDATE comments SET "post_id"=$1, "body"=$2 where "id"=$3"#,
+                r#"UPDATE posts SET "commented_at" = CURRENT_TIMESTAMP WHERE "id" = $1"#,
+                "COMMIT",
+            ]
+        );
+        assert_eq!(queries[2].params, [Value::Int(8)]);
+    });
+}
@@ -0,0 +1,296 @@
+use super::Relationship;
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::UniqueIdentifier;
+use crate::query::clause::ParamArgs;
+use crate::query::helpers::invalidate_cached_table;
+use crate::writers::{in_namespace, table_name, ColumnWriter, NextParam};
+use crate::Syntax;
+use std::marker::PhantomData;
+use welds_connections::value::{self, Value};
+use welds_connections::{Client, Param, Row};
+
+/// A many-to-many relationship, through a join table that has a row for each pair
+pub struct BelongsToMany<T> {
+    _t: PhantomData<T>,
+    through: Through,
+}
+
+impl<T> BelongsToMany<T> {
+    pub fn using(
+        through: &'static str,
+        foreign_key: &'static str,
+        related_key: &'static str,
+    ) -> BelongsToMany<T> {
+        BelongsToMany {
+            _t: Default::default(),
+            through: Through {
+                table: through,
+                my_key: foreign_key,
+                their_key: related_key,
+            },
+        }
+    }
+
+    /// The join table, used to add and remove the links between rows
+    pub fn join_table(&self) -> Through {
+        self.through
+    }
+}
+
+impl<R> Relationship<R> for BelongsToMany<R> {
+    fn my_key<ME, THEM>(&self) -> String
+    where
+        ME: UniqueIdentifier,
+        THEM: UniqueIdentifier,
+    {
+        THEM::id_column().name().to_owned()
+    }
+    fn their_key<ME, THEM>(&self) -> String
+    where
+        ME: UniqueIdentifier,
+        THEM: UniqueIdentifier,
+    {
+        ME::id_column().name().to_owned()
+    }
+    fn through(&self) -> Option<Through> {
+        Some(self.through)
+    }
+}
+
+/// The join table of a `BelongsToMany`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Through {
+    /// The join table
+    pub table: &'static str,
+    /// The column of the join table pointing at the model the relationship is on
+    pub my_key: &'static str,
+    /// The column of the join table pointing at the related model
+    pub their_key: &'static str,
+}
+
+impl Through {
+    /// Adds a row to the join table for each of `them` that isn't linked to `me` yet.
+    /// Returns the number of rows added
+    ///
+    /// The keys are compared by value, uuids and dates need the `uuid` and `chrono` features.
+    /// Keys of other types can't be compared and fail with `UnlinkableKey`
+    pub async fn attach(
+        &self,
+        client: &dyn Client,
+        me: &(dyn Param + Sync + Send),
+        them: &[&(dyn Param + Sync + Send)],
+    ) -> Result<u64> {
+        let mut linked = self.linked(client, me).await?;
+        let mut missing: Vec<&(dyn Param + Sync + Send)> = Vec::default();
+        for key in them {
+            let link = LinkKey::of_param(*key)?;
+            if link.is_some() && !linked.contains(&link) {
+                linked.push(link);
+                missing.push(*key);
+            }
+        }
+        if missing.is_empty() {
+            return Ok(0);
+        }
+
+        let syntax = client.syntax();
+        let next_params = NextParam::new(syntax);
+        let mut args: ParamArgs = Vec::default();
+        let mut values = Vec::default();
+        for key in missing {
+            values.push(format!("({}, {})", next_params.next(), next_params.next()));
+            args.push(me);
+            args.push(key);
+        }
+        let (my_key, their_key) = self.columns(syntax);
+        let sql = format!(
+            "INSERT INTO {} ({}, {}) VALUES {}",
+            in_namespace(client, || table_name(self.table)),
+            my_key,
+            their_key,
+            values.join(", ")
+        );
+        let added = client.execute(&sql, &args).await?.rows_affected();
+        invalidate_cached_table(client, self.table).await;
+        Ok(added)
+    }
+
+    /// Removes the rows of the join table linking `me` to any of `them`.
+    /// Returns the number of rows removed
+    pub async fn detach(
+        &self,
+        client: &dyn Client,
+        me: &(dyn Param + Sync + Send),
+        them: &[&(dyn Param + Sync + Send)],
+    ) -> Result<u64> {
+        if them.is_empty() {
+            return Ok(0);
+        }
+        self.delete(client, me, them, "IN").await
+    }
+
+    /// Links `me` to exactly `them`: the rows of the join table for anything else are removed,
+    /// and the missing ones are added
+    pub async fn sync(
+        &self,
+        client: &dyn Client,
+        me: &(dyn Param + Sync + Send),
+        them: &[&(dyn Param + Sync + Send)],
+    ) -> Result<()> {
+        self.delete(client, me, them, "NOT IN").await?;
+        self.attach(client, me, them).await?;
+        Ok(())
+    }
+
+    // deletes the links of `me` with keys IN / NOT IN `them`. NOT IN nothing is all of them
+    async fn delete(
+        &self,
+        client: &dyn Client,
+        me: &(dyn Param + Sync + Send),
+        them: &[&(dyn Param + Sync + Send)],
+        test: &str,
+    ) -> Result<u64> {
+        let syntax = client.syntax();
+        let next_params = NextParam::new(syntax);
+        let (my_key, their_key) = self.columns(syntax);
+        let mut sql = format!(
+            "DELETE FROM {} WHERE {} = {}",
+            in_namespace(client, || table_name(self.table)),
+            my_key,
+            next_params.next()
+        );
+        let mut args: ParamArgs = vec![me];
+        if !them.is_empty() {
+            let keys: Vec<String> = them.iter().map(|_| next_params.next()).collect();
+            sql = format!("{} AND {} {} ({})", sql, their_key, test, keys.join(", "));
+            args.extend(them.iter().copied());
+        }
+        let removed = client.execute(&sql, &args).await?.rows_affected();
+        invalidate_cached_table(client, self.table).await;
+        Ok(removed)
+    }
+
+    // the keys of the rows `me` is linked to
+    async fn linked(
+        &self,
+        client: &dyn Client,
+        me: &(dyn Param + Sync + Send),
+    ) -> Result<Vec<Option<LinkKey>>> {
+        let syntax = client.syntax();
+        let (my_key, their_key) = self.columns(syntax);
+        let sql = format!(
+            "SELECT {} FROM {} WHERE {} = {}",
+            their_key,
+            in_namespace(client, || table_name(self.table)),
+            my_key,
+            NextParam::new(syntax).next()
+        );
+        let rows = client.fetch_rows(&sql, &[me]).await?;
+        rows.iter().map(|row| read_link(row, 0)).collect()
+    }
+
+    fn columns(&self, syntax: Syntax) -> (String, String) {
+        let writer = ColumnWriter::new(syntax);
+        (writer.excape(self.my_key), writer.excape(self.their_key))
+    }
+}
+
+/// A key rows are linked by, compared by value: a key read as an `i32` matches the same key
+/// read as an `i64`, and a uuid or date stored as text matches the uuid or date
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum LinkKey {
+    Int(i64),
+    Bool(bool),
+    Text(String),
+    Bytes(Vec<u8>),
+    Uuid([u8; 16]),
+    DateTime(String),
+}
+
+impl LinkKey {
+    // None for NULL, a nullable key matches the key it points at.
+    // Floats and the types a `Value` can't hold can't be compared
+    pub(crate) fn of(value: Value) -> Result<Option<LinkKey>> {
+        Ok(Some(match typed(value) {
+            Value::Null => return Ok(None),
+            Value::Int(v) => LinkKey::Int(v),
+            Value::Bool(v) => LinkKey::Bool(v),
+            Value::Text(v) => LinkKey::Text(v),
+            Value::Bytes(v) => LinkKey::Bytes(v),
+            Value::Uuid(v) => LinkKey::Uuid(v),
+            Value::DateTime(v) => LinkKey::DateTime(v),
+            Value::Float(_) => return Err(WeldsError::UnlinkableKey("f64".to_owned())),
+            Value::Opaque(name) => return Err(WeldsError::UnlinkableKey(name)),
+        }))
+    }
+
+    pub(crate) fn of_param(key: &(dyn Param + Sync + Send)) -> Result<Option<LinkKey>> {
+        LinkKey::of(value::of_param(key))
+    }
+}
+
+// Text that reads as a uuid or a date is that uuid or date, written the way the `Value` of the
+// uuid or date is
+fn typed(value: Value) -> Value {
+    #[allow(unused_imports)]
+    use welds_connections::value::{FromValue, ToValue};
+    let Value::Text(_) = &value else {
+        return value;
+    };
+    #[cfg(feature = "uuid")]
+    if let Ok(v) = uuid::Uuid::read("", &value) {
+        return v.to_value();
+    }
+    #[cfg(feature = "chrono")]
+    {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+        if let Ok(v) = DateTime::<Utc>::read("", &value) {
+            return v.to_value();
+        }
+        if let Ok(v) = NaiveDateTime::read("", &value) {
+            return v.to_value();
+        }
+        if let Ok(v) = NaiveDate::read("", &value) {
+            return v.to_value();
+        }
+    }
+    value
+}
+
+// The key in a column of a join table row, read by the types a key can have
+pub(crate) fn read_link(row: &Row, index: usize) -> Result<Option<LinkKey>> {
+    use welds_connections::value::ToValue;
+    if let Ok(key) = row.get_by_position::<Option<i64>>(index) {
+        return LinkKey::of(key.to_value());
+    }
+    if let Ok(key) = row.get_by_position::<Option<i32>>(index) {
+        return LinkKey::of(key.to_value());
+    }
+    if let Ok(key) = row.get_by_position::<Option<i16>>(index) {
+        return LinkKey::of(key.to_value());
+    }
+    if let Ok(key) = row.get_by_position::<Option<String>>(index) {
+        return LinkKey::of(key.to_value());
+    }
+    #[cfg(feature = "uuid")]
+    if let Ok(key) = row.get_by_position::<Option<uuid::Uuid>>(index) {
+        return LinkKey::of(key.to_value());
+    }
+    #[cfg(feature = "chrono")]
+    {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+        if let Ok(key) = row.get_by_position::<Option<DateTime<Utc>>>(index) {
+            return LinkKey::of(key.to_value());
+        }
+        if let Ok(key) = row.get_by_position::<Option<NaiveDateTime>>(index) {
+            return LinkKey::of(key.to_value());
+        }
+        if let Ok(key) = row.get_by_position::<Option<NaiveDate>>(index) {
+            return LinkKey::of(key.to_value());
+        }
+    }
+    if let Ok(key) = row.get_by_position::<Option<Vec<u8>>>(index) {
+        return LinkKey::of(key.to_value());
+    }
+    Err(WeldsError::UnlinkableKey(format!("column {}", index)))
+}
@@ -0,0 +1,30 @@
+use crate::model_traits::{TableInfo, UniqueIdentifier};
+
+/// A `touch` on a `BelongsTo`: a timestamp on the parent set to the current time whenever
+/// a row pointing at it is saved or deleted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Touch {
+    /// The table of the parent
+    pub table: String,
+    /// The primary key of the parent
+    pub primary_key: String,
+    /// The timestamp column of the parent
+    pub column: String,
+    /// The column of the row pointing at the parent
+    pub foreign_key: String,
+}
+
+impl Touch {
+    /// Touches `column` on the parent schema `P`
+    pub fn new<P>(foreign_key: &str, column: &str) -> Touch
+    where
+        P: TableInfo + UniqueIdentifier,
+    {
+        Touch {
+            table: P::identifier().join("."),
+            primary_key: P::id_column().name().to_owned(),
+            column: column.to_owned(),
+            foreign_key: foreign_key.to_owned(),
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::hooks::{
+    AfterCreate, AfterDelete, AfterUpdate, BeforeCreate, BeforeDelete, BeforeUpdate,
+};
+use crate::model_traits::{
+    ColumnDefaultCheck, HasSchema, TableColumns, TableInfo, UpdateFromRow, WriteToArgs,
+};
+use crate::query::delete;
+use crate::query::insert;
+use crate::query::insert::Upsert;
+use crate::query::update;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use welds_connections::Client;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DbStatus {
+    /// The entity has NOT been saved.
+    NotInDatabase,
+    /// The entity is an exact copy of what is in the database
+    NotModified,
+    /// The entity is most likely different from what is in the database
+    Edited,
+}
+
+/// Wraps a T to keep track of changes and current state in the database
+///
+/// Also used to Save changes. Save will result in a create or update as needed.
+/// delete will remove from the database.
+pub struct DbState<T> {
+    _t: PhantomData<T>,
+    inner: T,
+    status: DbStatus,
+    // The columns known to be changed. None when any column may have changed
+    changed: Option<Vec<String>>,
+}
+
+impl<T> std::fmt::Debug for DbState<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.inner, f)
+    }
+}
+
+impl<T> DbState<T> {
+    /// Returns status of the entity. If it is in the database/unsaved/modified/..
+    pub fn db_status(&self) -> DbStatus {
+        self.status
+    }
+
+    /// Returns a DbState<T> that assumes its inner T does not exist in the database
+    pub fn new_uncreated(inner: T) -> DbState<T> {
+        DbState {
+            _t: PhantomData,
+            inner,
+            status: DbStatus::NotInDatabase,
+            changed: None,
+        }
+    }
+
+    /// Returns a DbState<T> that assumes its inner T already exist in the database
+    pub fn db_loaded(inner: T) -> DbState<T> {
+        DbState {
+            _t: PhantomData,
+            inner,
+            status: DbStatus::NotModified,
+            changed: None,
+        }
+    }
+
+    /// Saves the inner T to the database. Results in an insert or update if needed. If no change
+    /// has been detected on the inner T, No operation will occur
+    ///
+    /// A model with a `#[welds(version)]` column fails with `StaleRecord` if the row was updated
+    /// or deleted since it was read.
+    pub async fn save(&mut self, client: &dyn Client) -> Result<()>
+    where
+        T: HasSchema + WriteToArgs + ColumnDefaultCheck,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        T: UpdateFromRow,
+        T: BeforeCreate + AfterCreate,
+        T: BeforeUpdate + AfterUpdate,
+    {
+        match self.status {
+            DbStatus::NotModified => {}
+            DbStatus::Edited => match &self.changed {
+                Some(only) => {
+                    update::update_columns(&mut self.inner, Some(only.as_slice()), client).await?
+                }
+                None => update::update_one(&mut self.inner, client).await?,
+            },
+            DbStatus::NotInDatabase => {
+                insert::insert_one(&mut self.inner, client).await?;
+            }
+        }
+        self.status = DbStatus::NotModified;
+        self.changed = None;
+        Ok(())
+    }
+
+    /// Inserts the inner T leaving out the given columns, so the database fills them in with
+    /// their defaults (or triggers). Use `save` to update it afterwards.
+    ///
+    /// The values the database picked are read back into T when the backend returns the
+    /// inserted row (it always does unless the primary key was set by hand on MySQL or Sqlite).
+    ///
+    /// ```rust,ignore
+    /// let mut order = DbState::new_uncreated(Order { id: 0, status: "new".into(), created_at: now });
+    /// order.insert_omitting(&["created_at"], &client).await?;
+    /// ```
+    pub async fn insert_omitting(&mut self, omit: &[&str], client: &dyn Client) -> Result<()>
+    where
+        T: HasSchema + WriteToArgs + ColumnDefaultCheck,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        T: UpdateFromRow,
+        T: BeforeCreate + AfterCreate,
+    {
+        if self.status != DbStatus::NotInDatabase {
+            return Err(WeldsError::InsertFailed(
+                "the row is already in the database".to_owned(),
+            ));
+        }
+        insert::insert_omitting(&mut self.inner, omit, client).await?;
+        self.status = DbStatus::NotModified;
+        self.changed = None;
+        Ok(())
+    }
+
+    /// Inserts the inner T unless it conflicts with a row already in the database, on its
+    /// primary key or any other unique constraint (`ON CONFLICT DO NOTHING`, `INSERT IGNORE`).
+    ///
+    /// Returns if the row was inserted. When it wasn't, the inner T is left as it was and is
+    /// still not in the database.
+    ///
+    /// ```rust,ignore
+    /// let mut tag = DbState::new_uncreated(Tag { id: 0, name: "rust".into() });
+    /// if !tag.insert_ignore(&client).await? {
+    ///     println!("already tagged");
+    /// }
+    /// ```
+    pub async fn insert_ignore(&mut self, client: &dyn Client) -> Result<bool>
+    where
+        T: HasSchema + WriteToArgs + ColumnDefaultCheck,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        T: UpdateFromRow,
+        T: BeforeCreate + AfterCreate,
+    {
+        if self.status != DbStatus::NotInDatabase {
+            return Err(WeldsError::InsertFailed(
+                "the row is already in the database".to_owned(),
+            ));
+        }
+        let inserted = insert::insert_ignore_one(&mut self.inner, client).await?;
+        if inserted {
+            self.status = DbStatus::NotModified;
+            self.changed = None;
+        }
+        Ok(inserted)
+    }
+
+    /// Inserts the inner T, or updates the row in the database with the same primary key.
+    /// Runs as one statement (`ON CONFLICT ... DO UPDATE`, `ON DUPLICATE KEY UPDATE`, or
+    /// `MERGE` on MSSQL) and reads back the row as it is afterwards. Hooks are not ran.
+    pub async fn upsert(&mut self, client: &dyn Client) -> Result<()>
+    where
+        T: HasSchema + WriteToArgs + UpdateFromRow,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.upsert_with(&Upsert::new(), client).await
+    }
+
+    /// Like `upsert`, choosing which columns rows conflict on and which columns get updated
+    pub async fn upsert_with(&mut self, upsert: &Upsert, client: &dyn Client) -> Result<()>
+    where
+        T: HasSchema + WriteToArgs + UpdateFromRow,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        insert::upsert_one(&mut self.inner, upsert, client).await?;
+        self.status = DbStatus::NotModified;
+        self.changed = None;
+        Ok(())
+    }
+
+    /// Removes the inner T from the database. If T is not in the database no operation will occur
+    ///
+    /// A soft deleted model is kept in the database with its `soft_delete` column set to the time
+    /// it was deleted. It is read back with that time, and stays in the database.
+    pub async fn delete(&mut self, client: &dyn Client) -> Result<()>
+    where
+        T: HasSchema + WriteToArgs + UpdateFromRow,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        T: BeforeDelete + AfterDelete,
+    {
+        match self.status {
+            DbStatus::NotModified => {
+                delete::delete_one(&mut self.inner, client).await?;
+            }
+            DbStatus::Edited => {
+                delete::delete_one(&mut self.inner, client).await?;
+            }
+            DbStatus::NotInDatabase => {}
+        }
+        self.status = match <T as HasSchema>::Schema::soft_delete_column() {
+            Some(_) if self.status != DbStatus::NotInDatabase => DbStatus::NotModified,
+            _ => DbStatus::NotInDatabase,
+        };
+        self.changed = None;
+        Ok(())
+    }
+
+    /// Consumes the DbState, returning the wrapped value. The inner value is nolonger connected to
+    /// welds and can nolonger be saved/created/deleted
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Consumes the model and transforms it into an immutable object that is useful for Views and sharing.
+    pub fn into_vm(self) -> Arc<T> {
+        Arc::new(self.inner)
+    }
+
+    /// Overwrite the inner value with another, and set the db state ready for update.
+    ///
+    /// ⚠️ It may update the wrong row if the Primary Key is modified. Make sure to check beforehand. ⚠️
+    pub fn replace_inner(&mut self, new: T) {
+        self.mark_edited();
+        self.inner = new;
+    }
+}
+
+impl<T> DbState<T> {
+    // The inner value was handed out mutably, any of its columns could change
+    fn mark_edited(&mut self) {
+        if self.status == DbStatus::NotModified {
+            self.status = DbStatus::Edited
+        }
+        self.changed = None;
+    }
+
+    // Only the given columns were changed
+    pub(crate) fn mark_columns_edited(&mut self, columns: Vec<String>) {
+        if columns.is_empty() {
+            return;
+        }
+        match self.status {
+            DbStatus::NotInDatabase => {}
+            DbStatus::NotModified => {
+                self.status = DbStatus::Edited;
+                self.changed = Some(columns);
+            }
+            DbStatus::Edited => {
+                if let Some(changed) = &mut self.changed {
+                    for col in columns {
+                        if !changed.contains(&col) {
+                            changed.push(col);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+mod related;
+
+#[cfg(feature = "json")]
+mod patch;
+#[cfg(feature = "json")]
+pub use patch::ApplyPatch;
+
+// helper, method extensions, to make it really simple to map DB results into useful result sets
+pub trait VecStateExt<T> {
+    fn to_vms(self) -> Arc<Vec<Arc<T>>>;
+    fn into_inners(self) -> Vec<T>;
+}
+
+impl<T> VecStateExt<T> for Vec<DbState<T>> {
+    /// convert from DbState wrapped data to Arc wrapped data.
+    /// Very useful when passing to a View layer such as Yew (Server Side)
+    fn to_vms(mut self) -> Arc<Vec<Arc<T>>> {
+        let vec: Vec<_> = self.drain(..).map(|x| x.into_vm()).collect();
+        Arc::new(vec)
+    }
+
+    /// convert from DbState wrapped data to Unwrapped Models
+    fn into_inners(mut self) -> Vec<T> {
+        self.drain(..).map(|x| x.into_inner()).collect()
+    }
+}
+
+impl<T> Deref for DbState<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for DbState<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.mark_edited();
+        &mut self.inner
+    }
+}
+
+impl<T> AsRef<T> for DbState<T> {
+    fn as_ref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> AsMut<T> for DbState<T> {
+    fn as_mut(&mut self) -> &mut T {
+        self.mark_edited();
+        &mut self.inner
+    }
+}
@@ -0,0 +1,112 @@
+use super::DbState;
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{HasSchema, TableColumns};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Apply a partial JSON document (the body of an HTTP PATCH) onto a model.
+///
+/// The keys of the patch are the names of the fields on the model. The whole patch is validated
+/// before anything is changed: unknown fields, primary keys, fields that are not updatable,
+/// and values that don't fit the type of the field are all rejected.
+///
+/// Only the columns that actually changed are written on the next `save`.
+///
+/// ```rust,ignore
+/// let mut product = Product::find_by_id(&client, 1).await?.unwrap();
+/// let body = serde_json::json!({ "name": "Nessie Plush", "price": 3.50 });
+/// Product::apply_patch_only(&mut product, &body, &["name", "price"])?;
+/// product.save(&client).await?;
+/// ```
+pub trait ApplyPatch: Sized {
+    /// Applies the patch allowing any column other than the primary key to be updated.
+    /// Returns the db columns that were changed.
+    fn apply_patch(state: &mut DbState<Self>, patch: &Value) -> Result<Vec<String>>;
+
+    /// Applies the patch allowing only the given fields to be updated.
+    /// Returns the db columns that were changed.
+    fn apply_patch_only(
+        state: &mut DbState<Self>,
+        patch: &Value,
+        updatable: &[&str],
+    ) -> Result<Vec<String>>;
+}
+
+impl<T> ApplyPatch for T
+where
+    T: HasSchema + Serialize + DeserializeOwned,
+    <T as HasSchema>::Schema: TableColumns,
+{
+    fn apply_patch(state: &mut DbState<Self>, patch: &Value) -> Result<Vec<String>> {
+        apply(state, patch, None)
+    }
+
+    fn apply_patch_only(
+        state: &mut DbState<Self>,
+        patch: &Value,
+        updatable: &[&str],
+    ) -> Result<Vec<String>> {
+        apply(state, patch, Some(updatable))
+    }
+}
+
+fn apply<T>(
+    state: &mut DbState<T>,
+    patch: &Value,
+    updatable: Option<&[&str]>,
+) -> Result<Vec<String>>
+where
+    T: HasSchema + Serialize + DeserializeOwned,
+    <T as HasSchema>::Schema: TableColumns,
+{
+    let patch = patch
+        .as_object()
+        .ok_or_else(|| invalid("expected a JSON object"))?;
+    let pks: Vec<String> = <T::Schema as TableColumns>::primary_keys()
+        .iter()
+        .map(|c| c.name().to_owned())
+        .collect();
+
+    let mut doc = serde_json::to_value(&state.inner).map_err(invalid)?;
+    let fields = doc
+        .as_object_mut()
+        .ok_or_else(|| invalid("the model does not serialize to a JSON object"))?;
+
+    let mut changed = Vec::default();
+    for (field, value) in patch {
+        let column = <T::Schema as TableColumns>::field_column(field)
+            .ok_or_else(|| invalid(format!("unknown field `{}`", field)))?;
+        if pks.contains(&column) {
+            return Err(invalid(format!("`{}` is part of the primary key", field)));
+        }
+        if let Some(updatable) = updatable {
+            if !updatable.contains(&field.as_str()) {
+                return Err(invalid(format!("`{}` is not updatable", field)));
+            }
+        }
+        let current = fields
+            .get_mut(field)
+            .ok_or_else(|| invalid(format!("unknown field `{}`", field)))?;
+        if current != value {
+            *current = value.clone();
+            changed.push(column);
+        }
+    }
+
+    if changed.is_empty() {
+        return Ok(changed);
+    }
+
+    // Reading the document back checks the values match the types of the fields
+    state.inner = serde_json::from_value(doc).map_err(invalid)?;
+    state.mark_columns_edited(changed.clone());
+    Ok(changed)
+}
+
+fn invalid(reason: impl ToString) -> WeldsError {
+    WeldsError::InvalidPatch(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,102 @@
+use super::ApplyPatch;
+use crate::errors::WeldsError;
+use crate::state::{DbState, DbStatus};
+use crate::Syntax;
+use crate::WeldsModel;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Default, WeldsModel, Serialize, Deserialize)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+    #[welds(rename = "cost")]
+    pub price: f64,
+    pub description: Option<String>,
+}
+
+fn loaded() -> DbState<Product> {
+    DbState::db_loaded(Product {
+        id: 1,
+        name: "plush".to_owned(),
+        price: 3.5,
+        description: None,
+    })
+}
+
+#[test]
+fn should_only_update_the_patched_columns() {
+    futures::executor::block_on(async move {
+        let mut obj = loaded();
+        let patch = json!({ "name": "nessie", "price": 4.0 });
+        let changed = Product::apply_patch(&mut obj, &patch).unwrap();
+        assert_eq!(changed, vec!["name".to_owned(), "cost".to_owned()]);
+        assert_eq!(obj.name, "nessie");
+        assert_eq!(obj.db_status(), DbStatus::Edited);
+
+        let client = welds_connections::noop::build(Syntax::Postgres);
+        obj.save(&client).await.unwrap();
+        let sql = client.last_sql().unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE products SET \"name\"=$1, \"cost\"=$2 where \"id\"=$3"
+        );
+    });
+}
+
+#[test]
+fn should_not_mark_unchanged_values_as_edited() {
+    let mut obj = loaded();
+    let patch = json!({ "name": "plush" });
+    let changed = Product::apply_patch(&mut obj, &patch).unwrap();
+    assert!(changed.is_empty());
+    assert_eq!(obj.db_status(), DbStatus::NotModified);
+}
+
+#[test]
+fn should_write_every_column_if_edited_outside_of_a_patch() {
+    futures::executor::block_on(async move {
+        let mut obj = loaded();
+        Product::apply_patch(&mut obj, &json!({ "name": "nessie" })).unwrap();
+        obj.description = Some("green".to_owned());
+
+        let client = welds_connections::noop::build(Syntax::Postgres);
+        obj.save(&client).await.unwrap();
+        let sql = client.last_sql().unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE products SET \"name\"=$1, \"cost\"=$2, \"description\"=$3 where \"id\"=$4"
+        );
+    });
+}
+
+#[test]
+fn should_reject_fields_outside_the_allowlist() {
+    let mut obj = loaded();
+    let patch = json!({ "name": "nessie", "price": 0.0 });
+    let err = Product::apply_patch_only(&mut obj, &patch, &["name"]).unwrap_err();
+    assert!(matches!(err, WeldsError::InvalidPatch(_)));
+    // nothing is applied if any part of the patch is invalid
+    assert_eq!(obj.name, "plush");
+    assert_eq!(obj.db_status(), DbStatus::NotModified);
+}
+
+#[test]
+fn should_reject_primary_keys_and_unknown_fields() {
+    let mut obj = loaded();
+    assert!(Product::apply_patch(&mut obj, &json!({ "id": 2 })).is_err());
+    assert!(Product::apply_patch(&mut obj, &json!({ "color": "red" })).is_err());
+    assert!(Product::apply_patch(&mut obj, &json!(["name"])).is_err());
+}
+
+#[test]
+fn should_reject_values_of_the_wrong_type() {
+    let mut obj = loaded();
+    assert!(Product::apply_patch(&mut obj, &json!({ "price": "free" })).is_err());
+    assert!(Product::apply_patch(&mut obj, &json!({ "name": null })).is_err());
+    assert!(Product::apply_patch(&mut obj, &json!({ "description": null })).is_ok());
+    assert_eq!(obj.price, 3.5);
+}
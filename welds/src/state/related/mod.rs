@@ -0,0 +1,135 @@
+use super::{DbState, DbStatus};
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::hooks::{AfterCreate, AfterUpdate, BeforeCreate, BeforeUpdate};
+use crate::model_traits::{
+    ColumnDefaultCheck, HasSchema, KeyColumns, TableColumns, TableInfo, UniqueIdentifier,
+    UpdateFromRow, WriteToArgs,
+};
+use crate::query::builder::QueryBuilder;
+use crate::query::clause::{ClauseColManual, ParamArgs};
+use crate::relations::{key_columns, BelongsTo, CreateRelated, HasRelations, Relationship};
+use crate::Row;
+use welds_connections::Client;
+
+/// Fetching, creating and linking the rows of a model's relationships.
+/// The keys are copied from one model to the other for you.
+///
+/// ```rust,ignore
+/// let orders = customer.fetch_related(|c| c.orders, &client).await?;
+///
+/// let item = Item { name: "plush".to_owned(), ..Default::default() };
+/// let item = order.create_related(|o| o.items, item, &client).await?;
+///
+/// user.set_related(|u| u.team, &team, &client).await?;
+/// ```
+impl<T> DbState<T>
+where
+    T: Send + HasSchema + HasRelations + KeyColumns,
+    <T as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+{
+    /// Fetches the rows of one of the model's relationships
+    pub async fn fetch_related<R, Ship>(
+        &self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        client: &dyn Client,
+    ) -> Result<Vec<DbState<R>>>
+    where
+        Ship: Relationship<R>,
+        R: Send + Sync + HasSchema + TryFrom<Row>,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        WeldsError: From<<R as TryFrom<Row>>::Error>,
+    {
+        let me = self.query_self()?;
+        me.map_query(relationship).run(client).await
+    }
+
+    /// Inserts a row of a `HasMany`, `HasOne` or `BelongsToMany` relationship, pointing back at
+    /// this model. The keys of the relationship are set on the new row before it is inserted,
+    /// a `BelongsToMany` adds the row of the join table linking them
+    pub async fn create_related<R, Ship>(
+        &self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> Ship,
+        related: R,
+        client: &dyn Client,
+    ) -> Result<DbState<R>>
+    where
+        Ship: CreateRelated<R>,
+        T: WriteToArgs,
+        R: HasSchema + KeyColumns + WriteToArgs + ColumnDefaultCheck + UpdateFromRow,
+        R: BeforeCreate + AfterCreate + BeforeUpdate + AfterUpdate,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+    {
+        if self.status == DbStatus::NotInDatabase {
+            return Err(WeldsError::NotSaved);
+        }
+        let ship = relationship(Default::default());
+        let mut related = DbState::new_uncreated(related);
+
+        let through = match ship.through() {
+            Some(through) => through,
+            None => {
+                let (mine, theirs) = key_columns::<R, R::Schema, T::Schema>(&ship);
+                for (my_col, their_col) in mine.iter().zip(&theirs) {
+                    let value = self.inner.key_value(my_col);
+                    let value = value.ok_or_else(|| WeldsError::MissingDbColumn(my_col.clone()))?;
+                    related.inner.set_key(their_col, value)?;
+                }
+                related.save(client).await?;
+                return Ok(related);
+            }
+        };
+
+        related.save(client).await?;
+        let my_pk = <T::Schema as UniqueIdentifier>::id_column();
+        let their_pk = <R::Schema as UniqueIdentifier>::id_column();
+        let mut me: ParamArgs = Vec::default();
+        self.inner.bind(my_pk.name(), &mut me)?;
+        let mut them: ParamArgs = Vec::default();
+        related.inner.bind(their_pk.name(), &mut them)?;
+        through.attach(client, me[0], &them).await?;
+        Ok(related)
+    }
+
+    /// Points a `BelongsTo` relationship of this model at `parent` and saves the model.
+    /// Only the columns of the foreign key are written when the model is already in the database
+    pub async fn set_related<R>(
+        &mut self,
+        relationship: impl Fn(<T as HasRelations>::Relation) -> BelongsTo<R>,
+        parent: &R,
+        client: &dyn Client,
+    ) -> Result<()>
+    where
+        R: HasSchema + KeyColumns,
+        <R as HasSchema>::Schema: TableInfo + TableColumns + UniqueIdentifier,
+        T: WriteToArgs + ColumnDefaultCheck + UpdateFromRow,
+        T: BeforeCreate + AfterCreate + BeforeUpdate + AfterUpdate,
+    {
+        let ship = relationship(Default::default());
+        let (mine, theirs) = key_columns::<R, R::Schema, T::Schema>(&ship);
+        for (my_col, their_col) in mine.iter().zip(&theirs) {
+            let value = parent.key_value(their_col);
+            let value = value.ok_or_else(|| WeldsError::MissingDbColumn(their_col.clone()))?;
+            self.inner.set_key(my_col, value)?;
+        }
+        self.mark_columns_edited(mine);
+        self.save(client).await
+    }
+
+    // A query for the row of this model, by its primary keys
+    fn query_self(&self) -> Result<QueryBuilder<T>> {
+        let mut qb = QueryBuilder::new();
+        for pk in <T::Schema as TableColumns>::primary_keys() {
+            let value = self.inner.key_value(pk.name());
+            let value = value.ok_or_else(|| WeldsError::MissingDbColumn(pk.name().to_owned()))?;
+            qb.wheres.push(Box::new(ClauseColManual {
+                col: Some(pk.name().to_owned()),
+                sql: "= ?".to_owned(),
+                params: vec![value.into_param()],
+            }));
+        }
+        Ok(qb)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,158 @@
+use crate::connections::mock::{MockClient, MockRow};
+use crate::connections::value::Value;
+use crate::errors::WeldsError;
+use crate::state::{DbState, DbStatus};
+use crate::{Syntax, WeldsModel};
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsTo(customer, Customer, "customer_id"))]
+#[welds(HasMany(items, Item, "order_id"))]
+#[welds(BelongsToMany(tags, Tag, through = "orders_tags"))]
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub customer_id: Option<i32>,
+    pub note: String,
+}
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "customers")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(HasMany(orders, Order, "customer_id"))]
+struct Customer {
+    #[welds(primary_key)]
+    pub id: i32,
+}
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "items")]
+#[welds_path(crate)] // needed only within the welds crate.
+#[welds(BelongsTo(order, Order, "order_id"))]
+struct Item {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub order_id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "tags")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Tag {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+fn order(id: i32) -> DbState<Order> {
+    DbState::db_loaded(Order {
+        id,
+        customer_id: None,
+        note: "rush".to_owned(),
+    })
+}
+
+#[test]
+fn should_fetch_the_rows_of_a_relationship() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let customer = DbState::db_loaded(Customer { id: 5 });
+        customer.fetch_related(|c| c.orders, &client).await.unwrap();
+        assert_eq!(
+            client.last_sql().unwrap(),
+            r#"SELECT t2."id", t2."customer_id", t2."note" FROM orders t2 WHERE ( EXISTS ( SELECT id FROM customers t1 WHERE t1.id = $1 AND t1.id = t2.customer_id ) )"#
+        );
+        assert_eq!(client.queries()[0].params, [Value::Int(5)]);
+    });
+}
+
+#[test]
+fn should_set_the_keys_of_a_created_row() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let row = MockRow::new()
+            .with("id", 9)
+            .with("order_id", 3)
+            .with("name", "plush");
+        client.enqueue_rows("INSERT", vec![row]);
+        let item = Item {
+            name: "plush".to_owned(),
+            ..Default::default()
+        };
+        let item = order(3).create_related(|o| o.items, item, &client).await;
+        let item = item.unwrap();
+        assert_eq!(item.order_id, 3);
+        assert_eq!(item.id, 9);
+        assert_eq!(item.db_status(), DbStatus::NotModified);
+        let queries = client.queries();
+        assert_eq!(
+            queries[0].sql,
+            r#"INSERT INTO items ("order_id", "name") VALUES ($1, $2) RETURNING *"#
+        );
+        assert_eq!(queries[0].params[0], Value::Int(3));
+    });
+}
+
+#[test]
+fn should_link_a_created_row_through_the_join_table() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let row = MockRow::new().with("id", 4).with("name", "gift");
+        client.enqueue_rows("INSERT INTO tags", vec![row]);
+        let tag = Tag {
+            name: "gift".to_owned(),
+            ..Default::default()
+        };
+        let order = order(3);
+        let tag = order.create_related(|o| o.tags, tag, &client);
+        let tag = tag.await.unwrap();
+        assert_eq!(tag.id, 4);
+
+        let queries = client.queries();
+        let sql: Vec<&str> = queries.iter().map(|q| q.sql.as_str()).collect();
+        assert_eq!(
+            sql,
+            [
+                r#"INSERT INTO tags ("name") VALUES ($1) RETURNING *"#,
+                r#"SELECT "tag_id" FROM orders_tags WHERE "order_id" = $1"#,
+                r#"INSERT INTO orders_tags ("order_id", "tag_id") VALUES ($1, $2)"#,
+            ]
+        );
+        let expected = [3, 4].map(Value::Int);
+        assert_eq!(queries.last().unwrap().params, expected);
+    });
+}
+
+#[test]
+fn should_not_create_a_related_row_for_an_unsaved_model() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let order = DbState::new_uncreated(Order::default());
+        let err = order.create_related(|o| o.items, Item::default(), &client);
+        let err = err.await.unwrap_err();
+        assert!(matches!(err, WeldsError::NotSaved));
+        assert!(client.queries().is_empty());
+    });
+}
+
+#[test]
+fn should_only_write_the_foreign_key_when_setting_the_parent() {
+    block_on(async {
+        let client = MockClient::new(Syntax::Postgres);
+        let mut order = order(3);
+        let customer = Customer { id: 7 };
+        order
+            .set_related(|o| o.customer, &customer, &client)
+            .await
+            .unwrap();
+        assert_eq!(order.customer_id, Some(7));
+        assert_eq!(order.db_status(), DbStatus::NotModified);
+        assert_eq!(
+            client.last_sql().unwrap(),
+            r#"UPDATE orders SET "customer_id"=$1 where "id"=$2"#
+        );
+    });
+}
@@ -0,0 +1,115 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{Column, HasSchema, TableColumns, TableInfo};
+use crate::writers::{in_namespace, table_name, ColumnWriter};
+use crate::Syntax;
+use welds_connections::Client;
+
+/// Profile of the values in a single column of a table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    /// The name of the column in the database
+    pub column: String,
+    /// How many rows are in the table
+    pub rows: u64,
+    /// How many rows are NULL in this column
+    pub nulls: u64,
+    /// The fraction (0.0 - 1.0) of rows that are NULL. Zero for an empty table
+    pub null_fraction: f64,
+    /// How many different (non-NULL) values are in the column
+    pub distinct: u64,
+    /// The smallest value, as text. None if there are no values (or the column is a bool)
+    pub min: Option<String>,
+    /// The largest value, as text. None if there are no values (or the column is a bool)
+    pub max: Option<String>,
+}
+
+/// Computes the stats of the given columns of a table in a single pass over the table.
+///
+/// Columns are the names of the columns in the database. If no columns are given, all the
+/// columns of the model are profiled.
+///
+/// This is normally called from your model: `Product::column_stats(&client, &["price"])`
+pub async fn column_stats<T>(client: &dyn Client, columns: &[&str]) -> Result<Vec<ColumnStats>>
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    let known = <T::Schema as TableColumns>::columns();
+    let selected: Vec<Column> = if columns.is_empty() {
+        known
+    } else {
+        columns
+            .iter()
+            .map(|name| {
+                known
+                    .iter()
+                    .find(|c| c.name() == *name)
+                    .cloned()
+                    .ok_or_else(|| WeldsError::MissingDbColumn(name.to_string()))
+            })
+            .collect::<Result<_>>()?
+    };
+    if selected.is_empty() {
+        return Ok(Vec::default());
+    }
+
+    let syntax = client.syntax();
+    let identifier = <T::Schema as TableInfo>::identifier();
+    let sql = in_namespace(client, || write_sql(syntax, identifier, &selected));
+    let rows = client.fetch_rows(&sql, &[]).await?;
+    let row = rows.first().ok_or(WeldsError::RowNowFound)?;
+
+    let total: Option<i64> = row.get_by_position(0)?;
+    let total = total.unwrap_or_default() as u64;
+    let mut stats = Vec::default();
+    for (i, col) in selected.iter().enumerate() {
+        let offset = 1 + i * 4;
+        let non_null: Option<i64> = row.get_by_position(offset)?;
+        let distinct: Option<i64> = row.get_by_position(offset + 1)?;
+        let nulls = total.saturating_sub(non_null.unwrap_or_default() as u64);
+        stats.push(ColumnStats {
+            column: col.name().to_string(),
+            rows: total,
+            nulls,
+            null_fraction: if total == 0 {
+                0.0
+            } else {
+                nulls as f64 / total as f64
+            },
+            distinct: distinct.unwrap_or_default() as u64,
+            min: row.get_by_position(offset + 2)?,
+            max: row.get_by_position(offset + 3)?,
+        });
+    }
+    Ok(stats)
+}
+
+// One row with the total count, then (count, distinct, min, max) for each column
+fn write_sql(syntax: Syntax, identifier: &[&str], columns: &[Column]) -> String {
+    let writer = ColumnWriter::new(syntax);
+    let (int, text) = match syntax {
+        Syntax::Mysql | Syntax::MariaDb => ("SIGNED", "CHAR"),
+        Syntax::Postgres | Syntax::CockroachDb | Syntax::Sqlite => ("BIGINT", "TEXT"),
+        Syntax::Mssql => ("BIGINT", "NVARCHAR(MAX)"),
+    };
+
+    let mut parts = vec![format!("CAST(COUNT(*) AS {int})")];
+    for col in columns {
+        let name = writer.excape(col.name());
+        parts.push(format!("CAST(COUNT({name}) AS {int})"));
+        parts.push(format!("CAST(COUNT(DISTINCT {name}) AS {int})"));
+        // Postgres and MSSQL can't MIN/MAX a bool
+        if col.rust_type().trim() == "bool" {
+            parts.push(format!("CAST(NULL AS {text})"));
+            parts.push(format!("CAST(NULL AS {text})"));
+        } else {
+            parts.push(format!("CAST(MIN({name}) AS {text})"));
+            parts.push(format!("CAST(MAX({name}) AS {text})"));
+        }
+    }
+    let table = table_name(&identifier.join("."));
+    format!("SELECT {} FROM {}", parts.join(", "), table)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,66 @@
+use super::*;
+use crate::connections::mock::{MockClient, MockRow};
+use crate::WeldsModel;
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "products")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Product {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: Option<String>,
+    pub active: bool,
+}
+
+#[test]
+fn should_profile_the_requested_columns_in_one_query() {
+    let client = MockClient::new(Syntax::Postgres);
+    let row = MockRow::new()
+        .with("rows", 10_i64)
+        .with("count", 8_i64)
+        .with("distinct", 5_i64)
+        .with("min", "apple")
+        .with("max", "pear");
+    client.enqueue_rows("FROM products", vec![row]);
+
+    let stats = block_on(Product::column_stats(&client, &["name"])).unwrap();
+
+    assert_eq!(
+        client.last_sql().unwrap(),
+        r#"SELECT CAST(COUNT(*) AS BIGINT), CAST(COUNT("name") AS BIGINT), CAST(COUNT(DISTINCT "name") AS BIGINT), CAST(MIN("name") AS TEXT), CAST(MAX("name") AS TEXT) FROM products"#
+    );
+    assert_eq!(
+        stats,
+        vec![ColumnStats {
+            column: "name".to_string(),
+            rows: 10,
+            nulls: 2,
+            null_fraction: 0.2,
+            distinct: 5,
+            min: Some("apple".to_string()),
+            max: Some("pear".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn should_skip_min_max_for_bools() {
+    let sql = write_sql(
+        Syntax::Mysql,
+        &["products"],
+        &[Column::new("active", "bool", false)],
+    );
+    assert_eq!(
+        sql,
+        "SELECT CAST(COUNT(*) AS SIGNED), CAST(COUNT(active) AS SIGNED), CAST(COUNT(DISTINCT active) AS SIGNED), CAST(NULL AS CHAR), CAST(NULL AS CHAR) FROM products"
+    );
+}
+
+#[test]
+fn should_reject_unknown_columns() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let err = block_on(Product::column_stats(&client, &["price"])).unwrap_err();
+    assert!(matches!(err, WeldsError::MissingDbColumn(name) if name == "price"));
+    assert!(client.queries().is_empty());
+}
@@ -0,0 +1,184 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo, WriteToArgs};
+use crate::query::insert::bulk_insert;
+use crate::Client;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Buffers the saves of an append-only model and writes them as periodic bulk inserts.
+///
+/// High-frequency small writes (events, metrics, audit rows) each cost a round trip when
+/// saved on their own. A `WriteBehind` collects them and inserts them in batches instead.
+/// A batch is written when it reaches `max_rows`, when its oldest row has waited `max_delay`,
+/// when `flush` is called, and when the writer is closed.
+///
+/// Only use it for models that are never read back, updated or deleted right after they are
+/// saved: the rows are not in the database until their batch is written, and their primary
+/// keys are never read back.
+///
+/// # Delivery guarantees
+///
+/// - At most once on a crash. Rows still buffered when the process dies are lost.
+///   Call `close` on shutdown so the last batch is written.
+/// - A failed batch is kept and retried on the next flush, ahead of newer rows.
+/// - A batch larger than one statement is written in several inserts outside a transaction.
+///   If one of them fails, the rows of the inserts that did succeed are written again on retry.
+///   Keep `max_rows` under the client's chunk size (or use a natural key) to avoid duplicates.
+///
+/// ```rust,ignore
+/// let writer = Arc::new(WriteBehind::<Event>::new(client).max_delay(Duration::from_secs(2)));
+/// let flusher = writer.clone();
+/// tokio::spawn(async move { flusher.run_flusher(tokio::time::sleep).await });
+///
+/// writer.save(Event::new("login")).await?;
+/// // ... on shutdown
+/// writer.close().await?;
+/// ```
+pub struct WriteBehind<T> {
+    client: Arc<dyn Client>,
+    max_rows: usize,
+    max_delay: Duration,
+    buffer: Mutex<Buffer<T>>,
+}
+
+struct Buffer<T> {
+    rows: Vec<T>,
+    oldest: Option<Instant>,
+    closed: bool,
+}
+
+impl<T> WriteBehind<T>
+where
+    T: WriteToArgs + HasSchema + Send,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    /// A writer that flushes every 500 rows or every second, whichever comes first
+    pub fn new(client: Arc<dyn Client>) -> Self {
+        Self {
+            client,
+            max_rows: 500,
+            max_delay: Duration::from_secs(1),
+            buffer: Mutex::new(Buffer {
+                rows: Vec::default(),
+                oldest: None,
+                closed: false,
+            }),
+        }
+    }
+
+    /// Writes the batch once it holds this many rows
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows.max(1);
+        self
+    }
+
+    /// Writes the batch once its oldest row has waited this long
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Buffers the row to be written with the next batch.
+    ///
+    /// If this fills the batch (or it is overdue) the batch is written before returning.
+    /// An error from that write leaves the row buffered, it is retried with the next flush.
+    /// Fails with `WriterClosed` once the writer has been closed.
+    pub async fn save(&self, obj: T) -> Result<()> {
+        let due = {
+            let mut buffer = self.buffer.lock().unwrap();
+            if buffer.closed {
+                return Err(WeldsError::WriterClosed);
+            }
+            buffer.rows.push(obj);
+            let oldest = *buffer.oldest.get_or_insert_with(Instant::now);
+            buffer.rows.len() >= self.max_rows || oldest.elapsed() >= self.max_delay
+        };
+        if due {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// How many rows are waiting to be written
+    pub fn pending(&self) -> usize {
+        self.buffer.lock().unwrap().rows.len()
+    }
+
+    /// Writes everything that is buffered now. Returns how many rows were written.
+    ///
+    /// If the insert fails the rows are put back in front of the buffer and the error is returned.
+    pub async fn flush(&self) -> Result<usize> {
+        let (rows, oldest) = {
+            let mut buffer = self.buffer.lock().unwrap();
+            (std::mem::take(&mut buffer.rows), buffer.oldest.take())
+        };
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        match bulk_insert(self.client.as_ref(), &rows).await {
+            Ok(()) => Ok(rows.len()),
+            Err(err) => {
+                let mut buffer = self.buffer.lock().unwrap();
+                buffer.rows.splice(0..0, rows);
+                buffer.oldest = match (oldest, buffer.oldest) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+                Err(err)
+            }
+        }
+    }
+
+    /// Flushes the writer every `max_delay` until it is closed.
+    ///
+    /// welds doesn't pick an async runtime, so pass in its sleep function
+    /// (`tokio::time::sleep`, `async_std::task::sleep`, ...) and spawn the returned future.
+    /// Failed flushes are logged and retried on the next tick.
+    pub async fn run_flusher<S, F>(&self, sleep: S)
+    where
+        S: Fn(Duration) -> F,
+        F: Future<Output = ()>,
+    {
+        while !self.is_closed() {
+            sleep(self.max_delay).await;
+            if let Err(err) = self.flush().await {
+                log::warn!(
+                    "write-behind flush failed, the batch will be retried: {}",
+                    err
+                );
+            }
+        }
+    }
+
+    /// Refuses any more saves and writes what is left in the buffer.
+    ///
+    /// If the final write fails, the rows stay buffered and can still be written with `flush`.
+    pub async fn close(&self) -> Result<usize> {
+        self.buffer.lock().unwrap().closed = true;
+        self.flush().await
+    }
+
+    fn is_closed(&self) -> bool {
+        self.buffer.lock().unwrap().closed
+    }
+}
+
+impl<T> Drop for WriteBehind<T> {
+    fn drop(&mut self) {
+        let pending = match self.buffer.get_mut() {
+            Ok(buffer) => buffer.rows.len(),
+            Err(poisoned) => poisoned.into_inner().rows.len(),
+        };
+        if pending > 0 {
+            log::warn!(
+                "write-behind writer dropped with {} unwritten rows. Call `close` before dropping it",
+                pending
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
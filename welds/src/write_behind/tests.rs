@@ -0,0 +1,67 @@
+use super::*;
+use crate::connections::mock::MockClient;
+use crate::{Syntax, WeldsModel};
+use futures::executor::block_on;
+
+#[derive(Debug, Default, WeldsModel)]
+#[welds(table = "events")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Event {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+}
+
+fn event(name: &str) -> Event {
+    Event {
+        id: 0,
+        name: name.to_owned(),
+    }
+}
+
+#[test]
+fn should_write_the_batch_once_it_is_full() {
+    let client = MockClient::new(Syntax::Postgres);
+    let writer = WriteBehind::<Event>::new(Arc::new(client.clone())).max_rows(2);
+
+    block_on(writer.save(event("a"))).unwrap();
+    assert!(client.queries().is_empty());
+    block_on(writer.save(event("b"))).unwrap();
+    block_on(writer.save(event("c"))).unwrap();
+
+    let queries = client.queries();
+    assert_eq!(queries.len(), 1);
+    assert_eq!(
+        queries[0].sql,
+        "INSERT INTO events (\"name\") VALUES ($1),($2)"
+    );
+    assert_eq!(writer.pending(), 1);
+}
+
+#[test]
+fn should_write_the_rest_and_refuse_saves_when_closed() {
+    let client = MockClient::new(Syntax::Postgres);
+    let writer = WriteBehind::<Event>::new(Arc::new(client.clone()));
+
+    block_on(writer.save(event("a"))).unwrap();
+    assert_eq!(block_on(writer.close()).unwrap(), 1);
+    assert_eq!(writer.pending(), 0);
+    assert_eq!(
+        client.last_sql().unwrap(),
+        "INSERT INTO events (\"name\") VALUES ($1)"
+    );
+
+    let err = block_on(writer.save(event("b"))).err().unwrap();
+    assert!(matches!(err, WeldsError::WriterClosed));
+}
+
+#[test]
+fn should_write_overdue_rows_with_the_next_save() {
+    let client = MockClient::new(Syntax::Postgres);
+    let writer = WriteBehind::<Event>::new(Arc::new(client.clone())).max_delay(Duration::ZERO);
+
+    block_on(writer.save(event("a"))).unwrap();
+
+    assert_eq!(client.queries().len(), 1);
+    assert_eq!(writer.pending(), 0);
+}
@@ -1,6 +1,7 @@
 use std::sync::Mutex;
 
 pub struct TableAlias {
+    prefix: &'static str,
     i: Mutex<u32>,
 }
 
@@ -12,13 +13,21 @@ impl Default for TableAlias {
 
 impl TableAlias {
     pub fn new() -> Self {
-        TableAlias { i: Mutex::new(1) }
+        Self::with_prefix("t")
+    }
+
+    /// Hands out aliases that can't collide with the ones of another query (s1, s2, ...)
+    pub fn with_prefix(prefix: &'static str) -> Self {
+        TableAlias {
+            prefix,
+            i: Mutex::new(1),
+        }
     }
 
     /// Get the next Alias and bump it
     pub fn next(&self) -> String {
         let mut i = self.i.lock().unwrap();
-        let id = format!("t{}", *i);
+        let id = format!("{}{}", self.prefix, *i);
         *i += 1;
         id
     }
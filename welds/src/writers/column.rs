@@ -10,16 +10,16 @@ impl ColumnWriter {
     }
     pub fn write(&self, prefix: &str, col: &Column) -> String {
         match self.syntax {
-            Syntax::Mysql => MySql::write(prefix, col),
-            Syntax::Postgres => Postgres::write(prefix, col),
+            Syntax::Mysql | Syntax::MariaDb => MySql::write(prefix, col),
+            Syntax::Postgres | Syntax::CockroachDb => Postgres::write(prefix, col),
             Syntax::Sqlite => Sqlite::write(prefix, col),
             Syntax::Mssql => Mssql::write(prefix, col),
         }
     }
     pub fn excape(&self, name: &str) -> String {
         match self.syntax {
-            Syntax::Mysql => MySql::excape(name),
-            Syntax::Postgres => Postgres::excape(name),
+            Syntax::Mysql | Syntax::MariaDb => MySql::excape(name),
+            Syntax::Postgres | Syntax::CockroachDb => Postgres::excape(name),
             Syntax::Sqlite => Sqlite::excape(name),
             Syntax::Mssql => Mssql::excape(name),
         }
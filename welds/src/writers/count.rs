@@ -10,12 +10,29 @@ impl CountWriter {
     }
     pub fn count(&self, prefix: Option<&str>, x: Option<&str>) -> String {
         match self.syntax {
-            Syntax::Mysql => MySql::count(prefix, x),
-            Syntax::Postgres => Postgres::count(prefix, x),
+            Syntax::Mysql | Syntax::MariaDb => MySql::count(prefix, x),
+            Syntax::Postgres | Syntax::CockroachDb => Postgres::count(prefix, x),
             Syntax::Sqlite => Sqlite::count(prefix, x),
             Syntax::Mssql => Mssql::count(prefix, x),
         }
     }
+
+    /// COUNT(DISTINCT x)
+    pub fn count_distinct(&self, x: &str) -> String {
+        match self.syntax {
+            Syntax::Mysql | Syntax::MariaDb => format!("COUNT(DISTINCT {})", x),
+            _ => format!("CAST( COUNT(DISTINCT {}) as BIGINT )", x),
+        }
+    }
+
+    /// An estimate of COUNT(DISTINCT x) for backends that have a built-in for it.
+    /// Falls back to the exact count for the others
+    pub fn approx_count_distinct(&self, x: &str) -> String {
+        match self.syntax {
+            Syntax::Mssql => format!("CAST( APPROX_COUNT_DISTINCT({}) as BIGINT )", x),
+            _ => self.count_distinct(x),
+        }
+    }
 }
 
 struct Postgres;
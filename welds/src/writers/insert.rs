@@ -22,7 +22,11 @@ impl InsertWriter {
     ) -> Sql {
         match self.syntax {
             Syntax::Mysql => MySql::write(identifier, colargs, columns, pks),
-            Syntax::Postgres => Postgres::write(identifier, colargs, columns, pks),
+            // MariaDB can return the inserted row, no second select needed
+            Syntax::MariaDb => Postgres::write(identifier, colargs, columns, pks),
+            Syntax::Postgres | Syntax::CockroachDb => {
+                Postgres::write(identifier, colargs, columns, pks)
+            }
             Syntax::Sqlite => Sqlite::write(identifier, colargs, columns, pks),
             Syntax::Mssql => Mssql::write(identifier, colargs, columns, pks),
         }
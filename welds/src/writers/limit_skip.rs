@@ -10,8 +10,8 @@ impl LimitSkipWriter {
     }
     pub fn skiplimit(&self, s: &Option<i64>, l: &Option<i64>) -> Option<String> {
         match self.syntax {
-            Syntax::Mysql => MySql::skiplimit(s, l),
-            Syntax::Postgres => Postgres::skiplimit(s, l),
+            Syntax::Mysql | Syntax::MariaDb => MySql::skiplimit(s, l),
+            Syntax::Postgres | Syntax::CockroachDb => Postgres::skiplimit(s, l),
             Syntax::Sqlite => Sqlite::skiplimit(s, l),
             Syntax::Mssql => Mssql::skiplimit(s, l),
         }
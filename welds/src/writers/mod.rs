@@ -4,6 +4,7 @@ pub(crate) mod count;
 pub(crate) mod insert;
 pub(crate) mod limit_skip;
 pub(crate) mod nextparam;
+pub(crate) mod table;
 pub mod types;
 
 pub use column::ColumnWriter;
@@ -11,3 +12,4 @@ pub use count::CountWriter;
 pub use insert::InsertWriter;
 pub use limit_skip::LimitSkipWriter;
 pub use nextparam::NextParam;
+pub(crate) use table::{in_namespace, table_alias, table_name};
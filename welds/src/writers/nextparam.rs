@@ -1,32 +1,26 @@
 use crate::Syntax;
 use std::sync::{Arc, Mutex};
+use welds_connections::Capabilities;
 
 pub struct NextParam {
     i: Arc<Mutex<usize>>,
     db_next: fn(usize) -> String,
-    db_max: fn() -> u32,
+    max: u32,
 }
 
 impl NextParam {
     pub fn new(syntax: Syntax) -> Self {
         let next_fn_ptr = match syntax {
-            Syntax::Mysql => MySql::next,
-            Syntax::Postgres => Postgres::next,
+            Syntax::Mysql | Syntax::MariaDb => MySql::next,
+            Syntax::Postgres | Syntax::CockroachDb => Postgres::next,
             Syntax::Mssql => Mssql::next,
             Syntax::Sqlite => Sqlite::next,
         };
 
-        let max_fn_ptr = match syntax {
-            Syntax::Mysql => MySql::max_params,
-            Syntax::Postgres => Postgres::max_params,
-            Syntax::Mssql => Mssql::max_params,
-            Syntax::Sqlite => Sqlite::max_params,
-        };
-
         Self {
             i: Arc::new(Mutex::new(1)),
             db_next: next_fn_ptr,
-            db_max: max_fn_ptr,
+            max: Capabilities::for_syntax(syntax).max_bind_params as u32,
         }
     }
 
@@ -38,8 +32,9 @@ impl NextParam {
         p
     }
 
+    /// The most params the database accepts in a single statement
     pub fn max_params(&self) -> u32 {
-        (self.db_max)()
+        self.max
     }
 }
 
@@ -48,9 +43,6 @@ impl Postgres {
     fn next(i: usize) -> String {
         format!("${}", i)
     }
-    fn max_params() -> u32 {
-        65535
-    }
 }
 
 struct Sqlite;
@@ -58,9 +50,6 @@ impl Sqlite {
     fn next(_i: usize) -> String {
         "?".to_string()
     }
-    fn max_params() -> u32 {
-        999
-    }
 }
 
 struct Mssql;
@@ -68,10 +57,6 @@ impl Mssql {
     fn next(i: usize) -> String {
         format!("@p{}", i)
     }
-    fn max_params() -> u32 {
-        //60
-        2100
-    }
 }
 
 struct MySql;
@@ -79,9 +64,6 @@ impl MySql {
     fn next(_i: usize) -> String {
         "?".to_string()
     }
-    fn max_params() -> u32 {
-        64000
-    }
 }
 
 #[cfg(test)]
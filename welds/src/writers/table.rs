@@ -0,0 +1,42 @@
+use std::cell::RefCell;
+use welds_connections::{Client, Namespace};
+
+thread_local! {
+    // the namespace of the client the SQL being written is for
+    static NAMESPACE: RefCell<Option<Namespace>> = const { RefCell::new(None) };
+}
+
+/// Writes the SQL of a statement for a client. The tables written with `table_name` while
+/// `write` runs are in the client's namespace (`with_schema` / `with_table_prefix`)
+pub(crate) fn in_namespace<C, R>(client: &C, write: impl FnOnce() -> R) -> R
+where
+    C: Client + ?Sized,
+{
+    // put back when done, SQL written for another client (a sub-query's) is nested in this one
+    struct Restore(Option<Namespace>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            NAMESPACE.with(|n| *n.borrow_mut() = self.0.take());
+        }
+    }
+    let outer = NAMESPACE.with(|n| n.replace(client.namespace().cloned()));
+    let _restore = Restore(outer);
+    write()
+}
+
+/// The name a table is written with, in the namespace of the client the SQL is for
+pub(crate) fn table_name(table: &str) -> String {
+    NAMESPACE.with(|n| match &*n.borrow() {
+        Some(namespace) => namespace.table(table),
+        None => table.to_owned(),
+    })
+}
+
+/// How the columns of a table are written (`table.column`), in the namespace of the client the
+/// SQL is for
+pub(crate) fn table_alias(table: &str) -> String {
+    NAMESPACE.with(|n| match &*n.borrow() {
+        Some(namespace) => namespace.table_alias(table),
+        None => table.to_owned(),
+    })
+}
@@ -147,8 +147,9 @@ pub fn get_basic_type_pairs(syntax: Syntax) -> &'static [Pair] {
     match syntax {
         Syntax::Postgres => POSTGRES_PAIRS,
         Syntax::Sqlite => SQLITE_PAIRS,
-        Syntax::Mysql => MYSQL_PAIRS,
+        Syntax::Mysql | Syntax::MariaDb => MYSQL_PAIRS,
         Syntax::Mssql => MSSQL_PAIRS,
+        Syntax::CockroachDb => COCKROACHDB_PAIRS,
     }
 }
 
@@ -287,6 +288,38 @@ const POSTGRES_PAIRS: &[Pair] = &[
     Pair::new("JSONB", "serde_json::Value"),
 ];
 
+// CockroachDB's SERIAL is always an INT8 and plain INT is an alias for INT8.
+// Ids are made with IDENTITY columns instead (see create_table)
+const COCKROACHDB_PAIRS: &[Pair] = &[
+    Pair::new("UUID", "sqlx::types::Uuid"),
+    Pair::new("BOOL", "bool"),
+    Pair::new("INT2", "i16"),
+    Pair::new("SMALLINT", "i16"),
+    Pair::new("INT4", "i32"),
+    Pair::new("INT8", "i64"),
+    Pair::new("BIGINT", "i64"),
+    Pair::new("INT", "i64"),
+    Pair::new("FLOAT4", "f32"),
+    Pair::new("REAL", "f32"),
+    Pair::new("FLOAT8", "f64"),
+    Pair::new("DOUBLE PRECISION", "f64"),
+    Pair::new("TEXT", "String"),
+    Pair::new("STRING", "String"),
+    Pair::sized("VARCHAR", "String", "255"),
+    Pair::sized("CHAR", "String", "255"),
+    Pair::new("NAME", "String"),
+    Pair::new("BYTEA", "Vec<u8>"),
+    Pair::new("BYTES", "Vec<u8>"),
+    Pair::new("INTERVAL", "sqlx::postgres::types::PgInterval"),
+    Pair::new("TIMESTAMPTZ", "chrono::DateTime<chrono::Utc>"),
+    Pair::new("TIMESTAMPTZ", "chrono::DateTime<Utc>"),
+    Pair::new("TIMESTAMP", "chrono::NaiveDateTime"),
+    Pair::new("DATE", "chrono::NaiveDate"),
+    Pair::new("TIME", "chrono::NaiveTime"),
+    Pair::new("TIMETZ", "sqlx::postgres::types::PgTimeTz"),
+    Pair::new("JSONB", "serde_json::Value"),
+];
+
 /// Returns true if two types are a match
 pub(crate) fn are_equivalent_types(pairs: &[Pair], db: &str, rust: &str) -> bool {
     let db = db.trim().to_uppercase();